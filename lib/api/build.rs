@@ -122,6 +122,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CreateCollection.optimizers_config", ""),
             ("CreateCollection.vectors_config", ""),
             ("CreateCollection.quantization_config", ""),
+            ("CreateCollection.sparse_vectors_config", ""),
             ("UpdateCollection.collection_name", "length(min = 1, max = 255)"),
             ("UpdateCollection.optimizers_config", ""),
             ("UpdateCollection.params", ""),
@@ -129,12 +130,18 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpdateCollection.hnsw_config", ""),
             ("UpdateCollection.vectors_config", ""),
             ("UpdateCollection.quantization_config", ""),
+            ("UpdateCollection.strict_mode_config", ""),
+            ("UpdateCollection.sparse_vectors_config", ""),
             ("DeleteCollection.collection_name", "length(min = 1, max = 255)"),
             ("DeleteCollection.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CollectionConfig.params", ""),
             ("CollectionConfig.hnsw_config", ""),
             ("CollectionConfig.optimizers_config", ""),
             ("CollectionConfig.quantization_config", ""),
+            ("CollectionConfig.strict_mode_config", ""),
+            ("StrictModeConfig.max_query_limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.search_max_hnsw_ef", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.upsert_max_batchsize", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CollectionParams.vectors_config", ""),
             ("ChangeAliases.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("ListCollectionAliasesRequest.collection_name", "length(min = 1, max = 255)"),
@@ -142,15 +149,23 @@ fn configure_validation(builder: Builder) -> Builder {
             ("WalConfigDiff.wal_capacity_mb", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("OptimizersConfigDiff.deleted_threshold", "custom = \"crate::grpc::validate::validate_f64_range_1\""),
             ("OptimizersConfigDiff.vacuum_min_vector_number", "custom = \"crate::grpc::validate::validate_u64_range_min_100\""),
+            ("OptimizersConfigDiff.graph_healing_threshold", "custom = \"crate::grpc::validate::validate_f64_range_1\""),
             ("VectorsConfig.config", ""),
             ("VectorsConfigDiff.config", ""),
             ("VectorParams.size", "range(min = 1, max = 65536)"),
             ("VectorParams.hnsw_config", ""),
             ("VectorParams.quantization_config", ""),
+            ("VectorParams.multivector_config", ""),
+            ("MultiVectorConfig.max_tokens", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("VectorParamsMap.map", ""),
             ("VectorParamsDiff.hnsw_config", ""),
             ("VectorParamsDiff.quantization_config", ""),
             ("VectorParamsDiffMap.map", ""),
+            ("SparseVectorConfig.map", ""),
+            ("SparseVectorParams.bm25", ""),
+            ("Bm25Config.k", "custom = \"crate::grpc::validate::validate_f32_finite_non_negative\""),
+            ("Bm25Config.b", "custom = \"crate::grpc::validate::validate_f32_range_1\""),
+            ("Bm25Config.avg_len", "custom = \"crate::grpc::validate::validate_f32_range_min_1\""),
             ("QuantizationConfig.quantization", ""),
             ("QuantizationConfigDiff.quantization", ""),
             ("ScalarQuantization.quantile", "custom = \"crate::grpc::validate::validate_f32_range_min_0_5_max_1\""),
@@ -213,6 +228,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchPointGroups.limit", "range(min = 1)"),
             ("SearchPointGroups.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("SearchParams.quantization", ""),
+            ("SearchParams.max_tokens", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("QuantizationSearchParams.oversampling", "custom = \"crate::grpc::validate::validate_f64_range_min_1\""),
             ("ScrollPoints.collection_name", "length(min = 1, max = 255)"),
             ("ScrollPoints.filter", ""),
@@ -245,6 +261,38 @@ fn configure_validation(builder: Builder) -> Builder {
             ("DiscoverBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CountPoints.collection_name", "length(min = 1, max = 255)"),
             ("CountPoints.filter", ""),
+            ("PrefetchQuery.prefetch", ""),
+            ("PrefetchQuery.weight", "custom = \"crate::grpc::validate::validate_f32_finite_non_negative\""),
+            ("QueryPoints.collection_name", "length(min = 1, max = 255)"),
+            ("QueryPoints.prefetch", ""),
+            ("QueryPoints.filter", ""),
+            ("QueryPoints.search_params", ""),
+            ("QueryPoints.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("QueryPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("QueryBatchPoints.collection_name", "length(min = 1, max = 255)"),
+            ("QueryBatchPoints.query_points", ""),
+            ("QueryBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("QueryPointGroups.collection_name", "length(min = 1, max = 255)"),
+            ("QueryPointGroups.prefetch", ""),
+            ("QueryPointGroups.filter", ""),
+            ("QueryPointGroups.search_params", ""),
+            ("QueryPointGroups.group_by", "length(min = 1)"),
+            ("QueryPointGroups.group_size", "range(min = 1)"),
+            ("QueryPointGroups.limit", "range(min = 1)"),
+            ("QueryPointGroups.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("FacetCounts.collection_name", "length(min = 1, max = 255)"),
+            ("FacetCounts.key", "length(min = 1)"),
+            ("FacetCounts.filter", ""),
+            ("FacetCounts.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("FacetCounts.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchMatrixPoints.collection_name", "length(min = 1, max = 255)"),
+            ("SearchMatrixPoints.filter", ""),
+            ("SearchMatrixPoints.sample", "custom = \"crate::grpc::validate::validate_u64_range_min_2_max_10000\""),
+            ("SearchMatrixPoints.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchMatrixPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("ReadChanges.collection_name", "length(min = 1, max = 255)"),
+            ("ReadChanges.consumer", "length(min = 1)"),
+            ("ReadChanges.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("GeoPolygon.exterior", "custom = \"crate::grpc::validate::validate_geo_polygon_exterior\""),
             ("GeoPolygon.interiors", "custom = \"crate::grpc::validate::validate_geo_polygon_interiors\""),
             ("Filter.should", ""),
@@ -306,6 +354,9 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ListSnapshotsRequest.collection_name", "length(min = 1, max = 255)"),
             ("DeleteSnapshotRequest.collection_name", "length(min = 1, max = 255)"),
             ("DeleteSnapshotRequest.snapshot_name", "length(min = 1)"),
+            ("CreateSnapshotRequest.base", "length(min = 1)"),
+            ("CompactSnapshotRequest.collection_name", "length(min = 1, max = 255)"),
+            ("CompactSnapshotRequest.snapshot_name", "length(min = 1)"),
             ("DeleteFullSnapshotRequest.snapshot_name", "length(min = 1)"),
             ("CreateShardSnapshotRequest.collection_name", "length(min = 1, max = 255)"),
             ("ListShardSnapshotsRequest.collection_name", "length(min = 1, max = 255)"),
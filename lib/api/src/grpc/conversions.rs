@@ -4,8 +4,14 @@ use std::time::Instant;
 
 use chrono::{NaiveDateTime, Timelike};
 use itertools::Itertools;
+use segment::data_types::bool_index::BoolIndexType;
+use segment::data_types::datetime_index::DatetimeIndexType;
+use segment::data_types::float_index::FloatIndexType;
+use segment::data_types::geo_index::GeoIndexType;
 use segment::data_types::integer_index::IntegerIndexType;
+use segment::data_types::keyword_index::KeywordIndexType;
 use segment::data_types::text_index::TextIndexType;
+use segment::data_types::uuid_index::UuidIndexType;
 use segment::data_types::vectors as segment_vectors;
 use segment::json_path::JsonPath;
 use segment::types::{default_quantization_ignore_value, DateTimePayloadType, FloatPayloadType};
@@ -15,9 +21,12 @@ use uuid::Uuid;
 
 use super::qdrant::raw_query::RawContextPair;
 use super::qdrant::{
-    raw_query, start_from, BinaryQuantization, CompressionRatio, DatetimeRange, Direction,
-    GeoLineString, GroupId, MultiVectorComparator, MultiVectorConfig, OrderBy, OrderValue, Range,
-    RawVector, RecommendStrategy, ShardKeySelector, SparseIndices, StartFrom,
+    raw_query, start_from, BinaryQuantization, BoolIndexParams, CompressionRatio,
+    DatetimeIndexParams, DatetimeRange, Direction, FacetHit, FacetValue, FloatIndexParams,
+    GeoIndexParams, GeoLineString, GroupId, MultiVectorComparator, MultiVectorConfig,
+    MultiVectorRepresentative, OrderBy, OrderValue, Range, RawVector, RecommendStrategy,
+    ShardKeySelector, SparseIndices, StartFrom, TokenPooling, UuidIndexParams,
+    VectorValidationConfig,
 };
 use crate::grpc::models::{CollectionsResponse, VersionInfo};
 use crate::grpc::qdrant::condition::ConditionOneOf;
@@ -29,16 +38,17 @@ use crate::grpc::qdrant::vectors::VectorsOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
     shard_key, with_vectors_selector, CollectionDescription, CollectionOperationResponse,
-    Condition, DenseVector, Distance, FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon,
-    GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff, IntegerIndexParams,
-    IsEmptyCondition, IsNullCondition, ListCollectionsResponse, ListValue, Match, MinShould,
-    MultiDenseVector, NamedVectors, NestedCondition, PayloadExcludeSelector,
-    PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId,
-    PointsOperationResponse, PointsOperationResponseInternal, ProductQuantization,
-    QuantizationConfig, QuantizationSearchParams, QuantizationType, RepeatedIntegers,
-    RepeatedStrings, ScalarQuantization, ScoredPoint, SearchParams, ShardKey, SparseVector, Struct,
-    TextIndexParams, TokenizerType, UpdateResult, UpdateResultInternal, Value, ValuesCount, Vector,
-    Vectors, VectorsSelector, WithPayloadSelector, WithVectorsSelector,
+    Condition, DenseVector, Distance, Document, DocumentModel, FieldCondition, Filter,
+    GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius, HasIdCondition, HealthCheckReply,
+    HnswConfigDiff, IntegerIndexParams, IsEmptyCondition, IsNullCondition, KeywordIndexParams,
+    ListCollectionsResponse, ListValue, Match, MinShould, MultiDenseVector, NamedVectors,
+    NestedCondition, PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams,
+    PayloadSchemaInfo, PayloadSchemaType, PointId, PointsOperationResponse,
+    PointsOperationResponseInternal, ProductQuantization, QuantizationConfig,
+    QuantizationSearchParams, QuantizationType, RepeatedIntegers, RepeatedStrings,
+    ScalarQuantization, ScoredPoint, SearchParams, ShardKey, SparseVector, Struct, TextIndexParams,
+    TokenizerType, UpdateResult, UpdateResultInternal, Value, ValuesCount, Vector, Vectors,
+    VectorsSelector, WithPayloadSelector, WithVectorsSelector,
 };
 use crate::rest::schema as rest;
 
@@ -227,6 +237,7 @@ impl From<segment::data_types::text_index::TextIndexParams> for PayloadIndexPara
                 lowercase: params.lowercase,
                 min_token_len: params.min_token_len.map(|x| x as u64),
                 max_token_len: params.max_token_len.map(|x| x as u64),
+                on_disk: params.on_disk,
             })),
         }
     }
@@ -238,6 +249,69 @@ impl From<segment::data_types::integer_index::IntegerIndexParams> for PayloadInd
             index_params: Some(IndexParams::IntegerIndexParams(IntegerIndexParams {
                 lookup: params.lookup,
                 range: params.range,
+                is_tenant: params.is_tenant,
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::keyword_index::KeywordIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::keyword_index::KeywordIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::KeywordIndexParams(KeywordIndexParams {
+                is_tenant: params.is_tenant,
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::float_index::FloatIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::float_index::FloatIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::FloatIndexParams(FloatIndexParams {
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::geo_index::GeoIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::geo_index::GeoIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::GeoIndexParams(GeoIndexParams {
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::bool_index::BoolIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::bool_index::BoolIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::BoolIndexParams(BoolIndexParams {
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::datetime_index::DatetimeIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::datetime_index::DatetimeIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::DatetimeIndexParams(DatetimeIndexParams {
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::uuid_index::UuidIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::uuid_index::UuidIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::UuidIndexParams(UuidIndexParams {
+                on_disk: params.on_disk,
             })),
         }
     }
@@ -254,6 +328,7 @@ impl From<segment::types::PayloadIndexInfo> for PayloadSchemaInfo {
                 segment::types::PayloadSchemaType::Text => PayloadSchemaType::Text,
                 segment::types::PayloadSchemaType::Bool => PayloadSchemaType::Bool,
                 segment::types::PayloadSchemaType::Datetime => PayloadSchemaType::Datetime,
+                segment::types::PayloadSchemaType::Uuid => PayloadSchemaType::Uuid,
             }
             .into(),
             params: schema.params.map(|params| match params {
@@ -263,8 +338,19 @@ impl From<segment::types::PayloadIndexInfo> for PayloadSchemaInfo {
                 segment::types::PayloadSchemaParams::Integer(integer_params) => {
                     integer_params.into()
                 }
+                segment::types::PayloadSchemaParams::Keyword(keyword_params) => {
+                    keyword_params.into()
+                }
+                segment::types::PayloadSchemaParams::Float(float_params) => float_params.into(),
+                segment::types::PayloadSchemaParams::Geo(geo_params) => geo_params.into(),
+                segment::types::PayloadSchemaParams::Bool(bool_params) => bool_params.into(),
+                segment::types::PayloadSchemaParams::Datetime(datetime_params) => {
+                    datetime_params.into()
+                }
+                segment::types::PayloadSchemaParams::Uuid(uuid_params) => uuid_params.into(),
             }),
             points: Some(schema.points as u64),
+            disk_usage_bytes: schema.disk_usage_bytes.map(|bytes| bytes as u64),
         }
     }
 }
@@ -297,6 +383,7 @@ impl TryFrom<TextIndexParams> for segment::data_types::text_index::TextIndexPara
             lowercase: params.lowercase,
             min_token_len: params.min_token_len.map(|x| x as usize),
             max_token_len: params.max_token_len.map(|x| x as usize),
+            on_disk: params.on_disk,
         })
     }
 }
@@ -308,6 +395,69 @@ impl TryFrom<IntegerIndexParams> for segment::data_types::integer_index::Integer
             r#type: IntegerIndexType::Integer,
             lookup: params.lookup,
             range: params.range,
+            is_tenant: params.is_tenant,
+            on_disk: params.on_disk,
+        })
+    }
+}
+
+impl TryFrom<KeywordIndexParams> for segment::data_types::keyword_index::KeywordIndexParams {
+    type Error = Status;
+    fn try_from(params: KeywordIndexParams) -> Result<Self, Self::Error> {
+        Ok(segment::data_types::keyword_index::KeywordIndexParams {
+            r#type: KeywordIndexType::Keyword,
+            is_tenant: params.is_tenant,
+            on_disk: params.on_disk,
+        })
+    }
+}
+
+impl TryFrom<FloatIndexParams> for segment::data_types::float_index::FloatIndexParams {
+    type Error = Status;
+    fn try_from(params: FloatIndexParams) -> Result<Self, Self::Error> {
+        Ok(segment::data_types::float_index::FloatIndexParams {
+            r#type: FloatIndexType::Float,
+            on_disk: params.on_disk,
+        })
+    }
+}
+
+impl TryFrom<GeoIndexParams> for segment::data_types::geo_index::GeoIndexParams {
+    type Error = Status;
+    fn try_from(params: GeoIndexParams) -> Result<Self, Self::Error> {
+        Ok(segment::data_types::geo_index::GeoIndexParams {
+            r#type: GeoIndexType::Geo,
+            on_disk: params.on_disk,
+        })
+    }
+}
+
+impl TryFrom<BoolIndexParams> for segment::data_types::bool_index::BoolIndexParams {
+    type Error = Status;
+    fn try_from(params: BoolIndexParams) -> Result<Self, Self::Error> {
+        Ok(segment::data_types::bool_index::BoolIndexParams {
+            r#type: BoolIndexType::Bool,
+            on_disk: params.on_disk,
+        })
+    }
+}
+
+impl TryFrom<DatetimeIndexParams> for segment::data_types::datetime_index::DatetimeIndexParams {
+    type Error = Status;
+    fn try_from(params: DatetimeIndexParams) -> Result<Self, Self::Error> {
+        Ok(segment::data_types::datetime_index::DatetimeIndexParams {
+            r#type: DatetimeIndexType::Datetime,
+            on_disk: params.on_disk,
+        })
+    }
+}
+
+impl TryFrom<UuidIndexParams> for segment::data_types::uuid_index::UuidIndexParams {
+    type Error = Status;
+    fn try_from(params: UuidIndexParams) -> Result<Self, Self::Error> {
+        Ok(segment::data_types::uuid_index::UuidIndexParams {
+            r#type: UuidIndexType::Uuid,
+            on_disk: params.on_disk,
         })
     }
 }
@@ -323,6 +473,24 @@ impl TryFrom<IndexParams> for segment::types::PayloadSchemaParams {
             IndexParams::IntegerIndexParams(integer_params) => Ok(
                 segment::types::PayloadSchemaParams::Integer(integer_params.try_into()?),
             ),
+            IndexParams::KeywordIndexParams(keyword_params) => Ok(
+                segment::types::PayloadSchemaParams::Keyword(keyword_params.try_into()?),
+            ),
+            IndexParams::FloatIndexParams(float_params) => Ok(
+                segment::types::PayloadSchemaParams::Float(float_params.try_into()?),
+            ),
+            IndexParams::GeoIndexParams(geo_params) => Ok(
+                segment::types::PayloadSchemaParams::Geo(geo_params.try_into()?),
+            ),
+            IndexParams::BoolIndexParams(bool_params) => Ok(
+                segment::types::PayloadSchemaParams::Bool(bool_params.try_into()?),
+            ),
+            IndexParams::DatetimeIndexParams(datetime_params) => Ok(
+                segment::types::PayloadSchemaParams::Datetime(datetime_params.try_into()?),
+            ),
+            IndexParams::UuidIndexParams(uuid_params) => Ok(
+                segment::types::PayloadSchemaParams::Uuid(uuid_params.try_into()?),
+            ),
         }
     }
 }
@@ -345,6 +513,7 @@ impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
                 PayloadSchemaType::Text => segment::types::PayloadSchemaType::Text,
                 PayloadSchemaType::Bool => segment::types::PayloadSchemaType::Bool,
                 PayloadSchemaType::Datetime => segment::types::PayloadSchemaType::Datetime,
+                PayloadSchemaType::Uuid => segment::types::PayloadSchemaType::Uuid,
                 PayloadSchemaType::UnknownType => {
                     return Err(Status::invalid_argument(
                         "Malformed payload schema".to_string(),
@@ -364,6 +533,7 @@ impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
             data_type,
             params,
             points: schema.points.unwrap_or(0) as usize,
+            disk_usage_bytes: schema.disk_usage_bytes.map(|bytes| bytes as usize),
         })
     }
 }
@@ -469,6 +639,8 @@ impl From<SearchParams> for segment::types::SearchParams {
             exact: params.exact.unwrap_or(false),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: params.indexed_only.unwrap_or(false),
+            max_tokens: params.max_tokens.map(|x| x as usize),
+            acorn: params.acorn,
         }
     }
 }
@@ -480,6 +652,8 @@ impl From<segment::types::SearchParams> for SearchParams {
             exact: Some(params.exact),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: Some(params.indexed_only),
+            max_tokens: params.max_tokens.map(|x| x as u64),
+            acorn: params.acorn,
         }
     }
 }
@@ -502,6 +676,7 @@ impl From<segment_vectors::Vector> for Vector {
                 data: vector,
                 indices: None,
                 vectors_count: None,
+                document: None,
             },
             segment_vectors::Vector::Sparse(vector) => Self {
                 data: vector.values,
@@ -509,6 +684,7 @@ impl From<segment_vectors::Vector> for Vector {
                     data: vector.indices,
                 }),
                 vectors_count: None,
+                document: None,
             },
             segment_vectors::Vector::MultiDense(vector) => {
                 let vector_count = vector.multi_vectors().count() as u32;
@@ -516,6 +692,7 @@ impl From<segment_vectors::Vector> for Vector {
                     data: vector.flattened_vectors,
                     indices: None,
                     vectors_count: Some(vector_count),
+                    document: None,
                 }
             }
         }
@@ -526,6 +703,13 @@ impl TryFrom<Vector> for segment_vectors::Vector {
     type Error = Status;
 
     fn try_from(vector: Vector) -> Result<Self, Self::Error> {
+        // documents are only accepted in points and queries, see `rest::Vector::try_from`
+        if vector.document.is_some() {
+            return Err(Status::invalid_argument(
+                "Documents are not supported in this request",
+            ));
+        }
+
         // sparse vector
         if let Some(indices) = vector.indices {
             return Ok(segment_vectors::Vector::Sparse(
@@ -555,6 +739,40 @@ impl TryFrom<Vector> for segment_vectors::Vector {
     }
 }
 
+impl TryFrom<Document> for rest::Document {
+    type Error = Status;
+
+    fn try_from(document: Document) -> Result<Self, Self::Error> {
+        let Document { text, model } = document;
+        let model = DocumentModel::from_i32(model)
+            .ok_or_else(|| Status::invalid_argument(format!("Unknown document model: {model}")))?;
+        Ok(Self {
+            text,
+            model: model.into(),
+        })
+    }
+}
+
+impl From<DocumentModel> for rest::DocumentModel {
+    fn from(model: DocumentModel) -> Self {
+        match model {
+            DocumentModel::Bm25 => rest::DocumentModel::Bm25,
+        }
+    }
+}
+
+/// Same as the conversion into a segment vector, but keeps documents to be inferred by the collection
+impl TryFrom<Vector> for rest::Vector {
+    type Error = Status;
+
+    fn try_from(mut vector: Vector) -> Result<Self, Self::Error> {
+        if let Some(document) = vector.document.take() {
+            return Ok(rest::Vector::Document(document.try_into()?));
+        }
+        segment_vectors::Vector::try_from(vector).map(rest::Vector::from)
+    }
+}
+
 impl From<HashMap<String, segment_vectors::Vector>> for NamedVectors {
     fn from(vectors: HashMap<String, segment_vectors::Vector>) -> Self {
         Self {
@@ -626,6 +844,70 @@ impl TryFrom<OrderValue> for segment::data_types::order_by::OrderValue {
     }
 }
 
+impl From<segment::data_types::facets::FacetValue> for FacetValue {
+    fn from(value: segment::data_types::facets::FacetValue) -> Self {
+        use segment::data_types::facets as segment;
+
+        use crate::grpc::qdrant::facet_value::Variant;
+
+        let variant = match value {
+            segment::FacetValue::Keyword(value) => Variant::StringValue(value),
+            segment::FacetValue::Int(value) => Variant::IntegerValue(value),
+            segment::FacetValue::Bool(value) => Variant::BoolValue(value),
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<FacetValue> for segment::data_types::facets::FacetValue {
+    type Error = Status;
+
+    fn try_from(value: FacetValue) -> Result<Self, Self::Error> {
+        use segment::data_types::facets as segment;
+
+        use crate::grpc::qdrant::facet_value::Variant;
+
+        let variant = value
+            .variant
+            .ok_or_else(|| Status::invalid_argument("FacetValue should have a variant"))?;
+
+        let value = match variant {
+            Variant::StringValue(value) => segment::FacetValue::Keyword(value),
+            Variant::IntegerValue(value) => segment::FacetValue::Int(value),
+            Variant::BoolValue(value) => segment::FacetValue::Bool(value),
+        };
+
+        Ok(value)
+    }
+}
+
+impl From<segment::data_types::facets::FacetValueHit> for FacetHit {
+    fn from(hit: segment::data_types::facets::FacetValueHit) -> Self {
+        Self {
+            value: Some(hit.value.into()),
+            count: hit.count as u64,
+        }
+    }
+}
+
+impl TryFrom<FacetHit> for segment::data_types::facets::FacetValueHit {
+    type Error = Status;
+
+    fn try_from(hit: FacetHit) -> Result<Self, Self::Error> {
+        let value = hit
+            .value
+            .ok_or_else(|| Status::invalid_argument("FacetHit should have a value"))?;
+
+        Ok(Self {
+            value: value.try_into()?,
+            count: hit.count as usize,
+        })
+    }
+}
+
 impl From<segment::types::ScoredPoint> for ScoredPoint {
     fn from(point: segment::types::ScoredPoint) -> Self {
         Self {
@@ -685,6 +967,11 @@ impl TryFrom<Vectors> for segment_vectors::VectorStruct {
                             "Sparse vector must be named".to_string(),
                         ));
                     }
+                    if vector.document.is_some() {
+                        return Err(Status::invalid_argument(
+                            "Document must be named".to_string(),
+                        ));
+                    }
                     segment_vectors::VectorStruct::Single(vector.data)
                 }
                 VectorsOptions::Vectors(vectors) => {
@@ -696,6 +983,25 @@ impl TryFrom<Vectors> for segment_vectors::VectorStruct {
     }
 }
 
+/// Same as the conversion into a segment vector struct, but keeps documents to be inferred by the collection
+impl TryFrom<Vectors> for rest::VectorStruct {
+    type Error = Status;
+
+    fn try_from(vectors: Vectors) -> Result<Self, Self::Error> {
+        match vectors.vectors_options {
+            Some(VectorsOptions::Vectors(vectors)) => Ok(rest::VectorStruct::Multi(
+                vectors
+                    .vectors
+                    .into_iter()
+                    .map(|(name, vector)| Ok((name, rest::Vector::try_from(vector)?)))
+                    .collect::<Result<_, Status>>()?,
+            )),
+            vectors_options => segment_vectors::VectorStruct::try_from(Vectors { vectors_options })
+                .map(rest::VectorStruct::from),
+        }
+    }
+}
+
 impl From<segment::types::WithVector> for WithVectorsSelector {
     fn from(with_vectors: segment::types::WithVector) -> Self {
         let selector_options = match with_vectors {
@@ -884,6 +1190,43 @@ impl From<segment::types::MultiVectorConfig> for MultiVectorConfig {
     fn from(value: segment::types::MultiVectorConfig) -> Self {
         Self {
             comparator: MultiVectorComparator::from(value.comparator) as i32,
+            max_tokens: value.max_tokens.map(|max_tokens| max_tokens as u64),
+            pooling: value
+                .pooling
+                .map(|pooling| TokenPooling::from(pooling) as i32),
+            representative: value
+                .representative
+                .map(|representative| MultiVectorRepresentative::from(representative) as i32),
+        }
+    }
+}
+
+impl From<segment::types::VectorValidationConfig> for VectorValidationConfig {
+    fn from(value: segment::types::VectorValidationConfig) -> Self {
+        let segment::types::VectorValidationConfig {
+            reject_non_finite,
+            reject_zero,
+            require_unit_norm,
+        } = value;
+        Self {
+            reject_non_finite: Some(reject_non_finite),
+            reject_zero: Some(reject_zero),
+            require_unit_norm: Some(require_unit_norm),
+        }
+    }
+}
+
+impl From<VectorValidationConfig> for segment::types::VectorValidationConfig {
+    fn from(value: VectorValidationConfig) -> Self {
+        let VectorValidationConfig {
+            reject_non_finite,
+            reject_zero,
+            require_unit_norm,
+        } = value;
+        Self {
+            reject_non_finite: reject_non_finite.unwrap_or_default(),
+            reject_zero: reject_zero.unwrap_or_default(),
+            require_unit_norm: require_unit_norm.unwrap_or_default(),
         }
     }
 }
@@ -892,6 +1235,32 @@ impl From<segment::types::MultiVectorComparator> for MultiVectorComparator {
     fn from(value: segment::types::MultiVectorComparator) -> Self {
         match value {
             segment::types::MultiVectorComparator::MaxSim => MultiVectorComparator::MaxSim,
+            segment::types::MultiVectorComparator::AvgMaxSim => MultiVectorComparator::AvgMaxSim,
+            segment::types::MultiVectorComparator::SymmetricMaxSim => {
+                MultiVectorComparator::SymmetricMaxSim
+            }
+        }
+    }
+}
+
+impl From<segment::types::TokenPooling> for TokenPooling {
+    fn from(value: segment::types::TokenPooling) -> Self {
+        match value {
+            segment::types::TokenPooling::KeepFirst => TokenPooling::KeepFirst,
+            segment::types::TokenPooling::HierarchicalClustering => {
+                TokenPooling::HierarchicalClustering
+            }
+        }
+    }
+}
+
+impl From<segment::types::MultiVectorRepresentative> for MultiVectorRepresentative {
+    fn from(value: segment::types::MultiVectorRepresentative) -> Self {
+        match value {
+            segment::types::MultiVectorRepresentative::Mean => MultiVectorRepresentative::Mean,
+            segment::types::MultiVectorRepresentative::FirstToken => {
+                MultiVectorRepresentative::FirstToken
+            }
         }
     }
 }
@@ -902,8 +1271,27 @@ impl TryFrom<MultiVectorConfig> for segment::types::MultiVectorConfig {
     fn try_from(value: MultiVectorConfig) -> Result<Self, Self::Error> {
         let comparator = MultiVectorComparator::from_i32(value.comparator)
             .ok_or_else(|| Status::invalid_argument("Unknown multi vector comparator"))?;
+        let pooling = value
+            .pooling
+            .map(|pooling| {
+                TokenPooling::from_i32(pooling)
+                    .map(segment::types::TokenPooling::from)
+                    .ok_or_else(|| Status::invalid_argument("Unknown token pooling"))
+            })
+            .transpose()?;
+        let representative = value
+            .representative
+            .map(|representative| {
+                MultiVectorRepresentative::from_i32(representative)
+                    .map(segment::types::MultiVectorRepresentative::from)
+                    .ok_or_else(|| Status::invalid_argument("Unknown multivector representative"))
+            })
+            .transpose()?;
         Ok(segment::types::MultiVectorConfig {
             comparator: segment::types::MultiVectorComparator::from(comparator),
+            max_tokens: value.max_tokens.map(|max_tokens| max_tokens as usize),
+            pooling,
+            representative,
         })
     }
 }
@@ -912,6 +1300,32 @@ impl From<MultiVectorComparator> for segment::types::MultiVectorComparator {
     fn from(value: MultiVectorComparator) -> Self {
         match value {
             MultiVectorComparator::MaxSim => segment::types::MultiVectorComparator::MaxSim,
+            MultiVectorComparator::AvgMaxSim => segment::types::MultiVectorComparator::AvgMaxSim,
+            MultiVectorComparator::SymmetricMaxSim => {
+                segment::types::MultiVectorComparator::SymmetricMaxSim
+            }
+        }
+    }
+}
+
+impl From<TokenPooling> for segment::types::TokenPooling {
+    fn from(value: TokenPooling) -> Self {
+        match value {
+            TokenPooling::KeepFirst => segment::types::TokenPooling::KeepFirst,
+            TokenPooling::HierarchicalClustering => {
+                segment::types::TokenPooling::HierarchicalClustering
+            }
+        }
+    }
+}
+
+impl From<MultiVectorRepresentative> for segment::types::MultiVectorRepresentative {
+    fn from(value: MultiVectorRepresentative) -> Self {
+        match value {
+            MultiVectorRepresentative::Mean => segment::types::MultiVectorRepresentative::Mean,
+            MultiVectorRepresentative::FirstToken => {
+                segment::types::MultiVectorRepresentative::FirstToken
+            }
         }
     }
 }
@@ -26,7 +26,14 @@ pub struct VectorParams {
     pub datatype: ::core::option::Option<i32>,
     /// Configuration for multi-vector search
     #[prost(message, optional, tag = "7")]
+    #[validate]
     pub multivector_config: ::core::option::Option<MultiVectorConfig>,
+    /// Checks of input vectors, invalid vectors are rejected on insert
+    #[prost(message, optional, tag = "8")]
+    pub validation: ::core::option::Option<VectorValidationConfig>,
+    /// If true - vectors are normalized to unit length on insert, whatever the distance is
+    #[prost(bool, optional, tag = "9")]
+    pub normalize: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -108,6 +115,37 @@ pub mod vectors_config_diff {
         ParamsMap(super::VectorParamsDiffMap),
     }
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Bm25Config {
+    /// Controls saturation of the term frequency. Default: 1.2
+    #[prost(float, optional, tag = "1")]
+    #[validate(custom = "crate::grpc::validate::validate_f32_finite_non_negative")]
+    pub k: ::core::option::Option<f32>,
+    /// Controls normalization by the document length, in range \[0, 1\]. Default: 0.75
+    #[prost(float, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_f32_range_1")]
+    pub b: ::core::option::Option<f32>,
+    /// Expected average length of documents, in tokens. Default: 256
+    #[prost(float, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_f32_range_min_1")]
+    pub avg_len: ::core::option::Option<f32>,
+    /// Tokenizer used to split the text into terms. Default: word
+    #[prost(enumeration = "TokenizerType", tag = "4")]
+    pub tokenizer: i32,
+    /// Minimal token length
+    #[prost(uint64, optional, tag = "5")]
+    pub min_token_len: ::core::option::Option<u64>,
+    /// Maximal token length
+    #[prost(uint64, optional, tag = "6")]
+    pub max_token_len: ::core::option::Option<u64>,
+    /// If true - lowercase all tokens. Default: true
+    #[prost(bool, optional, tag = "7")]
+    pub lowercase: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -118,17 +156,27 @@ pub struct SparseVectorParams {
     /// If set - apply modifier to the vector values
     #[prost(enumeration = "Modifier", optional, tag = "2")]
     pub modifier: ::core::option::Option<i32>,
+    /// If set - text documents are converted into sparse vectors with BM25
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub bm25: ::core::option::Option<Bm25Config>,
+    /// Type of storage of the vectors. Default: Mmap for new collections
+    #[prost(enumeration = "SparseVectorStorageType", optional, tag = "4")]
+    pub storage_type: ::core::option::Option<i32>,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SparseVectorConfig {
     #[prost(map = "string, message", tag = "1")]
+    #[validate]
     pub map: ::std::collections::HashMap<
         ::prost::alloc::string::String,
         SparseVectorParams,
     >,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -136,6 +184,30 @@ pub struct MultiVectorConfig {
     /// Comparator for multi-vector search
     #[prost(enumeration = "MultiVectorComparator", tag = "1")]
     pub comparator: i32,
+    /// Maximal number of token vectors stored per point, extra tokens are pooled
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_tokens: ::core::option::Option<u64>,
+    /// How to reduce the number of token vectors down to `max_tokens`
+    #[prost(enumeration = "TokenPooling", optional, tag = "3")]
+    pub pooling: ::core::option::Option<i32>,
+    /// If set, the HNSW graph is built over a single representative vector per point
+    #[prost(enumeration = "MultiVectorRepresentative", optional, tag = "4")]
+    pub representative: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorValidationConfig {
+    /// Reject vectors with NaN or infinite values
+    #[prost(bool, optional, tag = "1")]
+    pub reject_non_finite: ::core::option::Option<bool>,
+    /// Reject vectors with all values equal to zero
+    #[prost(bool, optional, tag = "2")]
+    pub reject_zero: ::core::option::Option<bool>,
+    /// Reject vectors, which are not of unit length
+    #[prost(bool, optional, tag = "3")]
+    pub require_unit_norm: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -265,6 +337,10 @@ pub struct SparseIndexConfig {
     /// Store inverted index on disk. If set to false, the index will be stored in RAM.
     #[prost(bool, optional, tag = "2")]
     pub on_disk: ::core::option::Option<bool>,
+    ///
+    /// Datatype used to store weights in the index.
+    #[prost(enumeration = "Datatype", optional, tag = "3")]
+    pub datatype: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -348,6 +424,12 @@ pub struct OptimizersConfigDiff {
     /// If 0 - no optimization threads, optimizations will be disabled.
     #[prost(uint64, optional, tag = "8")]
     pub max_optimization_threads: ::core::option::Option<u64>,
+    ///
+    /// The minimal fraction of deleted vectors in an HNSW graph, required to heal the graph in place.
+    /// Healing reconnects neighbours of deleted points without rebuilding the segment.
+    #[prost(double, optional, tag = "9")]
+    #[validate(custom = "crate::grpc::validate::validate_f64_range_1")]
+    pub graph_healing_threshold: ::core::option::Option<f64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -494,6 +576,7 @@ pub struct CreateCollection {
     pub sharding_method: ::core::option::Option<i32>,
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "16")]
+    #[validate]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
 }
 #[derive(validator::Validate)]
@@ -531,7 +614,12 @@ pub struct UpdateCollection {
     pub quantization_config: ::core::option::Option<QuantizationConfigDiff>,
     /// New sparse vector parameters
     #[prost(message, optional, tag = "8")]
+    #[validate]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// New strict mode limits, only specified limits are changed
+    #[prost(message, optional, tag = "9")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -611,6 +699,36 @@ pub struct CollectionParamsDiff {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StrictModeConfig {
+    /// Whether strict mode is enabled for the collection
+    #[prost(bool, optional, tag = "1")]
+    pub enabled: ::core::option::Option<bool>,
+    /// Max allowed `limit` of search, query and scroll requests
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_query_limit: ::core::option::Option<u64>,
+    /// Allow filtering by payload fields without a suitable index in search, query, scroll and count requests
+    #[prost(bool, optional, tag = "3")]
+    pub unindexed_filtering_retrieve: ::core::option::Option<bool>,
+    /// Allow filtering by payload fields without a suitable index in update requests
+    #[prost(bool, optional, tag = "4")]
+    pub unindexed_filtering_update: ::core::option::Option<bool>,
+    /// Max allowed `hnsw_ef` search parameter
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub search_max_hnsw_ef: ::core::option::Option<u64>,
+    /// Allow exact search, which does not use the vector index
+    #[prost(bool, optional, tag = "6")]
+    pub search_allow_exact: ::core::option::Option<bool>,
+    /// Max number of points in a single upsert or vector update request
+    #[prost(uint64, optional, tag = "7")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub upsert_max_batchsize: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CollectionConfig {
     /// Collection parameters
     #[prost(message, optional, tag = "1")]
@@ -630,6 +748,10 @@ pub struct CollectionConfig {
     #[prost(message, optional, tag = "5")]
     #[validate]
     pub quantization_config: ::core::option::Option<QuantizationConfig>,
+    /// Strict mode limits of the collection
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -647,6 +769,9 @@ pub struct TextIndexParams {
     /// Maximal token length
     #[prost(uint64, optional, tag = "4")]
     pub max_token_len: ::core::option::Option<u64>,
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "5")]
+    pub on_disk: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -658,12 +783,69 @@ pub struct IntegerIndexParams {
     /// If true - support ranges filters.
     #[prost(bool, tag = "2")]
     pub range: bool,
+    /// If true - used for tenant optimization.
+    #[prost(bool, optional, tag = "3")]
+    pub is_tenant: ::core::option::Option<bool>,
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "4")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KeywordIndexParams {
+    /// If true - used for tenant optimization.
+    #[prost(bool, optional, tag = "1")]
+    pub is_tenant: ::core::option::Option<bool>,
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "2")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FloatIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BoolIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatetimeIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UuidIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PayloadIndexParams {
-    #[prost(oneof = "payload_index_params::IndexParams", tags = "1, 2")]
+    #[prost(oneof = "payload_index_params::IndexParams", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
     pub index_params: ::core::option::Option<payload_index_params::IndexParams>,
 }
 /// Nested message and enum types in `PayloadIndexParams`.
@@ -678,6 +860,24 @@ pub mod payload_index_params {
         /// Parameters for integer index
         #[prost(message, tag = "2")]
         IntegerIndexParams(super::IntegerIndexParams),
+        /// Parameters for keyword index
+        #[prost(message, tag = "3")]
+        KeywordIndexParams(super::KeywordIndexParams),
+        /// Parameters for float index
+        #[prost(message, tag = "4")]
+        FloatIndexParams(super::FloatIndexParams),
+        /// Parameters for geo index
+        #[prost(message, tag = "5")]
+        GeoIndexParams(super::GeoIndexParams),
+        /// Parameters for bool index
+        #[prost(message, tag = "6")]
+        BoolIndexParams(super::BoolIndexParams),
+        /// Parameters for datetime index
+        #[prost(message, tag = "7")]
+        DatetimeIndexParams(super::DatetimeIndexParams),
+        /// Parameters for uuid index
+        #[prost(message, tag = "8")]
+        UuidIndexParams(super::UuidIndexParams),
     }
 }
 #[derive(serde::Serialize)]
@@ -693,6 +893,9 @@ pub struct PayloadSchemaInfo {
     /// Number of points indexed within this field indexed
     #[prost(uint64, optional, tag = "3")]
     pub points: ::core::option::Option<u64>,
+    /// Size of the on-disk files of the index in bytes
+    #[prost(uint64, optional, tag = "4")]
+    pub disk_usage_bytes: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1170,8 +1373,39 @@ impl Modifier {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum SparseVectorStorageType {
+    /// Storage in RocksDB, used by collections created before Mmap storage was introduced
+    SparseOnDisk = 0,
+    /// Compact storage in memory mapped files
+    SparseMmap = 1,
+}
+impl SparseVectorStorageType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SparseVectorStorageType::SparseOnDisk => "SparseOnDisk",
+            SparseVectorStorageType::SparseMmap => "SparseMmap",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SparseOnDisk" => Some(Self::SparseOnDisk),
+            "SparseMmap" => Some(Self::SparseMmap),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum MultiVectorComparator {
     MaxSim = 0,
+    AvgMaxSim = 1,
+    SymmetricMaxSim = 2,
 }
 impl MultiVectorComparator {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1181,12 +1415,74 @@ impl MultiVectorComparator {
     pub fn as_str_name(&self) -> &'static str {
         match self {
             MultiVectorComparator::MaxSim => "MaxSim",
+            MultiVectorComparator::AvgMaxSim => "AvgMaxSim",
+            MultiVectorComparator::SymmetricMaxSim => "SymmetricMaxSim",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
             "MaxSim" => Some(Self::MaxSim),
+            "AvgMaxSim" => Some(Self::AvgMaxSim),
+            "SymmetricMaxSim" => Some(Self::SymmetricMaxSim),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TokenPooling {
+    /// Keep the first `max_tokens` token vectors
+    KeepFirst = 0,
+    /// Merge the most similar token vectors into their mean
+    HierarchicalClustering = 1,
+}
+impl TokenPooling {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            TokenPooling::KeepFirst => "KeepFirst",
+            TokenPooling::HierarchicalClustering => "HierarchicalClustering",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "KeepFirst" => Some(Self::KeepFirst),
+            "HierarchicalClustering" => Some(Self::HierarchicalClustering),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MultiVectorRepresentative {
+    /// Mean of all token vectors
+    Mean = 0,
+    /// First token vector, e.g. the `\[CLS\]` token
+    FirstToken = 1,
+}
+impl MultiVectorRepresentative {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            MultiVectorRepresentative::Mean => "Mean",
+            MultiVectorRepresentative::FirstToken => "FirstToken",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Mean" => Some(Self::Mean),
+            "FirstToken" => Some(Self::FirstToken),
             _ => None,
         }
     }
@@ -1279,6 +1575,7 @@ pub enum PayloadSchemaType {
     Text = 5,
     Bool = 6,
     Datetime = 7,
+    Uuid = 8,
 }
 impl PayloadSchemaType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1295,6 +1592,7 @@ impl PayloadSchemaType {
             PayloadSchemaType::Text => "Text",
             PayloadSchemaType::Bool => "Bool",
             PayloadSchemaType::Datetime => "Datetime",
+            PayloadSchemaType::Uuid => "Uuid",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1308,6 +1606,7 @@ impl PayloadSchemaType {
             "Text" => Some(Self::Text),
             "Bool" => Some(Self::Bool),
             "Datetime" => Some(Self::Datetime),
+            "Uuid" => Some(Self::Uuid),
             _ => None,
         }
     }
@@ -3736,6 +4035,9 @@ pub struct Vector {
     /// Number of vectors per multi vector
     #[prost(uint32, optional, tag = "3")]
     pub vectors_count: ::core::option::Option<u32>,
+    /// Text document, which is converted into a sparse vector by the server
+    #[prost(message, optional, tag = "4")]
+    pub document: ::core::option::Option<Document>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3760,12 +4062,24 @@ pub struct MultiDenseVector {
     #[prost(message, repeated, tag = "1")]
     pub vectors: ::prost::alloc::vec::Vec<DenseVector>,
 }
+/// Text document, which is converted into a vector by the server
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Document {
+    /// Text of the document
+    #[prost(string, tag = "1")]
+    pub text: ::prost::alloc::string::String,
+    /// Model used to convert the text into a vector
+    #[prost(enumeration = "DocumentModel", tag = "2")]
+    pub model: i32,
+}
 /// Vector type to be used in queries. Ids will be substituted with their corresponding vectors from the collection.
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct VectorInput {
-    #[prost(oneof = "vector_input::Variant", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "vector_input::Variant", tags = "1, 2, 3, 4, 5")]
     pub variant: ::core::option::Option<vector_input::Variant>,
 }
 /// Nested message and enum types in `VectorInput`.
@@ -3782,6 +4096,8 @@ pub mod vector_input {
         Sparse(super::SparseVector),
         #[prost(message, tag = "4")]
         MultiDense(super::MultiDenseVector),
+        #[prost(message, tag = "5")]
+        Document(super::Document),
     }
 }
 /// ---------------------------------------------
@@ -4190,6 +4506,17 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[prost(bool, optional, tag = "4")]
     pub indexed_only: ::core::option::Option<bool>,
+    ///
+    /// Maximal number of query token vectors used for multivector search.
+    /// Overrides `max_tokens` of the multivector config.
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_tokens: ::core::option::Option<u64>,
+    ///
+    /// Explore neighbours of the graph neighbours, which don't match the filter (two-hop traversal).
+    /// If not set, it is enabled automatically for filters of medium selectivity.
+    #[prost(bool, optional, tag = "6")]
+    pub acorn: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4801,12 +5128,14 @@ pub mod query {
         Fusion(i32),
     }
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PrefetchQuery {
     /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
     #[prost(message, repeated, tag = "1")]
+    #[validate]
     pub prefetch: ::prost::alloc::vec::Vec<PrefetchQuery>,
     /// Query to perform. If missing, returns points ordered by their IDs.
     #[prost(message, optional, tag = "2")]
@@ -4826,16 +5155,23 @@ pub struct PrefetchQuery {
     /// Max number of points. Default is 10
     #[prost(uint64, optional, tag = "7")]
     pub limit: ::core::option::Option<u64>,
+    /// Weight of the results of this prefetch, if the parent query is a fusion. Default is 1.0
+    #[prost(float, optional, tag = "8")]
+    #[validate(custom = "crate::grpc::validate::validate_f32_finite_non_negative")]
+    pub weight: ::core::option::Option<f32>,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct QueryPoints {
     /// Name of the collection
     #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
     /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
     #[prost(message, repeated, tag = "2")]
+    #[validate]
     pub prefetch: ::prost::alloc::vec::Vec<PrefetchQuery>,
     /// Query to perform. If missing, returns points ordered by their IDs.
     #[prost(message, optional, tag = "3")]
@@ -4845,15 +5181,18 @@ pub struct QueryPoints {
     pub using: ::core::option::Option<::prost::alloc::string::String>,
     /// Filter conditions - return only those points that satisfy the specified conditions.
     #[prost(message, optional, tag = "5")]
+    #[validate]
     pub filter: ::core::option::Option<Filter>,
     /// Search params for when there is no prefetch.
     #[prost(message, optional, tag = "6")]
+    #[validate]
     pub search_params: ::core::option::Option<SearchParams>,
     /// Return points with scores better than this threshold.
     #[prost(float, optional, tag = "7")]
     pub score_threshold: ::core::option::Option<f32>,
     /// Max number of points. Default is 10.
     #[prost(uint64, optional, tag = "8")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
     pub limit: ::core::option::Option<u64>,
     /// Offset of the result. Skip this many points. Default is 0.
     #[prost(uint64, optional, tag = "9")]
@@ -4870,69 +5209,359 @@ pub struct QueryPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards.
     #[prost(message, optional, tag = "13")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "14")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PointsUpdateOperation {
-    #[prost(
-        oneof = "points_update_operation::Operation",
-        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10"
-    )]
-    pub operation: ::core::option::Option<points_update_operation::Operation>,
+pub struct QueryBatchPoints {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    #[validate]
+    pub query_points: ::prost::alloc::vec::Vec<QueryPoints>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "3")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
 }
-/// Nested message and enum types in `PointsUpdateOperation`.
-pub mod points_update_operation {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct PointStructList {
-        #[prost(message, repeated, tag = "1")]
-        pub points: ::prost::alloc::vec::Vec<super::PointStruct>,
-        /// Option for custom sharding to specify used shard keys
-        #[prost(message, optional, tag = "2")]
-        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
-    }
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct SetPayload {
-        #[prost(map = "string, message", tag = "1")]
-        pub payload: ::std::collections::HashMap<
-            ::prost::alloc::string::String,
-            super::Value,
-        >,
-        /// Affected points
-        #[prost(message, optional, tag = "2")]
-        pub points_selector: ::core::option::Option<super::PointsSelector>,
-        /// Option for custom sharding to specify used shard keys
-        #[prost(message, optional, tag = "3")]
-        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
-        /// Option for indicate property of payload
-        #[prost(string, optional, tag = "4")]
-        pub key: ::core::option::Option<::prost::alloc::string::String>,
-    }
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct OverwritePayload {
-        #[prost(map = "string, message", tag = "1")]
-        pub payload: ::std::collections::HashMap<
-            ::prost::alloc::string::String,
-            super::Value,
-        >,
-        /// Affected points
-        #[prost(message, optional, tag = "2")]
-        pub points_selector: ::core::option::Option<super::PointsSelector>,
-        /// Option for custom sharding to specify used shard keys
-        #[prost(message, optional, tag = "3")]
-        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
-        /// Option for indicate property of payload
-        #[prost(string, optional, tag = "4")]
-        pub key: ::core::option::Option<::prost::alloc::string::String>,
-    }
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryPointGroups {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
+    #[prost(message, repeated, tag = "2")]
+    #[validate]
+    pub prefetch: ::prost::alloc::vec::Vec<PrefetchQuery>,
+    /// Query to perform. If missing, returns points ordered by their IDs.
+    #[prost(message, optional, tag = "3")]
+    pub query: ::core::option::Option<Query>,
+    /// Define which vector to use for querying. If missing, the default vector is used.
+    #[prost(string, optional, tag = "4")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// Filter conditions - return only those points that satisfy the specified conditions.
+    #[prost(message, optional, tag = "5")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Search params for when there is no prefetch.
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub search_params: ::core::option::Option<SearchParams>,
+    /// Return points with scores better than this threshold.
+    #[prost(float, optional, tag = "7")]
+    pub score_threshold: ::core::option::Option<f32>,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag = "8")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Options for specifying which vectors to include into response
+    #[prost(message, optional, tag = "9")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Payload field to group by, must be a string or number field. If there are multiple values for the field, all of them will be used. One point can be in multiple groups.
+    #[prost(string, tag = "10")]
+    #[validate(length(min = 1))]
+    pub group_by: ::prost::alloc::string::String,
+    /// Maximum amount of points to return per group
+    #[prost(uint32, tag = "11")]
+    #[validate(range(min = 1))]
+    pub group_size: u32,
+    /// Max number of groups to return
+    #[prost(uint32, tag = "12")]
+    #[validate(range(min = 1))]
+    pub limit: u32,
+    /// Options for specifying how to use the group id to lookup points in another collection
+    #[prost(message, optional, tag = "13")]
+    pub with_lookup: ::core::option::Option<WithLookup>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "14")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "15")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "16")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetCounts {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Payload key of the facet
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - only count values of the points that satisfy the specified conditions.
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of facet values to return. Default is 10.
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+    /// If true, return exact counts, slower but useful for debugging purposes. Default is false.
+    #[prost(bool, optional, tag = "5")]
+    pub exact: ::core::option::Option<bool>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "6")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "7")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "8")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetValue {
+    #[prost(oneof = "facet_value::Variant", tags = "1, 2, 3")]
+    pub variant: ::core::option::Option<facet_value::Variant>,
+}
+/// Nested message and enum types in `FacetValue`.
+pub mod facet_value {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// String value from the facet
+        #[prost(string, tag = "1")]
+        StringValue(::prost::alloc::string::String),
+        /// Integer value from the facet
+        #[prost(int64, tag = "2")]
+        IntegerValue(i64),
+        /// Boolean value from the facet
+        #[prost(bool, tag = "3")]
+        BoolValue(bool),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetHit {
+    /// Value from the facet
+    #[prost(message, optional, tag = "1")]
+    pub value: ::core::option::Option<FacetValue>,
+    /// Number of points with this value
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Filter conditions - return only those points that satisfy the specified conditions.
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// How many points to select and search within. Default is 10.
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_2_max_10000")]
+    pub sample: ::core::option::Option<u64>,
+    /// How many neighbours per sample to find. Default is 3.
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+    /// Define which vector to use for querying. If missing, the default vector is used.
+    #[prost(string, optional, tag = "5")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "6")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "7")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "8")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPairs {
+    /// List of pairs of points with scores
+    #[prost(message, repeated, tag = "1")]
+    pub pairs: ::prost::alloc::vec::Vec<SearchMatrixPair>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPair {
+    /// first id of the pair
+    #[prost(message, optional, tag = "1")]
+    pub a: ::core::option::Option<PointId>,
+    /// second id of the pair
+    #[prost(message, optional, tag = "2")]
+    pub b: ::core::option::Option<PointId>,
+    /// score of the pair
+    #[prost(float, tag = "3")]
+    pub score: f32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixOffsets {
+    /// Row indices of the matrix
+    #[prost(uint64, repeated, tag = "1")]
+    pub offsets_row: ::prost::alloc::vec::Vec<u64>,
+    /// Column indices of the matrix
+    #[prost(uint64, repeated, tag = "2")]
+    pub offsets_col: ::prost::alloc::vec::Vec<u64>,
+    /// Scores associated with matrix coordinates
+    #[prost(float, repeated, tag = "3")]
+    pub scores: ::prost::alloc::vec::Vec<f32>,
+    /// Ids of the points in order
+    #[prost(message, repeated, tag = "4")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClockTag {
+    #[prost(uint64, tag = "1")]
+    pub peer_id: u64,
+    #[prost(uint32, tag = "2")]
+    pub clock_id: u32,
+    #[prost(uint64, tag = "3")]
+    pub clock_tick: u64,
+    #[prost(uint64, tag = "4")]
+    pub token: u64,
+    #[prost(bool, tag = "5")]
+    pub force: bool,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangeCursor {
+    /// Sequence number of the change in the shard WAL
+    #[prost(uint64, tag = "1")]
+    pub op_num: u64,
+    /// Clock tag of the change, used to detect if the WAL was replaced, e.g. by a shard transfer
+    #[prost(message, optional, tag = "2")]
+    pub clock_tag: ::core::option::Option<ClockTag>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadChanges {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the shard, must be local to the peer
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Name of the consumer. If set, the change at the cursor and all later ones are kept in the WAL until the consumer moves on. Consumers, which don't acknowledge changes for 24 hours, are deleted
+    #[prost(string, optional, tag = "3")]
+    #[validate(length(min = 1))]
+    pub consumer: ::core::option::Option<::prost::alloc::string::String>,
+    /// Cursor of the last consumed change. If not set, changes are read from the oldest one still present in the WAL
+    #[prost(message, optional, tag = "4")]
+    pub cursor: ::core::option::Option<ChangeCursor>,
+    /// End the stream if there are no new changes for this many seconds. If not set, stream until cancelled
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangeRecord {
+    /// Cursor of this change, to resume reading right after it
+    #[prost(message, optional, tag = "1")]
+    pub cursor: ::core::option::Option<ChangeCursor>,
+    /// JSON encoded update operation, in the same format as in the REST API
+    #[prost(string, tag = "2")]
+    pub operation: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointsUpdateOperation {
+    #[prost(
+        oneof = "points_update_operation::Operation",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10"
+    )]
+    pub operation: ::core::option::Option<points_update_operation::Operation>,
+}
+/// Nested message and enum types in `PointsUpdateOperation`.
+pub mod points_update_operation {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct PointStructList {
+        #[prost(message, repeated, tag = "1")]
+        pub points: ::prost::alloc::vec::Vec<super::PointStruct>,
+        /// Option for custom sharding to specify used shard keys
+        #[prost(message, optional, tag = "2")]
+        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct SetPayload {
+        #[prost(map = "string, message", tag = "1")]
+        pub payload: ::std::collections::HashMap<
+            ::prost::alloc::string::String,
+            super::Value,
+        >,
+        /// Affected points
+        #[prost(message, optional, tag = "2")]
+        pub points_selector: ::core::option::Option<super::PointsSelector>,
+        /// Option for custom sharding to specify used shard keys
+        #[prost(message, optional, tag = "3")]
+        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+        /// Option for indicate property of payload
+        #[prost(string, optional, tag = "4")]
+        pub key: ::core::option::Option<::prost::alloc::string::String>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct OverwritePayload {
+        #[prost(map = "string, message", tag = "1")]
+        pub payload: ::std::collections::HashMap<
+            ::prost::alloc::string::String,
+            super::Value,
+        >,
+        /// Affected points
+        #[prost(message, optional, tag = "2")]
+        pub points_selector: ::core::option::Option<super::PointsSelector>,
+        /// Option for custom sharding to specify used shard keys
+        #[prost(message, optional, tag = "3")]
+        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+        /// Option for indicate property of payload
+        #[prost(string, optional, tag = "4")]
+        pub key: ::core::option::Option<::prost::alloc::string::String>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct DeletePayload {
         #[prost(string, repeated, tag = "1")]
@@ -5300,6 +5929,66 @@ pub struct UpdateBatchResponse {
     #[prost(double, tag = "2")]
     pub time: f64,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryBatchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<BatchResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryGroupsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<GroupsResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub hits: ::prost::alloc::vec::Vec<FacetHit>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPairsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<SearchMatrixPairs>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixOffsetsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<SearchMatrixOffsets>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5650,32 +6339,57 @@ impl WriteOrderingType {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum ReadConsistencyType {
-    /// Send request to all nodes and return points which are present on all of them
-    All = 0,
-    /// Send requests to all nodes and return points which are present on majority of them
-    Majority = 1,
-    /// Send requests to half + 1 nodes, return points which are present on all of them
-    Quorum = 2,
+pub enum ReadConsistencyType {
+    /// Send request to all nodes and return points which are present on all of them
+    All = 0,
+    /// Send requests to all nodes and return points which are present on majority of them
+    Majority = 1,
+    /// Send requests to half + 1 nodes, return points which are present on all of them
+    Quorum = 2,
+}
+impl ReadConsistencyType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReadConsistencyType::All => "All",
+            ReadConsistencyType::Majority => "Majority",
+            ReadConsistencyType::Quorum => "Quorum",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "All" => Some(Self::All),
+            "Majority" => Some(Self::Majority),
+            "Quorum" => Some(Self::Quorum),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum DocumentModel {
+    /// Sparse vector of BM25 term weights, requires `bm25` config of the sparse vector
+    Bm25 = 0,
 }
-impl ReadConsistencyType {
+impl DocumentModel {
     /// String value of the enum field names used in the ProtoBuf definition.
     ///
     /// The values are not transformed in any way and thus are considered stable
     /// (if the ProtoBuf definition does not change) and safe for programmatic use.
     pub fn as_str_name(&self) -> &'static str {
         match self {
-            ReadConsistencyType::All => "All",
-            ReadConsistencyType::Majority => "Majority",
-            ReadConsistencyType::Quorum => "Quorum",
+            DocumentModel::Bm25 => "Bm25",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
-            "All" => Some(Self::All),
-            "Majority" => Some(Self::Majority),
-            "Quorum" => Some(Self::Quorum),
+            "Bm25" => Some(Self::Bm25),
             _ => None,
         }
     }
@@ -5691,6 +6405,7 @@ pub enum FieldType {
     Text = 4,
     Bool = 5,
     Datetime = 6,
+    Uuid = 7,
 }
 impl FieldType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -5706,6 +6421,7 @@ impl FieldType {
             FieldType::Text => "FieldTypeText",
             FieldType::Bool => "FieldTypeBool",
             FieldType::Datetime => "FieldTypeDatetime",
+            FieldType::Uuid => "FieldTypeUuid",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -5718,6 +6434,7 @@ impl FieldType {
             "FieldTypeText" => Some(Self::Text),
             "FieldTypeBool" => Some(Self::Bool),
             "FieldTypeDatetime" => Some(Self::Datetime),
+            "FieldTypeUuid" => Some(Self::Uuid),
             _ => None,
         }
     }
@@ -5788,6 +6505,10 @@ impl RecommendStrategy {
 pub enum Fusion {
     /// Reciprocal Rank Fusion
     Rrf = 0,
+    /// Distribution-Based Score Fusion, normalizes scores of each prefetch by mean ± 3 standard deviations, then sums them
+    Dbsf = 1,
+    /// Min-Max Score Fusion, normalizes scores of each prefetch by their min and max, then sums them
+    MinMax = 2,
 }
 impl Fusion {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -5797,12 +6518,16 @@ impl Fusion {
     pub fn as_str_name(&self) -> &'static str {
         match self {
             Fusion::Rrf => "RRF",
+            Fusion::Dbsf => "DBSF",
+            Fusion::MinMax => "MIN_MAX",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
             "RRF" => Some(Self::Rrf),
+            "DBSF" => Some(Self::Dbsf),
+            "MIN_MAX" => Some(Self::MinMax),
             _ => None,
         }
     }
@@ -6495,6 +7220,179 @@ pub mod points_client {
             req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "UpdateBatch"));
             self.inner.unary(req, path, codec).await
         }
+        ///
+        /// Universally query points. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
+        pub async fn query(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryPoints>,
+        ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Query");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Query"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Universally query points in a batch fashion. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
+        pub async fn query_batch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryBatchPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryBatchResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/QueryBatch");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "QueryBatch"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Universally query points and group results by a payload field. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
+        pub async fn query_groups(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryPointGroups>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryGroupsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/QueryGroups",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "QueryGroups"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Count the number of points per value of a payload field, with given filtering conditions
+        pub async fn facet(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FacetCounts>,
+        ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Facet");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Facet"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Compute distance matrix for sampled points with a pair based output format
+        pub async fn search_matrix_pairs(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixPairsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SearchMatrixPairs",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SearchMatrixPairs"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Compute distance matrix for sampled points with an offset based output format
+        pub async fn search_matrix_offsets(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixOffsetsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SearchMatrixOffsets",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SearchMatrixOffsets"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Stream update operations of a local shard in the order they were applied
+        pub async fn stream_changes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReadChanges>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::ChangeRecord>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/StreamChanges",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "StreamChanges"));
+            self.inner.server_streaming(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -6704,6 +7602,69 @@ pub mod points_server {
             tonic::Response<super::UpdateBatchResponse>,
             tonic::Status,
         >;
+        ///
+        /// Universally query points. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
+        async fn query(
+            &self,
+            request: tonic::Request<super::QueryPoints>,
+        ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status>;
+        ///
+        /// Universally query points in a batch fashion. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
+        async fn query_batch(
+            &self,
+            request: tonic::Request<super::QueryBatchPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryBatchResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Universally query points and group results by a payload field. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
+        async fn query_groups(
+            &self,
+            request: tonic::Request<super::QueryPointGroups>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryGroupsResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Count the number of points per value of a payload field, with given filtering conditions
+        async fn facet(
+            &self,
+            request: tonic::Request<super::FacetCounts>,
+        ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status>;
+        ///
+        /// Compute distance matrix for sampled points with a pair based output format
+        async fn search_matrix_pairs(
+            &self,
+            request: tonic::Request<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixPairsResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Compute distance matrix for sampled points with an offset based output format
+        async fn search_matrix_offsets(
+            &self,
+            request: tonic::Request<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixOffsetsResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the StreamChanges method.
+        type StreamChangesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::ChangeRecord, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        ///
+        /// Stream update operations of a local shard in the order they were applied
+        async fn stream_changes(
+            &self,
+            request: tonic::Request<super::ReadChanges>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamChangesStream>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsServer<T: Points> {
@@ -6786,9 +7747,143 @@ pub mod points_server {
             match req.uri().path() {
                 "/qdrant.Points/Upsert" => {
                     #[allow(non_camel_case_types)]
-                    struct UpsertSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::UpsertPoints>
-                    for UpsertSvc<T> {
+                    struct UpsertSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::UpsertPoints>
+                    for UpsertSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpsertPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::upsert(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpsertSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Delete" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::DeletePoints>
+                    for DeleteSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeletePoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::delete(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Get" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::GetPoints>
+                    for GetSvc<T> {
+                        type Response = super::GetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::get(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/UpdateVectors" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateVectorsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::UpdatePointVectors>
+                    for UpdateVectorsSvc<T> {
                         type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -6796,11 +7891,11 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::UpsertPoints>,
+                            request: tonic::Request<super::UpdatePointVectors>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::upsert(&inner, request).await
+                                <T as Points>::update_vectors(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6812,7 +7907,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = UpsertSvc(inner);
+                        let method = UpdateVectorsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6828,11 +7923,193 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Delete" => {
+                "/qdrant.Points/DeleteVectors" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::DeletePoints>
-                    for DeleteSvc<T> {
+                    struct DeleteVectorsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::DeletePointVectors>
+                    for DeleteVectorsSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeletePointVectors>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::delete_vectors(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteVectorsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/SetPayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetPayloadSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::SetPayloadPoints>
+                    for SetPayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetPayloadPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::set_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetPayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/OverwritePayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct OverwritePayloadSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::SetPayloadPoints>
+                    for OverwritePayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetPayloadPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::overwrite_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = OverwritePayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/DeletePayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeletePayloadSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::DeletePayloadPoints>
+                    for DeletePayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeletePayloadPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::delete_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeletePayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/ClearPayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct ClearPayloadSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::ClearPayloadPoints>
+                    for ClearPayloadSvc<T> {
                         type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -6840,11 +8117,11 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeletePoints>,
+                            request: tonic::Request<super::ClearPayloadPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::delete(&inner, request).await
+                                <T as Points>::clear_payload(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6856,7 +8133,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeleteSvc(inner);
+                        let method = ClearPayloadSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6872,23 +8149,25 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Get" => {
+                "/qdrant.Points/CreateFieldIndex" => {
                     #[allow(non_camel_case_types)]
-                    struct GetSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::GetPoints>
-                    for GetSvc<T> {
-                        type Response = super::GetResponse;
+                    struct CreateFieldIndexSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::CreateFieldIndexCollection>
+                    for CreateFieldIndexSvc<T> {
+                        type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetPoints>,
+                            request: tonic::Request<super::CreateFieldIndexCollection>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::get(&inner, request).await
+                                <T as Points>::create_field_index(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6900,7 +8179,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = GetSvc(inner);
+                        let method = CreateFieldIndexSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6916,13 +8195,13 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/UpdateVectors" => {
+                "/qdrant.Points/DeleteFieldIndex" => {
                     #[allow(non_camel_case_types)]
-                    struct UpdateVectorsSvc<T: Points>(pub Arc<T>);
+                    struct DeleteFieldIndexSvc<T: Points>(pub Arc<T>);
                     impl<
                         T: Points,
-                    > tonic::server::UnaryService<super::UpdatePointVectors>
-                    for UpdateVectorsSvc<T> {
+                    > tonic::server::UnaryService<super::DeleteFieldIndexCollection>
+                    for DeleteFieldIndexSvc<T> {
                         type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -6930,11 +8209,11 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::UpdatePointVectors>,
+                            request: tonic::Request<super::DeleteFieldIndexCollection>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::update_vectors(&inner, request).await
+                                <T as Points>::delete_field_index(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6946,7 +8225,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = UpdateVectorsSvc(inner);
+                        let method = DeleteFieldIndexSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6962,25 +8241,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/DeleteVectors" => {
+                "/qdrant.Points/Search" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteVectorsSvc<T: Points>(pub Arc<T>);
-                    impl<
-                        T: Points,
-                    > tonic::server::UnaryService<super::DeletePointVectors>
-                    for DeleteVectorsSvc<T> {
-                        type Response = super::PointsOperationResponse;
+                    struct SearchSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::SearchPoints>
+                    for SearchSvc<T> {
+                        type Response = super::SearchResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeletePointVectors>,
+                            request: tonic::Request<super::SearchPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::delete_vectors(&inner, request).await
+                                <T as Points>::search(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6992,7 +8269,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeleteVectorsSvc(inner);
+                        let method = SearchSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7008,23 +8285,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/SetPayload" => {
+                "/qdrant.Points/SearchBatch" => {
                     #[allow(non_camel_case_types)]
-                    struct SetPayloadSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::SetPayloadPoints>
-                    for SetPayloadSvc<T> {
-                        type Response = super::PointsOperationResponse;
+                    struct SearchBatchSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::SearchBatchPoints>
+                    for SearchBatchSvc<T> {
+                        type Response = super::SearchBatchResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SetPayloadPoints>,
+                            request: tonic::Request<super::SearchBatchPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::set_payload(&inner, request).await
+                                <T as Points>::search_batch(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7036,7 +8313,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = SetPayloadSvc(inner);
+                        let method = SearchBatchSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7052,23 +8329,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/OverwritePayload" => {
+                "/qdrant.Points/SearchGroups" => {
                     #[allow(non_camel_case_types)]
-                    struct OverwritePayloadSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::SetPayloadPoints>
-                    for OverwritePayloadSvc<T> {
-                        type Response = super::PointsOperationResponse;
+                    struct SearchGroupsSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::SearchPointGroups>
+                    for SearchGroupsSvc<T> {
+                        type Response = super::SearchGroupsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SetPayloadPoints>,
+                            request: tonic::Request<super::SearchPointGroups>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::overwrite_payload(&inner, request).await
+                                <T as Points>::search_groups(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7080,7 +8357,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = OverwritePayloadSvc(inner);
+                        let method = SearchGroupsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7096,25 +8373,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/DeletePayload" => {
+                "/qdrant.Points/Scroll" => {
                     #[allow(non_camel_case_types)]
-                    struct DeletePayloadSvc<T: Points>(pub Arc<T>);
-                    impl<
-                        T: Points,
-                    > tonic::server::UnaryService<super::DeletePayloadPoints>
-                    for DeletePayloadSvc<T> {
-                        type Response = super::PointsOperationResponse;
+                    struct ScrollSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::ScrollPoints>
+                    for ScrollSvc<T> {
+                        type Response = super::ScrollResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeletePayloadPoints>,
+                            request: tonic::Request<super::ScrollPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::delete_payload(&inner, request).await
+                                <T as Points>::scroll(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7126,7 +8401,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeletePayloadSvc(inner);
+                        let method = ScrollSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7142,25 +8417,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/ClearPayload" => {
+                "/qdrant.Points/Recommend" => {
                     #[allow(non_camel_case_types)]
-                    struct ClearPayloadSvc<T: Points>(pub Arc<T>);
-                    impl<
-                        T: Points,
-                    > tonic::server::UnaryService<super::ClearPayloadPoints>
-                    for ClearPayloadSvc<T> {
-                        type Response = super::PointsOperationResponse;
+                    struct RecommendSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::RecommendPoints>
+                    for RecommendSvc<T> {
+                        type Response = super::RecommendResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ClearPayloadPoints>,
+                            request: tonic::Request<super::RecommendPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::clear_payload(&inner, request).await
+                                <T as Points>::recommend(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7172,7 +8445,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = ClearPayloadSvc(inner);
+                        let method = RecommendSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7188,25 +8461,25 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/CreateFieldIndex" => {
+                "/qdrant.Points/RecommendBatch" => {
                     #[allow(non_camel_case_types)]
-                    struct CreateFieldIndexSvc<T: Points>(pub Arc<T>);
+                    struct RecommendBatchSvc<T: Points>(pub Arc<T>);
                     impl<
                         T: Points,
-                    > tonic::server::UnaryService<super::CreateFieldIndexCollection>
-                    for CreateFieldIndexSvc<T> {
-                        type Response = super::PointsOperationResponse;
+                    > tonic::server::UnaryService<super::RecommendBatchPoints>
+                    for RecommendBatchSvc<T> {
+                        type Response = super::RecommendBatchResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CreateFieldIndexCollection>,
+                            request: tonic::Request<super::RecommendBatchPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::create_field_index(&inner, request).await
+                                <T as Points>::recommend_batch(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7218,7 +8491,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = CreateFieldIndexSvc(inner);
+                        let method = RecommendBatchSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7234,25 +8507,25 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/DeleteFieldIndex" => {
+                "/qdrant.Points/RecommendGroups" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteFieldIndexSvc<T: Points>(pub Arc<T>);
+                    struct RecommendGroupsSvc<T: Points>(pub Arc<T>);
                     impl<
                         T: Points,
-                    > tonic::server::UnaryService<super::DeleteFieldIndexCollection>
-                    for DeleteFieldIndexSvc<T> {
-                        type Response = super::PointsOperationResponse;
+                    > tonic::server::UnaryService<super::RecommendPointGroups>
+                    for RecommendGroupsSvc<T> {
+                        type Response = super::RecommendGroupsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeleteFieldIndexCollection>,
+                            request: tonic::Request<super::RecommendPointGroups>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::delete_field_index(&inner, request).await
+                                <T as Points>::recommend_groups(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7264,7 +8537,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeleteFieldIndexSvc(inner);
+                        let method = RecommendGroupsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7280,23 +8553,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Search" => {
+                "/qdrant.Points/Discover" => {
                     #[allow(non_camel_case_types)]
-                    struct SearchSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::SearchPoints>
-                    for SearchSvc<T> {
-                        type Response = super::SearchResponse;
+                    struct DiscoverSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::DiscoverPoints>
+                    for DiscoverSvc<T> {
+                        type Response = super::DiscoverResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SearchPoints>,
+                            request: tonic::Request<super::DiscoverPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::search(&inner, request).await
+                                <T as Points>::discover(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7308,7 +8581,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = SearchSvc(inner);
+                        let method = DiscoverSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7324,23 +8597,25 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/SearchBatch" => {
+                "/qdrant.Points/DiscoverBatch" => {
                     #[allow(non_camel_case_types)]
-                    struct SearchBatchSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::SearchBatchPoints>
-                    for SearchBatchSvc<T> {
-                        type Response = super::SearchBatchResponse;
+                    struct DiscoverBatchSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::DiscoverBatchPoints>
+                    for DiscoverBatchSvc<T> {
+                        type Response = super::DiscoverBatchResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SearchBatchPoints>,
+                            request: tonic::Request<super::DiscoverBatchPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::search_batch(&inner, request).await
+                                <T as Points>::discover_batch(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7352,7 +8627,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = SearchBatchSvc(inner);
+                        let method = DiscoverBatchSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7368,23 +8643,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/SearchGroups" => {
+                "/qdrant.Points/Count" => {
                     #[allow(non_camel_case_types)]
-                    struct SearchGroupsSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::SearchPointGroups>
-                    for SearchGroupsSvc<T> {
-                        type Response = super::SearchGroupsResponse;
+                    struct CountSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::CountPoints>
+                    for CountSvc<T> {
+                        type Response = super::CountResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SearchPointGroups>,
+                            request: tonic::Request<super::CountPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::search_groups(&inner, request).await
+                                <T as Points>::count(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7396,7 +8671,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = SearchGroupsSvc(inner);
+                        let method = CountSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7412,23 +8687,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Scroll" => {
+                "/qdrant.Points/UpdateBatch" => {
                     #[allow(non_camel_case_types)]
-                    struct ScrollSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::ScrollPoints>
-                    for ScrollSvc<T> {
-                        type Response = super::ScrollResponse;
+                    struct UpdateBatchSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::UpdateBatchPoints>
+                    for UpdateBatchSvc<T> {
+                        type Response = super::UpdateBatchResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ScrollPoints>,
+                            request: tonic::Request<super::UpdateBatchPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::scroll(&inner, request).await
+                                <T as Points>::update_batch(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7440,7 +8715,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = ScrollSvc(inner);
+                        let method = UpdateBatchSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7456,23 +8731,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Recommend" => {
+                "/qdrant.Points/Query" => {
                     #[allow(non_camel_case_types)]
-                    struct RecommendSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::RecommendPoints>
-                    for RecommendSvc<T> {
-                        type Response = super::RecommendResponse;
+                    struct QuerySvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::QueryPoints>
+                    for QuerySvc<T> {
+                        type Response = super::QueryResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::RecommendPoints>,
+                            request: tonic::Request<super::QueryPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::recommend(&inner, request).await
+                                <T as Points>::query(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7484,7 +8759,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = RecommendSvc(inner);
+                        let method = QuerySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7500,25 +8775,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/RecommendBatch" => {
+                "/qdrant.Points/QueryBatch" => {
                     #[allow(non_camel_case_types)]
-                    struct RecommendBatchSvc<T: Points>(pub Arc<T>);
-                    impl<
-                        T: Points,
-                    > tonic::server::UnaryService<super::RecommendBatchPoints>
-                    for RecommendBatchSvc<T> {
-                        type Response = super::RecommendBatchResponse;
+                    struct QueryBatchSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::QueryBatchPoints>
+                    for QueryBatchSvc<T> {
+                        type Response = super::QueryBatchResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::RecommendBatchPoints>,
+                            request: tonic::Request<super::QueryBatchPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::recommend_batch(&inner, request).await
+                                <T as Points>::query_batch(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7530,7 +8803,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = RecommendBatchSvc(inner);
+                        let method = QueryBatchSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7544,27 +8817,25 @@ pub mod points_server {
                         let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
-                    Box::pin(fut)
-                }
-                "/qdrant.Points/RecommendGroups" => {
-                    #[allow(non_camel_case_types)]
-                    struct RecommendGroupsSvc<T: Points>(pub Arc<T>);
-                    impl<
-                        T: Points,
-                    > tonic::server::UnaryService<super::RecommendPointGroups>
-                    for RecommendGroupsSvc<T> {
-                        type Response = super::RecommendGroupsResponse;
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/QueryGroups" => {
+                    #[allow(non_camel_case_types)]
+                    struct QueryGroupsSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::QueryPointGroups>
+                    for QueryGroupsSvc<T> {
+                        type Response = super::QueryGroupsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::RecommendPointGroups>,
+                            request: tonic::Request<super::QueryPointGroups>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::recommend_groups(&inner, request).await
+                                <T as Points>::query_groups(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7576,7 +8847,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = RecommendGroupsSvc(inner);
+                        let method = QueryGroupsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7592,23 +8863,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Discover" => {
+                "/qdrant.Points/Facet" => {
                     #[allow(non_camel_case_types)]
-                    struct DiscoverSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::DiscoverPoints>
-                    for DiscoverSvc<T> {
-                        type Response = super::DiscoverResponse;
+                    struct FacetSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::FacetCounts>
+                    for FacetSvc<T> {
+                        type Response = super::FacetResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DiscoverPoints>,
+                            request: tonic::Request<super::FacetCounts>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::discover(&inner, request).await
+                                <T as Points>::facet(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7620,7 +8891,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DiscoverSvc(inner);
+                        let method = FacetSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7636,25 +8907,25 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/DiscoverBatch" => {
+                "/qdrant.Points/SearchMatrixPairs" => {
                     #[allow(non_camel_case_types)]
-                    struct DiscoverBatchSvc<T: Points>(pub Arc<T>);
+                    struct SearchMatrixPairsSvc<T: Points>(pub Arc<T>);
                     impl<
                         T: Points,
-                    > tonic::server::UnaryService<super::DiscoverBatchPoints>
-                    for DiscoverBatchSvc<T> {
-                        type Response = super::DiscoverBatchResponse;
+                    > tonic::server::UnaryService<super::SearchMatrixPoints>
+                    for SearchMatrixPairsSvc<T> {
+                        type Response = super::SearchMatrixPairsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DiscoverBatchPoints>,
+                            request: tonic::Request<super::SearchMatrixPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::discover_batch(&inner, request).await
+                                <T as Points>::search_matrix_pairs(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7666,7 +8937,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DiscoverBatchSvc(inner);
+                        let method = SearchMatrixPairsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7682,23 +8953,26 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Count" => {
+                "/qdrant.Points/SearchMatrixOffsets" => {
                     #[allow(non_camel_case_types)]
-                    struct CountSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::CountPoints>
-                    for CountSvc<T> {
-                        type Response = super::CountResponse;
+                    struct SearchMatrixOffsetsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::SearchMatrixPoints>
+                    for SearchMatrixOffsetsSvc<T> {
+                        type Response = super::SearchMatrixOffsetsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CountPoints>,
+                            request: tonic::Request<super::SearchMatrixPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::count(&inner, request).await
+                                <T as Points>::search_matrix_offsets(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -7710,7 +8984,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = CountSvc(inner);
+                        let method = SearchMatrixOffsetsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7726,23 +9000,26 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/UpdateBatch" => {
+                "/qdrant.Points/StreamChanges" => {
                     #[allow(non_camel_case_types)]
-                    struct UpdateBatchSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::UpdateBatchPoints>
-                    for UpdateBatchSvc<T> {
-                        type Response = super::UpdateBatchResponse;
+                    struct StreamChangesSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::ServerStreamingService<super::ReadChanges>
+                    for StreamChangesSvc<T> {
+                        type Response = super::ChangeRecord;
+                        type ResponseStream = T::StreamChangesStream;
                         type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
+                            tonic::Response<Self::ResponseStream>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::UpdateBatchPoints>,
+                            request: tonic::Request<super::ReadChanges>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::update_batch(&inner, request).await
+                                <T as Points>::stream_changes(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7754,7 +9031,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = UpdateBatchSvc(inner);
+                        let method = StreamChangesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7765,7 +9042,7 @@ pub mod points_server {
                                 max_decoding_message_size,
                                 max_encoding_message_size,
                             );
-                        let res = grpc.unary(method, req).await;
+                        let res = grpc.server_streaming(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
@@ -7994,21 +9271,6 @@ pub struct UpdateResultInternal {
     pub clock_tag: ::core::option::Option<ClockTag>,
 }
 #[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ClockTag {
-    #[prost(uint64, tag = "1")]
-    pub peer_id: u64,
-    #[prost(uint32, tag = "2")]
-    pub clock_id: u32,
-    #[prost(uint64, tag = "3")]
-    pub clock_tick: u64,
-    #[prost(uint64, tag = "4")]
-    pub token: u64,
-    #[prost(bool, tag = "5")]
-    pub force: bool,
-}
-#[derive(serde::Serialize)]
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -8315,7 +9577,7 @@ pub mod query_shard_points {
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct Query {
-        #[prost(oneof = "query::Score", tags = "1, 2, 3")]
+        #[prost(oneof = "query::Score", tags = "1, 2, 3, 4")]
         pub score: ::core::option::Option<query::Score>,
     }
     /// Nested message and enum types in `Query`.
@@ -8333,6 +9595,9 @@ pub mod query_shard_points {
             /// Order by a field
             #[prost(message, tag = "3")]
             OrderBy(super::super::OrderBy),
+            /// Sample points
+            #[prost(enumeration = "super::super::Sample", tag = "4")]
+            Sample(i32),
         }
     }
     #[derive(serde::Serialize)]
@@ -8353,6 +9618,8 @@ pub mod query_shard_points {
         pub params: ::core::option::Option<super::SearchParams>,
         #[prost(float, optional, tag = "7")]
         pub score_threshold: ::core::option::Option<f32>,
+        #[prost(float, optional, tag = "8")]
+        pub weight: ::core::option::Option<f32>,
     }
 }
 #[derive(serde::Serialize)]
@@ -8365,6 +9632,8 @@ pub struct QueryPointsInternal {
     pub query_points: ::core::option::Option<QueryShardPoints>,
     #[prost(uint32, optional, tag = "3")]
     pub shard_id: ::core::option::Option<u32>,
+    #[prost(uint64, optional, tag = "4")]
+    pub timeout: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -8376,13 +9645,66 @@ pub struct IntermediateResult {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct QueryResponse {
+pub struct QueryResponseInternal {
     #[prost(message, repeated, tag = "1")]
     pub result: ::prost::alloc::vec::Vec<IntermediateResult>,
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetCountsInternal {
+    #[prost(string, tag = "1")]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<Filter>,
+    #[prost(uint64, tag = "4")]
+    pub limit: u64,
+    #[prost(bool, tag = "5")]
+    pub exact: bool,
+    #[prost(uint32, tag = "6")]
+    pub shard_id: u32,
+    #[prost(uint64, optional, tag = "7")]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetResponseInternal {
+    #[prost(message, repeated, tag = "1")]
+    pub hits: ::prost::alloc::vec::Vec<FacetHit>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Sample {
+    Random = 0,
+}
+impl Sample {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Sample::Random => "Random",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Random" => Some(Self::Random),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod points_internal_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -8861,7 +10183,10 @@ pub mod points_internal_client {
         pub async fn query(
             &mut self,
             request: impl tonic::IntoRequest<super::QueryPointsInternal>,
-        ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status> {
+        ) -> std::result::Result<
+            tonic::Response<super::QueryResponseInternal>,
+            tonic::Status,
+        > {
             self.inner
                 .ready()
                 .await
@@ -8880,6 +10205,31 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "Query"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn facet(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FacetCountsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::FacetResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Facet",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Facet"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -8995,7 +10345,17 @@ pub mod points_internal_server {
         async fn query(
             &self,
             request: tonic::Request<super::QueryPointsInternal>,
-        ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status>;
+        ) -> std::result::Result<
+            tonic::Response<super::QueryResponseInternal>,
+            tonic::Status,
+        >;
+        async fn facet(
+            &self,
+            request: tonic::Request<super::FacetCountsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::FacetResponseInternal>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsInternalServer<T: PointsInternal> {
@@ -9829,7 +11189,7 @@ pub mod points_internal_server {
                         T: PointsInternal,
                     > tonic::server::UnaryService<super::QueryPointsInternal>
                     for QuerySvc<T> {
-                        type Response = super::QueryResponse;
+                        type Response = super::QueryResponseInternal;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
@@ -9868,6 +11228,52 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/Facet" => {
+                    #[allow(non_camel_case_types)]
+                    struct FacetSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::FacetCountsInternal>
+                    for FacetSvc<T> {
+                        type Response = super::FacetResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FacetCountsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::facet(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FacetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -10927,6 +12333,10 @@ pub struct CreateSnapshotRequest {
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
+    /// Name of the existing snapshot to create an incremental snapshot from
+    #[prost(string, optional, tag = "2")]
+    #[validate(length(min = 1))]
+    pub base: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -10951,6 +12361,23 @@ pub struct DeleteSnapshotRequest {
     #[prost(string, tag = "2")]
     #[validate(length(min = 1))]
     pub snapshot_name: ::prost::alloc::string::String,
+    /// Delete the snapshot even if incremental snapshots are based on it
+    #[prost(bool, optional, tag = "3")]
+    pub force: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CompactSnapshotRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Name of the incremental collection snapshot
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub snapshot_name: ::prost::alloc::string::String,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -11157,6 +12584,30 @@ pub mod snapshots_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Fold an incremental collection snapshot and its base snapshots into a full snapshot
+        pub async fn compact(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CompactSnapshotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateSnapshotResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Snapshots/Compact");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Snapshots", "Compact"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
         /// Create full storage snapshot
         pub async fn create_full(
             &mut self,
@@ -11273,6 +12724,15 @@ pub mod snapshots_server {
             tonic::Status,
         >;
         ///
+        /// Fold an incremental collection snapshot and its base snapshots into a full snapshot
+        async fn compact(
+            &self,
+            request: tonic::Request<super::CompactSnapshotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateSnapshotResponse>,
+            tonic::Status,
+        >;
+        ///
         /// Create full storage snapshot
         async fn create_full(
             &self,
@@ -11517,6 +12977,52 @@ pub mod snapshots_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Snapshots/Compact" => {
+                    #[allow(non_camel_case_types)]
+                    struct CompactSvc<T: Snapshots>(pub Arc<T>);
+                    impl<
+                        T: Snapshots,
+                    > tonic::server::UnaryService<super::CompactSnapshotRequest>
+                    for CompactSvc<T> {
+                        type Response = super::CreateSnapshotResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CompactSnapshotRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Snapshots>::compact(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CompactSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.Snapshots/CreateFull" => {
                     #[allow(non_camel_case_types)]
                     struct CreateFullSvc<T: Snapshots>(pub Arc<T>);
@@ -228,6 +228,16 @@ impl Validate for grpc::FieldCondition {
 
 impl Validate for grpc::Vector {
     fn validate(&self) -> Result<(), ValidationErrors> {
+        if self.document.is_some()
+            && (!self.data.is_empty() || self.indices.is_some() || self.vectors_count.is_some())
+        {
+            let mut errors = ValidationErrors::new();
+            errors.add(
+                "document",
+                ValidationError::new("`document` cannot be specified together with vector data"),
+            );
+            return Err(errors);
+        }
         match (&self.indices, self.vectors_count) {
             (Some(_), Some(_)) => {
                 let mut errors = ValidationErrors::new();
@@ -274,6 +284,11 @@ pub fn validate_u64_range_min_1(value: &Option<u64>) -> Result<(), ValidationErr
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(1), None))
 }
 
+/// Validate the value is in `[2, 10000]` or `None`.
+pub fn validate_u64_range_min_2_max_10000(value: &Option<u64>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(2), Some(10_000)))
+}
+
 /// Validate the value is in `[1, ]` or `None`.
 pub fn validate_u32_range_min_1(value: &Option<u32>) -> Result<(), ValidationError> {
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(1), None))
@@ -304,6 +319,23 @@ pub fn validate_f32_range_min_0_5_max_1(value: &Option<f32>) -> Result<(), Valid
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(0.5), Some(1.0)))
 }
 
+/// Validate the value is finite and in `[0.0, ]` or `None`.
+pub fn validate_f32_finite_non_negative(value: &Option<f32>) -> Result<(), ValidationError> {
+    value
+        .as_ref()
+        .map_or(Ok(()), common::validation::validate_finite_non_negative)
+}
+
+/// Validate the value is in `[0.0, 1.0]` or `None`.
+pub fn validate_f32_range_1(value: &Option<f32>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(0.0), Some(1.0)))
+}
+
+/// Validate the value is in `[1.0, ]` or `None`.
+pub fn validate_f32_range_min_1(value: &Option<f32>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(1.0), None))
+}
+
 /// Validate the value is in `[0.0, 1.0]` or `None`.
 pub fn validate_f64_range_1(value: &Option<f64>) -> Result<(), ValidationError> {
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(0.0), Some(1.0)))
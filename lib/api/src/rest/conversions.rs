@@ -1,6 +1,7 @@
+use segment::data_types::facets::FacetParams;
 use segment::data_types::order_by::OrderBy;
 
-use super::schema::{BatchVectorStruct, ScoredPoint, Vector, VectorStruct};
+use super::schema::{BatchVectorStruct, FacetRequestInternal, ScoredPoint, Vector, VectorStruct};
 use super::{OrderByInterface, Query, QueryInterface};
 use crate::rest::{DenseVector, NamedVectorStruct};
 
@@ -28,6 +29,12 @@ impl From<Vector> for segment::data_types::vectors::Vector {
                     segment::data_types::vectors::MultiDenseVector::new_unchecked(vector),
                 )
             }
+            Vector::Document(_) => {
+                // Documents are replaced with inferred vectors by the collection
+                // before the operation reaches the shards
+                debug_assert!(false, "Document must be inferred before conversion");
+                segment::data_types::vectors::Vector::Sparse(Default::default())
+            }
         }
     }
 }
@@ -191,3 +198,21 @@ impl From<QueryInterface> for Query {
         }
     }
 }
+
+impl From<FacetRequestInternal> for FacetParams {
+    fn from(value: FacetRequestInternal) -> Self {
+        let FacetRequestInternal {
+            key,
+            limit,
+            filter,
+            exact,
+        } = value;
+
+        Self {
+            key,
+            limit: limit.unwrap_or(FacetParams::DEFAULT_LIMIT),
+            filter,
+            exact: exact.unwrap_or(false),
+        }
+    }
+}
@@ -5,7 +5,9 @@ use schemars::JsonSchema;
 use segment::common::utils::MaybeOneOrMany;
 use segment::data_types::order_by::OrderBy;
 use segment::json_path::JsonPath;
-use segment::types::{Filter, SearchParams, ShardKey, WithPayloadInterface, WithVector};
+use segment::types::{
+    ExtendedPointId, Filter, SearchParams, ShardKey, WithPayloadInterface, WithVector,
+};
 use serde::{Deserialize, Serialize};
 use sparse::common::sparse_vector::SparseVector;
 use validator::Validate;
@@ -22,6 +24,25 @@ pub enum Vector {
     Dense(DenseVector),
     Sparse(sparse::common::sparse_vector::SparseVector),
     MultiDense(MultiDenseVector),
+    Document(Document),
+}
+
+/// Model used to convert text documents into vectors on the server side
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentModel {
+    /// Sparse vector of BM25 term weights, requires `bm25` config of the sparse vector
+    Bm25,
+}
+
+/// Text document, which is converted into a vector by the server
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Document {
+    /// Text of the document
+    pub text: String,
+    /// Model used to convert the text into a vector
+    pub model: DocumentModel,
 }
 
 /// Full vector data per point separator with single and multiple vector modes
@@ -41,6 +62,7 @@ impl VectorStruct {
                 Vector::Dense(vector) => vector.is_empty(),
                 Vector::Sparse(vector) => vector.indices.is_empty(),
                 Vector::MultiDense(vector) => vector.is_empty(),
+                Vector::Document(document) => document.text.is_empty(),
             }),
         }
     }
@@ -160,6 +182,10 @@ pub enum OrderByInterface {
 pub enum Fusion {
     /// Reciprocal rank fusion
     Rrf,
+    /// Distribution-based score fusion, normalizes scores of each prefetch by mean ± 3 standard deviations, then sums them
+    Dbsf,
+    /// Min-max score fusion, normalizes scores of each prefetch by their min and max, then sums them
+    MinMax,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -169,6 +195,7 @@ pub enum VectorInput {
     SparseVector(SparseVector),
     MultiDenseVector(MultiDenseVector),
     Id(segment::types::PointIdType),
+    Document(Document),
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -217,6 +244,12 @@ pub struct QueryRequest {
     pub shard_key: Option<ShardKeySelector>,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct QueryRequestBatch {
+    #[validate]
+    pub searches: Vec<QueryRequest>,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum QueryInterface {
@@ -273,6 +306,10 @@ pub struct Prefetch {
 
     /// Max number of points to return. Default is 10.
     pub limit: Option<usize>,
+
+    /// Weight of the results of this prefetch, if the parent query is a fusion. Default is 1.0
+    #[validate(custom = "common::validation::validate_finite_non_negative")]
+    pub weight: Option<ScoreType>,
 }
 
 /// How to use positive and negative examples to find the results, default is `average_vector`:
@@ -350,3 +387,81 @@ impl ContextPair {
         std::iter::once(&self.positive).chain(std::iter::once(&self.negative))
     }
 }
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct FacetRequestInternal {
+    /// Payload key to use for faceting.
+    pub key: JsonPath,
+
+    /// Max number of hits to return. Default is 10.
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+
+    /// Filter conditions - only consider points that satisfy these conditions.
+    #[validate]
+    pub filter: Option<Filter>,
+
+    /// Whether to do a more expensive exact count for each of the values in the facet. Default is false.
+    pub exact: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct FacetRequest {
+    #[validate]
+    #[serde(flatten)]
+    pub facet_request: FacetRequestInternal,
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixRequestInternal {
+    /// Look only for points which satisfies this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+
+    /// How many points to select and search within. Default is 10.
+    #[validate(range(min = 2, max = 10000))]
+    pub sample: Option<usize>,
+
+    /// How many neighbours per sample to find. Default is 3.
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+
+    /// Define which vector name to use for querying. If missing, the default vector is used.
+    pub using: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct SearchMatrixRequest {
+    #[validate]
+    #[serde(flatten)]
+    pub search_request: SearchMatrixRequestInternal,
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SearchMatrixOffsetsResponse {
+    /// Row indices of the matrix
+    pub offsets_row: Vec<u64>,
+    /// Column indices of the matrix
+    pub offsets_col: Vec<u64>,
+    /// Scores associated with matrix coordinates
+    pub scores: Vec<ScoreType>,
+    /// Ids of the points in order
+    pub ids: Vec<ExtendedPointId>,
+}
+
+/// Pair of points (a, b) with score
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SearchMatrixPair {
+    pub a: ExtendedPointId,
+    pub b: ExtendedPointId,
+    pub score: ScoreType,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SearchMatrixPairsResponse {
+    /// List of pairs of points with scores
+    pub pairs: Vec<SearchMatrixPair>,
+}
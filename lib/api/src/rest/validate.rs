@@ -33,6 +33,7 @@ impl Validate for Vector {
             Vector::Dense(_) => Ok(()),
             Vector::Sparse(v) => v.validate(),
             Vector::MultiDense(m) => common::validation::validate_multi_vector(m),
+            Vector::Document(_) => Ok(()),
         }
     }
 }
@@ -76,6 +77,7 @@ impl Validate for VectorInput {
             VectorInput::DenseVector(_dense) => Ok(()),
             VectorInput::SparseVector(sparse) => sparse.validate(),
             VectorInput::MultiDenseVector(multi) => validate_multi_vector(multi),
+            VectorInput::Document(_document) => Ok(()),
         }
     }
 }
@@ -117,7 +119,7 @@ impl Validate for ContextInput {
 impl Validate for Fusion {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
-            Fusion::Rrf => Ok(()),
+            Fusion::Rrf | Fusion::Dbsf | Fusion::MinMax => Ok(()),
         }
     }
 }
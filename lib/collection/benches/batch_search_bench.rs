@@ -75,10 +75,12 @@ fn batch_search_bench(c: &mut Criterion) {
             indexing_threshold: Some(50_000),
             flush_interval_sec: 30,
             max_optimization_threads: Some(2),
+            graph_healing_threshold: None,
         },
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: None,
     };
 
     let optimizers_config = collection_config.optimizer_config.clone();
@@ -0,0 +1,40 @@
+use tokio::sync::watch;
+
+use super::Collection;
+use crate::operations::change_ops::{ChangesRequest, ChangesResult};
+use crate::operations::types::CollectionResult;
+use crate::shards::shard::ShardId;
+use crate::shards::shard_holder::shard_not_found_error;
+
+impl Collection {
+    /// Read update operations of the local shard `shard_id` in the order they were applied.
+    ///
+    /// Also returns a receiver, which is notified when new changes are written to the shard WAL.
+    /// The receiver does not hold any locks, so it is safe to wait on it for a long time.
+    pub async fn read_shard_changes(
+        &self,
+        shard_id: ShardId,
+        request: &ChangesRequest,
+    ) -> CollectionResult<(ChangesResult, watch::Receiver<u64>)> {
+        let shard_holder = self.shards_holder.read().await;
+        let replica_set = shard_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+        replica_set.read_changes(request).await
+    }
+
+    /// Unregister a change consumer of the local shard `shard_id`.
+    ///
+    /// Returns `false` if there was no such consumer.
+    pub async fn remove_shard_change_consumer(
+        &self,
+        shard_id: ShardId,
+        consumer: &str,
+    ) -> CollectionResult<bool> {
+        let shard_holder = self.shards_holder.read().await;
+        let replica_set = shard_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+        replica_set.remove_change_consumer(consumer).await
+    }
+}
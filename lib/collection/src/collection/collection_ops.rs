@@ -7,9 +7,13 @@ use segment::types::QuantizationConfig;
 use semver::Version;
 
 use super::Collection;
+use crate::config::StrictModeConfig;
 use crate::operations::config_diff::*;
+use crate::operations::document_inference::DocumentInference;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
+use crate::operations::verification::StrictModeVerification;
+use crate::operations::CollectionUpdateOperations;
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::replica_set::{Change, ReplicaState};
 use crate::shards::shard::PeerId;
@@ -110,6 +114,61 @@ impl Collection {
         Ok(())
     }
 
+    /// Updates strict mode config:
+    /// Saves new params on disk
+    ///
+    /// Limits are checked on each request, so optimizers don't need to be recreated.
+    pub async fn update_strict_mode_config(
+        &self,
+        strict_mode_diff: StrictModeConfig,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            let strict_mode_config = config.strict_mode_config.clone().unwrap_or_default();
+            config.strict_mode_config = Some(strict_mode_diff.update(&strict_mode_config)?);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Check the request against the strict mode limits of the collection, if there are any
+    ///
+    /// Read requests are checked at the entry points, as they are received from the user.
+    pub async fn check_strict_mode(
+        &self,
+        request: &impl StrictModeVerification,
+    ) -> CollectionResult<()> {
+        let Some(strict_mode_config) = self
+            .collection_config
+            .read()
+            .await
+            .strict_mode_config
+            .clone()
+        else {
+            return Ok(());
+        };
+        let payload_index_schema = self.payload_index_schema.read();
+        request.check_strict_mode(&strict_mode_config, &payload_index_schema.schema)
+    }
+
+    /// Convert text documents of the request into sparse vectors, using the collection config
+    pub(crate) async fn infer_documents(
+        &self,
+        request: &mut impl DocumentInference,
+    ) -> CollectionResult<()> {
+        let collection_config = self.collection_config.read().await;
+        request.infer_documents(&collection_config.params)
+    }
+
+    /// Reject vectors of the operation, which fail the validation configured in the collection
+    pub(crate) async fn check_vectors_validation(
+        &self,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        let collection_config = self.collection_config.read().await;
+        operation.check_vectors_validation(&collection_config.params)
+    }
+
     /// Updates shard optimization params: Saves new params on disk
     ///
     /// After this, `recreate_optimizers_blocking` must be called to create new optimizers using
@@ -0,0 +1,340 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use api::grpc::qdrant as grpc;
+use api::rest::{
+    SearchMatrixOffsetsResponse, SearchMatrixPair, SearchMatrixPairsResponse,
+    SearchMatrixRequestInternal,
+};
+use segment::data_types::vectors::{NamedVectorStruct, DEFAULT_VECTOR_NAME};
+use segment::types::{
+    Condition, Filter, HasIdCondition, PointIdType, ScoredPoint, WithPayloadInterface, WithVector,
+};
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
+};
+use crate::operations::universal_query::shard_query::{Sample, ScoringQuery, ShardQueryRequest};
+
+/// Internal representation of a distance matrix request
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectionSearchMatrixRequest {
+    /// Amount of points to sample
+    pub sample_size: usize,
+    /// Amount of nearest neighbours to find among the sample, for each sampled point
+    pub limit_per_sample: usize,
+    pub filter: Option<Filter>,
+    pub using: String,
+}
+
+impl CollectionSearchMatrixRequest {
+    pub const DEFAULT_SAMPLE: usize = 10;
+    pub const DEFAULT_LIMIT_PER_SAMPLE: usize = 3;
+}
+
+impl From<SearchMatrixRequestInternal> for CollectionSearchMatrixRequest {
+    fn from(request: SearchMatrixRequestInternal) -> Self {
+        let SearchMatrixRequestInternal {
+            filter,
+            sample,
+            limit,
+            using,
+        } = request;
+
+        Self {
+            sample_size: sample.unwrap_or(Self::DEFAULT_SAMPLE),
+            limit_per_sample: limit.unwrap_or(Self::DEFAULT_LIMIT_PER_SAMPLE),
+            filter,
+            using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string()),
+        }
+    }
+}
+
+/// Nearest neighbours of each sampled point, among the other sampled points
+#[derive(Debug, Default)]
+pub struct CollectionSearchMatrixResponse {
+    /// Sampled point ids, sorted
+    pub sample_ids: Vec<PointIdType>,
+    /// Nearest neighbours of each sampled point, in the same order as `sample_ids`
+    pub nearests: Vec<Vec<ScoredPoint>>,
+}
+
+impl From<CollectionSearchMatrixResponse> for SearchMatrixPairsResponse {
+    fn from(response: CollectionSearchMatrixResponse) -> Self {
+        let CollectionSearchMatrixResponse {
+            sample_ids,
+            nearests,
+        } = response;
+
+        let pairs = sample_ids
+            .into_iter()
+            .zip(nearests)
+            .flat_map(|(a, nearest)| {
+                nearest.into_iter().map(move |point| SearchMatrixPair {
+                    a,
+                    b: point.id,
+                    score: point.score,
+                })
+            })
+            .collect();
+
+        Self { pairs }
+    }
+}
+
+impl From<CollectionSearchMatrixResponse> for SearchMatrixOffsetsResponse {
+    fn from(response: CollectionSearchMatrixResponse) -> Self {
+        let CollectionSearchMatrixResponse {
+            sample_ids,
+            nearests,
+        } = response;
+
+        let mut offsets_row = Vec::new();
+        let mut offsets_col = Vec::new();
+        let mut scores = Vec::new();
+
+        for (row, nearest) in nearests.into_iter().enumerate() {
+            for point in nearest {
+                // Neighbours are always among the sample, which is sorted
+                let Ok(col) = sample_ids.binary_search(&point.id) else {
+                    debug_assert!(false, "neighbour {} is not part of the sample", point.id);
+                    continue;
+                };
+
+                offsets_row.push(row as u64);
+                offsets_col.push(col as u64);
+                scores.push(point.score);
+            }
+        }
+
+        Self {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids: sample_ids,
+        }
+    }
+}
+
+impl From<CollectionSearchMatrixResponse> for grpc::SearchMatrixPairs {
+    fn from(response: CollectionSearchMatrixResponse) -> Self {
+        let SearchMatrixPairsResponse { pairs } = SearchMatrixPairsResponse::from(response);
+
+        Self {
+            pairs: pairs
+                .into_iter()
+                .map(|pair| grpc::SearchMatrixPair {
+                    a: Some(pair.a.into()),
+                    b: Some(pair.b.into()),
+                    score: pair.score,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<CollectionSearchMatrixResponse> for grpc::SearchMatrixOffsets {
+    fn from(response: CollectionSearchMatrixResponse) -> Self {
+        let SearchMatrixOffsetsResponse {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids,
+        } = SearchMatrixOffsetsResponse::from(response);
+
+        Self {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids: ids.into_iter().map(grpc::PointId::from).collect(),
+        }
+    }
+}
+
+impl Collection {
+    /// Sample points matching the filter, and find the nearest neighbours of each of them
+    /// among the other sampled points.
+    pub async fn search_points_matrix(
+        &self,
+        request: CollectionSearchMatrixRequest,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<CollectionSearchMatrixResponse> {
+        let CollectionSearchMatrixRequest {
+            sample_size,
+            limit_per_sample,
+            filter,
+            using,
+        } = request;
+
+        if sample_size == 0 || limit_per_sample == 0 {
+            return Ok(Default::default());
+        }
+
+        // Make sure the vector exists, works for dense, multi-dense and sparse vectors
+        self.collection_config
+            .read()
+            .await
+            .params
+            .get_distance(&using)?;
+
+        // Sample random points, along with the vector to compare them with
+        let sampling_query = ShardQueryRequest {
+            prefetches: vec![],
+            query: Some(ScoringQuery::Sample(Sample::Random)),
+            filter,
+            score_threshold: None,
+            limit: sample_size,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Selector(vec![using.clone()]),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        let mut sampled_points = self
+            .query_resolved(sampling_query, read_consistency, &shard_selection, timeout)
+            .await?;
+
+        // Points without the vector can't be compared
+        sampled_points.retain(|point| {
+            point
+                .vector
+                .as_ref()
+                .is_some_and(|vector| vector.get(&using).is_some())
+        });
+
+        // There are no pairs without at least 2 points
+        if sampled_points.len() < 2 {
+            return Ok(Default::default());
+        }
+
+        // Sort by id for a deterministic output, and to be able to look up the ids later
+        sampled_points.sort_unstable_by_key(|point| point.id);
+
+        let sample_ids: Vec<_> = sampled_points.iter().map(|point| point.id).collect();
+
+        // Only look for neighbours among the sampled points.
+        // All searches share the same filter, so they can be executed as one batch.
+        let sample_filter = Filter::new_must(Condition::HasId(HasIdCondition::from(
+            sample_ids.iter().copied().collect::<HashSet<_>>(),
+        )));
+
+        let searches = sampled_points
+            .into_iter()
+            .map(|point| {
+                let vector = point
+                    .vector
+                    .as_ref()
+                    .and_then(|vector| vector.get(&using))
+                    .map(|vector| vector.to_owned())
+                    .ok_or_else(|| {
+                        CollectionError::service_error(format!(
+                            "Sampled point {} has no vector `{using}`",
+                            point.id,
+                        ))
+                    })?;
+
+                Ok(CoreSearchRequest {
+                    query: QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+                        vector,
+                        using.clone(),
+                    )),
+                    filter: Some(sample_filter.clone()),
+                    params: None,
+                    // One more, as the sampled point itself is usually the nearest one
+                    limit: limit_per_sample + 1,
+                    offset: 0,
+                    with_payload: None,
+                    with_vector: None,
+                    score_threshold: None,
+                })
+            })
+            .collect::<CollectionResult<Vec<_>>>()?;
+
+        let mut nearests = self
+            .core_search_batch(
+                CoreSearchRequestBatch { searches },
+                read_consistency,
+                shard_selection,
+                timeout,
+            )
+            .await?;
+
+        for (nearest, sample_id) in nearests.iter_mut().zip(&sample_ids) {
+            if let Some(position) = nearest.iter().position(|point| point.id == *sample_id) {
+                nearest.remove(position);
+            } else {
+                // The point itself was not found, drop the extra neighbour instead
+                nearest.truncate(limit_per_sample);
+            }
+        }
+
+        Ok(CollectionSearchMatrixResponse {
+            sample_ids,
+            nearests,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scored_point(id: u64, score: f32) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    fn matrix_response() -> CollectionSearchMatrixResponse {
+        CollectionSearchMatrixResponse {
+            sample_ids: vec![1.into(), 2.into(), 3.into()],
+            nearests: vec![
+                vec![scored_point(3, 0.9), scored_point(2, 0.5)],
+                vec![scored_point(1, 0.5)],
+                vec![scored_point(1, 0.9)],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_matrix_pairs_output() {
+        let SearchMatrixPairsResponse { pairs } = matrix_response().into();
+
+        let pairs: Vec<_> = pairs
+            .into_iter()
+            .map(|pair| (pair.a, pair.b, pair.score))
+            .collect();
+
+        assert_eq!(
+            pairs,
+            vec![
+                (1.into(), 3.into(), 0.9),
+                (1.into(), 2.into(), 0.5),
+                (2.into(), 1.into(), 0.5),
+                (3.into(), 1.into(), 0.9),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matrix_offsets_output() {
+        let response = SearchMatrixOffsetsResponse::from(matrix_response());
+
+        assert_eq!(response.offsets_row, vec![0, 0, 1, 2]);
+        assert_eq!(response.offsets_col, vec![2, 1, 0, 0]);
+        assert_eq!(response.scores, vec![0.9, 0.5, 0.5, 0.9]);
+        assert_eq!(response.ids, vec![1.into(), 2.into(), 3.into()]);
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream::FuturesUnordered;
+use futures::TryStreamExt as _;
+use segment::data_types::facets::{FacetParams, FacetResponse};
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::CollectionResult;
+
+impl Collection {
+    pub async fn facet(
+        &self,
+        request: FacetParams,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        if request.limit == 0 {
+            return Ok(FacetResponse::default());
+        }
+
+        let request = Arc::new(request);
+
+        let shards_holder = self.shards_holder.read().await;
+        let shards = shards_holder.select_shards(&shard_selection)?;
+
+        let mut requests: FuturesUnordered<_> = shards
+            .into_iter()
+            // `facet` requests received through internal gRPC *always* have `shard_selection`
+            .map(|(shard, _shard_key)| {
+                shard.facet(
+                    Arc::clone(&request),
+                    read_consistency,
+                    shard_selection.is_shard_id(),
+                    timeout,
+                )
+            })
+            .collect();
+
+        let mut responses = Vec::new();
+        while let Some(response) = requests.try_next().await? {
+            responses.push(response);
+        }
+
+        // Exact counts of an internal request are truncated by the node which received the
+        // original request, after merging them with the counts of the other shards
+        let limit = if request.exact && shard_selection.is_shard_id() {
+            usize::MAX
+        } else {
+            request.limit
+        };
+
+        // Counts of the shards are disjoint, so they are summed up
+        Ok(FacetResponse::merge(responses, limit))
+    }
+}
@@ -1,4 +1,7 @@
+mod changes;
 mod collection_ops;
+pub mod distance_matrix;
+mod facet;
 pub mod payload_index_schema;
 mod point_ops;
 pub mod query;
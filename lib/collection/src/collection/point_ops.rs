@@ -131,12 +131,15 @@ impl Collection {
     /// This method is cancel safe.
     pub async fn update_from_client(
         &self,
-        operation: CollectionUpdateOperations,
+        mut operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
         shard_keys_selection: Option<ShardKey>,
     ) -> CollectionResult<UpdateResult> {
         operation.validate()?;
+        self.infer_documents(&mut operation).await?;
+        self.check_vectors_validation(&operation).await?;
+        self.check_strict_mode(&operation).await?;
 
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
@@ -211,6 +214,8 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
     ) -> CollectionResult<ScrollResult> {
+        self.check_strict_mode(&request).await?;
+
         let default_request = ScrollRequestInternal::default();
 
         let id_offset = request.offset;
@@ -355,6 +360,8 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
     ) -> CollectionResult<CountResult> {
+        self.check_strict_mode(&request).await?;
+
         let shards_holder = self.shards_holder.read().await;
         let shards = shards_holder.select_shards(shard_selection)?;
 
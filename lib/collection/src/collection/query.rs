@@ -1,8 +1,8 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures::{future, TryFutureExt};
 use itertools::{Either, Itertools};
-use segment::common::reciprocal_rank_fusion::rrf_scoring;
 use segment::types::{Order, ScoredPoint};
 use segment::utils::scored_point_ties::ScoredPointTies;
 use tokio::time::Instant;
@@ -15,7 +15,7 @@ use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::universal_query::collection_query::CollectionQueryRequest;
 use crate::operations::universal_query::shard_query::{
-    Fusion, ScoringQuery, ShardQueryRequest, ShardQueryResponse,
+    fusion_weights, ScoringQuery, ShardQueryRequest, ShardQueryResponse,
 };
 
 struct IntermediateQueryInfo<'a> {
@@ -31,6 +31,7 @@ impl Collection {
         request: Arc<ShardQueryRequest>,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         // query all shards concurrently
         let shard_holder = self.shards_holder.read().await;
@@ -42,6 +43,7 @@ impl Collection {
                     Arc::clone(&request),
                     read_consistency,
                     shard_selection.is_shard_id(),
+                    timeout,
                 )
                 .and_then(move |mut records| async move {
                     if shard_key.is_none() {
@@ -63,11 +65,13 @@ impl Collection {
     /// This function is used to query the collection. It will return a list of scored points.
     pub async fn query(
         &self,
-        request: CollectionQueryRequest,
+        mut request: CollectionQueryRequest,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
     ) -> CollectionResult<Vec<ScoredPoint>> {
-        let instant = Instant::now();
+        // Turn documents into vectors, if necessary
+        self.infer_documents(&mut request).await?;
 
         // Turn ids into vectors, if necessary
         let ids_to_vectors = resolve_referenced_vectors_batch(
@@ -78,10 +82,66 @@ impl Collection {
         )
         .await?;
 
-        let request = Arc::new(request.try_into_shard_request(&ids_to_vectors)?);
+        let request = request.try_into_shard_request(&ids_to_vectors)?;
+
+        self.query_resolved(request, read_consistency, shard_selection, timeout)
+            .await
+    }
+
+    /// Same as [`Collection::query`], but for a batch of requests.
+    ///
+    /// Referenced vectors of all requests are resolved at once, then each request is executed concurrently.
+    pub async fn query_batch(
+        &self,
+        mut requests_batch: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        // Turn documents into vectors, if necessary
+        for (request, _) in requests_batch.iter_mut() {
+            self.infer_documents(request).await?;
+        }
+
+        // Turn ids into vectors, if necessary
+        let requests_refs = requests_batch
+            .iter()
+            .map(|(request, shard_selection)| (request, shard_selection.clone()))
+            .collect_vec();
+
+        let ids_to_vectors = resolve_referenced_vectors_batch(
+            &requests_refs,
+            self,
+            |_| async { unimplemented!("lookup_from is not implemented yet") },
+            read_consistency,
+        )
+        .await?;
+
+        let ids_to_vectors = &ids_to_vectors;
+        let futures = requests_batch
+            .into_iter()
+            .map(|(request, shard_selection)| async move {
+                let request = request.try_into_shard_request(ids_to_vectors)?;
+                self.query_resolved(request, read_consistency, &shard_selection, timeout)
+                    .await
+            });
+
+        future::try_join_all(futures).await
+    }
+
+    /// Executes a query which already has all of its point ids resolved into vectors.
+    pub(crate) async fn query_resolved(
+        &self,
+        request: ShardQueryRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let instant = Instant::now();
+
+        let request = Arc::new(request);
 
         let all_shards_results = self
-            .query_shards_concurrently(request.clone(), read_consistency, shard_selection)
+            .query_shards_concurrently(request.clone(), read_consistency, shard_selection, timeout)
             .await?;
 
         let mut merged_intermediates = self
@@ -90,9 +150,7 @@ impl Collection {
 
         let result = if let Some(ScoringQuery::Fusion(fusion)) = &request.query {
             // If the root query is a Fusion, the returned results correspond to each the prefetches.
-            match fusion {
-                Fusion::Rrf => rrf_scoring(merged_intermediates),
-            }
+            fusion.fuse(merged_intermediates, &fusion_weights(&request.prefetches))
         } else {
             // Otherwise, it will be a list with a single list of scored points.
             debug_assert_eq!(merged_intermediates.len(), 1);
@@ -103,12 +161,17 @@ impl Collection {
             })?
         };
 
-        let result: Vec<_> = result
+        let mut result: Vec<_> = result
             .into_iter()
             .skip(request.offset)
             .take(request.limit)
             .collect();
 
+        // Sampling keys are only used to merge the samples of the shards, don't expose them
+        if let Some(ScoringQuery::Sample(_)) = &request.query {
+            result.iter_mut().for_each(|point| point.score = 0.0);
+        }
+
         let filter_refs = request.filter_refs();
         self.post_process_if_slow_request(instant.elapsed(), filter_refs);
 
@@ -124,13 +187,19 @@ impl Collection {
         request: ShardQueryRequest,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
     ) -> CollectionResult<ShardQueryResponse> {
         let request = Arc::new(request);
 
         // Results from all shards
         // Shape: [num_shards, num_internal_queries, num_scored_points]
         let all_shards_results = self
-            .query_shards_concurrently(Arc::clone(&request), read_consistency, shard_selection)
+            .query_shards_concurrently(
+                Arc::clone(&request),
+                read_consistency,
+                shard_selection,
+                timeout,
+            )
             .await?;
 
         let merged = self
@@ -7,6 +7,9 @@ use common::defaults;
 use parking_lot::Mutex;
 
 use super::Collection;
+use crate::events::{
+    ShardTransferAbortedEvent, ShardTransferFinishedEvent, ShardTransferStartedEvent,
+};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::replica_set::ReplicaState;
@@ -115,6 +118,12 @@ impl Collection {
 
             is_local && is_sender
         };
+
+        issues::publish(ShardTransferStartedEvent {
+            collection_id: self.id.clone(),
+            transfer: shard_transfer.clone(),
+        });
+
         if do_transfer {
             self.send_shard(shard_transfer, consensus, temp_dir, on_finish, on_error)
                 .await;
@@ -238,6 +247,12 @@ impl Collection {
         }
         let finish_was_registered = shard_holder.register_finish_transfer(&transfer.key())?;
         log::debug!("finish_was_registered: {finish_was_registered}");
+
+        issues::publish(ShardTransferFinishedEvent {
+            collection_id: self.id.clone(),
+            transfer,
+        });
+
         Ok(())
     }
 
@@ -293,6 +308,11 @@ impl Collection {
 
         shard_holder.register_abort_transfer(&transfer_key)?;
 
+        issues::publish(ShardTransferAbortedEvent {
+            collection_id: self.id.clone(),
+            transfer,
+        });
+
         Ok(())
     }
 
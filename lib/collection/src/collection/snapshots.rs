@@ -7,8 +7,10 @@ use tokio::fs;
 
 use super::Collection;
 use crate::collection::CollectionVersion;
+use crate::common::snapshot_manifest::{
+    archive_snapshot_dir, unpack_snapshot_chain, SnapshotManifest,
+};
 use crate::common::snapshots_manager::SnapshotStorageManager;
-use crate::common::validate_snapshot_archive::validate_open_snapshot_archive;
 use crate::config::{CollectionConfig, ShardingMethod};
 use crate::operations::snapshot_ops::SnapshotDescription;
 use crate::operations::types::{CollectionError, CollectionResult, NodeType};
@@ -20,6 +22,9 @@ use crate::shards::shard_config::{self, ShardConfig};
 use crate::shards::shard_holder::{ShardKeyMapping, SHARD_KEY_MAPPING_FILE};
 use crate::shards::shard_versioning;
 
+/// Suffix of a compacted snapshot, while it is stored and has not replaced the original one yet
+const COMPACTING_SNAPSHOT_SUFFIX: &str = ".compacting";
+
 impl Collection {
     pub fn get_snapshots_storage_manager(&self) -> CollectionResult<SnapshotStorageManager> {
         SnapshotStorageManager::new(self.shared_storage_config.snapshots_config.clone())
@@ -47,6 +52,48 @@ impl Collection {
         &self,
         global_temp_dir: &Path,
         this_peer_id: PeerId,
+    ) -> CollectionResult<SnapshotDescription> {
+        self.create_snapshot_with_base(global_temp_dir, this_peer_id, None)
+            .await
+    }
+
+    /// Creates an incremental snapshot of the collection, based on the stored snapshot `base`.
+    ///
+    /// Only segments, which were added or changed since the base snapshot, are archived together
+    /// with the WAL. The base snapshot, and its own bases, are required to restore the snapshot.
+    pub async fn create_incremental_snapshot(
+        &self,
+        global_temp_dir: &Path,
+        this_peer_id: PeerId,
+        base: &str,
+    ) -> CollectionResult<SnapshotDescription> {
+        let snapshot_manager = self.get_snapshots_storage_manager()?;
+        let base_path = snapshot_manager
+            .get_snapshot_path(&self.snapshots_path, base)
+            .await?;
+
+        let base_manifest = snapshot_manager
+            .read_snapshot_manifest(&base_path)
+            .await?
+            .ok_or_else(|| {
+                CollectionError::bad_request(format!(
+                    "Snapshot {base} was created without a manifest and can't be used as a base",
+                ))
+            })?;
+
+        self.create_snapshot_with_base(
+            global_temp_dir,
+            this_peer_id,
+            Some((base.to_string(), base_manifest)),
+        )
+        .await
+    }
+
+    async fn create_snapshot_with_base(
+        &self,
+        global_temp_dir: &Path,
+        this_peer_id: PeerId,
+        base: Option<(String, SnapshotManifest)>,
     ) -> CollectionResult<SnapshotDescription> {
         let snapshot_name = format!(
             "{}-{this_peer_id}-{}.snapshot",
@@ -54,6 +101,17 @@ impl Collection {
             chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S"),
         );
 
+        let (base_name, base_manifest) = base.unzip();
+        if base_name.as_ref() == Some(&snapshot_name) {
+            return Err(CollectionError::bad_request(format!(
+                "Snapshot {snapshot_name} can't be based on itself, try again later",
+            )));
+        }
+        let mut manifest = SnapshotManifest {
+            base: base_name,
+            shards: Default::default(),
+        };
+
         // Final location of snapshot
         let snapshot_path = self.snapshots_path.join(&snapshot_name);
         log::info!(
@@ -106,11 +164,15 @@ impl Collection {
 
                 // If node is listener, we can save whatever currently is in the storage
                 let save_wal = self.shared_storage_config.node_type != NodeType::Listener;
-                replica_set
+                let shard_base = base_manifest
+                    .as_ref()
+                    .and_then(|base_manifest| base_manifest.shards.get(shard_id));
+                let shard_manifest = replica_set
                     .create_snapshot(
                         snapshot_temp_temp_dir.path(),
                         &shard_snapshot_path,
                         save_wal,
+                        shard_base,
                     )
                     .await
                     .map_err(|err| {
@@ -119,6 +181,10 @@ impl Collection {
                             shard_snapshot_path.display()
                         ))
                     })?;
+
+                if let Some(shard_manifest) = shard_manifest {
+                    manifest.shards.insert(*shard_id, shard_manifest);
+                }
             }
         }
 
@@ -154,11 +220,12 @@ impl Collection {
         // Archive snapshot folder into a single file
         log::debug!("Archiving snapshot {snapshot_temp_target_dir_path:?}");
         let archiving = tokio::task::spawn_blocking(move || -> CollectionResult<_> {
-            let mut builder = tar::Builder::new(snapshot_temp_arc_file.as_file_mut());
             // archive recursively collection directory `snapshot_path_with_arc_extension` into `snapshot_path`
-            builder.append_dir_all(".", &snapshot_temp_target_dir_path)?;
-            builder.finish()?;
-            drop(builder);
+            archive_snapshot_dir(
+                &snapshot_temp_target_dir_path,
+                &manifest,
+                snapshot_temp_arc_file.as_file_mut(),
+            )?;
             // return ownership of the file
             Ok(snapshot_temp_arc_file)
         });
@@ -178,8 +245,89 @@ impl Collection {
             })
     }
 
+    /// Fold an incremental snapshot and the chain of its bases into a full snapshot.
+    ///
+    /// The snapshot is replaced under the same name, so snapshots based on it stay valid.
+    /// Base snapshots are kept, they can be deleted if nothing else is based on them.
+    pub async fn compact_snapshot(
+        &self,
+        snapshot_name: &str,
+        global_temp_dir: &Path,
+    ) -> CollectionResult<SnapshotDescription> {
+        let snapshot_manager = self.get_snapshots_storage_manager()?;
+        let snapshot_path = snapshot_manager
+            .get_snapshot_path(&self.snapshots_path, snapshot_name)
+            .await?;
+
+        let manifest = snapshot_manager
+            .read_snapshot_manifest(&snapshot_path)
+            .await?;
+        if manifest.map_or(true, |manifest| manifest.base.is_none()) {
+            return Err(CollectionError::bad_request(format!(
+                "Snapshot {snapshot_name} is already a full snapshot",
+            )));
+        }
+
+        log::info!("Compacting collection snapshot {snapshot_name}");
+
+        // Dedicated temporary directory for the snapshot chain, if it needs to be downloaded
+        let snapshot_temp_chain_dir = tempfile::Builder::new()
+            .prefix(&format!("{snapshot_name}-chain-"))
+            .tempdir_in(global_temp_dir)?;
+        let local_snapshot_path = snapshot_manager
+            .get_local_snapshot_chain(
+                &self.snapshots_path,
+                snapshot_name,
+                snapshot_temp_chain_dir.path(),
+            )
+            .await?;
+
+        let snapshot_temp_target_dir = tempfile::Builder::new()
+            .prefix(&format!("{snapshot_name}-target-"))
+            .tempdir_in(global_temp_dir)?;
+        let mut snapshot_temp_arc_file = tempfile::Builder::new()
+            .prefix(&format!("{snapshot_name}-arc-"))
+            .tempfile_in(global_temp_dir)?;
+
+        let snapshot_temp_target_dir_path = snapshot_temp_target_dir.path().to_path_buf();
+        let compacting = tokio::task::spawn_blocking(move || -> CollectionResult<_> {
+            let manifest =
+                unpack_snapshot_chain(&local_snapshot_path, &snapshot_temp_target_dir_path)?
+                    .unwrap_or_default();
+            archive_snapshot_dir(
+                &snapshot_temp_target_dir_path,
+                &manifest,
+                snapshot_temp_arc_file.as_file_mut(),
+            )?;
+            Ok(snapshot_temp_arc_file)
+        });
+        snapshot_temp_arc_file = compacting.await?.map_err(|err| {
+            CollectionError::service_error(format!(
+                "failed to compact snapshot {snapshot_name}: {err}"
+            ))
+        })?;
+
+        // Store the compacted snapshot under a temporary name, and replace the snapshot only
+        // once it is stored completely
+        let compacted_snapshot_path =
+            snapshot_path.with_file_name(format!("{snapshot_name}{COMPACTING_SNAPSHOT_SUFFIX}"));
+        let snapshot_description = snapshot_manager
+            .store_file(snapshot_temp_arc_file.path(), &compacted_snapshot_path)
+            .await?;
+        snapshot_manager
+            .rename_snapshot(&compacted_snapshot_path, &snapshot_path)
+            .await?;
+
+        Ok(SnapshotDescription {
+            name: snapshot_name.to_string(),
+            ..snapshot_description
+        })
+    }
+
     /// Restore collection from snapshot
     ///
+    /// Base snapshots of an incremental snapshot must be next to it.
+    ///
     /// This method performs blocking IO.
     pub fn restore_snapshot(
         snapshot_path: &Path,
@@ -187,9 +335,8 @@ impl Collection {
         this_peer_id: PeerId,
         is_distributed: bool,
     ) -> CollectionResult<()> {
-        // decompress archive
-        let mut ar = validate_open_snapshot_archive(snapshot_path)?;
-        ar.unpack(target_dir)?;
+        // decompress archive, with segments of incremental snapshot taken from its bases
+        unpack_snapshot_chain(snapshot_path, target_dir)?;
 
         let config = CollectionConfig::load(target_dir)?;
         config.validate_and_warn();
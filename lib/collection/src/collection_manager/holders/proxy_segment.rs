@@ -1,12 +1,15 @@
 use std::cmp;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use bitvec::prelude::BitVec;
 use common::types::{PointOffsetType, TelemetryDetail};
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use segment::common::operation_error::{OperationResult, SegmentFailedState};
+use segment::common::random_sample::{key_random_sample, merge_keyed_samples};
+use segment::data_types::facets::{FacetParams, FacetValue};
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::order_by::OrderValue;
 use segment::data_types::query_context::{QueryContext, SegmentQueryContext};
@@ -218,7 +221,7 @@ impl ProxySegment {
     /// This is required if making both the wrapped segment and the writable segment available in a
     /// shard holder at the same time. If the wrapped segment is thrown away, then this is not
     /// required.
-    pub(super) fn propagate_to_wrapped(&self) -> OperationResult<()> {
+    pub(crate) fn propagate_to_wrapped(&self) -> OperationResult<()> {
         // Important: we must not keep a write lock on the wrapped segment for the duration of this
         // function to prevent a deadlock. The search functions conflict with it trying to take a
         // read lock on the wrapped segment as well while already holding the deleted points lock
@@ -662,6 +665,82 @@ impl SegmentEntry for ProxySegment {
         Ok(read_points)
     }
 
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> Vec<PointIdType> {
+        let mut rng = rand::thread_rng();
+
+        let deleted_points = self.deleted_points.read();
+        let wrapped_filter = if deleted_points.is_empty() {
+            filter.cloned()
+        } else {
+            Some(self.add_deleted_points_condition_to_filter(filter, &deleted_points))
+        };
+        let wrapped_points = {
+            let wrapped_segment = self.wrapped_segment.get();
+            let wrapped_segment = wrapped_segment.read();
+            key_random_sample(
+                wrapped_segment.read_random_filtered(limit, wrapped_filter.as_ref()),
+                wrapped_segment
+                    .estimate_point_count(wrapped_filter.as_ref())
+                    .exp,
+                &mut rng,
+            )
+        };
+
+        let write_segment_points = {
+            let write_segment = self.write_segment.get();
+            let write_segment = write_segment.read();
+            key_random_sample(
+                write_segment.read_random_filtered(limit, filter),
+                write_segment.estimate_point_count(filter).exp,
+                &mut rng,
+            )
+        };
+
+        // Weight both samples by the amount of points in their segment, so that the result is
+        // uniform. A point may be present in both segments, keep it once.
+        let mut seen = HashSet::new();
+        merge_keyed_samples([wrapped_points, write_segment_points], usize::MAX)
+            .into_iter()
+            .map(|(point_id, _)| point_id)
+            .filter(|point_id| seen.insert(*point_id))
+            .take(limit)
+            .collect()
+    }
+
+    fn facet(
+        &self,
+        request: &FacetParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, usize>> {
+        let deleted_points = self.deleted_points.read();
+        let mut counts = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .facet(request, is_stopped)?
+        } else {
+            let wrapped_filter = self
+                .add_deleted_points_condition_to_filter(request.filter.as_ref(), &deleted_points);
+            let wrapped_request = FacetParams {
+                filter: Some(wrapped_filter),
+                ..request.clone()
+            };
+            self.wrapped_segment
+                .get()
+                .read()
+                .facet(&wrapped_request, is_stopped)?
+        };
+        let write_segment_counts = self.write_segment.get().read().facet(request, is_stopped)?;
+        for (value, count) in write_segment_counts {
+            *counts.entry(value).or_insert(0) += count;
+        }
+        Ok(counts)
+    }
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let deleted_points = self.deleted_points.read();
@@ -1536,6 +1615,8 @@ mod tests {
                         quantization_config: None,
                         multivec_config: None,
                         datatype: None,
+                        validation: None,
+                        normalize: None,
                     },
                 ),
                 (
@@ -1548,6 +1629,8 @@ mod tests {
                         quantization_config: None,
                         multivec_config: None,
                         datatype: None,
+                        validation: None,
+                        normalize: None,
                     },
                 ),
             ]),
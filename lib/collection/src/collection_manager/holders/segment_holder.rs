@@ -8,7 +8,7 @@ use std::thread::sleep;
 use std::time::{Duration, Instant};
 
 use io::storage_version::StorageVersion;
-use parking_lot::{RwLock, RwLockReadGuard, RwLockUpgradableReadGuard, RwLockWriteGuard};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockUpgradableReadGuard, RwLockWriteGuard};
 use rand::seq::SliceRandom;
 use segment::common::operation_error::{OperationError, OperationResult};
 use segment::entry::entry_point::SegmentEntry;
@@ -17,6 +17,7 @@ use segment::segment_constructor::build_segment;
 use segment::types::{PointIdType, SegmentConfig, SeqNumberType};
 
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
+use crate::common::snapshot_manifest::ShardManifest;
 use crate::config::CollectionParams;
 use crate::operations::types::CollectionError;
 use crate::shards::update_tracker::UpdateTracker;
@@ -1086,6 +1087,9 @@ impl<'s> SegmentHolder {
 
     /// Take a snapshot of all segments into `snapshot_dir_path`
     ///
+    /// Segments, which are in the `base` manifest with the same version, are not snapshotted again.
+    /// Returns versions of all segments, including the skipped ones.
+    ///
     /// It is recommended to provide collection parameters. This function internally creates a
     /// temporary segment, which will source the configuration from it.
     ///
@@ -1096,14 +1100,44 @@ impl<'s> SegmentHolder {
         collection_params: Option<&CollectionParams>,
         temp_dir: &Path,
         snapshot_dir_path: &Path,
-    ) -> OperationResult<()> {
+        base: Option<&ShardManifest>,
+    ) -> OperationResult<ShardManifest> {
+        let manifest = Mutex::new(ShardManifest::default());
+
         // Snapshotting may take long-running read locks on segments blocking incoming writes, do
         // this through proxied segments to allow writes to continue.
-        Self::proxy_all_segments_and_apply(segments, segments_path, collection_params, |segment| {
-            let read_segment = segment.read();
-            read_segment.take_snapshot(temp_dir, snapshot_dir_path)?;
-            Ok(())
-        })
+        Self::proxy_all_segments_and_apply(
+            segments,
+            segments_path,
+            collection_params,
+            |segment| {
+                let read_segment = segment.read();
+
+                let segment_name = read_segment
+                    .data_path()
+                    .file_stem()
+                    .and_then(|name| name.to_str())
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        OperationError::service_error(format!(
+                            "invalid segment path {:?}",
+                            read_segment.data_path(),
+                        ))
+                    })?;
+                let version = read_segment.version();
+
+                // Segment is not changed since the base snapshot
+                let unchanged = base.map_or(false, |base| base.contains(&segment_name, version));
+                if !unchanged {
+                    read_segment.take_snapshot(temp_dir, snapshot_dir_path)?;
+                }
+
+                manifest.lock().segments.insert(segment_name, version);
+                Ok(())
+            },
+        )?;
+
+        Ok(manifest.into_inner())
     }
 
     pub fn report_optimizer_error<E: Into<CollectionError>>(&mut self, error: E) {
@@ -1479,6 +1513,7 @@ mod tests {
             None,
             temp_dir.path(),
             snapshot_dir.path(),
+            None,
         )
         .unwrap();
 
@@ -1497,4 +1532,60 @@ mod tests {
         // one archive produced per concrete segment in the SegmentHolder
         assert_eq!(archive_count, 2);
     }
+
+    #[test]
+    fn test_snapshot_all_incremental() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segment1 = build_segment_1(dir.path());
+        let segment2 = build_segment_2(dir.path());
+
+        let mut holder = SegmentHolder::default();
+
+        let sid1 = holder.add_new(segment1);
+        holder.add_new(segment2);
+
+        let holder = Arc::new(RwLock::new(holder));
+
+        let segments_dir = Builder::new().prefix("segments_dir").tempdir().unwrap();
+        let temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
+
+        let snapshot = |base: Option<&ShardManifest>| {
+            let snapshot_dir = Builder::new().prefix("snapshot_dir").tempdir().unwrap();
+            let manifest = SegmentHolder::snapshot_all_segments(
+                holder.clone(),
+                segments_dir.path(),
+                None,
+                temp_dir.path(),
+                snapshot_dir.path(),
+                base,
+            )
+            .unwrap();
+            let archive_count = read_dir(&snapshot_dir).unwrap().count();
+            (manifest, archive_count)
+        };
+
+        let (full, archive_count) = snapshot(None);
+        assert_eq!(full.segments.len(), 2);
+        assert_eq!(archive_count, 2);
+
+        // Nothing changed, nothing to archive
+        let (unchanged, archive_count) = snapshot(Some(&full));
+        assert_eq!(unchanged, full);
+        assert_eq!(archive_count, 0);
+
+        holder
+            .read()
+            .get(sid1)
+            .unwrap()
+            .get()
+            .write()
+            .delete_point(100, 1.into())
+            .unwrap();
+
+        // Only the changed segment is archived
+        let (changed, archive_count) = snapshot(Some(&full));
+        assert_eq!(changed.segments.len(), 2);
+        assert_ne!(changed, full);
+        assert_eq!(archive_count, 1);
+    }
 }
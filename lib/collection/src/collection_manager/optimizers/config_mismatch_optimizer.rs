@@ -6,7 +6,10 @@ use std::sync::Arc;
 use parking_lot::Mutex;
 use segment::common::operation_time_statistics::OperationDurationsAggregator;
 use segment::index::sparse_index::sparse_index_config::SparseIndexType;
-use segment::types::{HnswConfig, Indexes, QuantizationConfig, SegmentType};
+use segment::types::{
+    HnswConfig, Indexes, QuantizationConfig, SegmentType, SparseVectorStorageType,
+    VectorStorageDatatype,
+};
 
 use crate::collection_manager::holders::segment_holder::{LockedSegmentHolder, SegmentId};
 use crate::collection_manager::optimizers::segment_optimizer::{
@@ -69,6 +72,26 @@ impl ConfigMismatchOptimizer {
             .and_then(|index| index.on_disk)
     }
 
+    /// Get the storage type configured for the sparse vectors
+    fn get_sparse_vector_storage_type(&self, vector_name: &str) -> Option<SparseVectorStorageType> {
+        self.collection_params
+            .sparse_vectors
+            .as_ref()
+            .and_then(|vector_params| vector_params.get(vector_name))
+            .map(|params| params.storage_type.unwrap_or_default())
+    }
+
+    /// Get the datatype explicitly configured for the sparse vectors index, if any
+    fn get_sparse_vector_index_datatype(&self, vector_name: &str) -> Option<VectorStorageDatatype> {
+        self.collection_params
+            .sparse_vectors
+            .as_ref()
+            .and_then(|vector_params| vector_params.get(vector_name))
+            .and_then(|params| params.index)
+            .and_then(|index| index.datatype)
+            .map(VectorStorageDatatype::from)
+    }
+
     /// Calculates and HNSW config that should be used for a given vector
     /// with current configuration.
     ///
@@ -120,6 +143,8 @@ impl ConfigMismatchOptimizer {
                     return None; // Never optimize already optimized segment
                 }
 
+                // Payloads in RocksDB are not a mismatch on their own, they are moved into the mmap
+                // storage once the segment is rebuilt by any optimization
                 if self.collection_params.on_disk_payload
                     != segment_config.payload_storage_type.is_on_disk()
                 {
@@ -186,17 +211,38 @@ impl ConfigMismatchOptimizer {
                         .sparse_vector_data
                         .iter()
                         .any(|(vector_name, vector_data)| {
-                            let Some(is_required_on_disk) =
-                                self.check_if_sparse_vectors_index_on_disk(vector_name)
-                            else {
-                                return false; // Do nothing if not specified
-                            };
-
-                            match vector_data.index.index_type {
-                                SparseIndexType::MutableRam => false, // Do nothing for mutable RAM
-                                SparseIndexType::ImmutableRam => is_required_on_disk, // Rebuild if we require on disk
-                                SparseIndexType::Mmap => !is_required_on_disk, // Rebuild if we require in RAM
+                            // Rebuild if the storage type was changed, which moves the vectors into
+                            // the new storage, no matter the index type
+                            let storage_mismatch = self
+                                .get_sparse_vector_storage_type(vector_name)
+                                .map_or(false, |required_storage_type| {
+                                    vector_data.storage_type != required_storage_type
+                                });
+                            if storage_mismatch {
+                                return true;
                             }
+
+                            let index_type = vector_data.index.index_type;
+                            if index_type == SparseIndexType::MutableRam {
+                                return false; // Do nothing for mutable RAM
+                            }
+
+                            let on_disk_mismatch = self
+                                .check_if_sparse_vectors_index_on_disk(vector_name)
+                                .map_or(false, |is_required_on_disk| match index_type {
+                                    SparseIndexType::MutableRam => false,
+                                    SparseIndexType::ImmutableRam => is_required_on_disk, // Rebuild if we require on disk
+                                    SparseIndexType::Mmap => !is_required_on_disk, // Rebuild if we require in RAM
+                                });
+
+                            // Rebuild if the index weights are stored in another datatype
+                            let datatype_mismatch = self
+                                .get_sparse_vector_index_datatype(vector_name)
+                                .map_or(false, |required_datatype| {
+                                    vector_data.index.datatype != Some(required_datatype)
+                                });
+
+                            on_disk_mismatch || datatype_mismatch
                         });
                 (sparse_has_mismatch || dense_has_mismatch).then_some((*idx, vector_size))
             })
@@ -264,8 +310,8 @@ mod tests {
     use segment::entry::entry_point::SegmentEntry;
     use segment::index::hnsw_index::num_rayon_threads;
     use segment::types::{
-        CompressionRatio, Distance, ProductQuantization, ProductQuantizationConfig,
-        ScalarQuantizationConfig, ScalarType,
+        CompressionRatio, Distance, PayloadStorageType, ProductQuantization,
+        ProductQuantizationConfig, ScalarQuantizationConfig, ScalarType,
     };
     use tempfile::Builder;
 
@@ -748,4 +794,105 @@ mod tests {
                 );
             });
     }
+
+    /// Segments with RocksDB payload storage are not rebuilt just to migrate the payload storage
+    ///
+    /// Payloads are moved into the mmap storage once the segment is optimized for another reason.
+    /// The old segment, including its RocksDB data, is replaced only once the optimized segment
+    /// is built.
+    #[test]
+    fn test_rocksdb_payload_storage_lazy_migration() {
+        let (point_count, dim) = (100, 10);
+        let thresholds_config = OptimizerThresholds {
+            max_segment_size_kb: usize::MAX,
+            memmap_threshold_kb: usize::MAX,
+            indexing_threshold_kb: usize::MAX,
+        };
+        let collection_params = CollectionParams {
+            vectors: VectorsConfig::Single(
+                VectorParamsBuilder::new(dim as u64, Distance::Dot).build(),
+            ),
+            ..CollectionParams::empty()
+        };
+
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let mut holder = SegmentHolder::default();
+
+        let segment = random_segment(dir.path(), 100, point_count, dim as usize);
+        assert_eq!(
+            segment.config().payload_storage_type,
+            PayloadStorageType::InMemory,
+        );
+        let payloads: BTreeMap<_, _> = segment
+            .iter_points()
+            .map(|point_id| (point_id, segment.payload(point_id).unwrap()))
+            .collect();
+
+        holder.add_new(segment);
+        let locked_holder: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+        let hnsw_config = HnswConfig {
+            m: 16,
+            ef_construct: 100,
+            full_scan_threshold: 10,
+            max_indexing_threads: 0,
+            on_disk: None,
+            payload_m: None,
+        };
+        let config_mismatch_optimizer = ConfigMismatchOptimizer::new(
+            thresholds_config,
+            dir.path().to_owned(),
+            temp_dir.path().to_owned(),
+            collection_params.clone(),
+            hnsw_config.clone(),
+            Default::default(),
+        );
+
+        let suggested_to_optimize =
+            config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert!(suggested_to_optimize.is_empty());
+
+        // Optimization triggered by any other condition migrates the payloads
+        let suggested_to_optimize: Vec<_> =
+            locked_holder.read().iter().map(|(id, _)| *id).collect();
+        let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+        let permit = CpuPermit::dummy(permit_cpu_count as u32);
+        let changed = config_mismatch_optimizer
+            .optimize(
+                locked_holder.clone(),
+                suggested_to_optimize,
+                permit,
+                &false.into(),
+            )
+            .unwrap();
+        assert!(changed, "optimizer should have rebuilt this segment");
+
+        locked_holder
+            .read()
+            .iter()
+            .map(|(_, segment)| match segment {
+                LockedSegment::Original(s) => s.read(),
+                LockedSegment::Proxy(_) => unreachable!(),
+            })
+            .for_each(|segment| {
+                assert_eq!(
+                    segment.config().payload_storage_type,
+                    collection_params.payload_storage_type(),
+                );
+            });
+
+        let holder = locked_holder.read();
+        for (point_id, payload) in payloads {
+            let segment = holder
+                .iter()
+                .find_map(|(_, segment)| {
+                    let segment = segment.get();
+                    let has_point = segment.read().has_point(point_id);
+                    has_point.then_some(segment)
+                })
+                .expect("point must be kept by the optimized segment");
+            assert_eq!(segment.read().payload(point_id).unwrap(), payload);
+        }
+    }
 }
@@ -0,0 +1,438 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::cpu::CpuPermit;
+use io::storage_version::StorageVersion;
+use ordered_float::OrderedFloat;
+use parking_lot::{Mutex, RwLockUpgradableReadGuard};
+use segment::common::operation_error::check_process_stopped;
+use segment::common::operation_time_statistics::{
+    OperationDurationsAggregator, ScopeDurationMeasurer,
+};
+use segment::entry::entry_point::SegmentEntry;
+use segment::segment::SegmentVersion;
+use segment::types::{HnswConfig, QuantizationConfig, SegmentType};
+
+use crate::collection_manager::holders::proxy_segment::ProxySegment;
+use crate::collection_manager::holders::segment_holder::{
+    LockedSegment, LockedSegmentHolder, SegmentId,
+};
+use crate::collection_manager::optimizers::segment_optimizer::{
+    OptimizerThresholds, SegmentOptimizer,
+};
+use crate::config::CollectionParams;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Optimizer which repairs HNSW graphs of segments with many deleted vectors in place
+///
+/// Deleted vectors stay in the graph and are only skipped on search, so their neighbours
+/// gradually lose connectivity. Instead of rebuilding the segment, this optimizer drops deleted
+/// points from the graph and reconnects their neighbours with each other.
+///
+/// Segment is not copied, so healing is much cheaper than a vacuum of the same segment.
+pub struct GraphHealingOptimizer {
+    deleted_threshold: f64,
+    thresholds_config: OptimizerThresholds,
+    segments_path: PathBuf,
+    collection_temp_dir: PathBuf,
+    collection_params: CollectionParams,
+    hnsw_config: HnswConfig,
+    quantization_config: Option<QuantizationConfig>,
+    telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
+}
+
+impl GraphHealingOptimizer {
+    pub fn new(
+        deleted_threshold: f64,
+        thresholds_config: OptimizerThresholds,
+        segments_path: PathBuf,
+        collection_temp_dir: PathBuf,
+        collection_params: CollectionParams,
+        hnsw_config: HnswConfig,
+        quantization_config: Option<QuantizationConfig>,
+    ) -> Self {
+        GraphHealingOptimizer {
+            deleted_threshold,
+            thresholds_config,
+            segments_path,
+            collection_temp_dir,
+            collection_params,
+            hnsw_config,
+            quantization_config,
+            telemetry_durations_aggregator: OperationDurationsAggregator::new(),
+        }
+    }
+
+    /// Segment with the largest ratio of deleted vectors in its graphs, above the threshold
+    fn worst_segment(
+        &self,
+        segments: LockedSegmentHolder,
+        excluded_ids: &HashSet<SegmentId>,
+    ) -> Option<SegmentId> {
+        let segments_read_guard = segments.read();
+        segments_read_guard
+            .iter()
+            // Excluded externally, might already be scheduled for optimization
+            .filter(|(idx, _segment)| !excluded_ids.contains(idx))
+            .filter_map(|(idx, segment)| {
+                // We can only work with original segments
+                let LockedSegment::Original(segment) = segment else {
+                    return None;
+                };
+                let read_segment = segment.read();
+                // Never optimize special segments
+                if read_segment.segment_type() == SegmentType::Special {
+                    return None;
+                }
+                let deleted_ratio = read_segment.max_graph_deleted_ratio();
+                (deleted_ratio > self.deleted_threshold).then_some((*idx, deleted_ratio))
+            })
+            .max_by_key(|(_, ratio)| OrderedFloat(*ratio))
+            .map(|(idx, _)| idx)
+    }
+
+    /// Put the healed segment back in place of its proxy
+    ///
+    /// Changes collected by the proxy are propagated to the segment, temporary segment is added
+    /// to the collection if it has any points.
+    fn unwrap_healing_proxy(
+        &self,
+        segments: &LockedSegmentHolder,
+        segment_id: SegmentId,
+        proxy_id: SegmentId,
+        proxy_segment: LockedSegment,
+        tmp_segment: LockedSegment,
+    ) -> CollectionResult<()> {
+        let LockedSegment::Proxy(proxy_segment) = proxy_segment else {
+            unreachable!("segment under graph healing must be a proxy");
+        };
+
+        // Propagate most of the changes without blocking the collection
+        if let Err(err) = proxy_segment.read().propagate_to_wrapped() {
+            log::error!("Propagating proxy segment {proxy_id} changes to wrapped segment failed, ignoring: {err}");
+        }
+
+        let mut write_segments = segments.write();
+        let wrapped_segment = {
+            let proxy_segment = proxy_segment.read();
+            if let Err(err) = proxy_segment.propagate_to_wrapped() {
+                log::error!("Propagating proxy segment {proxy_id} changes to wrapped segment failed, ignoring: {err}");
+            }
+            proxy_segment.wrapped_segment.clone()
+        };
+        // Important: put the segment back with its original segment ID
+        let proxies = write_segments.swap_existing(segment_id, wrapped_segment, &[proxy_id]);
+
+        // Append a temp segment to collection if it is not empty or there is no other appendable segment
+        if tmp_segment.get().read().available_point_count() > 0
+            || !write_segments.has_appendable_segment()
+        {
+            write_segments.add_new_locked(tmp_segment);
+        } else {
+            // unlock collection for search and updates
+            drop(write_segments);
+            // Proxy contains pointer to the `tmp_segment`, so it should be released first
+            drop(proxies);
+            drop(proxy_segment);
+            tmp_segment.drop_data()?;
+        }
+        Ok(())
+    }
+}
+
+impl SegmentOptimizer for GraphHealingOptimizer {
+    fn name(&self) -> &str {
+        "graph_healing"
+    }
+
+    fn segments_path(&self) -> &Path {
+        self.segments_path.as_path()
+    }
+
+    fn temp_path(&self) -> &Path {
+        self.collection_temp_dir.as_path()
+    }
+
+    fn collection_params(&self) -> CollectionParams {
+        self.collection_params.clone()
+    }
+
+    fn hnsw_config(&self) -> &HnswConfig {
+        &self.hnsw_config
+    }
+
+    fn quantization_config(&self) -> Option<QuantizationConfig> {
+        self.quantization_config.clone()
+    }
+
+    fn threshold_config(&self) -> &OptimizerThresholds {
+        &self.thresholds_config
+    }
+
+    fn check_condition(
+        &self,
+        segments: LockedSegmentHolder,
+        excluded_ids: &HashSet<SegmentId>,
+    ) -> Vec<SegmentId> {
+        self.worst_segment(segments, excluded_ids)
+            .into_iter()
+            .collect()
+    }
+
+    fn get_telemetry_counter(&self) -> &Mutex<OperationDurationsAggregator> {
+        &self.telemetry_durations_aggregator
+    }
+
+    /// Heal graphs of the segments in place, without building a new segment
+    ///
+    /// Segment is wrapped into a proxy while its graphs are healed, so updates don't wait for
+    /// the healing. Only swapping the healed links in requires a short write lock.
+    fn optimize(
+        &self,
+        segments: LockedSegmentHolder,
+        ids: Vec<SegmentId>,
+        _permit: CpuPermit,
+        stopped: &AtomicBool,
+    ) -> CollectionResult<bool> {
+        check_process_stopped(stopped)?;
+
+        let mut timer = ScopeDurationMeasurer::new(self.get_telemetry_counter());
+        timer.set_success(false);
+
+        let mut healed_any = false;
+        for id in ids {
+            // Upgradable lock ensures, that the segment is not taken by another optimizer
+            // before it is wrapped into a proxy
+            let segments_lock = segments.upgradable_read();
+            let Some(LockedSegment::Original(segment)) = segments_lock.get(id).cloned() else {
+                // Segment is gone or under other optimization
+                continue;
+            };
+
+            // Persist deletes first, so the graph never misses points which are still
+            // present after a restart
+            segment.read().flush(true)?;
+
+            check_process_stopped(stopped)?;
+
+            let tmp_segment = self.temp_segment(false)?;
+            let mut proxy = ProxySegment::new(
+                LockedSegment::Original(segment.clone()),
+                tmp_segment.clone(),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+            );
+            // Write segment is fresh, so it has no operations
+            // Operation with number 0 will be applied
+            proxy.replicate_field_indexes(0)?;
+
+            // Save segment version once all payload indices have been converted
+            // If this ends up not being saved due to a crash, the segment will not be used
+            match &tmp_segment {
+                LockedSegment::Original(tmp_segment) => {
+                    SegmentVersion::save(&tmp_segment.read().current_path)?;
+                }
+                LockedSegment::Proxy(_) => unreachable!(),
+            }
+
+            let (proxy_id, proxy_segment) = {
+                let mut write_segments = RwLockUpgradableReadGuard::upgrade(segments_lock);
+                // Segment could have been changed in the gap between two calls
+                proxy.replicate_field_indexes(0)?;
+                let (proxy_id, _) = write_segments.swap_new(proxy, &[id]);
+                let proxy_segment = write_segments
+                    .get(proxy_id)
+                    .cloned()
+                    .expect("failed to get segment from segment holder we just swapped in");
+                (proxy_id, proxy_segment)
+            };
+
+            // ---- SLOW PART -----
+
+            // Wrapped segment is not changed by updates, so the read lock doesn't block them
+            let healed_graphs = segment
+                .read()
+                .heal_graphs(self.deleted_threshold, stopped)
+                .map_err(CollectionError::from)
+                .and_then(|healed_graphs| {
+                    check_process_stopped(stopped)?;
+                    Ok(healed_graphs)
+                });
+
+            // ---- SLOW PART ENDS HERE -----
+
+            let healed = match healed_graphs {
+                Ok(healed_graphs) if !healed_graphs.is_empty() => segment
+                    .write()
+                    .apply_healed_graphs(healed_graphs)
+                    .map(|()| true)
+                    .map_err(CollectionError::from),
+                Ok(_) => Ok(false),
+                Err(err) => Err(err),
+            };
+
+            // Always put the segment back, even if healing failed or was cancelled
+            self.unwrap_healing_proxy(&segments, id, proxy_id, proxy_segment, tmp_segment)?;
+
+            healed_any |= healed?;
+        }
+
+        timer.set_success(true);
+        Ok(healed_any)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    use itertools::Itertools;
+    use parking_lot::RwLock;
+    use segment::index::hnsw_index::num_rayon_threads;
+    use segment::index::VectorIndex;
+    use segment::types::Distance;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::collection_manager::fixtures::random_multi_vec_segment;
+    use crate::collection_manager::holders::segment_holder::SegmentHolder;
+    use crate::collection_manager::optimizers::indexing_optimizer::IndexingOptimizer;
+    use crate::operations::types::VectorsConfig;
+    use crate::operations::vector_params_builder::VectorParamsBuilder;
+
+    #[test]
+    fn test_graph_healing_deleted_points() {
+        let (point_count, vector1_dim, vector2_dim) = (1000, 10, 20);
+        let thresholds_config = OptimizerThresholds {
+            max_segment_size_kb: usize::MAX,
+            memmap_threshold_kb: usize::MAX,
+            indexing_threshold_kb: 10,
+        };
+        let collection_params = CollectionParams {
+            vectors: VectorsConfig::Multi(BTreeMap::from([
+                (
+                    "vector1".into(),
+                    VectorParamsBuilder::new(vector1_dim, Distance::Dot).build(),
+                ),
+                (
+                    "vector2".into(),
+                    VectorParamsBuilder::new(vector2_dim, Distance::Dot).build(),
+                ),
+            ])),
+            ..CollectionParams::empty()
+        };
+
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let mut holder = SegmentHolder::default();
+        let segment_id = holder.add_new(random_multi_vec_segment(
+            dir.path(),
+            100,
+            point_count,
+            vector1_dim as usize,
+            vector2_dim as usize,
+        ));
+        let locked_holder: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+        // Index the segment first
+        let hnsw_config = HnswConfig {
+            m: 16,
+            ef_construct: 100,
+            full_scan_threshold: 10,
+            max_indexing_threads: 0,
+            on_disk: None,
+            payload_m: None,
+        };
+        let index_optimizer = IndexingOptimizer::new(
+            2,
+            thresholds_config,
+            dir.path().to_owned(),
+            temp_dir.path().to_owned(),
+            collection_params.clone(),
+            hnsw_config.clone(),
+            Default::default(),
+        );
+        let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+        let permit = CpuPermit::dummy(permit_cpu_count as u32);
+        let changed = index_optimizer
+            .optimize(
+                locked_holder.clone(),
+                vec![segment_id],
+                permit,
+                &AtomicBool::new(false),
+            )
+            .unwrap();
+        assert!(changed, "optimizer should have rebuilt this segment");
+
+        let graph_healing_optimizer = GraphHealingOptimizer::new(
+            0.2,
+            thresholds_config,
+            dir.path().to_owned(),
+            temp_dir.path().to_owned(),
+            collection_params,
+            hnsw_config,
+            Default::default(),
+        );
+
+        // Graphs are intact yet
+        let suggested_to_optimize =
+            graph_healing_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert!(suggested_to_optimize.is_empty());
+
+        let (segment_id, segment) = locked_holder
+            .read()
+            .iter()
+            .find(|(_, segment)| segment.get().read().total_point_count() > 0)
+            .map(|(id, segment)| (*id, segment.clone()))
+            .unwrap();
+        let point_count = segment.get().read().available_point_count();
+
+        // Delete a third of the points
+        let points_to_delete = segment.get().read().iter_points().step_by(3).collect_vec();
+        for &point_id in &points_to_delete {
+            segment.get().write().delete_point(201, point_id).unwrap();
+        }
+
+        let suggested_to_optimize =
+            graph_healing_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_to_optimize, vec![segment_id]);
+
+        let permit = CpuPermit::dummy(permit_cpu_count as u32);
+        let healed = graph_healing_optimizer
+            .optimize(
+                locked_holder.clone(),
+                suggested_to_optimize,
+                permit,
+                &AtomicBool::new(false),
+            )
+            .unwrap();
+        assert!(healed);
+
+        // Segment is healed in place, not replaced
+        assert!(matches!(
+            locked_holder.read().get(segment_id),
+            Some(LockedSegment::Original(_)),
+        ));
+
+        let LockedSegment::Original(segment_arc) = &segment else {
+            unreachable!();
+        };
+        let segment_guard = segment_arc.read();
+        let available_point_count = point_count - points_to_delete.len();
+        assert_eq!(segment_guard.available_point_count(), available_point_count);
+        for vector_data in segment_guard.vector_data.values() {
+            let vector_index = vector_data.vector_index.borrow();
+            assert!(vector_index.is_index());
+            assert_eq!(vector_index.indexed_vector_count(), available_point_count);
+        }
+        drop(segment_guard);
+
+        let suggested_to_optimize =
+            graph_healing_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert!(suggested_to_optimize.is_empty());
+    }
+}
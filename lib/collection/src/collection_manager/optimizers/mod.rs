@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use super::holders::segment_holder::SegmentId;
 
 pub mod config_mismatch_optimizer;
+pub mod graph_healing_optimizer;
 pub mod indexing_optimizer;
 pub mod merge_optimizer;
 pub mod segment_optimizer;
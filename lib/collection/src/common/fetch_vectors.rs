@@ -149,6 +149,8 @@ impl ReferencedVectors {
     ) -> Option<Vector> {
         match vector_input {
             VectorInput::Vector(vector) => Some(vector),
+            // Documents are inferred into vectors before references are resolved
+            VectorInput::Document(_) => None,
             VectorInput::Id(vid) => {
                 let rec = self.get(&collection_name, vid).unwrap();
                 rec.get_vector_by_name(vector_name).map(|v| v.to_owned())
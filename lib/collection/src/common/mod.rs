@@ -5,6 +5,8 @@ pub mod file_utils;
 pub mod is_ready;
 pub mod retrieve_request_trait;
 pub mod sha_256;
+pub mod snapshot_manifest;
+pub mod snapshot_mirror;
 pub mod snapshot_stream;
 pub mod snapshots_manager;
 pub mod stoppable_task;
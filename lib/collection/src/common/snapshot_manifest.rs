@@ -0,0 +1,372 @@
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use segment::types::SeqNumberType;
+use serde::{Deserialize, Serialize};
+use tar::{Archive, Builder, Header};
+
+use crate::common::validate_snapshot_archive::validate_open_snapshot_archive;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::shard::ShardId;
+use crate::shards::shard_versioning;
+
+/// Manifest is always the first entry of a snapshot archive, so it can be read cheaply
+pub const SNAPSHOT_MANIFEST_FILE: &str = "snapshot_manifest.json";
+
+const SEGMENTS_PATH: &str = "segments";
+
+/// Describes segments of all local shards in a collection snapshot.
+///
+/// An incremental snapshot only archives segments, which were added or changed since its base
+/// snapshot. Other segments listed in the manifest are stored in the chain of base snapshots.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// File name of the snapshot this snapshot is based on, it must be next to this snapshot
+    pub base: Option<String>,
+    pub shards: HashMap<ShardId, ShardManifest>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShardManifest {
+    /// Version of each segment of the shard at the moment of the snapshot, by segment name
+    pub segments: HashMap<String, SeqNumberType>,
+}
+
+impl ShardManifest {
+    /// Whether the given segment is in this manifest with the same version
+    pub fn contains(&self, segment_name: &str, version: SeqNumberType) -> bool {
+        self.segments.get(segment_name) == Some(&version)
+    }
+}
+
+impl SnapshotManifest {
+    /// Read manifest of the snapshot archive.
+    ///
+    /// Returns `None` for snapshots created without a manifest.
+    ///
+    /// This method performs blocking IO.
+    pub fn load_from_archive(snapshot_path: &Path) -> CollectionResult<Option<Self>> {
+        // Only the first entry is read, no need to validate the whole archive
+        Self::read_from_archive(File::open(snapshot_path)?).map_err(|err| {
+            CollectionError::service_error(format!(
+                "failed to read manifest of snapshot {}: {err}",
+                snapshot_path.display(),
+            ))
+        })
+    }
+
+    /// Read manifest from the beginning of a snapshot archive.
+    ///
+    /// Returns `None` for snapshots created without a manifest.
+    pub fn read_from_archive<R: Read>(reader: R) -> CollectionResult<Option<Self>> {
+        let mut archive = Archive::new(reader);
+        let Some(entry) = archive.entries()?.next() else {
+            return Ok(None);
+        };
+        let entry = entry?;
+
+        if !is_manifest_path(&entry.path()?) {
+            return Ok(None);
+        }
+
+        let manifest: Self = serde_json::from_reader(entry).map_err(|err| {
+            CollectionError::service_error(format!("invalid snapshot manifest: {err}"))
+        })?;
+        manifest.validate()?;
+
+        Ok(Some(manifest))
+    }
+
+    /// Append manifest to a new snapshot archive, it must be the first entry
+    pub fn append_to_archive<W: Write>(&self, builder: &mut Builder<W>) -> CollectionResult<()> {
+        let data = serde_json::to_vec(self).map_err(|err| {
+            CollectionError::service_error(format!("failed to serialize snapshot manifest: {err}"))
+        })?;
+
+        let mut header = Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(chrono::Utc::now().timestamp() as u64);
+        builder.append_data(&mut header, SNAPSHOT_MANIFEST_FILE, data.as_slice())?;
+
+        Ok(())
+    }
+
+    /// Path of the base snapshot, which is expected next to the given snapshot
+    pub fn base_path(&self, snapshot_path: &Path) -> Option<PathBuf> {
+        self.base
+            .as_ref()
+            .map(|base| snapshot_path.with_file_name(base))
+    }
+
+    fn validate(&self) -> CollectionResult<()> {
+        // Base is resolved relative to the snapshot, don't let it point anywhere else
+        if let Some(base) = &self.base {
+            if Path::new(base).file_name() != Some(OsStr::new(base)) {
+                return Err(CollectionError::bad_input(format!(
+                    "invalid base snapshot name {base:?} in snapshot manifest",
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Unpack collection snapshot into `target_dir`, taking segments it does not contain from the
+/// chain of its base snapshots.
+///
+/// Returns manifest of the snapshot, with `base` cleared if the snapshot was incremental, because
+/// the unpacked snapshot is complete. The manifest file itself is not unpacked.
+///
+/// This method performs blocking IO.
+pub fn unpack_snapshot_chain(
+    snapshot_path: &Path,
+    target_dir: &Path,
+) -> CollectionResult<Option<SnapshotManifest>> {
+    let mut archive = validate_open_snapshot_archive(snapshot_path)?;
+    archive.unpack(target_dir)?;
+
+    let manifest_path = target_dir.join(SNAPSHOT_MANIFEST_FILE);
+    if !manifest_path.exists() {
+        return Ok(None);
+    }
+    std::fs::remove_file(&manifest_path)?;
+
+    let Some(mut manifest) = SnapshotManifest::load_from_archive(snapshot_path)? else {
+        return Err(CollectionError::service_error(format!(
+            "manifest of snapshot {} is not the first entry of the archive",
+            snapshot_path.display(),
+        )));
+    };
+
+    // Archives of segments, which are referenced but are not part of this snapshot
+    let mut missing: HashMap<PathBuf, (ShardId, String)> = manifest
+        .shards
+        .iter()
+        .flat_map(|(&shard_id, shard)| {
+            let segments_path = segment_archives_path(Path::new(""), shard_id);
+            shard.segments.keys().map(move |segment_name| {
+                let archive_path = segments_path.join(format!("{segment_name}.tar"));
+                (archive_path, (shard_id, segment_name.clone()))
+            })
+        })
+        .filter(|(archive_path, _)| !target_dir.join(archive_path).exists())
+        .collect();
+
+    let mut visited = HashSet::new();
+    let mut current_path = snapshot_path.to_path_buf();
+    let mut current_manifest = manifest.clone();
+
+    while !missing.is_empty() {
+        let Some(base_path) = current_manifest.base_path(&current_path) else {
+            return Err(CollectionError::service_error(format!(
+                "snapshot chain of {} misses {} segments",
+                snapshot_path.display(),
+                missing.len(),
+            )));
+        };
+
+        if !visited.insert(base_path.clone()) {
+            return Err(CollectionError::service_error(format!(
+                "snapshot chain of {} is cyclic",
+                snapshot_path.display(),
+            )));
+        }
+
+        if !base_path.exists() {
+            return Err(CollectionError::not_found(format!(
+                "base snapshot {}",
+                base_path.display(),
+            )));
+        }
+
+        let Some(base_manifest) = SnapshotManifest::load_from_archive(&base_path)? else {
+            return Err(CollectionError::service_error(format!(
+                "base snapshot {} has no manifest",
+                base_path.display(),
+            )));
+        };
+
+        log::debug!(
+            "Unpacking {} segments from base snapshot {}",
+            missing.len(),
+            base_path.display(),
+        );
+
+        let mut base_archive = validate_open_snapshot_archive(&base_path)?;
+        for entry in base_archive.entries()? {
+            let mut entry = entry?;
+            let entry_path = normalize_entry_path(&entry.path()?).to_path_buf();
+
+            let Some((shard_id, segment_name)) = missing.get(&entry_path) else {
+                continue;
+            };
+
+            // Base must have the very same segment version, the one this snapshot skipped
+            let version = manifest.shards[shard_id].segments[segment_name];
+            let in_base = base_manifest
+                .shards
+                .get(shard_id)
+                .map_or(false, |shard| shard.contains(segment_name, version));
+            if !in_base {
+                return Err(CollectionError::service_error(format!(
+                    "base snapshot {} has different version of segment {segment_name} of shard {shard_id}",
+                    base_path.display(),
+                )));
+            }
+
+            let target_path = target_dir.join(&entry_path);
+            if let Some(parent) = target_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            entry.unpack(&target_path)?;
+            missing.remove(&entry_path);
+        }
+
+        current_path = base_path;
+        current_manifest = base_manifest;
+    }
+
+    manifest.base = None;
+    Ok(Some(manifest))
+}
+
+/// Directory with segment archives of a shard in a collection snapshot
+pub fn segment_archives_path(snapshot_dir: &Path, shard_id: ShardId) -> PathBuf {
+    shard_versioning::versioned_shard_path(snapshot_dir, shard_id, 0).join(SEGMENTS_PATH)
+}
+
+/// Archive a collection snapshot directory, with the manifest as the first entry
+///
+/// This method performs blocking IO.
+pub fn archive_snapshot_dir(
+    snapshot_dir: &Path,
+    manifest: &SnapshotManifest,
+    target: &mut File,
+) -> CollectionResult<()> {
+    let mut builder = Builder::new(target);
+    manifest.append_to_archive(&mut builder)?;
+    builder.append_dir_all(".", snapshot_dir)?;
+    builder.finish()?;
+    Ok(())
+}
+
+/// Whether the archive entry path is the path of the snapshot manifest
+pub fn is_manifest_path(path: &Path) -> bool {
+    normalize_entry_path(path) == Path::new(SNAPSHOT_MANIFEST_FILE)
+}
+
+/// Entries are archived relative to `.`, strip it to compare paths
+fn normalize_entry_path(path: &Path) -> &Path {
+    path.strip_prefix(".").unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder as TempBuilder;
+
+    use super::*;
+
+    fn write_snapshot(
+        dir: &Path,
+        name: &str,
+        manifest: &SnapshotManifest,
+        segments: &[(ShardId, &str, &str)],
+    ) {
+        let content_dir = TempBuilder::new().prefix("content").tempdir().unwrap();
+        for (shard_id, segment_name, data) in segments {
+            let segments_path = segment_archives_path(content_dir.path(), *shard_id);
+            std::fs::create_dir_all(&segments_path).unwrap();
+            std::fs::write(segments_path.join(format!("{segment_name}.tar")), data).unwrap();
+        }
+        let mut file = File::create(dir.join(name)).unwrap();
+        archive_snapshot_dir(content_dir.path(), manifest, &mut file).unwrap();
+    }
+
+    fn manifest(base: Option<&str>, segments: &[(&str, SeqNumberType)]) -> SnapshotManifest {
+        let shard = ShardManifest {
+            segments: segments
+                .iter()
+                .map(|(name, version)| (name.to_string(), *version))
+                .collect(),
+        };
+        SnapshotManifest {
+            base: base.map(str::to_string),
+            shards: HashMap::from([(0, shard)]),
+        }
+    }
+
+    #[test]
+    fn test_unpack_snapshot_chain() {
+        let dir = TempBuilder::new().prefix("snapshots").tempdir().unwrap();
+
+        let full = manifest(None, &[("a", 1), ("b", 2)]);
+        write_snapshot(
+            dir.path(),
+            "full.snapshot",
+            &full,
+            &[(0, "a", "a1"), (0, "b", "b2")],
+        );
+
+        // `b` changed, `c` was added
+        let first = manifest(Some("full.snapshot"), &[("a", 1), ("b", 3), ("c", 4)]);
+        write_snapshot(
+            dir.path(),
+            "first.snapshot",
+            &first,
+            &[(0, "b", "b3"), (0, "c", "c4")],
+        );
+
+        // `a` was removed
+        let second = manifest(Some("first.snapshot"), &[("b", 3), ("c", 4), ("d", 5)]);
+        write_snapshot(dir.path(), "second.snapshot", &second, &[(0, "d", "d5")]);
+
+        assert_eq!(
+            SnapshotManifest::load_from_archive(&dir.path().join("second.snapshot")).unwrap(),
+            Some(second.clone()),
+        );
+
+        let target = TempBuilder::new().prefix("target").tempdir().unwrap();
+        let unpacked =
+            unpack_snapshot_chain(&dir.path().join("second.snapshot"), target.path()).unwrap();
+        assert_eq!(
+            unpacked,
+            Some(SnapshotManifest {
+                base: None,
+                ..second
+            })
+        );
+        assert!(!target.path().join(SNAPSHOT_MANIFEST_FILE).exists());
+
+        let segments_path = segment_archives_path(target.path(), 0);
+        let mut segments: Vec<_> = std::fs::read_dir(&segments_path)
+            .unwrap()
+            .map(|entry| {
+                let path = entry.unwrap().path();
+                std::fs::read_to_string(path).unwrap()
+            })
+            .collect();
+        segments.sort();
+        assert_eq!(segments, vec!["b3", "c4", "d5"]);
+    }
+
+    #[test]
+    fn test_unpack_snapshot_chain_missing_base() {
+        let dir = TempBuilder::new().prefix("snapshots").tempdir().unwrap();
+
+        let incremental = manifest(Some("full.snapshot"), &[("a", 1), ("b", 2)]);
+        write_snapshot(
+            dir.path(),
+            "incremental.snapshot",
+            &incremental,
+            &[(0, "b", "b2")],
+        );
+
+        let target = TempBuilder::new().prefix("target").tempdir().unwrap();
+        let result = unpack_snapshot_chain(&dir.path().join("incremental.snapshot"), target.path());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Replication of locally stored snapshots to a mirror in object storage.
+//!
+//! Every change of a local snapshot is recorded in a marker file next to it, holding a unique
+//! token of the change. A replication task brings the mirrored object in line with the local
+//! snapshot: uploads the snapshot if it exists, or deletes the object otherwise. The marker is
+//! only removed if it was not changed while the task was running, so that no change is lost.
+//!
+//! Markers of failed or interrupted replications stay on disk, and are picked up again
+//! by [`resume_replication`] on restart.
+
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use object_store::ObjectStore;
+use parking_lot::Mutex;
+
+use crate::operations::snapshot_storage_ops::{self, trim_dot_slash};
+use crate::operations::types::{CollectionError, CollectionResult};
+
+const MIRROR_MARKER_EXTENSION: &str = "mirror";
+
+/// Number of attempts to replicate a snapshot, before it is left until restart
+const REPLICATION_ATTEMPTS: u32 = 5;
+
+/// Delay before the first retry, doubled on every next one
+const REPLICATION_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Snapshots, which have a replication task running.
+///
+/// Also serializes writes and removals of marker files.
+static REPLICATING: Mutex<BTreeSet<PathBuf>> = parking_lot::const_mutex(BTreeSet::new());
+
+pub fn get_mirror_marker_path(snapshot_path: impl Into<PathBuf>) -> PathBuf {
+    let mut marker_path = snapshot_path.into().into_os_string();
+    marker_path.push(".");
+    marker_path.push(MIRROR_MARKER_EXTENSION);
+    marker_path.into()
+}
+
+/// Whether all changes of the local snapshot are replicated to the mirror
+pub fn is_replicated(snapshot_path: &Path) -> bool {
+    !get_mirror_marker_path(snapshot_path).exists()
+}
+
+/// Record a change of the local snapshot, and replicate it to the mirror in the background
+pub fn replicate(remote: &Arc<dyn ObjectStore>, snapshot_path: &Path) -> CollectionResult<()> {
+    let mut replicating = REPLICATING.lock();
+    std::fs::write(
+        get_mirror_marker_path(snapshot_path),
+        uuid::Uuid::new_v4().to_string(),
+    )?;
+    // A running task picks up the change by itself
+    if replicating.insert(snapshot_path.to_path_buf()) {
+        tokio::spawn(replication_task(
+            remote.clone(),
+            snapshot_path.to_path_buf(),
+        ));
+    }
+    Ok(())
+}
+
+/// Replicate changes, which were not replicated before restart
+///
+/// Returns the number of snapshots with pending changes.
+pub fn resume_replication(
+    remote: &Arc<dyn ObjectStore>,
+    snapshots_path: &Path,
+) -> CollectionResult<usize> {
+    let mut pending = Vec::new();
+    find_pending(snapshots_path, &mut pending)?;
+
+    let mut replicating = REPLICATING.lock();
+    for snapshot_path in &pending {
+        if replicating.insert(snapshot_path.clone()) {
+            tokio::spawn(replication_task(remote.clone(), snapshot_path.clone()));
+        }
+    }
+    Ok(pending.len())
+}
+
+fn find_pending(directory: &Path, pending: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_pending(&path, pending)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == MIRROR_MARKER_EXTENSION)
+        {
+            pending.push(path.with_extension(""));
+        }
+    }
+    Ok(())
+}
+
+async fn replication_task(remote: Arc<dyn ObjectStore>, snapshot_path: PathBuf) {
+    let marker_path = get_mirror_marker_path(&snapshot_path);
+    loop {
+        let token = {
+            let mut replicating = REPLICATING.lock();
+            match std::fs::read_to_string(&marker_path) {
+                Ok(token) => token,
+                Err(_) => {
+                    replicating.remove(&snapshot_path);
+                    return;
+                }
+            }
+        };
+
+        if let Err(err) = replicate_with_retries(remote.as_ref(), &snapshot_path).await {
+            log::error!(
+                "Failed to replicate snapshot {} to mirror, replication is resumed on restart: {err}",
+                snapshot_path.display(),
+            );
+            REPLICATING.lock().remove(&snapshot_path);
+            return;
+        }
+
+        let mut replicating = REPLICATING.lock();
+        // Snapshot was changed during replication, replicate it once again
+        if std::fs::read_to_string(&marker_path).is_ok_and(|current| current != token) {
+            continue;
+        }
+        if let Err(err) = std::fs::remove_file(&marker_path) {
+            log::warn!(
+                "Failed to remove mirror marker {}, ignoring: {err}",
+                marker_path.display(),
+            );
+        }
+        replicating.remove(&snapshot_path);
+        return;
+    }
+}
+
+async fn replicate_with_retries(
+    remote: &dyn ObjectStore,
+    snapshot_path: &Path,
+) -> CollectionResult<()> {
+    let mut backoff = REPLICATION_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match replicate_snapshot(remote, snapshot_path).await {
+            Ok(()) => return Ok(()),
+            Err(err) if attempt < REPLICATION_ATTEMPTS => {
+                log::warn!(
+                    "Failed to replicate snapshot {} to mirror, attempt {attempt} of {REPLICATION_ATTEMPTS}, retrying in {backoff:?}: {err}",
+                    snapshot_path.display(),
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Upload the local snapshot, or delete the mirrored object if there is no local snapshot anymore
+async fn replicate_snapshot(
+    remote: &dyn ObjectStore,
+    snapshot_path: &Path,
+) -> CollectionResult<()> {
+    match std::fs::File::open(snapshot_path) {
+        Ok(file) => {
+            snapshot_storage_ops::multipart_upload_file(remote, file, snapshot_path).await?;
+            log::debug!("Mirrored snapshot {}", snapshot_path.display());
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            match remote.delete(&trim_dot_slash(snapshot_path)?).await {
+                Ok(()) | Err(object_store::Error::NotFound { .. }) => {
+                    log::debug!("Deleted mirrored snapshot {}", snapshot_path.display());
+                }
+                Err(err) => {
+                    return Err(CollectionError::service_error(format!(
+                        "Failed to delete mirrored snapshot: {err}"
+                    )));
+                }
+            }
+        }
+        Err(err) => return Err(err.into()),
+    }
+    Ok(())
+}
@@ -3,13 +3,19 @@ use std::sync::Arc;
 
 use actix_web::HttpRequest;
 use object_store::aws::AmazonS3Builder;
+use object_store::azure::{AzureConfigKey, MicrosoftAzureBuilder};
+use object_store::gcp::GoogleCloudStorageBuilder;
+use object_store::local::LocalFileSystem;
+use object_store::ObjectStore;
 use serde::Deserialize;
 use tempfile::TempPath;
 use tokio::io::AsyncWriteExt;
 
+use super::snapshot_mirror;
 use super::snapshot_stream::{SnapShotStreamCloudStrage, SnapShotStreamLocalFS, SnapshotStream};
 use crate::common::file_utils::move_file;
 use crate::common::sha_256::hash_file;
+use crate::common::snapshot_manifest::SnapshotManifest;
 use crate::operations::snapshot_ops::{
     get_checksum_path, get_snapshot_description, SnapshotDescription,
 };
@@ -22,13 +28,23 @@ use crate::shards::shard_holder::LockedShardHolder;
 pub struct SnapShotsConfig {
     pub snapshots_storage: SnapshotsStorageConfig,
     pub s3_config: Option<S3Config>,
+    pub gcs_config: Option<GcsConfig>,
+    pub azure_config: Option<AzureConfig>,
+    pub mirror_config: Option<MirrorConfig>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
 pub enum SnapshotsStorageConfig {
     #[default]
+    #[serde(alias = "Local")]
     Local,
+    #[serde(alias = "S3")]
     S3,
+    Gcs,
+    Azure,
+    /// Store snapshots locally and replicate them to object storage in the background
+    Mirror,
 }
 
 #[derive(Clone, Deserialize, Debug, Default)]
@@ -40,57 +56,125 @@ pub struct S3Config {
     pub endpoint_url: Option<String>,
 }
 
+#[derive(Clone, Deserialize, Debug, Default)]
+pub struct GcsConfig {
+    pub bucket: String,
+    /// Path to a service account JSON file
+    pub service_account_path: Option<String>,
+    /// Content of a service account JSON file, alternative to `service_account_path`
+    pub service_account_key: Option<String>,
+    /// Path to application default credentials, as created by `gcloud auth application-default login`
+    pub application_credentials_path: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Debug, Default)]
+pub struct AzureConfig {
+    pub account: String,
+    pub container: String,
+    /// Shared key of the storage account
+    pub access_key: Option<String>,
+    /// Shared access signature, as a query string
+    pub sas_token: Option<String>,
+    /// Bearer token, e.g. obtained for a managed identity
+    pub bearer_token: Option<String>,
+    pub endpoint_url: Option<String>,
+    /// Use the Azurite storage emulator
+    #[serde(default)]
+    pub use_emulator: bool,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct MirrorConfig {
+    /// Storage to replicate snapshots to, configured by the respective section of the snapshots config
+    pub target: MirrorTarget,
+    /// Directory to replicate snapshots to, required for the `local` target
+    pub local_path: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MirrorTarget {
+    S3,
+    Gcs,
+    Azure,
+    Local,
+}
+
 #[allow(dead_code)]
 pub struct SnapshotStorageCloud {
-    client: Box<dyn object_store::ObjectStore>,
+    client: Box<dyn ObjectStore>,
 }
 
 pub struct SnapshotStorageLocalFS;
 
+/// Local snapshot storage, which replicates every stored snapshot to object storage.
+///
+/// Snapshots are always read from the local storage, object storage only keeps a copy of them.
+/// Replication is described in [`snapshot_mirror`].
+pub struct SnapshotStorageMirror {
+    local: SnapshotStorageLocalFS,
+    remote: Arc<dyn object_store::ObjectStore>,
+}
+
 pub enum SnapshotStorageManager {
     LocalFS(SnapshotStorageLocalFS),
     // Assuming that we can have common operations for all cloud storages
     S3(SnapshotStorageCloud),
-    // <TODO> : Implement other cloud storage
-    // GCS(SnapshotStorageCloud),
-    // AZURE(SnapshotStorageCloud),
+    Gcs(SnapshotStorageCloud),
+    Azure(SnapshotStorageCloud),
+    Mirror(SnapshotStorageMirror),
 }
 
 impl SnapshotStorageManager {
     pub fn new(snapshots_config: SnapShotsConfig) -> CollectionResult<Self> {
-        match snapshots_config.clone().snapshots_storage {
+        match snapshots_config.snapshots_storage {
             SnapshotsStorageConfig::Local => {
                 Ok(SnapshotStorageManager::LocalFS(SnapshotStorageLocalFS))
             }
-            SnapshotsStorageConfig::S3 => {
-                let mut builder = AmazonS3Builder::new();
-                if let Some(s3_config) = &snapshots_config.s3_config {
-                    builder = builder.with_bucket_name(&s3_config.bucket);
-
-                    if let Some(access_key) = &s3_config.access_key {
-                        builder = builder.with_access_key_id(access_key);
-                    }
-                    if let Some(secret_key) = &s3_config.secret_key {
-                        builder = builder.with_secret_access_key(secret_key);
-                    }
-                    if let Some(region) = &s3_config.region {
-                        builder = builder.with_region(region);
-                    }
-                    if let Some(endpoint_url) = &s3_config.endpoint_url {
-                        builder = builder.with_endpoint(endpoint_url);
-                        if endpoint_url.starts_with("http://") {
-                            builder = builder.with_allow_http(true);
-                        }
+            SnapshotsStorageConfig::S3 => Ok(SnapshotStorageManager::S3(SnapshotStorageCloud {
+                client: build_s3_client(snapshots_config.s3_config.as_ref())?,
+            })),
+            SnapshotsStorageConfig::Gcs => Ok(SnapshotStorageManager::Gcs(SnapshotStorageCloud {
+                client: build_gcs_client(snapshots_config.gcs_config.as_ref())?,
+            })),
+            SnapshotsStorageConfig::Azure => {
+                Ok(SnapshotStorageManager::Azure(SnapshotStorageCloud {
+                    client: build_azure_client(snapshots_config.azure_config.as_ref())?,
+                }))
+            }
+            SnapshotsStorageConfig::Mirror => {
+                let mirror_config = snapshots_config.mirror_config.as_ref().ok_or_else(|| {
+                    CollectionError::service_error(
+                        "mirror_config is required for mirror snapshots storage",
+                    )
+                })?;
+                let remote = match mirror_config.target {
+                    MirrorTarget::S3 => build_s3_client(snapshots_config.s3_config.as_ref())?,
+                    MirrorTarget::Gcs => build_gcs_client(snapshots_config.gcs_config.as_ref())?,
+                    MirrorTarget::Azure => {
+                        build_azure_client(snapshots_config.azure_config.as_ref())?
                     }
-                }
-                let client: Box<dyn object_store::ObjectStore> =
-                    Box::new(builder.build().map_err(|e| {
-                        CollectionError::service_error(format!("Failed to create S3 client: {}", e))
-                    })?);
+                    MirrorTarget::Local => build_local_client(mirror_config.local_path.as_deref())?,
+                };
+                Ok(SnapshotStorageManager::Mirror(SnapshotStorageMirror::new(
+                    Arc::from(remote),
+                )))
+            }
+        }
+    }
 
-                Ok(SnapshotStorageManager::S3(SnapshotStorageCloud { client }))
+    /// Resume replication of snapshots, which were not mirrored before restart
+    ///
+    /// Must be called within a Tokio runtime, replication runs in the background.
+    pub fn resume_mirror_replication(&self, snapshots_path: &Path) -> CollectionResult<()> {
+        if let SnapshotStorageManager::Mirror(storage_impl) = self {
+            let pending =
+                snapshot_mirror::resume_replication(&storage_impl.remote, snapshots_path)?;
+            if pending > 0 {
+                log::info!("Resuming replication of {pending} snapshots to mirror");
             }
         }
+        Ok(())
     }
 
     pub async fn delete_snapshot(&self, snapshot_name: &Path) -> CollectionResult<bool> {
@@ -98,11 +182,17 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.delete_snapshot(snapshot_name).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl)
+            | SnapshotStorageManager::Azure(storage_impl) => {
+                storage_impl.delete_snapshot(snapshot_name).await
+            }
+            SnapshotStorageManager::Mirror(storage_impl) => {
                 storage_impl.delete_snapshot(snapshot_name).await
             }
         }
     }
+
     pub async fn list_snapshots(
         &self,
         directory: &Path,
@@ -111,7 +201,12 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.list_snapshots(directory).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Mirror(storage_impl) => {
+                storage_impl.list_snapshots(directory).await
+            }
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl)
+            | SnapshotStorageManager::Azure(storage_impl) => {
                 storage_impl.list_snapshots(directory).await
             }
         }
@@ -125,12 +220,38 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.store_file(source_path, target_path).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Mirror(storage_impl) => {
+                storage_impl.store_file(source_path, target_path).await
+            }
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl)
+            | SnapshotStorageManager::Azure(storage_impl) => {
                 storage_impl.store_file(source_path, target_path).await
             }
         }
     }
 
+    /// Move a stored snapshot to `target_path`, replacing a snapshot stored there
+    pub async fn rename_snapshot(
+        &self,
+        source_path: &Path,
+        target_path: &Path,
+    ) -> CollectionResult<()> {
+        match self {
+            SnapshotStorageManager::LocalFS(storage_impl) => {
+                storage_impl.rename_snapshot(source_path, target_path).await
+            }
+            SnapshotStorageManager::Mirror(storage_impl) => {
+                storage_impl.rename_snapshot(source_path, target_path).await
+            }
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl)
+            | SnapshotStorageManager::Azure(storage_impl) => {
+                storage_impl.rename_snapshot(source_path, target_path).await
+            }
+        }
+    }
+
     pub async fn get_stored_file(
         &self,
         storage_path: &Path,
@@ -140,7 +261,12 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.get_stored_file(storage_path, local_path).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Mirror(storage_impl) => {
+                storage_impl.get_stored_file(storage_path, local_path).await
+            }
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl)
+            | SnapshotStorageManager::Azure(storage_impl) => {
                 storage_impl.get_stored_file(storage_path, local_path).await
             }
         }
@@ -157,7 +283,14 @@ impl SnapshotStorageManager {
                     .get_snapshot_path(snapshots_path, snapshot_name)
                     .await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Mirror(storage_impl) => {
+                storage_impl
+                    .get_snapshot_path(snapshots_path, snapshot_name)
+                    .await
+            }
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl)
+            | SnapshotStorageManager::Azure(storage_impl) => {
                 storage_impl
                     .get_snapshot_path(snapshots_path, snapshot_name)
                     .await
@@ -176,7 +309,14 @@ impl SnapshotStorageManager {
                     .get_full_snapshot_path(snapshots_path, snapshot_name)
                     .await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Mirror(storage_impl) => {
+                storage_impl
+                    .get_full_snapshot_path(snapshots_path, snapshot_name)
+                    .await
+            }
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl)
+            | SnapshotStorageManager::Azure(storage_impl) => {
                 storage_impl
                     .get_full_snapshot_path(snapshots_path, snapshot_name)
                     .await
@@ -202,7 +342,19 @@ impl SnapshotStorageManager {
                     )
                     .await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Mirror(storage_impl) => {
+                storage_impl
+                    .get_shard_snapshot_path(
+                        shards_holder,
+                        shard_id,
+                        snapshots_path,
+                        snapshot_file_name,
+                    )
+                    .await
+            }
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl)
+            | SnapshotStorageManager::Azure(storage_impl) => {
                 storage_impl
                     .get_shard_snapshot_path(
                         shards_holder,
@@ -215,6 +367,56 @@ impl SnapshotStorageManager {
         }
     }
 
+    /// Read manifest of a stored snapshot, `None` if the snapshot was created without one
+    pub async fn read_snapshot_manifest(
+        &self,
+        snapshot_path: &Path,
+    ) -> CollectionResult<Option<SnapshotManifest>> {
+        match self {
+            SnapshotStorageManager::LocalFS(storage_impl) => {
+                storage_impl.read_snapshot_manifest(snapshot_path).await
+            }
+            SnapshotStorageManager::Mirror(storage_impl) => {
+                storage_impl.read_snapshot_manifest(snapshot_path).await
+            }
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl)
+            | SnapshotStorageManager::Azure(storage_impl) => {
+                storage_impl.read_snapshot_manifest(snapshot_path).await
+            }
+        }
+    }
+
+    /// Get local path of a stored snapshot, with all its base snapshots next to it.
+    ///
+    /// Snapshots which are not stored locally are downloaded into `temp_dir`.
+    pub async fn get_local_snapshot_chain(
+        &self,
+        snapshots_path: &Path,
+        snapshot_name: &str,
+        temp_dir: &Path,
+    ) -> CollectionResult<PathBuf> {
+        match self {
+            SnapshotStorageManager::LocalFS(storage_impl) => {
+                storage_impl
+                    .get_snapshot_path(snapshots_path, snapshot_name)
+                    .await
+            }
+            SnapshotStorageManager::Mirror(storage_impl) => {
+                storage_impl
+                    .get_snapshot_path(snapshots_path, snapshot_name)
+                    .await
+            }
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl)
+            | SnapshotStorageManager::Azure(storage_impl) => {
+                storage_impl
+                    .get_local_snapshot_chain(snapshots_path, snapshot_name, temp_dir)
+                    .await
+            }
+        }
+    }
+
     pub async fn get_snapshot_stream(
         self,
         req: HttpRequest,
@@ -224,7 +426,12 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.get_snapshot_stream(req, snapshot_path).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Mirror(storage_impl) => {
+                storage_impl.get_snapshot_stream(req, snapshot_path).await
+            }
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl)
+            | SnapshotStorageManager::Azure(storage_impl) => {
                 storage_impl.get_snapshot_stream(snapshot_path).await
             }
         }
@@ -308,6 +515,28 @@ impl SnapshotStorageLocalFS {
         get_snapshot_description(target_path).await
     }
 
+    async fn rename_snapshot(
+        &self,
+        source_path: &Path,
+        target_path: &Path,
+    ) -> CollectionResult<()> {
+        // Drop the old checksum first, a snapshot without checksum is valid,
+        // while a snapshot with a checksum of another file is not
+        let target_checksum_path = get_checksum_path(target_path);
+        if target_checksum_path.exists() {
+            tokio::fs::remove_file(&target_checksum_path).await?;
+        }
+
+        // Rename is atomic, the old snapshot is readable until it is replaced
+        tokio::fs::rename(source_path, target_path).await?;
+
+        let source_checksum_path = get_checksum_path(source_path);
+        if source_checksum_path.exists() {
+            tokio::fs::rename(&source_checksum_path, &target_checksum_path).await?;
+        }
+        Ok(())
+    }
+
     async fn get_stored_file(
         &self,
         storage_path: &Path,
@@ -403,6 +632,15 @@ impl SnapshotStorageLocalFS {
             .await
     }
 
+    async fn read_snapshot_manifest(
+        &self,
+        snapshot_path: &Path,
+    ) -> CollectionResult<Option<SnapshotManifest>> {
+        let snapshot_path = snapshot_path.to_path_buf();
+        tokio::task::spawn_blocking(move || SnapshotManifest::load_from_archive(&snapshot_path))
+            .await?
+    }
+
     async fn get_snapshot_stream(
         &self,
         req: HttpRequest,
@@ -433,6 +671,14 @@ impl SnapshotStorageCloud {
         snapshot_storage_ops::get_snapshot_description(&self.client, target_path).await
     }
 
+    async fn rename_snapshot(
+        &self,
+        source_path: &Path,
+        target_path: &Path,
+    ) -> CollectionResult<()> {
+        snapshot_storage_ops::rename_snapshot(&self.client, source_path, target_path).await
+    }
+
     async fn get_stored_file(
         &self,
         storage_path: &Path,
@@ -482,6 +728,40 @@ impl SnapshotStorageCloud {
             .join(snapshot_file_name))
     }
 
+    async fn read_snapshot_manifest(
+        &self,
+        snapshot_path: &Path,
+    ) -> CollectionResult<Option<SnapshotManifest>> {
+        snapshot_storage_ops::read_snapshot_manifest(&self.client, snapshot_path).await
+    }
+
+    /// Download the snapshot and its bases into `temp_dir`, keeping their names
+    async fn get_local_snapshot_chain(
+        &self,
+        snapshots_path: &Path,
+        snapshot_name: &str,
+        temp_dir: &Path,
+    ) -> CollectionResult<PathBuf> {
+        let mut next_name = Some(snapshot_name.to_string());
+        while let Some(name) = next_name.take() {
+            let local_path = temp_dir.join(&name);
+            if local_path.exists() {
+                break;
+            }
+
+            let storage_path = self.get_snapshot_path(snapshots_path, &name).await?;
+            snapshot_storage_ops::download_snapshot(&self.client, &storage_path, &local_path)
+                .await?;
+
+            next_name = self
+                .read_snapshot_manifest(&storage_path)
+                .await?
+                .and_then(|manifest| manifest.base);
+        }
+
+        Ok(temp_dir.join(snapshot_name))
+    }
+
     pub async fn get_snapshot_stream(
         &self,
         snapshot_path: &Path,
@@ -498,3 +778,413 @@ impl SnapshotStorageCloud {
         }))
     }
 }
+
+impl SnapshotStorageMirror {
+    fn new(remote: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            local: SnapshotStorageLocalFS,
+            remote,
+        }
+    }
+
+    async fn delete_snapshot(&self, snapshot_path: &Path) -> CollectionResult<bool> {
+        let deleted = self.local.delete_snapshot(snapshot_path).await?;
+        snapshot_mirror::replicate(&self.remote, snapshot_path)?;
+        Ok(deleted)
+    }
+
+    async fn list_snapshots(&self, directory: &Path) -> CollectionResult<Vec<SnapshotDescription>> {
+        self.local.list_snapshots(directory).await
+    }
+
+    async fn store_file(
+        &self,
+        source_path: &Path,
+        target_path: &Path,
+    ) -> CollectionResult<SnapshotDescription> {
+        let description = self.local.store_file(source_path, target_path).await?;
+        snapshot_mirror::replicate(&self.remote, target_path)?;
+        Ok(description)
+    }
+
+    async fn rename_snapshot(
+        &self,
+        source_path: &Path,
+        target_path: &Path,
+    ) -> CollectionResult<()> {
+        self.local.rename_snapshot(source_path, target_path).await?;
+        snapshot_mirror::replicate(&self.remote, source_path)?;
+        snapshot_mirror::replicate(&self.remote, target_path)?;
+        Ok(())
+    }
+
+    /// Takes the file out of the local storage, the mirrored copy is deleted as well
+    async fn get_stored_file(
+        &self,
+        storage_path: &Path,
+        local_path: &Path,
+    ) -> CollectionResult<()> {
+        self.local.get_stored_file(storage_path, local_path).await?;
+        if storage_path != local_path {
+            snapshot_mirror::replicate(&self.remote, storage_path)?;
+        }
+        Ok(())
+    }
+
+    /// Same checks as for local storage, but the path is not canonicalized,
+    /// so that it maps to the same object storage key as the path the snapshot was stored with
+    async fn get_snapshot_path(
+        &self,
+        snapshots_path: &Path,
+        snapshot_name: &str,
+    ) -> CollectionResult<PathBuf> {
+        self.local
+            .get_snapshot_path(snapshots_path, snapshot_name)
+            .await?;
+        Ok(snapshots_path.join(snapshot_name))
+    }
+
+    async fn get_full_snapshot_path(
+        &self,
+        snapshots_path: &str,
+        snapshot_name: &str,
+    ) -> CollectionResult<PathBuf> {
+        self.local
+            .get_full_snapshot_path(snapshots_path, snapshot_name)
+            .await?;
+        Ok(Path::new(snapshots_path).join(snapshot_name))
+    }
+
+    async fn get_shard_snapshot_path(
+        &self,
+        shards_holder: Arc<LockedShardHolder>,
+        shard_id: ShardId,
+        snapshots_path: &Path,
+        snapshot_file_name: impl AsRef<Path>,
+    ) -> CollectionResult<PathBuf> {
+        self.local
+            .get_shard_snapshot_path(shards_holder, shard_id, snapshots_path, snapshot_file_name)
+            .await
+    }
+
+    async fn read_snapshot_manifest(
+        &self,
+        snapshot_path: &Path,
+    ) -> CollectionResult<Option<SnapshotManifest>> {
+        self.local.read_snapshot_manifest(snapshot_path).await
+    }
+
+    async fn get_snapshot_stream(
+        &self,
+        req: HttpRequest,
+        snapshot_path: &Path,
+    ) -> CollectionResult<SnapshotStream> {
+        self.local.get_snapshot_stream(req, snapshot_path).await
+    }
+}
+
+fn build_s3_client(s3_config: Option<&S3Config>) -> CollectionResult<Box<dyn ObjectStore>> {
+    let mut builder = AmazonS3Builder::new();
+    if let Some(s3_config) = s3_config {
+        builder = builder.with_bucket_name(&s3_config.bucket);
+
+        if let Some(access_key) = &s3_config.access_key {
+            builder = builder.with_access_key_id(access_key);
+        }
+        if let Some(secret_key) = &s3_config.secret_key {
+            builder = builder.with_secret_access_key(secret_key);
+        }
+        if let Some(region) = &s3_config.region {
+            builder = builder.with_region(region);
+        }
+        if let Some(endpoint_url) = &s3_config.endpoint_url {
+            builder = builder.with_endpoint(endpoint_url);
+            if endpoint_url.starts_with("http://") {
+                builder = builder.with_allow_http(true);
+            }
+        }
+    }
+    let client = builder.build().map_err(|e| {
+        CollectionError::service_error(format!("Failed to create S3 client: {}", e))
+    })?;
+    Ok(Box::new(client))
+}
+
+fn build_gcs_client(gcs_config: Option<&GcsConfig>) -> CollectionResult<Box<dyn ObjectStore>> {
+    let gcs_config = gcs_config.ok_or_else(|| {
+        CollectionError::service_error("gcs_config is required for GCS snapshots storage")
+    })?;
+
+    let mut builder = GoogleCloudStorageBuilder::new().with_bucket_name(&gcs_config.bucket);
+    if let Some(service_account_path) = &gcs_config.service_account_path {
+        builder = builder.with_service_account_path(service_account_path);
+    }
+    if let Some(service_account_key) = &gcs_config.service_account_key {
+        builder = builder.with_service_account_key(service_account_key);
+    }
+    if let Some(application_credentials_path) = &gcs_config.application_credentials_path {
+        builder = builder.with_application_credentials(application_credentials_path);
+    }
+    let client = builder.build().map_err(|e| {
+        CollectionError::service_error(format!("Failed to create GCS client: {}", e))
+    })?;
+    Ok(Box::new(client))
+}
+
+fn build_azure_client(
+    azure_config: Option<&AzureConfig>,
+) -> CollectionResult<Box<dyn ObjectStore>> {
+    let azure_config = azure_config.ok_or_else(|| {
+        CollectionError::service_error("azure_config is required for Azure snapshots storage")
+    })?;
+
+    let mut builder = MicrosoftAzureBuilder::new()
+        .with_account(&azure_config.account)
+        .with_container_name(&azure_config.container)
+        .with_use_emulator(azure_config.use_emulator);
+    if let Some(access_key) = &azure_config.access_key {
+        builder = builder.with_access_key(access_key);
+    }
+    if let Some(sas_token) = &azure_config.sas_token {
+        builder = builder.with_config(AzureConfigKey::SasKey, sas_token);
+    }
+    if let Some(bearer_token) = &azure_config.bearer_token {
+        builder = builder.with_bearer_token_authorization(bearer_token);
+    }
+    if let Some(endpoint_url) = &azure_config.endpoint_url {
+        builder = builder.with_endpoint(endpoint_url.clone());
+        if endpoint_url.starts_with("http://") {
+            builder = builder.with_allow_http(true);
+        }
+    }
+    let client = builder.build().map_err(|e| {
+        CollectionError::service_error(format!("Failed to create Azure client: {}", e))
+    })?;
+    Ok(Box::new(client))
+}
+
+fn build_local_client(local_path: Option<&str>) -> CollectionResult<Box<dyn ObjectStore>> {
+    let local_path = local_path.ok_or_else(|| {
+        CollectionError::service_error("local_path is required to mirror snapshots locally")
+    })?;
+
+    std::fs::create_dir_all(local_path)?;
+    let client = LocalFileSystem::new_with_prefix(local_path).map_err(|e| {
+        CollectionError::service_error(format!("Failed to open mirror directory: {}", e))
+    })?;
+    Ok(Box::new(client))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use object_store::memory::InMemory;
+    use tempfile::Builder;
+
+    use super::*;
+
+    /// Wait for the background replication to create or remove the object
+    async fn wait_for_object(remote: &dyn ObjectStore, path: &Path, exists: bool) -> bool {
+        let location = snapshot_storage_ops::trim_dot_slash(path).unwrap();
+        for _ in 0..100 {
+            if remote.head(&location).await.is_ok() == exists {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        false
+    }
+
+    async fn check_mirror(remote: Arc<dyn ObjectStore>) {
+        let dir = Builder::new().prefix("snapshots").tempdir().unwrap();
+        let snapshots_path = dir.path().join("collection");
+        let source_path = dir.path().join("source.snapshot");
+        std::fs::write(&source_path, b"snapshot data").unwrap();
+
+        let storage = SnapshotStorageManager::Mirror(SnapshotStorageMirror::new(remote.clone()));
+
+        let target_path = snapshots_path.join("test.snapshot");
+        storage
+            .store_file(&source_path, &target_path)
+            .await
+            .unwrap();
+        assert!(target_path.exists());
+        assert!(wait_for_object(remote.as_ref(), &target_path, true).await);
+
+        let location = snapshot_storage_ops::trim_dot_slash(&target_path).unwrap();
+        let mirrored = remote.get(&location).await.unwrap().bytes().await.unwrap();
+        assert_eq!(mirrored.as_ref(), b"snapshot data");
+
+        // Snapshots are read from the local storage
+        let snapshots = storage.list_snapshots(&snapshots_path).await.unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].name, "test.snapshot");
+
+        let snapshot_path = storage
+            .get_snapshot_path(&snapshots_path, "test.snapshot")
+            .await
+            .unwrap();
+        assert_eq!(snapshot_path, target_path);
+
+        assert!(storage.delete_snapshot(&snapshot_path).await.unwrap());
+        assert!(!target_path.exists());
+        assert!(wait_for_object(remote.as_ref(), &target_path, false).await);
+        assert!(wait_for_replication(&target_path).await);
+    }
+
+    /// Wait for the background replication to process all changes of the snapshot
+    async fn wait_for_replication(snapshot_path: &Path) -> bool {
+        for _ in 0..100 {
+            if snapshot_mirror::is_replicated(snapshot_path) {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn test_mirror_delete_during_upload() {
+        let dir = Builder::new().prefix("snapshots").tempdir().unwrap();
+        let remote: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let storage = SnapshotStorageManager::Mirror(SnapshotStorageMirror::new(remote.clone()));
+
+        let source_path = dir.path().join("source.snapshot");
+        std::fs::write(&source_path, b"snapshot data").unwrap();
+        let target_path = dir.path().join("collection").join("test.snapshot");
+
+        // Delete right away, while the upload is still pending
+        storage
+            .store_file(&source_path, &target_path)
+            .await
+            .unwrap();
+        assert!(storage.delete_snapshot(&target_path).await.unwrap());
+
+        assert!(wait_for_replication(&target_path).await);
+        let location = snapshot_storage_ops::trim_dot_slash(&target_path).unwrap();
+        assert!(remote.head(&location).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mirror_resume_replication() {
+        let dir = Builder::new().prefix("snapshots").tempdir().unwrap();
+        let remote: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let storage = SnapshotStorageManager::Mirror(SnapshotStorageMirror::new(remote.clone()));
+
+        // Snapshot, which was stored but not mirrored before restart
+        let snapshot_path = dir.path().join("collection").join("test.snapshot");
+        std::fs::create_dir_all(snapshot_path.parent().unwrap()).unwrap();
+        std::fs::write(&snapshot_path, b"snapshot data").unwrap();
+        std::fs::write(
+            snapshot_mirror::get_mirror_marker_path(&snapshot_path),
+            "token",
+        )
+        .unwrap();
+        assert!(!snapshot_mirror::is_replicated(&snapshot_path));
+
+        storage.resume_mirror_replication(dir.path()).unwrap();
+
+        assert!(wait_for_object(remote.as_ref(), &snapshot_path, true).await);
+        assert!(wait_for_replication(&snapshot_path).await);
+    }
+
+    #[test]
+    fn test_snapshots_storage_config_aliases() {
+        for (config, expected) in [
+            ("local", SnapshotsStorageConfig::Local),
+            ("Local", SnapshotsStorageConfig::Local),
+            ("s3", SnapshotsStorageConfig::S3),
+            ("S3", SnapshotsStorageConfig::S3),
+            ("gcs", SnapshotsStorageConfig::Gcs),
+            ("azure", SnapshotsStorageConfig::Azure),
+            ("mirror", SnapshotsStorageConfig::Mirror),
+        ] {
+            let parsed: SnapshotsStorageConfig =
+                serde_json::from_value(serde_json::Value::from(config)).unwrap();
+            assert_eq!(parsed, expected);
+        }
+    }
+
+    #[test]
+    fn test_build_gcs_client() {
+        assert!(build_gcs_client(None).is_err());
+
+        let gcs_config = GcsConfig {
+            bucket: "snapshots".to_string(),
+            ..Default::default()
+        };
+        assert!(build_gcs_client(Some(&gcs_config)).is_ok());
+
+        let gcs_config = GcsConfig {
+            bucket: "snapshots".to_string(),
+            service_account_path: Some("/nonexistent/service_account.json".to_string()),
+            ..Default::default()
+        };
+        assert!(build_gcs_client(Some(&gcs_config)).is_err());
+    }
+
+    #[test]
+    fn test_build_azure_client() {
+        assert!(build_azure_client(None).is_err());
+
+        let azure_config = AzureConfig {
+            account: "devstoreaccount1".to_string(),
+            container: "snapshots".to_string(),
+            use_emulator: true,
+            ..Default::default()
+        };
+        assert!(build_azure_client(Some(&azure_config)).is_ok());
+
+        let azure_config = AzureConfig {
+            account: "account".to_string(),
+            container: "snapshots".to_string(),
+            sas_token: Some("sv=2021-06-08&sig=signature".to_string()),
+            endpoint_url: Some("http://localhost:10000/account".to_string()),
+            ..Default::default()
+        };
+        assert!(build_azure_client(Some(&azure_config)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_mirror_in_memory() {
+        check_mirror(Arc::new(InMemory::new())).await;
+    }
+
+    #[tokio::test]
+    async fn test_local_rename_snapshot() {
+        let dir = Builder::new().prefix("snapshots").tempdir().unwrap();
+        let storage = SnapshotStorageManager::LocalFS(SnapshotStorageLocalFS);
+
+        let snapshots_path = dir.path().join("collection");
+        let snapshot_path = snapshots_path.join("test.snapshot");
+        let compacted_path = snapshots_path.join("test.snapshot.compacting");
+        for (target_path, data) in [
+            (&snapshot_path, b"old data"),
+            (&compacted_path, b"new data"),
+        ] {
+            let source_path = dir.path().join("source");
+            std::fs::write(&source_path, data).unwrap();
+            storage.store_file(&source_path, target_path).await.unwrap();
+        }
+
+        storage
+            .rename_snapshot(&compacted_path, &snapshot_path)
+            .await
+            .unwrap();
+
+        assert!(!compacted_path.exists());
+        assert!(!get_checksum_path(&compacted_path).exists());
+        assert_eq!(std::fs::read(&snapshot_path).unwrap(), b"new data");
+
+        let checksum = std::fs::read_to_string(get_checksum_path(&snapshot_path)).unwrap();
+        assert_eq!(checksum, hash_file(&snapshot_path).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_mirror_local_file() {
+        let mirror_dir = Builder::new().prefix("mirror").tempdir().unwrap();
+        let remote = build_local_client(mirror_dir.path().to_str()).unwrap();
+        check_mirror(Arc::from(remote)).await;
+    }
+}
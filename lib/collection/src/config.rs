@@ -6,6 +6,7 @@ use std::path::Path;
 
 use atomicwrites::AtomicFile;
 use atomicwrites::OverwriteBehavior::AllowOverwrite;
+use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
@@ -109,11 +110,12 @@ pub struct CollectionParams {
 }
 
 impl CollectionParams {
+    /// Payload storage of new segments, RocksDB-based storages are only kept for existing segments
     pub fn payload_storage_type(&self) -> PayloadStorageType {
         if self.on_disk_payload {
-            PayloadStorageType::OnDisk
+            PayloadStorageType::Mmap
         } else {
-            PayloadStorageType::InMemory
+            PayloadStorageType::InRamMmap
         }
     }
 }
@@ -161,6 +163,48 @@ pub struct CollectionConfig {
     pub wal_config: WalConfig,
     #[serde(default)]
     pub quantization_config: Option<QuantizationConfig>,
+    #[serde(default)]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
+}
+
+/// Limits of the requests to the collection, which are checked before the request is executed.
+///
+/// Requests exceeding the limits are rejected, limits which are not set are not checked.
+#[derive(
+    Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash, Merge,
+)]
+pub struct StrictModeConfig {
+    /// Whether strict mode is enabled for the collection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Max allowed `limit` of search, query and scroll requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_query_limit: Option<usize>,
+    /// Allow filtering by payload fields without a suitable index in search, query, scroll and count requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unindexed_filtering_retrieve: Option<bool>,
+    /// Allow filtering by payload fields without a suitable index in update requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unindexed_filtering_update: Option<bool>,
+    /// Max allowed `hnsw_ef` search parameter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub search_max_hnsw_ef: Option<usize>,
+    /// Allow exact search, which does not use the vector index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_allow_exact: Option<bool>,
+    /// Max number of points in a single upsert or vector update request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub upsert_max_batchsize: Option<usize>,
+}
+
+impl StrictModeConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
 }
 
 impl CollectionConfig {
@@ -318,12 +362,34 @@ impl CollectionParams {
     ) -> CollectionResult<()> {
         for (vector_name, update_params) in update_vectors.0.iter() {
             let sparse_vector_params = self.get_sparse_vector_params_mut(vector_name)?;
-            let SparseVectorParams { index, modifier } = update_params.clone();
+            let SparseVectorParams {
+                index,
+                modifier,
+                bm25,
+                storage_type,
+            } = update_params.clone();
 
             if let Some(modifier) = modifier {
                 sparse_vector_params.modifier = Some(modifier);
             }
 
+            if let Some(storage_type) = storage_type {
+                sparse_vector_params.storage_type = Some(storage_type);
+            }
+
+            if let Some(bm25) = bm25 {
+                // Stored vectors were inferred with the existing config, or were not inferred at
+                // all, so it can only be set when the collection is created
+                if sparse_vector_params.bm25.as_ref() != Some(&bm25) {
+                    return Err(CollectionError::BadInput {
+                        description: format!(
+                            "BM25 config of sparse vector {vector_name} can't be set or changed, \
+                             recreate the collection to use another config",
+                        ),
+                    });
+                }
+            }
+
             if let Some(index) = index {
                 if let Some(existing_index) = &mut sparse_vector_params.index {
                     existing_index.update_from_other(&index);
@@ -361,6 +427,8 @@ impl CollectionParams {
                         },
                         multivec_config: params.multivec_config,
                         datatype: params.datatype.map(VectorStorageDatatype::from),
+                        validation: params.validation,
+                        normalize: params.normalize,
                     },
                 )
             })
@@ -371,6 +439,9 @@ impl CollectionParams {
     ///
     /// It is the job of the segment optimizer to change this configuration with optimized settings
     /// based on threshold configurations.
+    ///
+    /// Sparse vectors without a configured storage type belong to collections created before mmap
+    /// storage was introduced, they keep being stored in RocksDB.
     pub fn to_sparse_vector_data(
         &self,
     ) -> CollectionResult<HashMap<String, SparseVectorDataConfig>> {
@@ -386,7 +457,14 @@ impl CollectionParams {
                                     .index
                                     .and_then(|index| index.full_scan_threshold),
                                 index_type: SparseIndexType::MutableRam,
+                                datatype: Some(VectorStorageDatatype::from(
+                                    params
+                                        .index
+                                        .and_then(|index| index.datatype)
+                                        .unwrap_or_default(),
+                                )),
                             },
+                            storage_type: params.storage_type.unwrap_or_default(),
                         },
                     )
                 })
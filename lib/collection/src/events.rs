@@ -2,20 +2,84 @@ use std::collections::HashMap;
 
 use segment::json_path::JsonPathV2;
 use segment::types::{Filter, PayloadFieldSchema};
+use serde::Serialize;
 
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::collection_manager::optimizers::TrackerStatus;
+use crate::shards::replica_set::ReplicaState;
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::transfer::ShardTransfer;
 use crate::shards::CollectionId;
 
+#[derive(Debug, Serialize)]
+pub struct CollectionCreatedEvent {
+    pub collection_id: CollectionId,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CollectionUpdatedEvent {
+    pub collection_id: CollectionId,
+}
+
+#[derive(Debug, Serialize)]
 pub struct CollectionDeletedEvent {
     pub collection_id: CollectionId,
 }
 
+#[derive(Debug, Serialize)]
 pub struct SlowQueryEvent {
     pub collection_id: CollectionId,
     pub filters: Vec<Filter>,
     pub schema: HashMap<JsonPathV2, PayloadFieldSchema>,
 }
 
+#[derive(Debug, Serialize)]
 pub struct IndexCreatedEvent {
     pub collection_id: CollectionId,
     pub field_name: JsonPathV2,
 }
+
+#[derive(Debug, Serialize)]
+pub struct ShardTransferStartedEvent {
+    pub collection_id: CollectionId,
+    pub transfer: ShardTransfer,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShardTransferFinishedEvent {
+    pub collection_id: CollectionId,
+    pub transfer: ShardTransfer,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShardTransferAbortedEvent {
+    pub collection_id: CollectionId,
+    pub transfer: ShardTransfer,
+}
+
+/// State of a replica has changed, `None` state means the replica does not exist
+#[derive(Debug, Serialize)]
+pub struct ReplicaStateChangedEvent {
+    pub collection_id: CollectionId,
+    pub shard_id: ShardId,
+    pub peer_id: PeerId,
+    pub from_state: Option<ReplicaState>,
+    pub to_state: Option<ReplicaState>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OptimizationStartedEvent {
+    pub collection_id: CollectionId,
+    pub shard_id: ShardId,
+    pub optimizer: String,
+    pub segment_ids: Vec<SegmentId>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OptimizationFinishedEvent {
+    pub collection_id: CollectionId,
+    pub shard_id: ShardId,
+    pub optimizer: String,
+    pub segment_ids: Vec<SegmentId>,
+    pub status: TrackerStatus,
+}
@@ -2,26 +2,31 @@ use std::collections::HashMap;
 use std::future::Future;
 use std::time::Duration;
 
+use api::rest::QueryRequestInternal;
 use fnv::FnvBuildHasher;
 use indexmap::IndexSet;
 use segment::json_path::{JsonPath, JsonPathInterface as _};
 use segment::types::{
-    AnyVariants, Condition, FieldCondition, Filter, Match, Order, ScoredPoint, WithPayloadInterface,
+    AnyVariants, Condition, FieldCondition, Filter, Match, Order, ScoredPoint,
+    WithPayloadInterface, WithVector,
 };
 use serde_json::Value;
 use tokio::sync::RwLockReadGuard;
 
 use super::aggregator::GroupsAggregator;
-use super::types::CoreGroupRequest;
+use super::types::{CoreGroupRequest, CoreSourceRequest};
 use crate::collection::Collection;
 use crate::common::fetch_vectors;
 use crate::lookup::WithLookup;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
-    BaseGroupRequest, CollectionResult, PointGroup, RecommendGroupsRequestInternal,
-    RecommendRequestInternal, SearchGroupsRequestInternal, SearchRequestInternal,
+    BaseGroupRequest, CollectionResult, PointGroup, QueryGroupsRequestInternal,
+    RecommendGroupsRequestInternal, RecommendRequestInternal, SearchGroupsRequestInternal,
+    SearchRequestInternal,
 };
+use crate::operations::universal_query::collection_query::CollectionQueryRequest;
+use crate::operations::universal_query::shard_query::{ScoringQuery, ShardPrefetch};
 use crate::recommendations::recommend_into_core_search;
 
 const MAX_GET_GROUPS_REQUESTS: usize = 5;
@@ -31,11 +36,12 @@ const MAX_GROUP_FILLING_REQUESTS: usize = 5;
 pub enum SourceRequest {
     Search(SearchRequestInternal),
     Recommend(RecommendRequestInternal),
+    Query(CollectionQueryRequest),
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct GroupRequest {
-    /// Request to use (search or recommend)
+    /// Request to use (search, recommend or query)
     pub source: SourceRequest,
 
     /// Path to the field to group by
@@ -60,6 +66,7 @@ impl GroupRequest {
         let limit = match &source {
             SourceRequest::Search(request) => request.limit,
             SourceRequest::Recommend(request) => request.limit,
+            SourceRequest::Query(request) => request.limit,
         };
         Self {
             source,
@@ -81,8 +88,8 @@ impl GroupRequest {
         F: Fn(String) -> Fut,
         Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
     {
-        let core_search = match self.source {
-            SourceRequest::Search(search_req) => search_req.into(),
+        let core_source = match self.source {
+            SourceRequest::Search(search_req) => CoreSourceRequest::Search(search_req.into()),
             SourceRequest::Recommend(recommend_req) => {
                 let referenced_vectors = fetch_vectors::resolve_referenced_vectors_batch(
                     &[(recommend_req.clone(), shard_selection)],
@@ -92,12 +99,29 @@ impl GroupRequest {
                 )
                 .await?;
 
-                recommend_into_core_search(recommend_req, &referenced_vectors)?
+                CoreSourceRequest::Search(recommend_into_core_search(
+                    recommend_req,
+                    &referenced_vectors,
+                )?)
+            }
+            SourceRequest::Query(mut query_req) => {
+                // Turn documents into vectors, if necessary
+                collection.infer_documents(&mut query_req).await?;
+
+                let referenced_vectors = fetch_vectors::resolve_referenced_vectors_batch(
+                    &[(&query_req, shard_selection)],
+                    collection,
+                    collection_by_name,
+                    read_consistency,
+                )
+                .await?;
+
+                CoreSourceRequest::Query(query_req.try_into_shard_request(&referenced_vectors)?)
             }
         };
 
         Ok(CoreGroupRequest {
-            source: core_search,
+            source: core_source,
             group_by: self.group_by,
             group_size: self.group_size,
             limit: self.limit,
@@ -119,25 +143,72 @@ impl CoreGroupRequest {
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<ScoredPoint>> {
-        let mut request = self.source.clone();
-
-        request.limit = self.limit * self.group_size;
+        let mut source = self.source.clone();
 
         let key_not_empty = Filter::new_must_not(Condition::IsEmpty(self.group_by.clone().into()));
-        request.filter = Some(request.filter.unwrap_or_default().merge(&key_not_empty));
+        source.merge_filter(&key_not_empty);
 
         let with_group_by_payload = self.group_by_to_payload_selector(&self.group_by);
 
-        // We're enriching the final results at the end, so we'll keep this minimal
-        request.with_payload = Some(with_group_by_payload);
-        request.with_vector = None;
+        match source {
+            CoreSourceRequest::Search(mut request) => {
+                request.limit = self.limit * self.group_size;
+
+                // We're enriching the final results at the end, so we'll keep this minimal
+                request.with_payload = Some(with_group_by_payload);
+                request.with_vector = None;
+
+                collection
+                    .search(request, read_consistency, &shard_selection, timeout)
+                    .await
+            }
+            CoreSourceRequest::Query(mut request) => {
+                request.limit = self.limit * self.group_size;
+                request.offset = 0;
+
+                // We're enriching the final results at the end, so we'll keep this minimal
+                request.with_payload = with_group_by_payload;
+                request.with_vector = WithVector::Bool(false);
+
+                collection
+                    .query_resolved(request, read_consistency, &shard_selection, timeout)
+                    .await
+            }
+        }
+    }
+}
+
+impl CoreSourceRequest {
+    /// Restrict the request with an additional filter.
+    ///
+    /// For queries, the filter is propagated into every prefetch, so that the intermediate
+    /// results are not spent on points which would be discarded at the root.
+    fn merge_filter(&mut self, filter: &Filter) {
+        match self {
+            CoreSourceRequest::Search(request) => {
+                request.filter = Some(merge_filters(request.filter.as_ref(), filter));
+            }
+            CoreSourceRequest::Query(request) => {
+                request.filter = Some(merge_filters(request.filter.as_ref(), filter));
+                merge_filter_into_prefetches(&mut request.prefetches, filter);
+            }
+        }
+    }
+}
 
-        collection
-            .search(request, read_consistency, &shard_selection, timeout)
-            .await
+fn merge_filter_into_prefetches(prefetches: &mut [ShardPrefetch], filter: &Filter) {
+    for prefetch in prefetches {
+        prefetch.filter = Some(merge_filters(prefetch.filter.as_ref(), filter));
+        merge_filter_into_prefetches(&mut prefetch.prefetches, filter);
     }
 }
 
+fn merge_filters(current: Option<&Filter>, filter: &Filter) -> Filter {
+    current
+        .map(|current| current.merge(filter))
+        .unwrap_or_else(|| filter.clone())
+}
+
 impl From<SearchGroupsRequestInternal> for GroupRequest {
     fn from(request: SearchGroupsRequestInternal) -> Self {
         let SearchGroupsRequestInternal {
@@ -224,6 +295,49 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
     }
 }
 
+impl From<QueryGroupsRequestInternal> for GroupRequest {
+    fn from(request: QueryGroupsRequestInternal) -> Self {
+        let QueryGroupsRequestInternal {
+            prefetch,
+            query,
+            using,
+            filter,
+            params,
+            score_threshold,
+            with_vector,
+            with_payload,
+            group_request:
+                BaseGroupRequest {
+                    group_by,
+                    group_size,
+                    limit,
+                    with_lookup: with_lookup_interface,
+                },
+        } = request;
+
+        let query = CollectionQueryRequest::from(QueryRequestInternal {
+            prefetch,
+            query,
+            using,
+            filter,
+            params,
+            score_threshold,
+            limit: None,
+            offset: None,
+            with_vector,
+            with_payload,
+        });
+
+        GroupRequest {
+            source: SourceRequest::Query(query),
+            group_by,
+            group_size: group_size as usize,
+            limit: limit as usize,
+            with_lookup: with_lookup_interface.map(Into::into),
+        }
+    }
+}
+
 /// Uses the request to fill up groups of points.
 pub async fn group_by(
     request: CoreGroupRequest,
@@ -232,13 +346,21 @@ pub async fn group_by(
     shard_selection: ShardSelectorInternal,
     timeout: Option<Duration>,
 ) -> CollectionResult<Vec<PointGroup>> {
-    let score_ordering = if request.source.query.is_distance_scored() {
-        let vector_name = request.source.query.get_vector_name();
-        let collection_params = collection.collection_config.read().await;
-        let distance = collection_params.params.get_distance(vector_name)?;
-        distance.distance_order()
-    } else {
-        Order::LargeBetter
+    let score_ordering = match &request.source {
+        CoreSourceRequest::Search(search) => {
+            if search.query.is_distance_scored() {
+                let vector_name = search.query.get_vector_name();
+                let collection_params = collection.collection_config.read().await;
+                let distance = collection_params.params.get_distance(vector_name)?;
+                distance.distance_order()
+            } else {
+                Order::LargeBetter
+            }
+        }
+        CoreSourceRequest::Query(query) => {
+            let collection_params = collection.collection_config.read().await;
+            ScoringQuery::order(query.query.as_ref(), &collection_params.params)?
+        }
     };
 
     let mut aggregator = GroupsAggregator::new(
@@ -264,13 +386,7 @@ pub async fn group_by(
                     must: Some(except_any),
                     ..Default::default()
                 };
-                source.filter = Some(
-                    source
-                        .filter
-                        .as_ref()
-                        .map(|filter| filter.merge(&exclude_groups))
-                        .unwrap_or(exclude_groups),
-                );
+                source.merge_filter(&exclude_groups);
             }
         }
 
@@ -278,13 +394,7 @@ pub async fn group_by(
         let ids = aggregator.ids().clone();
         if !ids.is_empty() {
             let exclude_ids = Filter::new_must_not(Condition::HasId(ids.into()));
-            source.filter = Some(
-                source
-                    .filter
-                    .as_ref()
-                    .map(|filter| filter.merge(&exclude_ids))
-                    .unwrap_or(exclude_ids),
-            );
+            source.merge_filter(&exclude_ids);
         }
 
         // Make request
@@ -325,26 +435,14 @@ pub async fn group_by(
                     must: Some(match_any),
                     ..Default::default()
                 };
-                source.filter = Some(
-                    source
-                        .filter
-                        .as_ref()
-                        .map(|filter| filter.merge(&include_groups))
-                        .unwrap_or(include_groups),
-                );
+                source.merge_filter(&include_groups);
             }
 
             // Exclude already aggregated points
             let ids = aggregator.ids().clone();
             if !ids.is_empty() {
                 let exclude_ids = Filter::new_must_not(Condition::HasId(ids.into()));
-                source.filter = Some(
-                    source
-                        .filter
-                        .as_ref()
-                        .map(|filter| filter.merge(&exclude_ids))
-                        .unwrap_or(exclude_ids),
-                );
+                source.merge_filter(&exclude_ids);
             }
 
             // Make request
@@ -379,12 +477,19 @@ pub async fn group_by(
         .flat_map(|group| group.hits)
         .collect();
 
+    let (with_payload, with_vector) = match request.source {
+        CoreSourceRequest::Search(search) => {
+            (search.with_payload, search.with_vector.unwrap_or_default())
+        }
+        CoreSourceRequest::Query(query) => (Some(query.with_payload), query.with_vector),
+    };
+
     // enrich with payload and vector
     let enriched_points: HashMap<_, _> = collection
         .fill_search_result_with_payload(
             bare_points,
-            request.source.with_payload,
-            request.source.with_vector.unwrap_or_default(),
+            with_payload,
+            with_vector,
             read_consistency,
             &shard_selection,
         )
@@ -6,6 +6,7 @@ use segment::types::{PointIdType, ScoredPoint};
 
 use crate::lookup::WithLookup;
 use crate::operations::types::{CoreSearchRequest, PointGroup};
+use crate::operations::universal_query::shard_query::ShardQueryRequest;
 
 #[derive(PartialEq, Debug)]
 pub(super) enum AggregatorError {
@@ -43,10 +44,17 @@ impl From<Group> for PointGroup {
     }
 }
 
+/// Request with all point ids already resolved into vectors
+#[derive(Clone)]
+pub enum CoreSourceRequest {
+    Search(CoreSearchRequest),
+    Query(ShardQueryRequest),
+}
+
 #[derive(Clone)]
 pub struct CoreGroupRequest {
     /// Core request to use
-    pub source: CoreSearchRequest,
+    pub source: CoreSourceRequest,
 
     /// Path to the field to group by
     pub group_by: JsonPath,
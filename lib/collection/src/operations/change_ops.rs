@@ -0,0 +1,89 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::{ClockTag, CollectionUpdateOperations};
+
+/// Default number of changes to return at once
+pub const DEFAULT_CHANGES_LIMIT: usize = 100;
+
+/// Position of a change in the WAL of a shard
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct ChangeCursor {
+    /// Sequence number of the change in the shard WAL
+    pub op_num: u64,
+    /// Clock tag of the change, used to detect if the WAL was replaced, e.g. by a shard transfer
+    pub clock_tag: Option<ClockTag>,
+}
+
+/// Update operation accepted by a shard
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ChangeRecord {
+    /// Cursor of this change, to resume reading right after it
+    pub cursor: ChangeCursor,
+    /// Update operation, in the same format as it is stored in the WAL
+    #[schemars(with = "serde_json::Value")]
+    pub operation: CollectionUpdateOperations,
+}
+
+/// Read update operations of a shard in the order they were applied
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct ChangesRequest {
+    /// Name of the consumer. If set, the consumer is registered and the cursor is acknowledged:
+    /// the change at the cursor and all later ones are kept in the WAL until the consumer moves on
+    /// or is deleted. Consumers, which don't acknowledge changes for 24 hours, are deleted.
+    #[validate(length(min = 1))]
+    pub consumer: Option<String>,
+    /// Cursor of the last consumed change. If not set, changes are read from the oldest one still
+    /// present in the WAL.
+    pub cursor: Option<ChangeCursor>,
+    /// Maximum number of changes to return. Set to 0 to only acknowledge the cursor. Default: 100
+    pub limit: Option<usize>,
+    /// If there are no new changes yet, wait for them up to this many seconds. Default: 0
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ChangesResult {
+    /// Changes after the requested cursor, in the order they were applied
+    pub changes: Vec<ChangeRecord>,
+    /// Cursor to read the next changes from. Is not set, if there are no changes in the WAL at all
+    pub next_cursor: Option<ChangeCursor>,
+}
+
+impl From<api::grpc::qdrant::ChangeCursor> for ChangeCursor {
+    fn from(cursor: api::grpc::qdrant::ChangeCursor) -> Self {
+        let api::grpc::qdrant::ChangeCursor { op_num, clock_tag } = cursor;
+        Self {
+            op_num,
+            clock_tag: clock_tag.map(ClockTag::from),
+        }
+    }
+}
+
+impl From<ChangeCursor> for api::grpc::qdrant::ChangeCursor {
+    fn from(cursor: ChangeCursor) -> Self {
+        let ChangeCursor { op_num, clock_tag } = cursor;
+        Self {
+            op_num,
+            clock_tag: clock_tag.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<ChangeRecord> for api::grpc::qdrant::ChangeRecord {
+    type Error = CollectionError;
+
+    fn try_from(record: ChangeRecord) -> CollectionResult<Self> {
+        let ChangeRecord { cursor, operation } = record;
+        Ok(Self {
+            cursor: Some(cursor.into()),
+            operation: serde_json::to_string(&operation)?,
+        })
+    }
+}
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use validator::{Validate, ValidationErrors};
 
-use crate::config::{CollectionParams, WalConfig};
+use crate::config::{CollectionParams, StrictModeConfig, WalConfig};
 use crate::operations::types::CollectionResult;
 use crate::optimizers_builder::OptimizersConfig;
 
@@ -162,6 +162,9 @@ pub struct OptimizersConfigDiff {
     /// If null - have no limit and choose dynamically to saturate CPU.
     /// If 0 - no optimization threads, optimizations will be disabled.
     pub max_optimization_threads: Option<usize>,
+    /// The minimal fraction of deleted vectors in an HNSW graph, required to heal the graph in place.
+    /// Healing reconnects neighbours of deleted points without rebuilding the segment.
+    pub graph_healing_threshold: Option<f64>,
 }
 
 impl std::hash::Hash for OptimizersConfigDiff {
@@ -174,6 +177,9 @@ impl std::hash::Hash for OptimizersConfigDiff {
         self.indexing_threshold.hash(state);
         self.flush_interval_sec.hash(state);
         self.max_optimization_threads.hash(state);
+        self.graph_healing_threshold
+            .map(f64::to_le_bytes)
+            .hash(state);
     }
 }
 
@@ -188,6 +194,8 @@ impl PartialEq for OptimizersConfigDiff {
             && self.indexing_threshold == other.indexing_threshold
             && self.flush_interval_sec == other.flush_interval_sec
             && self.max_optimization_threads == other.max_optimization_threads
+            && self.graph_healing_threshold.map(f64::to_le_bytes)
+                == other.graph_healing_threshold.map(f64::to_le_bytes)
     }
 }
 
@@ -203,6 +211,8 @@ impl DiffConfig<WalConfig> for WalConfigDiff {}
 
 impl DiffConfig<CollectionParams> for CollectionParamsDiff {}
 
+impl DiffConfig<StrictModeConfig> for StrictModeConfig {}
+
 impl From<HnswConfig> for HnswConfigDiff {
     fn from(config: HnswConfig) -> Self {
         HnswConfigDiff::from_full(&config).unwrap()
@@ -376,6 +386,7 @@ mod tests {
             indexing_threshold: Some(50_000),
             flush_interval_sec: 30,
             max_optimization_threads: Some(1),
+            graph_healing_threshold: None,
         };
         let update: OptimizersConfigDiff =
             serde_json::from_str(r#"{ "indexing_threshold": 10000 }"#).unwrap();
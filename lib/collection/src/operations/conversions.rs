@@ -14,11 +14,15 @@ use api::grpc::qdrant::CreateShardKey;
 use api::rest::schema::ShardKeySelector;
 use common::types::ScoreType;
 use itertools::Itertools;
+use segment::data_types::bm25::Bm25Config;
+use segment::data_types::text_index::TokenizerType;
 use segment::data_types::vectors::{
     BatchVectorStruct, Named, NamedQuery, NamedVectorStruct, Vector, VectorStruct,
     DEFAULT_VECTOR_NAME,
 };
-use segment::types::{Distance, MultiVectorConfig, QuantizationConfig, ScoredPoint};
+use segment::types::{
+    Distance, MultiVectorConfig, QuantizationConfig, ScoredPoint, SparseVectorStorageType,
+};
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
 use sparse::common::sparse_vector::{validate_sparse_vector_impl, SparseVector};
 use tonic::Status;
@@ -32,7 +36,7 @@ use super::types::{
 };
 use crate::config::{
     default_replication_factor, default_write_consistency_factor, CollectionConfig,
-    CollectionParams, ShardingMethod, WalConfig,
+    CollectionParams, ShardingMethod, StrictModeConfig, WalConfig,
 };
 use crate::lookup::types::WithLookupInterface;
 use crate::lookup::WithLookup;
@@ -260,6 +264,34 @@ impl From<api::grpc::qdrant::WalConfigDiff> for WalConfigDiff {
     }
 }
 
+impl From<api::grpc::qdrant::StrictModeConfig> for StrictModeConfig {
+    fn from(value: api::grpc::qdrant::StrictModeConfig) -> Self {
+        Self {
+            enabled: value.enabled,
+            max_query_limit: value.max_query_limit.map(|v| v as usize),
+            unindexed_filtering_retrieve: value.unindexed_filtering_retrieve,
+            unindexed_filtering_update: value.unindexed_filtering_update,
+            search_max_hnsw_ef: value.search_max_hnsw_ef.map(|v| v as usize),
+            search_allow_exact: value.search_allow_exact,
+            upsert_max_batchsize: value.upsert_max_batchsize.map(|v| v as usize),
+        }
+    }
+}
+
+impl From<StrictModeConfig> for api::grpc::qdrant::StrictModeConfig {
+    fn from(value: StrictModeConfig) -> Self {
+        Self {
+            enabled: value.enabled,
+            max_query_limit: value.max_query_limit.map(|v| v as u64),
+            unindexed_filtering_retrieve: value.unindexed_filtering_retrieve,
+            unindexed_filtering_update: value.unindexed_filtering_update,
+            search_max_hnsw_ef: value.search_max_hnsw_ef.map(|v| v as u64),
+            search_allow_exact: value.search_allow_exact,
+            upsert_max_batchsize: value.upsert_max_batchsize.map(|v| v as u64),
+        }
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::CollectionParamsDiff> for CollectionParamsDiff {
     type Error = Status;
 
@@ -297,6 +329,7 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfigDiff {
             indexing_threshold: value.indexing_threshold.map(|v| v as usize),
             flush_interval_sec: value.flush_interval_sec,
             max_optimization_threads: value.max_optimization_threads.map(|v| v as usize),
+            graph_healing_threshold: value.graph_healing_threshold,
         }
     }
 }
@@ -330,6 +363,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
             segments_count,
             config,
             payload_schema,
+            snapshot_schedule: _,
         } = value;
 
         api::grpc::qdrant::CollectionInfo {
@@ -421,12 +455,14 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                         .optimizer_config
                         .max_optimization_threads
                         .map(|n| n as u64),
+                    graph_healing_threshold: config.optimizer_config.graph_healing_threshold,
                 }),
                 wal_config: Some(api::grpc::qdrant::WalConfigDiff {
                     wal_capacity_mb: Some(config.wal_config.wal_capacity_mb as u64),
                     wal_segments_ahead: Some(config.wal_config.wal_segments_ahead as u64),
                 }),
                 quantization_config: config.quantization_config.map(|x| x.into()),
+                strict_mode_config: config.strict_mode_config.map(|x| x.into()),
             }),
             payload_schema: payload_schema
                 .into_iter()
@@ -478,6 +514,7 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
             max_optimization_threads: optimizer_config
                 .max_optimization_threads
                 .map(|n| n as usize),
+            graph_healing_threshold: optimizer_config.graph_healing_threshold,
         }
     }
 }
@@ -552,6 +589,8 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
                 .multivector_config
                 .map(MultiVectorConfig::try_from)
                 .transpose()?,
+            validation: vector_params.validation.map(Into::into),
+            normalize: vector_params.normalize,
         })
     }
 }
@@ -609,11 +648,72 @@ impl From<api::grpc::qdrant::SparseVectorParams> for SparseVectorParams {
                 .map(|index_config| SparseIndexParams {
                     full_scan_threshold: index_config.full_scan_threshold.map(|v| v as usize),
                     on_disk: index_config.on_disk,
+                    datatype: convert_datatype_from_proto(index_config.datatype)
+                        .ok()
+                        .flatten(),
                 }),
             modifier: sparse_vector_params
                 .modifier
                 .and_then(api::grpc::qdrant::Modifier::from_i32)
                 .map(Modifier::from),
+            bm25: sparse_vector_params.bm25.map(Bm25Config::from),
+            storage_type: sparse_vector_params
+                .storage_type
+                .and_then(api::grpc::qdrant::SparseVectorStorageType::from_i32)
+                .map(SparseVectorStorageType::from),
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::SparseVectorStorageType> for SparseVectorStorageType {
+    fn from(value: api::grpc::qdrant::SparseVectorStorageType) -> Self {
+        match value {
+            api::grpc::qdrant::SparseVectorStorageType::SparseOnDisk => {
+                SparseVectorStorageType::OnDisk
+            }
+            api::grpc::qdrant::SparseVectorStorageType::SparseMmap => SparseVectorStorageType::Mmap,
+        }
+    }
+}
+
+impl From<SparseVectorStorageType> for api::grpc::qdrant::SparseVectorStorageType {
+    fn from(value: SparseVectorStorageType) -> Self {
+        match value {
+            SparseVectorStorageType::OnDisk => {
+                api::grpc::qdrant::SparseVectorStorageType::SparseOnDisk
+            }
+            SparseVectorStorageType::Mmap => api::grpc::qdrant::SparseVectorStorageType::SparseMmap,
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::Bm25Config> for Bm25Config {
+    fn from(config: api::grpc::qdrant::Bm25Config) -> Self {
+        Self {
+            k: config.k,
+            b: config.b,
+            avg_len: config.avg_len,
+            // Unknown tokenizer falls back to the default one
+            tokenizer: api::grpc::qdrant::TokenizerType::from_i32(config.tokenizer)
+                .and_then(|tokenizer| TokenizerType::try_from(tokenizer).ok())
+                .unwrap_or_default(),
+            min_token_len: config.min_token_len.map(|v| v as usize),
+            max_token_len: config.max_token_len.map(|v| v as usize),
+            lowercase: config.lowercase,
+        }
+    }
+}
+
+impl From<Bm25Config> for api::grpc::qdrant::Bm25Config {
+    fn from(config: Bm25Config) -> Self {
+        Self {
+            k: config.k,
+            b: config.b,
+            avg_len: config.avg_len,
+            tokenizer: api::grpc::qdrant::TokenizerType::from(config.tokenizer) as i32,
+            min_token_len: config.min_token_len.map(|v| v as u64),
+            max_token_len: config.max_token_len.map(|v| v as u64),
+            lowercase: config.lowercase,
         }
     }
 }
@@ -634,11 +734,20 @@ impl From<SparseVectorParams> for api::grpc::qdrant::SparseVectorParams {
                 api::grpc::qdrant::SparseIndexConfig {
                     full_scan_threshold: index_config.full_scan_threshold.map(|v| v as u64),
                     on_disk: index_config.on_disk,
+                    datatype: index_config
+                        .datatype
+                        .map(|datatype| api::grpc::qdrant::Datatype::from(datatype) as i32),
                 }
             }),
             modifier: sparse_vector_params
                 .modifier
                 .map(|modifier| api::grpc::qdrant::Modifier::from(modifier) as i32),
+            bm25: sparse_vector_params
+                .bm25
+                .map(api::grpc::qdrant::Bm25Config::from),
+            storage_type: sparse_vector_params.storage_type.map(|storage_type| {
+                api::grpc::qdrant::SparseVectorStorageType::from(storage_type) as i32
+            }),
         }
     }
 }
@@ -749,6 +858,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     None
                 }
             },
+            strict_mode_config: config.strict_mode_config.map(StrictModeConfig::from),
         })
     }
 }
@@ -794,6 +904,7 @@ impl TryFrom<api::grpc::qdrant::GetCollectionInfoResponse> for CollectionInfo {
                     .into_iter()
                     .map(|(k, v)| Ok::<_, Status>((json_path_from_proto(&k)?, v.try_into()?)))
                     .try_collect()?,
+                snapshot_schedule: None,
             }),
         }
     }
@@ -816,7 +927,7 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
             Some(proto_to_payloads(payload)?)
         };
 
-        let vector_struct: VectorStruct = match vectors {
+        let vector_struct: api::rest::VectorStruct = match vectors {
             None => return Err(Status::invalid_argument("Expected some vectors")),
             Some(vectors) => vectors.try_into()?,
         };
@@ -825,7 +936,7 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
             id: id
                 .ok_or_else(|| Status::invalid_argument("Empty ID is not allowed"))?
                 .try_into()?,
-            vector: api::rest::VectorStruct::from(vector_struct),
+            vector: vector_struct,
             payload: converted_payload,
         })
     }
@@ -1645,6 +1756,8 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             multivector_config: value
                 .multivec_config
                 .map(api::grpc::qdrant::MultiVectorConfig::from),
+            validation: value.validation.map(Into::into),
+            normalize: value.normalize,
         }
     }
 }
@@ -0,0 +1,158 @@
+use api::rest::{BatchVectorStruct, Document, DocumentModel, Vector, VectorStruct};
+use segment::data_types::bm25::Bm25;
+use sparse::common::sparse_vector::SparseVector;
+
+use super::point_ops::{PointInsertOperationsInternal, PointOperations, PointStruct};
+use super::types::{CollectionError, CollectionResult};
+use super::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest, Query, VectorInput,
+};
+use super::vector_ops::VectorOperations;
+use super::CollectionUpdateOperations;
+use crate::config::CollectionParams;
+
+/// Conversion of text documents in a request into sparse vectors, using the models configured
+/// on the sparse vectors of a collection.
+///
+/// Documents are inferred on the node which receives the request, shards only see vectors.
+pub trait DocumentInference {
+    fn infer_documents(&mut self, params: &CollectionParams) -> CollectionResult<()>;
+}
+
+/// Convert a document into a sparse vector, to be stored in or searched with `vector_name`
+fn infer_document(
+    params: &CollectionParams,
+    vector_name: &str,
+    document: &Document,
+    is_query: bool,
+) -> CollectionResult<SparseVector> {
+    let Some(sparse_params) = params.get_sparse_vector_params_opt(vector_name) else {
+        return Err(CollectionError::bad_input(format!(
+            "Documents can only be used with sparse vectors, but vector `{vector_name}` is not sparse"
+        )));
+    };
+
+    match document.model {
+        DocumentModel::Bm25 => {
+            let Some(bm25_config) = &sparse_params.bm25 else {
+                return Err(CollectionError::bad_input(format!(
+                    "Sparse vector `{vector_name}` has no bm25 config, documents can't be converted"
+                )));
+            };
+            let bm25 = Bm25::new(bm25_config);
+            Ok(if is_query {
+                bm25.query_embed(&document.text)
+            } else {
+                bm25.doc_embed(&document.text)
+            })
+        }
+    }
+}
+
+fn infer_vector_struct(
+    params: &CollectionParams,
+    vector_struct: &mut VectorStruct,
+) -> CollectionResult<()> {
+    match vector_struct {
+        VectorStruct::Single(_) => {}
+        VectorStruct::Multi(vectors) => {
+            for (name, vector) in vectors.iter_mut() {
+                infer_vector(params, name, vector)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn infer_vector(
+    params: &CollectionParams,
+    name: &str,
+    vector: &mut Vector,
+) -> CollectionResult<()> {
+    if let Vector::Document(document) = vector {
+        *vector = Vector::Sparse(infer_document(params, name, document, false)?);
+    }
+    Ok(())
+}
+
+fn infer_points(params: &CollectionParams, points: &mut [PointStruct]) -> CollectionResult<()> {
+    points
+        .iter_mut()
+        .try_for_each(|point| infer_vector_struct(params, &mut point.vector))
+}
+
+impl DocumentInference for CollectionUpdateOperations {
+    fn infer_documents(&mut self, params: &CollectionParams) -> CollectionResult<()> {
+        match self {
+            CollectionUpdateOperations::PointOperation(operation) => match operation {
+                PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(
+                    points,
+                )) => infer_points(params, points),
+                PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsBatch(
+                    batch,
+                )) => match &mut batch.vectors {
+                    BatchVectorStruct::Single(_) => Ok(()),
+                    BatchVectorStruct::Multi(vectors) => {
+                        for (name, vectors) in vectors.iter_mut() {
+                            for vector in vectors.iter_mut() {
+                                infer_vector(params, name, vector)?;
+                            }
+                        }
+                        Ok(())
+                    }
+                },
+                PointOperations::SyncPoints(operation) => {
+                    infer_points(params, &mut operation.points)
+                }
+                PointOperations::DeletePoints { .. } | PointOperations::DeletePointsByFilter(_) => {
+                    Ok(())
+                }
+            },
+            CollectionUpdateOperations::VectorOperation(operation) => match operation {
+                VectorOperations::UpdateVectors(operation) => operation
+                    .points
+                    .iter_mut()
+                    .try_for_each(|point| infer_vector_struct(params, &mut point.vector)),
+                VectorOperations::DeleteVectors(..)
+                | VectorOperations::DeleteVectorsByFilter(..) => Ok(()),
+            },
+            CollectionUpdateOperations::PayloadOperation(_)
+            | CollectionUpdateOperations::FieldIndexOperation(_) => Ok(()),
+        }
+    }
+}
+
+fn infer_query(
+    params: &CollectionParams,
+    using: &str,
+    query: &mut Option<Query>,
+) -> CollectionResult<()> {
+    let Some(Query::Vector(vector_query)) = query else {
+        return Ok(());
+    };
+    for input in vector_query.flat_iter_mut() {
+        if let VectorInput::Document(document) = input {
+            let vector = infer_document(params, using, document, true)?;
+            *input = VectorInput::Vector(vector.into());
+        }
+    }
+    Ok(())
+}
+
+impl DocumentInference for CollectionPrefetch {
+    fn infer_documents(&mut self, params: &CollectionParams) -> CollectionResult<()> {
+        for prefetch in self.prefetch.iter_mut() {
+            prefetch.infer_documents(params)?;
+        }
+        infer_query(params, &self.using, &mut self.query)
+    }
+}
+
+impl DocumentInference for CollectionQueryRequest {
+    fn infer_documents(&mut self, params: &CollectionParams) -> CollectionResult<()> {
+        for prefetch in self.prefetch.iter_mut() {
+            prefetch.infer_documents(params)?;
+        }
+        infer_query(params, &self.using, &mut self.query)
+    }
+}
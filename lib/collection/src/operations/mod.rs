@@ -1,8 +1,10 @@
+pub mod change_ops;
 pub mod cluster_ops;
 pub mod config_diff;
 pub mod consistency_params;
 pub mod conversions;
 pub mod conversions_rest;
+pub mod document_inference;
 pub mod operation_effect;
 pub mod payload_ops;
 pub mod point_ops;
@@ -16,16 +18,20 @@ pub mod universal_query;
 pub mod validation;
 pub mod vector_ops;
 pub mod vector_params_builder;
+pub mod verification;
 
 use std::collections::HashMap;
 
+use schemars::JsonSchema;
 use segment::json_path::JsonPath;
 use segment::types::{ExtendedPointId, PayloadFieldSchema};
 use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 use validator::Validate;
 
+use crate::config::CollectionParams;
 use crate::hash_ring::{HashRing, ShardIds};
+use crate::operations::types::CollectionResult;
 use crate::shards::shard::{PeerId, ShardId};
 
 pub type ClockToken = u64;
@@ -74,7 +80,7 @@ impl From<CollectionUpdateOperations> for OperationWithClockTag {
     }
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 pub struct ClockTag {
     pub peer_id: PeerId,
     pub clock_id: u32,
@@ -207,6 +213,22 @@ impl Validate for CollectionUpdateOperations {
     }
 }
 
+impl CollectionUpdateOperations {
+    /// Check vectors of the operation against the validation configured in the collection
+    pub fn check_vectors_validation(&self, params: &CollectionParams) -> CollectionResult<()> {
+        match self {
+            CollectionUpdateOperations::PointOperation(operation) => {
+                operation.check_vectors_validation(params)
+            }
+            CollectionUpdateOperations::VectorOperation(operation) => {
+                operation.check_vectors_validation(params)
+            }
+            CollectionUpdateOperations::PayloadOperation(_)
+            | CollectionUpdateOperations::FieldIndexOperation(_) => Ok(()),
+        }
+    }
+}
+
 /// Get the shards for a point ID
 ///
 /// Normally returns a single shard ID. Might return multiple if resharding is currently in
@@ -7,14 +7,15 @@ use schemars::JsonSchema;
 use segment::common::utils::transpose_map_into_named_vector;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::{Vector, DEFAULT_VECTOR_NAME};
-use segment::types::{Filter, Payload, PointIdType};
+use segment::types::{Filter, Payload, PointIdType, VectorValidationConfig};
 use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 use validator::Validate;
 
 use super::{point_to_shards, split_iter_by_shard, OperationToShard, SplitByShard};
+use crate::config::CollectionParams;
 use crate::hash_ring::HashRing;
-use crate::operations::types::Record;
+use crate::operations::types::{CollectionResult, Record};
 use crate::shards::shard::ShardId;
 
 /// Defines write ordering guarantees for collection operations
@@ -372,6 +373,99 @@ impl Validate for PointOperations {
     }
 }
 
+impl PointOperations {
+    /// Check vectors of the operation against the validation configured in the collection
+    pub fn check_vectors_validation(&self, params: &CollectionParams) -> CollectionResult<()> {
+        match self {
+            PointOperations::UpsertPoints(upsert_points) => {
+                upsert_points.check_vectors_validation(params)
+            }
+            PointOperations::SyncPoints(operation) => operation
+                .points
+                .iter()
+                .try_for_each(|point| check_vector_struct(params, point.id, &point.vector)),
+            PointOperations::DeletePoints { .. } | PointOperations::DeletePointsByFilter(_) => {
+                Ok(())
+            }
+        }
+    }
+}
+
+impl PointInsertOperationsInternal {
+    /// Check vectors of the points against the validation configured in the collection
+    pub fn check_vectors_validation(&self, params: &CollectionParams) -> CollectionResult<()> {
+        match self {
+            PointInsertOperationsInternal::PointsList(points) => points
+                .iter()
+                .try_for_each(|point| check_vector_struct(params, point.id, &point.vector)),
+            PointInsertOperationsInternal::PointsBatch(batch) => match &batch.vectors {
+                BatchVectorStruct::Single(vectors) => {
+                    izip!(&batch.ids, vectors).try_for_each(|(&id, vector)| {
+                        check_dense_vector(params, id, DEFAULT_VECTOR_NAME, vector)
+                    })
+                }
+                BatchVectorStruct::Multi(named_vectors) => {
+                    named_vectors.iter().try_for_each(|(name, vectors)| {
+                        izip!(&batch.ids, vectors)
+                            .try_for_each(|(&id, vector)| check_vector(params, id, name, vector))
+                    })
+                }
+            },
+        }
+    }
+}
+
+/// Check all vectors of a point against the validation configured in the collection
+pub(super) fn check_vector_struct(
+    params: &CollectionParams,
+    point_id: PointIdType,
+    vector_struct: &VectorStruct,
+) -> CollectionResult<()> {
+    match vector_struct {
+        VectorStruct::Single(vector) => {
+            check_dense_vector(params, point_id, DEFAULT_VECTOR_NAME, vector)
+        }
+        VectorStruct::Multi(vectors) => vectors
+            .iter()
+            .try_for_each(|(name, vector)| check_vector(params, point_id, name, vector)),
+    }
+}
+
+fn check_vector(
+    params: &CollectionParams,
+    point_id: PointIdType,
+    vector_name: &str,
+    vector: &api::rest::Vector,
+) -> CollectionResult<()> {
+    match vector {
+        api::rest::Vector::Dense(vector) => {
+            check_dense_vector(params, point_id, vector_name, vector)
+        }
+        api::rest::Vector::MultiDense(vectors) => vectors
+            .iter()
+            .try_for_each(|vector| check_dense_vector(params, point_id, vector_name, vector)),
+        api::rest::Vector::Sparse(_) | api::rest::Vector::Document(_) => Ok(()),
+    }
+}
+
+fn check_dense_vector(
+    params: &CollectionParams,
+    point_id: PointIdType,
+    vector_name: &str,
+    vector: &[f32],
+) -> CollectionResult<()> {
+    let Some(validation) = params
+        .vectors
+        .get_params(vector_name)
+        .and_then(|vector_params| vector_params.validation)
+    else {
+        return Ok(());
+    };
+    validation
+        .check_dense(vector)
+        .map_err(|problem| VectorValidationConfig::error(point_id, vector_name, problem).into())
+}
+
 impl SplitByShard for Batch {
     fn split_by_shard(self, ring: &HashRing) -> OperationToShard<Self> {
         let batch = self;
@@ -1,5 +1,10 @@
-use segment::data_types::vectors::{DenseVector, Named, NamedQuery, NamedVectorStruct, Vector};
-use segment::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery};
+use segment::common::operation_error::OperationResult;
+use segment::data_types::token_pooling::pool_tokens;
+use segment::data_types::vectors::{
+    DenseVector, Named, NamedMultiDenseVector, NamedQuery, NamedVectorStruct, Vector,
+};
+use segment::types::TokenPooling;
+use segment::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery, TransformInto};
 use sparse::common::sparse_vector::SparseVector;
 
 impl QueryEnum {
@@ -22,6 +27,42 @@ impl QueryEnum {
         }
     }
 
+    /// Reduce the number of token vectors of all multivectors of the query down to `max_tokens`
+    pub fn pool_tokens(self, max_tokens: usize, pooling: TokenPooling) -> OperationResult<Self> {
+        let pool = |vector: Vector| -> OperationResult<Vector> {
+            Ok(match vector {
+                Vector::MultiDense(multi_vector) => {
+                    Vector::MultiDense(pool_tokens(multi_vector, max_tokens, pooling))
+                }
+                Vector::Dense(_) | Vector::Sparse(_) => vector,
+            })
+        };
+        Ok(match self {
+            QueryEnum::Nearest(NamedVectorStruct::MultiDense(NamedMultiDenseVector {
+                name,
+                vector,
+            })) => QueryEnum::Nearest(NamedVectorStruct::MultiDense(NamedMultiDenseVector {
+                name,
+                vector: pool_tokens(vector, max_tokens, pooling),
+            })),
+            QueryEnum::Nearest(_) => self,
+            QueryEnum::RecommendBestScore(NamedQuery { query, using }) => {
+                QueryEnum::RecommendBestScore(NamedQuery {
+                    query: query.transform(pool)?,
+                    using,
+                })
+            }
+            QueryEnum::Discover(NamedQuery { query, using }) => QueryEnum::Discover(NamedQuery {
+                query: query.transform(pool)?,
+                using,
+            }),
+            QueryEnum::Context(NamedQuery { query, using }) => QueryEnum::Context(NamedQuery {
+                query: query.transform(pool)?,
+                using,
+            }),
+        })
+    }
+
     pub fn iterate_sparse(&self, mut f: impl FnMut(&str, &SparseVector)) {
         match self {
             QueryEnum::Nearest(vector) => match vector {
@@ -2,13 +2,15 @@ use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 use api::grpc::conversions::naive_date_time_to_proto;
-use chrono::{DateTime, NaiveDateTime};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
 use serde::{Deserialize, Serialize};
 use url::Url;
 use validator::Validate;
 
 use crate::operations::types::CollectionResult;
+use crate::shards::shard::ShardId;
 
 /// Defines source of truth for snapshot recovery:
 /// `NoSync` means - restore snapshot without *any* additional synchronization.
@@ -101,6 +103,49 @@ impl From<SnapshotDescription> for api::grpc::qdrant::SnapshotDescription {
     }
 }
 
+/// State of scheduled snapshots, of a collection or of the whole storage
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct SnapshotScheduleStatus {
+    /// Interval between scheduled snapshots in seconds
+    pub interval_sec: u64,
+    /// Time of the next scheduled snapshot
+    pub next_run: DateTime<Utc>,
+    /// Time the last scheduled snapshot was started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<DateTime<Utc>>,
+    /// Snapshots created by the last run
+    pub last_snapshots: Vec<String>,
+    /// Error of the last run, if it failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Number of snapshots created by the schedule
+    pub created: usize,
+    /// Number of snapshots deleted by the retention policy
+    pub deleted: usize,
+    /// Number of failed runs
+    pub failed: usize,
+    /// Shards this peer took snapshots of in the last run, only in cluster mode.
+    /// Every shard is taken by a single peer holding an active replica of it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shards: Option<Vec<ShardId>>,
+}
+
+impl Anonymize for SnapshotScheduleStatus {
+    fn anonymize(&self) -> Self {
+        SnapshotScheduleStatus {
+            interval_sec: self.interval_sec,
+            next_run: self.next_run,
+            last_run: self.last_run,
+            last_snapshots: Vec::new(),
+            last_error: None,
+            created: self.created,
+            deleted: self.deleted,
+            failed: self.failed,
+            shards: self.shards.clone(),
+        }
+    }
+}
+
 pub async fn get_snapshot_description(path: &Path) -> CollectionResult<SnapshotDescription> {
     let name = path.file_name().unwrap().to_str().unwrap();
     let file_meta = tokio::fs::metadata(&path).await?;
@@ -8,6 +8,9 @@ use tokio::io::AsyncWriteExt;
 
 use super::snapshot_ops::SnapshotDescription;
 use super::types::{CollectionError, CollectionResult};
+use crate::common::snapshot_manifest::{is_manifest_path, SnapshotManifest};
+
+const TAR_HEADER_SIZE: usize = 512;
 
 pub(crate) fn trim_dot_slash(path: &Path) -> CollectionResult<object_store::path::Path> {
     // Get file name by trimming the path.
@@ -70,13 +73,12 @@ pub async fn get_snapshot_description(
 ///     partsize: min 5 MB, max 5 GB, up to 10,000 parts.
 /// * Azure Storage: https://learn.microsoft.com/en-us/rest/api/storageservices/put-blob?tabs=microsoft-entra-id#remarks
 ///     <TODO> It looks like Azure Storage has different limits for different service versions.
-pub async fn get_appropriate_chunk_size(local_source_path: &Path) -> CollectionResult<usize> {
+pub fn get_appropriate_chunk_size(file_size: u64) -> CollectionResult<usize> {
     const DEFAULT_CHUNK_SIZE: usize = 50 * 1024 * 1024;
     const MAX_PART_NUMBER: usize = 10000;
     const MAX_UPLOAD_SIZE: usize = 5 * 1024 * 1024 * 1024;
 
-    let file_meta = tokio::fs::metadata(local_source_path).await?;
-    let file_size = file_meta.len() as usize;
+    let file_size = file_size as usize;
 
     // check if the file size exceeds the maximum upload size
     if file_size > MAX_UPLOAD_SIZE {
@@ -99,6 +101,18 @@ pub async fn multipart_upload(
     client: &dyn object_store::ObjectStore,
     source_path: &Path,
     target_path: &Path,
+) -> CollectionResult<()> {
+    let file = File::open(source_path)?;
+    multipart_upload_file(client, file, target_path).await
+}
+
+/// Upload an already opened file.
+///
+/// The file stays readable through the handle, even if it is moved or removed meanwhile.
+pub async fn multipart_upload_file(
+    client: &dyn object_store::ObjectStore,
+    file: File,
+    target_path: &Path,
 ) -> CollectionResult<()> {
     let s3_path = trim_dot_slash(target_path)?;
     let upload = client
@@ -106,9 +120,8 @@ pub async fn multipart_upload(
         .await
         .map_err(|e| CollectionError::service_error(format!("Failed to put multipart: {}", e)))?;
 
-    let chunk_size: usize = get_appropriate_chunk_size(source_path).await?;
+    let chunk_size: usize = get_appropriate_chunk_size(file.metadata()?.len())?;
     let mut write = WriteMultipart::new_with_chunk_size(upload, chunk_size);
-    let file = File::open(source_path)?;
     let mut reader = BufReader::new(file);
     let mut buffer = vec![0u8; chunk_size];
 
@@ -164,6 +177,47 @@ pub async fn delete_snapshot(
     Ok(true)
 }
 
+/// Move a snapshot to `target_path`, replacing an object stored there
+pub async fn rename_snapshot(
+    client: &dyn object_store::ObjectStore,
+    source_path: &Path,
+    target_path: &Path,
+) -> CollectionResult<()> {
+    client
+        .rename(&trim_dot_slash(source_path)?, &trim_dot_slash(target_path)?)
+        .await
+        .map_err(|e| CollectionError::service_error(format!("Failed to rename snapshot: {}", e)))?;
+    Ok(())
+}
+
+/// Read manifest of a stored snapshot, fetching only the first entry of the archive
+pub async fn read_snapshot_manifest(
+    client: &dyn object_store::ObjectStore,
+    path: &Path,
+) -> CollectionResult<Option<SnapshotManifest>> {
+    let s3_path = trim_dot_slash(path)?;
+    let get_error = |e| CollectionError::service_error(format!("Failed to get {}: {}", s3_path, e));
+
+    let header_bytes = client
+        .get_range(&s3_path, 0..TAR_HEADER_SIZE)
+        .await
+        .map_err(get_error)?;
+    if header_bytes.len() < TAR_HEADER_SIZE {
+        return Ok(None);
+    }
+    let header = tar::Header::from_byte_slice(&header_bytes);
+    if !is_manifest_path(&header.path()?) {
+        return Ok(None);
+    }
+
+    let entry_size = header.entry_size()? as usize;
+    let entry_bytes = client
+        .get_range(&s3_path, 0..TAR_HEADER_SIZE + entry_size)
+        .await
+        .map_err(get_error)?;
+    SnapshotManifest::read_from_archive(&entry_bytes[..])
+}
+
 pub async fn download_snapshot(
     client: &dyn object_store::ObjectStore,
     path: &Path,
@@ -7,7 +7,7 @@ use std::num::NonZeroU64;
 use std::time::SystemTimeError;
 
 use api::grpc::transport_channel_pool::RequestError;
-use api::rest::{OrderByInterface, RecommendStrategy, ShardKeySelector};
+use api::rest::{OrderByInterface, Prefetch, QueryInterface, RecommendStrategy, ShardKeySelector};
 use common::defaults;
 use common::types::ScoreType;
 use common::validation::validate_range_generic;
@@ -17,6 +17,8 @@ use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::operation_error::OperationError;
+use segment::common::utils::MaybeOneOrMany;
+use segment::data_types::bm25::Bm25Config;
 use segment::data_types::groups::GroupId;
 use segment::data_types::vectors::{
     DenseVector, QueryVector, VectorRef, VectorStruct, DEFAULT_VECTOR_NAME,
@@ -24,8 +26,8 @@ use segment::data_types::vectors::{
 use segment::json_path::{JsonPath, JsonPathInterface};
 use segment::types::{
     Distance, Filter, MultiVectorConfig, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType,
-    QuantizationConfig, SearchParams, SeqNumberType, ShardKey, VectorStorageDatatype,
-    WithPayloadInterface, WithVector,
+    QuantizationConfig, SearchParams, SeqNumberType, ShardKey, SparseVectorStorageType,
+    VectorStorageDatatype, VectorValidationConfig, WithPayloadInterface, WithVector,
 };
 use semver::Version;
 use serde;
@@ -45,6 +47,7 @@ use crate::config::{CollectionConfig, CollectionParams};
 use crate::lookup::types::WithLookupInterface;
 use crate::operations::config_diff::{HnswConfigDiff, QuantizationConfigDiff};
 use crate::operations::query_enum::QueryEnum;
+use crate::operations::snapshot_ops::SnapshotScheduleStatus;
 use crate::save_on_disk;
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
@@ -126,6 +129,9 @@ pub struct CollectionInfo {
     pub config: CollectionConfig,
     /// Types of stored payload
     pub payload_schema: HashMap<PayloadKeyType, PayloadIndexInfo>,
+    /// Scheduled snapshots of the collection on this peer, if configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot_schedule: Option<SnapshotScheduleStatus>,
 }
 
 impl CollectionInfo {
@@ -139,6 +145,7 @@ impl CollectionInfo {
             segments_count: 0,
             config: collection_config,
             payload_schema: HashMap::new(),
+            snapshot_schedule: None,
         }
     }
 }
@@ -154,6 +161,7 @@ impl From<CollectionInfoInternal> for CollectionInfo {
             segments_count: info.segments_count,
             config: info.config,
             payload_schema: info.payload_schema,
+            snapshot_schedule: None,
         }
     }
 }
@@ -460,6 +468,52 @@ pub struct SearchGroupsRequestInternal {
     pub group_request: BaseGroupRequest,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct QueryGroupsRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub query_group_request: QueryGroupsRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct QueryGroupsRequestInternal {
+    /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetch(es).
+    #[validate]
+    #[serde(default, with = "MaybeOneOrMany")]
+    #[schemars(with = "MaybeOneOrMany<Prefetch>")]
+    pub prefetch: Option<Vec<Prefetch>>,
+
+    /// Query to perform. If missing without prefetches, returns points ordered by their IDs.
+    #[validate]
+    pub query: Option<QueryInterface>,
+
+    /// Define which vector name to use for querying. If missing, the default vector is used.
+    pub using: Option<String>,
+
+    /// Filter conditions - return only those points that satisfy the specified conditions.
+    #[validate]
+    pub filter: Option<Filter>,
+
+    /// Search params for when there is no prefetch
+    pub params: Option<SearchParams>,
+
+    /// Return points with scores better than this threshold.
+    pub score_threshold: Option<ScoreType>,
+
+    /// Options for specifying which vectors to include into the response. Default is false.
+    pub with_vector: Option<WithVector>,
+
+    /// Options for specifying which payload to include or not. Default is false.
+    pub with_payload: Option<WithPayloadInterface>,
+
+    #[serde(flatten)]
+    #[validate]
+    pub group_request: BaseGroupRequest,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
 pub struct PointRequest {
     #[serde(flatten)]
@@ -867,6 +921,8 @@ pub enum CollectionError {
     PreConditionFailed { description: String },
     #[error("Object Store error: {what}")]
     ObjectStoreError { what: String },
+    #[error("Forbidden by strict mode: {description}")]
+    StrictMode { description: String },
 }
 
 impl CollectionError {
@@ -941,6 +997,12 @@ impl CollectionError {
         }
     }
 
+    pub fn strict_mode(description: impl Into<String>) -> CollectionError {
+        CollectionError::StrictMode {
+            description: description.into(),
+        }
+    }
+
     /// Returns true if the error is transient and the operation can be retried.
     /// Returns false if the error is not transient and the operation should fail on all replicas.
     pub fn is_transient(&self) -> bool {
@@ -960,6 +1022,7 @@ impl CollectionError {
             Self::InconsistentShardFailure { .. } => false,
             Self::ForwardProxyError { .. } => false,
             Self::ObjectStoreError { .. } => false,
+            Self::StrictMode { .. } => false,
         }
     }
 }
@@ -1282,7 +1345,19 @@ pub struct VectorParams {
     pub datatype: Option<Datatype>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
     pub multivec_config: Option<MultiVectorConfig>,
+
+    /// Checks of input vectors, invalid vectors are rejected on insert.
+    /// Default: no checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation: Option<VectorValidationConfig>,
+
+    /// If true, vectors are normalized to unit length on insert, whatever the distance is.
+    /// Vectors of `Cosine` distance are always normalized.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<bool>,
 }
 
 /// Validate the value is in `[1, 65536]` or `None`.
@@ -1329,6 +1404,18 @@ pub struct SparseVectorParams {
     /// Default: none
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub modifier: Option<Modifier>,
+
+    /// Allows to upsert and query text documents, which are converted into sparse vectors
+    /// of BM25 term weights on the server side. Default: none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub bm25: Option<Bm25Config>,
+
+    /// Type of storage of sparse vectors.
+    /// Changing it moves the vectors into the new storage during optimization.
+    /// Default: `mmap` for new collections, `on_disk` for collections created before `mmap` storage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_type: Option<SparseVectorStorageType>,
 }
 
 impl Anonymize for SparseVectorParams {
@@ -1336,6 +1423,8 @@ impl Anonymize for SparseVectorParams {
         Self {
             index: self.index.anonymize(),
             modifier: self.modifier.clone(),
+            bm25: self.bm25.clone(),
+            storage_type: self.storage_type,
         }
     }
 }
@@ -1352,6 +1441,12 @@ pub struct SparseIndexParams {
     /// Store index on disk. If set to false, the index will be stored in RAM. Default: false
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+    /// Defines which datatype should be used for the index weights.
+    /// - `float16` halves the index size at the cost of precision.
+    /// - `uint8` quantizes weights of each posting list into 256 levels between its min and max weight.
+    /// Default: float32
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<Datatype>,
 }
 
 impl Anonymize for SparseIndexParams {
@@ -1359,15 +1454,21 @@ impl Anonymize for SparseIndexParams {
         SparseIndexParams {
             full_scan_threshold: self.full_scan_threshold,
             on_disk: self.on_disk,
+            datatype: self.datatype,
         }
     }
 }
 
 impl SparseIndexParams {
-    pub fn new(full_scan_threshold: Option<usize>, on_disk: Option<bool>) -> Self {
+    pub fn new(
+        full_scan_threshold: Option<usize>,
+        on_disk: Option<bool>,
+        datatype: Option<Datatype>,
+    ) -> Self {
         SparseIndexParams {
             full_scan_threshold,
             on_disk,
+            datatype,
         }
     }
 
@@ -1378,6 +1479,9 @@ impl SparseIndexParams {
         if let Some(on_disk) = other.on_disk {
             self.on_disk = Some(on_disk);
         }
+        if let Some(datatype) = other.datatype {
+            self.datatype = Some(datatype);
+        }
     }
 }
 
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use api::rest::RecommendStrategy;
+use api::rest::{Document, RecommendStrategy};
 use common::types::ScoreType;
 use itertools::Itertools;
 use segment::data_types::order_by::OrderBy;
@@ -20,6 +20,7 @@ use crate::operations::types::{CollectionError, CollectionResult};
 use crate::recommendations::avg_vector_for_recommendation;
 
 /// Internal representation of a query request, used to converge from REST and gRPC. This can have IDs referencing vectors.
+#[derive(Clone, Debug, PartialEq)]
 pub struct CollectionQueryRequest {
     pub prefetch: Vec<CollectionPrefetch>,
     pub query: Option<Query>,
@@ -44,6 +45,7 @@ impl CollectionQueryRequest {
     const DEFAULT_WITH_PAYLOAD: WithPayloadInterface = WithPayloadInterface::Bool(false);
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum Query {
     /// Score points against some vector(s)
     Vector(VectorQuery<VectorInput>),
@@ -67,7 +69,7 @@ impl Query {
             Query::Vector(vector_query) => {
                 let query_enum = vector_query
                     // Homogenize the input into raw vectors
-                    .ids_into_vectors(ids_to_vectors, lookup_vector_name, lookup_collection)
+                    .ids_into_vectors(ids_to_vectors, lookup_vector_name, lookup_collection)?
                     // Turn into QueryEnum
                     .into_query_enum(using)?;
 
@@ -80,20 +82,25 @@ impl Query {
         Ok(scoring_query)
     }
 }
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum VectorInput {
     Id(PointIdType),
     Vector(Vector),
+    /// Text document, must be inferred into a vector before the ids are resolved
+    Document(Document),
 }
 
 impl VectorInput {
     pub fn as_id(&self) -> Option<&PointIdType> {
         match self {
             VectorInput::Id(id) => Some(id),
-            VectorInput::Vector(_) => None,
+            VectorInput::Vector(_) | VectorInput::Document(_) => None,
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum VectorQuery<T> {
     Nearest(T),
     RecommendAverageVector(RecoQuery<T>),
@@ -113,26 +120,56 @@ impl<T> VectorQuery<T> {
             VectorQuery::Context(query) => Box::new(query.flat_iter()),
         }
     }
+
+    /// Iterate mutably through all items, without any kind of structure
+    pub fn flat_iter_mut(&mut self) -> Box<dyn Iterator<Item = &mut T> + '_> {
+        match self {
+            VectorQuery::Nearest(input) => Box::new(std::iter::once(input)),
+            VectorQuery::RecommendAverageVector(query) | VectorQuery::RecommendBestScore(query) => {
+                Box::new(query.positives.iter_mut().chain(query.negatives.iter_mut()))
+            }
+            VectorQuery::Discover(query) => Box::new(
+                std::iter::once(&mut query.target).chain(
+                    query
+                        .pairs
+                        .iter_mut()
+                        .flat_map(|pair| [&mut pair.positive, &mut pair.negative]),
+                ),
+            ),
+            VectorQuery::Context(query) => Box::new(
+                query
+                    .pairs
+                    .iter_mut()
+                    .flat_map(|pair| [&mut pair.positive, &mut pair.negative]),
+            ),
+        }
+    }
 }
 
 impl VectorQuery<VectorInput> {
     /// Turns all [VectorInput]s into [Vector]s, using the provided [ReferencedVectors] to look up the vectors.
     ///
-    /// Will panic if the ids are not found in the [ReferencedVectors].
+    /// Returns an error if a required vector can't be resolved, e.g. a text document which was
+    /// not inferred into a vector.
     fn ids_into_vectors(
         self,
         ids_to_vectors: &ReferencedVectors,
         lookup_vector_name: &str,
         lookup_collection: Option<&String>,
-    ) -> VectorQuery<Vector> {
-        match self {
-            VectorQuery::Nearest(vector_input) => {
-                let vector = ids_to_vectors
-                    .resolve_reference(lookup_collection, lookup_vector_name, vector_input)
-                    .unwrap();
+    ) -> CollectionResult<VectorQuery<Vector>> {
+        let resolve = |vector_input: VectorInput| {
+            ids_to_vectors
+                .resolve_reference(lookup_collection, lookup_vector_name, vector_input)
+                .ok_or_else(|| {
+                    CollectionError::bad_request(format!(
+                        "Query vector can't be resolved, referenced point has no vector \
+                         `{lookup_vector_name}` or the document was not inferred",
+                    ))
+                })
+        };
 
-                VectorQuery::Nearest(vector)
-            }
+        let vector_query = match self {
+            VectorQuery::Nearest(vector_input) => VectorQuery::Nearest(resolve(vector_input)?),
             VectorQuery::RecommendAverageVector(reco) => {
                 let positives = reco
                     .positives
@@ -187,21 +224,17 @@ impl VectorQuery<VectorInput> {
                 VectorQuery::RecommendBestScore(RecoQuery::new(positives, negatives))
             }
             VectorQuery::Discover(discover) => {
-                let target = ids_to_vectors
-                    .resolve_reference(lookup_collection, lookup_vector_name, discover.target)
-                    .unwrap();
+                let target = resolve(discover.target)?;
                 let pairs = discover
                     .pairs
                     .into_iter()
-                    .map(|pair| ContextPair {
-                        positive: ids_to_vectors
-                            .resolve_reference(lookup_collection, lookup_vector_name, pair.positive)
-                            .unwrap(),
-                        negative: ids_to_vectors
-                            .resolve_reference(lookup_collection, lookup_vector_name, pair.negative)
-                            .unwrap(),
+                    .map(|pair| {
+                        Ok(ContextPair {
+                            positive: resolve(pair.positive)?,
+                            negative: resolve(pair.negative)?,
+                        })
                     })
-                    .collect();
+                    .collect::<CollectionResult<_>>()?;
 
                 VectorQuery::Discover(DiscoveryQuery { target, pairs })
             }
@@ -209,19 +242,19 @@ impl VectorQuery<VectorInput> {
                 let pairs = context
                     .pairs
                     .into_iter()
-                    .map(|pair| ContextPair {
-                        positive: ids_to_vectors
-                            .resolve_reference(lookup_collection, lookup_vector_name, pair.positive)
-                            .unwrap(),
-                        negative: ids_to_vectors
-                            .resolve_reference(lookup_collection, lookup_vector_name, pair.negative)
-                            .unwrap(),
+                    .map(|pair| {
+                        Ok(ContextPair {
+                            positive: resolve(pair.positive)?,
+                            negative: resolve(pair.negative)?,
+                        })
                     })
-                    .collect();
+                    .collect::<CollectionResult<_>>()?;
 
                 VectorQuery::Context(ContextQuery { pairs })
             }
-        }
+        };
+
+        Ok(vector_query)
     }
 }
 
@@ -257,6 +290,7 @@ impl VectorQuery<Vector> {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct CollectionPrefetch {
     pub prefetch: Vec<CollectionPrefetch>,
     pub query: Option<Query>,
@@ -266,6 +300,8 @@ pub struct CollectionPrefetch {
     pub limit: usize,
     /// Search params for when there is no prefetch
     pub params: Option<SearchParams>,
+    /// Weight of the results of this prefetch, if the parent query is a fusion
+    pub weight: Option<ScoreType>,
 }
 
 /// Exclude the referenced ids by editing the filter.
@@ -336,6 +372,7 @@ impl CollectionPrefetch {
             score_threshold: self.score_threshold,
             limit: self.limit,
             params: self.params,
+            weight: self.weight,
         })
     }
 }
@@ -451,6 +488,7 @@ mod from_rest {
                 score_threshold,
                 params,
                 limit,
+                weight,
             } = value;
 
             Self {
@@ -461,6 +499,7 @@ mod from_rest {
                 score_threshold,
                 limit: limit.unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
                 params,
+                weight,
             }
         }
     }
@@ -547,6 +586,7 @@ mod from_rest {
                     // TODO(universal-query): Validate at API level
                     Vector::MultiDense(MultiDenseVector::new_unchecked(multi_dense)),
                 ),
+                rest::VectorInput::Document(document) => VectorInput::Document(document),
             }
         }
     }
@@ -565,17 +605,25 @@ mod from_rest {
         fn from(value: rest::Fusion) -> Self {
             match value {
                 rest::Fusion::Rrf => Fusion::Rrf,
+                rest::Fusion::Dbsf => Fusion::Dbsf,
+                rest::Fusion::MinMax => Fusion::MinMax,
             }
         }
     }
 }
 
+pub use from_grpc::{IntoCollectionQueryRequest, IntoQueryGroupRequest};
+
 mod from_grpc {
+    use std::time::Duration;
+
+    use api::grpc::conversions::json_path_from_proto;
     use api::grpc::qdrant::{self as grpc};
     use api::rest::ShardKeySelector;
     use tonic::Status;
 
     use super::*;
+    use crate::grouping::group_by::{GroupRequest, SourceRequest};
     use crate::operations::consistency_params::ReadConsistency;
     use crate::operations::shard_selector_internal::ShardSelectorInternal;
 
@@ -584,6 +632,7 @@ mod from_grpc {
         pub collection_name: String,
         pub shard_key: ShardSelectorInternal,
         pub read_consistency: Option<ReadConsistency>,
+        pub timeout: Option<Duration>,
     }
 
     impl TryFrom<grpc::QueryPoints> for IntoCollectionQueryRequest {
@@ -604,6 +653,7 @@ mod from_grpc {
                 with_vectors,
                 read_consistency,
                 shard_key_selector,
+                timeout,
             } = value;
 
             let request = CollectionQueryRequest {
@@ -641,6 +691,83 @@ mod from_grpc {
                 collection_name,
                 shard_key,
                 read_consistency,
+                timeout: timeout.map(Duration::from_secs),
+            })
+        }
+    }
+
+    pub struct IntoQueryGroupRequest {
+        pub request: GroupRequest,
+        pub collection_name: String,
+        pub shard_key: ShardSelectorInternal,
+        pub read_consistency: Option<ReadConsistency>,
+        pub timeout: Option<Duration>,
+    }
+
+    impl TryFrom<grpc::QueryPointGroups> for IntoQueryGroupRequest {
+        type Error = Status;
+
+        fn try_from(value: grpc::QueryPointGroups) -> Result<Self, Self::Error> {
+            let grpc::QueryPointGroups {
+                collection_name,
+                prefetch,
+                query,
+                using,
+                filter,
+                search_params,
+                score_threshold,
+                with_payload,
+                with_vectors,
+                group_by,
+                group_size,
+                limit,
+                with_lookup,
+                read_consistency,
+                timeout,
+                shard_key_selector,
+            } = value;
+
+            let query_request = CollectionQueryRequest {
+                prefetch: prefetch
+                    .into_iter()
+                    .map(TryFrom::try_from)
+                    .collect::<Result<_, _>>()?,
+                query: query.map(TryFrom::try_from).transpose()?,
+                using: using.unwrap_or(DEFAULT_VECTOR_NAME.to_string()),
+                filter: filter.map(TryFrom::try_from).transpose()?,
+                score_threshold,
+                // Limit and offset are overridden by the grouping
+                limit: 0,
+                offset: 0,
+                params: search_params.map(From::from),
+                with_vector: with_vectors
+                    .map(From::from)
+                    .unwrap_or(CollectionQueryRequest::DEFAULT_WITH_VECTOR),
+                with_payload: with_payload
+                    .map(TryFrom::try_from)
+                    .transpose()?
+                    .unwrap_or(CollectionQueryRequest::DEFAULT_WITH_PAYLOAD),
+            };
+
+            let request = GroupRequest {
+                source: SourceRequest::Query(query_request),
+                group_by: json_path_from_proto(&group_by)?,
+                group_size: group_size as usize,
+                limit: limit as usize,
+                with_lookup: with_lookup.map(TryFrom::try_from).transpose()?,
+            };
+
+            let shard_key =
+                ShardSelectorInternal::from(shard_key_selector.map(ShardKeySelector::from));
+
+            let read_consistency = read_consistency.map(TryFrom::try_from).transpose()?;
+
+            Ok(IntoQueryGroupRequest {
+                request,
+                collection_name,
+                shard_key,
+                read_consistency,
+                timeout: timeout.map(Duration::from_secs),
             })
         }
     }
@@ -657,6 +784,7 @@ mod from_grpc {
                 search_params,
                 score_threshold,
                 limit,
+                weight,
             } = value;
 
             let collection_query = Self {
@@ -672,6 +800,7 @@ mod from_grpc {
                     .map(|l| l as usize)
                     .unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
                 params: search_params.map(From::from),
+                weight,
             };
 
             Ok(collection_query)
@@ -789,6 +918,7 @@ mod from_grpc {
                     // TODO(universal-query): Validate at API level
                     Vector::MultiDense(From::from(multi_dense)),
                 ),
+                Variant::Document(document) => VectorInput::Document(TryFrom::try_from(document)?),
             };
 
             Ok(vector_input)
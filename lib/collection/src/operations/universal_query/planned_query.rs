@@ -2,26 +2,49 @@
 
 use std::sync::Arc;
 
-use api::rest::OrderByInterface;
 use common::types::ScoreType;
+use segment::data_types::order_by::OrderBy;
 use segment::types::{Filter, WithPayloadInterface, WithVector};
 
-use super::shard_query::{ScoringQuery, ShardPrefetch, ShardQueryRequest};
+use super::shard_query::{fusion_weights, Sample, ScoringQuery, ShardPrefetch, ShardQueryRequest};
 use crate::operations::types::{
     CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
-    ScrollRequestInternal,
 };
 
 #[derive(Debug)]
 pub struct PlannedQuery {
     pub merge_plan: MergePlan,
     pub searches: Arc<CoreSearchRequestBatch>,
-    pub scrolls: Arc<Vec<ScrollRequestInternal>>,
+    pub scrolls: Arc<Vec<QueryScrollRequestInternal>>,
     pub offset: usize,
     pub with_vector: WithVector,
     pub with_payload: WithPayloadInterface,
 }
 
+/// Scroll request of a query plan, reads a single page from the segments of a shard
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryScrollRequestInternal {
+    pub limit: usize,
+    pub filter: Option<Filter>,
+    pub with_vector: WithVector,
+    pub with_payload: WithPayloadInterface,
+    pub scroll_order: ScrollOrder,
+}
+
+/// Order in which the points of a [`QueryScrollRequestInternal`] are read
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ScrollOrder {
+    /// Ascending by point id
+    #[default]
+    ById,
+
+    /// Ordered by a payload field
+    ByField(OrderBy),
+
+    /// Randomly sampled points
+    Random,
+}
+
 /// Defines how to merge multiple [prefetch sources](PrefetchSource)
 #[derive(Debug, PartialEq)]
 pub struct ResultsMerge {
@@ -36,6 +59,10 @@ pub struct ResultsMerge {
 
     /// Keep only points with better score than this threshold
     pub score_threshold: Option<ScoreType>,
+
+    /// Weights of the sources, in the same order, when rescoring with a fusion.
+    /// Empty means all sources weigh the same.
+    pub weights: Vec<ScoreType>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -87,6 +114,7 @@ impl TryFrom<ShardQueryRequest> for PlannedQuery {
 
         let merge_plan = if !prefetches.is_empty() {
             offset = req_offset;
+            let weights = fusion_weights(&prefetches);
             let sources = recurse_prefetches(&mut core_searches, &mut scrolls, prefetches, offset)?;
             let rescore = query.ok_or_else(|| {
                 CollectionError::bad_request("cannot have prefetches without a query".to_string())
@@ -102,6 +130,7 @@ impl TryFrom<ShardQueryRequest> for PlannedQuery {
                     filter: req_filter,
                     limit,
                     score_threshold: req_score_threshold,
+                    weights,
                 }),
             }
         } else {
@@ -132,13 +161,28 @@ impl TryFrom<ShardQueryRequest> for PlannedQuery {
                 }
                 Some(ScoringQuery::OrderBy(order_by)) => {
                     // Everything should come from 1 scroll
-                    let scroll = ScrollRequestInternal {
-                        order_by: Some(OrderByInterface::Struct(order_by)),
+                    let scroll = QueryScrollRequestInternal {
+                        scroll_order: ScrollOrder::ByField(order_by),
                         filter: req_filter,
                         with_vector: req_with_vector,
-                        with_payload: Some(req_with_payload),
-                        limit: Some(limit + req_offset),
-                        offset: None,
+                        with_payload: req_with_payload,
+                        limit: limit + req_offset,
+                    };
+
+                    scrolls.push(scroll);
+
+                    offset = req_offset;
+
+                    vec![PrefetchSource::ScrollsIdx(0)]
+                }
+                Some(ScoringQuery::Sample(Sample::Random)) => {
+                    // Everything should come from 1 scroll
+                    let scroll = QueryScrollRequestInternal {
+                        scroll_order: ScrollOrder::Random,
+                        filter: req_filter,
+                        with_vector: req_with_vector,
+                        with_payload: req_with_payload,
+                        limit: limit + req_offset,
                     };
 
                     scrolls.push(scroll);
@@ -149,13 +193,12 @@ impl TryFrom<ShardQueryRequest> for PlannedQuery {
                 }
                 None => {
                     // Everything should come from 1 scroll
-                    let scroll = ScrollRequestInternal {
-                        order_by: None,
+                    let scroll = QueryScrollRequestInternal {
+                        scroll_order: ScrollOrder::ById,
                         filter: req_filter,
                         with_vector: req_with_vector,
-                        with_payload: Some(req_with_payload),
-                        limit: Some(limit + req_offset),
-                        offset: None,
+                        with_payload: req_with_payload,
+                        limit: limit + req_offset,
                     };
 
                     scrolls.push(scroll);
@@ -191,7 +234,7 @@ impl TryFrom<ShardQueryRequest> for PlannedQuery {
 
 fn recurse_prefetches(
     core_searches: &mut Vec<CoreSearchRequest>,
-    scrolls: &mut Vec<ScrollRequestInternal>,
+    scrolls: &mut Vec<QueryScrollRequestInternal>,
     prefetches: Vec<ShardPrefetch>,
     offset: usize,
 ) -> CollectionResult<Vec<PrefetchSource>> {
@@ -205,6 +248,7 @@ fn recurse_prefetches(
             params,
             filter,
             score_threshold,
+            weight: _,
         } = prefetch;
 
         // Offset is replicated at each step from the root to the leaves
@@ -236,13 +280,26 @@ fn recurse_prefetches(
                     ))
                 }
                 Some(ScoringQuery::OrderBy(order_by)) => {
-                    let scroll = ScrollRequestInternal {
-                        order_by: Some(OrderByInterface::Struct(order_by)),
+                    let scroll = QueryScrollRequestInternal {
+                        scroll_order: ScrollOrder::ByField(order_by),
                         filter,
                         with_vector: WithVector::Bool(false),
-                        with_payload: Some(WithPayloadInterface::Bool(false)),
-                        limit: Some(limit),
-                        offset: None,
+                        with_payload: WithPayloadInterface::Bool(false),
+                        limit,
+                    };
+
+                    let idx = scrolls.len();
+                    scrolls.push(scroll);
+
+                    PrefetchSource::ScrollsIdx(idx)
+                }
+                Some(ScoringQuery::Sample(Sample::Random)) => {
+                    let scroll = QueryScrollRequestInternal {
+                        scroll_order: ScrollOrder::Random,
+                        filter,
+                        with_vector: WithVector::Bool(false),
+                        with_payload: WithPayloadInterface::Bool(false),
+                        limit,
                     };
 
                     let idx = scrolls.len();
@@ -251,13 +308,12 @@ fn recurse_prefetches(
                     PrefetchSource::ScrollsIdx(idx)
                 }
                 None => {
-                    let scroll = ScrollRequestInternal {
-                        order_by: None,
+                    let scroll = QueryScrollRequestInternal {
+                        scroll_order: ScrollOrder::ById,
                         filter,
                         with_vector: WithVector::Bool(false),
-                        with_payload: Some(WithPayloadInterface::Bool(false)),
-                        limit: Some(limit),
-                        offset: None,
+                        with_payload: WithPayloadInterface::Bool(false),
+                        limit,
                     };
 
                     let idx = scrolls.len();
@@ -268,6 +324,7 @@ fn recurse_prefetches(
             }
         } else {
             // This has nested prefetches. Recurse into them
+            let weights = fusion_weights(&prefetches);
             let inner_sources = recurse_prefetches(core_searches, scrolls, prefetches, offset)?;
 
             let rescore = query.ok_or_else(|| {
@@ -281,6 +338,7 @@ fn recurse_prefetches(
                     filter,
                     limit,
                     score_threshold,
+                    weights,
                 }),
             };
 
@@ -322,6 +380,7 @@ mod tests {
                     params: None,
                     filter: None,
                     score_threshold: None,
+                    weight: None,
                 }],
                 query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
                     NamedVectorStruct::new_from_vector(Vector::Dense(dummy_vector.clone()), "full"),
@@ -330,6 +389,7 @@ mod tests {
                 params: None,
                 filter: None,
                 score_threshold: None,
+                weight: None,
             }],
             query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
                 NamedVectorStruct::new_from_vector(
@@ -379,7 +439,8 @@ mod tests {
                         )),
                         filter: None,
                         limit: 100,
-                        score_threshold: None
+                        score_threshold: None,
+                        weights: vec![]
                     })
                 })],
                 merge: Some(ResultsMerge {
@@ -394,6 +455,7 @@ mod tests {
                     filter: Some(Filter::default()),
                     limit: 10,
                     score_threshold: None,
+                    weights: vec![],
                 })
             }
         );
@@ -473,6 +535,7 @@ mod tests {
                     params: None,
                     filter: None,
                     score_threshold: None,
+                    weight: None,
                 },
                 ShardPrefetch {
                     prefetches: Vec::new(),
@@ -486,6 +549,7 @@ mod tests {
                     params: None,
                     filter: None,
                     score_threshold: None,
+                    weight: None,
                 },
             ],
             query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
@@ -543,7 +607,8 @@ mod tests {
                     rescore: ScoringQuery::Fusion(Fusion::Rrf),
                     filter: Some(Filter::default()),
                     limit: 50,
-                    score_threshold: None
+                    score_threshold: None,
+                    weights: vec![]
                 })
             }
         );
@@ -602,6 +667,7 @@ mod tests {
                 params: dummy_params,
                 filter: dummy_filter.clone(),
                 score_threshold: Some(0.1),
+                weight: None,
             }],
             query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
             filter: Some(Filter::default()),
@@ -635,7 +701,8 @@ mod tests {
                     rescore: ScoringQuery::Fusion(Fusion::Rrf),
                     filter: Some(Filter::default()),
                     limit: 50,
-                    score_threshold: Some(0.666)
+                    score_threshold: Some(0.666),
+                    weights: vec![]
                 })
             }
         );
@@ -657,4 +724,91 @@ mod tests {
             }]
         )
     }
+
+    #[test]
+    fn test_try_from_weighted_fusion() {
+        let dummy_vector = vec![1.0, 2.0, 3.0];
+        let prefetch = |weight| ShardPrefetch {
+            prefetches: Vec::new(),
+            query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+                NamedVectorStruct::new_from_vector(Vector::Dense(dummy_vector.clone()), "dense"),
+            ))),
+            limit: 100,
+            params: None,
+            filter: None,
+            score_threshold: None,
+            weight,
+        };
+
+        let request = ShardQueryRequest {
+            prefetches: vec![prefetch(Some(2.0)), prefetch(None)],
+            query: Some(ScoringQuery::Fusion(Fusion::Dbsf)),
+            filter: None,
+            score_threshold: None,
+            limit: 50,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        let planned_query = PlannedQuery::try_from(request).unwrap();
+
+        assert_eq!(
+            planned_query.merge_plan,
+            MergePlan {
+                sources: vec![
+                    PrefetchSource::SearchesIdx(0),
+                    PrefetchSource::SearchesIdx(1)
+                ],
+                merge: Some(ResultsMerge {
+                    rescore: ScoringQuery::Fusion(Fusion::Dbsf),
+                    filter: None,
+                    limit: 50,
+                    score_threshold: None,
+                    weights: vec![2.0, 1.0]
+                })
+            }
+        );
+    }
+
+    #[test]
+    fn test_try_from_sample() {
+        let request = ShardQueryRequest {
+            prefetches: vec![],
+            query: Some(ScoringQuery::Sample(Sample::Random)),
+            filter: Some(Filter::default()),
+            score_threshold: None,
+            limit: 10,
+            offset: 5,
+            params: None,
+            with_vector: WithVector::Bool(true),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        let planned_query = PlannedQuery::try_from(request).unwrap();
+
+        assert!(planned_query.searches.searches.is_empty());
+
+        assert_eq!(
+            planned_query.scrolls.as_ref(),
+            &vec![QueryScrollRequestInternal {
+                limit: 15, // limit + offset
+                filter: Some(Filter::default()),
+                with_vector: WithVector::Bool(true),
+                with_payload: WithPayloadInterface::Bool(false),
+                scroll_order: ScrollOrder::Random,
+            }]
+        );
+
+        assert_eq!(
+            planned_query.merge_plan,
+            MergePlan {
+                sources: vec![PrefetchSource::ScrollsIdx(0)],
+                merge: None,
+            }
+        );
+
+        assert_eq!(planned_query.offset, 5);
+    }
 }
@@ -1,6 +1,8 @@
 use api::grpc::qdrant as grpc;
 use common::types::ScoreType;
 use itertools::Itertools;
+use segment::common::reciprocal_rank_fusion::rrf_scoring;
+use segment::common::score_fusion::{score_fusion, ScoreNormalization};
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::{NamedQuery, NamedVectorStruct, Vector, DEFAULT_VECTOR_NAME};
 use segment::types::{Filter, Order, ScoredPoint, SearchParams, WithPayloadInterface, WithVector};
@@ -13,7 +15,7 @@ use crate::operations::types::CollectionResult;
 
 /// Internal response type for a universal query request.
 ///
-/// Capable of returning multiple intermediate results if needed, like the case of RRF (Reciprocal Rank Fusion)
+/// Capable of returning multiple intermediate results if needed, like the case of a root-level fusion
 pub type ShardQueryResponse = Vec<Vec<ScoredPoint>>;
 
 /// Internal representation of a universal query request.
@@ -35,7 +37,35 @@ pub struct ShardQueryRequest {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Fusion {
+    /// Reciprocal rank fusion
     Rrf,
+    /// Distribution-based score fusion, normalizes scores by `mean ± 3σ` of each source
+    Dbsf,
+    /// Min-max score fusion, normalizes scores by the min and max of each source
+    MinMax,
+}
+
+impl Fusion {
+    /// Fuse the results of multiple sources into a single list, larger score is better.
+    ///
+    /// `weights` correspond to the sources by index, see [`fusion_weights`].
+    pub fn fuse(
+        &self,
+        sources: impl IntoIterator<Item = Vec<ScoredPoint>>,
+        weights: &[ScoreType],
+    ) -> Vec<ScoredPoint> {
+        match self {
+            Fusion::Rrf => rrf_scoring(sources, weights),
+            Fusion::Dbsf => score_fusion(sources, ScoreNormalization::Distribution, weights),
+            Fusion::MinMax => score_fusion(sources, ScoreNormalization::MinMax, weights),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sample {
+    /// Uniformly random points, in random order
+    Random,
 }
 
 /// Same as `Query`, but with the resolved vector references.
@@ -44,20 +74,23 @@ pub enum ScoringQuery {
     /// Score points against some vector(s)
     Vector(QueryEnum),
 
-    /// Reciprocal rank fusion
+    /// Fuse the results of the prefetches
     Fusion(Fusion),
 
     /// Order by a payload field
     OrderBy(OrderBy),
+
+    /// Sample points, without scoring them
+    Sample(Sample),
 }
 
 impl ScoringQuery {
     pub fn needs_intermediate_results(&self) -> bool {
         match self {
             ScoringQuery::Fusion(fusion) => match fusion {
-                Fusion::Rrf => true,
+                Fusion::Rrf | Fusion::Dbsf | Fusion::MinMax => true,
             },
-            ScoringQuery::Vector(_) | ScoringQuery::OrderBy(_) => false,
+            ScoringQuery::Vector(_) | ScoringQuery::OrderBy(_) | ScoringQuery::Sample(_) => false,
         }
     }
 
@@ -86,9 +119,11 @@ impl ScoringQuery {
                     }
                 }
                 ScoringQuery::Fusion(fusion) => match fusion {
-                    Fusion::Rrf => Order::LargeBetter,
+                    Fusion::Rrf | Fusion::Dbsf | Fusion::MinMax => Order::LargeBetter,
                 },
                 ScoringQuery::OrderBy(order_by) => Order::from(order_by.direction()),
+                // Sampled points are scored by their random sampling keys, the largest keys win
+                ScoringQuery::Sample(Sample::Random) => Order::LargeBetter,
             },
             None => {
                 // Order by ID
@@ -107,6 +142,22 @@ pub struct ShardPrefetch {
     pub params: Option<SearchParams>,
     pub filter: Option<Filter>,
     pub score_threshold: Option<ScoreType>,
+    /// Weight of this prefetch's results, if the parent query is a fusion
+    pub weight: Option<ScoreType>,
+}
+
+/// Weights to fuse the results of the given prefetches with, in the same order.
+///
+/// Empty if none of the prefetches defines a weight, which means equal weights.
+pub fn fusion_weights(prefetches: &[ShardPrefetch]) -> Vec<ScoreType> {
+    if prefetches.iter().all(|prefetch| prefetch.weight.is_none()) {
+        return Vec::new();
+    }
+
+    prefetches
+        .iter()
+        .map(|prefetch| prefetch.weight.unwrap_or(1.0))
+        .collect()
 }
 
 impl ShardQueryRequest {
@@ -191,6 +242,7 @@ impl TryFrom<grpc::query_shard_points::Prefetch> for ShardPrefetch {
             filter,
             score_threshold,
             using,
+            weight,
         } = value;
 
         let shard_prefetch = Self {
@@ -205,6 +257,7 @@ impl TryFrom<grpc::query_shard_points::Prefetch> for ShardPrefetch {
             params: params.map(SearchParams::from),
             filter: filter.map(Filter::try_from).transpose()?,
             score_threshold,
+            weight,
         };
 
         Ok(shard_prefetch)
@@ -272,6 +325,8 @@ impl From<api::grpc::qdrant::Fusion> for Fusion {
     fn from(fusion: api::grpc::qdrant::Fusion) -> Self {
         match fusion {
             api::grpc::qdrant::Fusion::Rrf => Fusion::Rrf,
+            api::grpc::qdrant::Fusion::Dbsf => Fusion::Dbsf,
+            api::grpc::qdrant::Fusion::MinMax => Fusion::MinMax,
         }
     }
 }
@@ -280,6 +335,36 @@ impl From<Fusion> for api::grpc::qdrant::Fusion {
     fn from(fusion: Fusion) -> Self {
         match fusion {
             Fusion::Rrf => api::grpc::qdrant::Fusion::Rrf,
+            Fusion::Dbsf => api::grpc::qdrant::Fusion::Dbsf,
+            Fusion::MinMax => api::grpc::qdrant::Fusion::MinMax,
+        }
+    }
+}
+
+impl TryFrom<i32> for Sample {
+    type Error = tonic::Status;
+
+    fn try_from(sample: i32) -> Result<Self, Self::Error> {
+        let sample = api::grpc::qdrant::Sample::from_i32(sample).ok_or_else(|| {
+            tonic::Status::invalid_argument(format!("invalid sample type value {sample}",))
+        })?;
+
+        Ok(Sample::from(sample))
+    }
+}
+
+impl From<api::grpc::qdrant::Sample> for Sample {
+    fn from(sample: api::grpc::qdrant::Sample) -> Self {
+        match sample {
+            api::grpc::qdrant::Sample::Random => Sample::Random,
+        }
+    }
+}
+
+impl From<Sample> for api::grpc::qdrant::Sample {
+    fn from(sample: Sample) -> Self {
+        match sample {
+            Sample::Random => api::grpc::qdrant::Sample::Random,
         }
     }
 }
@@ -302,6 +387,9 @@ impl ScoringQuery {
             grpc::query_shard_points::query::Score::OrderBy(order_by) => {
                 ScoringQuery::OrderBy(OrderBy::try_from(order_by)?)
             }
+            grpc::query_shard_points::query::Score::Sample(sample) => {
+                ScoringQuery::Sample(Sample::try_from(sample)?)
+            }
         };
 
         Ok(scoring_query)
@@ -345,6 +433,9 @@ impl From<ScoringQuery> for grpc::query_shard_points::Query {
             ScoringQuery::OrderBy(order_by) => Self {
                 score: Some(Score::OrderBy(grpc::OrderBy::from(order_by))),
             },
+            ScoringQuery::Sample(sample) => Self {
+                score: Some(Score::Sample(api::grpc::qdrant::Sample::from(sample) as i32)),
+            },
         }
     }
 }
@@ -358,6 +449,7 @@ impl From<ShardPrefetch> for grpc::query_shard_points::Prefetch {
             params,
             filter,
             score_threshold,
+            weight,
         } = value;
         Self {
             prefetch: prefetches.into_iter().map(Self::from).collect(),
@@ -369,6 +461,7 @@ impl From<ShardPrefetch> for grpc::query_shard_points::Prefetch {
             params: params.map(grpc::SearchParams::from),
             score_threshold,
             limit: limit as u64,
+            weight,
         }
     }
 }
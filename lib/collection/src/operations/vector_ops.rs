@@ -8,9 +8,11 @@ use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 use validator::{Validate, ValidationError, ValidationErrors};
 
-use super::point_ops::PointIdsList;
+use super::point_ops::{check_vector_struct, PointIdsList};
 use super::{point_to_shards, split_iter_by_shard, OperationToShard, SplitByShard};
+use crate::config::CollectionParams;
 use crate::hash_ring::HashRing;
+use crate::operations::types::CollectionResult;
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 pub struct UpdateVectors {
@@ -90,6 +92,21 @@ impl VectorOperations {
     }
 }
 
+impl VectorOperations {
+    /// Check vectors of the operation against the validation configured in the collection
+    pub fn check_vectors_validation(&self, params: &CollectionParams) -> CollectionResult<()> {
+        match self {
+            VectorOperations::UpdateVectors(update_vectors) => update_vectors
+                .points
+                .iter()
+                .try_for_each(|point| check_vector_struct(params, point.id, &point.vector)),
+            VectorOperations::DeleteVectors(..) | VectorOperations::DeleteVectorsByFilter(..) => {
+                Ok(())
+            }
+        }
+    }
+}
+
 impl Validate for VectorOperations {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
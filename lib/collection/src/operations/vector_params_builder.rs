@@ -1,6 +1,6 @@
 use std::num::NonZeroU64;
 
-use segment::types::{Distance, MultiVectorConfig, QuantizationConfig};
+use segment::types::{Distance, MultiVectorConfig, QuantizationConfig, VectorValidationConfig};
 
 use crate::operations::config_diff::HnswConfigDiff;
 use crate::operations::types::{Datatype, VectorParams};
@@ -20,6 +20,8 @@ impl VectorParamsBuilder {
                 on_disk: None,
                 datatype: None,
                 multivec_config: None,
+                validation: None,
+                normalize: None,
             },
         }
     }
@@ -49,6 +51,16 @@ impl VectorParamsBuilder {
         self
     }
 
+    pub fn with_validation(mut self, validation: VectorValidationConfig) -> Self {
+        self.vector_params.validation = Some(validation);
+        self
+    }
+
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.vector_params.normalize = Some(normalize);
+        self
+    }
+
     pub fn build(self) -> VectorParams {
         self.vector_params
     }
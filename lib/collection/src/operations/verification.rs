@@ -0,0 +1,522 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use segment::problems::unindexed_field::unindexed_fields;
+use segment::types::{Filter, PayloadFieldSchema, PayloadKeyType, SearchParams};
+
+use super::payload_ops::PayloadOps;
+use super::point_ops::{PointInsertOperationsInternal, PointOperations};
+use super::types::{
+    CollectionError, CollectionResult, CoreSearchRequest, CountRequestInternal,
+    DiscoverRequestInternal, RecommendRequestInternal, ScrollRequestInternal,
+    SearchRequestInternal,
+};
+use super::universal_query::collection_query::{CollectionPrefetch, CollectionQueryRequest};
+use super::vector_ops::VectorOperations;
+use super::CollectionUpdateOperations;
+use crate::collection::distance_matrix::CollectionSearchMatrixRequest;
+use crate::config::StrictModeConfig;
+use crate::grouping::group_by::{GroupRequest, SourceRequest};
+
+/// Checks of a request against the strict mode limits of a collection.
+///
+/// Requests only describe what they contain, the limits are checked in [`check_strict_mode`].
+/// Read requests are checked as sent by the user, before they are turned into internal requests
+/// with adjusted limits.
+///
+/// [`check_strict_mode`]: StrictModeVerification::check_strict_mode
+pub trait StrictModeVerification {
+    /// Maximal number of points the request returns
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    /// Filters of the request which only read points
+    fn read_filters(&self) -> Vec<&Filter> {
+        Vec::new()
+    }
+
+    /// Filters of the request which select points to modify
+    fn update_filters(&self) -> Vec<&Filter> {
+        Vec::new()
+    }
+
+    /// Search params of the request, including nested ones
+    fn search_params(&self) -> Vec<&SearchParams> {
+        Vec::new()
+    }
+
+    /// Number of points inserted or updated by the request
+    fn batch_size(&self) -> Option<usize> {
+        None
+    }
+
+    fn check_strict_mode(
+        &self,
+        config: &StrictModeConfig,
+        payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> CollectionResult<()> {
+        if !config.is_enabled() {
+            return Ok(());
+        }
+
+        if let (Some(limit), Some(max_limit)) = (self.query_limit(), config.max_query_limit) {
+            if limit > max_limit {
+                return Err(CollectionError::strict_mode(format!(
+                    "Limit {limit} exceeds the maximal query limit {max_limit}, decrease the limit"
+                )));
+            }
+        }
+
+        if config.unindexed_filtering_retrieve == Some(false) {
+            check_indexed_filters(self.read_filters(), payload_schema)?;
+        }
+
+        if config.unindexed_filtering_update == Some(false) {
+            check_indexed_filters(self.update_filters(), payload_schema)?;
+        }
+
+        for params in self.search_params() {
+            if config.search_allow_exact == Some(false) && params.exact {
+                return Err(CollectionError::strict_mode(
+                    "Exact search is not allowed, use approximate search instead",
+                ));
+            }
+
+            if let (Some(hnsw_ef), Some(max_hnsw_ef)) = (params.hnsw_ef, config.search_max_hnsw_ef)
+            {
+                if hnsw_ef > max_hnsw_ef {
+                    return Err(CollectionError::strict_mode(format!(
+                        "HNSW ef {hnsw_ef} exceeds the maximal value {max_hnsw_ef}, decrease hnsw_ef"
+                    )));
+                }
+            }
+        }
+
+        if let (Some(batch_size), Some(max_batch_size)) =
+            (self.batch_size(), config.upsert_max_batchsize)
+        {
+            if batch_size > max_batch_size {
+                return Err(CollectionError::strict_mode(format!(
+                    "Batch of {batch_size} points exceeds the maximal batch size {max_batch_size}, split the request into smaller batches"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_indexed_filters(
+    filters: Vec<&Filter>,
+    payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+) -> CollectionResult<()> {
+    for filter in filters {
+        let fields = unindexed_fields(filter, payload_schema);
+        if !fields.is_empty() {
+            return Err(CollectionError::strict_mode(format!(
+                "Filtering by unindexed fields is not allowed, create a payload index for: {}",
+                fields.iter().join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+impl StrictModeVerification for CoreSearchRequest {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit + self.offset)
+    }
+
+    fn read_filters(&self) -> Vec<&Filter> {
+        self.filter.iter().collect()
+    }
+
+    fn search_params(&self) -> Vec<&SearchParams> {
+        self.params.iter().collect()
+    }
+}
+
+impl StrictModeVerification for SearchRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit + self.offset.unwrap_or_default())
+    }
+
+    fn read_filters(&self) -> Vec<&Filter> {
+        self.filter.iter().collect()
+    }
+
+    fn search_params(&self) -> Vec<&SearchParams> {
+        self.params.iter().collect()
+    }
+}
+
+impl StrictModeVerification for RecommendRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit + self.offset.unwrap_or_default())
+    }
+
+    fn read_filters(&self) -> Vec<&Filter> {
+        self.filter.iter().collect()
+    }
+
+    fn search_params(&self) -> Vec<&SearchParams> {
+        self.params.iter().collect()
+    }
+}
+
+impl StrictModeVerification for DiscoverRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit + self.offset.unwrap_or_default())
+    }
+
+    fn read_filters(&self) -> Vec<&Filter> {
+        self.filter.iter().collect()
+    }
+
+    fn search_params(&self) -> Vec<&SearchParams> {
+        self.params.iter().collect()
+    }
+}
+
+impl StrictModeVerification for CollectionQueryRequest {
+    fn query_limit(&self) -> Option<usize> {
+        let prefetch_limit = self.prefetch.iter().map(prefetch_max_limit).max();
+        prefetch_limit.max(Some(self.limit + self.offset))
+    }
+
+    fn read_filters(&self) -> Vec<&Filter> {
+        let mut filters: Vec<_> = self.filter.iter().collect();
+        for prefetch in &self.prefetch {
+            collect_prefetch(prefetch, &mut |prefetch| filters.extend(&prefetch.filter));
+        }
+        filters
+    }
+
+    fn search_params(&self) -> Vec<&SearchParams> {
+        let mut params: Vec<_> = self.params.iter().collect();
+        for prefetch in &self.prefetch {
+            collect_prefetch(prefetch, &mut |prefetch| params.extend(&prefetch.params));
+        }
+        params
+    }
+}
+
+/// Visit the prefetch and all of its nested prefetches
+fn collect_prefetch<'a>(
+    prefetch: &'a CollectionPrefetch,
+    visit: &mut impl FnMut(&'a CollectionPrefetch),
+) {
+    visit(prefetch);
+    for nested in &prefetch.prefetch {
+        collect_prefetch(nested, visit);
+    }
+}
+
+fn prefetch_max_limit(prefetch: &CollectionPrefetch) -> usize {
+    prefetch
+        .prefetch
+        .iter()
+        .map(prefetch_max_limit)
+        .fold(prefetch.limit, usize::max)
+}
+
+impl StrictModeVerification for SourceRequest {
+    fn query_limit(&self) -> Option<usize> {
+        match self {
+            SourceRequest::Search(request) => request.query_limit(),
+            SourceRequest::Recommend(request) => request.query_limit(),
+            SourceRequest::Query(request) => request.query_limit(),
+        }
+    }
+
+    fn read_filters(&self) -> Vec<&Filter> {
+        match self {
+            SourceRequest::Search(request) => request.read_filters(),
+            SourceRequest::Recommend(request) => request.read_filters(),
+            SourceRequest::Query(request) => request.read_filters(),
+        }
+    }
+
+    fn search_params(&self) -> Vec<&SearchParams> {
+        match self {
+            SourceRequest::Search(request) => request.search_params(),
+            SourceRequest::Recommend(request) => request.search_params(),
+            SourceRequest::Query(request) => request.search_params(),
+        }
+    }
+}
+
+impl StrictModeVerification for GroupRequest {
+    /// Both the number of groups and the number of points per group are limited
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit.max(self.group_size))
+    }
+
+    fn read_filters(&self) -> Vec<&Filter> {
+        self.source.read_filters()
+    }
+
+    fn search_params(&self) -> Vec<&SearchParams> {
+        self.source.search_params()
+    }
+}
+
+impl StrictModeVerification for CollectionSearchMatrixRequest {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.sample_size)
+    }
+
+    fn read_filters(&self) -> Vec<&Filter> {
+        self.filter.iter().collect()
+    }
+}
+
+impl StrictModeVerification for ScrollRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    fn read_filters(&self) -> Vec<&Filter> {
+        self.filter.iter().collect()
+    }
+}
+
+impl StrictModeVerification for CountRequestInternal {
+    fn read_filters(&self) -> Vec<&Filter> {
+        self.filter.iter().collect()
+    }
+}
+
+impl StrictModeVerification for CollectionUpdateOperations {
+    fn update_filters(&self) -> Vec<&Filter> {
+        let filter = match self {
+            CollectionUpdateOperations::PointOperation(PointOperations::DeletePointsByFilter(
+                filter,
+            )) => Some(filter),
+            CollectionUpdateOperations::VectorOperation(
+                VectorOperations::DeleteVectorsByFilter(filter, _),
+            ) => Some(filter),
+            CollectionUpdateOperations::PayloadOperation(payload_ops) => match payload_ops {
+                PayloadOps::SetPayload(op) | PayloadOps::OverwritePayload(op) => op.filter.as_ref(),
+                PayloadOps::DeletePayload(op) => op.filter.as_ref(),
+                PayloadOps::ClearPayloadByFilter(filter) => Some(filter),
+                PayloadOps::ClearPayload { .. } => None,
+            },
+            CollectionUpdateOperations::PointOperation(_)
+            | CollectionUpdateOperations::VectorOperation(_)
+            | CollectionUpdateOperations::FieldIndexOperation(_) => None,
+        };
+        filter.into_iter().collect()
+    }
+
+    fn batch_size(&self) -> Option<usize> {
+        match self {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsBatch(batch),
+            )) => Some(batch.ids.len()),
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )) => Some(points.len()),
+            CollectionUpdateOperations::VectorOperation(VectorOperations::UpdateVectors(op)) => {
+                Some(op.points.len())
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::vectors::VectorStruct;
+    use segment::types::{
+        Condition, FieldCondition, Match, PayloadSchemaType, ValueVariants, WithPayloadInterface,
+        WithVector,
+    };
+
+    use super::*;
+    use crate::operations::point_ops::PointStruct;
+
+    fn strict_config() -> StrictModeConfig {
+        StrictModeConfig {
+            enabled: Some(true),
+            max_query_limit: Some(100),
+            unindexed_filtering_retrieve: Some(false),
+            unindexed_filtering_update: Some(false),
+            search_max_hnsw_ef: Some(256),
+            search_allow_exact: Some(false),
+            upsert_max_batchsize: Some(2),
+        }
+    }
+
+    fn keyword_filter(key: &str) -> Filter {
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            key.try_into().unwrap(),
+            Match::new_value(ValueVariants::Keyword("hello".to_string())),
+        )))
+    }
+
+    fn scroll(limit: usize, filter: Option<Filter>) -> ScrollRequestInternal {
+        ScrollRequestInternal {
+            limit: Some(limit),
+            filter,
+            ..Default::default()
+        }
+    }
+
+    fn query(limit: usize, prefetch: Vec<CollectionPrefetch>) -> CollectionQueryRequest {
+        CollectionQueryRequest {
+            prefetch,
+            query: None,
+            using: String::new(),
+            filter: None,
+            score_threshold: None,
+            limit,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+        }
+    }
+
+    fn prefetch(
+        limit: usize,
+        filter: Option<Filter>,
+        params: Option<SearchParams>,
+        prefetch: Vec<CollectionPrefetch>,
+    ) -> CollectionPrefetch {
+        CollectionPrefetch {
+            prefetch,
+            query: None,
+            using: String::new(),
+            limit,
+            params,
+            filter,
+            score_threshold: None,
+            weight: None,
+        }
+    }
+
+    #[test]
+    fn test_disabled_strict_mode() {
+        let config = StrictModeConfig {
+            enabled: Some(false),
+            ..strict_config()
+        };
+        let request = scroll(1000, Some(keyword_filter("city")));
+        assert!(request.check_strict_mode(&config, &HashMap::new()).is_ok());
+    }
+
+    #[test]
+    fn test_query_limit() {
+        let config = strict_config();
+        let schema = HashMap::new();
+
+        assert!(scroll(100, None)
+            .check_strict_mode(&config, &schema)
+            .is_ok());
+        let err = scroll(101, None)
+            .check_strict_mode(&config, &schema)
+            .unwrap_err();
+        assert!(matches!(err, CollectionError::StrictMode { .. }));
+
+        // Limits of nested prefetches are checked as well
+        let nested = prefetch(1000, None, None, vec![]);
+        let request = query(10, vec![prefetch(10, None, None, vec![nested])]);
+        assert!(request.check_strict_mode(&config, &schema).is_err());
+
+        // Groups are checked by the requested limits, not by the number of points to scan
+        let group = |limit: usize, group_size: usize| GroupRequest {
+            source: SourceRequest::Query(query(limit, vec![])),
+            group_by: "group".try_into().unwrap(),
+            group_size,
+            limit,
+            with_lookup: None,
+        };
+        assert!(group(100, 100).check_strict_mode(&config, &schema).is_ok());
+        assert!(group(10, 101).check_strict_mode(&config, &schema).is_err());
+    }
+
+    #[test]
+    fn test_unindexed_filtering() {
+        let config = strict_config();
+        let mut schema = HashMap::new();
+
+        let request = scroll(10, Some(keyword_filter("city")));
+        let err = request.check_strict_mode(&config, &schema).unwrap_err();
+        assert!(err.to_string().contains("city"));
+
+        schema.insert(
+            "city".try_into().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+        );
+        assert!(request.check_strict_mode(&config, &schema).is_ok());
+
+        // Index of a different type is not suitable for the condition
+        schema.insert(
+            "city".try_into().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Integer),
+        );
+        assert!(request.check_strict_mode(&config, &schema).is_err());
+
+        let delete = CollectionUpdateOperations::PointOperation(
+            PointOperations::DeletePointsByFilter(keyword_filter("country")),
+        );
+        assert!(delete.check_strict_mode(&config, &schema).is_err());
+
+        let config = StrictModeConfig {
+            unindexed_filtering_update: None,
+            ..config
+        };
+        assert!(delete.check_strict_mode(&config, &schema).is_ok());
+    }
+
+    #[test]
+    fn test_search_params() {
+        let config = strict_config();
+        let schema = HashMap::new();
+
+        let exact = SearchParams {
+            exact: true,
+            ..Default::default()
+        };
+        let request = query(10, vec![prefetch(10, None, Some(exact), vec![])]);
+        assert!(request.check_strict_mode(&config, &schema).is_err());
+
+        let large_ef = SearchParams {
+            hnsw_ef: Some(512),
+            ..Default::default()
+        };
+        let request = query(10, vec![prefetch(10, None, Some(large_ef), vec![])]);
+        assert!(request.check_strict_mode(&config, &schema).is_err());
+
+        let small_ef = SearchParams {
+            hnsw_ef: Some(128),
+            ..Default::default()
+        };
+        let request = query(10, vec![prefetch(10, None, Some(small_ef), vec![])]);
+        assert!(request.check_strict_mode(&config, &schema).is_ok());
+    }
+
+    #[test]
+    fn test_upsert_batch_size() {
+        let config = strict_config();
+        let schema = HashMap::new();
+
+        let upsert = |count: u64| {
+            let points = (0..count)
+                .map(|id| PointStruct {
+                    id: id.into(),
+                    vector: VectorStruct::from(vec![1.0, 2.0]).into(),
+                    payload: None,
+                })
+                .collect();
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            ))
+        };
+
+        assert!(upsert(2).check_strict_mode(&config, &schema).is_ok());
+        assert!(upsert(3).check_strict_mode(&config, &schema).is_err());
+    }
+}
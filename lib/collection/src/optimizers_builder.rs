@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use crate::collection_manager::optimizers::config_mismatch_optimizer::ConfigMismatchOptimizer;
+use crate::collection_manager::optimizers::graph_healing_optimizer::GraphHealingOptimizer;
 use crate::collection_manager::optimizers::indexing_optimizer::IndexingOptimizer;
 use crate::collection_manager::optimizers::merge_optimizer::MergeOptimizer;
 use crate::collection_manager::optimizers::segment_optimizer::OptimizerThresholds;
@@ -77,6 +78,14 @@ pub struct OptimizersConfig {
     /// If 0 - no optimization threads, optimizations will be disabled.
     #[serde(default)]
     pub max_optimization_threads: Option<usize>,
+    /// The minimal fraction of deleted vectors in an HNSW graph, required to heal the graph in place.
+    /// Healing reconnects neighbours of deleted points without rebuilding the segment.
+    ///
+    /// Should be lower than `deleted_threshold`, otherwise segments are vacuumed before they are healed.
+    /// If null - graph healing is disabled.
+    #[serde(default)]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub graph_healing_threshold: Option<f64>,
 }
 
 impl OptimizersConfig {
@@ -91,6 +100,7 @@ impl OptimizersConfig {
             indexing_threshold: Some(100_000),
             flush_interval_sec: 60,
             max_optimization_threads: Some(0),
+            graph_healing_threshold: None,
         }
     }
 
@@ -159,7 +169,7 @@ pub fn build_optimizers(
     let temp_segments_path = shard_path.join(TEMP_SEGMENTS_PATH);
     let threshold_config = optimizers_config.optimizer_thresholds(num_indexing_threads);
 
-    Arc::new(vec![
+    let mut optimizers: Vec<Arc<Optimizer>> = vec![
         Arc::new(MergeOptimizer::new(
             optimizers_config.get_number_segments(),
             threshold_config,
@@ -178,23 +188,39 @@ pub fn build_optimizers(
             hnsw_config.clone(),
             quantization_config.clone(),
         )),
-        Arc::new(VacuumOptimizer::new(
-            optimizers_config.deleted_threshold,
-            optimizers_config.vacuum_min_vector_number,
+    ];
+
+    // Heal graphs before vacuum, so cheap in place repair is preferred over a segment rebuild
+    if let Some(graph_healing_threshold) = optimizers_config.graph_healing_threshold {
+        optimizers.push(Arc::new(GraphHealingOptimizer::new(
+            graph_healing_threshold,
             threshold_config,
             segments_path.clone(),
             temp_segments_path.clone(),
             collection_params.clone(),
             hnsw_config.clone(),
             quantization_config.clone(),
-        )),
-        Arc::new(ConfigMismatchOptimizer::new(
-            threshold_config,
-            segments_path,
-            temp_segments_path,
-            collection_params.clone(),
-            hnsw_config.clone(),
-            quantization_config.clone(),
-        )),
-    ])
+        )));
+    }
+
+    optimizers.push(Arc::new(VacuumOptimizer::new(
+        optimizers_config.deleted_threshold,
+        optimizers_config.vacuum_min_vector_number,
+        threshold_config,
+        segments_path.clone(),
+        temp_segments_path.clone(),
+        collection_params.clone(),
+        hnsw_config.clone(),
+        quantization_config.clone(),
+    )));
+    optimizers.push(Arc::new(ConfigMismatchOptimizer::new(
+        threshold_config,
+        segments_path,
+        temp_segments_path,
+        collection_params.clone(),
+        hnsw_config.clone(),
+        quantization_config.clone(),
+    )));
+
+    Arc::new(optimizers)
 }
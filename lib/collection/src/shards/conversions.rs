@@ -364,6 +364,9 @@ pub fn internal_create_index(
                     segment::types::PayloadSchemaType::Datetime => {
                         api::grpc::qdrant::FieldType::Datetime as i32
                     }
+                    segment::types::PayloadSchemaType::Uuid => {
+                        api::grpc::qdrant::FieldType::Uuid as i32
+                    }
                 },
                 None,
             ),
@@ -376,6 +379,30 @@ pub fn internal_create_index(
                     api::grpc::qdrant::FieldType::Integer as i32,
                     Some(integer_params.into()),
                 ),
+                PayloadSchemaParams::Keyword(keyword_params) => (
+                    api::grpc::qdrant::FieldType::Keyword as i32,
+                    Some(keyword_params.into()),
+                ),
+                PayloadSchemaParams::Float(float_params) => (
+                    api::grpc::qdrant::FieldType::Float as i32,
+                    Some(float_params.into()),
+                ),
+                PayloadSchemaParams::Geo(geo_params) => (
+                    api::grpc::qdrant::FieldType::Geo as i32,
+                    Some(geo_params.into()),
+                ),
+                PayloadSchemaParams::Bool(bool_params) => (
+                    api::grpc::qdrant::FieldType::Bool as i32,
+                    Some(bool_params.into()),
+                ),
+                PayloadSchemaParams::Datetime(datetime_params) => (
+                    api::grpc::qdrant::FieldType::Datetime as i32,
+                    Some(datetime_params.into()),
+                ),
+                PayloadSchemaParams::Uuid(uuid_params) => (
+                    api::grpc::qdrant::FieldType::Uuid as i32,
+                    Some(uuid_params.into()),
+                ),
             },
         })
         .map(|(field_type, field_params)| (Some(field_type), field_params))
@@ -3,12 +3,14 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
 use tokio::runtime::Handle;
 
+use crate::common::snapshot_manifest::ShardManifest;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
     CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
@@ -35,7 +37,8 @@ impl DummyShard {
         _temp_path: &Path,
         _target_path: &Path,
         _save_wal: bool,
-    ) -> CollectionResult<()> {
+        _base: Option<&ShardManifest>,
+    ) -> CollectionResult<ShardManifest> {
         self.dummy()
     }
 
@@ -106,7 +109,17 @@ impl ShardOperation for DummyShard {
         &self,
         _: Arc<ShardQueryRequest>,
         _: &Handle,
+        _: Option<Duration>,
     ) -> CollectionResult<ShardQueryResponse> {
         self.dummy()
     }
+
+    async fn facet(
+        &self,
+        _: Arc<FacetParams>,
+        _: &Handle,
+        _: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.dummy()
+    }
 }
@@ -0,0 +1,87 @@
+use tokio::sync::watch;
+
+use super::LocalShard;
+use crate::operations::change_ops::{
+    ChangeCursor, ChangeRecord, ChangesRequest, ChangesResult, DEFAULT_CHANGES_LIMIT,
+};
+use crate::operations::types::{CollectionError, CollectionResult};
+
+impl LocalShard {
+    /// Subscribe to the number of the last record written to the WAL of this shard
+    pub fn subscribe_changes(&self) -> watch::Receiver<u64> {
+        self.wal.subscribe_writes()
+    }
+
+    /// Read update operations from the WAL of this shard, which come after `request.cursor`.
+    ///
+    /// If the request names a consumer, the cursor is acknowledged for it, so that the WAL keeps
+    /// the changes from the cursor onwards.
+    pub fn read_changes(&self, request: &ChangesRequest) -> CollectionResult<ChangesResult> {
+        let limit = request.limit.unwrap_or(DEFAULT_CHANGES_LIMIT);
+
+        let wal = self.wal.wal.lock();
+        let first_index = wal.first_closed_index();
+
+        let read_from = match request.cursor {
+            None => first_index,
+            Some(cursor) => {
+                if cursor.op_num < first_index {
+                    return Err(CollectionError::bad_request(format!(
+                        "Change {} is already truncated from the WAL, oldest available change is {first_index}",
+                        cursor.op_num,
+                    )));
+                }
+                match wal.read(cursor.op_num).next() {
+                    Some((_, record)) if record.clock_tag == cursor.clock_tag => {}
+                    Some(_) => {
+                        return Err(CollectionError::bad_request(format!(
+                            "Change {} does not match the cursor, WAL of the shard was replaced",
+                            cursor.op_num,
+                        )));
+                    }
+                    None => {
+                        return Err(CollectionError::bad_request(format!(
+                            "Change {} is not in the WAL yet",
+                            cursor.op_num,
+                        )));
+                    }
+                }
+                cursor.op_num + 1
+            }
+        };
+
+        // Acknowledge under the WAL lock, so that the changes are not truncated before we
+        // register the consumer
+        if let Some(consumer) = &request.consumer {
+            let keep_from = request.cursor.map_or(first_index, |cursor| cursor.op_num);
+            self.wal_retention.acknowledge(consumer, keep_from)?;
+        }
+
+        let changes: Vec<_> = wal
+            .read(read_from)
+            .take(limit)
+            .map(|(op_num, record)| ChangeRecord {
+                cursor: ChangeCursor {
+                    op_num,
+                    clock_tag: record.clock_tag,
+                },
+                operation: record.operation,
+            })
+            .collect();
+
+        let next_cursor = changes
+            .last()
+            .map(|change| change.cursor)
+            .or(request.cursor);
+
+        Ok(ChangesResult {
+            changes,
+            next_cursor,
+        })
+    }
+
+    /// Unregister a change consumer, so that the WAL no longer keeps changes for it
+    pub fn remove_change_consumer(&self, consumer: &str) -> CollectionResult<bool> {
+        self.wal_retention.remove(consumer)
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::try_join_all;
+use segment::data_types::facets::{FacetParams, FacetResponse};
+use tokio::runtime::Handle;
+
+use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::LockedSegment;
+use crate::common::stopping_guard::StoppingGuard;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+impl LocalShard {
+    /// Count the values of the `request.key` field in all segments of the shard.
+    ///
+    /// Counts are exact within the shard. If the request is not exact, only the top
+    /// `request.limit` values are returned, so that less data is sent over the network.
+    pub async fn do_facet(
+        &self,
+        request: Arc<FacetParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let is_stopped_guard = StoppingGuard::new();
+
+        let segments: Vec<_> = self
+            .segments()
+            .read()
+            .non_appendable_then_appendable_segments()
+            .collect();
+
+        let facet_segment = |segment: LockedSegment| {
+            let request = Arc::clone(&request);
+            let is_stopped = is_stopped_guard.get_is_stopped();
+
+            search_runtime_handle
+                .spawn_blocking(move || segment.get().read().facet(&request, &is_stopped))
+        };
+
+        let facet_request = try_join_all(segments.into_iter().map(facet_segment));
+
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        let segment_counts = tokio::time::timeout(timeout, facet_request)
+            .await
+            .map_err(|_| {
+                log::debug!("Facet timeout reached: {} seconds", timeout.as_secs());
+                // StoppingGuard takes care of setting is_stopped to true
+                CollectionError::timeout(timeout.as_secs() as usize, "Facet")
+            })??;
+
+        let mut counts = HashMap::new();
+        for segment_count in segment_counts {
+            for (value, count) in segment_count? {
+                *counts.entry(value).or_insert(0) += count;
+            }
+        }
+
+        let limit = if request.exact {
+            usize::MAX
+        } else {
+            request.limit
+        };
+
+        Ok(FacetResponse::top_hits(counts, limit))
+    }
+}
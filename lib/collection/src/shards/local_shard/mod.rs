@@ -1,5 +1,7 @@
+pub(super) mod changes;
 pub mod clock_map;
 pub mod disk_usage_watcher;
+pub(super) mod facet;
 pub(super) mod query;
 pub(super) mod scroll;
 pub(super) mod search;
@@ -27,8 +29,8 @@ use segment::index::field_index::CardinalityEstimation;
 use segment::segment::Segment;
 use segment::segment_constructor::{build_segment, load_segment};
 use segment::types::{
-    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, SegmentType,
+    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PointIdType, QuantizationConfig,
+    SegmentConfig, SegmentType,
 };
 use segment::utils::mem::Mem;
 use tokio::fs::{copy, create_dir_all, remove_dir_all, remove_file};
@@ -46,6 +48,7 @@ use crate::collection_manager::holders::segment_holder::{
 };
 use crate::collection_manager::optimizers::TrackerLog;
 use crate::common::file_utils::{move_dir, move_file};
+use crate::common::snapshot_manifest::ShardManifest;
 use crate::config::CollectionConfig;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{
@@ -60,7 +63,7 @@ use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry};
 use crate::shards::CollectionId;
 use crate::update_handler::{Optimizer, UpdateHandler, UpdateSignal};
 use crate::wal::SerdeWal;
-use crate::wal_delta::{LockedWal, RecoverableWal};
+use crate::wal_delta::{LockedWal, RecoverableWal, WalRetentionGuard};
 
 /// If rendering WAL load progression in basic text form, report progression every 60 seconds.
 const WAL_LOAD_REPORT_EVERY: Duration = Duration::from_secs(60);
@@ -75,6 +78,7 @@ pub struct LocalShard {
     pub(super) collection_config: Arc<TokioRwLock<CollectionConfig>>,
     pub(super) shared_storage_config: Arc<SharedStorageConfig>,
     pub(super) wal: RecoverableWal,
+    pub(super) wal_retention: WalRetentionGuard,
     pub(super) update_handler: Arc<Mutex<UpdateHandler>>,
     pub(super) update_sender: ArcSwap<Sender<UpdateSignal>>,
     pub(super) update_tracker: UpdateTracker,
@@ -97,6 +101,7 @@ impl LocalShard {
         move_dir(segments_from, segments_to).await?;
 
         LocalShardClocks::move_data(from, to).await?;
+        WalRetentionGuard::move_data(from, to).await?;
 
         Ok(())
     }
@@ -125,12 +130,15 @@ impl LocalShard {
         }
 
         LocalShardClocks::delete_data(shard_path).await?;
+        WalRetentionGuard::delete_data(shard_path).await?;
 
         Ok(())
     }
 
     #[allow(clippy::too_many_arguments)]
     pub async fn new(
+        id: ShardId,
+        collection_id: CollectionId,
         segment_holder: SegmentHolder,
         collection_config: Arc<TokioRwLock<CollectionConfig>>,
         shared_storage_config: Arc<SharedStorageConfig>,
@@ -139,6 +147,7 @@ impl LocalShard {
         optimizer_cpu_budget: CpuBudget,
         shard_path: &Path,
         clocks: LocalShardClocks,
+        wal_retention: WalRetentionGuard,
         update_runtime: Handle,
     ) -> Self {
         let segment_holder = Arc::new(RwLock::new(segment_holder));
@@ -157,6 +166,8 @@ impl LocalShard {
         .await;
 
         let mut update_handler = UpdateHandler::new(
+            collection_id,
+            id,
             shared_storage_config.clone(),
             optimizers.clone(),
             optimizers_log.clone(),
@@ -168,6 +179,7 @@ impl LocalShard {
             config.optimizer_config.max_optimization_threads,
             clocks.clone(),
             shard_path.into(),
+            wal_retention.clone(),
         );
 
         let (update_sender, update_receiver) =
@@ -183,6 +195,7 @@ impl LocalShard {
             collection_config,
             shared_storage_config,
             wal: RecoverableWal::new(locked_wal, clocks.newest_clocks, clocks.oldest_clocks),
+            wal_retention,
             update_handler: Arc::new(Mutex::new(update_handler)),
             update_sender: ArcSwap::from_pointee(update_sender),
             update_tracker,
@@ -311,6 +324,7 @@ impl LocalShard {
         drop(collection_config_read); // release `shared_config` from borrow checker
 
         let clocks = LocalShardClocks::load(shard_path)?;
+        let wal_retention = WalRetentionGuard::load(shard_path)?;
 
         // Always make sure we have any appendable segments, needed for update operations
         if !segment_holder.has_appendable_segment() {
@@ -325,6 +339,8 @@ impl LocalShard {
         }
 
         let local_shard = LocalShard::new(
+            id,
+            collection_id.clone(),
             segment_holder,
             collection_config,
             shared_storage_config,
@@ -333,6 +349,7 @@ impl LocalShard {
             optimizer_cpu_budget,
             shard_path,
             clocks,
+            wal_retention,
             update_runtime,
         )
         .await;
@@ -446,11 +463,7 @@ impl LocalShard {
             let segment_config = SegmentConfig {
                 vector_data: vector_params.clone(),
                 sparse_vector_data: sparse_vector_params.clone(),
-                payload_storage_type: if config.params.on_disk_payload {
-                    PayloadStorageType::OnDisk
-                } else {
-                    PayloadStorageType::InMemory
-                },
+                payload_storage_type: config.params.payload_storage_type(),
             };
             let segment = thread::Builder::new()
                 .name(format!("shard-build-{collection_id}-{id}"))
@@ -491,6 +504,8 @@ impl LocalShard {
         drop(config); // release `shared_config` from borrow checker
 
         let collection = LocalShard::new(
+            id,
+            collection_id,
             segment_holder,
             collection_config,
             shared_storage_config,
@@ -499,6 +514,7 @@ impl LocalShard {
             optimizer_cpu_budget,
             shard_path,
             LocalShardClocks::default(),
+            WalRetentionGuard::load(shard_path)?,
             update_runtime,
         )
         .await;
@@ -726,12 +742,16 @@ impl LocalShard {
     }
 
     /// Create snapshot for local shard into `target_path`
+    ///
+    /// Segments, which are in the `base` manifest with the same version, are not snapshotted.
+    /// Returns versions of all segments of the shard.
     pub async fn create_snapshot(
         &self,
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
-    ) -> CollectionResult<()> {
+        base: Option<&ShardManifest>,
+    ) -> CollectionResult<ShardManifest> {
         let snapshot_shard_path = target_path;
 
         // snapshot all shard's segment
@@ -755,23 +775,27 @@ impl LocalShard {
         let segments_path = Self::segments_path(&self.path);
         let collection_params = self.collection_config.read().await.params.clone();
         let temp_path = temp_path.to_owned();
+        let base = base.cloned();
 
-        tokio::task::spawn_blocking(move || {
+        let manifest = tokio::task::spawn_blocking(move || {
             // Do not change segments while snapshotting
-            SegmentHolder::snapshot_all_segments(
+            let manifest = SegmentHolder::snapshot_all_segments(
                 segments.clone(),
                 &segments_path,
                 Some(&collection_params),
                 &temp_path,
                 &snapshot_segments_shard_path,
+                base.as_ref(),
             )?;
 
             if save_wal {
                 // snapshot all shard's WAL
-                Self::snapshot_wal(wal, &snapshot_shard_path_owned)
+                Self::snapshot_wal(wal, &snapshot_shard_path_owned)?;
             } else {
-                Self::snapshot_empty_wal(wal, &snapshot_shard_path_owned)
+                Self::snapshot_empty_wal(wal, &snapshot_shard_path_owned)?;
             }
+
+            CollectionResult::Ok(manifest)
         })
         .await??;
 
@@ -782,7 +806,7 @@ impl LocalShard {
         let target_shard_config_path = snapshot_shard_path.join(SHARD_CONFIG_FILE);
         copy(&shard_config_path, &target_shard_config_path).await?;
 
-        Ok(())
+        Ok(manifest)
     }
 
     /// Create empty WAL which is compatible with currently stored data
@@ -966,7 +990,14 @@ impl LocalShard {
                 for (key, val) in segment_info.index_schema {
                     schema
                         .entry(key)
-                        .and_modify(|entry| entry.points += val.points)
+                        .and_modify(|entry| {
+                            entry.points += val.points;
+                            entry.disk_usage_bytes =
+                                match (entry.disk_usage_bytes, val.disk_usage_bytes) {
+                                    (Some(a), Some(b)) => Some(a + b),
+                                    (a, b) => a.or(b),
+                                };
+                        })
                         .or_insert(val);
                 }
             }
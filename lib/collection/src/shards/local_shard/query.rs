@@ -3,10 +3,8 @@ use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
-use api::rest::OrderByInterface;
 use futures::future::BoxFuture;
 use futures::FutureExt;
-use segment::common::reciprocal_rank_fusion::rrf_scoring;
 use segment::types::{
     Filter, HasIdCondition, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -16,12 +14,11 @@ use super::LocalShard;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::operations::types::{
     CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
-    ScrollRequestInternal,
 };
 use crate::operations::universal_query::planned_query::{
-    MergePlan, PlannedQuery, PrefetchSource, ResultsMerge,
+    MergePlan, PlannedQuery, PrefetchSource, QueryScrollRequestInternal, ResultsMerge, ScrollOrder,
 };
-use crate::operations::universal_query::shard_query::{Fusion, ScoringQuery, ShardQueryResponse};
+use crate::operations::universal_query::shard_query::{Sample, ScoringQuery, ShardQueryResponse};
 
 struct PrefetchHolder {
     core_results: Vec<Vec<ScoredPoint>>,
@@ -36,44 +33,23 @@ impl PrefetchHolder {
         }
     }
 
-    /// Returns an iterator by Cow over the sources in no specific order.
-    fn iter_sources(
-        &self,
-        core_indices: Vec<usize>,
-        scroll_indices: Vec<usize>,
-        merged_list: Vec<Vec<ScoredPoint>>,
-    ) -> PrefetchIterator {
-        PrefetchIterator {
-            prefetch_holder: self,
-            core_indices,
-            scroll_indices,
-            merged_list,
-        }
+    fn get_core_result(&self, idx: usize) -> CollectionResult<Cow<'_, Vec<ScoredPoint>>> {
+        self.core_results
+            .get(idx)
+            .map(Cow::Borrowed)
+            .ok_or_else(|| {
+                CollectionError::service_error(format!(
+                    "Core search result at index {idx} is missing in the query plan"
+                ))
+            })
     }
-}
 
-struct PrefetchIterator<'a> {
-    prefetch_holder: &'a PrefetchHolder,
-    core_indices: Vec<usize>,
-    scroll_indices: Vec<usize>,
-    merged_list: Vec<Vec<ScoredPoint>>,
-}
-
-impl<'a> Iterator for PrefetchIterator<'a> {
-    type Item = Cow<'a, Vec<ScoredPoint>>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(idx) = self.core_indices.pop() {
-            return self
-                .prefetch_holder
-                .core_results
-                .get(idx)
-                .map(Cow::Borrowed);
-        }
-        if let Some(idx) = self.scroll_indices.pop() {
-            return self.prefetch_holder.scrolls.get(idx).map(Cow::Borrowed);
-        }
-        self.merged_list.pop().map(Cow::Owned)
+    fn get_scroll(&self, idx: usize) -> CollectionResult<Cow<'_, Vec<ScoredPoint>>> {
+        self.scrolls.get(idx).map(Cow::Borrowed).ok_or_else(|| {
+            CollectionError::service_error(format!(
+                "Scroll result at index {idx} is missing in the query plan"
+            ))
+        })
     }
 }
 
@@ -146,15 +122,17 @@ impl LocalShard {
         'shard: 'query,
     {
         async move {
-            let max_len = merge_plan.sources.len();
-            let mut search_indices = Vec::with_capacity(max_len);
-            let mut scroll_indices = Vec::with_capacity(max_len);
-            let mut merged_list = Vec::with_capacity(max_len);
+            // Sources are kept in the same order as the prefetches, so that fusion weights match them
+            let mut sources = Vec::with_capacity(merge_plan.sources.len());
 
             for source in merge_plan.sources.into_iter() {
                 match source {
-                    PrefetchSource::SearchesIdx(idx) => search_indices.push(idx),
-                    PrefetchSource::ScrollsIdx(idx) => scroll_indices.push(idx),
+                    PrefetchSource::SearchesIdx(idx) => {
+                        sources.push(prefetch_holder.get_core_result(idx)?)
+                    }
+                    PrefetchSource::ScrollsIdx(idx) => {
+                        sources.push(prefetch_holder.get_scroll(idx)?)
+                    }
                     PrefetchSource::Prefetch(prefetch) => {
                         let merged = self
                             .recurse_prefetch(
@@ -165,13 +143,11 @@ impl LocalShard {
                                 depth + 1,
                             )
                             .await?;
-                        merged_list.extend(merged);
+                        sources.extend(merged.into_iter().map(Cow::Owned));
                     }
                 }
             }
 
-            let sources = prefetch_holder.iter_sources(search_indices, scroll_indices, merged_list);
-
             let root_query_needs_intermediate_results = || {
                 merge_plan
                     .merge
@@ -181,11 +157,16 @@ impl LocalShard {
             };
 
             if depth == 0 && root_query_needs_intermediate_results() {
-                // in case of top level RRF, we need to propagate intermediate results
-                Ok(sources.map(Cow::into_owned).collect())
+                // in case of top level fusion, we need to propagate intermediate results
+                Ok(sources.into_iter().map(Cow::into_owned).collect())
             } else {
                 let merged = self
-                    .merge_prefetches(sources, merge_plan.merge, search_runtime_handle, timeout)
+                    .merge_prefetches(
+                        sources.into_iter(),
+                        merge_plan.merge,
+                        search_runtime_handle,
+                        timeout,
+                    )
                     .await?;
                 Ok(vec![merged])
             }
@@ -207,10 +188,11 @@ impl LocalShard {
             filter,
             score_threshold,
             limit,
+            weights,
         } = merge;
 
         match rescore {
-            ScoringQuery::Fusion(Fusion::Rrf) => {
+            ScoringQuery::Fusion(fusion) => {
                 let sources: Vec<_> = sources.map(Cow::into_owned).collect();
 
                 // TODO(universal-query): Remove this ugly part when we propagate merged filters to leaf queries
@@ -222,9 +204,9 @@ impl LocalShard {
                     None
                 };
 
-                let mut top_rrf = rrf_scoring(sources);
+                let mut top_fused = fusion.fuse(sources, &weights);
 
-                top_rrf = top_rrf
+                top_fused = top_fused
                     .into_iter()
                     .filter(|point| {
                         // TODO(universal-query): Remove this ugly part when we propagate merged filters to leaf queries
@@ -242,19 +224,18 @@ impl LocalShard {
                     .take(limit)
                     .collect();
 
-                Ok(top_rrf)
+                Ok(top_fused)
             }
             ScoringQuery::OrderBy(order_by) => {
                 // create single scroll request for rescoring query
                 let filter = filter_with_sources_ids(sources, filter);
 
-                let scroll_request = ScrollRequestInternal {
-                    offset: None,
-                    limit: Some(limit),
+                let scroll_request = QueryScrollRequestInternal {
+                    limit,
                     filter: Some(filter),
-                    with_payload: Some(WithPayloadInterface::Bool(false)),
+                    with_payload: WithPayloadInterface::Bool(false),
                     with_vector: WithVector::Bool(false),
-                    order_by: Some(OrderByInterface::Struct(order_by)),
+                    scroll_order: ScrollOrder::ByField(order_by),
                 };
 
                 self.query_scroll_batch(Arc::new(vec![scroll_request]), search_runtime_handle)
@@ -266,6 +247,27 @@ impl LocalShard {
                         )
                     })
             }
+            ScoringQuery::Sample(Sample::Random) => {
+                // create single scroll request for sampling among the sources
+                let filter = filter_with_sources_ids(sources, filter);
+
+                let scroll_request = QueryScrollRequestInternal {
+                    limit,
+                    filter: Some(filter),
+                    with_payload: WithPayloadInterface::Bool(false),
+                    with_vector: WithVector::Bool(false),
+                    scroll_order: ScrollOrder::Random,
+                };
+
+                self.query_scroll_batch(Arc::new(vec![scroll_request]), search_runtime_handle)
+                    .await?
+                    .pop()
+                    .ok_or_else(|| {
+                        CollectionError::service_error(
+                            "Rescoring with sample query didn't return expected batch of results",
+                        )
+                    })
+            }
             ScoringQuery::Vector(query_enum) => {
                 // create single search request for rescoring query
                 let filter = filter_with_sources_ids(sources, filter);
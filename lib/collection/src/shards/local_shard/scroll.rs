@@ -1,7 +1,10 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use common::types::ScoreType;
 use futures::future::try_join_all;
 use itertools::Itertools as _;
+use segment::common::random_sample::{key_random_sample, merge_keyed_samples};
 use segment::data_types::order_by::{Direction, OrderBy, OrderValue};
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -11,20 +14,19 @@ use tokio::runtime::Handle;
 use super::LocalShard;
 use crate::collection_manager::holders::segment_holder::LockedSegment;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
-use crate::operations::types::{CollectionResult, Record, ScrollRequestInternal};
+use crate::operations::types::{CollectionResult, Record};
+use crate::operations::universal_query::planned_query::{QueryScrollRequestInternal, ScrollOrder};
 
 impl LocalShard {
     /// Basic parallel batching, it is conveniently used for the universal query API.
     pub(super) async fn query_scroll_batch(
         &self,
-        batch: Arc<Vec<ScrollRequestInternal>>,
+        batch: Arc<Vec<QueryScrollRequestInternal>>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
-        let default_request = ScrollRequestInternal::default();
-
         let scrolls = batch
             .iter()
-            .map(|request| self.query_scroll(request, &default_request, search_runtime_handle));
+            .map(|request| self.query_scroll(request, search_runtime_handle));
 
         try_join_all(scrolls).await
     }
@@ -32,59 +34,81 @@ impl LocalShard {
     /// Scroll a single page, to be used for the universal query API only.
     async fn query_scroll(
         &self,
-        request: &ScrollRequestInternal,
-        default_request: &ScrollRequestInternal,
+        request: &QueryScrollRequestInternal,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<ScoredPoint>> {
-        let ScrollRequestInternal {
-            offset,
+        let QueryScrollRequestInternal {
             limit,
             with_vector,
             filter,
-            order_by,
+            scroll_order,
             with_payload,
         } = request;
 
-        let with_payload = with_payload
-            .as_ref()
-            .or(default_request.with_payload.as_ref())
-            .unwrap();
-        let order_by = order_by.clone().map(OrderBy::from);
+        let records_to_scored_points = |records: Vec<Record>| {
+            records
+                .into_iter()
+                .map(|record| ScoredPoint {
+                    id: record.id,
+                    version: 0,
+                    score: 0.0,
+                    payload: record.payload,
+                    vector: record.vector,
+                    shard_key: record.shard_key,
+                    order_value: None,
+                })
+                .collect()
+        };
 
-        match order_by {
-            None => self
+        match scroll_order {
+            ScrollOrder::ById => self
                 .scroll_by_id(
-                    *offset,
-                    limit.or(default_request.limit).unwrap(),
+                    None,
+                    *limit,
                     with_payload,
                     with_vector,
                     filter.as_ref(),
                     search_runtime_handle,
                 )
                 .await
-                .map(|records| {
-                    records
-                        .into_iter()
-                        .map(|record| ScoredPoint {
-                            id: record.id,
-                            version: 0,
-                            score: 0.0,
-                            payload: record.payload,
-                            vector: record.vector,
-                            shard_key: record.shard_key,
-                            order_value: None,
-                        })
-                        .collect()
-                }),
-            Some(order_by) => {
+                .map(records_to_scored_points),
+            ScrollOrder::Random => {
+                let (records, keys) = self
+                    .scroll_randomly(
+                        *limit,
+                        with_payload,
+                        with_vector,
+                        filter.as_ref(),
+                        search_runtime_handle,
+                    )
+                    .await?;
+
+                // Sampling keys are used as scores, to merge the samples of different shards
+                let scored_points = records
+                    .into_iter()
+                    .zip(keys)
+                    .map(|(record, key)| ScoredPoint {
+                        id: record.id,
+                        version: 0,
+                        score: key,
+                        payload: record.payload,
+                        vector: record.vector,
+                        shard_key: record.shard_key,
+                        order_value: None,
+                    })
+                    .collect();
+
+                Ok(scored_points)
+            }
+            ScrollOrder::ByField(order_by) => {
                 let (records, values) = self
                     .scroll_by_field(
-                        limit.or(default_request.limit).unwrap(),
+                        *limit,
                         with_payload,
                         with_vector,
                         filter.as_ref(),
                         search_runtime_handle,
-                        &order_by,
+                        order_by,
                     )
                     .await?;
 
@@ -203,4 +227,58 @@ impl LocalShard {
 
         Ok((records, values))
     }
+
+    /// Read up to `limit` uniformly random points, along with their random sampling keys.
+    ///
+    /// Points are returned in descending order of their keys. Keys are generated as if every
+    /// point of the shard got an independent random key, so that the samples of several shards can
+    /// be merged by key into a uniform sample of all of them.
+    pub async fn scroll_randomly(
+        &self,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<(Vec<Record>, Vec<ScoreType>)> {
+        let segments = self.segments();
+
+        let (non_appendable, appendable) = segments.read().split_segments();
+
+        let read_random_filtered = |segment: LockedSegment| {
+            let filter = filter.cloned();
+
+            search_runtime_handle.spawn_blocking(move || {
+                let segment = segment.get();
+                let segment = segment.read();
+                let sample = segment.read_random_filtered(limit, filter.as_ref());
+                let population = segment.estimate_point_count(filter.as_ref()).exp;
+                key_random_sample(sample, population, &mut rand::thread_rng())
+            })
+        };
+
+        let non_appendable =
+            try_join_all(non_appendable.into_iter().map(read_random_filtered)).await?;
+        let appendable = try_join_all(appendable.into_iter().map(read_random_filtered)).await?;
+
+        // Each segment returns its own random sample, weighted by the size of the segment,
+        // pick the final sample among all of them
+        let (point_ids, keys): (Vec<_>, Vec<_>) =
+            merge_keyed_samples(non_appendable.into_iter().chain(appendable), usize::MAX)
+                .into_iter()
+                .unique_by(|(point_id, _)| *point_id)
+                .take(limit)
+                .unzip();
+
+        let with_payload = WithPayload::from(with_payload_interface);
+
+        // Records are returned in the same order as the requested ids
+        let records = SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)?;
+
+        // Some points may have been deleted in the meantime, match the keys by id
+        let keys: HashMap<_, _> = point_ids.into_iter().zip(keys).collect();
+        let keys = records.iter().map(|record| keys[&record.id]).collect();
+
+        Ok((records, keys))
+    }
 }
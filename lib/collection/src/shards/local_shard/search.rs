@@ -1,14 +1,17 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use segment::types::ScoredPoint;
+use segment::types::{ScoredPoint, TokenPooling};
 use tokio::runtime::Handle;
 
 use super::LocalShard;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::common::stopping_guard::StoppingGuard;
+use crate::config::CollectionParams;
 use crate::operations::query_enum::QueryEnum;
-use crate::operations::types::{CollectionError, CollectionResult, CoreSearchRequestBatch};
+use crate::operations::types::{
+    CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
+};
 
 impl LocalShard {
     pub async fn do_search(
@@ -19,9 +22,12 @@ impl LocalShard {
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let is_stopped_guard = StoppingGuard::new();
 
-        let (query_context, collection_params) = {
+        let (core_request, query_context, collection_params) = {
             let collection_config = self.collection_config.read().await;
 
+            // Pool query multivectors once for the shard, instead of once per segment
+            let core_request = pool_query_tokens(core_request, &collection_config.params)?;
+
             let query_context_opt = SegmentsSearcher::prepare_query_context(
                 self.segments.clone(),
                 &core_request,
@@ -35,7 +41,11 @@ impl LocalShard {
                 return Ok(vec![]);
             };
 
-            (query_context, collection_config.params.clone())
+            (
+                core_request,
+                query_context,
+                collection_config.params.clone(),
+            )
         };
 
         let search_request = SegmentsSearcher::search(
@@ -89,3 +99,49 @@ impl LocalShard {
         Ok(top_results)
     }
 }
+
+/// Limit of query tokens and pooling method for the multivector query of the request, if any
+fn query_token_pooling(
+    request: &CoreSearchRequest,
+    collection_params: &CollectionParams,
+) -> Option<(usize, TokenPooling)> {
+    let multivec_config = collection_params
+        .vectors
+        .get_params(request.query.get_vector_name())?
+        .multivec_config
+        .as_ref()?;
+    let max_tokens = request
+        .params
+        .and_then(|params| params.max_tokens)
+        .or(multivec_config.max_tokens)?;
+    Some((max_tokens, multivec_config.pooling.unwrap_or_default()))
+}
+
+/// Reduce the number of token vectors of multivector queries, as configured for the collection
+/// or overridden by the `max_tokens` search param
+fn pool_query_tokens(
+    core_request: Arc<CoreSearchRequestBatch>,
+    collection_params: &CollectionParams,
+) -> CollectionResult<Arc<CoreSearchRequestBatch>> {
+    if core_request
+        .searches
+        .iter()
+        .all(|request| query_token_pooling(request, collection_params).is_none())
+    {
+        return Ok(core_request);
+    }
+
+    let searches = core_request
+        .searches
+        .iter()
+        .map(|request| {
+            let mut request = request.clone();
+            if let Some((max_tokens, pooling)) = query_token_pooling(&request, collection_params) {
+                request.query = request.query.pool_tokens(max_tokens, pooling)?;
+            }
+            Ok(request)
+        })
+        .collect::<CollectionResult<_>>()?;
+
+    Ok(Arc::new(CoreSearchRequestBatch { searches }))
+}
@@ -6,6 +6,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
@@ -16,6 +17,7 @@ use tokio::sync::{oneshot, RwLock};
 use tokio::time::timeout;
 
 use super::update_tracker::UpdateTracker;
+use crate::common::snapshot_manifest::ShardManifest;
 use crate::operations::operation_effect::{
     EstimateOperationEffectArea, OperationEffectArea, PointsOperationEffect,
 };
@@ -71,9 +73,10 @@ impl ProxyShard {
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
-    ) -> CollectionResult<()> {
+        base: Option<&ShardManifest>,
+    ) -> CollectionResult<ShardManifest> {
         self.wrapped_shard
-            .create_snapshot(temp_path, target_path, save_wal)
+            .create_snapshot(temp_path, target_path, save_wal, base)
             .await
     }
 
@@ -252,9 +255,21 @@ impl ShardOperation for ProxyShard {
         &self,
         request: Arc<ShardQueryRequest>,
         search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
     ) -> CollectionResult<ShardQueryResponse> {
         self.wrapped_shard
-            .query(request, search_runtime_handle)
+            .query(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    async fn facet(
+        &self,
+        request: Arc<FacetParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.wrapped_shard
+            .facet(request, search_runtime_handle, timeout)
             .await
     }
 }
@@ -17,7 +17,7 @@ use common::types::TelemetryDetail;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tokio::runtime::Handle;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{watch, Mutex, RwLock};
 
 use super::local_shard::clock_map::RecoveryPoint;
 use super::local_shard::LocalShard;
@@ -26,6 +26,8 @@ use super::transfer::ShardTransfer;
 use super::CollectionId;
 use crate::common::snapshots_manager::SnapshotStorageManager;
 use crate::config::CollectionConfig;
+use crate::events::ReplicaStateChangedEvent;
+use crate::operations::change_ops::{ChangesRequest, ChangesResult};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::optimizers_builder::OptimizersConfig;
@@ -488,12 +490,17 @@ impl ShardReplicaSet {
         let old_shard = self.local.write().await.replace(Shard::Local(local));
 
         if !self.replica_state.read().is_local || state.is_some() {
-            self.replica_state.write(|rs| {
+            let from_state = self.replica_state.write(|rs| {
                 rs.is_local = true;
+                let from_state = rs.get_peer_state(&rs.this_peer_id).copied();
                 if let Some(active) = state {
                     rs.set_peer_state(self.this_peer_id(), active);
                 }
+                from_state
             })?;
+            if state.is_some() {
+                self.notify_replica_state_change(self.this_peer_id(), from_state, state);
+            }
         }
         self.update_locally_disabled(self.this_peer_id());
         Ok(old_shard)
@@ -502,12 +509,13 @@ impl ShardReplicaSet {
     pub async fn remove_local(&self) -> CollectionResult<()> {
         // TODO: Ensure cancel safety!
 
-        self.replica_state.write(|rs| {
+        let from_state = self.replica_state.write(|rs| {
             rs.is_local = false;
             let this_peer_id = rs.this_peer_id;
-            rs.remove_peer_state(&this_peer_id);
+            rs.remove_peer_state(&this_peer_id)
         })?;
 
+        self.notify_replica_state_change(self.this_peer_id(), from_state, None);
         self.update_locally_disabled(self.this_peer_id());
 
         let removing_local = {
@@ -526,10 +534,13 @@ impl ShardReplicaSet {
     pub async fn add_remote(&self, peer_id: PeerId, state: ReplicaState) -> CollectionResult<()> {
         debug_assert!(peer_id != self.this_peer_id());
 
-        self.replica_state.write(|rs| {
+        let from_state = self.replica_state.write(|rs| {
+            let from_state = rs.get_peer_state(&peer_id).copied();
             rs.set_peer_state(peer_id, state);
+            from_state
         })?;
 
+        self.notify_replica_state_change(peer_id, from_state, Some(state));
         self.update_locally_disabled(peer_id);
 
         let mut remotes = self.remotes.write().await;
@@ -550,10 +561,11 @@ impl ShardReplicaSet {
     }
 
     pub async fn remove_remote(&self, peer_id: PeerId) -> CollectionResult<()> {
-        self.replica_state.write(|rs| {
-            rs.remove_peer_state(&peer_id);
-        })?;
+        let from_state = self
+            .replica_state
+            .write(|rs| rs.remove_peer_state(&peer_id))?;
 
+        self.notify_replica_state_change(peer_id, from_state, None);
         self.update_locally_disabled(peer_id);
 
         let mut remotes = self.remotes.write().await;
@@ -585,12 +597,15 @@ impl ShardReplicaSet {
             self.replica_state.read().get_peer_state(peer_id),
         );
 
-        self.replica_state.write(|rs| {
+        let from_state = self.replica_state.write(|rs| {
             if rs.this_peer_id == *peer_id {
                 rs.is_local = true;
             }
+            let from_state = rs.get_peer_state(peer_id).copied();
             rs.set_peer_state(*peer_id, state);
+            from_state
         })?;
+        self.notify_replica_state_change(*peer_id, from_state, Some(state));
         self.update_locally_disabled(*peer_id);
         Ok(())
     }
@@ -614,6 +629,11 @@ impl ShardReplicaSet {
             state.set_peers(replicas.clone());
         })?;
 
+        for (peer_id, state) in &replicas {
+            let from_state = old_peers.get(peer_id).copied();
+            self.notify_replica_state_change(*peer_id, from_state, Some(*state));
+        }
+
         self.locally_disabled_peers.write().clear();
 
         let removed_peers = old_peers
@@ -622,6 +642,7 @@ impl ShardReplicaSet {
             .copied()
             .collect::<Vec<_>>();
         for peer_id in removed_peers {
+            self.notify_replica_state_change(peer_id, old_peers.get(&peer_id).copied(), None);
             self.remove_peer(peer_id).await?;
         }
 
@@ -827,6 +848,24 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Publish an event about the replica state change, if the state did actually change
+    fn notify_replica_state_change(
+        &self,
+        peer_id: PeerId,
+        from_state: Option<ReplicaState>,
+        to_state: Option<ReplicaState>,
+    ) {
+        if from_state != to_state {
+            issues::publish(ReplicaStateChangedEvent {
+                collection_id: self.collection_id.clone(),
+                shard_id: self.shard_id,
+                peer_id,
+                from_state,
+                to_state,
+            });
+        }
+    }
+
     fn notify_peer_failure(&self, peer_id: PeerId) {
         log::debug!("Notify peer failure: {}", peer_id);
         self.notify_peer_failure_cb.deref()(peer_id, self.shard_id)
@@ -871,6 +910,35 @@ impl ShardReplicaSet {
         local_shard.update_cutoff(cutoff).await
     }
 
+    /// Read changes from the WAL of the local shard.
+    ///
+    /// Also returns a receiver, which is notified when new changes are written to the WAL.
+    pub(crate) async fn read_changes(
+        &self,
+        request: &ChangesRequest,
+    ) -> CollectionResult<(ChangesResult, watch::Receiver<u64>)> {
+        let local_shard = self.local.read().await;
+        let Some(local_shard) = local_shard.as_ref() else {
+            return Err(CollectionError::NotFound {
+                what: "Peer does not have local shard".into(),
+            });
+        };
+
+        local_shard.read_changes(request)
+    }
+
+    /// Unregister a change consumer of the local shard.
+    pub(crate) async fn remove_change_consumer(&self, consumer: &str) -> CollectionResult<bool> {
+        let local_shard = self.local.read().await;
+        let Some(local_shard) = local_shard.as_ref() else {
+            return Err(CollectionError::NotFound {
+                what: "Peer does not have local shard".into(),
+            });
+        };
+
+        local_shard.remove_change_consumer(consumer)
+    }
+
     pub(crate) fn get_snapshots_storage_manager(&self) -> CollectionResult<SnapshotStorageManager> {
         SnapshotStorageManager::new(self.shared_storage_config.snapshots_config.clone())
     }
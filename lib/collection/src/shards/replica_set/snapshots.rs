@@ -2,6 +2,7 @@ use std::ops::Deref as _;
 use std::path::Path;
 
 use super::{ReplicaSetState, ReplicaState, ShardReplicaSet, REPLICA_STATE_FILE};
+use crate::common::snapshot_manifest::ShardManifest;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::dummy_shard::DummyShard;
@@ -10,26 +11,33 @@ use crate::shards::shard::{PeerId, Shard};
 use crate::shards::shard_config::ShardConfig;
 
 impl ShardReplicaSet {
+    /// Create snapshot of the replica set in `target_path`.
+    ///
+    /// Returns versions of all segments of the local shard, if there is one.
     pub async fn create_snapshot(
         &self,
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
-    ) -> CollectionResult<()> {
+        base: Option<&ShardManifest>,
+    ) -> CollectionResult<Option<ShardManifest>> {
         let local_read = self.local.read().await;
 
-        if let Some(local) = &*local_read {
-            local
-                .create_snapshot(temp_path, target_path, save_wal)
-                .await?
-        }
+        let manifest = match &*local_read {
+            Some(local) => Some(
+                local
+                    .create_snapshot(temp_path, target_path, save_wal, base)
+                    .await?,
+            ),
+            None => None,
+        };
 
         self.replica_state
             .save_to(target_path.join(REPLICA_STATE_FILE))?;
 
         let shard_config = ShardConfig::new_replica_set();
         shard_config.save(target_path)?;
-        Ok(())
+        Ok(manifest)
     }
 
     pub fn restore_snapshot(
@@ -553,6 +553,7 @@ mod tests {
         indexing_threshold: Some(50_000),
         flush_interval_sec: 30,
         max_optimization_threads: Some(2),
+        graph_healing_threshold: None,
     };
 
     async fn new_shard_replica_set(collection_dir: &TempDir) -> ShardReplicaSet {
@@ -578,6 +579,7 @@ mod tests {
             wal_config,
             hnsw_config: Default::default(),
             quantization_config: None,
+            strict_mode_config: None,
         };
 
         let shared_config = Arc::new(RwLock::new(config.clone()));
@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::hash;
 
+use segment::data_types::facets::{FacetResponse, FacetValueHit};
 use segment::types::{Payload, ScoredPoint};
 use tinyvec::TinyVec;
 
@@ -41,6 +42,43 @@ impl Resolve for CountResult {
     }
 }
 
+impl Resolve for FacetResponse {
+    /// Resolve the count of each value independently, same as for `CountResult`.
+    /// Values missing in a response are considered to have a count of zero.
+    fn resolve(responses: Vec<Self>, condition: ResolveCondition) -> Self {
+        let replicas = responses.len();
+
+        let mut counts_per_value: HashMap<_, Vec<usize>> = HashMap::new();
+        for hit in responses.into_iter().flat_map(|response| response.hits) {
+            counts_per_value
+                .entry(hit.value)
+                .or_default()
+                .push(hit.count);
+        }
+
+        let mut hits: Vec<_> = counts_per_value
+            .into_iter()
+            .filter_map(|(value, mut counts)| {
+                counts.resize(replicas, 0);
+
+                let count = match condition {
+                    ResolveCondition::All => counts.iter().copied().min().unwrap_or_default(),
+                    ResolveCondition::Majority => {
+                        counts.sort_unstable();
+                        counts.get(counts.len() / 2).copied().unwrap_or_default()
+                    }
+                };
+
+                (count > 0).then_some(FacetValueHit { value, count })
+            })
+            .collect();
+
+        hits.sort_unstable_by(|a, b| b.cmp(a));
+
+        Self { hits }
+    }
+}
+
 impl Resolve for Vec<Record> {
     fn resolve(records: Vec<Self>, condition: ResolveCondition) -> Self {
         let mut resolved = Resolver::resolve(records, |record| record.id, record_eq, condition);
@@ -524,6 +562,34 @@ mod test {
         test_resolve_simple(input_4(), expected_4_majority(), ResolveCondition::Majority);
     }
 
+    #[test]
+    fn resolve_facet_counts() {
+        use segment::data_types::facets::FacetValue;
+
+        let hit = |value: i64, count| FacetValueHit {
+            value: FacetValue::Int(value),
+            count,
+        };
+
+        let responses = vec![
+            FacetResponse {
+                hits: vec![hit(1, 10), hit(2, 5), hit(3, 1)],
+            },
+            FacetResponse {
+                hits: vec![hit(1, 9), hit(2, 6)],
+            },
+            FacetResponse {
+                hits: vec![hit(2, 7), hit(1, 8), hit(3, 1)],
+            },
+        ];
+
+        let all = FacetResponse::resolve(responses.clone(), ResolveCondition::All);
+        assert_eq!(all.hits, vec![hit(1, 8), hit(2, 5)]);
+
+        let majority = FacetResponse::resolve(responses, ResolveCondition::Majority);
+        assert_eq!(majority.hits, vec![hit(1, 9), hit(2, 6), hit(3, 1)]);
+    }
+
     fn test_resolve<T, E>(input: Vec<T>, expected: E, condition: ResolveCondition)
     where
         T: Resolve + Clone + PartialEq<E> + fmt::Debug,
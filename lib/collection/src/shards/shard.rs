@@ -3,9 +3,12 @@ use std::future::{self, Future};
 use std::path::Path;
 
 use common::types::TelemetryDetail;
+use tokio::sync::watch;
 
 use super::local_shard::clock_map::RecoveryPoint;
 use super::update_tracker::UpdateTracker;
+use crate::common::snapshot_manifest::ShardManifest;
+use crate::operations::change_ops::{ChangesRequest, ChangesResult};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
@@ -80,31 +83,32 @@ impl Shard {
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
-    ) -> CollectionResult<()> {
+        base: Option<&ShardManifest>,
+    ) -> CollectionResult<ShardManifest> {
         match self {
             Shard::Local(local_shard) => {
                 local_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, base)
                     .await
             }
             Shard::Proxy(proxy_shard) => {
                 proxy_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, base)
                     .await
             }
             Shard::ForwardProxy(proxy_shard) => {
                 proxy_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, base)
                     .await
             }
             Shard::QueueProxy(proxy_shard) => {
                 proxy_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, base)
                     .await
             }
             Shard::Dummy(dummy_shard) => {
                 dummy_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, base)
                     .await
             }
         }
@@ -209,4 +213,36 @@ impl Shard {
             ))),
         }
     }
+
+    /// Read changes from the WAL of the local shard.
+    ///
+    /// Also returns a receiver, which is notified when new changes are written to the WAL.
+    pub fn read_changes(
+        &self,
+        request: &ChangesRequest,
+    ) -> CollectionResult<(ChangesResult, watch::Receiver<u64>)> {
+        let local_shard = self.changes_source()?;
+        // Subscribe first, so that no change written after the read is missed
+        let writes = local_shard.subscribe_changes();
+        let changes = local_shard.read_changes(request)?;
+        Ok((changes, writes))
+    }
+
+    pub fn remove_change_consumer(&self, consumer: &str) -> CollectionResult<bool> {
+        self.changes_source()?.remove_change_consumer(consumer)
+    }
+
+    fn changes_source(&self) -> CollectionResult<&LocalShard> {
+        match self {
+            Self::Local(local_shard) => Ok(local_shard),
+            Self::ForwardProxy(proxy_shard) => Ok(&proxy_shard.wrapped_shard),
+
+            Self::Proxy(_) | Self::QueueProxy(_) | Self::Dummy(_) => {
+                Err(CollectionError::service_error(format!(
+                    "Reading changes not supported on {}",
+                    self.variant_name(),
+                )))
+            }
+        }
+    }
 }
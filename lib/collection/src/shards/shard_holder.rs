@@ -1017,7 +1017,12 @@ impl ShardHolder {
             .tempdir_in(temp_dir)?;
 
         shard
-            .create_snapshot(snapshot_temp_dir.path(), snapshot_target_dir.path(), false)
+            .create_snapshot(
+                snapshot_temp_dir.path(),
+                snapshot_target_dir.path(),
+                false,
+                None,
+            )
             .await?;
 
         let snapshot_temp_dir_path = snapshot_temp_dir.path().to_path_buf();
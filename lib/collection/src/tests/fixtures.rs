@@ -17,6 +17,7 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     indexing_threshold: Some(50_000),
     flush_interval_sec: 30,
     max_optimization_threads: Some(2),
+    graph_healing_threshold: None,
 };
 
 pub fn create_collection_config() -> CollectionConfig {
@@ -41,6 +42,7 @@ pub fn create_collection_config() -> CollectionConfig {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: None,
     }
 }
 
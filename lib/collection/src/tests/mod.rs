@@ -63,6 +63,8 @@ async fn test_optimization_process() {
     let optimizers_log = Arc::new(Mutex::new(Default::default()));
     let segments: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
     let handles = UpdateHandler::launch_optimization(
+        &"test".to_string(),
+        0,
         optimizers.clone(),
         optimizers_log.clone(),
         &CpuBudget::default(),
@@ -105,6 +107,8 @@ async fn test_optimization_process() {
     }
 
     let handles = UpdateHandler::launch_optimization(
+        &"test".to_string(),
+        0,
         optimizers.clone(),
         optimizers_log.clone(),
         &CpuBudget::default(),
@@ -158,6 +162,8 @@ async fn test_cancel_optimization() {
     let optimizers_log = Arc::new(Mutex::new(Default::default()));
     let segments: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
     let handles = UpdateHandler::launch_optimization(
+        &"test".to_string(),
+        0,
         optimizers.clone(),
         optimizers_log.clone(),
         &CpuBudget::default(),
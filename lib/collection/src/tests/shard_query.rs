@@ -56,7 +56,7 @@ async fn test_shard_query_rrf_rescoring() {
         with_payload: WithPayloadInterface::Bool(false),
     };
 
-    let sources_scores = shard.query(Arc::new(query), &current_runtime).await;
+    let sources_scores = shard.query(Arc::new(query), &current_runtime, None).await;
     let expected_error =
         CollectionError::bad_request("cannot apply Fusion without prefetches".to_string());
     assert!(matches!(sources_scores, Err(err) if err == expected_error));
@@ -74,6 +74,7 @@ async fn test_shard_query_rrf_rescoring() {
         params: None,
         filter: None,
         score_threshold: None,
+        weight: None,
     };
     let outer_limit = 2;
     let query = ShardQueryRequest {
@@ -89,7 +90,7 @@ async fn test_shard_query_rrf_rescoring() {
     };
 
     let sources_scores = shard
-        .query(Arc::new(query), &current_runtime)
+        .query(Arc::new(query), &current_runtime, None)
         .await
         .unwrap();
 
@@ -115,6 +116,7 @@ async fn test_shard_query_rrf_rescoring() {
         params: None,
         filter: None,
         score_threshold: None,
+        weight: None,
     };
     let outer_limit = 2;
     let query = ShardQueryRequest {
@@ -133,7 +135,7 @@ async fn test_shard_query_rrf_rescoring() {
     };
 
     let sources_scores = shard
-        .query(Arc::new(query), &current_runtime)
+        .query(Arc::new(query), &current_runtime, None)
         .await
         .unwrap();
 
@@ -188,6 +190,7 @@ async fn test_shard_query_vector_rescoring() {
         params: None,
         filter: None,
         score_threshold: None,
+        weight: None,
     };
 
     // rescoring against a vector without prefetches
@@ -205,7 +208,7 @@ async fn test_shard_query_vector_rescoring() {
     };
 
     let sources_scores = shard
-        .query(Arc::new(query), &current_runtime)
+        .query(Arc::new(query), &current_runtime, None)
         .await
         .unwrap();
 
@@ -229,7 +232,7 @@ async fn test_shard_query_vector_rescoring() {
     };
 
     let sources_scores = shard
-        .query(Arc::new(query), &current_runtime)
+        .query(Arc::new(query), &current_runtime, None)
         .await
         .unwrap();
 
@@ -256,7 +259,7 @@ async fn test_shard_query_vector_rescoring() {
     };
 
     let sources_scores = shard
-        .query(Arc::new(query), &current_runtime)
+        .query(Arc::new(query), &current_runtime, None)
         .await
         .unwrap();
 
@@ -314,7 +317,7 @@ async fn test_shard_query_payload_vector() {
     };
 
     let sources_scores = shard
-        .query(Arc::new(query), &current_runtime)
+        .query(Arc::new(query), &current_runtime, None)
         .await
         .unwrap();
 
@@ -27,12 +27,15 @@ use crate::collection_manager::optimizers::segment_optimizer::{
 use crate::collection_manager::optimizers::{Tracker, TrackerLog, TrackerStatus};
 use crate::common::stoppable_task::{spawn_stoppable, StoppableTaskHandle};
 use crate::config::CollectionParams;
+use crate::events::{OptimizationFinishedEvent, OptimizationStartedEvent};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::local_shard::LocalShardClocks;
+use crate::shards::shard::ShardId;
+use crate::shards::CollectionId;
 use crate::wal::WalError;
-use crate::wal_delta::LockedWal;
+use crate::wal_delta::{LockedWal, WalRetentionGuard};
 
 /// Interval at which the optimizer worker cleans up old optimization handles
 ///
@@ -80,6 +83,9 @@ pub enum OptimizerSignal {
 
 /// Structure, which holds object, required for processing updates of the collection
 pub struct UpdateHandler {
+    /// Collection and shard this handler belongs to, used to identify published events
+    collection_id: CollectionId,
+    shard_id: ShardId,
     shared_storage_config: Arc<SharedStorageConfig>,
     /// List of used optimizers
     pub optimizers: Arc<Vec<Arc<Optimizer>>>,
@@ -108,6 +114,8 @@ pub struct UpdateHandler {
     /// queue proxy shard.
     /// Defaults to `u64::MAX` to allow acknowledging all confirmed versions.
     pub(super) wal_keep_from: Arc<AtomicU64>,
+    /// Prevents acknowledging WAL records, which change data capture consumers still need.
+    wal_retention: WalRetentionGuard,
     optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
     /// Maximum number of concurrent optimization jobs in this update handler.
     /// This parameter depends on the optimizer config and should be updated accordingly.
@@ -122,6 +130,8 @@ pub struct UpdateHandler {
 impl UpdateHandler {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
+        collection_id: CollectionId,
+        shard_id: ShardId,
         shared_storage_config: Arc<SharedStorageConfig>,
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         optimizers_log: Arc<Mutex<TrackerLog>>,
@@ -133,8 +143,11 @@ impl UpdateHandler {
         max_optimization_threads: Option<usize>,
         clocks: LocalShardClocks,
         shard_path: PathBuf,
+        wal_retention: WalRetentionGuard,
     ) -> UpdateHandler {
         UpdateHandler {
+            collection_id,
+            shard_id,
             shared_storage_config,
             optimizers,
             segments,
@@ -147,6 +160,7 @@ impl UpdateHandler {
             runtime_handle,
             wal,
             wal_keep_from: Arc::new(u64::MAX.into()),
+            wal_retention,
             flush_interval_sec,
             optimization_handles: Arc::new(TokioMutex::new(vec![])),
             max_optimization_threads,
@@ -159,6 +173,8 @@ impl UpdateHandler {
     pub fn run_workers(&mut self, update_receiver: Receiver<UpdateSignal>) {
         let (tx, rx) = mpsc::channel(self.shared_storage_config.update_queue_size);
         self.optimizer_worker = Some(self.runtime_handle.spawn(Self::optimization_worker_fn(
+            self.collection_id.clone(),
+            self.shard_id,
             self.optimizers.clone(),
             tx.clone(),
             rx,
@@ -181,6 +197,7 @@ impl UpdateHandler {
             self.segments.clone(),
             self.wal.clone(),
             self.wal_keep_from.clone(),
+            self.wal_retention.clone(),
             self.flush_interval_sec,
             flush_rx,
             self.clocks.clone(),
@@ -247,7 +264,10 @@ impl UpdateHandler {
     /// Checks conditions for all optimizers until there is no suggested segment
     /// Starts a task for each optimization
     /// Returns handles for started tasks
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn launch_optimization<F>(
+        collection_id: &CollectionId,
+        shard_id: ShardId,
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         optimizers_log: Arc<Mutex<TrackerLog>>,
         optimizer_cpu_budget: &CpuBudget,
@@ -301,6 +321,7 @@ impl UpdateHandler {
                 );
 
                 let optimizer = optimizer.clone();
+                let collection_id = collection_id.clone();
                 let optimizers_log = optimizers_log.clone();
                 let segments = segments.clone();
                 let nsi = nonoptimal_segment_ids.clone();
@@ -317,16 +338,33 @@ impl UpdateHandler {
                             let tracker_handle = tracker.handle();
                             optimizers_log.lock().register(tracker);
 
+                            issues::publish(OptimizationStartedEvent {
+                                collection_id: collection_id.clone(),
+                                shard_id,
+                                optimizer: optimizer.name().to_string(),
+                                segment_ids: nsi.clone(),
+                            });
+                            let publish_finished = |status: TrackerStatus| {
+                                issues::publish(OptimizationFinishedEvent {
+                                    collection_id,
+                                    shard_id,
+                                    optimizer: optimizer.name().to_string(),
+                                    segment_ids: nsi.clone(),
+                                    status,
+                                });
+                            };
+
                             // Optimize and handle result
                             match optimizer.as_ref().optimize(
                                 segments.clone(),
-                                nsi,
+                                nsi.clone(),
                                 permit,
                                 stopped,
                             ) {
                                 // Perform some actions when optimization if finished
                                 Ok(result) => {
                                     tracker_handle.update(TrackerStatus::Done);
+                                    publish_finished(TrackerStatus::Done);
                                     callback(result);
                                     result
                                 }
@@ -335,7 +373,8 @@ impl UpdateHandler {
                                     CollectionError::Cancelled { description } => {
                                         debug!("Optimization cancelled - {description}");
                                         tracker_handle
-                                            .update(TrackerStatus::Cancelled(description));
+                                            .update(TrackerStatus::Cancelled(description.clone()));
+                                        publish_finished(TrackerStatus::Cancelled(description));
                                         false
                                     }
                                     _ => {
@@ -349,6 +388,7 @@ impl UpdateHandler {
 
                                         tracker_handle
                                             .update(TrackerStatus::Error(error.to_string()));
+                                        publish_finished(TrackerStatus::Error(error.to_string()));
 
                                         panic!("Optimization error: {error}");
                                     }
@@ -439,7 +479,10 @@ impl UpdateHandler {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn process_optimization(
+        collection_id: &CollectionId,
+        shard_id: ShardId,
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         segments: LockedSegmentHolder,
         optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
@@ -449,6 +492,8 @@ impl UpdateHandler {
         limit: usize,
     ) {
         let mut new_handles = Self::launch_optimization(
+            collection_id,
+            shard_id,
             optimizers.clone(),
             optimizers_log,
             optimizer_cpu_budget,
@@ -495,6 +540,8 @@ impl UpdateHandler {
 
     #[allow(clippy::too_many_arguments)]
     async fn optimization_worker_fn(
+        collection_id: CollectionId,
+        shard_id: ShardId,
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         sender: Sender<OptimizerSignal>,
         mut receiver: Receiver<OptimizerSignal>,
@@ -592,6 +639,8 @@ impl UpdateHandler {
                     }
 
                     Self::process_optimization(
+                        &collection_id,
+                        shard_id,
                         optimizers.clone(),
                         segments.clone(),
                         optimization_handles.clone(),
@@ -686,6 +735,7 @@ impl UpdateHandler {
         segments: LockedSegmentHolder,
         wal: LockedWal,
         wal_keep_from: Arc<AtomicU64>,
+        wal_retention: WalRetentionGuard,
         flush_interval_sec: u64,
         mut stop_receiver: oneshot::Receiver<()>,
         clocks: LocalShardClocks,
@@ -725,12 +775,25 @@ impl UpdateHandler {
                 }
             };
 
+            match wal_retention.remove_expired() {
+                Ok(expired) => {
+                    for consumer in expired {
+                        log::warn!(
+                            "Dropped change consumer {consumer}, it did not acknowledge changes for too long"
+                        );
+                    }
+                }
+                Err(err) => log::warn!("Failed to drop expired change consumers: {err}"),
+            }
+
             // Acknowledge confirmed version in WAL, but don't acknowledge the specified
             // `keep_from` index or higher.
             // This is to prevent truncating WAL entries that other bits of code still depend on
-            // such as the queue proxy shard.
+            // such as the queue proxy shard or change data capture consumers.
             // Default keep_from is `u64::MAX` to allow acknowledging all confirmed.
-            let keep_from = wal_keep_from.load(std::sync::atomic::Ordering::Relaxed);
+            let keep_from = wal_keep_from
+                .load(std::sync::atomic::Ordering::Relaxed)
+                .min(wal_retention.keep_from().unwrap_or(u64::MAX));
 
             // If we should keep the first message, do not acknowledge at all
             if keep_from == 0 {
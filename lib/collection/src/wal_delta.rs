@@ -1,11 +1,20 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use parking_lot::{Mutex as ParkingMutex, MutexGuard as ParkingMutexGuard};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tokio::sync::Mutex;
+use tokio::fs::remove_file;
+use tokio::sync::{watch, Mutex};
 
+use crate::common::file_utils::move_file;
+use crate::operations::types::CollectionResult;
 use crate::operations::{ClockTag, OperationWithClockTag};
+use crate::save_on_disk::SaveOnDisk;
 use crate::shards::local_shard::clock_map::{ClockMap, RecoveryPoint};
 use crate::wal::SerdeWal;
 
@@ -28,6 +37,9 @@ pub struct RecoverableWal {
     ///   - (so if we advance these clocks, we have to advance `newest_clocks` as well)
     /// - this WAL cannot resolve any delta below any of these clocks
     pub(super) oldest_clocks: Arc<Mutex<ClockMap>>,
+
+    /// Number of the last record written to the WAL, to wake up waiting change readers.
+    last_written: watch::Sender<u64>,
 }
 
 impl RecoverableWal {
@@ -36,10 +48,12 @@ impl RecoverableWal {
         highest_clocks: Arc<Mutex<ClockMap>>,
         cutoff_clocks: Arc<Mutex<ClockMap>>,
     ) -> Self {
+        let last_index = wal.lock().last_index();
         Self {
             wal,
             newest_clocks: highest_clocks,
             oldest_clocks: cutoff_clocks,
+            last_written: watch::Sender::new(last_index),
         }
     }
 
@@ -72,7 +86,14 @@ impl RecoverableWal {
 
         // Write operation to WAL
         let mut wal_lock = self.wal.lock();
-        wal_lock.write(operation).map(|op_num| (op_num, wal_lock))
+        let op_num = wal_lock.write(operation)?;
+        self.last_written.send_replace(op_num);
+        Ok((op_num, wal_lock))
+    }
+
+    /// Subscribe to the number of the last record written to the WAL.
+    pub fn subscribe_writes(&self) -> watch::Receiver<u64> {
+        self.last_written.subscribe()
     }
 
     /// Update the cutoff clock map based on the given recovery point
@@ -144,6 +165,141 @@ impl RecoverableWal {
     }
 }
 
+const CHANGE_CONSUMERS_FILE: &str = "change_consumers.json";
+
+/// Consumers, which did not acknowledge changes for this long, are dropped,
+/// so that an abandoned consumer doesn't keep the WAL from being truncated forever
+pub const CHANGE_CONSUMER_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Repeated acknowledgements of the same position are persisted at most once per this many seconds
+const ACKNOWLEDGE_REFRESH_INTERVAL_SECS: i64 = 60;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+struct ConsumerPosition {
+    /// Lowest WAL record number to keep for the consumer
+    op_num: u64,
+    /// Time of the last acknowledgement
+    acknowledged_at: DateTime<Utc>,
+}
+
+impl ConsumerPosition {
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        (now - self.acknowledged_at)
+            .to_std()
+            .is_ok_and(|elapsed| elapsed > CHANGE_CONSUMER_TTL)
+    }
+}
+
+/// WAL positions of registered change data capture consumers.
+///
+/// Each consumer acknowledges the record it has consumed last. This record and all later ones are
+/// not truncated from the WAL, so the consumer can resume reading after it. Consumers, which don't
+/// acknowledge anything for [`CHANGE_CONSUMER_TTL`], are considered abandoned and are dropped.
+#[derive(Clone, Debug)]
+pub struct WalRetentionGuard {
+    consumers: Arc<SaveOnDisk<HashMap<String, ConsumerPosition>>>,
+}
+
+impl WalRetentionGuard {
+    /// Load consumer positions of the shard, no consumers are registered if there is no file yet
+    pub fn load(shard_path: &Path) -> CollectionResult<Self> {
+        let consumers = SaveOnDisk::load_or_init_default(Self::consumers_path(shard_path))?;
+        Ok(Self {
+            consumers: Arc::new(consumers),
+        })
+    }
+
+    /// Register `consumer` or move it to `op_num`, which may be lower than its previous position
+    pub fn acknowledge(&self, consumer: &str, op_num: u64) -> CollectionResult<()> {
+        let now = Utc::now();
+        let is_fresh = |position: &ConsumerPosition| {
+            position.op_num == op_num
+                && now - position.acknowledged_at
+                    < chrono::Duration::seconds(ACKNOWLEDGE_REFRESH_INTERVAL_SECS)
+        };
+        if self.consumers.read().get(consumer).is_some_and(is_fresh) {
+            return Ok(());
+        }
+        self.consumers.write(|consumers| {
+            consumers.insert(
+                consumer.to_string(),
+                ConsumerPosition {
+                    op_num,
+                    acknowledged_at: now,
+                },
+            );
+        })?;
+        Ok(())
+    }
+
+    /// Unregister `consumer`, returns `false` if it was not registered
+    pub fn remove(&self, consumer: &str) -> CollectionResult<bool> {
+        if !self.consumers.read().contains_key(consumer) {
+            return Ok(false);
+        }
+        self.consumers
+            .write(|consumers| consumers.remove(consumer).is_some())?;
+        Ok(true)
+    }
+
+    /// Unregister consumers, which did not acknowledge changes within the TTL.
+    ///
+    /// Returns names of the removed consumers.
+    pub fn remove_expired(&self) -> CollectionResult<Vec<String>> {
+        let now = Utc::now();
+        let expired: Vec<_> = self
+            .consumers
+            .read()
+            .iter()
+            .filter(|(_, position)| position.is_expired(now))
+            .map(|(consumer, _)| consumer.clone())
+            .collect();
+        if expired.is_empty() {
+            return Ok(expired);
+        }
+        self.consumers.write(|consumers| {
+            // Consumer might have acknowledged changes in the meantime
+            consumers.retain(|_, position| !position.is_expired(now));
+        })?;
+        Ok(expired)
+    }
+
+    /// Lowest WAL record number, which must be kept for the registered consumers
+    ///
+    /// Expired consumers are ignored, even if they are not removed yet.
+    pub fn keep_from(&self) -> Option<u64> {
+        let now = Utc::now();
+        self.consumers
+            .read()
+            .values()
+            .filter(|position| !position.is_expired(now))
+            .map(|position| position.op_num)
+            .min()
+    }
+
+    /// Move consumer positions on disk from one shard path to another.
+    pub async fn move_data(from: &Path, to: &Path) -> CollectionResult<()> {
+        let consumers_from = Self::consumers_path(from);
+        if consumers_from.exists() {
+            move_file(consumers_from, Self::consumers_path(to)).await?;
+        }
+        Ok(())
+    }
+
+    /// Delete consumer positions from disk at the given shard path.
+    pub async fn delete_data(shard_path: &Path) -> CollectionResult<()> {
+        let consumers_path = Self::consumers_path(shard_path);
+        if consumers_path.exists() {
+            remove_file(consumers_path).await?;
+        }
+        Ok(())
+    }
+
+    fn consumers_path(shard_path: &Path) -> PathBuf {
+        shard_path.join(CHANGE_CONSUMERS_FILE)
+    }
+}
+
 /// Resolve the WAL delta for the given `recovery_point`
 ///
 /// A `local_wal`, `newest_clocks` and `oldest_clocks` are required to resolve the
@@ -1958,4 +2114,65 @@ mod tests {
         )
         .unwrap_err();
     }
+
+    #[test]
+    fn test_wal_retention_guard() {
+        let dir = Builder::new().prefix("wal_retention").tempdir().unwrap();
+
+        let guard = WalRetentionGuard::load(dir.path()).unwrap();
+        assert_eq!(guard.keep_from(), None);
+
+        guard.acknowledge("first", 10).unwrap();
+        guard.acknowledge("second", 5).unwrap();
+        assert_eq!(guard.keep_from(), Some(5));
+
+        // Consumer may move back, e.g. to re-read changes
+        guard.acknowledge("first", 3).unwrap();
+        assert_eq!(guard.keep_from(), Some(3));
+
+        // Positions are persisted
+        let guard = WalRetentionGuard::load(dir.path()).unwrap();
+        assert_eq!(guard.keep_from(), Some(3));
+
+        assert!(guard.remove("first").unwrap());
+        assert!(!guard.remove("first").unwrap());
+        assert_eq!(guard.keep_from(), Some(5));
+
+        assert!(guard.remove("second").unwrap());
+        assert_eq!(guard.keep_from(), None);
+    }
+
+    #[test]
+    fn test_wal_retention_guard_expiry() {
+        let dir = Builder::new().prefix("wal_retention").tempdir().unwrap();
+
+        let guard = WalRetentionGuard::load(dir.path()).unwrap();
+        guard.acknowledge("active", 10).unwrap();
+        guard.acknowledge("abandoned", 5).unwrap();
+        assert_eq!(guard.keep_from(), Some(5));
+
+        // Consumer stopped acknowledging changes a while ago
+        guard
+            .consumers
+            .write(|consumers| {
+                let position = consumers.get_mut("abandoned").unwrap();
+                position.acknowledged_at -= chrono::Duration::from_std(CHANGE_CONSUMER_TTL)
+                    .unwrap()
+                    + chrono::Duration::seconds(1);
+            })
+            .unwrap();
+
+        // Expired consumer doesn't hold the WAL back, even before it is removed
+        assert_eq!(guard.keep_from(), Some(10));
+
+        assert_eq!(
+            guard.remove_expired().unwrap(),
+            vec!["abandoned".to_string()]
+        );
+        assert!(guard.remove_expired().unwrap().is_empty());
+
+        let guard = WalRetentionGuard::load(dir.path()).unwrap();
+        assert!(!guard.remove("abandoned").unwrap());
+        assert_eq!(guard.keep_from(), Some(10));
+    }
 }
@@ -32,6 +32,7 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     indexing_threshold: Some(50_000),
     flush_interval_sec: 30,
     max_optimization_threads: Some(2),
+    graph_healing_threshold: None,
 };
 
 #[cfg(test)]
@@ -54,6 +55,7 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: None,
     };
 
     let snapshot_path = collection_path.join("snapshots");
@@ -49,6 +49,16 @@ pub fn validate_not_empty(value: &Option<String>) -> Result<(), ValidationError>
     }
 }
 
+/// Validate the value is a finite number, not smaller than zero.
+pub fn validate_finite_non_negative(value: &f32) -> Result<(), ValidationError> {
+    if !value.is_finite() {
+        let mut err = ValidationError::new("finite");
+        err.message.replace("value must be a finite number".into());
+        return Err(err);
+    }
+    validate_range_generic(*value, Some(0.0), None)
+}
+
 /// Validate the collection name contains no illegal characters
 ///
 /// This does not check the length of the name.
@@ -261,6 +271,17 @@ mod tests {
         assert!(validate_not_empty(&Some("".into())).is_err());
     }
 
+    #[test]
+    fn test_validate_finite_non_negative() {
+        assert!(validate_finite_non_negative(&0.0).is_ok());
+        assert!(validate_finite_non_negative(&0.5).is_ok());
+        assert!(validate_finite_non_negative(&100.0).is_ok());
+        assert!(validate_finite_non_negative(&-0.1).is_err());
+        assert!(validate_finite_non_negative(&f32::NAN).is_err());
+        assert!(validate_finite_non_negative(&f32::INFINITY).is_err());
+        assert!(validate_finite_non_negative(&f32::NEG_INFINITY).is_err());
+    }
+
     #[test]
     fn test_validate_collection_name() {
         assert!(validate_collection_name("test_collection").is_ok());
@@ -48,6 +48,8 @@ fn multi_vector_search_benchmark(c: &mut Criterion) {
                 quantization_config: None,
                 multivec_config: Some(MultiVectorConfig::default()), // uses multivec config
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
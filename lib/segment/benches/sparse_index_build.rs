@@ -65,7 +65,7 @@ fn sparse_vector_index_build_benchmark(c: &mut Criterion) {
     }
 
     // save index config to disk
-    let index_config = SparseIndexConfig::new(Some(10_000), SparseIndexType::ImmutableRam);
+    let index_config = SparseIndexConfig::new(Some(10_000), SparseIndexType::ImmutableRam, None);
 
     let permit_cpu_count = num_rayon_threads(0);
     let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
@@ -4,10 +4,12 @@ pub mod macros;
 pub mod mmap_type;
 pub mod operation_error;
 pub mod operation_time_statistics;
+pub mod random_sample;
 pub mod reciprocal_rank_fusion;
 pub mod rocksdb_buffered_delete_wrapper;
 pub mod rocksdb_buffered_update_wrapper;
 pub mod rocksdb_wrapper;
+pub mod score_fusion;
 pub mod utils;
 pub mod validate_snapshot_archive;
 pub mod vector_utils;
@@ -17,7 +19,9 @@ use std::sync::atomic::AtomicBool;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::vectors::{QueryVector, VectorRef};
-use crate::types::{SegmentConfig, SparseVectorDataConfig, VectorDataConfig};
+use crate::types::{
+    PointIdType, SegmentConfig, SparseVectorDataConfig, VectorDataConfig, VectorValidationConfig,
+};
 
 pub type Flusher = Box<dyn FnOnce() -> OperationResult<()> + Send>;
 
@@ -137,6 +141,34 @@ pub fn check_named_vectors(
     Ok(())
 }
 
+/// Check that the given named vectors of a point pass the validation configured for them.
+///
+/// Returns an error, which identifies the point and the vector, if any vector is invalid.
+pub fn check_named_vectors_validation(
+    point_id: PointIdType,
+    vectors: &NamedVectors,
+    segment_config: &SegmentConfig,
+) -> OperationResult<()> {
+    for (vector_name, vector) in vectors.iter() {
+        let Some(validation) = segment_config
+            .vector_data
+            .get(vector_name)
+            .and_then(|vector_config| vector_config.validation)
+        else {
+            continue;
+        };
+        let result = match vector {
+            VectorRef::Dense(vector) => validation.check_dense(vector),
+            VectorRef::MultiDense(multi_vector) => multi_vector
+                .multi_vectors()
+                .try_for_each(|vector| validation.check_dense(vector)),
+            VectorRef::Sparse(_) => Ok(()),
+        };
+        result.map_err(|problem| VectorValidationConfig::error(point_id, vector_name, problem))?;
+    }
+    Ok(())
+}
+
 /// Get the vector config for the given name, or return a name error.
 ///
 /// Returns an error if incompatible.
@@ -0,0 +1,106 @@
+//! Merging of random samples taken from disjoint populations of different sizes.
+//!
+//! Every point of a population gets an independent uniform random key, and a sample of size `k`
+//! consists of the points with the `k` largest keys. This way, samples of different populations
+//! can be merged by their keys, and the result is a uniform sample of the union of populations.
+//!
+//! Keys are never materialized for the whole population: the largest keys of `n` uniform variables
+//! are generated directly, and assigned to a uniform random subset of the population.
+
+use common::types::ScoreType;
+use rand::Rng;
+
+/// Log-space keys of the `count` largest out of `population` independent uniform random variables,
+/// in descending order.
+///
+/// Log-space keeps the keys distinguishable for large populations, where all of the largest keys
+/// are very close to `1.0`.
+pub fn largest_random_keys<R: Rng + ?Sized>(
+    population: usize,
+    count: usize,
+    rng: &mut R,
+) -> Vec<ScoreType> {
+    let count = count.min(population);
+    let mut keys = Vec::with_capacity(count);
+    let mut log_key = 0.0f64;
+    for i in 0..count {
+        // The maximum of `m` uniform variables is distributed as `U^(1/m)`
+        let remaining = (population - i) as f64;
+        let uniform: f64 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+        log_key += uniform.ln() / remaining;
+        keys.push(log_key as ScoreType);
+    }
+    keys
+}
+
+/// Assign random keys to a sample of the given population.
+///
+/// The `sample` must be a uniform random subset of the population, in random order.
+/// If the population is smaller than the sample, the sample size is used as the population.
+pub fn key_random_sample<T, R: Rng + ?Sized>(
+    sample: Vec<T>,
+    population: usize,
+    rng: &mut R,
+) -> Vec<(T, ScoreType)> {
+    let keys = largest_random_keys(population.max(sample.len()), sample.len(), rng);
+    sample.into_iter().zip(keys).collect()
+}
+
+/// Merge keyed samples of disjoint populations into a single sample of up to `limit` items,
+/// in descending order of keys.
+pub fn merge_keyed_samples<T>(
+    samples: impl IntoIterator<Item = Vec<(T, ScoreType)>>,
+    limit: usize,
+) -> Vec<(T, ScoreType)> {
+    let mut merged: Vec<_> = samples.into_iter().flatten().collect();
+    merged.sort_unstable_by(|(_, a), (_, b)| b.total_cmp(a));
+    merged.truncate(limit);
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn test_largest_random_keys_descending() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let keys = largest_random_keys(1_000_000_000, 100, &mut rng);
+        assert_eq!(keys.len(), 100);
+        assert!(keys.windows(2).all(|pair| pair[0] > pair[1]));
+        assert!(keys.iter().all(|key| *key < 0.0));
+
+        assert_eq!(largest_random_keys(3, 10, &mut rng).len(), 3);
+    }
+
+    #[test]
+    fn test_merge_is_weighted_by_population() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let small_population = 100;
+        let large_population = 900;
+        let limit = 10;
+        let rounds = 2000;
+
+        let mut from_large = 0;
+        for _ in 0..rounds {
+            // Each population contributes a full sample of `limit` items
+            let small = key_random_sample(vec![false; limit], small_population, &mut rng);
+            let large = key_random_sample(vec![true; limit], large_population, &mut rng);
+
+            let merged = merge_keyed_samples([small, large], limit);
+            assert_eq!(merged.len(), limit);
+            from_large += merged.iter().filter(|(is_large, _)| *is_large).count();
+        }
+
+        // 90% of the merged sample is expected to come from the large population
+        let share = from_large as f64 / (rounds * limit) as f64;
+        assert!(
+            (share - 0.9).abs() < 0.02,
+            "share of the large population: {share}"
+        );
+    }
+}
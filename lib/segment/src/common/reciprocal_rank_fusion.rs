@@ -4,6 +4,7 @@
 use std::collections::hash_map::Entry;
 
 use ahash::{HashMap, HashMapExt};
+use common::types::ScoreType;
 use ordered_float::OrderedFloat;
 
 use crate::types::{ExtendedPointId, ScoredPoint};
@@ -20,15 +21,22 @@ fn position_score(position: usize) -> f32 {
 /// Each response can have a different length.
 /// The input scores are irrelevant, only the order matters.
 ///
+/// `weights` are applied to the RRF scores of the response with the same index.
+/// Responses without a weight get a weight of `1.0`.
+///
 /// The output is a single sorted list of ScoredPoint.
 /// Does not break ties.
-pub fn rrf_scoring(responses: impl IntoIterator<Item = Vec<ScoredPoint>>) -> Vec<ScoredPoint> {
+pub fn rrf_scoring(
+    responses: impl IntoIterator<Item = Vec<ScoredPoint>>,
+    weights: &[ScoreType],
+) -> Vec<ScoredPoint> {
     // track scored points by id
     let mut points_by_id: HashMap<ExtendedPointId, ScoredPoint> = HashMap::new();
 
-    for response in responses {
+    for (idx, response) in responses.into_iter().enumerate() {
+        let weight = weights.get(idx).copied().unwrap_or(1.0);
         for (pos, mut point) in response.into_iter().enumerate() {
-            let rrf_score = position_score(pos);
+            let rrf_score = weight * position_score(pos);
             match points_by_id.entry(point.id) {
                 Entry::Occupied(mut entry) => {
                     // accumulate score
@@ -72,14 +80,14 @@ mod tests {
     #[test]
     fn test_rrf_scoring_empty() {
         let responses = vec![];
-        let scored_points = rrf_scoring(responses);
+        let scored_points = rrf_scoring(responses, &[]);
         assert_eq!(scored_points.len(), 0);
     }
 
     #[test]
     fn test_rrf_scoring_one() {
         let responses = vec![vec![make_scored_point(1, 0.9)]];
-        let scored_points = rrf_scoring(responses);
+        let scored_points = rrf_scoring(responses, &[]);
         assert_eq!(scored_points.len(), 1);
         assert_eq!(scored_points[0].id, 1.into());
         assert_eq!(scored_points[0].score, 0.5); // 1 / (0 + 2)
@@ -102,7 +110,7 @@ mod tests {
         ];
 
         // top 10
-        let scored_points = rrf_scoring(responses.clone());
+        let scored_points = rrf_scoring(responses.clone(), &[]);
         assert_eq!(scored_points.len(), 4);
         // assert that the list is sorted
         assert!(scored_points.windows(2).all(|w| w[0].score >= w[1].score));
@@ -119,5 +127,16 @@ mod tests {
 
         assert_eq!(scored_points[3].id, 5.into());
         assert_eq!(scored_points[3].score, 0.5);
+
+        // weighted
+        let scored_points = rrf_scoring(responses, &[4.0]);
+        assert_eq!(scored_points.len(), 4);
+        assert!(scored_points.windows(2).all(|w| w[0].score >= w[1].score));
+
+        assert_eq!(scored_points[0].id, 2.into());
+        assert_eq!(scored_points[0].score, 2.3333333);
+
+        assert_eq!(scored_points[1].id, 1.into());
+        assert_eq!(scored_points[1].score, 2.0833335);
     }
 }
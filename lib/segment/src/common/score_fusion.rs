@@ -0,0 +1,222 @@
+//! Score-based fusion is a method for combining results from multiple sources.
+//! Unlike RRF, it takes the score magnitudes into account: the scores of each source
+//! are first normalized into a comparable range, and then summed up per point.
+
+use std::collections::hash_map::Entry;
+
+use ahash::{HashMap, HashMapExt};
+use common::types::ScoreType;
+use ordered_float::OrderedFloat;
+
+use crate::types::{ExtendedPointId, ScoredPoint};
+
+/// Normalized score assigned to all points of a source in which every score is the same.
+const DEGENERATE_NORMALIZED_SCORE: ScoreType = 0.5;
+
+/// Number of standard deviations from the mean that are mapped into `[0, 1]` by the
+/// distribution-based normalization.
+const DISTRIBUTION_SIGMAS: ScoreType = 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreNormalization {
+    /// Map `mean - 3σ` and `mean + 3σ` of each source into `0` and `1` respectively.
+    ///
+    /// Robust to outliers, so one extreme score does not squash all the others.
+    Distribution,
+
+    /// Map the worst and the best score of each source into `0` and `1` respectively.
+    MinMax,
+}
+
+/// Normalize the scores of a single source in place.
+///
+/// The source is expected to be sorted from the best to the worst point, which
+/// allows to infer whether larger or smaller scores are better.
+fn normalize(points: &mut [ScoredPoint], normalization: ScoreNormalization) {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return;
+    };
+
+    // Smaller-is-better scores (e.g. euclidean distance) are flipped, so that larger is better
+    let sign = if first.score < last.score { -1.0 } else { 1.0 };
+
+    let (low, high) = match normalization {
+        ScoreNormalization::Distribution => {
+            let count = points.len() as ScoreType;
+            let mean = points.iter().map(|p| sign * p.score).sum::<ScoreType>() / count;
+            let variance = points
+                .iter()
+                .map(|p| (sign * p.score - mean).powi(2))
+                .sum::<ScoreType>()
+                / count;
+            let std_dev = variance.sqrt();
+
+            (
+                mean - DISTRIBUTION_SIGMAS * std_dev,
+                mean + DISTRIBUTION_SIGMAS * std_dev,
+            )
+        }
+        ScoreNormalization::MinMax => (sign * last.score, sign * first.score),
+    };
+
+    let range = high - low;
+
+    for point in points.iter_mut() {
+        point.score = if range > 0.0 && range.is_finite() {
+            (sign * point.score - low) / range
+        } else {
+            DEGENERATE_NORMALIZED_SCORE
+        };
+    }
+}
+
+/// Fuse multiple results from different sources by normalizing and summing their scores.
+/// Each response can have a different length, and must be sorted from best to worst.
+///
+/// `weights` are applied to the normalized scores of the response with the same index.
+/// Responses without a weight get a weight of `1.0`.
+///
+/// The output is a single sorted list of ScoredPoint, larger score is better.
+/// Does not break ties.
+pub fn score_fusion(
+    responses: impl IntoIterator<Item = Vec<ScoredPoint>>,
+    normalization: ScoreNormalization,
+    weights: &[ScoreType],
+) -> Vec<ScoredPoint> {
+    // track scored points by id
+    let mut points_by_id: HashMap<ExtendedPointId, ScoredPoint> = HashMap::new();
+
+    for (idx, mut response) in responses.into_iter().enumerate() {
+        let weight = weights.get(idx).copied().unwrap_or(1.0);
+
+        normalize(&mut response, normalization);
+
+        for mut point in response {
+            let score = weight * point.score;
+            match points_by_id.entry(point.id) {
+                Entry::Occupied(mut entry) => {
+                    // accumulate score
+                    entry.get_mut().score += score;
+                }
+                Entry::Vacant(entry) => {
+                    point.score = score;
+                    // init score
+                    entry.insert(point);
+                }
+            }
+        }
+    }
+
+    let mut scores: Vec<_> = points_by_id.into_values().collect();
+    scores.sort_unstable_by(|a, b| {
+        // sort by score descending
+        OrderedFloat(b.score).cmp(&OrderedFloat(a.score))
+    });
+
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_scored_point(id: u64, score: f32) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    #[test]
+    fn test_score_fusion_empty() {
+        let scored_points = score_fusion(vec![], ScoreNormalization::Distribution, &[]);
+        assert_eq!(scored_points.len(), 0);
+    }
+
+    #[test]
+    fn test_score_fusion_one() {
+        let responses = vec![vec![make_scored_point(1, 0.9)]];
+        for normalization in [ScoreNormalization::Distribution, ScoreNormalization::MinMax] {
+            let scored_points = score_fusion(responses.clone(), normalization, &[]);
+            assert_eq!(scored_points.len(), 1);
+            assert_eq!(scored_points[0].id, 1.into());
+            assert_eq!(scored_points[0].score, DEGENERATE_NORMALIZED_SCORE);
+        }
+    }
+
+    #[test]
+    fn test_min_max_fusion() {
+        let responses = vec![
+            vec![
+                make_scored_point(1, 10.0),
+                make_scored_point(2, 5.0),
+                make_scored_point(3, 0.0),
+            ],
+            // smaller is better, like a euclidean distance
+            vec![
+                make_scored_point(3, 1.0),
+                make_scored_point(2, 2.0),
+                make_scored_point(1, 5.0),
+            ],
+        ];
+
+        let scored_points = score_fusion(responses.clone(), ScoreNormalization::MinMax, &[]);
+        assert!(scored_points.windows(2).all(|w| w[0].score >= w[1].score));
+
+        assert_eq!(scored_points.len(), 3);
+        assert_eq!(scored_points[0].id, 2.into());
+        assert_eq!(scored_points[0].score, 1.25); // 0.5 + 0.75
+        assert_eq!(scored_points[1].score, 1.0);
+        assert_eq!(scored_points[2].score, 1.0);
+
+        // Weight the first source higher
+        let scored_points = score_fusion(responses, ScoreNormalization::MinMax, &[2.0]);
+        assert_eq!(scored_points[0].id, 1.into());
+        assert_eq!(scored_points[0].score, 2.0);
+        assert_eq!(scored_points[1].id, 2.into());
+        assert_eq!(scored_points[1].score, 1.75);
+        assert_eq!(scored_points[2].id, 3.into());
+        assert_eq!(scored_points[2].score, 1.0);
+    }
+
+    #[test]
+    fn test_distribution_fusion() {
+        let responses = vec![
+            vec![
+                make_scored_point(1, 0.9),
+                make_scored_point(2, 0.6),
+                make_scored_point(3, 0.3),
+            ],
+            vec![
+                make_scored_point(3, 30.0),
+                make_scored_point(2, 20.0),
+                make_scored_point(4, 10.0),
+            ],
+        ];
+
+        let scored_points = score_fusion(responses, ScoreNormalization::Distribution, &[]);
+        assert!(scored_points.windows(2).all(|w| w[0].score >= w[1].score));
+        assert_eq!(scored_points.len(), 4);
+
+        let score_of = |id: u64| {
+            scored_points
+                .iter()
+                .find(|point| point.id == id.into())
+                .unwrap()
+                .score
+        };
+
+        // The mean of each source is mapped to 0.5
+        assert!((score_of(2) - 1.0).abs() < 1e-6);
+
+        // Both sources contribute with the same magnitude, despite different score ranges
+        assert!((score_of(3) - 1.0).abs() < 1e-6);
+        assert!((score_of(1) + score_of(4) - 1.0).abs() < 1e-6);
+        assert!(score_of(1) > score_of(4));
+    }
+}
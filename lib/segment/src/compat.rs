@@ -54,6 +54,8 @@ impl From<SegmentConfigV5> for SegmentConfig {
                         .unwrap_or_else(|| old_segment.storage_type.into()),
                     multivec_config: None,
                     datatype: None,
+                    validation: None,
+                    normalize: None,
                 };
 
                 (vector_name, new_data)
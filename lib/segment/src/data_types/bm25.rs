@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::{DimId, DimWeight};
+use validator::Validate;
+
+use crate::data_types::text_index::{TextIndexParams, TextIndexType, TokenizerType};
+use crate::index::field_index::full_text_index::tokenizers::Tokenizer;
+
+const DEFAULT_K: f32 = 1.2;
+const DEFAULT_B: f32 = 0.75;
+const DEFAULT_AVG_LEN: f32 = 256.0;
+
+/// Configuration of BM25 conversion of text documents into sparse vectors.
+///
+/// Tokens of the document are hashed into dimension ids, term frequencies are saturated
+/// and normalized by the document length. Combine with `idf` modifier to get full BM25 scoring.
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct Bm25Config {
+    /// Controls saturation of the term frequency. Default: 1.2
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0))]
+    pub k: Option<f32>,
+    /// Controls normalization by the document length, in range [0, 1]. Default: 0.75
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub b: Option<f32>,
+    /// Expected average length of documents, in tokens. Default: 256
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1.0))]
+    pub avg_len: Option<f32>,
+    /// Tokenizer used to split the text into terms. Default: word
+    #[serde(default)]
+    pub tokenizer: TokenizerType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_token_len: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_token_len: Option<usize>,
+    /// If true, lowercase all tokens. Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lowercase: Option<bool>,
+}
+
+impl Hash for Bm25Config {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.k.map(f32::to_bits).hash(state);
+        self.b.map(f32::to_bits).hash(state);
+        self.avg_len.map(f32::to_bits).hash(state);
+        self.tokenizer.hash(state);
+        self.min_token_len.hash(state);
+        self.max_token_len.hash(state);
+        self.lowercase.hash(state);
+    }
+}
+
+impl Eq for Bm25Config {}
+
+/// Converts text into sparse vectors, using the same tokenizer for documents and queries
+pub struct Bm25 {
+    k: f32,
+    b: f32,
+    avg_len: f32,
+    text_params: TextIndexParams,
+}
+
+impl Bm25 {
+    pub fn new(config: &Bm25Config) -> Self {
+        Self {
+            k: config.k.unwrap_or(DEFAULT_K),
+            b: config.b.unwrap_or(DEFAULT_B),
+            avg_len: config.avg_len.unwrap_or(DEFAULT_AVG_LEN),
+            text_params: TextIndexParams {
+                r#type: TextIndexType::Text,
+                tokenizer: config.tokenizer,
+                min_token_len: config.min_token_len,
+                max_token_len: config.max_token_len,
+                lowercase: config.lowercase,
+                on_disk: None,
+            },
+        }
+    }
+
+    /// Deterministic dimension id of a token, stable across versions and platforms
+    pub fn token_dimension(token: &str) -> DimId {
+        seahash::hash(token.as_bytes()) as DimId
+    }
+
+    /// Sparse vector of a document, weighted by saturated and length-normalized term frequencies
+    pub fn doc_embed(&self, text: &str) -> SparseVector {
+        let mut term_frequencies = BTreeMap::<DimId, u32>::new();
+        let mut doc_len = 0usize;
+        Tokenizer::tokenize_doc(text, &self.text_params, |token| {
+            *term_frequencies
+                .entry(Self::token_dimension(token))
+                .or_default() += 1;
+            doc_len += 1;
+        });
+
+        let length_norm = 1.0 - self.b + self.b * doc_len as f32 / self.avg_len;
+        let (indices, values) = term_frequencies
+            .into_iter()
+            .map(|(dim, tf)| {
+                let tf = tf as DimWeight;
+                (dim, tf * (self.k + 1.0) / (tf + self.k * length_norm))
+            })
+            .unzip();
+        SparseVector { indices, values }
+    }
+
+    /// Sparse vector of a query, where every unique term has the weight of 1
+    pub fn query_embed(&self, text: &str) -> SparseVector {
+        let mut dims = BTreeMap::<DimId, DimWeight>::new();
+        Tokenizer::tokenize_query(text, &self.text_params, |token| {
+            dims.insert(Self::token_dimension(token), 1.0);
+        });
+        let (indices, values) = dims.into_iter().unzip();
+        SparseVector { indices, values }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bm25_doc_embed() {
+        let bm25 = Bm25::new(&Bm25Config {
+            avg_len: Some(4.0),
+            ..Default::default()
+        });
+
+        let vector = bm25.doc_embed("The quick fox, the lazy dog");
+        assert!(vector.is_sorted());
+        assert_eq!(vector.indices.len(), 5);
+
+        let weight = |token: &str| {
+            let dim = Bm25::token_dimension(token);
+            let position = vector.indices.iter().position(|&d| d == dim).unwrap();
+            vector.values[position]
+        };
+        // Lowercased repeated term weighs more, but less than twice as much
+        assert!(weight("the") > weight("fox"));
+        assert!(weight("the") < 2.0 * weight("fox"));
+        // 6 tokens in a document of expected length 4
+        let length_norm = 1.0 - DEFAULT_B + DEFAULT_B * 6.0 / 4.0;
+        let expected = (DEFAULT_K + 1.0) / (1.0 + DEFAULT_K * length_norm);
+        assert!((weight("fox") - expected).abs() < 1e-6);
+
+        // Same text is always converted into the same vector
+        assert_eq!(vector, bm25.doc_embed("The quick fox, the lazy dog"));
+    }
+
+    #[test]
+    fn test_bm25_query_embed() {
+        let bm25 = Bm25::new(&Bm25Config::default());
+
+        let query = bm25.query_embed("Lazy dog and the LAZY fox");
+        assert_eq!(query.indices.len(), 5);
+        assert!(query.values.iter().all(|&value| value == 1.0));
+
+        let doc = bm25.doc_embed("the lazy dog");
+        assert!(doc
+            .indices
+            .iter()
+            .all(|index| query.indices.contains(index)));
+    }
+}
@@ -0,0 +1,140 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::json_path::JsonPath;
+use crate::spaces::tools::peek_top_largest_iterable;
+use crate::types::{Filter, IntPayloadType};
+
+/// Internal representation of a facet counting request
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetParams {
+    pub key: JsonPath,
+    pub limit: usize,
+    pub filter: Option<Filter>,
+    pub exact: bool,
+}
+
+impl FacetParams {
+    pub const DEFAULT_LIMIT: usize = 10;
+}
+
+/// A value of a payload field which can be used for faceting
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(untagged)]
+pub enum FacetValue {
+    Keyword(String),
+    Int(IntPayloadType),
+    Bool(bool),
+}
+
+/// Borrowed version of [`FacetValue`], as it is stored in the field indexes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FacetValueRef<'a> {
+    Keyword(&'a str),
+    Int(&'a IntPayloadType),
+    Bool(bool),
+}
+
+impl<'a> FacetValueRef<'a> {
+    pub fn to_owned(&self) -> FacetValue {
+        match self {
+            FacetValueRef::Keyword(value) => FacetValue::Keyword((*value).to_string()),
+            FacetValueRef::Int(value) => FacetValue::Int(**value),
+            FacetValueRef::Bool(value) => FacetValue::Bool(*value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FacetValueHit {
+    /// Value from the facet
+    pub value: FacetValue,
+    /// Number of points with this value
+    pub count: usize,
+}
+
+impl Ord for FacetValueHit {
+    /// Larger count is greater, ties are broken by the smaller value being greater
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.count
+            .cmp(&other.count)
+            .then_with(|| other.value.cmp(&self.value))
+    }
+}
+
+impl PartialOrd for FacetValueHit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FacetResponse {
+    pub hits: Vec<FacetValueHit>,
+}
+
+impl FacetResponse {
+    /// Select the `limit` values with the largest counts, most frequent first
+    pub fn top_hits(counts: HashMap<FacetValue, usize>, limit: usize) -> Self {
+        let hits = counts
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(value, count)| FacetValueHit { value, count });
+
+        let mut hits = peek_top_largest_iterable(hits, limit);
+        hits.sort_unstable_by(|a, b| b.cmp(a));
+
+        Self { hits }
+    }
+
+    /// Sum up the counts of multiple responses, and select the `limit` most frequent values
+    pub fn merge(responses: impl IntoIterator<Item = FacetResponse>, limit: usize) -> Self {
+        let mut counts = HashMap::new();
+        for hit in responses.into_iter().flat_map(|response| response.hits) {
+            *counts.entry(hit.value).or_insert(0) += hit.count;
+        }
+
+        Self::top_hits(counts, limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_facet_responses() {
+        let hit = |value: &str, count| FacetValueHit {
+            value: FacetValue::Keyword(value.to_string()),
+            count,
+        };
+
+        let responses = vec![
+            FacetResponse {
+                hits: vec![hit("a", 5), hit("b", 3), hit("c", 1)],
+            },
+            FacetResponse {
+                hits: vec![hit("c", 6), hit("b", 2)],
+            },
+        ];
+
+        let merged = FacetResponse::merge(responses, 2);
+
+        assert_eq!(merged.hits, vec![hit("c", 7), hit("a", 5)]);
+
+        // Ties are broken by value
+        let merged = FacetResponse::merge(
+            vec![FacetResponse {
+                hits: vec![hit("z", 1), hit("x", 1), hit("y", 1)],
+            }],
+            10,
+        );
+
+        assert_eq!(merged.hits, vec![hit("x", 1), hit("y", 1), hit("z", 1)]);
+    }
+}
@@ -17,4 +17,10 @@ pub struct IntegerIndexParams {
     pub lookup: bool,
     /// If true - support ranges filters.
     pub range: bool,
+    /// If true - used for tenant optimization. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_tenant: Option<bool>,
+    /// If true, store the index on disk. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
 }
@@ -1,9 +1,18 @@
+pub mod bm25;
+pub mod bool_index;
+pub mod datetime_index;
+pub mod facets;
+pub mod float_index;
+pub mod geo_index;
 pub mod groups;
 pub mod integer_index;
+pub mod keyword_index;
 pub mod named_vectors;
 pub mod order_by;
 pub mod primitive;
 pub mod query_context;
 pub mod text_index;
 pub mod tiny_map;
+pub mod token_pooling;
+pub mod uuid_index;
 pub mod vectors;
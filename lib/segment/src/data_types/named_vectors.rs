@@ -5,6 +5,7 @@ use sparse::common::sparse_vector::SparseVector;
 
 use super::primitive::PrimitiveVectorElement;
 use super::tiny_map;
+use super::token_pooling::pool_tokens;
 use super::vectors::{
     DenseVector, MultiDenseVector, TypedMultiDenseVector, TypedMultiDenseVectorRef, Vector,
     VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
@@ -294,6 +295,16 @@ impl<'a> NamedVectors<'a> {
                     std::mem::swap(&mut tmp_multi_vector, multi_vector);
                     let mut owned_multi_vector = tmp_multi_vector.to_owned();
                     let config = get_vector_data(name.as_ref());
+                    // pool raw token vectors, before they are preprocessed
+                    if let Some(multivec_config) = &config.multivec_config {
+                        if let Some(max_tokens) = multivec_config.max_tokens {
+                            owned_multi_vector = pool_tokens(
+                                owned_multi_vector,
+                                max_tokens,
+                                multivec_config.pooling.unwrap_or_default(),
+                            );
+                        }
+                    }
                     for dense_vector in owned_multi_vector.multi_vectors_mut() {
                         let preprocessed_vector =
                             Self::preprocess_dense_vector(dense_vector.to_vec(), config);
@@ -310,8 +321,15 @@ impl<'a> NamedVectors<'a> {
         dense_vector: DenseVector,
         config: &VectorDataConfig,
     ) -> DenseVector {
+        // Vectors to normalize are preprocessed the same way as for cosine distance,
+        // preprocessing of the other distances doesn't change vectors
+        let distance = if config.normalize.unwrap_or(false) {
+            Distance::Cosine
+        } else {
+            config.distance
+        };
         match config.datatype {
-            Some(VectorStorageDatatype::Float32) | None => match config.distance {
+            Some(VectorStorageDatatype::Float32) | None => match distance {
                 Distance::Cosine => {
                     <CosineMetric as Metric<VectorElementType>>::preprocess(dense_vector)
                 }
@@ -325,7 +343,7 @@ impl<'a> NamedVectors<'a> {
                     <ManhattanMetric as Metric<VectorElementType>>::preprocess(dense_vector)
                 }
             },
-            Some(VectorStorageDatatype::Uint8) => match config.distance {
+            Some(VectorStorageDatatype::Uint8) => match distance {
                 Distance::Cosine => {
                     <CosineMetric as Metric<VectorElementTypeByte>>::preprocess(dense_vector)
                 }
@@ -339,7 +357,7 @@ impl<'a> NamedVectors<'a> {
                     <ManhattanMetric as Metric<VectorElementTypeByte>>::preprocess(dense_vector)
                 }
             },
-            Some(VectorStorageDatatype::Float16) => match config.distance {
+            Some(VectorStorageDatatype::Float16) => match distance {
                 Distance::Cosine => {
                     <CosineMetric as Metric<VectorElementTypeHalf>>::preprocess(dense_vector)
                 }
@@ -0,0 +1,209 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{
+    DenseVector, MultiDenseVector, TypedMultiDenseVectorRef, VectorElementType,
+};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::CosineMetric;
+use crate::types::{Distance, MultiVectorRepresentative, TokenPooling};
+
+/// Reduce the number of token vectors of a multivector down to `max_tokens`
+pub fn pool_tokens(
+    multi_vector: MultiDenseVector,
+    max_tokens: usize,
+    pooling: TokenPooling,
+) -> MultiDenseVector {
+    if multi_vector.vectors_count() <= max_tokens {
+        return multi_vector;
+    }
+    match pooling {
+        TokenPooling::KeepFirst => {
+            let MultiDenseVector {
+                mut flattened_vectors,
+                dim,
+            } = multi_vector;
+            flattened_vectors.truncate(max_tokens * dim);
+            MultiDenseVector::new(flattened_vectors, dim)
+        }
+        TokenPooling::HierarchicalClustering => hierarchical_pooling(&multi_vector, max_tokens),
+    }
+}
+
+/// Single vector which represents a multivector, preprocessed for the given distance
+pub fn representative_vector(
+    multi_vector: TypedMultiDenseVectorRef<VectorElementType>,
+    representative: MultiVectorRepresentative,
+    distance: Distance,
+) -> DenseVector {
+    let dim = multi_vector.dim;
+    let vector = match representative {
+        MultiVectorRepresentative::FirstToken => multi_vector
+            .multi_vectors()
+            .next()
+            .map(|token| token.to_vec())
+            .unwrap_or_else(|| vec![0.0; dim]),
+        MultiVectorRepresentative::Mean => {
+            let count = multi_vector.vectors_count().max(1);
+            let mut mean = vec![0.0; dim];
+            for token in multi_vector.multi_vectors() {
+                // Tokens of queries are not preprocessed yet, stored ones are
+                let token = preprocess(distance, token.to_vec());
+                for (value, token_value) in mean.iter_mut().zip(token) {
+                    *value += token_value / count as f32;
+                }
+            }
+            mean
+        }
+    };
+    preprocess(distance, vector)
+}
+
+fn preprocess(distance: Distance, vector: DenseVector) -> DenseVector {
+    match distance {
+        Distance::Cosine => <CosineMetric as Metric<VectorElementType>>::preprocess(vector),
+        Distance::Euclid | Distance::Dot | Distance::Manhattan => vector,
+    }
+}
+
+/// Agglomerative clustering of token vectors by the cosine similarity of cluster centroids.
+///
+/// The two most similar clusters are merged until `max_tokens` clusters are left,
+/// each cluster is then represented by the mean of its token vectors.
+fn hierarchical_pooling(multi_vector: &MultiDenseVector, max_tokens: usize) -> MultiDenseVector {
+    let dim = multi_vector.dim;
+    // Sum of the token vectors and number of tokens of each cluster.
+    // Cosine similarity of sums is the same as of the means.
+    let mut clusters: Vec<(DenseVector, usize)> = multi_vector
+        .multi_vectors()
+        .map(|token| (token.to_vec(), 1))
+        .collect();
+
+    let mut similarities: Vec<Vec<ScoreType>> = clusters
+        .iter()
+        .map(|(a, _)| clusters.iter().map(|(b, _)| cosine(a, b)).collect())
+        .collect();
+
+    while clusters.len() > max_tokens.max(1) {
+        let mut best = (0, 1, ScoreType::NEG_INFINITY);
+        for (i, row) in similarities.iter().enumerate() {
+            for (j, &similarity) in row.iter().enumerate().skip(i + 1) {
+                if similarity > best.2 {
+                    best = (i, j, similarity);
+                }
+            }
+        }
+        let (keep, merged, _) = best;
+
+        // `keep < merged`, so removing `merged` doesn't move `keep`
+        let (merged_sum, merged_count) = clusters.swap_remove(merged);
+        similarities.swap_remove(merged);
+        for row in similarities.iter_mut() {
+            row.swap_remove(merged);
+        }
+
+        let (sum, count) = &mut clusters[keep];
+        for (value, merged_value) in sum.iter_mut().zip(merged_sum) {
+            *value += merged_value;
+        }
+        *count += merged_count;
+
+        for other in 0..clusters.len() {
+            let similarity = cosine(&clusters[keep].0, &clusters[other].0);
+            similarities[keep][other] = similarity;
+            similarities[other][keep] = similarity;
+        }
+    }
+
+    let flattened_vectors = clusters
+        .into_iter()
+        .flat_map(|(sum, count)| sum.into_iter().map(move |value| value / count as f32))
+        .collect();
+    MultiDenseVector::new(flattened_vectors, dim)
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> ScoreType {
+    let mut dot = 0.0;
+    let mut norm_a = 0.0;
+    let mut norm_b = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keep_first_pooling() {
+        let multi_vector =
+            MultiDenseVector::new_unchecked(vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]]);
+
+        let pooled = pool_tokens(multi_vector.clone(), 2, TokenPooling::KeepFirst);
+        assert_eq!(
+            pooled,
+            MultiDenseVector::new_unchecked(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+        );
+
+        let pooled = pool_tokens(multi_vector.clone(), 3, TokenPooling::KeepFirst);
+        assert_eq!(pooled, multi_vector);
+    }
+
+    #[test]
+    fn test_hierarchical_pooling() {
+        let multi_vector = MultiDenseVector::new_unchecked(vec![
+            vec![1.0, 0.0],
+            vec![0.0, 1.0],
+            vec![0.9, 0.1],
+            vec![0.1, 0.9],
+            vec![1.0, 0.2],
+        ]);
+
+        let pooled = pool_tokens(multi_vector, 2, TokenPooling::HierarchicalClustering);
+        assert_eq!(pooled.vectors_count(), 2);
+
+        let mut tokens = pooled.into_multi_vectors();
+        tokens.sort_by(|a, b| b[0].total_cmp(&a[0]));
+        // Mean of the tokens close to the x axis, then of the tokens close to the y axis
+        let expected = [[(1.0 + 0.9 + 1.0) / 3.0, 0.1], [0.05, 0.95]];
+        for (token, expected) in tokens.iter().zip(expected) {
+            for (value, expected) in token.iter().zip(expected) {
+                assert!((value - expected).abs() < 1e-6, "{tokens:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_representative_vector() {
+        let multi_vector = MultiDenseVector::new_unchecked(vec![vec![2.0, 0.0], vec![0.0, 4.0]]);
+
+        let first = representative_vector(
+            (&multi_vector).into(),
+            MultiVectorRepresentative::FirstToken,
+            Distance::Dot,
+        );
+        assert_eq!(first, vec![2.0, 0.0]);
+
+        let mean = representative_vector(
+            (&multi_vector).into(),
+            MultiVectorRepresentative::Mean,
+            Distance::Dot,
+        );
+        assert_eq!(mean, vec![1.0, 2.0]);
+
+        // Tokens are normalized before averaging, the mean is normalized again
+        let mean = representative_vector(
+            (&multi_vector).into(),
+            MultiVectorRepresentative::Mean,
+            Distance::Cosine,
+        );
+        let expected = std::f32::consts::FRAC_1_SQRT_2;
+        assert!((mean[0] - expected).abs() < 1e-6 && (mean[1] - expected).abs() < 1e-6);
+    }
+}
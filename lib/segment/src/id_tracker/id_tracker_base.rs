@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use bitvec::prelude::BitSlice;
 use common::types::PointOffsetType;
 use rand::rngs::StdRng;
@@ -5,6 +7,7 @@ use rand::{Rng, SeedableRng};
 
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::id_tracker::immutable_id_tracker::ImmutableIdTracker;
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
 use crate::types::{PointIdType, SeqNumberType};
 
@@ -142,13 +145,18 @@ pub trait IdTracker {
     /// It might happen that point doesn't have version due to un-flushed WAL.
     /// This method makes those points usable again.
     fn cleanup_versions(&mut self) -> OperationResult<()>;
+
+    /// Files of the tracker, which are stored outside of the segment database
+    fn files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
 }
 
 pub type IdTrackerSS = dyn IdTracker + Sync + Send;
 
 pub enum IdTrackerEnum {
     MutableIdTracker(SimpleIdTracker),
-    ImmutableIdTracker(SimpleIdTracker),
+    ImmutableIdTracker(ImmutableIdTracker),
 }
 
 impl IdTracker for IdTrackerEnum {
@@ -293,4 +301,11 @@ impl IdTracker for IdTrackerEnum {
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.cleanup_versions(),
         }
     }
+
+    fn files(&self) -> Vec<PathBuf> {
+        match self {
+            IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.files(),
+            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.files(),
+        }
+    }
 }
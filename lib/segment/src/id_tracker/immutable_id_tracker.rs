@@ -0,0 +1,491 @@
+use std::fs::{create_dir_all, remove_dir_all, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use bitvec::prelude::BitSlice;
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
+use memmap2::Mmap;
+use memory::mmap_ops::{
+    open_read_mmap, open_write_mmap, transmute_from_u8_to_slice, transmute_to_u8_slice,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::common::error_logging::LogError;
+use crate::common::mmap_type::MmapSlice;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::id_tracker::IdTracker;
+use crate::index::field_index::mmap_flags::MmapFlags;
+use crate::types::{PointIdType, SeqNumberType};
+
+pub const ID_TRACKER_PATH: &str = "id_tracker";
+
+const STATS_FILE: &str = "stats.json";
+const NUM_IDS_FILE: &str = "num_ids.bin";
+const NUM_OFFSETS_FILE: &str = "num_offsets.bin";
+const UUID_IDS_FILE: &str = "uuid_ids.bin";
+const UUID_OFFSETS_FILE: &str = "uuid_offsets.bin";
+const INTERNAL_TO_EXTERNAL_FILE: &str = "internal_to_external.bin";
+const VERSIONS_FILE: &str = "versions.bin";
+const DELETED_FILE: &str = "deleted.bin";
+
+/// Position of the external id for internal ids, which are not linked to any point
+const NO_EXTERNAL_ID: u32 = u32::MAX;
+
+/// Statistics of the tracker at the moment of building
+#[derive(Serialize, Deserialize)]
+struct Stats {
+    total_point_count: usize,
+}
+
+/// Id tracker of non-appendable segments, which keeps all mappings in memory mapped files.
+///
+/// Mappings are written once from another tracker and are read-only afterwards.
+/// External ids are stored in sorted arrays, numeric ids first and uuids after them,
+/// so the lookup is a binary search and the position in the arrays defines the iteration order.
+/// Points can only be removed, which is tracked by a bitset of deleted internal ids.
+pub struct ImmutableIdTracker {
+    path: PathBuf,
+    total_point_count: usize,
+    deleted_count: usize,
+    /// Sorted numeric external ids
+    num_ids: Mmap,
+    /// Internal ids of `num_ids`
+    num_offsets: Mmap,
+    /// Sorted uuid external ids, stored as bytes to keep the order of `Uuid`
+    uuid_ids: Mmap,
+    /// Internal ids of `uuid_ids`
+    uuid_offsets: Mmap,
+    /// Position of the external id of each internal id, in `num_ids` followed by `uuid_ids`
+    internal_to_external: Mmap,
+    versions: MmapSlice<SeqNumberType>,
+    deleted: MmapFlags,
+}
+
+fn write_slice<T>(path: &Path, data: &[T]) -> OperationResult<()> {
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(&file);
+    writer.write_all(transmute_to_u8_slice(data))?;
+    writer.flush()?;
+    drop(writer);
+    file.sync_all()?;
+    Ok(())
+}
+
+fn as_slice<T>(mmap: &Mmap) -> &[T] {
+    if mmap.is_empty() {
+        // Empty mmap might be not aligned
+        return &[];
+    }
+    transmute_from_u8_to_slice(mmap)
+}
+
+impl ImmutableIdTracker {
+    /// Stats are written last, so incomplete files are never considered as built
+    pub fn files_exist(path: &Path) -> bool {
+        path.join(STATS_FILE).exists()
+    }
+
+    /// Write files of the tracker from mappings of another tracker and open them
+    ///
+    /// Points without a version are not written, same as they would be dropped by
+    /// [`IdTracker::cleanup_versions`].
+    pub fn build(path: &Path, id_tracker: &impl IdTracker) -> OperationResult<Self> {
+        // Leftovers of an interrupted build should not be reused
+        if path.exists() {
+            remove_dir_all(path)?;
+        }
+        create_dir_all(path)?;
+
+        let total_point_count = id_tracker.total_point_count();
+
+        let mut num_ids: Vec<u64> = Vec::new();
+        let mut num_offsets: Vec<PointOffsetType> = Vec::new();
+        let mut uuid_ids: Vec<[u8; 16]> = Vec::new();
+        let mut uuid_offsets: Vec<PointOffsetType> = Vec::new();
+
+        // Ids are iterated in sorted order
+        for (external_id, internal_id) in id_tracker.iter_from(None) {
+            if id_tracker.internal_version(internal_id).is_none() {
+                continue;
+            }
+            match external_id {
+                PointIdType::NumId(num) => {
+                    num_ids.push(num);
+                    num_offsets.push(internal_id);
+                }
+                PointIdType::Uuid(uuid) => {
+                    uuid_ids.push(*uuid.as_bytes());
+                    uuid_offsets.push(internal_id);
+                }
+            }
+        }
+
+        let mut internal_to_external = vec![NO_EXTERNAL_ID; total_point_count];
+        for (position, internal_id) in num_offsets.iter().chain(&uuid_offsets).enumerate() {
+            if let Some(slot) = internal_to_external.get_mut(*internal_id as usize) {
+                *slot = position as u32;
+            }
+        }
+
+        // File must not be empty to be memory mapped for writing
+        let mut versions: Vec<SeqNumberType> = (0..total_point_count as PointOffsetType)
+            .map(|internal_id| id_tracker.internal_version(internal_id).unwrap_or(0))
+            .collect();
+        if versions.is_empty() {
+            versions.push(0);
+        }
+
+        write_slice(&path.join(NUM_IDS_FILE), &num_ids)?;
+        write_slice(&path.join(NUM_OFFSETS_FILE), &num_offsets)?;
+        write_slice(&path.join(UUID_IDS_FILE), &uuid_ids)?;
+        write_slice(&path.join(UUID_OFFSETS_FILE), &uuid_offsets)?;
+        write_slice(&path.join(INTERNAL_TO_EXTERNAL_FILE), &internal_to_external)?;
+        write_slice(&path.join(VERSIONS_FILE), &versions)?;
+        MmapFlags::create(
+            &path.join(DELETED_FILE),
+            total_point_count,
+            internal_to_external
+                .iter()
+                .enumerate()
+                .filter(|(_, position)| **position == NO_EXTERNAL_ID)
+                .map(|(internal_id, _)| internal_id as PointOffsetType),
+        )?;
+
+        atomic_save_json(&path.join(STATS_FILE), &Stats { total_point_count })?;
+
+        Self::open(path)
+    }
+
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        let stats: Stats = read_json(&path.join(STATS_FILE))?;
+
+        let open = |name: &str| {
+            open_read_mmap(&path.join(name)).describe("Open mmap id tracker for reading")
+        };
+        let versions_mmap = open_write_mmap(&path.join(VERSIONS_FILE))
+            .describe("Open mmap id tracker versions for writing")?;
+        let versions = unsafe { MmapSlice::try_from(versions_mmap)? };
+        let deleted = MmapFlags::open(&path.join(DELETED_FILE))?;
+
+        let total_point_count = stats.total_point_count;
+        let deleted_count = deleted
+            .iter_ones()
+            .filter(|internal_id| (*internal_id as usize) < total_point_count)
+            .count();
+
+        Ok(Self {
+            path: path.to_owned(),
+            total_point_count,
+            deleted_count,
+            num_ids: open(NUM_IDS_FILE)?,
+            num_offsets: open(NUM_OFFSETS_FILE)?,
+            uuid_ids: open(UUID_IDS_FILE)?,
+            uuid_offsets: open(UUID_OFFSETS_FILE)?,
+            internal_to_external: open(INTERNAL_TO_EXTERNAL_FILE)?,
+            versions,
+            deleted,
+        })
+    }
+
+    fn num_ids(&self) -> &[u64] {
+        as_slice(&self.num_ids)
+    }
+
+    fn uuid_ids(&self) -> &[[u8; 16]] {
+        as_slice(&self.uuid_ids)
+    }
+
+    fn mappings_count(&self) -> usize {
+        self.num_ids().len() + self.uuid_ids().len()
+    }
+
+    fn external_id_at(&self, position: usize) -> Option<PointIdType> {
+        let num_ids = self.num_ids();
+        match num_ids.get(position) {
+            Some(num) => Some(PointIdType::NumId(*num)),
+            None => self
+                .uuid_ids()
+                .get(position - num_ids.len())
+                .map(|bytes| PointIdType::Uuid(Uuid::from_bytes(*bytes))),
+        }
+    }
+
+    fn internal_id_at(&self, position: usize) -> Option<PointOffsetType> {
+        let num_offsets: &[PointOffsetType] = as_slice(&self.num_offsets);
+        match num_offsets.get(position) {
+            Some(internal_id) => Some(*internal_id),
+            None => as_slice::<PointOffsetType>(&self.uuid_offsets)
+                .get(position - num_offsets.len())
+                .copied(),
+        }
+    }
+
+    fn position(&self, external_id: PointIdType) -> Option<usize> {
+        match external_id {
+            PointIdType::NumId(num) => self.num_ids().binary_search(&num).ok(),
+            PointIdType::Uuid(uuid) => self
+                .uuid_ids()
+                .binary_search(uuid.as_bytes())
+                .ok()
+                .map(|position| self.num_ids().len() + position),
+        }
+    }
+}
+
+impl IdTracker for ImmutableIdTracker {
+    fn internal_version(&self, internal_id: PointOffsetType) -> Option<SeqNumberType> {
+        if internal_id as usize >= self.total_point_count {
+            return None;
+        }
+        self.versions.get(internal_id as usize).copied()
+    }
+
+    fn set_internal_version(
+        &mut self,
+        internal_id: PointOffsetType,
+        version: SeqNumberType,
+    ) -> OperationResult<()> {
+        if self.external_id(internal_id).is_some() {
+            self.versions[internal_id as usize] = version;
+        }
+        Ok(())
+    }
+
+    fn internal_id(&self, external_id: PointIdType) -> Option<PointOffsetType> {
+        let internal_id = self.internal_id_at(self.position(external_id)?)?;
+        (!self.deleted.get(internal_id)).then_some(internal_id)
+    }
+
+    fn external_id(&self, internal_id: PointOffsetType) -> Option<PointIdType> {
+        if self.is_deleted_point(internal_id) {
+            return None;
+        }
+        let internal_to_external: &[u32] = as_slice(&self.internal_to_external);
+        let position = *internal_to_external.get(internal_id as usize)?;
+        if position == NO_EXTERNAL_ID {
+            return None;
+        }
+        self.external_id_at(position as usize)
+    }
+
+    fn set_link(
+        &mut self,
+        external_id: PointIdType,
+        internal_id: PointOffsetType,
+    ) -> OperationResult<()> {
+        Err(OperationError::service_error(format!(
+            "Can't link point {external_id} to {internal_id}, id tracker is immutable"
+        )))
+    }
+
+    fn drop(&mut self, external_id: PointIdType) -> OperationResult<()> {
+        if let Some(internal_id) = self.internal_id(external_id) {
+            if !self.deleted.set(internal_id, true) {
+                self.deleted_count += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn iter_external(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
+        Box::new(self.iter_from(None).map(|(external_id, _)| external_id))
+    }
+
+    fn iter_internal(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        Box::new(
+            (0..self.total_point_count as PointOffsetType)
+                .filter(move |internal_id| !self.deleted.get(*internal_id)),
+        )
+    }
+
+    fn iter_from(
+        &self,
+        external_id: Option<PointIdType>,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+        let start = match external_id {
+            None => 0,
+            Some(PointIdType::NumId(num)) => self.num_ids().partition_point(|id| *id < num),
+            Some(PointIdType::Uuid(uuid)) => {
+                let uuid = uuid.as_bytes();
+                self.num_ids().len() + self.uuid_ids().partition_point(|id| id < uuid)
+            }
+        };
+        Box::new((start..self.mappings_count()).filter_map(move |position| {
+            let internal_id = self.internal_id_at(position)?;
+            if self.deleted.get(internal_id) {
+                return None;
+            }
+            Some((self.external_id_at(position)?, internal_id))
+        }))
+    }
+
+    fn iter_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        self.iter_internal()
+    }
+
+    /// Mappings are not changed after building, only removed points are flushed
+    fn mapping_flusher(&self) -> Flusher {
+        self.deleted.flusher()
+    }
+
+    fn versions_flusher(&self) -> Flusher {
+        self.versions.flusher()
+    }
+
+    fn total_point_count(&self) -> usize {
+        self.total_point_count
+    }
+
+    fn deleted_point_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_point_bitslice(&self) -> &BitSlice {
+        self.deleted.as_bitslice()
+    }
+
+    fn is_deleted_point(&self, internal_id: PointOffsetType) -> bool {
+        internal_id as usize >= self.total_point_count || self.deleted.get(internal_id)
+    }
+
+    /// All points are written with a version, points without it are skipped on build
+    fn cleanup_versions(&mut self) -> OperationResult<()> {
+        Ok(())
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = [
+            STATS_FILE,
+            NUM_IDS_FILE,
+            NUM_OFFSETS_FILE,
+            UUID_IDS_FILE,
+            UUID_OFFSETS_FILE,
+            INTERNAL_TO_EXTERNAL_FILE,
+            VERSIONS_FILE,
+        ]
+        .into_iter()
+        .map(|name| self.path.join(name))
+        .collect();
+        files.extend(self.deleted.files());
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+    use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
+
+    fn make_simple_id_tracker(dir: &Path, values: &[PointIdType]) -> SimpleIdTracker {
+        let db = open_db(dir, &[DB_VECTOR_CF]).unwrap();
+        let mut id_tracker = SimpleIdTracker::open(db).unwrap();
+        for (internal_id, value) in values.iter().enumerate() {
+            let internal_id = internal_id as PointOffsetType;
+            id_tracker.set_link(*value, internal_id).unwrap();
+            id_tracker
+                .set_internal_version(internal_id, internal_id as SeqNumberType + 10)
+                .unwrap();
+        }
+        id_tracker
+    }
+
+    #[test]
+    fn test_build_from_simple_id_tracker() {
+        let db_dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let dir = Builder::new().prefix("id_tracker_dir").tempdir().unwrap();
+
+        let values: Vec<PointIdType> = vec![
+            100.into(),
+            PointIdType::Uuid(Uuid::from_u128(123_u128)),
+            150.into(),
+            PointIdType::Uuid(Uuid::from_u128(12_u128)),
+            120.into(),
+            PointIdType::Uuid(Uuid::from_u128(673_u128)),
+            110.into(),
+        ];
+        let mut simple_id_tracker = make_simple_id_tracker(db_dir.path(), &values);
+        simple_id_tracker.drop(150.into()).unwrap();
+
+        let id_tracker = ImmutableIdTracker::build(dir.path(), &simple_id_tracker).unwrap();
+
+        assert_eq!(id_tracker.total_point_count(), values.len());
+        assert_eq!(id_tracker.deleted_point_count(), 1);
+        assert_eq!(
+            id_tracker.iter_from(None).collect_vec(),
+            simple_id_tracker.iter_from(None).collect_vec(),
+        );
+        assert_eq!(
+            id_tracker
+                .iter_from(Some(PointIdType::Uuid(Uuid::from_u128(100_u128))))
+                .collect_vec(),
+            simple_id_tracker
+                .iter_from(Some(PointIdType::Uuid(Uuid::from_u128(100_u128))))
+                .collect_vec(),
+        );
+        assert_eq!(
+            id_tracker.iter_internal().collect_vec(),
+            simple_id_tracker.iter_internal().collect_vec(),
+        );
+
+        for (internal_id, value) in values.iter().enumerate() {
+            let internal_id = internal_id as PointOffsetType;
+            assert_eq!(
+                id_tracker.internal_id(*value),
+                simple_id_tracker.internal_id(*value),
+            );
+            assert_eq!(
+                id_tracker.external_id(internal_id),
+                simple_id_tracker.external_id(internal_id),
+            );
+            assert_eq!(
+                id_tracker.internal_version(internal_id),
+                Some(internal_id as SeqNumberType + 10),
+            );
+        }
+        assert!(id_tracker.is_deleted_point(2));
+        assert!(id_tracker.is_deleted_point(values.len() as PointOffsetType));
+    }
+
+    #[test]
+    fn test_drop_and_reopen() {
+        let db_dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let dir = Builder::new().prefix("id_tracker_dir").tempdir().unwrap();
+
+        let values: Vec<PointIdType> = (0..100).map(|i| PointIdType::NumId(i * 2)).collect();
+        let simple_id_tracker = make_simple_id_tracker(db_dir.path(), &values);
+
+        let mut id_tracker = ImmutableIdTracker::build(dir.path(), &simple_id_tracker).unwrap();
+        assert!(id_tracker.set_link(1.into(), 100).is_err());
+
+        id_tracker.drop(10.into()).unwrap();
+        id_tracker.drop(10.into()).unwrap();
+        id_tracker.drop(11.into()).unwrap();
+        id_tracker.set_internal_version(7, 1000).unwrap();
+        assert_eq!(id_tracker.deleted_point_count(), 1);
+
+        id_tracker.mapping_flusher()().unwrap();
+        id_tracker.versions_flusher()().unwrap();
+        drop(id_tracker);
+
+        assert!(ImmutableIdTracker::files_exist(dir.path()));
+        let id_tracker = ImmutableIdTracker::open(dir.path()).unwrap();
+
+        assert_eq!(id_tracker.available_point_count(), 99);
+        assert_eq!(id_tracker.internal_id(10.into()), None);
+        assert_eq!(id_tracker.external_id(5), None);
+        assert_eq!(id_tracker.internal_id(12.into()), Some(6));
+        assert_eq!(id_tracker.internal_version(7), Some(1000));
+        assert_eq!(id_tracker.iter_external().count(), 99);
+        assert_eq!(
+            id_tracker.iter_from(Some(11.into())).next(),
+            Some((12.into(), 6))
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
@@ -5,9 +6,11 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 
 use self::memory::{BinaryItem, BinaryMemory};
+use self::mmap::BinaryMmap;
 use super::{CardinalityEstimation, PayloadFieldIndex, PrimaryCondition, ValueIndexer};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{FieldCondition, Match, MatchValue, PayloadKeyType, ValueVariants};
 
@@ -149,6 +152,11 @@ mod memory {
             self.indexed_count
         }
 
+        /// Number of point slots, including ones without values
+        pub fn len(&self) -> usize {
+            self.trues.len()
+        }
+
         pub fn iter_has_true(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
             self.trues.iter_ones().map(|v| v as PointOffsetType)
         }
@@ -159,8 +167,265 @@ mod memory {
     }
 }
 
+mod mmap {
+    use std::fs::{create_dir_all, remove_dir_all};
+    use std::path::{Path, PathBuf};
+
+    use common::types::PointOffsetType;
+    use io::file_operations::{atomic_save_json, read_json};
+    use serde::{Deserialize, Serialize};
+
+    use super::memory::{BinaryItem, BinaryMemory};
+    use crate::common::operation_error::OperationResult;
+    use crate::common::Flusher;
+    use crate::index::field_index::mmap_flags::MmapFlags;
+
+    const STATS_FILE: &str = "stats.json";
+    const TRUES_FILE: &str = "trues.bin";
+    const FALSES_FILE: &str = "falses.bin";
+    const DELETED_FILE: &str = "deleted.bin";
+
+    /// Statistics of the index at the moment of building, removed points are not taken into account
+    #[derive(Serialize, Deserialize)]
+    struct Stats {
+        trues_count: usize,
+        falses_count: usize,
+        indexed_count: usize,
+    }
+
+    struct Storage {
+        trues: MmapFlags,
+        falses: MmapFlags,
+        deleted: MmapFlags,
+    }
+
+    /// Same flags as in `BinaryMemory`, but kept in memory mapped files.
+    /// Flags are read-only once built, removed points are marked in a separate file.
+    pub struct BinaryMmap {
+        path: PathBuf,
+        storage: Option<Storage>,
+        trues_count: usize,
+        falses_count: usize,
+        indexed_count: usize,
+    }
+
+    impl BinaryMmap {
+        pub fn new(path: &Path) -> Self {
+            Self {
+                path: path.to_owned(),
+                storage: None,
+                trues_count: 0,
+                falses_count: 0,
+                indexed_count: 0,
+            }
+        }
+
+        pub fn files_exist(&self) -> bool {
+            self.path.join(STATS_FILE).exists()
+                && self.path.join(TRUES_FILE).exists()
+                && self.path.join(FALSES_FILE).exists()
+                && self.path.join(DELETED_FILE).exists()
+        }
+
+        /// Write flags of the in-memory representation into files and open them
+        pub fn build(&mut self, memory: &BinaryMemory) -> OperationResult<()> {
+            create_dir_all(&self.path)?;
+
+            let len = memory.len();
+            MmapFlags::create(&self.path.join(TRUES_FILE), len, memory.iter_has_true())?;
+            MmapFlags::create(&self.path.join(FALSES_FILE), len, memory.iter_has_false())?;
+            MmapFlags::create(&self.path.join(DELETED_FILE), len, [])?;
+
+            let indexed_count = (0..len as PointOffsetType)
+                .filter(|&id| {
+                    let item = memory.get(id);
+                    item.has_true() || item.has_false()
+                })
+                .count();
+
+            // Stats are written last, so incomplete files are never considered as built
+            atomic_save_json(
+                &self.path.join(STATS_FILE),
+                &Stats {
+                    trues_count: memory.trues_count(),
+                    falses_count: memory.falses_count(),
+                    indexed_count,
+                },
+            )?;
+
+            self.open()
+        }
+
+        pub fn open(&mut self) -> OperationResult<()> {
+            let stats: Stats = read_json(&self.path.join(STATS_FILE))?;
+            let storage = Storage {
+                trues: MmapFlags::open(&self.path.join(TRUES_FILE))?,
+                falses: MmapFlags::open(&self.path.join(FALSES_FILE))?,
+                deleted: MmapFlags::open(&self.path.join(DELETED_FILE))?,
+            };
+
+            self.trues_count = stats.trues_count;
+            self.falses_count = stats.falses_count;
+            self.indexed_count = stats.indexed_count;
+
+            // Exclude points, which were removed after the files were built
+            for id in storage.deleted.iter_ones() {
+                let has_true = storage.trues.get(id);
+                let has_false = storage.falses.get(id);
+                self.exclude(has_true, has_false);
+            }
+
+            self.storage = Some(storage);
+            Ok(())
+        }
+
+        fn exclude(&mut self, has_true: bool, has_false: bool) {
+            if has_true {
+                self.trues_count -= 1;
+            }
+            if has_false {
+                self.falses_count -= 1;
+            }
+            if has_true || has_false {
+                self.indexed_count -= 1;
+            }
+        }
+
+        pub fn get(&self, id: PointOffsetType) -> BinaryItem {
+            let Some(storage) = &self.storage else {
+                return BinaryItem::empty();
+            };
+            if storage.deleted.get(id) {
+                return BinaryItem::empty();
+            }
+            BinaryItem::from_bools(storage.trues.get(id), storage.falses.get(id))
+        }
+
+        pub fn remove(&mut self, id: PointOffsetType) {
+            let item = self.get(id);
+            let Some(storage) = &mut self.storage else {
+                return;
+            };
+            storage.deleted.set(id, true);
+            self.exclude(item.has_true(), item.has_false());
+        }
+
+        pub fn trues_count(&self) -> usize {
+            self.trues_count
+        }
+
+        pub fn falses_count(&self) -> usize {
+            self.falses_count
+        }
+
+        pub fn indexed_count(&self) -> usize {
+            self.indexed_count
+        }
+
+        pub fn iter_has_true(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+            self.storage.iter().flat_map(|storage| {
+                storage
+                    .trues
+                    .iter_ones()
+                    .filter(|&id| !storage.deleted.get(id))
+            })
+        }
+
+        pub fn iter_has_false(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+            self.storage.iter().flat_map(|storage| {
+                storage
+                    .falses
+                    .iter_ones()
+                    .filter(|&id| !storage.deleted.get(id))
+            })
+        }
+
+        pub fn files(&self) -> Vec<PathBuf> {
+            let Some(storage) = &self.storage else {
+                return vec![];
+            };
+            let mut files = vec![self.path.join(STATS_FILE)];
+            files.extend(storage.trues.files());
+            files.extend(storage.falses.files());
+            files.extend(storage.deleted.files());
+            files
+        }
+
+        pub fn flusher(&self) -> Flusher {
+            match &self.storage {
+                Some(storage) => storage.deleted.flusher(),
+                None => Box::new(|| Ok(())),
+            }
+        }
+
+        /// Remove the on-disk files of the index, they are rebuilt from RocksDB on the next load
+        pub fn clear_files(&self) -> OperationResult<()> {
+            if self.path.exists() {
+                remove_dir_all(&self.path)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+enum BinaryStorage {
+    Memory(BinaryMemory),
+    Mmap(BinaryMmap),
+}
+
+impl BinaryStorage {
+    fn get(&self, id: PointOffsetType) -> BinaryItem {
+        match self {
+            BinaryStorage::Memory(memory) => memory.get(id),
+            BinaryStorage::Mmap(mmap) => mmap.get(id),
+        }
+    }
+
+    fn remove(&mut self, id: PointOffsetType) {
+        match self {
+            BinaryStorage::Memory(memory) => memory.remove(id),
+            BinaryStorage::Mmap(mmap) => mmap.remove(id),
+        }
+    }
+
+    fn trues_count(&self) -> usize {
+        match self {
+            BinaryStorage::Memory(memory) => memory.trues_count(),
+            BinaryStorage::Mmap(mmap) => mmap.trues_count(),
+        }
+    }
+
+    fn falses_count(&self) -> usize {
+        match self {
+            BinaryStorage::Memory(memory) => memory.falses_count(),
+            BinaryStorage::Mmap(mmap) => mmap.falses_count(),
+        }
+    }
+
+    fn indexed_count(&self) -> usize {
+        match self {
+            BinaryStorage::Memory(memory) => memory.indexed_count(),
+            BinaryStorage::Mmap(mmap) => mmap.indexed_count(),
+        }
+    }
+
+    fn iter_has_true(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match self {
+            BinaryStorage::Memory(memory) => Box::new(memory.iter_has_true()),
+            BinaryStorage::Mmap(mmap) => Box::new(mmap.iter_has_true()),
+        }
+    }
+
+    fn iter_has_false(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match self {
+            BinaryStorage::Memory(memory) => Box::new(memory.iter_has_false()),
+            BinaryStorage::Mmap(mmap) => Box::new(mmap.iter_has_false()),
+        }
+    }
+}
+
 pub struct BinaryIndex {
-    memory: BinaryMemory,
+    storage: BinaryStorage,
     db_wrapper: DatabaseColumnWrapper,
 }
 
@@ -169,7 +434,17 @@ impl BinaryIndex {
         let store_cf_name = Self::storage_cf_name(field_name);
         let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
         Self {
-            memory: BinaryMemory::new(),
+            storage: BinaryStorage::Memory(BinaryMemory::new()),
+            db_wrapper,
+        }
+    }
+
+    /// Create an index, which is stored in memory mapped files in `path`
+    pub fn new_mmap(db: Arc<RwLock<DB>>, field_name: &str, path: &Path) -> BinaryIndex {
+        let store_cf_name = Self::storage_cf_name(field_name);
+        let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
+        Self {
+            storage: BinaryStorage::Mmap(BinaryMmap::new(path)),
             db_wrapper,
         }
     }
@@ -179,20 +454,30 @@ impl BinaryIndex {
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
+        if let BinaryStorage::Mmap(mmap) = &self.storage {
+            mmap.clear_files()?;
+        }
         self.db_wrapper.recreate_column_family()
     }
 
+    pub fn files(&self) -> Vec<PathBuf> {
+        match &self.storage {
+            BinaryStorage::Memory(_) => vec![],
+            BinaryStorage::Mmap(mmap) => mmap.files(),
+        }
+    }
+
     pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
         PayloadIndexTelemetry {
             field_name: None,
-            points_count: self.memory.indexed_count(),
-            points_values_count: self.memory.trues_count() + self.memory.falses_count(),
+            points_count: self.storage.indexed_count(),
+            points_values_count: self.storage.trues_count() + self.storage.falses_count(),
             histogram_bucket_size: None,
         }
     }
 
     pub fn values_count(&self, point_id: PointOffsetType) -> usize {
-        let binary_item = self.memory.get(point_id);
+        let binary_item = self.storage.get(point_id);
         binary_item.has_true() as usize + binary_item.has_false() as usize
     }
 
@@ -202,12 +487,32 @@ impl BinaryIndex {
 
     /// Check if the point has a true value
     pub fn values_has_true(&self, point_id: PointOffsetType) -> bool {
-        self.memory.get(point_id).has_true()
+        self.storage.get(point_id).has_true()
     }
 
     /// Check if the point has a false value
     pub fn values_has_false(&self, point_id: PointOffsetType) -> bool {
-        self.memory.get(point_id).has_false()
+        self.storage.get(point_id).has_false()
+    }
+
+    /// Iterate over both values, along with the amount of points having each of them
+    pub fn iter_counts_per_value(&self) -> impl Iterator<Item = (bool, usize)> {
+        [
+            (true, self.storage.trues_count()),
+            (false, self.storage.falses_count()),
+        ]
+        .into_iter()
+    }
+
+    /// Iterate over both values, along with the points having each of them
+    pub fn iter_values_map(
+        &self,
+    ) -> impl Iterator<Item = (bool, Box<dyn Iterator<Item = PointOffsetType> + '_>)> + '_ {
+        [
+            (true, self.storage.iter_has_true()),
+            (false, self.storage.iter_has_false()),
+        ]
+        .into_iter()
     }
 }
 
@@ -217,23 +522,49 @@ impl PayloadFieldIndex for BinaryIndex {
             return Ok(false);
         }
 
+        if let BinaryStorage::Mmap(mmap) = &mut self.storage {
+            if mmap.files_exist() {
+                mmap.open()?;
+                return Ok(true);
+            }
+        }
+
+        let mut memory = BinaryMemory::new();
         for (key, value) in self.db_wrapper.lock_db().iter()? {
             let idx = PointOffsetType::from_be_bytes(key.as_ref().try_into().unwrap());
 
             debug_assert_eq!(value.len(), 1);
 
             let item = BinaryItem::from(value[0]);
-            self.memory.set_or_insert(idx, &item);
+            memory.set_or_insert(idx, &item);
+        }
+
+        match &mut self.storage {
+            BinaryStorage::Memory(storage) => *storage = memory,
+            BinaryStorage::Mmap(mmap) => mmap.build(&memory)?,
         }
         Ok(true)
     }
 
     fn clear(self) -> OperationResult<()> {
+        if let BinaryStorage::Mmap(mmap) = &self.storage {
+            mmap.clear_files()?;
+        }
         self.db_wrapper.remove_column_family()
     }
 
-    fn flusher(&self) -> crate::common::Flusher {
-        self.db_wrapper.flusher()
+    fn flusher(&self) -> Flusher {
+        let storage_flusher = match &self.storage {
+            BinaryStorage::Memory(_) => None,
+            BinaryStorage::Mmap(mmap) => Some(mmap.flusher()),
+        };
+        let db_flusher = self.db_wrapper.flusher();
+        Box::new(move || {
+            if let Some(storage_flusher) = storage_flusher {
+                storage_flusher()?;
+            }
+            db_flusher()
+        })
     }
 
     fn filter<'a>(
@@ -245,9 +576,9 @@ impl PayloadFieldIndex for BinaryIndex {
                 value: ValueVariants::Bool(value),
             })) => {
                 if *value {
-                    Ok(Box::new(self.memory.iter_has_true()))
+                    Ok(self.storage.iter_has_true())
                 } else {
-                    Ok(Box::new(self.memory.iter_has_false()))
+                    Ok(self.storage.iter_has_false())
                 }
             }
             _ => Err(OperationError::service_error("failed to filter")),
@@ -263,9 +594,9 @@ impl PayloadFieldIndex for BinaryIndex {
                 value: ValueVariants::Bool(value),
             })) => {
                 let count = if *value {
-                    self.memory.trues_count()
+                    self.storage.trues_count()
                 } else {
-                    self.memory.falses_count()
+                    self.storage.falses_count()
                 };
 
                 let estimation = CardinalityEstimation::exact(count)
@@ -302,8 +633,8 @@ impl PayloadFieldIndex for BinaryIndex {
 
         // just two possible blocks: true and false
         let iter = [
-            make_block(self.memory.trues_count(), true, key.clone()),
-            make_block(self.memory.falses_count(), false, key),
+            make_block(self.storage.trues_count(), true, key.clone()),
+            make_block(self.storage.falses_count(), false, key),
         ]
         .into_iter()
         .flatten();
@@ -312,7 +643,7 @@ impl PayloadFieldIndex for BinaryIndex {
     }
 
     fn count_indexed_points(&self) -> usize {
-        self.memory.indexed_count()
+        self.storage.indexed_count()
     }
 }
 
@@ -327,7 +658,14 @@ impl ValueIndexer<bool> for BinaryIndex {
 
         let item = BinaryItem::from_bools(has_true, has_false);
 
-        self.memory.set_or_insert(id, &item);
+        match &mut self.storage {
+            BinaryStorage::Memory(memory) => memory.set_or_insert(id, &item),
+            BinaryStorage::Mmap(_) => {
+                return Err(OperationError::service_error(
+                    "Can't add values to mmap binary index",
+                ))
+            }
+        }
 
         self.db_wrapper.put(id.to_be_bytes(), item.as_bytes())?;
 
@@ -339,7 +677,7 @@ impl ValueIndexer<bool> for BinaryIndex {
     }
 
     fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
-        self.memory.remove(id);
+        self.storage.remove(id);
         self.db_wrapper.remove(id.to_be_bytes())?;
         Ok(())
     }
@@ -454,6 +792,54 @@ mod tests {
         assert_eq!(point_offsets, vec![0, 2, 3, 4, 6, 11]);
     }
 
+    #[test]
+    fn load_from_disk_mmap() {
+        let (tmp_dir, mut index) = new_binary_index();
+
+        bools_fixture()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, value)| {
+                index.add_point(i as u32, &[&value]).unwrap();
+            });
+
+        index.flusher()().unwrap();
+        let db = index.db_wrapper.database;
+        let mmap_path = tmp_dir.path().join("mmap_index");
+
+        let mut mmap_index = BinaryIndex::new_mmap(db.clone(), FIELD_NAME, &mmap_path);
+        assert!(mmap_index.load().unwrap());
+        assert!(!mmap_index.files().is_empty());
+        assert_eq!(mmap_index.count_indexed_points(), 9);
+
+        let point_offsets = mmap_index.filter(&match_bool(false)).unwrap().collect_vec();
+        assert_eq!(point_offsets, vec![1, 2, 3, 5, 6, 10]);
+
+        let point_offsets = mmap_index.filter(&match_bool(true)).unwrap().collect_vec();
+        assert_eq!(point_offsets, vec![0, 2, 3, 4, 6, 11]);
+
+        assert!(mmap_index.add_point(20, &[&json!(true)]).is_err());
+
+        mmap_index.remove_point(2).unwrap();
+        mmap_index.remove_point(5).unwrap();
+        mmap_index.flusher()().unwrap();
+        drop(mmap_index);
+
+        // Removed points are excluded after reopening the files
+        let mut mmap_index = BinaryIndex::new_mmap(db, FIELD_NAME, &mmap_path);
+        assert!(mmap_index.load().unwrap());
+        assert_eq!(mmap_index.count_indexed_points(), 7);
+
+        let point_offsets = mmap_index.filter(&match_bool(false)).unwrap().collect_vec();
+        assert_eq!(point_offsets, vec![1, 3, 6, 10]);
+
+        let point_offsets = mmap_index.filter(&match_bool(true)).unwrap().collect_vec();
+        assert_eq!(point_offsets, vec![0, 3, 4, 6, 11]);
+
+        let cardinality = mmap_index.estimate_cardinality(&match_bool(true)).unwrap();
+        assert_eq!(cardinality.exp, 5);
+    }
+
     #[rstest]
     #[case(json!(false), json!(true))]
     #[case(json!([false, true]), json!(true))]
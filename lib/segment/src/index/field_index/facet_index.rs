@@ -0,0 +1,105 @@
+use common::types::PointOffsetType;
+use itertools::Itertools;
+use smol_str::SmolStr;
+
+use super::binary_index::BinaryIndex;
+use super::map_index::MapIndex;
+use crate::data_types::facets::FacetValueRef;
+use crate::types::IntPayloadType;
+
+/// Field index which is able to enumerate its values, and therefore can be used for faceting
+pub enum FacetIndex<'a> {
+    Keyword(&'a MapIndex<SmolStr>),
+    Int(&'a MapIndex<IntPayloadType>),
+    Bool(&'a BinaryIndex),
+}
+
+impl<'a> FacetIndex<'a> {
+    /// Distinct values of the given point
+    pub fn get_point_values(
+        &self,
+        point_id: PointOffsetType,
+    ) -> Box<dyn Iterator<Item = FacetValueRef<'a>> + 'a> {
+        match *self {
+            FacetIndex::Keyword(index) => Box::new(
+                index
+                    .get_values(point_id)
+                    .into_iter()
+                    .flatten()
+                    .map(|value| FacetValueRef::Keyword(value.as_str()))
+                    .unique(),
+            ),
+            FacetIndex::Int(index) => Box::new(
+                index
+                    .get_values(point_id)
+                    .into_iter()
+                    .flatten()
+                    .map(FacetValueRef::Int)
+                    .unique(),
+            ),
+            FacetIndex::Bool(index) => {
+                let has_true = index.values_has_true(point_id);
+                let has_false = index.values_has_false(point_id);
+                Box::new(
+                    [(true, has_true), (false, has_false)]
+                        .into_iter()
+                        .filter(|(_, has_value)| *has_value)
+                        .map(|(value, _)| FacetValueRef::Bool(value)),
+                )
+            }
+        }
+    }
+
+    /// All values of the index, along with the amount of points having each of them
+    pub fn iter_counts_per_value(
+        &self,
+    ) -> Box<dyn Iterator<Item = (FacetValueRef<'a>, usize)> + 'a> {
+        match *self {
+            FacetIndex::Keyword(index) => Box::new(
+                index
+                    .iter_counts_per_value()
+                    .map(|(value, count)| (FacetValueRef::Keyword(value.as_str()), count)),
+            ),
+            FacetIndex::Int(index) => Box::new(
+                index
+                    .iter_counts_per_value()
+                    .map(|(value, count)| (FacetValueRef::Int(value), count)),
+            ),
+            FacetIndex::Bool(index) => Box::new(
+                index
+                    .iter_counts_per_value()
+                    .map(|(value, count)| (FacetValueRef::Bool(value), count)),
+            ),
+        }
+    }
+
+    /// All values of the index, along with the points having each of them
+    pub fn iter_values_map(
+        &self,
+    ) -> Box<
+        dyn Iterator<
+                Item = (
+                    FacetValueRef<'a>,
+                    Box<dyn Iterator<Item = PointOffsetType> + 'a>,
+                ),
+            > + 'a,
+    > {
+        match *self {
+            FacetIndex::Keyword(index) => Box::new(
+                index
+                    .iter_values_map()
+                    .map(|(value, points)| (FacetValueRef::Keyword(value.as_str()), points)),
+            ),
+            FacetIndex::Int(index) => Box::new(
+                index
+                    .iter_values_map()
+                    .map(|(value, points)| (FacetValueRef::Int(value), points)),
+            ),
+            FacetIndex::Bool(index) => Box::new(
+                index
+                    .iter_values_map()
+                    .map(|(value, points)| (FacetValueRef::Bool(value), points)),
+            ),
+        }
+    }
+}
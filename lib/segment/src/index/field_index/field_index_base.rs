@@ -1,9 +1,11 @@
 use std::fmt::Formatter;
+use std::path::PathBuf;
 
 use common::types::PointOffsetType;
 use serde_json::Value;
 use smol_str::SmolStr;
 
+use super::facet_index::FacetIndex;
 use super::map_index::MapIndex;
 use super::numeric_index::StreamRange;
 use crate::common::operation_error::OperationResult;
@@ -17,7 +19,7 @@ use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
     DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchText,
-    PayloadKeyType, RangeInterface,
+    PayloadKeyType, RangeInterface, UuidIntType,
 };
 
 pub trait PayloadFieldIndex {
@@ -101,6 +103,7 @@ pub enum FieldIndex {
     DatetimeIndex(NumericIndex<IntPayloadType>),
     IntMapIndex(MapIndex<IntPayloadType>),
     KeywordIndex(MapIndex<SmolStr>),
+    UuidMapIndex(MapIndex<UuidIntType>),
     FloatIndex(NumericIndex<FloatPayloadType>),
     GeoIndex(GeoMapIndex),
     FullTextIndex(FullTextIndex),
@@ -114,6 +117,7 @@ impl std::fmt::Debug for FieldIndex {
             FieldIndex::DatetimeIndex(_index) => write!(f, "DatetimeIndex"),
             FieldIndex::IntMapIndex(_index) => write!(f, "IntMapIndex"),
             FieldIndex::KeywordIndex(_index) => write!(f, "KeywordIndex"),
+            FieldIndex::UuidMapIndex(_index) => write!(f, "UuidMapIndex"),
             FieldIndex::FloatIndex(_index) => write!(f, "FloatIndex"),
             FieldIndex::GeoIndex(_index) => write!(f, "GeoIndex"),
             FieldIndex::BinaryIndex(_index) => write!(f, "BinaryIndex"),
@@ -140,6 +144,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(_) => None,
             FieldIndex::IntMapIndex(_) => None,
             FieldIndex::KeywordIndex(_) => None,
+            FieldIndex::UuidMapIndex(_) => None,
             FieldIndex::FloatIndex(_) => None,
             FieldIndex::GeoIndex(_) => None,
             FieldIndex::BinaryIndex(_) => None,
@@ -165,6 +170,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(payload_field_index) => payload_field_index,
             FieldIndex::IntMapIndex(payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(payload_field_index) => payload_field_index,
+            FieldIndex::UuidMapIndex(payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(payload_field_index) => payload_field_index,
             FieldIndex::BinaryIndex(payload_field_index) => payload_field_index,
@@ -179,6 +185,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::UuidMapIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::BinaryIndex(ref mut payload_field_index) => payload_field_index,
@@ -192,6 +199,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::UuidMapIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::BinaryIndex(ref mut payload_field_index) => payload_field_index.load(),
@@ -205,6 +213,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.clear(),
             FieldIndex::IntMapIndex(index) => index.clear(),
             FieldIndex::KeywordIndex(index) => index.clear(),
+            FieldIndex::UuidMapIndex(index) => index.clear(),
             FieldIndex::FloatIndex(index) => index.clear(),
             FieldIndex::GeoIndex(index) => index.clear(),
             FieldIndex::BinaryIndex(index) => index.clear(),
@@ -218,6 +227,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.recreate(),
             FieldIndex::IntMapIndex(index) => index.recreate(),
             FieldIndex::KeywordIndex(index) => index.recreate(),
+            FieldIndex::UuidMapIndex(index) => index.recreate(),
             FieldIndex::FloatIndex(index) => index.recreate(),
             FieldIndex::GeoIndex(index) => index.recreate(),
             FieldIndex::BinaryIndex(index) => index.recreate(),
@@ -225,6 +235,21 @@ impl FieldIndex {
         }
     }
 
+    /// Files used by the index, apart from RocksDB
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            FieldIndex::IntMapIndex(index) => index.files(),
+            FieldIndex::KeywordIndex(index) => index.files(),
+            FieldIndex::UuidMapIndex(index) => index.files(),
+            FieldIndex::IntIndex(index) => index.files(),
+            FieldIndex::DatetimeIndex(index) => index.files(),
+            FieldIndex::FloatIndex(index) => index.files(),
+            FieldIndex::GeoIndex(index) => index.files(),
+            FieldIndex::BinaryIndex(index) => index.files(),
+            FieldIndex::FullTextIndex(index) => index.files(),
+        }
+    }
+
     pub fn count_indexed_points(&self) -> usize {
         self.get_payload_field_index().count_indexed_points()
     }
@@ -271,6 +296,9 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::UuidMapIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
             FieldIndex::FloatIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
@@ -292,6 +320,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.remove_point(point_id),
             FieldIndex::IntMapIndex(index) => index.remove_point(point_id),
             FieldIndex::KeywordIndex(index) => index.remove_point(point_id),
+            FieldIndex::UuidMapIndex(index) => index.remove_point(point_id),
             FieldIndex::FloatIndex(index) => index.remove_point(point_id),
             FieldIndex::GeoIndex(index) => index.remove_point(point_id),
             FieldIndex::BinaryIndex(index) => index.remove_point(point_id),
@@ -305,6 +334,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.get_telemetry_data(),
             FieldIndex::IntMapIndex(index) => index.get_telemetry_data(),
             FieldIndex::KeywordIndex(index) => index.get_telemetry_data(),
+            FieldIndex::UuidMapIndex(index) => index.get_telemetry_data(),
             FieldIndex::FloatIndex(index) => index.get_telemetry_data(),
             FieldIndex::GeoIndex(index) => index.get_telemetry_data(),
             FieldIndex::BinaryIndex(index) => index.get_telemetry_data(),
@@ -318,6 +348,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.values_count(point_id),
             FieldIndex::IntMapIndex(index) => index.values_count(point_id),
             FieldIndex::KeywordIndex(index) => index.values_count(point_id),
+            FieldIndex::UuidMapIndex(index) => index.values_count(point_id),
             FieldIndex::FloatIndex(index) => index.values_count(point_id),
             FieldIndex::GeoIndex(index) => index.values_count(point_id),
             FieldIndex::BinaryIndex(index) => index.values_count(point_id),
@@ -331,6 +362,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.values_is_empty(point_id),
             FieldIndex::IntMapIndex(index) => index.values_is_empty(point_id),
             FieldIndex::KeywordIndex(index) => index.values_is_empty(point_id),
+            FieldIndex::UuidMapIndex(index) => index.values_is_empty(point_id),
             FieldIndex::FloatIndex(index) => index.values_is_empty(point_id),
             FieldIndex::GeoIndex(index) => index.values_is_empty(point_id),
             FieldIndex::BinaryIndex(index) => index.values_is_empty(point_id),
@@ -345,11 +377,26 @@ impl FieldIndex {
             FieldIndex::FloatIndex(index) => Some(NumericFieldIndex::FloatIndex(index)),
             FieldIndex::IntMapIndex(_)
             | FieldIndex::KeywordIndex(_)
+            | FieldIndex::UuidMapIndex(_)
             | FieldIndex::GeoIndex(_)
             | FieldIndex::BinaryIndex(_)
             | FieldIndex::FullTextIndex(_) => None,
         }
     }
+
+    pub fn as_facet_index(&self) -> Option<FacetIndex> {
+        match self {
+            FieldIndex::KeywordIndex(index) => Some(FacetIndex::Keyword(index)),
+            FieldIndex::IntMapIndex(index) => Some(FacetIndex::Int(index)),
+            FieldIndex::BinaryIndex(index) => Some(FacetIndex::Bool(index)),
+            FieldIndex::UuidMapIndex(_)
+            | FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::FullTextIndex(_) => None,
+        }
+    }
 }
 
 pub enum NumericFieldIndex<'a> {
@@ -1,13 +1,16 @@
 use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
 use common::types::PointOffsetType;
 use serde::{Deserialize, Serialize};
 
+use super::mmap_inverted_index::MmapInvertedIndex;
 use super::posting_list::{CompressedPostingList, PostingList};
 use super::postings_iterator::{
     intersect_compressed_postings_iterator, intersect_postings_iterator,
 };
 use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
 use crate::types::{FieldCondition, Match, PayloadKeyType};
 
@@ -62,6 +65,7 @@ impl ParsedQuery {
 pub enum InvertedIndex {
     Mutable(MutableInvertedIndex),
     Immutable(ImmutableInvertedIndex),
+    Mmap(MmapInvertedIndex),
 }
 
 impl InvertedIndex {
@@ -73,10 +77,15 @@ impl InvertedIndex {
         }
     }
 
+    pub fn new_mmap(path: &Path) -> InvertedIndex {
+        InvertedIndex::Mmap(MmapInvertedIndex::new(path))
+    }
+
     pub fn document_from_tokens(&mut self, tokens: &BTreeSet<String>) -> Document {
         let vocab = match self {
             InvertedIndex::Mutable(index) => &mut index.vocab,
             InvertedIndex::Immutable(index) => &mut index.vocab,
+            InvertedIndex::Mmap(index) => return index.document_from_tokens(tokens),
         };
         Self::document_from_tokens_impl(vocab, tokens)
     }
@@ -112,6 +121,9 @@ impl InvertedIndex {
             InvertedIndex::Immutable(_index) => Err(OperationError::service_error(
                 "Can't add values to immutable text index",
             )),
+            InvertedIndex::Mmap(_index) => Err(OperationError::service_error(
+                "Can't add values to mmap text index",
+            )),
         }
     }
 
@@ -119,6 +131,7 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.remove_document(idx),
             InvertedIndex::Immutable(index) => index.remove_document(idx),
+            InvertedIndex::Mmap(index) => index.remove_document(idx),
         }
     }
 
@@ -126,6 +139,7 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.filter(query),
             InvertedIndex::Immutable(index) => index.filter(query),
+            InvertedIndex::Mmap(index) => index.filter(query),
         }
     }
 
@@ -137,6 +151,7 @@ impl InvertedIndex {
         let points_count = match self {
             InvertedIndex::Mutable(index) => index.points_count,
             InvertedIndex::Immutable(index) => index.points_count,
+            InvertedIndex::Mmap(index) => index.points_count,
         };
         let posting_lengths: Option<Vec<usize>> = query
             .tokens
@@ -157,6 +172,7 @@ impl InvertedIndex {
                         .unwrap()
                         .as_ref()
                         .map(|p| p.len()),
+                    Self::Mmap(index) => index.posting_len(idx),
                 },
             })
             .collect();
@@ -233,6 +249,11 @@ impl InvertedIndex {
                     .vocab_with_positngs_len_iter()
                     .filter_map(map_filter_condition),
             ),
+            InvertedIndex::Mmap(index) => Box::new(
+                index
+                    .vocab_with_postings_len_iter()
+                    .filter_map(map_filter_condition),
+            ),
         }
     }
 
@@ -250,6 +271,9 @@ impl InvertedIndex {
             InvertedIndex::Immutable(i) => {
                 *i = index.into();
             }
+            InvertedIndex::Mmap(i) => {
+                i.build(index)?;
+            }
         }
 
         Ok(())
@@ -259,6 +283,7 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.check_match(parsed_query, point_id),
             InvertedIndex::Immutable(index) => index.check_match(parsed_query, point_id),
+            InvertedIndex::Mmap(index) => index.check_match(parsed_query, point_id),
         }
     }
 
@@ -266,6 +291,7 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.values_is_empty(point_id),
             InvertedIndex::Immutable(index) => index.values_is_empty(point_id),
+            InvertedIndex::Mmap(index) => index.values_is_empty(point_id),
         }
     }
 
@@ -273,6 +299,7 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.values_count(point_id),
             InvertedIndex::Immutable(index) => index.values_count(point_id),
+            InvertedIndex::Mmap(index) => index.values_count(point_id),
         }
     }
 
@@ -280,6 +307,7 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.points_count,
             InvertedIndex::Immutable(index) => index.points_count,
+            InvertedIndex::Mmap(index) => index.points_count,
         }
     }
 
@@ -287,16 +315,31 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.vocab.get(token).copied(),
             InvertedIndex::Immutable(index) => index.vocab.get(token).copied(),
+            InvertedIndex::Mmap(index) => index.get_token(token),
+        }
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        match self {
+            InvertedIndex::Mutable(_) | InvertedIndex::Immutable(_) => Box::new(|| Ok(())),
+            InvertedIndex::Mmap(index) => index.flusher(),
+        }
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            InvertedIndex::Mutable(_) | InvertedIndex::Immutable(_) => vec![],
+            InvertedIndex::Mmap(index) => index.files(),
         }
     }
 }
 
 #[derive(Default)]
 pub struct MutableInvertedIndex {
-    postings: Vec<Option<PostingList>>,
-    vocab: HashMap<String, TokenId>,
-    point_to_docs: Vec<Option<Document>>,
-    points_count: usize,
+    pub(super) postings: Vec<Option<PostingList>>,
+    pub(super) vocab: HashMap<String, TokenId>,
+    pub(super) point_to_docs: Vec<Option<Document>>,
+    pub(super) points_count: usize,
 }
 
 impl MutableInvertedIndex {
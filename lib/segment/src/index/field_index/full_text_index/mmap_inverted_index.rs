@@ -0,0 +1,305 @@
+use std::cmp::Ordering;
+use std::fs::{create_dir_all, remove_dir_all};
+use std::iter;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use super::inverted_index::{Document, MutableInvertedIndex, ParsedQuery, TokenId};
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::index::field_index::mmap_flags::MmapFlags;
+use crate::index::field_index::mmap_point_to_values::MmapPointToValues;
+
+const STATS_FILE: &str = "stats.json";
+const DELETED_FILE: &str = "deleted.bin";
+const VOCAB: &str = "vocab";
+const POSTINGS: &str = "postings";
+const POINT_TO_TOKENS: &str = "point_to_tokens";
+
+/// Inverted index, which keeps vocabulary, postings and documents in memory mapped files.
+///
+/// Tokens are stored in sorted order, so the position of the token is its id.
+pub struct MmapInvertedIndex {
+    path: PathBuf,
+    storage: Option<Storage>,
+    pub(super) points_count: usize,
+}
+
+struct Storage {
+    /// Bytes of each token, sorted lexicographically
+    vocab: MmapPointToValues<u8>,
+    /// Sorted points of each token
+    postings: MmapPointToValues<PointOffsetType>,
+    /// Sorted tokens of each point
+    point_to_tokens: MmapPointToValues<TokenId>,
+    deleted: MmapFlags,
+}
+
+/// Statistics of the index at the moment of building, removed points are not taken into account
+#[derive(Serialize, Deserialize)]
+struct Stats {
+    points_count: usize,
+}
+
+impl MmapInvertedIndex {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_owned(),
+            storage: None,
+            points_count: 0,
+        }
+    }
+
+    pub fn files_exist(&self) -> bool {
+        self.path.join(STATS_FILE).exists()
+            && self.path.join(DELETED_FILE).exists()
+            && MmapPointToValues::<u8>::exists(&self.path, VOCAB)
+            && MmapPointToValues::<PointOffsetType>::exists(&self.path, POSTINGS)
+            && MmapPointToValues::<TokenId>::exists(&self.path, POINT_TO_TOKENS)
+    }
+
+    /// Write files of the index from the in-memory representation and open them
+    pub fn build(&mut self, index: MutableInvertedIndex) -> OperationResult<()> {
+        let MutableInvertedIndex {
+            postings,
+            vocab,
+            point_to_docs,
+            ..
+        } = index;
+
+        create_dir_all(&self.path)?;
+
+        let mut vocab: Vec<(String, TokenId)> = vocab.into_iter().collect();
+        vocab.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        // Map ids of the in-memory vocabulary into positions of the sorted one
+        let mut token_ids = vec![0; vocab.len()];
+        for (new_id, (_, old_id)) in vocab.iter().enumerate() {
+            token_ids[*old_id as usize] = new_id as TokenId;
+        }
+
+        MmapPointToValues::<u8>::create(
+            &self.path,
+            VOCAB,
+            vocab.iter().map(|(token, _)| token.as_bytes()),
+        )?;
+        MmapPointToValues::<PointOffsetType>::create(
+            &self.path,
+            POSTINGS,
+            vocab.iter().map(|(_, old_id)| {
+                postings
+                    .get(*old_id as usize)
+                    .and_then(Option::as_ref)
+                    .map(|posting| posting.iter().collect::<Vec<_>>())
+                    .unwrap_or_default()
+            }),
+        )?;
+        MmapPointToValues::<TokenId>::create(
+            &self.path,
+            POINT_TO_TOKENS,
+            point_to_docs.iter().map(|doc| {
+                let mut tokens: Vec<TokenId> = doc
+                    .iter()
+                    .flat_map(|doc| doc.tokens())
+                    .map(|old_id| token_ids[*old_id as usize])
+                    .collect();
+                tokens.sort_unstable();
+                tokens
+            }),
+        )?;
+        MmapFlags::create(&self.path.join(DELETED_FILE), point_to_docs.len(), [])?;
+
+        // Points without tokens are not considered indexed, as they can't be found by any query
+        let points_count = point_to_docs
+            .iter()
+            .filter(|doc| doc.as_ref().is_some_and(|doc| !doc.is_empty()))
+            .count();
+
+        // Stats are written last, so incomplete files are never considered as built
+        atomic_save_json(&self.path.join(STATS_FILE), &Stats { points_count })?;
+
+        self.open()
+    }
+
+    pub fn open(&mut self) -> OperationResult<()> {
+        let stats: Stats = read_json(&self.path.join(STATS_FILE))?;
+        let storage = Storage {
+            vocab: MmapPointToValues::open(&self.path, VOCAB)?,
+            postings: MmapPointToValues::open(&self.path, POSTINGS)?,
+            point_to_tokens: MmapPointToValues::open(&self.path, POINT_TO_TOKENS)?,
+            deleted: MmapFlags::open(&self.path.join(DELETED_FILE))?,
+        };
+
+        // Exclude points, which were removed after the files were built
+        let removed_points = storage
+            .deleted
+            .iter_ones()
+            .filter(|&idx| {
+                storage
+                    .point_to_tokens
+                    .get_values(idx)
+                    .is_some_and(|tokens| !tokens.is_empty())
+            })
+            .count();
+
+        self.points_count = stats.points_count - removed_points;
+        self.storage = Some(storage);
+        Ok(())
+    }
+
+    fn get_tokens(&self, idx: PointOffsetType) -> Option<&[TokenId]> {
+        let storage = self.storage.as_ref()?;
+        if storage.deleted.get(idx) {
+            return None;
+        }
+        storage.point_to_tokens.get_values(idx)
+    }
+
+    pub fn get_token(&self, token: &str) -> Option<TokenId> {
+        let storage = self.storage.as_ref()?;
+        let token = token.as_bytes();
+
+        // Binary search over the sorted vocabulary
+        let (mut left, mut right) = (0, storage.vocab.len());
+        while left < right {
+            let middle = left + (right - left) / 2;
+            let middle_token = storage
+                .vocab
+                .get_values(middle as TokenId)
+                .unwrap_or_default();
+            match middle_token.cmp(token) {
+                Ordering::Less => left = middle + 1,
+                Ordering::Greater => right = middle,
+                Ordering::Equal => return Some(middle as TokenId),
+            }
+        }
+        None
+    }
+
+    /// Convert tokens into a document, tokens which are not in the vocabulary are skipped
+    pub fn document_from_tokens<'a>(
+        &self,
+        tokens: impl IntoIterator<Item = &'a String>,
+    ) -> Document {
+        Document::new(
+            tokens
+                .into_iter()
+                .filter_map(|token| self.get_token(token))
+                .collect(),
+        )
+    }
+
+    /// Mark point as removed, returns `false` if the point was not indexed
+    pub fn remove_document(&mut self, idx: PointOffsetType) -> bool {
+        let Some(storage) = &mut self.storage else {
+            return false;
+        };
+        if storage.deleted.get(idx) {
+            return false;
+        }
+        let Some(tokens) = storage.point_to_tokens.get_values(idx) else {
+            return false;
+        };
+        if !tokens.is_empty() {
+            self.points_count -= 1;
+        }
+        storage.deleted.set(idx, true);
+        true
+    }
+
+    pub fn posting_len(&self, token: TokenId) -> Option<usize> {
+        let storage = self.storage.as_ref()?;
+        storage
+            .postings
+            .get_values(token)
+            .map(|points| points.len())
+    }
+
+    pub fn filter(&self, query: &ParsedQuery) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let Some(storage) = &self.storage else {
+            return Box::new(iter::empty());
+        };
+        let postings: Option<Vec<&[PointOffsetType]>> = query
+            .tokens
+            .iter()
+            .map(|&token| token.and_then(|token| storage.postings.get_values(token)))
+            .collect();
+        let Some(mut postings) = postings else {
+            // There are unseen tokens -> no matches
+            return Box::new(iter::empty());
+        };
+        if postings.is_empty() {
+            // Empty request -> no matches
+            return Box::new(iter::empty());
+        }
+
+        // Iterate over the smallest posting and check the presence of the point in others
+        postings.sort_unstable_by_key(|posting| posting.len());
+        let smallest = postings.remove(0);
+        Box::new(smallest.iter().copied().filter(move |idx| {
+            !storage.deleted.get(*idx)
+                && postings
+                    .iter()
+                    .all(|posting| posting.binary_search(idx).is_ok())
+        }))
+    }
+
+    pub fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
+        let Some(tokens) = self.get_tokens(point_id) else {
+            return false;
+        };
+        parsed_query.tokens.iter().all(|query_token| {
+            query_token.is_some_and(|query_token| tokens.binary_search(&query_token).is_ok())
+        })
+    }
+
+    pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
+        self.get_tokens(point_id)
+            .map_or(true, |tokens| tokens.is_empty())
+    }
+
+    pub fn values_count(&self, point_id: PointOffsetType) -> usize {
+        self.get_tokens(point_id).map_or(0, |tokens| tokens.len())
+    }
+
+    pub fn vocab_with_postings_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.storage.iter().flat_map(|storage| {
+            (0..storage.vocab.len() as TokenId).filter_map(|token_id| {
+                let token = storage.vocab.get_values(token_id)?;
+                let token = std::str::from_utf8(token).ok()?;
+                let postings = storage.postings.get_values(token_id)?;
+                Some((token, postings.len()))
+            })
+        })
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        let Some(storage) = &self.storage else {
+            return vec![];
+        };
+        let mut files = vec![self.path.join(STATS_FILE)];
+        files.extend(storage.vocab.files());
+        files.extend(storage.postings.files());
+        files.extend(storage.point_to_tokens.files());
+        files.extend(storage.deleted.files());
+        files
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        match &self.storage {
+            Some(storage) => storage.deleted.flusher(),
+            None => Box::new(|| Ok(())),
+        }
+    }
+
+    /// Remove the on-disk files of the index, they are rebuilt from RocksDB on the next load
+    pub fn clear_files(&self) -> OperationResult<()> {
+        if self.path.exists() {
+            remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
 mod inverted_index;
+mod mmap_inverted_index;
 mod posting_list;
 mod postings_iterator;
 pub mod text_index;
-mod tokenizers;
+pub(crate) mod tokenizers;
 
 #[cfg(test)]
 mod tests;
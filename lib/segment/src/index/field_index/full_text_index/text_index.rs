@@ -1,4 +1,5 @@
 use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
@@ -78,6 +79,22 @@ impl FullTextIndex {
         }
     }
 
+    /// Create an index, which is stored in memory mapped files in `path`
+    pub fn new_mmap(
+        db: Arc<RwLock<DB>>,
+        config: TextIndexParams,
+        field: &str,
+        path: &Path,
+    ) -> Self {
+        let store_cf_name = Self::storage_cf_name(field);
+        let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
+        FullTextIndex {
+            inverted_index: InvertedIndex::new_mmap(path),
+            db_wrapper,
+            config,
+        }
+    }
+
     pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
         PayloadIndexTelemetry {
             field_name: None,
@@ -88,9 +105,16 @@ impl FullTextIndex {
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
+        if let InvertedIndex::Mmap(index) = &self.inverted_index {
+            index.clear_files()?;
+        }
         self.db_wrapper.recreate_column_family()
     }
 
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.inverted_index.files()
+    }
+
     pub fn parse_query(&self, text: &str) -> ParsedQuery {
         let mut tokens = HashSet::new();
         Tokenizer::tokenize_query(text, &self.config, |token| {
@@ -181,6 +205,13 @@ impl PayloadFieldIndex for FullTextIndex {
             return Ok(false);
         };
 
+        if let InvertedIndex::Mmap(index) = &mut self.inverted_index {
+            if index.files_exist() {
+                index.open()?;
+                return Ok(true);
+            }
+        }
+
         let db = self.db_wrapper.lock_db();
         let i = db.iter()?.map(|(key, value)| {
             let idx = Self::restore_key(&key);
@@ -193,11 +224,19 @@ impl PayloadFieldIndex for FullTextIndex {
     }
 
     fn clear(self) -> OperationResult<()> {
+        if let InvertedIndex::Mmap(index) = &self.inverted_index {
+            index.clear_files()?;
+        }
         self.db_wrapper.remove_column_family()
     }
 
     fn flusher(&self) -> Flusher {
-        self.db_wrapper.flusher()
+        let index_flusher = self.inverted_index.flusher();
+        let db_flusher = self.db_wrapper.flusher();
+        Box::new(move || {
+            index_flusher()?;
+            db_flusher()
+        })
     }
 
     fn filter(
@@ -245,14 +284,37 @@ mod tests {
     use crate::data_types::text_index::{TextIndexType, TokenizerType};
     use crate::json_path::path;
 
+    #[derive(Clone, Copy)]
+    enum IndexType {
+        Mutable,
+        Immutable,
+        Mmap,
+    }
+
     fn filter_request(text: &str) -> FieldCondition {
         FieldCondition::new_match(path("text"), Match::new_text(text))
     }
 
+    fn open_index(
+        temp_dir: &tempfile::TempDir,
+        config: TextIndexParams,
+        index_type: IndexType,
+    ) -> FullTextIndex {
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        match index_type {
+            IndexType::Mutable => FullTextIndex::new(db, config, "text", true),
+            IndexType::Immutable => FullTextIndex::new(db, config, "text", false),
+            IndexType::Mmap => {
+                FullTextIndex::new_mmap(db, config, "text", &temp_dir.path().join("mmap_index"))
+            }
+        }
+    }
+
     #[rstest]
-    #[case(true)]
-    #[case(false)]
-    fn test_full_text_indexing(#[case] immutable: bool) {
+    #[case(IndexType::Mutable)]
+    #[case(IndexType::Immutable)]
+    #[case(IndexType::Mmap)]
+    fn test_full_text_indexing(#[case] index_type: IndexType) {
         let payloads: Vec<_> = vec![
             serde_json::json!("The celebration had a long way to go and even in the silent depths of Multivac's underground chambers, it hung in the air."),
             serde_json::json!("If nothing else, there was the mere fact of isolation and silence."),
@@ -271,6 +333,7 @@ mod tests {
             min_token_len: None,
             max_token_len: None,
             lowercase: None,
+            on_disk: None,
         };
 
         {
@@ -323,8 +386,7 @@ mod tests {
         }
 
         {
-            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
-            let mut index = FullTextIndex::new(db, config, "text", immutable);
+            let mut index = open_index(&temp_dir, config.clone(), index_type);
             let loaded = index.load().unwrap();
             assert!(loaded);
 
@@ -354,6 +416,19 @@ mod tests {
             // check deletion of non-existing point
             index.remove_point(3).unwrap();
             assert_eq!(index.count_indexed_points(), 2);
+
+            index.flusher()().unwrap();
+        }
+
+        {
+            // check that deletions are persisted
+            let mut index = open_index(&temp_dir, config, index_type);
+            assert!(index.load().unwrap());
+            assert_eq!(index.count_indexed_points(), 2);
+
+            let filter_condition = filter_request("the");
+            let search_res: Vec<_> = index.filter(&filter_condition).unwrap().collect();
+            assert_eq!(search_res, vec![1, 4]);
         }
     }
 }
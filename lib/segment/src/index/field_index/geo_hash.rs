@@ -2,7 +2,7 @@ use std::ops::Range;
 
 use geo::algorithm::haversine_distance::HaversineDistance;
 use geo::{Coord, Intersects, LineString, Point, Polygon};
-use geohash::{decode, decode_bbox, encode, Direction, GeohashError};
+use geohash::{decode, decode_bbox, encode, neighbor, Direction, GeohashError};
 use itertools::Itertools;
 use smol_str::SmolStr;
 
@@ -12,7 +12,7 @@ use crate::types::{GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius};
 pub type GeoHash = SmolStr;
 
 /// Max size of geo-hash used for indexing. size=12 is about 6cm2
-const GEOHASH_MAX_LENGTH: usize = 12;
+pub const GEOHASH_MAX_LENGTH: usize = 12;
 
 const LON_RANGE: Range<f64> = -180.0..180.0;
 const LAT_RANGE: Range<f64> = -90.0..90.0;
@@ -98,6 +98,41 @@ pub fn geo_hash_to_box(geo_hash: &GeoHash) -> GeoBoundingBox {
     }
 }
 
+/// Neighbour region of the same precision in the given direction, together with the bounding box
+/// which covers both regions.
+///
+/// Returns `None` for neighbours across the antimeridian or the poles, as a single bounding box
+/// can't cover both regions then.
+pub fn geo_hash_with_neighbor_box(
+    geo_hash: &GeoHash,
+    direction: Direction,
+) -> Option<(GeoHash, GeoBoundingBox)> {
+    let neighbor_hash: GeoHash = neighbor(geo_hash, direction).ok()?.into();
+    let region = geo_hash_to_box(geo_hash);
+    let neighbor_region = geo_hash_to_box(&neighbor_hash);
+    let top_left = GeoPoint {
+        lon: region.top_left.lon.min(neighbor_region.top_left.lon),
+        lat: region.top_left.lat.max(neighbor_region.top_left.lat),
+    };
+    let bottom_right = GeoPoint {
+        lon: region
+            .bottom_right
+            .lon
+            .max(neighbor_region.bottom_right.lon),
+        lat: region
+            .bottom_right
+            .lat
+            .min(neighbor_region.bottom_right.lat),
+    };
+    Some((
+        neighbor_hash,
+        GeoBoundingBox {
+            top_left,
+            bottom_right,
+        },
+    ))
+}
+
 #[derive(Debug)]
 struct GeohashBoundingBox {
     north_west: GeoHash,
@@ -0,0 +1,411 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{create_dir_all, remove_dir_all};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
+use parking_lot::RwLock;
+use rocksdb::DB;
+use serde::{Deserialize, Serialize};
+
+use super::mutable_geo_index::MutableGeoMapIndex;
+use super::GeoMapIndex;
+use crate::common::operation_error::OperationResult;
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
+use crate::index::field_index::geo_hash::{encode_max_precision, GeoHash, GEOHASH_MAX_LENGTH};
+use crate::index::field_index::mmap_flags::MmapFlags;
+use crate::index::field_index::mmap_point_to_values::MmapPointToValues;
+use crate::index::field_index::mmap_vec::MmapVec;
+use crate::types::GeoPoint;
+
+const STATS_FILE: &str = "stats.json";
+const DELETED_FILE: &str = "deleted.bin";
+const COUNTS_HASHES_FILE: &str = "counts_hashes.bin";
+const COUNTS_FILE: &str = "counts.bin";
+const POINTS_MAP_HASHES_FILE: &str = "points_map_hashes.bin";
+const POINTS_MAP: &str = "points_map";
+const POINT_TO_VALUES: &str = "point_to_values";
+
+/// Geo hash, padded with zeros to the max length.
+/// Padded hashes are sorted in the same order as the original strings.
+type PackedGeoHash = [u8; GEOHASH_MAX_LENGTH];
+
+fn pack_hash(hash: &str) -> PackedGeoHash {
+    let mut packed = [0; GEOHASH_MAX_LENGTH];
+    let len = hash.len().min(GEOHASH_MAX_LENGTH);
+    packed[..len].copy_from_slice(&hash.as_bytes()[..len]);
+    packed
+}
+
+fn unpack_hash(packed: &PackedGeoHash) -> GeoHash {
+    let len = packed
+        .iter()
+        .position(|&byte| byte == 0)
+        .unwrap_or(GEOHASH_MAX_LENGTH);
+    GeoHash::new(std::str::from_utf8(&packed[..len]).unwrap_or_default())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Default, Debug)]
+struct HashCounts {
+    points: u32,
+    values: u32,
+}
+
+/// Geo index, which keeps per-hash counts, hash-to-points and point-to-values maps
+/// in memory mapped files.
+///
+/// Files are built from the RocksDB records on the first load, after that the index is read-only,
+/// except for point removal.
+pub struct MmapGeoMapIndex {
+    path: PathBuf,
+    storage: Option<Storage>,
+    /// Counts of the values of removed points, which are subtracted from the stored counts
+    removed_counts: HashMap<GeoHash, HashCounts>,
+    pub points_count: usize,
+    pub points_values_count: usize,
+    pub max_values_per_point: usize,
+    db_wrapper: DatabaseColumnWrapper,
+}
+
+struct Storage {
+    /// Sorted hashes of all regions, which contain indexed points
+    counts_hashes: MmapVec<PackedGeoHash>,
+    /// Number of points and values in each region of `counts_hashes`
+    counts: MmapVec<HashCounts>,
+    /// Sorted max precision hashes of indexed values
+    points_map_hashes: MmapVec<PackedGeoHash>,
+    /// Points of each hash in `points_map_hashes`
+    points_map: MmapPointToValues<PointOffsetType>,
+    point_to_values: MmapPointToValues<GeoPoint>,
+    deleted: MmapFlags,
+}
+
+/// Statistics of the index at the moment of building, removed points are not taken into account
+#[derive(Serialize, Deserialize)]
+struct Stats {
+    points_count: usize,
+    points_values_count: usize,
+    max_values_per_point: usize,
+}
+
+impl MmapGeoMapIndex {
+    pub fn new(db: Arc<RwLock<DB>>, store_cf_name: &str, path: &Path) -> Self {
+        let db_wrapper = DatabaseColumnWrapper::new(db, store_cf_name);
+        Self {
+            path: path.to_owned(),
+            storage: None,
+            removed_counts: Default::default(),
+            points_count: 0,
+            points_values_count: 0,
+            max_values_per_point: 0,
+            db_wrapper,
+        }
+    }
+
+    pub fn db_wrapper(&self) -> &DatabaseColumnWrapper {
+        &self.db_wrapper
+    }
+
+    fn files_exist(path: &Path) -> bool {
+        path.join(STATS_FILE).exists()
+            && path.join(DELETED_FILE).exists()
+            && path.join(COUNTS_HASHES_FILE).exists()
+            && path.join(COUNTS_FILE).exists()
+            && path.join(POINTS_MAP_HASHES_FILE).exists()
+            && MmapPointToValues::<PointOffsetType>::exists(path, POINTS_MAP)
+            && MmapPointToValues::<GeoPoint>::exists(path, POINT_TO_VALUES)
+    }
+
+    pub fn load(&mut self) -> OperationResult<bool> {
+        if !self.db_wrapper.has_column_family()? {
+            return Ok(false);
+        }
+
+        if !Self::files_exist(&self.path) {
+            // Use `MutableGeoMapIndex` to load data from db
+            // and convert it into the on-disk representation
+            let mut mutable = MutableGeoMapIndex::new(
+                self.db_wrapper.database.clone(),
+                &self.db_wrapper.column_name,
+            );
+            if !mutable.load()? {
+                return Ok(false);
+            }
+            Self::build_files(&self.path, mutable)?;
+        }
+
+        self.open_files()?;
+        Ok(true)
+    }
+
+    fn build_files(path: &Path, mutable: MutableGeoMapIndex) -> OperationResult<()> {
+        let MutableGeoMapIndex {
+            points_per_hash,
+            values_per_hash,
+            points_map,
+            point_to_values,
+            points_count,
+            points_values_count,
+            max_values_per_point,
+            ..
+        } = mutable;
+
+        create_dir_all(path)?;
+
+        let mut counts_per_hash: HashMap<&GeoHash, HashCounts> = HashMap::new();
+        for (hash, &points) in &points_per_hash {
+            counts_per_hash.entry(hash).or_default().points = points as u32;
+        }
+        for (hash, &values) in &values_per_hash {
+            counts_per_hash.entry(hash).or_default().values = values as u32;
+        }
+        let mut counts_per_hash: Vec<_> = counts_per_hash.into_iter().collect();
+        counts_per_hash.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        MmapVec::create(
+            &path.join(COUNTS_HASHES_FILE),
+            counts_per_hash.iter().map(|(hash, _)| pack_hash(hash)),
+        )?;
+        MmapVec::create(
+            &path.join(COUNTS_FILE),
+            counts_per_hash.iter().map(|(_, counts)| *counts),
+        )?;
+
+        // `points_map` is a `BTreeMap`, so hashes are already sorted
+        MmapVec::create(
+            &path.join(POINTS_MAP_HASHES_FILE),
+            points_map.keys().map(|hash| pack_hash(hash)),
+        )?;
+        MmapPointToValues::<PointOffsetType>::create(
+            path,
+            POINTS_MAP,
+            points_map.values().map(|points| {
+                let mut points: Vec<_> = points.iter().copied().collect();
+                points.sort_unstable();
+                points
+            }),
+        )?;
+
+        MmapPointToValues::<GeoPoint>::create(path, POINT_TO_VALUES, &point_to_values)?;
+        MmapFlags::create(&path.join(DELETED_FILE), point_to_values.len(), [])?;
+
+        // Stats are written last, so incomplete files are never considered as built
+        atomic_save_json(
+            &path.join(STATS_FILE),
+            &Stats {
+                points_count,
+                points_values_count,
+                max_values_per_point,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn open_files(&mut self) -> OperationResult<()> {
+        let stats: Stats = read_json(&self.path.join(STATS_FILE))?;
+
+        let storage = Storage {
+            counts_hashes: MmapVec::open(&self.path.join(COUNTS_HASHES_FILE))?,
+            counts: MmapVec::open(&self.path.join(COUNTS_FILE))?,
+            points_map_hashes: MmapVec::open(&self.path.join(POINTS_MAP_HASHES_FILE))?,
+            points_map: MmapPointToValues::open(&self.path, POINTS_MAP)?,
+            point_to_values: MmapPointToValues::open(&self.path, POINT_TO_VALUES)?,
+            deleted: MmapFlags::open(&self.path.join(DELETED_FILE))?,
+        };
+
+        self.points_count = stats.points_count;
+        self.points_values_count = stats.points_values_count;
+        self.max_values_per_point = stats.max_values_per_point;
+        self.removed_counts.clear();
+
+        // Exclude points, which were removed after the files were built
+        for idx in storage.deleted.iter_ones() {
+            if let Some(values) = storage.point_to_values.get_values(idx) {
+                self.exclude_values(values);
+            }
+        }
+
+        self.storage = Some(storage);
+        Ok(())
+    }
+
+    /// Update statistics for the values of a removed point
+    fn exclude_values(&mut self, values: &[GeoPoint]) {
+        if values.is_empty() {
+            return;
+        }
+        self.points_count -= 1;
+        self.points_values_count -= values.len();
+
+        let hashes: Vec<GeoHash> = values
+            .iter()
+            .filter_map(|value| encode_max_precision(value.lon, value.lat).ok())
+            .collect();
+        let mut seen_hashes: HashSet<&str> = HashSet::new();
+        for hash in &hashes {
+            for i in 0..=hash.len() {
+                let sub_hash = &hash[0..i];
+                let counts = self.removed_counts.entry(sub_hash.into()).or_default();
+                counts.values += 1;
+                if seen_hashes.insert(sub_hash) {
+                    counts.points += 1;
+                }
+            }
+        }
+    }
+
+    pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        // Storage is taken out temporarily, so removed values can be borrowed while updating stats
+        let Some(mut storage) = self.storage.take() else {
+            return Ok(());
+        };
+        let result = self.remove_point_from_storage(&mut storage, idx);
+        self.storage = Some(storage);
+        result
+    }
+
+    fn remove_point_from_storage(
+        &mut self,
+        storage: &mut Storage,
+        idx: PointOffsetType,
+    ) -> OperationResult<()> {
+        if storage.deleted.get(idx) {
+            return Ok(());
+        }
+        let Some(values) = storage.point_to_values.get_values(idx) else {
+            return Ok(());
+        };
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        for value in values {
+            let hash = encode_max_precision(value.lon, value.lat).unwrap();
+            self.db_wrapper
+                .remove(GeoMapIndex::encode_db_key(&hash, idx))?;
+        }
+
+        self.exclude_values(values);
+        storage.deleted.set(idx, true);
+        Ok(())
+    }
+
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<&[GeoPoint]> {
+        let storage = self.storage.as_ref()?;
+        let values = storage.point_to_values.get_values(idx)?;
+        if storage.deleted.get(idx) {
+            return Some(&[]);
+        }
+        Some(values)
+    }
+
+    fn removed_counts(&self, hash: &str) -> HashCounts {
+        self.removed_counts.get(hash).copied().unwrap_or_default()
+    }
+
+    fn stored_counts(&self, hash: &str) -> HashCounts {
+        let Some(storage) = &self.storage else {
+            return HashCounts::default();
+        };
+        match storage
+            .counts_hashes
+            .as_slice()
+            .binary_search(&pack_hash(hash))
+        {
+            Ok(position) => storage.counts.as_slice()[position],
+            Err(_) => HashCounts::default(),
+        }
+    }
+
+    pub fn get_points_of_hash(&self, hash: &GeoHash) -> usize {
+        let stored = self.stored_counts(hash).points;
+        let removed = self.removed_counts(hash).points;
+        stored.saturating_sub(removed) as usize
+    }
+
+    pub fn get_values_of_hash(&self, hash: &GeoHash) -> usize {
+        let stored = self.stored_counts(hash).values;
+        let removed = self.removed_counts(hash).values;
+        stored.saturating_sub(removed) as usize
+    }
+
+    pub fn get_points_per_hash(&self) -> impl Iterator<Item = (GeoHash, usize)> + '_ {
+        self.storage.iter().flat_map(move |storage| {
+            storage
+                .counts_hashes
+                .as_slice()
+                .iter()
+                .zip(storage.counts.as_slice())
+                .map(move |(packed, counts)| {
+                    let hash = unpack_hash(packed);
+                    let removed = self.removed_counts(&hash).points;
+                    let points = counts.points.saturating_sub(removed) as usize;
+                    (hash, points)
+                })
+        })
+    }
+
+    /// Iterate over not removed points, which have values in the sub-regions of the `geo` hash
+    pub fn get_points_of_sub_regions(
+        &self,
+        geo: &GeoHash,
+    ) -> impl Iterator<Item = PointOffsetType> + '_ {
+        let prefix = geo.as_bytes().to_vec();
+        self.storage.iter().flat_map(move |storage| {
+            let hashes = storage.points_map_hashes.as_slice();
+            let start = hashes.partition_point(|hash| hash.as_slice() < prefix.as_slice());
+            let prefix = prefix.clone();
+            (start..hashes.len())
+                .take_while(move |&position| hashes[position].starts_with(&prefix))
+                .flat_map(move |position| {
+                    storage
+                        .points_map
+                        .get_values(position as u32)
+                        .unwrap_or_default()
+                        .iter()
+                        .copied()
+                })
+                .filter(move |&idx| !storage.deleted.get(idx))
+        })
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        let Some(storage) = &self.storage else {
+            return vec![];
+        };
+        let mut files = vec![self.path.join(STATS_FILE)];
+        files.extend(storage.counts_hashes.files());
+        files.extend(storage.counts.files());
+        files.extend(storage.points_map_hashes.files());
+        files.extend(storage.points_map.files());
+        files.extend(storage.point_to_values.files());
+        files.extend(storage.deleted.files());
+        files
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        let db_flusher = self.db_wrapper.flusher();
+        let deleted_flusher = self
+            .storage
+            .as_ref()
+            .map(|storage| storage.deleted.flusher());
+        Box::new(move || {
+            if let Some(deleted_flusher) = deleted_flusher {
+                deleted_flusher()?;
+            }
+            db_flusher()
+        })
+    }
+
+    /// Remove the on-disk files of the index, they are rebuilt from RocksDB on the next load
+    pub fn clear_files(&self) -> OperationResult<()> {
+        if self.path.exists() {
+            remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+}
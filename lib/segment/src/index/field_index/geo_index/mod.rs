@@ -1,24 +1,28 @@
 pub mod immutable_geo_index;
+pub mod mmap_geo_index;
 pub mod mutable_geo_index;
 
 use std::cmp::{max, min};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
+use geohash::Direction;
 use itertools::Itertools;
 use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
 
 use self::immutable_geo_index::ImmutableGeoMapIndex;
+use self::mmap_geo_index::MmapGeoMapIndex;
 use self::mutable_geo_index::MutableGeoMapIndex;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
 use crate::index::field_index::geo_hash::{
-    circle_hashes, common_hash_prefix, geo_hash_to_box, polygon_hashes, polygon_hashes_estimation,
-    rectangle_hashes, GeoHash,
+    circle_hashes, common_hash_prefix, geo_hash_to_box, geo_hash_with_neighbor_box, polygon_hashes,
+    polygon_hashes_estimation, rectangle_hashes, GeoHash,
 };
 use crate::index::field_index::stat_tools::estimate_multi_value_selection_cardinality;
 use crate::index::field_index::{
@@ -36,6 +40,7 @@ const GEO_QUERY_MAX_REGION: usize = 12;
 pub enum GeoMapIndex {
     Mutable(MutableGeoMapIndex),
     Immutable(ImmutableGeoMapIndex),
+    Mmap(MmapGeoMapIndex),
 }
 
 impl GeoMapIndex {
@@ -48,10 +53,17 @@ impl GeoMapIndex {
         }
     }
 
+    /// Create an index, which is stored in memory mapped files in `path`
+    pub fn new_mmap(db: Arc<RwLock<DB>>, field: &str, path: &Path) -> Self {
+        let store_cf_name = GeoMapIndex::storage_cf_name(field);
+        GeoMapIndex::Mmap(MmapGeoMapIndex::new(db, &store_cf_name, path))
+    }
+
     fn db_wrapper(&self) -> &DatabaseColumnWrapper {
         match self {
             GeoMapIndex::Mutable(index) => index.db_wrapper(),
             GeoMapIndex::Immutable(index) => index.db_wrapper(),
+            GeoMapIndex::Mmap(index) => index.db_wrapper(),
         }
     }
 
@@ -59,6 +71,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.points_count,
             GeoMapIndex::Immutable(index) => index.points_count,
+            GeoMapIndex::Mmap(index) => index.points_count,
         }
     }
 
@@ -66,6 +79,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.points_values_count,
             GeoMapIndex::Immutable(index) => index.points_values_count,
+            GeoMapIndex::Mmap(index) => index.points_values_count,
         }
     }
 
@@ -78,6 +92,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.max_values_per_point,
             GeoMapIndex::Immutable(index) => index.max_values_per_point,
+            GeoMapIndex::Mmap(index) => index.max_values_per_point,
         }
     }
 
@@ -85,6 +100,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.get_points_of_hash(hash),
             GeoMapIndex::Immutable(index) => index.get_points_of_hash(hash),
+            GeoMapIndex::Mmap(index) => index.get_points_of_hash(hash),
         }
     }
 
@@ -92,6 +108,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.get_values_of_hash(hash),
             GeoMapIndex::Immutable(index) => index.get_values_of_hash(hash),
+            GeoMapIndex::Mmap(index) => index.get_values_of_hash(hash),
         }
     }
 
@@ -100,6 +117,9 @@ impl GeoMapIndex {
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
+        if let GeoMapIndex::Mmap(index) = self {
+            index.clear_files()?;
+        }
         self.db_wrapper().recreate_column_family()
     }
 
@@ -145,13 +165,24 @@ impl GeoMapIndex {
     }
 
     pub fn flusher(&self) -> Flusher {
-        self.db_wrapper().flusher()
+        match self {
+            GeoMapIndex::Mutable(_) | GeoMapIndex::Immutable(_) => self.db_wrapper().flusher(),
+            GeoMapIndex::Mmap(index) => index.flusher(),
+        }
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            GeoMapIndex::Mutable(_) | GeoMapIndex::Immutable(_) => vec![],
+            GeoMapIndex::Mmap(index) => index.files(),
+        }
     }
 
     pub fn get_values(&self, idx: PointOffsetType) -> Option<&[GeoPoint]> {
         match self {
             GeoMapIndex::Mutable(index) => index.get_values(idx),
             GeoMapIndex::Immutable(index) => index.get_values(idx),
+            GeoMapIndex::Mmap(index) => index.get_values(idx),
         }
     }
 
@@ -251,6 +282,12 @@ impl GeoMapIndex {
                     })
                     .unique(),
             ),
+            GeoMapIndex::Mmap(index) => Box::new(
+                values
+                    .into_iter()
+                    .flat_map(|top_geo_hash| index.get_points_of_sub_regions(&top_geo_hash))
+                    .unique(),
+            ),
         }
     }
 
@@ -258,15 +295,21 @@ impl GeoMapIndex {
     fn get_large_hashes(
         &self,
         threshold: usize,
-    ) -> Box<dyn Iterator<Item = (&GeoHash, usize)> + '_> {
+    ) -> Box<dyn Iterator<Item = (GeoHash, usize)> + '_> {
         let filter_condition =
-            |(hash, size): &(&GeoHash, usize)| *size > threshold && !hash.is_empty();
+            |(hash, size): &(GeoHash, usize)| *size > threshold && !hash.is_empty();
         let mut large_regions = match self {
             GeoMapIndex::Mutable(index) => index
                 .get_points_per_hash()
+                .map(|(hash, size)| (hash.clone(), size))
                 .filter(filter_condition)
                 .collect_vec(),
             GeoMapIndex::Immutable(index) => index
+                .get_points_per_hash()
+                .map(|(hash, size)| (hash.clone(), size))
+                .filter(filter_condition)
+                .collect_vec(),
+            GeoMapIndex::Mmap(index) => index
                 .get_points_per_hash()
                 .filter(filter_condition)
                 .collect_vec(),
@@ -309,6 +352,9 @@ impl ValueIndexer<GeoPoint> for GeoMapIndex {
             GeoMapIndex::Immutable(_) => Err(OperationError::service_error(
                 "Can't add values to immutable geo index",
             )),
+            GeoMapIndex::Mmap(_) => Err(OperationError::service_error(
+                "Can't add values to mmap geo index",
+            )),
         }
     }
 
@@ -331,6 +377,7 @@ impl ValueIndexer<GeoPoint> for GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.remove_point(id),
             GeoMapIndex::Immutable(index) => index.remove_point(id),
+            GeoMapIndex::Mmap(index) => index.remove_point(id),
         }
     }
 }
@@ -344,10 +391,14 @@ impl PayloadFieldIndex for GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.load(),
             GeoMapIndex::Immutable(index) => index.load(),
+            GeoMapIndex::Mmap(index) => index.load(),
         }
     }
 
     fn clear(self) -> OperationResult<()> {
+        if let GeoMapIndex::Mmap(index) = &self {
+            index.clear_files()?;
+        }
         self.db_wrapper().remove_column_family()
     }
 
@@ -459,16 +510,58 @@ impl PayloadFieldIndex for GeoMapIndex {
         threshold: usize,
         key: PayloadKeyType,
     ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
-        Box::new(
-            self.get_large_hashes(threshold)
-                .map(move |(geo_hash, size)| PayloadBlockCondition {
-                    condition: FieldCondition::new_geo_bounding_box(
-                        key.clone(),
-                        geo_hash_to_box(geo_hash),
-                    ),
-                    cardinality: size,
-                }),
-        )
+        let edge_regions = self.get_large_hashes(threshold).collect_vec();
+
+        // Regions one level up, so searches over an area larger than a single edge region
+        // stay within one block
+        let parent_regions = edge_regions
+            .iter()
+            .map(|(geo_hash, _)| GeoHash::from(&geo_hash[..geo_hash.len() - 1]))
+            .filter(|parent| !parent.is_empty())
+            .unique()
+            .map(|parent| {
+                let size = self.get_points_of_hash(&parent);
+                (parent, size)
+            })
+            .collect_vec();
+
+        let mut blocks = edge_regions
+            .iter()
+            .chain(&parent_regions)
+            .map(|(geo_hash, size)| PayloadBlockCondition {
+                condition: FieldCondition::new_geo_bounding_box(
+                    key.clone(),
+                    geo_hash_to_box(geo_hash),
+                ),
+                cardinality: *size,
+            })
+            .collect_vec();
+
+        // Blocks across the eastern and northern borders of edge regions, so searches around
+        // a border don't jump between blocks, which are not connected with each other
+        for (geo_hash, _) in &edge_regions {
+            for direction in [Direction::E, Direction::N] {
+                let Some((neighbor_hash, bounding_box)) =
+                    geo_hash_with_neighbor_box(geo_hash, direction)
+                else {
+                    continue;
+                };
+                if self.get_points_of_hash(&neighbor_hash) == 0 {
+                    continue;
+                }
+                let condition = FieldCondition::new_geo_bounding_box(key.clone(), bounding_box);
+                let Ok(points) = self.filter(&condition) else {
+                    continue;
+                };
+                let cardinality = points.count();
+                blocks.push(PayloadBlockCondition {
+                    condition,
+                    cardinality,
+                });
+            }
+        }
+
+        Box::new(blocks.into_iter())
     }
 }
 
@@ -873,6 +966,26 @@ mod tests {
         });
     }
 
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn test_payload_blocks_across_borders(#[case] is_appendable: bool) {
+        let field_index = build_random_index(1000, 1, is_appendable);
+        let threshold = 10;
+        let edge_regions = field_index.get_large_hashes(threshold).collect_vec();
+        assert!(!edge_regions.is_empty());
+
+        let blocks = field_index
+            .payload_blocks(threshold, path("test"))
+            .collect_vec();
+        // Edge regions and blocks across their borders
+        assert!(blocks.len() > edge_regions.len());
+        for block in &blocks {
+            let block_points = field_index.filter(&block.condition).unwrap().collect_vec();
+            assert_eq!(block_points.len(), block.cardinality);
+        }
+    }
+
     #[test]
     fn match_cardinality_point_with_multi_far_geo_payload() {
         let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
@@ -1233,4 +1346,82 @@ mod tests {
         // Only LOS_ANGELES is in the bounding box
         assert_eq!(point_offsets, vec![2]);
     }
+
+    #[test]
+    fn test_mmap_index_matches_immutable() {
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+
+        let mut rnd = StdRng::seed_from_u64(42);
+        let mut index = GeoMapIndex::new(db.clone(), FIELD_NAME, true);
+        index.recreate().unwrap();
+        for idx in 0..1000 {
+            let geo_points = random_geo_payload(&mut rnd, 1..=3);
+            index
+                .add_point(idx as PointOffsetType, &[&Value::Array(geo_points)])
+                .unwrap();
+        }
+        index.flusher()().unwrap();
+        drop(index);
+
+        let mmap_path = temp_dir.path().join("mmap_index");
+        let mut immutable_index = GeoMapIndex::new(db.clone(), FIELD_NAME, false);
+        assert!(immutable_index.load().unwrap());
+        let mut mmap_index = GeoMapIndex::new_mmap(db.clone(), FIELD_NAME, &mmap_path);
+        assert!(mmap_index.load().unwrap());
+        assert!(!mmap_index.files().is_empty());
+
+        let geo_radius = GeoRadius {
+            center: BERLIN,
+            radius: 2_000_000.0,
+        };
+        let condition = condition_for_geo_radius("test", geo_radius);
+
+        let check = |immutable_index: &GeoMapIndex, mmap_index: &GeoMapIndex| {
+            assert_eq!(immutable_index.points_count(), mmap_index.points_count());
+            assert_eq!(
+                immutable_index.points_values_count(),
+                mmap_index.points_values_count()
+            );
+            for idx in 0..1000 {
+                assert_eq!(immutable_index.get_values(idx), mmap_index.get_values(idx));
+            }
+            let immutable_points = immutable_index
+                .filter(&condition)
+                .unwrap()
+                .sorted()
+                .collect_vec();
+            let mmap_points = mmap_index
+                .filter(&condition)
+                .unwrap()
+                .sorted()
+                .collect_vec();
+            assert!(!mmap_points.is_empty());
+            assert_eq!(immutable_points, mmap_points);
+            let immutable_card = immutable_index.estimate_cardinality(&condition).unwrap();
+            let mmap_card = mmap_index.estimate_cardinality(&condition).unwrap();
+            assert_eq!(immutable_card.min, mmap_card.min);
+            assert_eq!(immutable_card.exp, mmap_card.exp);
+            assert_eq!(immutable_card.max, mmap_card.max);
+            assert_eq!(
+                immutable_index.get_large_hashes(50).collect_vec(),
+                mmap_index.get_large_hashes(50).collect_vec()
+            );
+        };
+
+        check(&immutable_index, &mmap_index);
+
+        for idx in (0..1000).step_by(7) {
+            immutable_index.remove_point(idx).unwrap();
+            mmap_index.remove_point(idx).unwrap();
+        }
+        check(&immutable_index, &mmap_index);
+
+        // Removed points should be excluded after reload as well
+        mmap_index.flusher()().unwrap();
+        drop(mmap_index);
+        let mut mmap_index = GeoMapIndex::new_mmap(db, FIELD_NAME, &mmap_path);
+        assert!(mmap_index.load().unwrap());
+        check(&immutable_index, &mmap_index);
+    }
 }
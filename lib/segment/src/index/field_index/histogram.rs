@@ -5,18 +5,20 @@ use std::ops::Bound;
 
 use itertools::Itertools;
 use num_traits::{Num, Signed};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
 use crate::index::field_index::utils::check_boundaries;
 
 const MIN_BUCKET_SIZE: usize = 10;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Counts {
     pub left: usize,
     pub right: usize,
 }
 
-#[derive(PartialEq, PartialOrd, Debug, Clone)]
+#[derive(PartialEq, PartialOrd, Debug, Clone, Serialize, Deserialize)]
 pub struct Point<T> {
     pub val: T,
     pub idx: usize,
@@ -35,7 +37,9 @@ impl<T: PartialOrd + Copy> Ord for Point<T> {
 
 /// A trait that should represent common properties of integer and floating point types.
 /// In particular, i64 and f64.
-pub trait Numericable: Num + Signed + PartialEq + PartialOrd + Copy {
+pub trait Numericable:
+    Num + Signed + PartialEq + PartialOrd + Copy + Serialize + DeserializeOwned
+{
     fn min_value() -> Self;
     fn max_value() -> Self;
     fn to_f64(self) -> f64;
@@ -110,7 +114,7 @@ impl Numericable for f64 {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Histogram<T: Numericable + PartialEq + PartialOrd + Copy> {
     max_bucket_size: usize,
     precision: f64,
@@ -1,86 +1,193 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
 
 use parking_lot::RwLock;
 use rocksdb::DB;
 
 use super::binary_index::BinaryIndex;
+use crate::data_types::text_index::TextIndexParams;
 use crate::index::field_index::full_text_index::text_index::FullTextIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
+use crate::index::field_index::histogram::Numericable;
 use crate::index::field_index::map_index::MapIndex;
-use crate::index::field_index::numeric_index::NumericIndex;
+use crate::index::field_index::numeric_index::{Encodable, NumericIndex};
 use crate::index::field_index::FieldIndex;
 use crate::json_path::JsonPath;
-use crate::types::{
-    FloatPayloadType, IntPayloadType, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType,
-};
+use crate::types::{PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType};
+
+/// Directory for the on-disk data of the indexes of a given field.
+///
+/// Field name is escaped, so that any JSON path results in a valid and unique directory name.
+pub fn field_index_dir(path: &Path, field: &JsonPath) -> PathBuf {
+    let mut dir_name = String::new();
+    for byte in field.to_string().bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-' {
+            dir_name.push(byte as char);
+        } else {
+            dir_name.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    path.join(dir_name)
+}
+
+/// Whether indexes of the field are stored in memory mapped files instead of RAM.
+///
+/// On-disk indexes are immutable, so they are only used in non-appendable segments.
+/// Tenant indexes are always stored on disk, as tenants are usually queried one at a time,
+/// and only a small part of the index is required in RAM.
+pub fn is_on_disk_index(payload_schema: &PayloadFieldSchema, is_appendable: bool) -> bool {
+    (payload_schema.is_tenant() || payload_schema.is_on_disk()) && !is_appendable
+}
 
 /// Selects index types based on field type
+///
+/// `path` is a directory for the indexes, which store their data in files instead of RocksDB.
 pub fn index_selector(
     field: &JsonPath,
     payload_schema: &PayloadFieldSchema,
     db: Arc<RwLock<DB>>,
+    path: &Path,
     is_appendable: bool,
 ) -> Vec<FieldIndex> {
+    let use_mmap = is_on_disk_index(payload_schema, is_appendable);
+    let field_dir = field_index_dir(path, field);
+
     let field: String = field.to_string();
     let field = field.as_str();
 
+    let map_dir = use_mmap.then(|| field_dir.join("map"));
+    let map_dir = map_dir.as_deref();
+    let numeric_dir = use_mmap.then(|| field_dir.join("numeric"));
+    let numeric_dir = numeric_dir.as_deref();
+    let geo_index = |db: Arc<RwLock<DB>>| {
+        if use_mmap {
+            GeoMapIndex::new_mmap(db, field, &field_dir.join("geo"))
+        } else {
+            GeoMapIndex::new(db, field, is_appendable)
+        }
+    };
+    let text_index = |db: Arc<RwLock<DB>>, params: TextIndexParams| {
+        if use_mmap {
+            FullTextIndex::new_mmap(db, params, field, &field_dir.join("text"))
+        } else {
+            FullTextIndex::new(db, params, field, is_appendable)
+        }
+    };
+    let bool_index = |db: Arc<RwLock<DB>>| {
+        if use_mmap {
+            BinaryIndex::new_mmap(db, field, &field_dir.join("bool"))
+        } else {
+            BinaryIndex::new(db, field)
+        }
+    };
+
     match payload_schema {
         PayloadFieldSchema::FieldType(payload_type) => match payload_type {
-            PayloadSchemaType::Keyword => {
-                vec![FieldIndex::KeywordIndex(MapIndex::new(
-                    db,
-                    field,
-                    is_appendable,
-                ))]
-            }
+            PayloadSchemaType::Keyword => vec![FieldIndex::KeywordIndex(map_index(
+                db,
+                field,
+                map_dir,
+                is_appendable,
+            ))],
             PayloadSchemaType::Integer => vec![
-                FieldIndex::IntMapIndex(MapIndex::new(db.clone(), field, is_appendable)),
-                FieldIndex::IntIndex(NumericIndex::<IntPayloadType>::new(
-                    db,
-                    field,
-                    is_appendable,
-                )),
+                FieldIndex::IntMapIndex(map_index(db.clone(), field, map_dir, is_appendable)),
+                FieldIndex::IntIndex(numeric_index(db, field, numeric_dir, is_appendable)),
             ],
-            PayloadSchemaType::Float => {
-                vec![FieldIndex::FloatIndex(
-                    NumericIndex::<FloatPayloadType>::new(db, field, is_appendable),
-                )]
+            PayloadSchemaType::Float => vec![FieldIndex::FloatIndex(numeric_index(
+                db,
+                field,
+                numeric_dir,
+                is_appendable,
+            ))],
+            PayloadSchemaType::Geo => vec![FieldIndex::GeoIndex(geo_index(db))],
+            PayloadSchemaType::Text => {
+                vec![FieldIndex::FullTextIndex(text_index(
+                    db,
+                    Default::default(),
+                ))]
             }
-            PayloadSchemaType::Geo => vec![FieldIndex::GeoIndex(GeoMapIndex::new(
+            PayloadSchemaType::Bool => vec![FieldIndex::BinaryIndex(bool_index(db))],
+            PayloadSchemaType::Datetime => vec![FieldIndex::DatetimeIndex(numeric_index(
                 db,
                 field,
+                numeric_dir,
                 is_appendable,
             ))],
-            PayloadSchemaType::Text => vec![FieldIndex::FullTextIndex(FullTextIndex::new(
+            PayloadSchemaType::Uuid => vec![FieldIndex::UuidMapIndex(map_index(
                 db,
-                Default::default(),
                 field,
+                map_dir,
                 is_appendable,
             ))],
-            PayloadSchemaType::Bool => vec![FieldIndex::BinaryIndex(BinaryIndex::new(db, field))],
-            PayloadSchemaType::Datetime => {
-                vec![FieldIndex::DatetimeIndex(
-                    NumericIndex::<IntPayloadType>::new(db, field, is_appendable),
-                )]
-            }
         },
         PayloadFieldSchema::FieldParams(payload_params) => match payload_params {
             PayloadSchemaParams::Text(text_index_params) => vec![FieldIndex::FullTextIndex(
-                FullTextIndex::new(db, text_index_params.clone(), field, is_appendable),
+                text_index(db, text_index_params.clone()),
             )],
             PayloadSchemaParams::Integer(integer_params) => {
                 let lookup = integer_params.lookup.then(|| {
-                    FieldIndex::IntMapIndex(MapIndex::new(db.clone(), field, is_appendable))
+                    FieldIndex::IntMapIndex(map_index(db.clone(), field, map_dir, is_appendable))
                 });
                 let range = integer_params.range.then(|| {
-                    FieldIndex::IntIndex(NumericIndex::<IntPayloadType>::new(
-                        db,
-                        field,
-                        is_appendable,
-                    ))
+                    FieldIndex::IntIndex(numeric_index(db, field, numeric_dir, is_appendable))
                 });
                 lookup.into_iter().chain(range).collect()
             }
+            PayloadSchemaParams::Keyword(_) => vec![FieldIndex::KeywordIndex(map_index(
+                db,
+                field,
+                map_dir,
+                is_appendable,
+            ))],
+            PayloadSchemaParams::Float(_) => vec![FieldIndex::FloatIndex(numeric_index(
+                db,
+                field,
+                numeric_dir,
+                is_appendable,
+            ))],
+            PayloadSchemaParams::Geo(_) => vec![FieldIndex::GeoIndex(geo_index(db))],
+            PayloadSchemaParams::Bool(_) => vec![FieldIndex::BinaryIndex(bool_index(db))],
+            PayloadSchemaParams::Datetime(_) => {
+                vec![FieldIndex::DatetimeIndex(numeric_index(
+                    db,
+                    field,
+                    numeric_dir,
+                    is_appendable,
+                ))]
+            }
+            PayloadSchemaParams::Uuid(_) => vec![FieldIndex::UuidMapIndex(map_index(
+                db,
+                field,
+                map_dir,
+                is_appendable,
+            ))],
         },
     }
 }
+
+fn map_index<N: Hash + Eq + Clone + Display + FromStr + Default>(
+    db: Arc<RwLock<DB>>,
+    field: &str,
+    mmap_dir: Option<&Path>,
+    is_appendable: bool,
+) -> MapIndex<N> {
+    match mmap_dir {
+        Some(dir) => MapIndex::new_mmap(db, field, dir),
+        None => MapIndex::new(db, field, is_appendable),
+    }
+}
+
+fn numeric_index<T: Encodable + Numericable + Default>(
+    db: Arc<RwLock<DB>>,
+    field: &str,
+    mmap_dir: Option<&Path>,
+    is_appendable: bool,
+) -> NumericIndex<T> {
+    match mmap_dir {
+        Some(dir) => NumericIndex::new_mmap(db, field, dir),
+        None => NumericIndex::new(db, field, is_appendable),
+    }
+}
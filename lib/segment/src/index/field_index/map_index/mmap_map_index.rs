@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::{create_dir_all, remove_dir_all};
+use std::hash::Hash;
+use std::iter;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
+use parking_lot::RwLock;
+use rocksdb::DB;
+
+use super::mutable_map_index::MutableMapIndex;
+use super::MapIndex;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
+use crate::index::field_index::mmap_flags::MmapFlags;
+use crate::index::field_index::mmap_point_to_values::MmapPointToValues;
+
+const VALUES_FILE: &str = "values.json";
+const DELETED_FILE: &str = "deleted.bin";
+const POINT_TO_VALUES: &str = "point_to_values";
+const VALUE_TO_POINTS: &str = "value_to_points";
+
+/// Map index, which keeps both points-to-values and values-to-points maps in memory mapped files.
+/// Only unique values and their counts are kept in RAM.
+///
+/// Files are built from the RocksDB records on the first load, after that the index is read-only,
+/// except for point removal.
+pub struct MmapMapIndex<N: Hash + Eq + Clone + Display + FromStr + Default> {
+    path: PathBuf,
+    storage: Option<Storage>,
+    /// Unique values, position of the value is its id in the mmap files
+    values: Vec<N>,
+    value_ids: HashMap<N, u32>,
+    /// Amount of not removed points for each value id
+    value_points_count: Vec<usize>,
+    /// Amount of point which have at least one indexed payload value
+    indexed_points: usize,
+    values_count: usize,
+    db_wrapper: DatabaseColumnWrapper,
+}
+
+struct Storage {
+    value_to_points: MmapPointToValues<PointOffsetType>,
+    point_to_values: MmapPointToValues<u32>,
+    deleted: MmapFlags,
+}
+
+impl<N: Hash + Eq + Clone + Display + FromStr + Default> MmapMapIndex<N> {
+    pub fn new(db: Arc<RwLock<DB>>, field_name: &str, path: &Path) -> Self {
+        let store_cf_name = MapIndex::<N>::storage_cf_name(field_name);
+        let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
+        Self {
+            path: path.to_owned(),
+            storage: None,
+            values: Vec::new(),
+            value_ids: HashMap::new(),
+            value_points_count: Vec::new(),
+            indexed_points: 0,
+            values_count: 0,
+            db_wrapper,
+        }
+    }
+
+    pub fn get_db_wrapper(&self) -> &DatabaseColumnWrapper {
+        &self.db_wrapper
+    }
+
+    fn files_exist(path: &Path) -> bool {
+        path.join(VALUES_FILE).exists()
+            && path.join(DELETED_FILE).exists()
+            && MmapPointToValues::<u32>::exists(path, POINT_TO_VALUES)
+            && MmapPointToValues::<PointOffsetType>::exists(path, VALUE_TO_POINTS)
+    }
+
+    pub fn load_from_db(&mut self) -> OperationResult<bool> {
+        if !self.db_wrapper.has_column_family()? {
+            return Ok(false);
+        }
+
+        if !Self::files_exist(&self.path) {
+            // To avoid code duplication, use `MutableMapIndex` to load data from db
+            // and convert it into the on-disk representation
+            let mut mutable = MutableMapIndex {
+                map: Default::default(),
+                point_to_values: Vec::new(),
+                indexed_points: 0,
+                values_count: 0,
+                db_wrapper: self.db_wrapper.clone(),
+            };
+            if !mutable.load_from_db()? {
+                return Ok(false);
+            }
+            Self::build_files(&self.path, mutable)?;
+        }
+
+        self.open_files()?;
+        Ok(true)
+    }
+
+    fn build_files(path: &Path, mutable: MutableMapIndex<N>) -> OperationResult<()> {
+        let MutableMapIndex {
+            map,
+            point_to_values,
+            ..
+        } = mutable;
+
+        create_dir_all(path)?;
+
+        let (values, value_to_points): (Vec<_>, Vec<_>) = map
+            .into_iter()
+            .map(|(value, points)| (value, points.into_iter().collect::<Vec<_>>()))
+            .unzip();
+
+        let value_ids: HashMap<&N, u32> = values
+            .iter()
+            .enumerate()
+            .map(|(value_id, value)| (value, value_id as u32))
+            .collect();
+
+        MmapPointToValues::<PointOffsetType>::create(path, VALUE_TO_POINTS, &value_to_points)?;
+        MmapPointToValues::<u32>::create(
+            path,
+            POINT_TO_VALUES,
+            point_to_values.iter().map(|point_values| {
+                point_values
+                    .iter()
+                    .map(|value| value_ids[value])
+                    .collect::<Vec<_>>()
+            }),
+        )?;
+
+        MmapFlags::create(&path.join(DELETED_FILE), point_to_values.len(), [])?;
+
+        // Values are written last, so incomplete files are never considered as built
+        let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+        atomic_save_json(&path.join(VALUES_FILE), &values)?;
+
+        Ok(())
+    }
+
+    fn open_files(&mut self) -> OperationResult<()> {
+        let values: Vec<String> = read_json(&self.path.join(VALUES_FILE))?;
+        let values = values
+            .iter()
+            .map(|value| {
+                N::from_str(value).map_err(|_| {
+                    OperationError::service_error("Index load error: wrong value format")
+                })
+            })
+            .collect::<OperationResult<Vec<N>>>()?;
+
+        let value_to_points = MmapPointToValues::open(&self.path, VALUE_TO_POINTS)?;
+        let point_to_values = MmapPointToValues::open(&self.path, POINT_TO_VALUES)?;
+
+        let deleted = MmapFlags::open(&self.path.join(DELETED_FILE))?;
+
+        self.value_points_count = (0..values.len() as u32)
+            .map(|value_id| value_to_points.get_values(value_id).map_or(0, |p| p.len()))
+            .collect();
+        self.indexed_points = (0..point_to_values.len() as PointOffsetType)
+            .filter(|&idx| {
+                point_to_values
+                    .get_values(idx)
+                    .is_some_and(|v| !v.is_empty())
+            })
+            .count();
+        self.values_count = point_to_values.values_count();
+
+        // Exclude points, which were removed after the files were built
+        for idx in deleted.iter_ones() {
+            let Some(value_ids) = point_to_values.get_values(idx) else {
+                continue;
+            };
+            if !value_ids.is_empty() {
+                self.indexed_points -= 1;
+            }
+            self.values_count -= value_ids.len();
+            for &value_id in value_ids {
+                self.value_points_count[value_id as usize] -= 1;
+            }
+        }
+
+        self.value_ids = values
+            .iter()
+            .enumerate()
+            .map(|(value_id, value)| (value.clone(), value_id as u32))
+            .collect();
+        self.values = values;
+        self.storage = Some(Storage {
+            value_to_points,
+            point_to_values,
+            deleted,
+        });
+
+        Ok(())
+    }
+
+    fn is_deleted(storage: &Storage, idx: PointOffsetType) -> bool {
+        storage.deleted.get(idx)
+    }
+
+    pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        let Some(storage) = &mut self.storage else {
+            return Ok(());
+        };
+        if Self::is_deleted(storage, idx) {
+            return Ok(());
+        }
+        let Some(value_ids) = storage.point_to_values.get_values(idx) else {
+            return Ok(());
+        };
+
+        if !value_ids.is_empty() {
+            self.indexed_points -= 1;
+        }
+        self.values_count -= value_ids.len();
+
+        for &value_id in value_ids {
+            self.value_points_count[value_id as usize] -= 1;
+            let key = MapIndex::encode_db_record(&self.values[value_id as usize], idx);
+            self.db_wrapper.remove(key)?;
+        }
+
+        storage.deleted.set(idx, true);
+        Ok(())
+    }
+
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<Box<dyn Iterator<Item = &N> + '_>> {
+        let storage = self.storage.as_ref()?;
+        let value_ids = storage.point_to_values.get_values(idx)?;
+        if Self::is_deleted(storage, idx) {
+            return Some(Box::new(iter::empty()));
+        }
+        Some(Box::new(
+            value_ids
+                .iter()
+                .map(|&value_id| &self.values[value_id as usize]),
+        ))
+    }
+
+    pub fn values_count(&self, idx: PointOffsetType) -> usize {
+        let Some(storage) = &self.storage else {
+            return 0;
+        };
+        if Self::is_deleted(storage, idx) {
+            return 0;
+        }
+        storage
+            .point_to_values
+            .get_values(idx)
+            .map_or(0, |value_ids| value_ids.len())
+    }
+
+    pub fn get_indexed_points(&self) -> usize {
+        self.indexed_points
+    }
+
+    pub fn get_values_count(&self) -> usize {
+        self.values_count
+    }
+
+    pub fn get_unique_values_count(&self) -> usize {
+        self.value_points_count
+            .iter()
+            .filter(|&&count| count > 0)
+            .count()
+    }
+
+    pub fn get_points_with_value_count<Q>(&self, value: &Q) -> Option<usize>
+    where
+        Q: ?Sized,
+        N: std::borrow::Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        let value_id = *self.value_ids.get(value)?;
+        Some(self.value_points_count[value_id as usize])
+    }
+
+    pub fn get_iterator<Q>(&self, value: &Q) -> Box<dyn Iterator<Item = PointOffsetType> + '_>
+    where
+        Q: ?Sized,
+        N: std::borrow::Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        let points = self.storage.as_ref().and_then(|storage| {
+            let value_id = *self.value_ids.get(value)?;
+            let points = storage.value_to_points.get_values(value_id)?;
+            Some(
+                points
+                    .iter()
+                    .copied()
+                    .filter(move |&idx| !Self::is_deleted(storage, idx)),
+            )
+        });
+
+        match points {
+            Some(points) => Box::new(points),
+            None => Box::new(iter::empty::<PointOffsetType>()),
+        }
+    }
+
+    pub fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N> + '_> {
+        Box::new(
+            self.values
+                .iter()
+                .zip(&self.value_points_count)
+                .filter(|(_, &count)| count > 0)
+                .map(|(value, _)| value),
+        )
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        let Some(storage) = &self.storage else {
+            return vec![];
+        };
+        let mut files = vec![self.path.join(VALUES_FILE)];
+        files.extend(storage.deleted.files());
+        files.extend(storage.value_to_points.files());
+        files.extend(storage.point_to_values.files());
+        files
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        let db_flusher = self.db_wrapper.flusher();
+        let deleted_flusher = self
+            .storage
+            .as_ref()
+            .map(|storage| storage.deleted.flusher());
+        Box::new(move || {
+            if let Some(deleted_flusher) = deleted_flusher {
+                deleted_flusher()?;
+            }
+            db_flusher()
+        })
+    }
+
+    /// Remove the on-disk files of the index, they are rebuilt from RocksDB on the next load
+    pub fn clear_files(&self) -> OperationResult<()> {
+        if self.path.exists() {
+            remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+}
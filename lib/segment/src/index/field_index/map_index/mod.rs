@@ -1,8 +1,10 @@
 pub mod immutable_map_index;
+pub mod mmap_map_index;
 pub mod mutable_map_index;
 
 use std::fmt::Display;
 use std::hash::{BuildHasher, Hash};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -10,11 +12,13 @@ use common::types::PointOffsetType;
 use immutable_map_index::ImmutableMapIndex;
 use indexmap::IndexSet;
 use itertools::Itertools;
+use mmap_map_index::MmapMapIndex;
 use mutable_map_index::MutableMapIndex;
 use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
 use smol_str::SmolStr;
+use uuid::Uuid;
 
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
@@ -27,12 +31,27 @@ use crate::index::query_estimator::combine_should_estimations;
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
     AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept, MatchValue,
-    PayloadKeyType, ValueVariants,
+    PayloadKeyType, UuidIntType, ValueVariants,
 };
 
+/// Parse a string into the representation used by uuid indexes.
+/// Returns `None` if the string is not a valid UUID.
+pub fn parse_uuid(value: &str) -> Option<UuidIntType> {
+    Uuid::parse_str(value).ok().map(|uuid| uuid.as_u128())
+}
+
+/// Parse all strings as UUIDs, or return `None` if any of them is not a valid UUID
+pub fn parse_uuids<'a, C>(values: impl IntoIterator<Item = &'a String>) -> Option<C>
+where
+    C: FromIterator<UuidIntType>,
+{
+    values.into_iter().map(|value| parse_uuid(value)).collect()
+}
+
 pub enum MapIndex<N: Hash + Eq + Clone + Display + FromStr + Default> {
     Mutable(MutableMapIndex<N>),
     Immutable(ImmutableMapIndex<N>),
+    Mmap(MmapMapIndex<N>),
 }
 
 impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
@@ -44,10 +63,17 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
+    /// Create index, which keeps its data in memory mapped files in `path`.
+    /// Such index is read-only, so it is only used in non-appendable segments.
+    pub fn new_mmap(db: Arc<RwLock<DB>>, field_name: &str, path: &Path) -> Self {
+        MapIndex::Mmap(MmapMapIndex::new(db, field_name, path))
+    }
+
     fn get_db_wrapper(&self) -> &DatabaseColumnWrapper {
         match self {
             MapIndex::Mutable(index) => index.get_db_wrapper(),
             MapIndex::Immutable(index) => index.get_db_wrapper(),
+            MapIndex::Mmap(index) => index.get_db_wrapper(),
         }
     }
 
@@ -55,13 +81,19 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.load_from_db(),
             MapIndex::Immutable(index) => index.load_from_db(),
+            MapIndex::Mmap(index) => index.load_from_db(),
         }
     }
 
-    pub fn get_values(&self, idx: PointOffsetType) -> Option<&[N]> {
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<Box<dyn Iterator<Item = &N> + '_>> {
         match self {
-            MapIndex::Mutable(index) => index.get_values(idx),
-            MapIndex::Immutable(index) => index.get_values(idx),
+            MapIndex::Mutable(index) => index
+                .get_values(idx)
+                .map(|values| Box::new(values.iter()) as Box<dyn Iterator<Item = &N> + '_>),
+            MapIndex::Immutable(index) => index
+                .get_values(idx)
+                .map(|values| Box::new(values.iter()) as Box<dyn Iterator<Item = &N> + '_>),
+            MapIndex::Mmap(index) => index.get_values(idx),
         }
     }
 
@@ -69,6 +101,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_indexed_points(),
             MapIndex::Immutable(index) => index.get_indexed_points(),
+            MapIndex::Mmap(index) => index.get_indexed_points(),
         }
     }
 
@@ -76,6 +109,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_values_count(),
             MapIndex::Immutable(index) => index.get_values_count(),
+            MapIndex::Mmap(index) => index.get_values_count(),
         }
     }
 
@@ -83,6 +117,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_unique_values_count(),
             MapIndex::Immutable(index) => index.get_unique_values_count(),
+            MapIndex::Mmap(index) => index.get_unique_values_count(),
         }
     }
 
@@ -95,6 +130,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_points_with_value_count(value),
             MapIndex::Immutable(index) => index.get_points_with_value_count(value),
+            MapIndex::Mmap(index) => index.get_points_with_value_count(value),
         }
     }
 
@@ -107,6 +143,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_iterator(value),
             MapIndex::Immutable(index) => index.get_iterator(value),
+            MapIndex::Mmap(index) => index.get_iterator(value),
         }
     }
 
@@ -114,19 +151,48 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_values_iterator(),
             MapIndex::Immutable(index) => index.get_values_iterator(),
+            MapIndex::Mmap(index) => index.get_values_iterator(),
         }
     }
 
+    /// Iterate over all indexed values, along with the amount of points having each of them
+    pub fn iter_counts_per_value(&self) -> impl Iterator<Item = (&N, usize)> + '_ {
+        self.get_values_iterator()
+            .map(|value| (value, self.get_points_with_value_count(value).unwrap_or(0)))
+    }
+
+    /// Iterate over all indexed values, along with the points having each of them
+    pub fn iter_values_map(
+        &self,
+    ) -> impl Iterator<Item = (&N, Box<dyn Iterator<Item = PointOffsetType> + '_>)> + '_ {
+        self.get_values_iterator()
+            .map(|value| (value, self.get_iterator(value)))
+    }
+
     pub fn storage_cf_name(field: &str) -> String {
         format!("{field}_map")
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
+        if let MapIndex::Mmap(index) = self {
+            index.clear_files()?;
+        }
         self.get_db_wrapper().recreate_column_family()
     }
 
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            MapIndex::Mutable(_) | MapIndex::Immutable(_) => vec![],
+            MapIndex::Mmap(index) => index.files(),
+        }
+    }
+
     fn flusher(&self) -> Flusher {
-        self.get_db_wrapper().flusher()
+        match self {
+            MapIndex::Mutable(index) => index.get_db_wrapper().flusher(),
+            MapIndex::Immutable(index) => index.get_db_wrapper().flusher(),
+            MapIndex::Mmap(index) => index.flusher(),
+        }
     }
 
     fn match_cardinality<Q>(&self, value: &Q) -> CardinalityEstimation
@@ -171,13 +237,15 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
     }
 
     pub fn values_count(&self, point_id: PointOffsetType) -> usize {
-        self.get_values(point_id).map(|x| x.len()).unwrap_or(0)
+        match self {
+            MapIndex::Mutable(index) => index.get_values(point_id).map_or(0, |x| x.len()),
+            MapIndex::Immutable(index) => index.get_values(point_id).map_or(0, |x| x.len()),
+            MapIndex::Mmap(index) => index.values_count(point_id),
+        }
     }
 
     pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
-        self.get_values(point_id)
-            .map(|x| x.is_empty())
-            .unwrap_or(true)
+        self.values_count(point_id) == 0
     }
 
     /// Estimates cardinality for `except` clause
@@ -321,7 +389,7 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        self.recreate()
     }
 
     fn flusher(&self) -> Flusher {
@@ -435,7 +503,7 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        self.recreate()
     }
 
     fn flusher(&self) -> Flusher {
@@ -542,6 +610,135 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
     }
 }
 
+/// Uuid index only contains values which are valid UUIDs.
+/// Conditions with any other string can't be resolved by this index, so we return an error,
+/// which makes the caller fall back to checking the payload directly.
+///
+/// For the same reason `Match::Except` is never resolved by this index: a point which has a
+/// string, that is not a valid UUID, matches the condition, but it is not known to the index.
+impl PayloadFieldIndex for MapIndex<UuidIntType> {
+    fn count_indexed_points(&self) -> usize {
+        self.get_indexed_points()
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        self.load_from_db()
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        self.recreate()
+    }
+
+    fn flusher(&self) -> Flusher {
+        MapIndex::flusher(self)
+    }
+
+    fn filter<'a>(
+        &'a self,
+        condition: &'a FieldCondition,
+    ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => match parse_uuid(keyword) {
+                Some(uuid) => Ok(self.get_iterator(&uuid)),
+                None => Err(OperationError::service_error("failed to filter")),
+            },
+            Some(Match::Any(MatchAny { any: any_variant })) => match any_variant {
+                AnyVariants::Keywords(keywords) => match parse_uuids::<Vec<_>>(keywords) {
+                    Some(uuids) => Ok(Box::new(
+                        uuids
+                            .into_iter()
+                            .flat_map(|uuid| self.get_iterator(&uuid))
+                            .unique(),
+                    )),
+                    None => Err(OperationError::service_error("failed to filter")),
+                },
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Ok(Box::new(vec![].into_iter()))
+                    } else {
+                        Err(OperationError::service_error(
+                            "failed to estimate cardinality",
+                        ))
+                    }
+                }
+            },
+            _ => Err(OperationError::service_error("failed to filter")),
+        }
+    }
+
+    fn estimate_cardinality(
+        &self,
+        condition: &FieldCondition,
+    ) -> OperationResult<CardinalityEstimation> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => {
+                let uuid = parse_uuid(keyword).ok_or_else(|| {
+                    OperationError::service_error("failed to estimate cardinality")
+                })?;
+                let mut estimation = self.match_cardinality(&uuid);
+                estimation
+                    .primary_clauses
+                    .push(PrimaryCondition::Condition(condition.clone()));
+                Ok(estimation)
+            }
+            Some(Match::Any(MatchAny { any: any_variant })) => match any_variant {
+                AnyVariants::Keywords(keywords) => {
+                    let uuids: Vec<_> = parse_uuids(keywords).ok_or_else(|| {
+                        OperationError::service_error("failed to estimate cardinality")
+                    })?;
+                    let estimations = uuids
+                        .iter()
+                        .map(|uuid| self.match_cardinality(uuid))
+                        .collect::<Vec<_>>();
+                    let estimation = if estimations.is_empty() {
+                        CardinalityEstimation::exact(0)
+                    } else {
+                        combine_should_estimations(&estimations, self.get_indexed_points())
+                    };
+                    Ok(estimation
+                        .with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+                }
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Ok(CardinalityEstimation::exact(0)
+                            .with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+                    } else {
+                        Err(OperationError::service_error(
+                            "failed to estimate cardinality",
+                        ))
+                    }
+                }
+            },
+            _ => Err(OperationError::service_error(
+                "failed to estimate cardinality",
+            )),
+        }
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        Box::new(
+            self.get_values_iterator()
+                .map(|value| (value, self.get_points_with_value_count(value).unwrap_or(0)))
+                .filter(move |(_value, count)| *count >= threshold)
+                .map(move |(value, count)| PayloadBlockCondition {
+                    condition: FieldCondition::new_match(
+                        key.clone(),
+                        Uuid::from_u128(*value).to_string().into(),
+                    ),
+                    cardinality: count,
+                }),
+        )
+    }
+}
+
 impl ValueIndexer<String> for MapIndex<SmolStr> {
     fn add_many(&mut self, id: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
         match self {
@@ -549,6 +746,9 @@ impl ValueIndexer<String> for MapIndex<SmolStr> {
             MapIndex::Immutable(_) => Err(OperationError::service_error(
                 "Can't add values to immutable map index",
             )),
+            MapIndex::Mmap(_) => Err(OperationError::service_error(
+                "Can't add values to mmap map index",
+            )),
         }
     }
 
@@ -563,6 +763,7 @@ impl ValueIndexer<String> for MapIndex<SmolStr> {
         match self {
             MapIndex::Mutable(index) => index.remove_point(id),
             MapIndex::Immutable(index) => index.remove_point(id),
+            MapIndex::Mmap(index) => index.remove_point(id),
         }
     }
 }
@@ -578,6 +779,9 @@ impl ValueIndexer<IntPayloadType> for MapIndex<IntPayloadType> {
             MapIndex::Immutable(_) => Err(OperationError::service_error(
                 "Can't add values to immutable map index",
             )),
+            MapIndex::Mmap(_) => Err(OperationError::service_error(
+                "Can't add values to mmap map index",
+            )),
         }
     }
 
@@ -592,6 +796,37 @@ impl ValueIndexer<IntPayloadType> for MapIndex<IntPayloadType> {
         match self {
             MapIndex::Mutable(index) => index.remove_point(id),
             MapIndex::Immutable(index) => index.remove_point(id),
+            MapIndex::Mmap(index) => index.remove_point(id),
+        }
+    }
+}
+
+impl ValueIndexer<UuidIntType> for MapIndex<UuidIntType> {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<UuidIntType>) -> OperationResult<()> {
+        match self {
+            MapIndex::Mutable(index) => index.add_many_to_map(id, values),
+            MapIndex::Immutable(_) => Err(OperationError::service_error(
+                "Can't add values to immutable map index",
+            )),
+            MapIndex::Mmap(_) => Err(OperationError::service_error(
+                "Can't add values to mmap map index",
+            )),
+        }
+    }
+
+    /// Strings which are not valid UUIDs are not indexed
+    fn get_value(&self, value: &Value) -> Option<UuidIntType> {
+        if let Value::String(keyword) = value {
+            return parse_uuid(keyword);
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        match self {
+            MapIndex::Mutable(index) => index.remove_point(id),
+            MapIndex::Immutable(index) => index.remove_point(id),
+            MapIndex::Mmap(index) => index.remove_point(id),
         }
     }
 }
@@ -636,13 +871,8 @@ mod tests {
             MapIndex::<N>::new(open_db_with_existing_cf(path).unwrap(), FIELD_NAME, true);
         index.load_from_db().unwrap();
         for (idx, values) in data.iter().enumerate() {
-            let index_values: HashSet<N> = HashSet::from_iter(
-                index
-                    .get_values(idx as PointOffsetType)
-                    .unwrap()
-                    .iter()
-                    .cloned(),
-            );
+            let index_values: HashSet<N> =
+                HashSet::from_iter(index.get_values(idx as PointOffsetType).unwrap().cloned());
             let check_values: HashSet<N> = HashSet::from_iter(values.iter().cloned());
             assert_eq!(index_values, check_values);
         }
@@ -706,6 +936,118 @@ mod tests {
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
 
+    #[test]
+    fn test_uuid_disk_map_index() {
+        let uuids = [
+            "f9a5a3e4-3c3f-4e61-9b4a-8a2f0c7e7d01",
+            "0b8d7c4e-2d1a-4f0e-8c3b-5e6f7a8b9c02",
+            "5e0a1b2c-3d4e-4f5a-8b6c-7d8e9f0a1b03",
+        ]
+        .map(|uuid| parse_uuid(uuid).unwrap());
+
+        let data = vec![
+            vec![uuids[0], uuids[1]],
+            vec![uuids[1]],
+            vec![uuids[2]],
+            vec![uuids[0], uuids[2]],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let index = load_map_index(&data, temp_dir.path());
+
+        // Upper case UUIDs match the same values
+        let condition = FieldCondition::new_match(
+            FIELD_NAME.parse().unwrap(),
+            "F9A5A3E4-3C3F-4E61-9B4A-8A2F0C7E7D01".to_string().into(),
+        );
+        let points: HashSet<_> = index.filter(&condition).unwrap().collect();
+        assert_eq!(points, HashSet::from([0, 3]));
+
+        let condition = FieldCondition::new_match(
+            FIELD_NAME.parse().unwrap(),
+            vec![
+                "0b8d7c4e-2d1a-4f0e-8c3b-5e6f7a8b9c02".to_string(),
+                "5e0a1b2c-3d4e-4f5a-8b6c-7d8e9f0a1b03".to_string(),
+            ]
+            .into(),
+        );
+        let points: HashSet<_> = index.filter(&condition).unwrap().collect();
+        assert_eq!(points, HashSet::from([0, 1, 2, 3]));
+
+        // Strings which are not UUIDs can't be resolved by the index
+        let condition =
+            FieldCondition::new_match(FIELD_NAME.parse().unwrap(), "not-a-uuid".to_string().into());
+        assert!(index.filter(&condition).is_err());
+        assert!(index.estimate_cardinality(&condition).is_err());
+
+        // Points with strings which are not UUIDs are unknown to the index, it can't resolve except
+        let condition = FieldCondition::new_match(
+            FIELD_NAME.parse().unwrap(),
+            Match::new_except(AnyVariants::Keywords(
+                ["f9a5a3e4-3c3f-4e61-9b4a-8a2f0c7e7d01".to_string()]
+                    .into_iter()
+                    .collect(),
+            )),
+        );
+        assert!(index.filter(&condition).is_err());
+        assert!(index.estimate_cardinality(&condition).is_err());
+
+        assert_eq!(
+            index.get_value(&Value::String("not-a-uuid".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_mmap_map_index() {
+        let data: Vec<Vec<SmolStr>> = vec![
+            vec!["a".into(), "b".into()],
+            vec![],
+            vec!["b".into()],
+            vec!["c".into(), "a".into()],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+
+        let db = open_db_with_existing_cf(temp_dir.path()).unwrap();
+        let mmap_dir = temp_dir.path().join("mmap");
+
+        let mut index = MapIndex::<SmolStr>::new_mmap(db.clone(), FIELD_NAME, &mmap_dir);
+        assert!(index.load_from_db().unwrap());
+        assert!(!index.files().is_empty());
+
+        for (idx, values) in data.iter().enumerate() {
+            let index_values: HashSet<_> =
+                index.get_values(idx as PointOffsetType).unwrap().collect();
+            assert_eq!(index_values, values.iter().collect());
+        }
+
+        let points: HashSet<_> = index.get_iterator("a").collect();
+        assert_eq!(points, HashSet::from([0, 3]));
+        assert_eq!(index.get_indexed_points(), 3);
+
+        index.remove_point(0).unwrap();
+        index.flusher()().unwrap();
+
+        let check_removed = |index: &MapIndex<SmolStr>| {
+            let points: HashSet<_> = index.get_iterator("a").collect();
+            assert_eq!(points, HashSet::from([3]));
+            assert_eq!(index.get_points_with_value_count("b"), Some(1));
+            assert_eq!(index.values_count(0), 0);
+            assert_eq!(index.get_indexed_points(), 2);
+            assert_eq!(index.get_values_count(), 3);
+        };
+        check_removed(&index);
+
+        // Files are reused, removed points are persisted
+        drop(index);
+        let mut index = MapIndex::<SmolStr>::new_mmap(db, FIELD_NAME, &mmap_dir);
+        assert!(index.load_from_db().unwrap());
+        check_removed(&index);
+    }
+
     #[test]
     fn test_empty_index() {
         let data: Vec<Vec<String>> = vec![];
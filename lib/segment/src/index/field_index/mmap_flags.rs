@@ -0,0 +1,111 @@
+use std::fs::remove_file;
+use std::path::{Path, PathBuf};
+
+use bitvec::prelude::BitSlice;
+use common::types::PointOffsetType;
+use memory::mmap_ops::{create_and_ensure_length, open_write_mmap};
+
+use crate::common::error_logging::LogError;
+use crate::common::mmap_type::MmapBitSlice;
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+
+// Bit flag per point, stored in a memory mapped file.
+// On-disk field indices are read-only once built, so flags are used to mark removed points.
+pub struct MmapFlags {
+    path: PathBuf,
+    flags: MmapBitSlice,
+}
+
+impl MmapFlags {
+    /// Size of the file, it should be a non-empty multiple of `usize`
+    fn file_size(len: usize) -> usize {
+        let word_bits = usize::BITS as usize;
+        len.div_ceil(word_bits).max(1) * std::mem::size_of::<usize>()
+    }
+
+    /// Create a file with at least `len` flags, only flags from `ones` are set
+    pub fn create(
+        path: &Path,
+        len: usize,
+        ones: impl IntoIterator<Item = PointOffsetType>,
+    ) -> OperationResult<()> {
+        // Leftovers of an interrupted build should not be reused
+        if path.exists() {
+            remove_file(path)?;
+        }
+        create_and_ensure_length(path, Self::file_size(len))?;
+        let mut flags = Self::open(path)?;
+        for idx in ones {
+            flags.set(idx, true);
+        }
+        flags.flusher()()
+    }
+
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        let mmap = open_write_mmap(path).describe("Open mmap flags for writing")?;
+        let flags = MmapBitSlice::try_from(mmap, 0)?;
+        Ok(Self {
+            path: path.to_owned(),
+            flags,
+        })
+    }
+
+    pub fn get(&self, idx: PointOffsetType) -> bool {
+        self.flags.get(idx as usize).map_or(false, |bit| *bit)
+    }
+
+    /// Set the flag and return its previous value, flags out of the file bounds are ignored
+    pub fn set(&mut self, idx: PointOffsetType, value: bool) -> bool {
+        if idx as usize >= self.flags.len() {
+            return false;
+        }
+        self.flags.replace(idx as usize, value)
+    }
+
+    pub fn as_bitslice(&self) -> &BitSlice {
+        &self.flags
+    }
+
+    pub fn iter_ones(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.flags.iter_ones().map(|idx| idx as PointOffsetType)
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.flags.flusher()
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        vec![self.path.clone()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_mmap_flags() {
+        let dir = Builder::new().prefix("mmap_dir").tempdir().unwrap();
+        let path = dir.path().join("flags.bin");
+
+        MmapFlags::create(&path, 100, [1, 5, 99]).unwrap();
+
+        let mut flags = MmapFlags::open(&path).unwrap();
+        assert_eq!(flags.iter_ones().collect::<Vec<_>>(), vec![1, 5, 99]);
+
+        assert!(flags.set(5, false));
+        assert!(!flags.set(7, true));
+        assert!(!flags.set(100_000, true));
+        flags.flusher()().unwrap();
+        drop(flags);
+
+        let flags = MmapFlags::open(&path).unwrap();
+        assert!(flags.get(1));
+        assert!(!flags.get(5));
+        assert!(flags.get(7));
+        assert!(!flags.get(100_000));
+    }
+}
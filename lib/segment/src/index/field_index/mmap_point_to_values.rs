@@ -0,0 +1,212 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use memmap2::Mmap;
+use memory::mmap_ops::{open_read_mmap, transmute_from_u8_to_slice, transmute_to_u8_slice};
+
+use crate::common::error_logging::LogError;
+use crate::common::operation_error::OperationResult;
+
+// Flatten map from ids to lists of values, stored in two memory mapped files.
+// It's an on-disk analogue of `ImmutablePointToValues`, it is read-only once created.
+// It's used in on-disk field indices like `MmapMapIndex` to store both points-to-values and
+// values-to-points maps, and in the compact mmap sparse vector storage.
+pub struct MmapPointToValues<T> {
+    offsets_path: PathBuf,
+    values_path: PathBuf,
+    // `len + 1` offsets into `values`, values of id `i` are stored in `offsets[i]..offsets[i + 1]`
+    offsets: Mmap,
+    // flattened values
+    values: Mmap,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> MmapPointToValues<T> {
+    fn offsets_path(dir: &Path, name: &str) -> PathBuf {
+        dir.join(format!("{name}_offsets.bin"))
+    }
+
+    fn values_path(dir: &Path, name: &str) -> PathBuf {
+        dir.join(format!("{name}_values.bin"))
+    }
+
+    pub fn exists(dir: &Path, name: &str) -> bool {
+        Self::offsets_path(dir, name).exists() && Self::values_path(dir, name).exists()
+    }
+
+    /// Write lists of values into files in `dir`, the n-th list is associated with id `n`
+    pub fn create<L>(
+        dir: &Path,
+        name: &str,
+        lists: impl IntoIterator<Item = L>,
+    ) -> OperationResult<()>
+    where
+        L: AsRef<[T]>,
+    {
+        File::create(Self::offsets_path(dir, name))?;
+        File::create(Self::values_path(dir, name))?;
+        Self::append(dir, name, lists)
+    }
+
+    /// Append lists of values to files in `dir`, files are created if they don't exist
+    ///
+    /// Ids of the appended lists continue after the ids of the existing ones.
+    /// Opened instances don't see the appended lists, they have to be opened again.
+    pub fn append<L>(
+        dir: &Path,
+        name: &str,
+        lists: impl IntoIterator<Item = L>,
+    ) -> OperationResult<()>
+    where
+        L: AsRef<[T]>,
+    {
+        let open_append = |path: PathBuf| {
+            OpenOptions::new()
+                .read(true)
+                .append(true)
+                .create(true)
+                .open(path)
+        };
+        let mut offsets_file = open_append(Self::offsets_path(dir, name))?;
+        let values_file = open_append(Self::values_path(dir, name))?;
+
+        // Continue from the last offset, a new file starts with the zero offset
+        let is_new = offsets_file.metadata()?.len() == 0;
+        let mut offset: u64 = 0;
+        if !is_new {
+            let mut last_offset = [0u8; std::mem::size_of::<u64>()];
+            offsets_file.seek(SeekFrom::End(-(last_offset.len() as i64)))?;
+            offsets_file.read_exact(&mut last_offset)?;
+            offset = u64::from_ne_bytes(last_offset);
+        }
+
+        let mut offsets_writer = BufWriter::new(&offsets_file);
+        let mut values_writer = BufWriter::new(&values_file);
+
+        if is_new {
+            offsets_writer.write_all(&offset.to_ne_bytes())?;
+        }
+        for list in lists {
+            let list = list.as_ref();
+            values_writer.write_all(transmute_to_u8_slice(list))?;
+            offset += list.len() as u64;
+            offsets_writer.write_all(&offset.to_ne_bytes())?;
+        }
+
+        offsets_writer.flush()?;
+        values_writer.flush()?;
+        drop(offsets_writer);
+        drop(values_writer);
+
+        offsets_file.sync_all()?;
+        values_file.sync_all()?;
+
+        Ok(())
+    }
+
+    pub fn open(dir: &Path, name: &str) -> OperationResult<Self> {
+        let offsets_path = Self::offsets_path(dir, name);
+        let values_path = Self::values_path(dir, name);
+
+        let offsets = open_read_mmap(&offsets_path).describe("Open mmap offsets for reading")?;
+        let values = open_read_mmap(&values_path).describe("Open mmap values for reading")?;
+
+        Ok(Self {
+            offsets_path,
+            values_path,
+            offsets,
+            values,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn offsets(&self) -> &[u64] {
+        transmute_from_u8_to_slice(&self.offsets)
+    }
+
+    /// Number of ids in the map
+    pub fn len(&self) -> usize {
+        self.offsets().len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Total number of values for all ids
+    pub fn values_count(&self) -> usize {
+        self.offsets().last().copied().unwrap_or(0) as usize
+    }
+
+    pub fn get_values(&self, id: u32) -> Option<&[T]> {
+        let offsets = self.offsets();
+        let start = *offsets.get(id as usize)? as usize;
+        let end = *offsets.get(id as usize + 1)? as usize;
+        if start == end {
+            // Values mmap might be empty, and therefore not aligned
+            return Some(&[]);
+        }
+        let values: &[T] = transmute_from_u8_to_slice(&self.values);
+        Some(&values[start..end])
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        vec![self.offsets_path.clone(), self.values_path.clone()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_mmap_point_to_values() {
+        let dir = Builder::new().prefix("mmap_dir").tempdir().unwrap();
+
+        let values: Vec<Vec<u32>> = vec![vec![0, 1, 2], vec![], vec![3], vec![4, 5]];
+
+        MmapPointToValues::create(dir.path(), "test", &values).unwrap();
+        assert!(MmapPointToValues::<u32>::exists(dir.path(), "test"));
+
+        let point_to_values = MmapPointToValues::<u32>::open(dir.path(), "test").unwrap();
+        assert_eq!(point_to_values.len(), values.len());
+        assert_eq!(point_to_values.values_count(), 6);
+
+        for (id, expected) in values.iter().enumerate() {
+            assert_eq!(
+                point_to_values.get_values(id as u32).unwrap(),
+                expected.as_slice()
+            );
+        }
+        assert!(point_to_values.get_values(values.len() as u32).is_none());
+    }
+
+    #[test]
+    fn test_mmap_point_to_values_append() {
+        let dir = Builder::new().prefix("mmap_dir").tempdir().unwrap();
+
+        let values: Vec<Vec<u32>> = vec![vec![0, 1, 2], vec![], vec![3], vec![4, 5]];
+
+        MmapPointToValues::append(dir.path(), "test", &values[..2]).unwrap();
+        MmapPointToValues::append(dir.path(), "test", &values[2..]).unwrap();
+
+        let point_to_values = MmapPointToValues::<u32>::open(dir.path(), "test").unwrap();
+        assert_eq!(point_to_values.len(), values.len());
+        for (id, expected) in values.iter().enumerate() {
+            assert_eq!(
+                point_to_values.get_values(id as u32).unwrap(),
+                expected.as_slice()
+            );
+        }
+
+        // Creating the files again drops the previous lists
+        MmapPointToValues::create(dir.path(), "test", &values[2..]).unwrap();
+        let point_to_values = MmapPointToValues::<u32>::open(dir.path(), "test").unwrap();
+        assert_eq!(point_to_values.len(), 2);
+        assert_eq!(point_to_values.get_values(0).unwrap(), &[3]);
+    }
+}
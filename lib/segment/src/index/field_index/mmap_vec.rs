@@ -0,0 +1,85 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use memmap2::Mmap;
+use memory::mmap_ops::{open_read_mmap, transmute_from_u8_to_slice, transmute_to_u8_slice};
+
+use crate::common::error_logging::LogError;
+use crate::common::operation_error::OperationResult;
+
+// Flat array of values, stored in a memory mapped file. It is read-only once created.
+// It's used in on-disk field indices to store sorted keys and their statistics.
+pub struct MmapVec<T> {
+    path: PathBuf,
+    mmap: Mmap,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> MmapVec<T> {
+    pub fn create(path: &Path, values: impl IntoIterator<Item = T>) -> OperationResult<()> {
+        let file = File::create(path)?;
+        let mut writer = BufWriter::new(&file);
+        for value in values {
+            writer.write_all(transmute_to_u8_slice(std::slice::from_ref(&value)))?;
+        }
+        writer.flush()?;
+        drop(writer);
+        file.sync_all()?;
+        Ok(())
+    }
+
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        let mmap = open_read_mmap(path).describe("Open mmap vec for reading")?;
+        Ok(Self {
+            path: path.to_owned(),
+            mmap,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        if self.mmap.is_empty() {
+            // Empty mmap might be not aligned
+            return &[];
+        }
+        transmute_from_u8_to_slice(&self.mmap)
+    }
+
+    pub fn len(&self) -> usize {
+        self.mmap.len() / std::mem::size_of::<T>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        vec![self.path.clone()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_mmap_vec() {
+        let dir = Builder::new().prefix("mmap_dir").tempdir().unwrap();
+
+        let path = dir.path().join("values.bin");
+        MmapVec::create(&path, [[1u32, 2], [3, 4], [5, 6]]).unwrap();
+        let values = MmapVec::<[u32; 2]>::open(&path).unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values.as_slice(), &[[1, 2], [3, 4], [5, 6]]);
+
+        let path = dir.path().join("empty.bin");
+        MmapVec::<u64>::create(&path, []).unwrap();
+        let values = MmapVec::<u64>::open(&path).unwrap();
+        assert!(values.is_empty());
+        assert!(values.as_slice().is_empty());
+    }
+}
@@ -24,8 +24,8 @@ pub struct ImmutableNumericIndex<T: Encodable + Numericable + Default> {
 
 #[derive(Clone, PartialEq, Debug)]
 pub(super) struct NumericIndexKey<T> {
-    key: T,
-    idx: PointOffsetType,
+    pub(super) key: T,
+    pub(super) idx: PointOffsetType,
     deleted: bool,
 }
 
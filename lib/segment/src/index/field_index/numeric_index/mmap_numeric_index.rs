@@ -0,0 +1,372 @@
+use std::fs::{create_dir_all, remove_dir_all};
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_bin, atomic_save_json, read_bin, read_json};
+use parking_lot::RwLock;
+use rocksdb::DB;
+use serde::{Deserialize, Serialize};
+
+use super::immutable_numeric_index::NumericIndexKey;
+use super::mutable_numeric_index::MutableNumericIndex;
+use super::{Encodable, NumericIndex, HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION};
+use crate::common::operation_error::OperationResult;
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
+use crate::index::field_index::histogram::{Histogram, Numericable, Point};
+use crate::index::field_index::mmap_flags::MmapFlags;
+use crate::index::field_index::mmap_point_to_values::MmapPointToValues;
+use crate::index::field_index::mmap_vec::MmapVec;
+
+const STATS_FILE: &str = "stats.json";
+const HISTOGRAM_FILE: &str = "histogram.bin";
+const DELETED_FILE: &str = "deleted.bin";
+const SORTED_VALUES_FILE: &str = "sorted_values.bin";
+const SORTED_POINTS_FILE: &str = "sorted_points.bin";
+const POINT_TO_VALUES: &str = "point_to_values";
+
+/// Numeric index, which keeps sorted `(value, point)` pairs and points-to-values map
+/// in memory mapped files. Only the histogram is kept in RAM.
+///
+/// Files are built from the RocksDB records on the first load, after that the index is read-only,
+/// except for point removal.
+pub struct MmapNumericIndex<T: Encodable + Numericable + Default> {
+    path: PathBuf,
+    storage: Option<Storage<T>>,
+    db_wrapper: DatabaseColumnWrapper,
+    pub(super) histogram: Histogram<T>,
+    pub(super) points_count: usize,
+    pub(super) max_values_per_point: usize,
+    values_count: usize,
+}
+
+struct Storage<T> {
+    /// Values of all `(value, point)` pairs, sorted by value and then by point
+    sorted_values: MmapVec<T>,
+    /// Points of all `(value, point)` pairs, in the same order as `sorted_values`
+    sorted_points: MmapVec<PointOffsetType>,
+    point_to_values: MmapPointToValues<T>,
+    deleted: MmapFlags,
+}
+
+/// Statistics of the index at the moment of building, removed points are not taken into account
+#[derive(Serialize, Deserialize)]
+struct Stats {
+    points_count: usize,
+    max_values_per_point: usize,
+}
+
+impl<T: Encodable + Numericable + Default> MmapNumericIndex<T> {
+    pub(super) fn new(db: Arc<RwLock<DB>>, field: &str, path: &Path) -> Self {
+        let store_cf_name = NumericIndex::<T>::storage_cf_name(field);
+        let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
+        Self {
+            path: path.to_owned(),
+            storage: None,
+            db_wrapper,
+            histogram: Histogram::new(HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION),
+            points_count: 0,
+            max_values_per_point: 0,
+            values_count: 0,
+        }
+    }
+
+    pub(super) fn get_db_wrapper(&self) -> &DatabaseColumnWrapper {
+        &self.db_wrapper
+    }
+
+    fn files_exist(path: &Path) -> bool {
+        path.join(STATS_FILE).exists()
+            && path.join(HISTOGRAM_FILE).exists()
+            && path.join(DELETED_FILE).exists()
+            && path.join(SORTED_VALUES_FILE).exists()
+            && path.join(SORTED_POINTS_FILE).exists()
+            && MmapPointToValues::<T>::exists(path, POINT_TO_VALUES)
+    }
+
+    pub(super) fn load(&mut self) -> OperationResult<bool> {
+        if !self.db_wrapper.has_column_family()? {
+            return Ok(false);
+        }
+
+        if !Self::files_exist(&self.path) {
+            // Use `MutableNumericIndex` to load data from db
+            // and convert it into the on-disk representation
+            let mut mutable = MutableNumericIndex::<T> {
+                map: Default::default(),
+                db_wrapper: self.db_wrapper.clone(),
+                histogram: Histogram::new(HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION),
+                points_count: 0,
+                max_values_per_point: 0,
+                point_to_values: Default::default(),
+            };
+            if !mutable.load()? {
+                return Ok(false);
+            }
+            Self::build_files(&self.path, mutable)?;
+        }
+
+        self.open_files()?;
+        Ok(true)
+    }
+
+    fn build_files(path: &Path, mutable: MutableNumericIndex<T>) -> OperationResult<()> {
+        let MutableNumericIndex {
+            map,
+            histogram,
+            points_count,
+            max_values_per_point,
+            point_to_values,
+            ..
+        } = mutable;
+
+        create_dir_all(path)?;
+
+        // Keys are encoded in the same order as `NumericIndexKey` is sorted
+        let keys: Vec<_> = map
+            .keys()
+            .map(|encoded| NumericIndexKey::<T>::decode(encoded))
+            .collect();
+
+        MmapVec::create(&path.join(SORTED_VALUES_FILE), keys.iter().map(|k| k.key))?;
+        MmapVec::create(&path.join(SORTED_POINTS_FILE), keys.iter().map(|k| k.idx))?;
+        MmapPointToValues::<T>::create(path, POINT_TO_VALUES, &point_to_values)?;
+        MmapFlags::create(&path.join(DELETED_FILE), point_to_values.len(), [])?;
+        atomic_save_bin(&path.join(HISTOGRAM_FILE), &histogram)?;
+
+        // Stats are written last, so incomplete files are never considered as built
+        atomic_save_json(
+            &path.join(STATS_FILE),
+            &Stats {
+                points_count,
+                max_values_per_point,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn open_files(&mut self) -> OperationResult<()> {
+        let stats: Stats = read_json(&self.path.join(STATS_FILE))?;
+        self.histogram = read_bin(&self.path.join(HISTOGRAM_FILE))?;
+
+        let storage = Storage {
+            sorted_values: MmapVec::open(&self.path.join(SORTED_VALUES_FILE))?,
+            sorted_points: MmapVec::open(&self.path.join(SORTED_POINTS_FILE))?,
+            point_to_values: MmapPointToValues::open(&self.path, POINT_TO_VALUES)?,
+            deleted: MmapFlags::open(&self.path.join(DELETED_FILE))?,
+        };
+
+        self.points_count = stats.points_count;
+        self.max_values_per_point = stats.max_values_per_point;
+        self.values_count = storage.sorted_values.len();
+
+        // Exclude points, which were removed after the files were built
+        for idx in storage.deleted.iter_ones() {
+            Self::exclude_point(
+                &storage,
+                &mut self.histogram,
+                &mut self.points_count,
+                &mut self.values_count,
+                idx,
+            );
+        }
+
+        self.storage = Some(storage);
+        Ok(())
+    }
+
+    /// Update statistics and histogram for the point, which is already marked as deleted
+    fn exclude_point(
+        storage: &Storage<T>,
+        histogram: &mut Histogram<T>,
+        points_count: &mut usize,
+        values_count: &mut usize,
+        idx: PointOffsetType,
+    ) {
+        let Some(values) = storage.point_to_values.get_values(idx) else {
+            return;
+        };
+        if !values.is_empty() {
+            *points_count -= 1;
+        }
+        *values_count -= values.len();
+        for value in values {
+            let point = Point {
+                val: *value,
+                idx: idx as usize,
+            };
+            histogram.remove(
+                &point,
+                |x| {
+                    let key: NumericIndexKey<T> = x.clone().into();
+                    Self::range(storage, Bound::Unbounded, Bound::Excluded(key))
+                        .next_back()
+                        .map(|(val, idx)| Point {
+                            val,
+                            idx: idx as usize,
+                        })
+                },
+                |x| {
+                    let key: NumericIndexKey<T> = x.clone().into();
+                    Self::range(storage, Bound::Excluded(key), Bound::Unbounded)
+                        .next()
+                        .map(|(val, idx)| Point {
+                            val,
+                            idx: idx as usize,
+                        })
+                },
+            );
+        }
+    }
+
+    pub(super) fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        let Some(storage) = &mut self.storage else {
+            return Ok(());
+        };
+        if storage.deleted.get(idx) {
+            return Ok(());
+        }
+        let Some(values) = storage.point_to_values.get_values(idx) else {
+            return Ok(());
+        };
+
+        for value in values {
+            self.db_wrapper.remove(value.encode_key(idx))?;
+        }
+
+        storage.deleted.set(idx, true);
+        Self::exclude_point(
+            storage,
+            &mut self.histogram,
+            &mut self.points_count,
+            &mut self.values_count,
+            idx,
+        );
+        Ok(())
+    }
+
+    pub(super) fn get_values(&self, idx: PointOffsetType) -> Option<&[T]> {
+        let storage = self.storage.as_ref()?;
+        let values = storage.point_to_values.get_values(idx)?;
+        if storage.deleted.get(idx) {
+            return Some(&[]);
+        }
+        Some(values)
+    }
+
+    pub(super) fn get_values_count(&self) -> usize {
+        self.values_count
+    }
+
+    fn key_at(storage: &Storage<T>, position: usize) -> NumericIndexKey<T> {
+        NumericIndexKey::new(
+            storage.sorted_values.as_slice()[position],
+            storage.sorted_points.as_slice()[position],
+        )
+    }
+
+    /// Position of the first pair, which is not less than `key`, or greater than `key` if `strict`
+    fn lower_bound(storage: &Storage<T>, key: &NumericIndexKey<T>, strict: bool) -> usize {
+        let mut low = 0;
+        let mut high = storage.sorted_values.len();
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let mid_key = Self::key_at(storage, mid);
+            let go_right = if strict {
+                mid_key <= *key
+            } else {
+                mid_key < *key
+            };
+            if go_right {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    fn range(
+        storage: &Storage<T>,
+        start_bound: Bound<NumericIndexKey<T>>,
+        end_bound: Bound<NumericIndexKey<T>>,
+    ) -> impl DoubleEndedIterator<Item = (T, PointOffsetType)> + '_ {
+        let start = match &start_bound {
+            Bound::Included(key) => Self::lower_bound(storage, key, false),
+            Bound::Excluded(key) => Self::lower_bound(storage, key, true),
+            Bound::Unbounded => 0,
+        };
+        let end = match &end_bound {
+            Bound::Included(key) => Self::lower_bound(storage, key, true),
+            Bound::Excluded(key) => Self::lower_bound(storage, key, false),
+            Bound::Unbounded => storage.sorted_values.len(),
+        };
+
+        let values = storage.sorted_values.as_slice();
+        let points = storage.sorted_points.as_slice();
+        (start..end.max(start))
+            .filter(move |&position| !storage.deleted.get(points[position]))
+            .map(move |position| (values[position], points[position]))
+    }
+
+    pub(super) fn values_range(
+        &self,
+        start_bound: Bound<NumericIndexKey<T>>,
+        end_bound: Bound<NumericIndexKey<T>>,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match &self.storage {
+            Some(storage) => {
+                Box::new(Self::range(storage, start_bound, end_bound).map(|(_value, idx)| idx))
+            }
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    pub(super) fn orderable_values_range(
+        &self,
+        start_bound: Bound<NumericIndexKey<T>>,
+        end_bound: Bound<NumericIndexKey<T>>,
+    ) -> Box<dyn DoubleEndedIterator<Item = (T, PointOffsetType)> + '_> {
+        match &self.storage {
+            Some(storage) => Box::new(Self::range(storage, start_bound, end_bound)),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    pub(super) fn files(&self) -> Vec<PathBuf> {
+        let Some(storage) = &self.storage else {
+            return vec![];
+        };
+        let mut files = vec![self.path.join(STATS_FILE), self.path.join(HISTOGRAM_FILE)];
+        files.extend(storage.sorted_values.files());
+        files.extend(storage.sorted_points.files());
+        files.extend(storage.point_to_values.files());
+        files.extend(storage.deleted.files());
+        files
+    }
+
+    pub(super) fn flusher(&self) -> Flusher {
+        let db_flusher = self.db_wrapper.flusher();
+        let deleted_flusher = self
+            .storage
+            .as_ref()
+            .map(|storage| storage.deleted.flusher());
+        Box::new(move || {
+            if let Some(deleted_flusher) = deleted_flusher {
+                deleted_flusher()?;
+            }
+            db_flusher()
+        })
+    }
+
+    /// Remove the on-disk files of the index, they are rebuilt from RocksDB on the next load
+    pub(super) fn clear_files(&self) -> OperationResult<()> {
+        if self.path.exists() {
+            remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+}
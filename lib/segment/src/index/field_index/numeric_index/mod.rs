@@ -1,4 +1,5 @@
 mod immutable_numeric_index;
+mod mmap_numeric_index;
 mod mutable_numeric_index;
 
 #[cfg(test)]
@@ -7,6 +8,7 @@ mod tests;
 use std::cmp::{max, min};
 use std::ops::Bound;
 use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -18,6 +20,7 @@ use rocksdb::DB;
 use serde_json::Value;
 
 use self::immutable_numeric_index::{ImmutableNumericIndex, NumericIndexKey};
+use self::mmap_numeric_index::MmapNumericIndex;
 use super::utils::check_boundaries;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
@@ -40,6 +43,8 @@ use crate::types::{
 
 const HISTOGRAM_MAX_BUCKET_SIZE: usize = 10_000;
 const HISTOGRAM_PRECISION: f64 = 0.01;
+/// Growth of the block size between levels of payload blocks
+const COARSER_BLOCKS_FACTOR: usize = 4;
 
 pub trait StreamRange<T> {
     fn stream_range(
@@ -142,6 +147,7 @@ impl<T: Encodable + Numericable> Range<T> {
 pub enum NumericIndex<T: Encodable + Numericable + Default> {
     Mutable(MutableNumericIndex<T>),
     Immutable(ImmutableNumericIndex<T>),
+    Mmap(MmapNumericIndex<T>),
 }
 
 impl<T: Encodable + Numericable + Default> NumericIndex<T> {
@@ -153,10 +159,16 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         }
     }
 
+    /// Create an index, which is stored in memory mapped files in `path`
+    pub fn new_mmap(db: Arc<RwLock<DB>>, field: &str, path: &Path) -> Self {
+        NumericIndex::Mmap(MmapNumericIndex::new(db, field, path))
+    }
+
     fn get_db_wrapper(&self) -> &DatabaseColumnWrapper {
         match self {
             NumericIndex::Mutable(index) => index.get_db_wrapper(),
             NumericIndex::Immutable(index) => index.get_db_wrapper(),
+            NumericIndex::Mmap(index) => index.get_db_wrapper(),
         }
     }
 
@@ -164,6 +176,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => &index.histogram,
             NumericIndex::Immutable(index) => &index.histogram,
+            NumericIndex::Mmap(index) => &index.histogram,
         }
     }
 
@@ -171,6 +184,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.points_count,
             NumericIndex::Immutable(index) => index.points_count,
+            NumericIndex::Mmap(index) => index.points_count,
         }
     }
 
@@ -178,6 +192,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.get_values_count(),
             NumericIndex::Immutable(index) => index.get_values_count(),
+            NumericIndex::Mmap(index) => index.get_values_count(),
         }
     }
 
@@ -186,6 +201,9 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
+        if let NumericIndex::Mmap(index) = self {
+            index.clear_files()?;
+        }
         self.get_db_wrapper().recreate_column_family()
     }
 
@@ -193,17 +211,31 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.load(),
             NumericIndex::Immutable(index) => index.load(),
+            NumericIndex::Mmap(index) => index.load(),
         }
     }
 
     pub fn flusher(&self) -> Flusher {
-        self.get_db_wrapper().flusher()
+        match self {
+            NumericIndex::Mutable(_) | NumericIndex::Immutable(_) => {
+                self.get_db_wrapper().flusher()
+            }
+            NumericIndex::Mmap(index) => index.flusher(),
+        }
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            NumericIndex::Mutable(_) | NumericIndex::Immutable(_) => vec![],
+            NumericIndex::Mmap(index) => index.files(),
+        }
     }
 
     pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
         match self {
             NumericIndex::Mutable(index) => index.remove_point(idx),
             NumericIndex::Immutable(index) => index.remove_point(idx),
+            NumericIndex::Mmap(index) => index.remove_point(idx),
         }
     }
 
@@ -211,6 +243,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.get_values(idx),
             NumericIndex::Immutable(index) => index.get_values(idx),
+            NumericIndex::Mmap(index) => index.get_values(idx),
         }
     }
 
@@ -223,7 +256,76 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.max_values_per_point,
             NumericIndex::Immutable(index) => index.max_values_per_point,
+            NumericIndex::Mmap(index) => index.max_values_per_point,
+        }
+    }
+
+    /// Blocks of `2 * bucket_size` values, each consecutive pair of blocks overlaps by one bucket
+    fn histogram_blocks(
+        &self,
+        bucket_size: usize,
+        key: &PayloadKeyType,
+    ) -> Vec<PayloadBlockCondition> {
+        let mut lower_bound = Unbounded;
+        let mut pre_lower_bound: Option<Bound<T>> = None;
+        let mut payload_conditions = Vec::new();
+
+        loop {
+            let upper_bound = self
+                .get_histogram()
+                .get_range_by_size(lower_bound, bucket_size);
+
+            if let Some(pre_lower_bound) = pre_lower_bound {
+                let range = Range {
+                    lt: match upper_bound {
+                        Excluded(val) => Some(val.to_f64()),
+                        _ => None,
+                    },
+                    gt: match pre_lower_bound {
+                        Excluded(val) => Some(val.to_f64()),
+                        _ => None,
+                    },
+                    gte: match pre_lower_bound {
+                        Included(val) => Some(val.to_f64()),
+                        _ => None,
+                    },
+                    lte: match upper_bound {
+                        Included(val) => Some(val.to_f64()),
+                        _ => None,
+                    },
+                };
+                let cardinality = self.range_cardinality(&RangeInterface::Float(range.clone()));
+                let condition = PayloadBlockCondition {
+                    condition: FieldCondition::new_range(key.clone(), range),
+                    cardinality: cardinality.exp,
+                };
+
+                payload_conditions.push(condition);
+            } else if upper_bound == Unbounded {
+                // One block covers all points
+                payload_conditions.push(PayloadBlockCondition {
+                    condition: FieldCondition::new_range(
+                        key.clone(),
+                        Range {
+                            gte: None,
+                            lte: None,
+                            lt: None,
+                            gt: None,
+                        },
+                    ),
+                    cardinality: self.get_points_count(),
+                });
+            }
+
+            pre_lower_bound = Some(lower_bound);
+
+            lower_bound = match upper_bound {
+                Included(val) => Excluded(val),
+                Excluded(val) => Excluded(val),
+                Unbounded => break,
+            };
         }
+        payload_conditions
     }
 
     fn range_cardinality(&self, range: &RangeInterface) -> CardinalityEstimation {
@@ -324,7 +426,7 @@ impl<T: Encodable + Numericable + Default> PayloadFieldIndex for NumericIndex<T>
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        self.recreate()
     }
 
     fn flusher(&self) -> Flusher {
@@ -361,6 +463,7 @@ impl<T: Encodable + Numericable + Default> PayloadFieldIndex for NumericIndex<T>
                 Box::new(index.values_range(start_bound, end_bound))
             }
             NumericIndex::Immutable(index) => Box::new(index.values_range(start_bound, end_bound)),
+            NumericIndex::Mmap(index) => index.values_range(start_bound, end_bound),
         })
     }
 
@@ -386,68 +489,20 @@ impl<T: Encodable + Numericable + Default> PayloadFieldIndex for NumericIndex<T>
         threshold: usize,
         key: PayloadKeyType,
     ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
-        let mut lower_bound = Unbounded;
-        let mut pre_lower_bound: Option<Bound<T>> = None;
-        let mut payload_conditions = Vec::new();
-
         let value_per_point = self.get_values_count() as f64 / self.get_points_count() as f64;
         let effective_threshold = (threshold as f64 * value_per_point) as usize;
 
-        loop {
-            let upper_bound = self
-                .get_histogram()
-                .get_range_by_size(lower_bound, effective_threshold / 2);
-
-            if let Some(pre_lower_bound) = pre_lower_bound {
-                let range = Range {
-                    lt: match upper_bound {
-                        Excluded(val) => Some(val.to_f64()),
-                        _ => None,
-                    },
-                    gt: match pre_lower_bound {
-                        Excluded(val) => Some(val.to_f64()),
-                        _ => None,
-                    },
-                    gte: match pre_lower_bound {
-                        Included(val) => Some(val.to_f64()),
-                        _ => None,
-                    },
-                    lte: match upper_bound {
-                        Included(val) => Some(val.to_f64()),
-                        _ => None,
-                    },
-                };
-                let cardinality = self.range_cardinality(&RangeInterface::Float(range.clone()));
-                let condition = PayloadBlockCondition {
-                    condition: FieldCondition::new_range(key.clone(), range),
-                    cardinality: cardinality.exp,
-                };
-
-                payload_conditions.push(condition);
-            } else if upper_bound == Unbounded {
-                // One block covers all points
-                payload_conditions.push(PayloadBlockCondition {
-                    condition: FieldCondition::new_range(
-                        key.clone(),
-                        Range {
-                            gte: None,
-                            lte: None,
-                            lt: None,
-                            gt: None,
-                        },
-                    ),
-                    cardinality: self.get_points_count(),
-                });
-            }
-
-            pre_lower_bound = Some(lower_bound);
+        let mut bucket_size = effective_threshold / 2;
+        let mut payload_conditions = self.histogram_blocks(bucket_size, &key);
 
-            lower_bound = match upper_bound {
-                Included(val) => Excluded(val),
-                Excluded(val) => Excluded(val),
-                Unbounded => break,
-            };
+        // Coarser levels of blocks, so wider ranges are covered by a single well-connected
+        // block too, instead of a chain of small ones
+        bucket_size = bucket_size.saturating_mul(COARSER_BLOCKS_FACTOR);
+        while bucket_size > 0 && bucket_size.saturating_mul(2) < self.get_values_count() {
+            payload_conditions.extend(self.histogram_blocks(bucket_size, &key));
+            bucket_size = bucket_size.saturating_mul(COARSER_BLOCKS_FACTOR);
         }
+
         Box::new(payload_conditions.into_iter())
     }
 }
@@ -460,9 +515,9 @@ impl ValueIndexer<IntPayloadType> for NumericIndex<IntPayloadType> {
     ) -> OperationResult<()> {
         match self {
             NumericIndex::Mutable(index) => index.add_many_to_list(id, values),
-            NumericIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable numeric index",
-            )),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable numeric index"),
+            ),
         }
     }
 
@@ -485,9 +540,9 @@ impl ValueIndexer<DateTimePayloadType> for NumericIndex<IntPayloadType> {
             NumericIndex::Mutable(index) => {
                 index.add_many_to_list(id, values.into_iter().map(|x| x.timestamp()))
             }
-            NumericIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable numeric index",
-            )),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable numeric index"),
+            ),
         }
     }
 
@@ -508,9 +563,9 @@ impl ValueIndexer<FloatPayloadType> for NumericIndex<FloatPayloadType> {
     ) -> OperationResult<()> {
         match self {
             NumericIndex::Mutable(index) => index.add_many_to_list(id, values),
-            NumericIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable numeric index",
-            )),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable numeric index"),
+            ),
         }
     }
 
@@ -554,6 +609,7 @@ where
             NumericIndex::Immutable(index) => {
                 Box::new(index.orderable_values_range(start_bound, end_bound))
             }
+            NumericIndex::Mmap(index) => index.orderable_values_range(start_bound, end_bound),
         }
     }
 }
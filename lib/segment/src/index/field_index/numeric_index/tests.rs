@@ -10,6 +10,13 @@ use crate::json_path::path;
 
 const COLUMN_NAME: &str = "test";
 
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum IndexType {
+    Mutable,
+    Immutable,
+    Mmap,
+}
+
 fn get_index() -> (TempDir, NumericIndex<f64>) {
     let temp_dir = Builder::new()
         .prefix("test_numeric_index")
@@ -21,10 +28,28 @@ fn get_index() -> (TempDir, NumericIndex<f64>) {
     (temp_dir, index)
 }
 
+/// Load index of the given type from the storage of the `index`
+fn load_index(
+    temp_dir: &TempDir,
+    index: &NumericIndex<f64>,
+    index_type: IndexType,
+) -> NumericIndex<f64> {
+    let db_ref = index.get_db_wrapper().database.clone();
+    let mut new_index: NumericIndex<f64> = match index_type {
+        IndexType::Mutable => NumericIndex::new(db_ref, COLUMN_NAME, true),
+        IndexType::Immutable => NumericIndex::new(db_ref, COLUMN_NAME, false),
+        IndexType::Mmap => {
+            NumericIndex::new_mmap(db_ref, COLUMN_NAME, &temp_dir.path().join("mmap_index"))
+        }
+    };
+    new_index.load().unwrap();
+    new_index
+}
+
 fn random_index(
     num_points: usize,
     values_per_point: usize,
-    immutable: bool,
+    index_type: IndexType,
 ) -> (TempDir, NumericIndex<f64>) {
     let mut rng = StdRng::seed_from_u64(42);
     let (temp_dir, mut index) = get_index();
@@ -35,20 +60,20 @@ fn random_index(
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(i as PointOffsetType, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => {
+                unreachable!("index is mutable")
+            }
         }
     }
 
     index.flusher()().unwrap();
 
     // if immutable, we have to reload the index
-    if immutable {
-        let db_ref = index.get_db_wrapper().database.clone();
-        let mut new_index: NumericIndex<f64> = NumericIndex::new(db_ref, COLUMN_NAME, false);
-        new_index.load().unwrap();
-        (temp_dir, new_index)
-    } else {
+    if index_type == IndexType::Mutable {
         (temp_dir, index)
+    } else {
+        let new_index = load_index(&temp_dir, &index, index_type);
+        (temp_dir, new_index)
     }
 }
 
@@ -73,7 +98,7 @@ fn cardinality_request(
 
 #[test]
 fn test_set_empty_payload() {
-    let (_temp_dir, mut index) = random_index(1000, 1, false);
+    let (_temp_dir, mut index) = random_index(1000, 1, IndexType::Mutable);
 
     let point_id = 42;
 
@@ -90,10 +115,11 @@ fn test_set_empty_payload() {
 }
 
 #[rstest]
-#[case(true)]
-#[case(false)]
-fn test_cardinality_exp(#[case] immutable: bool) {
-    let (_temp_dir, index) = random_index(1000, 1, immutable);
+#[case(IndexType::Mutable)]
+#[case(IndexType::Immutable)]
+#[case(IndexType::Mmap)]
+fn test_cardinality_exp(#[case] index_type: IndexType) {
+    let (_temp_dir, index) = random_index(1000, 1, index_type);
 
     cardinality_request(
         &index,
@@ -114,7 +140,7 @@ fn test_cardinality_exp(#[case] immutable: bool) {
         },
     );
 
-    let (_temp_dir, index) = random_index(1000, 2, immutable);
+    let (_temp_dir, index) = random_index(1000, 2, index_type);
     cardinality_request(
         &index,
         Range {
@@ -156,10 +182,11 @@ fn test_cardinality_exp(#[case] immutable: bool) {
 }
 
 #[rstest]
-#[case(true)]
-#[case(false)]
-fn test_payload_blocks(#[case] immutable: bool) {
-    let (_temp_dir, index) = random_index(1000, 2, immutable);
+#[case(IndexType::Mutable)]
+#[case(IndexType::Immutable)]
+#[case(IndexType::Mmap)]
+fn test_payload_blocks(#[case] index_type: IndexType) {
+    let (_temp_dir, index) = random_index(1000, 2, index_type);
     let threshold = 100;
     let blocks = index.payload_blocks(threshold, path("test")).collect_vec();
     assert!(!blocks.is_empty());
@@ -182,10 +209,27 @@ fn test_payload_blocks(#[case] immutable: bool) {
 }
 
 #[rstest]
-#[case(true)]
-#[case(false)]
-fn test_payload_blocks_small(#[case] immutable: bool) {
-    let (_temp_dir, mut index) = get_index();
+#[case(IndexType::Mutable)]
+#[case(IndexType::Immutable)]
+#[case(IndexType::Mmap)]
+fn test_payload_blocks_levels(#[case] index_type: IndexType) {
+    let (_temp_dir, index) = random_index(1000, 2, index_type);
+    let threshold = 100;
+    let blocks = index.payload_blocks(threshold, path("test")).collect_vec();
+
+    // Blocks of the smallest level and of coarser levels
+    assert!(blocks.iter().any(|block| block.cardinality < 3 * threshold));
+    assert!(blocks.iter().any(|block| block.cardinality > 5 * threshold));
+    // No block covers all points, the main graph does it already
+    assert!(blocks.iter().all(|block| block.cardinality < 1000));
+}
+
+#[rstest]
+#[case(IndexType::Mutable)]
+#[case(IndexType::Immutable)]
+#[case(IndexType::Mmap)]
+fn test_payload_blocks_small(#[case] index_type: IndexType) {
+    let (temp_dir, mut index) = get_index();
     let threshold = 4;
     let values = vec![
         vec![1.0],
@@ -206,19 +250,18 @@ fn test_payload_blocks_small(#[case] immutable: bool) {
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(idx as PointOffsetType + 1, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => {
+                unreachable!("index is mutable")
+            }
         });
 
     index.flusher()().unwrap();
 
     // if immutable, we have to reload the index
-    let index = if immutable {
-        let db_ref = index.get_db_wrapper().database.clone();
-        let mut new_index: NumericIndex<f64> = NumericIndex::new(db_ref, COLUMN_NAME, false);
-        new_index.load().unwrap();
-        new_index
-    } else {
+    let index = if index_type == IndexType::Mutable {
         index
+    } else {
+        load_index(&temp_dir, &index, index_type)
     };
 
     let blocks = index.payload_blocks(threshold, path("test")).collect_vec();
@@ -226,10 +269,11 @@ fn test_payload_blocks_small(#[case] immutable: bool) {
 }
 
 #[rstest]
-#[case(true)]
-#[case(false)]
-fn test_numeric_index_load_from_disk(#[case] immutable: bool) {
-    let (_temp_dir, mut index) = get_index();
+#[case(IndexType::Mutable)]
+#[case(IndexType::Immutable)]
+#[case(IndexType::Mmap)]
+fn test_numeric_index_load_from_disk(#[case] index_type: IndexType) {
+    let (temp_dir, mut index) = get_index();
 
     let values = vec![
         vec![1.0],
@@ -250,14 +294,14 @@ fn test_numeric_index_load_from_disk(#[case] immutable: bool) {
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(idx as PointOffsetType + 1, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => {
+                unreachable!("index is mutable")
+            }
         });
 
     index.flusher()().unwrap();
 
-    let db_ref = index.get_db_wrapper().database.clone();
-    let mut new_index: NumericIndex<f64> = NumericIndex::new(db_ref, COLUMN_NAME, !immutable);
-    new_index.load().unwrap();
+    let new_index = load_index(&temp_dir, &index, index_type);
 
     test_cond(
         &new_index,
@@ -272,10 +316,11 @@ fn test_numeric_index_load_from_disk(#[case] immutable: bool) {
 }
 
 #[rstest]
-#[case(true)]
-#[case(false)]
-fn test_numeric_index(#[case] immutable: bool) {
-    let (_temp_dir, mut index) = get_index();
+#[case(IndexType::Mutable)]
+#[case(IndexType::Immutable)]
+#[case(IndexType::Mmap)]
+fn test_numeric_index(#[case] index_type: IndexType) {
+    let (temp_dir, mut index) = get_index();
 
     let values = vec![
         vec![1.0],
@@ -296,19 +341,18 @@ fn test_numeric_index(#[case] immutable: bool) {
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(idx as PointOffsetType + 1, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => {
+                unreachable!("index is mutable")
+            }
         });
 
     index.flusher()().unwrap();
 
     // if immutable, we have to reload the index
-    let index = if immutable {
-        let db_ref = index.get_db_wrapper().database.clone();
-        let mut new_index: NumericIndex<f64> = NumericIndex::new(db_ref, COLUMN_NAME, false);
-        new_index.load().unwrap();
-        new_index
-    } else {
+    let index = if index_type == IndexType::Mutable {
         index
+    } else {
+        load_index(&temp_dir, &index, index_type)
     };
 
     test_cond(
@@ -367,6 +411,46 @@ fn test_numeric_index(#[case] immutable: bool) {
     );
 }
 
+#[test]
+fn test_mmap_index_remove_point() {
+    let (temp_dir, index) = random_index(100, 2, IndexType::Mutable);
+    let mut mmap_index = load_index(&temp_dir, &index, IndexType::Mmap);
+
+    let range = Range {
+        gt: None,
+        gte: None,
+        lt: None,
+        lte: None,
+    };
+
+    assert_eq!(mmap_index.count_indexed_points(), 100);
+
+    mmap_index.remove_point(10).unwrap();
+    mmap_index.remove_point(20).unwrap();
+    mmap_index.flusher()().unwrap();
+
+    let check = |index: &NumericIndex<f64>| {
+        assert_eq!(index.count_indexed_points(), 98);
+        assert_eq!(index.get_values_count(), 196);
+        assert!(index.values_is_empty(10));
+
+        let points = index
+            .filter(&FieldCondition::new_range(path("unused"), range.clone()))
+            .unwrap()
+            .unique()
+            .collect_vec();
+        assert_eq!(points.len(), 98);
+        assert!(!points.contains(&10));
+        assert!(!points.contains(&20));
+    };
+
+    check(&mmap_index);
+
+    // Removed points are still excluded after reloading the files
+    drop(mmap_index);
+    check(&load_index(&temp_dir, &index, IndexType::Mmap));
+}
+
 fn test_cond<T: Encodable + Numericable + PartialOrd + Clone + Default>(
     index: &NumericIndex<T>,
     rng: Range<FloatPayloadType>,
@@ -379,10 +463,11 @@ fn test_cond<T: Encodable + Numericable + PartialOrd + Clone + Default>(
 
 // Check we don't panic on an empty index. See <https://github.com/qdrant/qdrant/pull/2933>.
 #[rstest]
-#[case(true)]
-#[case(false)]
-fn test_empty_cardinality(#[case] immutable: bool) {
-    let (_temp_dir, index) = random_index(0, 1, immutable);
+#[case(IndexType::Mutable)]
+#[case(IndexType::Immutable)]
+#[case(IndexType::Mmap)]
+fn test_empty_cardinality(#[case] index_type: IndexType) {
+    let (_temp_dir, index) = random_index(0, 1, index_type);
     cardinality_request(
         &index,
         Range {
@@ -393,7 +478,7 @@ fn test_empty_cardinality(#[case] immutable: bool) {
         },
     );
 
-    let (_temp_dir, index) = random_index(0, 0, immutable);
+    let (_temp_dir, index) = random_index(0, 0, index_type);
     cardinality_request(
         &index,
         Range {
@@ -4,10 +4,11 @@ use io::file_operations::{atomic_save_json, read_json};
 use serde::{Deserialize, Serialize};
 
 use crate::common::operation_error::OperationResult;
+use crate::types::PayloadKeyType;
 
 pub const HNSW_INDEX_CONFIG_FILE: &str = "hnsw_config.json";
 
-#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct HnswGraphConfig {
     pub m: usize,
     /// Requested M
@@ -29,6 +30,9 @@ pub struct HnswGraphConfig {
     pub payload_m0: Option<usize>,
     #[serde(default)]
     pub indexed_vector_count: Option<usize>,
+    /// Payload fields, for which additional links are built in the graph
+    #[serde(default)]
+    pub payload_fields: Vec<PayloadKeyType>,
 }
 
 impl HnswGraphConfig {
@@ -50,6 +54,7 @@ impl HnswGraphConfig {
             payload_m,
             payload_m0: payload_m.map(|v| v * 2),
             indexed_vector_count: Some(indexed_vector_count),
+            payload_fields: Vec::new(),
         }
     }
 
@@ -91,6 +91,33 @@ impl EntryPoints {
         None
     }
 
+    /// Remove points, for which `is_removed` is true, from the entry points.
+    ///
+    /// Returns `true` if any entry point is removed, remaining points of the graph
+    /// should be added again with [`EntryPoints::new_point`] to replace them.
+    pub fn remove_points<F>(&mut self, is_removed: F) -> bool
+    where
+        F: Fn(PointOffsetType) -> bool,
+    {
+        let entry_points_count = self.entry_points.len();
+        self.entry_points
+            .retain(|entry| !is_removed(entry.point_id));
+
+        let extra_entry_points = std::mem::replace(
+            &mut self.extra_entry_points,
+            FixedLengthPriorityQueue::new(self.extra_entry_points.len().max(1)),
+        );
+        let extra_entry_points_count = extra_entry_points.len();
+        for entry in extra_entry_points.into_vec() {
+            if !is_removed(entry.point_id) {
+                self.extra_entry_points.push(entry);
+            }
+        }
+
+        self.entry_points.len() < entry_points_count
+            || self.extra_entry_points.len() < extra_entry_points_count
+    }
+
     /// Find the highest `EntryPoint` which satisfies filtering condition of `checker`
     pub fn get_entry_point<F>(&self, checker: F) -> Option<EntryPoint>
     where
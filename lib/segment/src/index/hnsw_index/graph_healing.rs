@@ -0,0 +1,158 @@
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
+
+use super::entry_points::EntryPoints;
+use super::graph_layers::{GraphLayers, GraphLayersBase};
+use super::graph_links::{GraphLinks, GraphLinksConverter};
+use crate::common::operation_error::{check_process_stopped, OperationResult};
+use crate::index::visited_pool::VisitedPool;
+
+/// Links of a graph, in which deleted points are dropped and their neighbours are reconnected
+pub struct HealedGraph {
+    /// Links on each level, by point id. Deleted points have no links
+    edges: Vec<Vec<Vec<PointOffsetType>>>,
+    entry_points: EntryPoints,
+    /// Number of not deleted points in the graph
+    point_count: usize,
+}
+
+impl HealedGraph {
+    pub fn point_count(&self) -> usize {
+        self.point_count
+    }
+
+    /// Drop points, for which `is_deleted` is true, together with links to them.
+    ///
+    /// Neighbours of such points are not reconnected, which is used for points deleted
+    /// after the graph was healed.
+    pub fn remove_deleted<D>(&mut self, is_deleted: D)
+    where
+        D: Fn(PointOffsetType) -> bool,
+    {
+        let mut point_levels = Vec::new();
+        for (point_id, point_edges) in self.edges.iter_mut().enumerate() {
+            let point_id = point_id as PointOffsetType;
+            if is_deleted(point_id) {
+                *point_edges = vec![vec![]];
+                continue;
+            }
+            for links in point_edges.iter_mut() {
+                links.retain(|&link| !is_deleted(link));
+            }
+            point_levels.push((point_id, point_edges.len() - 1));
+        }
+
+        if self.entry_points.remove_points(&is_deleted) {
+            for &(point_id, level) in &point_levels {
+                self.entry_points
+                    .new_point(point_id, level, |_point_id| true);
+            }
+        }
+        self.point_count = point_levels.len();
+    }
+}
+
+impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
+    /// Drop deleted points from the graph and reconnect their neighbours.
+    ///
+    /// Links to not deleted points are kept as they are. Each link to a deleted point is replaced
+    /// by one of the not deleted links of that deleted point, which are selected with the same
+    /// heuristic as on build.
+    pub fn heal<D, F>(
+        &self,
+        is_deleted: D,
+        score_internal: F,
+        stopped: &AtomicBool,
+    ) -> OperationResult<HealedGraph>
+    where
+        D: Fn(PointOffsetType) -> bool,
+        F: Fn(PointOffsetType, PointOffsetType) -> ScoreType,
+    {
+        let num_points = self.num_points();
+        let mut edges = Vec::with_capacity(num_points);
+
+        for point_id in 0..num_points as PointOffsetType {
+            check_process_stopped(stopped)?;
+
+            if is_deleted(point_id) {
+                edges.push(vec![vec![]]);
+                continue;
+            }
+
+            let level = self.point_level(point_id);
+            let mut point_edges = Vec::with_capacity(level + 1);
+            for curr_level in 0..=level {
+                let links = self.links.links(point_id, curr_level);
+                let (deleted_links, mut kept_links): (Vec<_>, Vec<_>) =
+                    links.iter().copied().partition(|&link| is_deleted(link));
+
+                let mut candidates: Vec<_> = deleted_links
+                    .iter()
+                    .flat_map(|&deleted_link| self.links.links(deleted_link, curr_level))
+                    .copied()
+                    .filter(|&link| {
+                        link != point_id && !is_deleted(link) && !kept_links.contains(&link)
+                    })
+                    .collect();
+                candidates.sort_unstable();
+                candidates.dedup();
+
+                let mut scored_candidates: Vec<_> = candidates
+                    .into_iter()
+                    .map(|idx| ScoredPointOffset {
+                        idx,
+                        score: score_internal(point_id, idx),
+                    })
+                    .collect();
+                // Closest candidates first
+                scored_candidates.sort_unstable();
+                scored_candidates.reverse();
+
+                // Same heuristic as on build, with the kept links as already selected ones
+                let max_links = kept_links.len() + deleted_links.len();
+                for candidate in scored_candidates {
+                    if kept_links.len() >= max_links {
+                        break;
+                    }
+                    let is_good = kept_links.iter().all(|&selected| {
+                        score_internal(candidate.idx, selected) <= candidate.score
+                    });
+                    if is_good {
+                        kept_links.push(candidate.idx);
+                    }
+                }
+                point_edges.push(kept_links);
+            }
+            edges.push(point_edges);
+        }
+
+        let mut healed_graph = HealedGraph {
+            edges,
+            entry_points: self.entry_points.clone(),
+            point_count: 0,
+        };
+        healed_graph.remove_deleted(is_deleted);
+        Ok(healed_graph)
+    }
+
+    /// Graph with the links of `healed`, which are saved to `links_path`
+    pub fn with_healed(&self, healed: HealedGraph, links_path: &Path) -> OperationResult<Self> {
+        let HealedGraph {
+            edges,
+            entry_points,
+            point_count: _,
+        } = healed;
+        let mut links = GraphLinksConverter::new(edges);
+        links.save_as(links_path)?;
+        Ok(Self {
+            m: self.m,
+            m0: self.m0,
+            ef_construct: self.ef_construct,
+            links: TGraphLinks::from_converter(links)?,
+            entry_points,
+            visited_pool: VisitedPool::new(),
+        })
+    }
+}
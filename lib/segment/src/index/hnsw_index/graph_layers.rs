@@ -90,6 +90,64 @@ pub trait GraphLayersBase {
         }
     }
 
+    /// Greedy search within a single graph layer, which also explores neighbours of the
+    /// neighbours, that don't match the filter (ACORN-style two-hop traversal).
+    ///
+    /// With selective filters, most direct neighbours are filtered out and the search gets stuck
+    /// in a disconnected region of the graph. Stepping over filtered out points keeps it going.
+    fn _search_on_level_two_hop(
+        &self,
+        searcher: &mut SearchContext,
+        level: usize,
+        visited_list: &mut VisitedListHandle,
+        points_scorer: &mut FilteredScorer,
+    ) {
+        let limit = self.get_m(level);
+        let mut points_ids: Vec<PointOffsetType> = Vec::with_capacity(2 * limit);
+        let mut filtered_out: Vec<PointOffsetType> = Vec::with_capacity(limit);
+
+        while let Some(candidate) = searcher.candidates.pop() {
+            if candidate.score < searcher.lower_bound() {
+                break;
+            }
+
+            points_ids.clear();
+            filtered_out.clear();
+            self.links_map(candidate.idx, level, |link| {
+                if !visited_list.check(link) {
+                    if points_scorer.check_vector(link) {
+                        points_ids.push(link);
+                    } else {
+                        filtered_out.push(link);
+                    }
+                }
+            });
+
+            for &hop in &filtered_out {
+                // Same budget of scored points per candidate as in the regular search
+                if points_ids.len() >= limit {
+                    break;
+                }
+                // Filtered out points are never scored, expand each of them only once
+                visited_list.check_and_update_visited(hop);
+                self.links_map(hop, level, |link| {
+                    if !visited_list.check(link)
+                        && !points_ids.contains(&link)
+                        && points_scorer.check_vector(link)
+                    {
+                        points_ids.push(link);
+                    }
+                });
+            }
+
+            let scores = points_scorer.score_points(&mut points_ids, limit);
+            scores.iter().copied().for_each(|score_point| {
+                searcher.process_candidate(score_point);
+                visited_list.check_and_update_visited(score_point.idx);
+            });
+        }
+    }
+
     fn search_on_level(
         &self,
         level_entry: ScoredPointOffset,
@@ -105,6 +163,21 @@ pub trait GraphLayersBase {
         search_context.nearest
     }
 
+    fn search_on_level_two_hop(
+        &self,
+        level_entry: ScoredPointOffset,
+        level: usize,
+        ef: usize,
+        points_scorer: &mut FilteredScorer,
+    ) -> FixedLengthPriorityQueue<ScoredPointOffset> {
+        let mut visited_list = self.get_visited_list_from_pool();
+        visited_list.check_and_update_visited(level_entry.idx);
+        let mut search_context = SearchContext::new(level_entry, ef);
+
+        self._search_on_level_two_hop(&mut search_context, level, &mut visited_list, points_scorer);
+        search_context.nearest
+    }
+
     /// Greedy searches for entry point of level `target_level`.
     /// Beam size is 1.
     fn search_entry(
@@ -202,11 +275,34 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
     }
 
     pub fn search(
+        &self,
+        top: usize,
+        ef: usize,
+        points_scorer: FilteredScorer,
+        custom_entry_points: Option<&[PointOffsetType]>,
+    ) -> Vec<ScoredPointOffset> {
+        self.search_impl(top, ef, points_scorer, custom_entry_points, false)
+    }
+
+    /// Same as [`Self::search`], but the zero level is traversed over two hops through points,
+    /// which don't match the filter. Useful for filters, which break the graph connectivity.
+    pub fn search_two_hop(
+        &self,
+        top: usize,
+        ef: usize,
+        points_scorer: FilteredScorer,
+        custom_entry_points: Option<&[PointOffsetType]>,
+    ) -> Vec<ScoredPointOffset> {
+        self.search_impl(top, ef, points_scorer, custom_entry_points, true)
+    }
+
+    fn search_impl(
         &self,
         top: usize,
         ef: usize,
         mut points_scorer: FilteredScorer,
         custom_entry_points: Option<&[PointOffsetType]>,
+        two_hop: bool,
     ) -> Vec<ScoredPointOffset> {
         let Some(entry_point) = self.get_entry_point(&points_scorer, custom_entry_points) else {
             return Vec::default();
@@ -218,7 +314,11 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
             0,
             &mut points_scorer,
         );
-        let nearest = self.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer);
+        let nearest = if two_hop {
+            self.search_on_level_two_hop(zero_level_entry, 0, max(top, ef), &mut points_scorer)
+        } else {
+            self.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer)
+        };
         nearest.into_iter().take(top).collect_vec()
     }
 
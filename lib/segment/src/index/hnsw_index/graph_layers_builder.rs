@@ -455,6 +455,61 @@ impl GraphLayersBuilder {
             });
     }
 
+    /// Add a point with already known links, e.g. taken from a previously built graph.
+    ///
+    /// Levels of the point must be set before. Links to points, for which `is_deleted` is true,
+    /// are dropped. Neighbours of the point are not updated.
+    ///
+    /// Links of a previously built graph also contain the links of its payload subgraphs.
+    /// Those are built again, so links exceeding `m` of the level are pruned with the heuristic.
+    pub fn add_linked_point(
+        &self,
+        point_id: PointOffsetType,
+        links: &[Vec<PointOffsetType>],
+        is_deleted: impl Fn(PointOffsetType) -> bool,
+        mut score_internal: impl FnMut(PointOffsetType, PointOffsetType) -> ScoreType,
+    ) {
+        let level = self.get_point_level(point_id);
+        for (curr_level, level_links) in links.iter().enumerate().take(level + 1) {
+            let level_m = self.get_m(curr_level);
+            let mut new_links: Vec<_> = level_links
+                .iter()
+                .copied()
+                .filter(|&link| !is_deleted(link))
+                .collect();
+            if new_links.len() > level_m {
+                let mut candidates: Vec<_> = new_links
+                    .iter()
+                    .map(|&idx| ScoredPointOffset {
+                        idx,
+                        score: score_internal(point_id, idx),
+                    })
+                    .collect();
+                candidates.sort_unstable_by(|a, b| b.score.total_cmp(&a.score));
+                new_links = if self.use_heuristic {
+                    Self::select_candidate_with_heuristic_from_sorted(
+                        candidates.into_iter(),
+                        level_m,
+                        &mut score_internal,
+                    )
+                } else {
+                    candidates
+                        .into_iter()
+                        .take(level_m)
+                        .map(|candidate| candidate.idx)
+                        .collect()
+                };
+            }
+            let mut point_links = self.links_layers[point_id as usize][curr_level].write();
+            point_links.clear();
+            point_links.extend(new_links);
+        }
+        self.ready_list.write().set(point_id as usize, true);
+        self.entry_points
+            .lock()
+            .new_point(point_id, level, |_point_id| true);
+    }
+
     /// This function returns average number of links per node in HNSW graph
     /// on specified level.
     ///
@@ -832,6 +887,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_add_linked_point_prunes_links() {
+        const NUM_VECTORS: usize = 100;
+        const DIM: usize = 16;
+        const M: usize = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let vector_holder = TestRawScorerProducer::<EuclidMetric>::new(DIM, NUM_VECTORS, &mut rng);
+        let scorer = vector_holder
+            .get_raw_scorer(random_vector(&mut rng, DIM))
+            .unwrap();
+
+        let graph_layers_builder = GraphLayersBuilder::new(NUM_VECTORS, M, M * 2, 32, 1, false);
+
+        // Links of the old graph, merged with links of payload subgraphs
+        let old_links = vec![(1..NUM_VECTORS as PointOffsetType).collect_vec()];
+        let is_deleted = |link: PointOffsetType| link % 10 == 0;
+
+        graph_layers_builder.add_linked_point(0, &old_links, is_deleted, |a, b| {
+            scorer.score_internal(a, b)
+        });
+
+        let mut expected = old_links[0]
+            .iter()
+            .copied()
+            .filter(|&link| !is_deleted(link))
+            .collect_vec();
+        expected.sort_by(|&a, &b| {
+            scorer
+                .score_internal(0, b)
+                .total_cmp(&scorer.score_internal(0, a))
+        });
+        expected.truncate(M * 2);
+
+        let links = graph_layers_builder.links_layers[0][0].read().clone();
+        assert_eq!(links, expected);
+    }
+
     #[test]
     fn test_connect_new_point() {
         let num_points = 10;
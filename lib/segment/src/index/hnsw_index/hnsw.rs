@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
@@ -17,7 +18,10 @@ use rand::thread_rng;
 use rayon::prelude::*;
 use rayon::ThreadPool;
 
+use super::graph_healing::HealedGraph;
 use super::graph_links::{GraphLinks, GraphLinksMmap};
+use super::old_graph::OldGraph;
+use super::representatives::Representatives;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
@@ -26,12 +30,13 @@ use crate::common::BYTES_IN_KB;
 use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::{QueryVector, Vector, VectorRef};
 use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::CardinalityEstimation;
 use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
 use crate::index::hnsw_index::config::HnswGraphConfig;
 use crate::index::hnsw_index::graph_layers::GraphLayers;
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
-use crate::index::query_estimator::adjust_to_available_vectors;
+use crate::index::query_estimator::{adjust_to_available_vectors, is_two_hop_selectivity};
 use crate::index::sample_estimation::sample_check_cardinality;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
@@ -39,13 +44,13 @@ use crate::index::{PayloadIndex, VectorIndex};
 use crate::telemetry::VectorIndexSearchesTelemetry;
 use crate::types::Condition::Field;
 use crate::types::{
-    default_quantization_ignore_value, default_quantization_oversampling_value, FieldCondition,
-    Filter, HnswConfig, QuantizationSearchParams, SearchParams,
+    default_quantization_ignore_value, default_quantization_oversampling_value, Condition,
+    FieldCondition, Filter, HnswConfig, QuantizationSearchParams, SearchParams,
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::query::DiscoveryQuery;
 use crate::vector_storage::{
-    new_raw_scorer, new_stoppable_raw_scorer, RawScorer, VectorStorage, VectorStorageEnum,
+    new_stoppable_raw_scorer, RawScorer, VectorStorage, VectorStorageEnum,
 };
 
 const HNSW_USE_HEURISTIC: bool = true;
@@ -65,6 +70,10 @@ pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     config: HnswGraphConfig,
     path: PathBuf,
     graph: Option<GraphLayers<TGraphLinks>>,
+    /// Single vectors of multivector points, the graph is built over them if present
+    representatives: Option<Representatives>,
+    /// Graph of another segment to start the build from
+    old_graph: Option<OldGraph>,
     searches_telemetry: HNSWSearchesTelemetry,
 }
 
@@ -75,6 +84,76 @@ struct HNSWSearchesTelemetry {
     large_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
     exact_filtered: Arc<Mutex<OperationDurationsAggregator>>,
     exact_unfiltered: Arc<Mutex<OperationDurationsAggregator>>,
+    /// Searches with range or geo conditions, which are served by range and geo payload blocks
+    range_plain: Arc<Mutex<OperationDurationsAggregator>>,
+    range_hnsw: Arc<Mutex<OperationDurationsAggregator>>,
+    geo_plain: Arc<Mutex<OperationDurationsAggregator>>,
+    geo_hnsw: Arc<Mutex<OperationDurationsAggregator>>,
+    /// Filtered graph searches by the kind of traversal
+    one_hop: Arc<Mutex<OperationDurationsAggregator>>,
+    two_hop: Arc<Mutex<OperationDurationsAggregator>>,
+}
+
+impl HNSWSearchesTelemetry {
+    /// Additional timer for filtered searches with range or geo conditions
+    fn payload_block_timer(
+        &self,
+        filter: &Filter,
+        hnsw: bool,
+    ) -> Option<ScopeDurationMeasurer<'_>> {
+        let aggregator = if has_field_condition(filter, &|condition| condition.range.is_some()) {
+            if hnsw {
+                &self.range_hnsw
+            } else {
+                &self.range_plain
+            }
+        } else if has_field_condition(filter, &|condition| {
+            condition.geo_bounding_box.is_some()
+                || condition.geo_radius.is_some()
+                || condition.geo_polygon.is_some()
+        }) {
+            if hnsw {
+                &self.geo_hnsw
+            } else {
+                &self.geo_plain
+            }
+        } else {
+            return None;
+        };
+        Some(ScopeDurationMeasurer::new(aggregator))
+    }
+}
+
+/// Whether the point or its vector is deleted
+fn is_deleted_point(
+    deleted_points: &BitSlice,
+    deleted_vectors: &BitSlice,
+    point_id: PointOffsetType,
+) -> bool {
+    let point_id = point_id as usize;
+    deleted_points.get(point_id).map(|x| *x).unwrap_or(false)
+        || deleted_vectors.get(point_id).map(|x| *x).unwrap_or(false)
+}
+
+/// Whether any of the conditions, which points must satisfy, matches `check`
+fn has_field_condition(filter: &Filter, check: &dyn Fn(&FieldCondition) -> bool) -> bool {
+    filter
+        .must
+        .iter()
+        .flatten()
+        .chain(filter.should.iter().flatten())
+        .chain(
+            filter
+                .min_should
+                .iter()
+                .flat_map(|min_should| &min_should.conditions),
+        )
+        .any(|condition| match condition {
+            Condition::Field(field_condition) => check(field_condition),
+            Condition::Nested(nested) => has_field_condition(nested.filter(), check),
+            Condition::Filter(filter) => has_field_condition(filter, check),
+            Condition::IsEmpty(_) | Condition::IsNull(_) | Condition::HasId(_) => false,
+        })
 }
 
 impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
@@ -122,6 +201,12 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         } else {
             None
         };
+        // Graphs built before representatives were configured keep using the full vectors
+        let representatives = if graph.is_none() || Representatives::exist(path) {
+            Representatives::open(path, &vector_storage.borrow())?
+        } else {
+            None
+        };
         Ok(HNSWIndex {
             id_tracker,
             vector_storage,
@@ -130,6 +215,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             config,
             path: path.to_owned(),
             graph,
+            representatives,
+            old_graph: None,
             searches_telemetry: HNSWSearchesTelemetry {
                 unfiltered_hnsw: OperationDurationsAggregator::new(),
                 unfiltered_plain: OperationDurationsAggregator::new(),
@@ -137,6 +224,12 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 large_cardinality: OperationDurationsAggregator::new(),
                 exact_filtered: OperationDurationsAggregator::new(),
                 exact_unfiltered: OperationDurationsAggregator::new(),
+                range_plain: OperationDurationsAggregator::new(),
+                range_hnsw: OperationDurationsAggregator::new(),
+                geo_plain: OperationDurationsAggregator::new(),
+                geo_hnsw: OperationDurationsAggregator::new(),
+                one_hop: OperationDurationsAggregator::new(),
+                two_hop: OperationDurationsAggregator::new(),
             },
         })
     }
@@ -146,6 +239,105 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         self.graph.as_ref()
     }
 
+    /// Links of the built graph, remapped to the internal ids of a new segment
+    pub fn old_graph(
+        &self,
+        id_mapping: &HashMap<PointOffsetType, PointOffsetType>,
+    ) -> Option<OldGraph> {
+        let graph = self.graph.as_ref()?;
+        Some(OldGraph::new(graph, id_mapping))
+    }
+
+    /// Build the index starting from the links of `old_graph`, only new points are inserted
+    pub fn set_old_graph(&mut self, old_graph: OldGraph) {
+        self.old_graph = Some(old_graph);
+    }
+
+    /// Fraction of indexed vectors, which are deleted since the graph was built.
+    ///
+    /// Both deleted points and deleted vectors of remaining points are counted.
+    pub fn deleted_ratio(&self) -> f64 {
+        let indexed_vector_count = self.indexed_vector_count();
+        if indexed_vector_count == 0 {
+            return 0.0;
+        }
+        let vector_storage = self.vector_storage.borrow();
+        let live_vector_count = self
+            .id_tracker
+            .borrow()
+            .iter_ids_excluding(vector_storage.deleted_vector_bitslice())
+            .count();
+        indexed_vector_count.saturating_sub(live_vector_count) as f64 / indexed_vector_count as f64
+    }
+
+    /// Reconnect neighbours of deleted vectors in the graph.
+    ///
+    /// The index is not changed, the result is applied with [`HNSWIndex::apply_healed_graph`].
+    pub fn heal_graph(&self, stopped: &AtomicBool) -> OperationResult<Option<HealedGraph>> {
+        let Some(graph) = &self.graph else {
+            return Ok(None);
+        };
+
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_vectors = self.quantized_vectors.borrow();
+
+        let Some(first_id) = id_tracker
+            .iter_ids_excluding(vector_storage.deleted_vector_bitslice())
+            .next()
+        else {
+            return Ok(None);
+        };
+
+        // Only scores between stored points are used, query is not important
+        let raw_scorer = self.point_raw_scorer(
+            first_id,
+            &vector_storage,
+            quantized_vectors.as_ref(),
+            id_tracker.deleted_point_bitslice(),
+            stopped,
+        )?;
+
+        let deleted_points = id_tracker.deleted_point_bitslice();
+        let deleted_vectors = vector_storage.deleted_vector_bitslice();
+        let is_deleted = |point_id| is_deleted_point(deleted_points, deleted_vectors, point_id);
+
+        let healed_graph =
+            graph.heal(is_deleted, |a, b| raw_scorer.score_internal(a, b), stopped)?;
+        Ok(Some(healed_graph))
+    }
+
+    /// Replace links of the graph with the healed ones and save the index
+    ///
+    /// Points, deleted after the graph was healed, are dropped from the healed graph.
+    /// Healed links replace the links file only after the rest of the index is saved.
+    pub fn apply_healed_graph(&mut self, mut healed_graph: HealedGraph) -> OperationResult<()> {
+        let Some(graph) = &self.graph else {
+            return Ok(());
+        };
+        {
+            let id_tracker = self.id_tracker.borrow();
+            let vector_storage = self.vector_storage.borrow();
+            let deleted_points = id_tracker.deleted_point_bitslice();
+            let deleted_vectors = vector_storage.deleted_vector_bitslice();
+            healed_graph.remove_deleted(|point_id| {
+                is_deleted_point(deleted_points, deleted_vectors, point_id)
+            });
+        }
+        let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(&self.path);
+        let healed_links_path = graph_links_path.with_extension("healed");
+        let indexed_vector_count = healed_graph.point_count();
+        self.graph = Some(graph.with_healed(healed_graph, &healed_links_path)?);
+        self.config
+            .indexed_vector_count
+            .replace(indexed_vector_count);
+        self.save()?;
+        // Entry points of the healed graph are present in the old links as well, so the index
+        // stays consistent if the process stops before the rename
+        std::fs::rename(healed_links_path, graph_links_path)?;
+        Ok(())
+    }
+
     pub fn get_quantized_vectors(&self) -> Arc<AtomicRefCell<Option<QuantizedVectors>>> {
         self.quantized_vectors.clone()
     }
@@ -215,19 +407,13 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let insert_points = |block_point_id| {
             check_process_stopped(stopped)?;
 
-            let vector = vector_storage.get_vector(block_point_id);
-            let vector = vector.as_vec_ref().into();
-            let raw_scorer = match quantized_vectors.as_ref() {
-                Some(quantized_storage) => quantized_storage.raw_scorer(
-                    vector,
-                    id_tracker.deleted_point_bitslice(),
-                    deleted_bitslice,
-                    stopped,
-                ),
-                None => {
-                    new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice())
-                }
-            }?;
+            let raw_scorer = self.point_raw_scorer(
+                block_point_id,
+                &vector_storage,
+                quantized_vectors.as_ref(),
+                id_tracker.deleted_point_bitslice(),
+                stopped,
+            )?;
             let block_condition_checker = BuildConditionChecker {
                 filter_list: block_filter_list,
                 current_point: block_point_id,
@@ -274,9 +460,18 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let ef = params
             .and_then(|params| params.hnsw_ef)
             .unwrap_or(self.config.ef);
+        let two_hop = filter.is_some() && params.and_then(|params| params.acorn).unwrap_or(false);
 
         let is_stopped = vector_query_context.is_stopped();
 
+        let _hop_timer = filter.map(|_| {
+            ScopeDurationMeasurer::new(if two_hop {
+                &self.searches_telemetry.two_hop
+            } else {
+                &self.searches_telemetry.one_hop
+            })
+        });
+
         let id_tracker = self.id_tracker.borrow();
         let payload_index = self.payload_index.borrow();
         let vector_storage = self.vector_storage.borrow();
@@ -286,29 +481,94 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             .deleted_points()
             .unwrap_or(id_tracker.deleted_point_bitslice());
 
-        let raw_scorer = Self::construct_search_scorer(
-            vector,
-            &vector_storage,
-            quantized_vectors.as_ref(),
-            deleted_points,
-            params,
-            &is_stopped,
-        )?;
         let oversampled_top = Self::get_oversampled_top(quantized_vectors.as_ref(), params, top);
+        let (raw_scorer, search_top) = match &self.representatives {
+            // Traverse the graph with the representative of the query, all found candidates
+            // are re-scored with the full multivectors afterwards
+            Some(representatives) => {
+                let raw_scorer = representatives.raw_scorer(
+                    representatives.query(vector)?,
+                    deleted_points,
+                    vector_storage.deleted_vector_bitslice(),
+                    &is_stopped,
+                )?;
+                (raw_scorer, oversampled_top.max(ef))
+            }
+            None => {
+                let raw_scorer = Self::construct_search_scorer(
+                    vector,
+                    &vector_storage,
+                    quantized_vectors.as_ref(),
+                    deleted_points,
+                    params,
+                    &is_stopped,
+                )?;
+                (raw_scorer, oversampled_top)
+            }
+        };
 
         let filter_context = filter.map(|f| payload_index.filter_context(f));
         let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
 
         match &self.graph {
             Some(graph) => {
-                let search_result =
-                    graph.search(oversampled_top, ef, points_scorer, custom_entry_points);
-                self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
+                let search_result = if two_hop {
+                    graph.search_two_hop(search_top, ef, points_scorer, custom_entry_points)
+                } else {
+                    graph.search(search_top, ef, points_scorer, custom_entry_points)
+                };
+                // Scores of representatives only approximate the scores of the full multivectors
+                let force_rescore = self.representatives.is_some();
+                self.postprocess_search_result(
+                    search_result,
+                    vector,
+                    params,
+                    top,
+                    force_rescore,
+                    &is_stopped,
+                )
             }
             None => Ok(Default::default()),
         }
     }
 
+    /// Search params of a filtered graph search, in which two-hop traversal is enabled for
+    /// filters of medium selectivity, unless it is set explicitly
+    ///
+    /// Filters, which are served by additional links of a payload field, keep the regular
+    /// traversal, as matching points are already connected with each other.
+    fn filtered_graph_params(
+        &self,
+        filter: &Filter,
+        params: Option<&SearchParams>,
+        query_cardinality: &CardinalityEstimation,
+        available_vector_count: usize,
+    ) -> SearchParams {
+        let mut params = params.copied().unwrap_or_default();
+        params.acorn = params.acorn.or_else(|| {
+            Some(
+                !self.has_payload_links(filter)
+                    && is_two_hop_selectivity(query_cardinality, available_vector_count),
+            )
+        });
+        params
+    }
+
+    /// Whether points must match a condition on a payload field, for which additional links
+    /// are built in the graph
+    fn has_payload_links(&self, filter: &Filter) -> bool {
+        filter
+            .must
+            .iter()
+            .flatten()
+            .any(|condition| match condition {
+                Condition::Field(field_condition) => {
+                    self.config.payload_fields.contains(&field_condition.key)
+                }
+                _ => false,
+            })
+    }
+
     fn search_vectors_with_graph(
         &self,
         vectors: &[&QueryVector],
@@ -365,7 +625,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let search_result =
             raw_scorer.peek_top_iter(&mut filtered_points.iter().copied(), oversampled_top);
 
-        self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
+        self.postprocess_search_result(search_result, vector, params, top, false, &is_stopped)
     }
 
     fn search_vectors_plain(
@@ -460,6 +720,37 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         }
     }
 
+    /// Scorer with a stored point as the query, which compares points in the same way as
+    /// on graph build
+    fn point_raw_scorer<'a>(
+        &'a self,
+        point_id: PointOffsetType,
+        vector_storage: &'a VectorStorageEnum,
+        quantized_storage: Option<&'a QuantizedVectors>,
+        deleted_points: &'a BitSlice,
+        stopped: &'a AtomicBool,
+    ) -> OperationResult<Box<dyn RawScorer + 'a>> {
+        if let Some(representatives) = &self.representatives {
+            return representatives.raw_scorer(
+                representatives.point_query(point_id),
+                deleted_points,
+                vector_storage.deleted_vector_bitslice(),
+                stopped,
+            );
+        }
+        let vector = vector_storage.get_vector(point_id);
+        let vector = vector.as_vec_ref().into();
+        match quantized_storage {
+            Some(quantized_storage) => quantized_storage.raw_scorer(
+                vector,
+                deleted_points,
+                vector_storage.deleted_vector_bitslice(),
+                stopped,
+            ),
+            None => new_stoppable_raw_scorer(vector, vector_storage, deleted_points, stopped),
+        }
+    }
+
     fn get_oversampled_top(
         quantized_storage: Option<&QuantizedVectors>,
         params: Option<&SearchParams>,
@@ -486,6 +777,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         vector: &QueryVector,
         params: Option<&SearchParams>,
         top: usize,
+        force_rescore: bool,
         is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
         let id_tracker = self.id_tracker.borrow();
@@ -498,11 +790,12 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             .as_ref()
             .map(|q| q.default_rescoring())
             .unwrap_or(false);
-        let rescore = quantization_enabled
-            && params
-                .and_then(|p| p.quantization)
-                .and_then(|q| q.rescore)
-                .unwrap_or(default_rescoring);
+        let rescore = force_rescore
+            || (quantization_enabled
+                && params
+                    .and_then(|p| p.quantization)
+                    .and_then(|q| q.rescore)
+                    .unwrap_or(default_rescoring));
 
         let mut postprocess_result = if rescore {
             let raw_scorer = new_stoppable_raw_scorer(
@@ -627,6 +920,9 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     // if cardinality is small - use plain index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    let _block_timer = self
+                        .searches_telemetry
+                        .payload_block_timer(query_filter, false);
                     return self.search_vectors_plain(
                         vectors,
                         query_filter,
@@ -640,11 +936,20 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
+                    let _block_timer = self
+                        .searches_telemetry
+                        .payload_block_timer(query_filter, true);
+                    let graph_params = self.filtered_graph_params(
+                        query_filter,
+                        params,
+                        &query_cardinality,
+                        available_vector_count,
+                    );
                     return self.search_vectors_with_graph(
                         vectors,
                         filter,
                         top,
-                        params,
+                        Some(&graph_params),
                         query_context,
                     );
                 }
@@ -662,11 +967,29 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    self.search_vectors_with_graph(vectors, filter, top, params, query_context)
+                    let _block_timer = self
+                        .searches_telemetry
+                        .payload_block_timer(query_filter, true);
+                    let graph_params = self.filtered_graph_params(
+                        query_filter,
+                        params,
+                        &query_cardinality,
+                        available_vector_count,
+                    );
+                    self.search_vectors_with_graph(
+                        vectors,
+                        filter,
+                        top,
+                        Some(&graph_params),
+                        query_context,
+                    )
                 } else {
                     // if cardinality is small - use plain index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    let _block_timer = self
+                        .searches_telemetry
+                        .payload_block_timer(query_filter, false);
                     self.search_vectors_plain(vectors, query_filter, top, params, query_context)
                 }
             }
@@ -679,6 +1002,11 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
         stopped: &AtomicBool,
         _tick_progress: impl FnMut(),
     ) -> OperationResult<()> {
+        if let Some(representatives) = &mut self.representatives {
+            representatives.build(&self.vector_storage.borrow(), stopped)?;
+        }
+        let old_graph = self.old_graph.take();
+
         // Build main index graph
         let id_tracker = self.id_tracker.borrow();
         let vector_storage = self.vector_storage.borrow();
@@ -732,16 +1060,62 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             })
             .build()?;
 
+        let old_point_links = |vector_id| {
+            old_graph
+                .as_ref()
+                .and_then(|graph| graph.point_links(vector_id))
+        };
+
         for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
             check_process_stopped(stopped)?;
-            let level = graph_layers_builder.get_random_layer(&mut rng);
+            // Points of the old graph keep their levels
+            let level = match old_point_links(vector_id) {
+                Some(links) => links.len() - 1,
+                None => graph_layers_builder.get_random_layer(&mut rng),
+            };
             graph_layers_builder.set_levels(vector_id, level);
         }
 
         let mut indexed_vectors = 0;
 
         if self.config.m > 0 {
-            let mut ids_iterator = id_tracker.iter_ids_excluding(deleted_bitslice);
+            let mut reused_vectors = 0;
+            let first_id = id_tracker.iter_ids_excluding(deleted_bitslice).next();
+            if let (Some(old_graph), Some(first_id)) = (&old_graph, first_id) {
+                debug!(
+                    "reusing links of {} points from old graph",
+                    old_graph.num_points(),
+                );
+                // Only scores between stored points are used to prune links, query is not important
+                let raw_scorer = self.point_raw_scorer(
+                    first_id,
+                    &vector_storage,
+                    quantized_vectors.as_ref(),
+                    id_tracker.deleted_point_bitslice(),
+                    stopped,
+                )?;
+                for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
+                    check_process_stopped(stopped)?;
+                    if let Some(links) = old_point_links(vector_id) {
+                        graph_layers_builder.add_linked_point(
+                            vector_id,
+                            links,
+                            |link| {
+                                deleted_bitslice
+                                    .get(link as usize)
+                                    .map(|x| *x)
+                                    .unwrap_or(false)
+                            },
+                            |a, b| raw_scorer.score_internal(a, b),
+                        );
+                        reused_vectors += 1;
+                    }
+                }
+            }
+
+            let mut ids_iterator = id_tracker
+                .iter_ids_excluding(deleted_bitslice)
+                .filter(|&vector_id| old_point_links(vector_id).is_none());
 
             let first_few_ids: Vec<_> = ids_iterator
                 .by_ref()
@@ -749,22 +1123,17 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                 .collect();
             let ids: Vec<_> = ids_iterator.collect();
 
-            indexed_vectors = ids.len() + first_few_ids.len();
+            indexed_vectors = reused_vectors + ids.len() + first_few_ids.len();
 
             let insert_point = |vector_id| {
                 check_process_stopped(stopped)?;
-                let vector = vector_storage.get_vector(vector_id);
-                let vector = vector.as_vec_ref().into();
-                let raw_scorer = if let Some(quantized_storage) = quantized_vectors.as_ref() {
-                    quantized_storage.raw_scorer(
-                        vector,
-                        id_tracker.deleted_point_bitslice(),
-                        vector_storage.deleted_vector_bitslice(),
-                        stopped,
-                    )
-                } else {
-                    new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice())
-                }?;
+                let raw_scorer = self.point_raw_scorer(
+                    vector_id,
+                    &vector_storage,
+                    quantized_vectors.as_ref(),
+                    id_tracker.deleted_point_bitslice(),
+                    stopped,
+                )?;
                 let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
 
                 graph_layers_builder.link_new_point(vector_id, points_scorer);
@@ -790,6 +1159,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
 
         let payload_index = self.payload_index.borrow();
         let payload_m = self.config.payload_m.unwrap_or(self.config.m);
+        let mut payload_fields = Vec::new();
 
         if payload_m > 0 {
             // Calculate true average number of links per vertex in the HNSW graph
@@ -813,6 +1183,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                 };
                 let min_block_size = indexing_threshold;
 
+                let mut has_blocks = false;
                 for payload_block in payload_index.payload_blocks(&field, min_block_size) {
                     check_process_stopped(stopped)?;
                     if payload_block.cardinality > max_block_size {
@@ -836,6 +1207,10 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                         &mut block_filter_list,
                     )?;
                     graph_layers_builder.merge_from_other(additional_graph);
+                    has_blocks = true;
+                }
+                if has_blocks {
+                    payload_fields.push(field);
                 }
             }
 
@@ -849,6 +1224,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
         }
 
         self.config.indexed_vector_count.replace(indexed_vectors);
+        self.config.payload_fields = payload_fields;
 
         let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(&self.path);
         self.graph = Some(graph_layers_builder.into_graph_layers(Some(&graph_links_path))?);
@@ -877,6 +1253,12 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             filtered_small_cardinality: tm.small_cardinality.lock().get_statistics(detail),
             filtered_large_cardinality: tm.large_cardinality.lock().get_statistics(detail),
             filtered_exact: tm.exact_filtered.lock().get_statistics(detail),
+            filtered_range_plain: tm.range_plain.lock().get_statistics(detail),
+            filtered_range_hnsw: tm.range_hnsw.lock().get_statistics(detail),
+            filtered_geo_plain: tm.geo_plain.lock().get_statistics(detail),
+            filtered_geo_hnsw: tm.geo_hnsw.lock().get_statistics(detail),
+            filtered_one_hop: tm.one_hop.lock().get_statistics(detail),
+            filtered_two_hop: tm.two_hop.lock().get_statistics(detail),
             filtered_sparse: Default::default(),
             unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(detail),
             unfiltered_sparse: Default::default(),
@@ -884,14 +1266,18 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        if self.graph.is_some() {
+        let mut files = if self.graph.is_some() {
             vec![
                 GraphLayers::<TGraphLinks>::get_path(&self.path),
                 GraphLayers::<TGraphLinks>::get_links_path(&self.path),
             ]
         } else {
             vec![]
+        };
+        if let Some(representatives) = &self.representatives {
+            files.extend(representatives.files());
         }
+        files
     }
 
     fn indexed_vector_count(&self) -> usize {
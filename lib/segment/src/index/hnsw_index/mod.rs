@@ -4,11 +4,14 @@ mod build_cache;
 pub mod build_condition_checker;
 mod config;
 mod entry_points;
+pub mod graph_healing;
 pub mod graph_layers;
 pub mod graph_layers_builder;
 pub mod graph_links;
 pub mod hnsw;
+pub mod old_graph;
 pub mod point_scorer;
+mod representatives;
 mod search_context;
 
 #[cfg(test)]
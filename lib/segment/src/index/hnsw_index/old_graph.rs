@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use common::types::PointOffsetType;
+
+use super::graph_layers::GraphLayers;
+use super::graph_links::GraphLinks;
+
+/// Links of an already built HNSW graph, remapped to the internal ids of a new segment.
+///
+/// Points of the old graph keep their links in the new graph, so only the points
+/// which were not in the old graph have to be inserted.
+pub struct OldGraph {
+    /// Links on each level, by new internal id. `None` if the point is not in the old graph
+    links: Vec<Option<Vec<Vec<PointOffsetType>>>>,
+}
+
+impl OldGraph {
+    /// Remap links of `graph` with `id_mapping` (old internal id -> new internal id).
+    ///
+    /// Points missing in the mapping, e.g. deleted ones, are dropped together with links to them.
+    pub(super) fn new<TGraphLinks: GraphLinks>(
+        graph: &GraphLayers<TGraphLinks>,
+        id_mapping: &HashMap<PointOffsetType, PointOffsetType>,
+    ) -> Self {
+        let mut links = Vec::new();
+        for (&old_id, &new_id) in id_mapping {
+            if old_id as usize >= graph.num_points() {
+                continue;
+            }
+            let point_links: Vec<Vec<_>> = (0..=graph.point_level(old_id))
+                .map(|level| {
+                    graph
+                        .links
+                        .links(old_id, level)
+                        .iter()
+                        .filter_map(|old_link| id_mapping.get(old_link).copied())
+                        .collect()
+                })
+                .collect();
+            // Points, which lost all their neighbours, are inserted again
+            if point_links[0].is_empty() && id_mapping.len() > 1 {
+                continue;
+            }
+            if links.len() <= new_id as usize {
+                links.resize(new_id as usize + 1, None);
+            }
+            links[new_id as usize] = Some(point_links);
+        }
+        Self { links }
+    }
+
+    /// Links of the point on each level, if it is taken from the old graph
+    pub fn point_links(&self, point_id: PointOffsetType) -> Option<&[Vec<PointOffsetType>]> {
+        self.links
+            .get(point_id as usize)
+            .and_then(|links| links.as_deref())
+    }
+
+    pub fn num_points(&self) -> usize {
+        self.links.iter().filter(|links| links.is_some()).count()
+    }
+}
@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use bitvec::prelude::BitSlice;
+use common::types::PointOffsetType;
+
+use crate::common::operation_error::{check_process_stopped, OperationResult};
+use crate::data_types::token_pooling::representative_vector;
+use crate::data_types::vectors::{QueryVector, TypedMultiDenseVectorRef, Vector, VectorRef};
+use crate::types::{Distance, MultiVectorRepresentative};
+use crate::vector_storage::dense::appendable_mmap_dense_vector_storage::open_appendable_memmap_vector_storage;
+use crate::vector_storage::raw_scorer::raw_scorer_with_deleted_vectors_impl;
+use crate::vector_storage::{
+    new_stoppable_raw_scorer, MultiVectorStorage, RawScorer, VectorStorage, VectorStorageEnum,
+};
+
+const REPRESENTATIVES_DIR: &str = "representatives";
+
+/// Single dense vector per multivector point, used to build and traverse the HNSW graph
+/// instead of the full multivectors.
+pub struct Representatives {
+    representative: MultiVectorRepresentative,
+    distance: Distance,
+    storage: VectorStorageEnum,
+}
+
+impl Representatives {
+    pub fn exist(index_path: &Path) -> bool {
+        index_path.join(REPRESENTATIVES_DIR).exists()
+    }
+
+    /// Open representatives of the index, if the multivector config asks for them
+    pub fn open(
+        index_path: &Path,
+        vector_storage: &VectorStorageEnum,
+    ) -> OperationResult<Option<Self>> {
+        let Some(representative) = vector_storage
+            .try_multi_vector_config()
+            .and_then(|config| config.representative)
+        else {
+            return Ok(None);
+        };
+        let dim = match vector_storage {
+            VectorStorageEnum::MultiDenseSimple(storage) => storage.vector_dim(),
+            VectorStorageEnum::MultiDenseSimpleByte(storage) => storage.vector_dim(),
+            VectorStorageEnum::MultiDenseSimpleHalf(storage) => storage.vector_dim(),
+            VectorStorageEnum::MultiDenseAppendableMemmap(storage) => storage.vector_dim(),
+            VectorStorageEnum::MultiDenseAppendableMemmapByte(storage) => storage.vector_dim(),
+            VectorStorageEnum::MultiDenseAppendableMemmapHalf(storage) => storage.vector_dim(),
+            _ => return Ok(None),
+        };
+        let distance = vector_storage.distance();
+        let storage = open_appendable_memmap_vector_storage(
+            &index_path.join(REPRESENTATIVES_DIR),
+            dim,
+            distance,
+        )?;
+        Ok(Some(Self {
+            representative,
+            distance,
+            storage,
+        }))
+    }
+
+    /// Compute representatives of all vectors of the storage, deleted vectors stay deleted
+    pub fn build(
+        &mut self,
+        vector_storage: &VectorStorageEnum,
+        stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        for id in 0..vector_storage.total_vector_count() as PointOffsetType {
+            check_process_stopped(stopped)?;
+            let vector = vector_storage.get_vector(id);
+            let multi_vector: TypedMultiDenseVectorRef<f32> = vector.as_vec_ref().try_into()?;
+            let representative =
+                representative_vector(multi_vector, self.representative, self.distance);
+            self.storage
+                .insert_vector(id, VectorRef::from(&representative))?;
+            if vector_storage.is_deleted_vector(id) {
+                self.storage.delete_vector(id)?;
+            }
+        }
+        self.storage.flusher()()?;
+        Ok(())
+    }
+
+    /// Replace multivectors of the query with their representatives
+    pub fn query(&self, query: &QueryVector) -> OperationResult<QueryVector> {
+        query.clone().transform(|vector| match vector {
+            Vector::MultiDense(multi_vector) => Ok(Vector::Dense(representative_vector(
+                TypedMultiDenseVectorRef::from(&multi_vector),
+                self.representative,
+                self.distance,
+            ))),
+            Vector::Dense(_) | Vector::Sparse(_) => Ok(vector),
+        })
+    }
+
+    /// Representative of a stored point, used as a query while building the graph
+    pub fn point_query(&self, id: PointOffsetType) -> QueryVector {
+        self.storage.get_vector(id).as_vec_ref().into()
+    }
+
+    /// Scorer of the representatives.
+    ///
+    /// Vectors deleted after the representatives were built are only marked as deleted in the
+    /// storage of the full multivectors, so `vec_deleted` must come from that storage.
+    pub fn raw_scorer<'a>(
+        &'a self,
+        query: QueryVector,
+        point_deleted: &'a BitSlice,
+        vec_deleted: &'a BitSlice,
+        is_stopped: &'a AtomicBool,
+    ) -> OperationResult<Box<dyn RawScorer + 'a>> {
+        match &self.storage {
+            VectorStorageEnum::DenseAppendableMemmap(storage) => {
+                raw_scorer_with_deleted_vectors_impl(
+                    query,
+                    storage.as_ref(),
+                    point_deleted,
+                    vec_deleted,
+                    is_stopped,
+                )
+            }
+            // Representatives are always opened as appendable mmap storage
+            storage => new_stoppable_raw_scorer(query, storage, point_deleted, is_stopped),
+        }
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.storage.files()
+    }
+}
@@ -1,5 +1,7 @@
 mod test_compact_graph_layer;
 mod test_graph_connectivity;
+mod test_graph_healing;
+mod test_two_hop_search;
 
 use std::path::Path;
 
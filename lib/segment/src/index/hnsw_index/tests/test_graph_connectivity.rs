@@ -47,6 +47,8 @@ fn test_graph_connectivity() {
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::cpu::CpuPermit;
+use common::types::PointOffsetType;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use tempfile::Builder;
+
+use crate::data_types::vectors::{only_default_vector, QueryVector, DEFAULT_VECTOR_NAME};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::hnsw_index::graph_links::{GraphLinks, GraphLinksRam};
+use crate::index::hnsw_index::hnsw::HNSWIndex;
+use crate::index::hnsw_index::num_rayon_threads;
+use crate::index::VectorIndex;
+use crate::segment_constructor::build_segment;
+use crate::types::{
+    Distance, HnswConfig, Indexes, SearchParams, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+
+#[test]
+fn test_graph_healing() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 16;
+    let m = 8;
+    let num_vectors: u64 = 1_000;
+    let ef_construct = 64;
+    let distance = Distance::Cosine;
+    let full_scan_threshold = 10_000;
+    let top = 10;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivec_config: None,
+                datatype: None,
+                validation: None,
+                normalize: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+        sparse_vector_data: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let idx = n.into();
+        let vector = random_vector(&mut rnd, dim);
+
+        segment
+            .upsert_point(n as SeqNumberType, idx, only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let hnsw_config = HnswConfig {
+        m,
+        ef_construct,
+        full_scan_threshold,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+    };
+
+    let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+
+    let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
+        hnsw_dir.path(),
+        segment.id_tracker.clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        Default::default(),
+        segment.payload_index.clone(),
+        hnsw_config,
+    )
+    .unwrap();
+
+    hnsw_index.build_index(permit, &stopped).unwrap();
+    assert_eq!(hnsw_index.deleted_ratio(), 0.0);
+
+    // Delete every third point
+    let deleted_count = (0..num_vectors).step_by(3).count();
+    for n in (0..num_vectors).step_by(3) {
+        segment.delete_point(num_vectors + n, n.into()).unwrap();
+    }
+
+    let expected_ratio = deleted_count as f64 / num_vectors as f64;
+    assert!((hnsw_index.deleted_ratio() - expected_ratio).abs() < 1e-9);
+
+    let graph = hnsw_index.graph().unwrap();
+    let old_links: Vec<Vec<PointOffsetType>> = (0..num_vectors as PointOffsetType)
+        .map(|point_id| graph.links.links(point_id, 0).to_vec())
+        .collect();
+
+    let healed_graph = hnsw_index.heal_graph(&stopped).unwrap().unwrap();
+    assert_eq!(
+        healed_graph.point_count(),
+        num_vectors as usize - deleted_count
+    );
+
+    // Point deleted while the graph is healed is dropped when the healed graph is applied
+    let deleted_after_heal: PointOffsetType = 1;
+    segment
+        .delete_point(2 * num_vectors, u64::from(deleted_after_heal).into())
+        .unwrap();
+
+    hnsw_index.apply_healed_graph(healed_graph).unwrap();
+    assert_eq!(hnsw_index.deleted_ratio(), 0.0);
+
+    let id_tracker = segment.id_tracker.borrow();
+    let deleted_points = id_tracker.deleted_point_bitslice();
+    let graph = hnsw_index.graph().unwrap();
+    for point_id in 0..num_vectors as PointOffsetType {
+        let is_deleted = deleted_points[point_id as usize];
+        let links = graph.links.links(point_id, 0);
+        if is_deleted {
+            assert!(links.is_empty(), "Deleted point {point_id} has links");
+        } else {
+            assert!(!links.is_empty(), "Point {point_id} lost all its links");
+            assert!(
+                links.iter().all(|&link| !deleted_points[link as usize]),
+                "Point {point_id} links to a deleted point",
+            );
+            // Links to not deleted points are kept
+            assert!(
+                old_links[point_id as usize]
+                    .iter()
+                    .filter(|&&link| !deleted_points[link as usize])
+                    .all(|link| links.contains(link)),
+                "Point {point_id} lost a link to a not deleted point",
+            );
+        }
+    }
+    drop(id_tracker);
+
+    let mut sames = 0;
+    let attempts = 20;
+    for _ in 0..attempts {
+        let query = QueryVector::from(random_vector(&mut rnd, dim));
+        let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_index
+            .borrow()
+            .search(&[&query], None, top, None, &Default::default())
+            .unwrap();
+        let index_result = hnsw_index
+            .search(
+                &[&query],
+                None,
+                top,
+                Some(&SearchParams {
+                    hnsw_ef: Some(64),
+                    ..Default::default()
+                }),
+                &Default::default(),
+            )
+            .unwrap();
+        sames += index_result[0]
+            .iter()
+            .filter(|scored| plain_result[0].iter().any(|plain| plain.idx == scored.idx))
+            .count();
+    }
+    let acc = 100.0 * sames as f64 / (attempts * top) as f64;
+    assert!(acc > 80.0, "acc = {acc}");
+}
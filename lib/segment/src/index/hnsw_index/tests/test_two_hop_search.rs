@@ -0,0 +1,77 @@
+use common::types::{PointOffsetType, ScoredPointOffset};
+use itertools::Itertools;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::index::hnsw_index::tests::create_graph_layer_fixture;
+use crate::payload_storage::FilterContext;
+use crate::spaces::simple::CosineMetric;
+
+/// Matches every n-th point
+struct EveryNthFilterContext {
+    n: PointOffsetType,
+}
+
+impl FilterContext for EveryNthFilterContext {
+    fn check(&self, point_id: PointOffsetType) -> bool {
+        point_id % self.n == 0
+    }
+}
+
+#[test]
+/// Check that two-hop traversal finds more of the true nearest points with a selective filter
+fn test_two_hop_search_selective_filter() {
+    let num_vectors = 2000;
+    let num_queries = 50;
+    let m = 8;
+    let dim = 16;
+    let top = 10;
+    let ef = 32;
+
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let (vector_holder, graph_layers) =
+        create_graph_layer_fixture::<CosineMetric, _>(num_vectors, m, dim, true, &mut rng, None);
+
+    let filter_context = EveryNthFilterContext { n: 10 };
+    let filtered_points = (0..num_vectors as PointOffsetType)
+        .filter(|&point_id| filter_context.check(point_id))
+        .collect_vec();
+
+    let mut regular_sames = 0;
+    let mut two_hop_sames = 0;
+    for _ in 0..num_queries {
+        let query = random_vector(&mut rng, dim);
+        let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+
+        let plain_result = raw_scorer.peek_top_iter(&mut filtered_points.iter().copied(), top);
+
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&filter_context));
+        let regular_result = graph_layers.search(top, ef, scorer, None);
+
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&filter_context));
+        let two_hop_result = graph_layers.search_two_hop(top, ef, scorer, None);
+
+        assert!(two_hop_result
+            .iter()
+            .all(|scored| filter_context.check(scored.idx)));
+
+        let count_sames = |result: &[ScoredPointOffset]| {
+            result
+                .iter()
+                .filter(|scored| plain_result.iter().any(|plain| plain.idx == scored.idx))
+                .count()
+        };
+        regular_sames += count_sames(&regular_result);
+        two_hop_sames += count_sames(&two_hop_result);
+    }
+
+    assert!(
+        two_hop_sames >= regular_sames,
+        "two-hop: {two_hop_sames}, regular: {regular_sames}",
+    );
+    let acc = 100.0 * two_hop_sames as f64 / (num_queries * top) as f64;
+    assert!(acc > 70.0, "acc = {acc}");
+}
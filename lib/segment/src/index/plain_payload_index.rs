@@ -346,6 +346,12 @@ impl VectorIndex for PlainIndex {
             filtered_small_cardinality: OperationDurationStatistics::default(),
             filtered_large_cardinality: OperationDurationStatistics::default(),
             filtered_exact: OperationDurationStatistics::default(),
+            filtered_range_plain: OperationDurationStatistics::default(),
+            filtered_range_hnsw: OperationDurationStatistics::default(),
+            filtered_geo_plain: OperationDurationStatistics::default(),
+            filtered_geo_hnsw: OperationDurationStatistics::default(),
+            filtered_one_hop: OperationDurationStatistics::default(),
+            filtered_two_hop: OperationDurationStatistics::default(),
             filtered_sparse: Default::default(),
             unfiltered_exact: OperationDurationStatistics::default(),
             unfiltered_sparse: OperationDurationStatistics::default(),
@@ -74,6 +74,28 @@ pub fn adjust_to_available_vectors(
     }
 }
 
+/// Share of available vectors, below which a filter noticeably breaks the HNSW graph connectivity
+pub const TWO_HOP_MAX_SELECTIVITY: f64 = 0.2;
+
+/// Whether filtered graph search should also explore neighbours of points, which don't match
+/// the filter.
+///
+/// Filters, which select too few points for a graph search, are served by a full scan.
+/// Filters of medium selectivity leave too few links between matching points though,
+/// so the regular graph traversal gets stuck in disconnected regions.
+///
+/// # Arguments
+///
+/// * `estimation` - cardinality estimation, adjusted to available vectors
+/// * `available_vectors` - number of available vectors for the named vector storage
+pub fn is_two_hop_selectivity(
+    estimation: &CardinalityEstimation,
+    available_vectors: usize,
+) -> bool {
+    available_vectors > 0
+        && (estimation.exp as f64) < available_vectors as f64 * TWO_HOP_MAX_SELECTIVITY
+}
+
 pub fn combine_should_estimations(
     estimations: &[CardinalityEstimation],
     total: usize,
@@ -580,4 +602,19 @@ mod tests {
         assert_eq!(new_estimation.exp, 16);
         assert_eq!(new_estimation.max, 50);
     }
+
+    #[test]
+    fn test_two_hop_selectivity() {
+        let estimation = |exp| CardinalityEstimation {
+            primary_clauses: vec![],
+            min: 0,
+            exp,
+            max: TOTAL,
+        };
+
+        assert!(is_two_hop_selectivity(&estimation(100), TOTAL));
+        assert!(!is_two_hop_selectivity(&estimation(200), TOTAL));
+        assert!(!is_two_hop_selectivity(&estimation(TOTAL), TOTAL));
+        assert!(!is_two_hop_selectivity(&estimation(0), 0));
+    }
 }
@@ -5,6 +5,7 @@ use serde_json::Value;
 
 use crate::common::utils::IndexesMap;
 use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::map_index::{parse_uuid, parse_uuids};
 use crate::index::field_index::FieldIndex;
 use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
@@ -271,14 +272,23 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
                 Some(Box::new(move |point_id: PointOffsetType| {
                     index
                         .get_values(point_id)
-                        .map_or(false, |values| values.iter().any(|k| k == &keyword))
+                        .map_or(false, |mut values| values.any(|k| k == &keyword))
                 }))
             }
             (ValueVariants::Integer(value), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
                     index
                         .get_values(point_id)
-                        .map_or(false, |values| values.iter().any(|i| i == &value))
+                        .map_or(false, |mut values| values.any(|i| i == &value))
+                }))
+            }
+            (ValueVariants::Keyword(keyword), FieldIndex::UuidMapIndex(index)) => {
+                // Not a UUID, fall back to checking the payload
+                let uuid = parse_uuid(&keyword)?;
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index
+                        .get_values(point_id)
+                        .map_or(false, |mut values| values.any(|u| u == &uuid))
                 }))
             }
             (ValueVariants::Bool(is_true), FieldIndex::BinaryIndex(index)) => {
@@ -304,55 +314,63 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
         Match::Any(MatchAny { any }) => match (any, index) {
             (AnyVariants::Keywords(list), FieldIndex::KeywordIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
+                    index.get_values(point_id).map_or(false, |mut values| {
                         if list.len() < INDEXSET_ITER_THRESHOLD {
-                            values
-                                .iter()
-                                .any(|k| list.iter().any(|s| s.as_str() == k.as_ref()))
+                            values.any(|k| list.iter().any(|s| s.as_str() == k.as_ref()))
                         } else {
-                            values.iter().any(|k| list.contains(k.as_str()))
+                            values.any(|k| list.contains(k.as_str()))
                         }
                     })
                 }))
             }
             (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
+                    index.get_values(point_id).map_or(false, |mut values| {
                         if list.len() < INDEXSET_ITER_THRESHOLD {
-                            values.iter().any(|i| list.iter().any(|k| k == i))
+                            values.any(|i| list.iter().any(|k| k == i))
                         } else {
-                            values.iter().any(|i| list.contains(i))
+                            values.any(|i| list.contains(i))
                         }
                     })
                 }))
             }
+            (AnyVariants::Keywords(list), FieldIndex::UuidMapIndex(index)) => {
+                // Not all of them are UUIDs, fall back to checking the payload
+                let uuids: HashSet<_> = parse_uuids(&list)?;
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index
+                        .get_values(point_id)
+                        .map_or(false, |mut values| values.any(|u| uuids.contains(u)))
+                }))
+            }
             _ => None,
         },
         Match::Except(MatchExcept { except }) => match (except, index) {
             (AnyVariants::Keywords(list), FieldIndex::KeywordIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
+                    index.get_values(point_id).map_or(false, |mut values| {
                         if list.len() < INDEXSET_ITER_THRESHOLD {
-                            values
-                                .iter()
-                                .any(|k| !list.iter().any(|s| s.as_str() == k.as_ref()))
+                            values.any(|k| !list.iter().any(|s| s.as_str() == k.as_ref()))
                         } else {
-                            values.iter().any(|k| !list.contains(k.as_str()))
+                            values.any(|k| !list.contains(k.as_str()))
                         }
                     })
                 }))
             }
             (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
+                    index.get_values(point_id).map_or(false, |mut values| {
                         if list.len() < INDEXSET_ITER_THRESHOLD {
-                            values.iter().any(|i| !list.iter().any(|k| k == i))
+                            values.any(|i| !list.iter().any(|k| k == i))
                         } else {
-                            values.iter().any(|i| !list.contains(i))
+                            values.any(|i| !list.contains(i))
                         }
                     })
                 }))
             }
+            // Strings which are not valid UUIDs are not indexed, so the index can't tell whether
+            // a point has any other value than the excluded ones, fall back to checking the payload
+            (_, FieldIndex::UuidMapIndex(_)) => None,
             (_, index) => Some(Box::new(|point_id: PointOffsetType| {
                 // If there is any other value of any other index, then it's a match
                 index.values_count(point_id) > 0
@@ -51,6 +51,11 @@ impl PayloadProvider {
                 .read_payload(point_id)
                 .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
                 .map(|x| x.into()),
+            // Same as for `OnDiskPayloadStorage`, failure means the storage is corrupted
+            PayloadStorageEnum::MmapPayloadStorage(s) => s
+                .read_payload(point_id)
+                .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
+                .map(|x| x.into()),
         };
 
         let payload = if let Some(payload_ptr) = payload_ptr_opt {
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::common::anonymize::Anonymize;
 use crate::common::operation_error::OperationResult;
+use crate::types::VectorStorageDatatype;
 
 pub const SPARSE_INDEX_CONFIG_FILE: &str = "sparse_index_config.json";
 
@@ -49,6 +50,10 @@ pub struct SparseIndexConfig {
     pub full_scan_threshold: Option<usize>,
     /// Type of sparse index
     pub index_type: SparseIndexType,
+    /// Datatype used to store weights in the index.
+    /// If not specified, weights are stored in the legacy uncompressed format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<VectorStorageDatatype>,
 }
 
 impl Anonymize for SparseIndexConfig {
@@ -56,15 +61,21 @@ impl Anonymize for SparseIndexConfig {
         SparseIndexConfig {
             full_scan_threshold: self.full_scan_threshold,
             index_type: self.index_type,
+            datatype: self.datatype,
         }
     }
 }
 
 impl SparseIndexConfig {
-    pub fn new(full_scan_threshold: Option<usize>, index_type: SparseIndexType) -> Self {
+    pub fn new(
+        full_scan_threshold: Option<usize>,
+        index_type: SparseIndexType,
+        datatype: Option<VectorStorageDatatype>,
+    ) -> Self {
         SparseIndexConfig {
             full_scan_threshold,
             index_type,
+            datatype,
         }
     }
 
@@ -34,6 +34,12 @@ impl SparseSearchesTelemetry {
             filtered_small_cardinality: self.small_cardinality.lock().get_statistics(detail),
             filtered_large_cardinality: Default::default(),
             filtered_exact: Default::default(),
+            filtered_range_plain: Default::default(),
+            filtered_range_hnsw: Default::default(),
+            filtered_geo_plain: Default::default(),
+            filtered_geo_hnsw: Default::default(),
+            filtered_one_hop: Default::default(),
+            filtered_two_hop: Default::default(),
             filtered_sparse: self.filtered_sparse.lock().get_statistics(detail),
             unfiltered_sparse: self.unfiltered_sparse.lock().get_statistics(detail),
             unfiltered_exact: Default::default(),
@@ -1,5 +1,5 @@
 use std::collections::{HashMap, HashSet};
-use std::fs::create_dir_all;
+use std::fs::{create_dir_all, remove_dir_all};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -16,7 +16,7 @@ use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
 use crate::common::utils::IndexesMap;
 use crate::common::Flusher;
 use crate::id_tracker::IdTrackerSS;
-use crate::index::field_index::index_selector::index_selector;
+use crate::index::field_index::index_selector::{field_index_dir, index_selector};
 use crate::index::field_index::{
     CardinalityEstimation, FieldIndex, PayloadBlockCondition, PrimaryCondition,
 };
@@ -93,6 +93,11 @@ impl StructPayloadIndex {
         PayloadConfig::get_config_path(&self.path)
     }
 
+    /// Directory for field indexes, which store their data in files instead of RocksDB
+    fn field_indexes_path(&self) -> PathBuf {
+        self.path.join(PAYLOAD_FIELD_INDEX_PATH)
+    }
+
     fn save_config(&self) -> OperationResult<()> {
         let config_path = self.config_path();
         self.config.save(&config_path)
@@ -115,7 +120,13 @@ impl StructPayloadIndex {
         payload_schema: PayloadFieldSchema,
         is_appendable: bool,
     ) -> OperationResult<Vec<FieldIndex>> {
-        let mut indexes = index_selector(field, &payload_schema, self.db.clone(), is_appendable);
+        let mut indexes = index_selector(
+            field,
+            &payload_schema,
+            self.db.clone(),
+            &self.field_indexes_path(),
+            is_appendable,
+        );
 
         let mut is_loaded = true;
         for ref mut index in indexes.iter_mut() {
@@ -176,11 +187,24 @@ impl StructPayloadIndex {
         payload_schema: PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndex>> {
         let payload_storage = self.payload.borrow();
-        let mut field_indexes = index_selector(field, &payload_schema, self.db.clone(), true);
+        let field_indexes_path = self.field_indexes_path();
+        let mut field_indexes = index_selector(
+            field,
+            &payload_schema,
+            self.db.clone(),
+            &field_indexes_path,
+            true,
+        );
         for index in &field_indexes {
             index.recreate()?;
         }
 
+        // On-disk data of a previous index of this field is outdated, it is rebuilt on load
+        let field_dir = field_index_dir(&field_indexes_path, field);
+        if field_dir.exists() {
+            remove_dir_all(field_dir)?;
+        }
+
         payload_storage.iter(|point_id, point_payload| {
             let field_value = &point_payload.get_value(field);
             for field_index in field_indexes.iter_mut() {
@@ -201,6 +225,24 @@ impl StructPayloadIndex {
         Ok(())
     }
 
+    /// Primary clauses of a condition on a tenant field in the `must` clause of the filter.
+    ///
+    /// Points of the same tenant are stored next to each other in optimized segments,
+    /// so reading all of them is cheap compared to the random access of other indexes.
+    fn tenant_primary_clauses(&self, query: &Filter) -> Option<Vec<PrimaryCondition>> {
+        query.must.iter().flatten().find_map(|condition| {
+            let Condition::Field(field_condition) = condition else {
+                return None;
+            };
+            let schema = self.config.indexed_fields.get(&field_condition.key)?;
+            if !schema.is_tenant() {
+                return None;
+            }
+            let estimation = self.estimate_field_condition(field_condition, None)?;
+            (!estimation.primary_clauses.is_empty()).then_some(estimation.primary_clauses)
+        })
+    }
+
     /// Number of available points
     ///
     /// - excludes soft deleted points
@@ -323,6 +365,27 @@ impl StructPayloadIndex {
             .collect()
     }
 
+    /// Size of the files of the field indexes, stored outside of RocksDB.
+    ///
+    /// Returns `None` if all indexes of the field are kept in RocksDB.
+    pub fn disk_usage_bytes(&self, field: PayloadKeyTypeRef) -> Option<usize> {
+        let files: Vec<_> = self
+            .field_indexes
+            .get(field)?
+            .iter()
+            .flat_map(|index| index.files())
+            .collect();
+        if files.is_empty() {
+            return None;
+        }
+        let size = files
+            .iter()
+            .filter_map(|file| file.metadata().ok())
+            .map(|metadata| metadata.len() as usize)
+            .sum();
+        Some(size)
+    }
+
     pub fn restore_database_snapshot(
         snapshot_path: &Path,
         segment_path: &Path,
@@ -375,7 +438,21 @@ impl PayloadIndex for StructPayloadIndex {
     fn estimate_cardinality(&self, query: &Filter) -> CardinalityEstimation {
         let available_points = self.available_point_count();
         let estimator = |condition: &Condition| self.condition_cardinality(condition, None);
-        estimate_filter(&estimator, query, available_points)
+        let mut estimation = estimate_filter(&estimator, query, available_points);
+
+        // Prefer to scan points of a single tenant over other indexed conditions,
+        // unless points are already selected by ids
+        let selected_by_ids = estimation
+            .primary_clauses
+            .iter()
+            .any(|clause| matches!(clause, PrimaryCondition::Ids(_)));
+        if !selected_by_ids {
+            if let Some(tenant_clauses) = self.tenant_primary_clauses(query) {
+                estimation.primary_clauses = tenant_clauses;
+            }
+        }
+
+        estimation
     }
 
     fn estimate_nested_cardinality(
@@ -566,6 +643,10 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        vec![self.config_path()]
+        let mut files = vec![self.config_path()];
+        for indexes in self.field_indexes.values() {
+            files.extend(indexes.iter().flat_map(|index| index.files()));
+        }
+        files
     }
 }
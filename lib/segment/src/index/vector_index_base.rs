@@ -4,6 +4,9 @@ use std::sync::Arc;
 
 use common::cpu::CpuPermit;
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
+use sparse::common::types::QuantizedU8;
+use sparse::index::inverted_index::inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam;
+use sparse::index::inverted_index::inverted_index_compressed_mmap::InvertedIndexCompressedMmap;
 use sparse::index::inverted_index::inverted_index_immutable_ram::InvertedIndexImmutableRam;
 use sparse::index::inverted_index::inverted_index_mmap::InvertedIndexMmap;
 use sparse::index::inverted_index::inverted_index_ram::InvertedIndexRam;
@@ -61,6 +64,16 @@ pub enum VectorIndexEnum {
     SparseRam(SparseVectorIndex<InvertedIndexRam>),
     SparseImmutableRam(SparseVectorIndex<InvertedIndexImmutableRam>),
     SparseMmap(SparseVectorIndex<InvertedIndexMmap>),
+    SparseCompressedImmutableRamF32(SparseVectorIndex<InvertedIndexCompressedImmutableRam<f32>>),
+    SparseCompressedImmutableRamF16(
+        SparseVectorIndex<InvertedIndexCompressedImmutableRam<half::f16>>,
+    ),
+    SparseCompressedImmutableRamU8(
+        SparseVectorIndex<InvertedIndexCompressedImmutableRam<QuantizedU8>>,
+    ),
+    SparseCompressedMmapF32(SparseVectorIndex<InvertedIndexCompressedMmap<f32>>),
+    SparseCompressedMmapF16(SparseVectorIndex<InvertedIndexCompressedMmap<half::f16>>),
+    SparseCompressedMmapU8(SparseVectorIndex<InvertedIndexCompressedMmap<QuantizedU8>>),
 }
 
 impl VectorIndexEnum {
@@ -72,6 +85,12 @@ impl VectorIndexEnum {
             Self::SparseRam(_) => true,
             Self::SparseImmutableRam(_) => true,
             Self::SparseMmap(_) => true,
+            Self::SparseCompressedImmutableRamF32(_) => true,
+            Self::SparseCompressedImmutableRamF16(_) => true,
+            Self::SparseCompressedImmutableRamU8(_) => true,
+            Self::SparseCompressedMmapF32(_) => true,
+            Self::SparseCompressedMmapF16(_) => true,
+            Self::SparseCompressedMmapU8(_) => true,
         }
     }
 }
@@ -104,6 +123,24 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::SparseMmap(index) => {
                 index.search(vectors, filter, top, params, query_context)
             }
+            VectorIndexEnum::SparseCompressedImmutableRamF32(index) => {
+                index.search(vectors, filter, top, params, query_context)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamF16(index) => {
+                index.search(vectors, filter, top, params, query_context)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamU8(index) => {
+                index.search(vectors, filter, top, params, query_context)
+            }
+            VectorIndexEnum::SparseCompressedMmapF32(index) => {
+                index.search(vectors, filter, top, params, query_context)
+            }
+            VectorIndexEnum::SparseCompressedMmapF16(index) => {
+                index.search(vectors, filter, top, params, query_context)
+            }
+            VectorIndexEnum::SparseCompressedMmapU8(index) => {
+                index.search(vectors, filter, top, params, query_context)
+            }
         }
     }
 
@@ -132,6 +169,24 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::SparseMmap(index) => {
                 index.build_index_with_progress(permit, stopped, tick_progress)
             }
+            VectorIndexEnum::SparseCompressedImmutableRamF32(index) => {
+                index.build_index_with_progress(permit, stopped, tick_progress)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamF16(index) => {
+                index.build_index_with_progress(permit, stopped, tick_progress)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamU8(index) => {
+                index.build_index_with_progress(permit, stopped, tick_progress)
+            }
+            VectorIndexEnum::SparseCompressedMmapF32(index) => {
+                index.build_index_with_progress(permit, stopped, tick_progress)
+            }
+            VectorIndexEnum::SparseCompressedMmapF16(index) => {
+                index.build_index_with_progress(permit, stopped, tick_progress)
+            }
+            VectorIndexEnum::SparseCompressedMmapU8(index) => {
+                index.build_index_with_progress(permit, stopped, tick_progress)
+            }
         }
     }
 
@@ -143,6 +198,18 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::SparseRam(index) => index.get_telemetry_data(detail),
             VectorIndexEnum::SparseImmutableRam(index) => index.get_telemetry_data(detail),
             VectorIndexEnum::SparseMmap(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::SparseCompressedImmutableRamF32(index) => {
+                index.get_telemetry_data(detail)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamF16(index) => {
+                index.get_telemetry_data(detail)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamU8(index) => {
+                index.get_telemetry_data(detail)
+            }
+            VectorIndexEnum::SparseCompressedMmapF32(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::SparseCompressedMmapF16(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::SparseCompressedMmapU8(index) => index.get_telemetry_data(detail),
         }
     }
 
@@ -154,6 +221,12 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::SparseRam(index) => index.files(),
             VectorIndexEnum::SparseImmutableRam(index) => index.files(),
             VectorIndexEnum::SparseMmap(index) => index.files(),
+            VectorIndexEnum::SparseCompressedImmutableRamF32(index) => index.files(),
+            VectorIndexEnum::SparseCompressedImmutableRamF16(index) => index.files(),
+            VectorIndexEnum::SparseCompressedImmutableRamU8(index) => index.files(),
+            VectorIndexEnum::SparseCompressedMmapF32(index) => index.files(),
+            VectorIndexEnum::SparseCompressedMmapF16(index) => index.files(),
+            VectorIndexEnum::SparseCompressedMmapU8(index) => index.files(),
         }
     }
 
@@ -165,6 +238,12 @@ impl VectorIndex for VectorIndexEnum {
             Self::SparseRam(index) => index.indexed_vector_count(),
             Self::SparseImmutableRam(index) => index.indexed_vector_count(),
             Self::SparseMmap(index) => index.indexed_vector_count(),
+            Self::SparseCompressedImmutableRamF32(index) => index.indexed_vector_count(),
+            Self::SparseCompressedImmutableRamF16(index) => index.indexed_vector_count(),
+            Self::SparseCompressedImmutableRamU8(index) => index.indexed_vector_count(),
+            Self::SparseCompressedMmapF32(index) => index.indexed_vector_count(),
+            Self::SparseCompressedMmapF16(index) => index.indexed_vector_count(),
+            Self::SparseCompressedMmapU8(index) => index.indexed_vector_count(),
         }
     }
 
@@ -176,6 +255,12 @@ impl VectorIndex for VectorIndexEnum {
             Self::SparseRam(index) => index.update_vector(id, vector),
             Self::SparseImmutableRam(index) => index.update_vector(id, vector),
             Self::SparseMmap(index) => index.update_vector(id, vector),
+            Self::SparseCompressedImmutableRamF32(index) => index.update_vector(id, vector),
+            Self::SparseCompressedImmutableRamF16(index) => index.update_vector(id, vector),
+            Self::SparseCompressedImmutableRamU8(index) => index.update_vector(id, vector),
+            Self::SparseCompressedMmapF32(index) => index.update_vector(id, vector),
+            Self::SparseCompressedMmapF16(index) => index.update_vector(id, vector),
+            Self::SparseCompressedMmapU8(index) => index.update_vector(id, vector),
         }
     }
 }
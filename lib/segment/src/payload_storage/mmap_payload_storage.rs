@@ -0,0 +1,509 @@
+use std::fs::{create_dir_all, remove_dir_all};
+use std::hint::black_box;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use bitvec::prelude::{BitSlice, BitVec};
+use common::types::PointOffsetType;
+use parking_lot::Mutex;
+use serde_json::Value;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::json_path::JsonPath;
+use crate::payload_storage::PayloadStorage;
+use crate::types::Payload;
+use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
+use crate::vector_storage::chunked_utils::{chunk_name, create_chunk, read_mmaps, MmapChunk};
+
+pub const MMAP_PAYLOAD_STORAGE_PATH: &str = "payload_storage";
+
+const TRACKER_PATH: &str = "tracker";
+const PAGES_PATH: &str = "pages";
+
+/// Allocation unit of the pages, each value occupies a continuous range of blocks
+const BLOCK_SIZE: usize = 128;
+
+#[cfg(debug_assertions)]
+const DEFAULT_PAGE_SIZE: usize = 512 * 1024; // 512Kb
+#[cfg(not(debug_assertions))]
+const DEFAULT_PAGE_SIZE: usize = 32 * 1024 * 1024; // 32Mb
+
+/// Location of the serialized payload of a point
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct ValuePointer {
+    page_id: u32,
+    /// Index of the first block of the value within the page
+    block_offset: u32,
+    /// Length of the value in bytes, zero if the point has no payload
+    length: u32,
+}
+
+impl ValuePointer {
+    fn blocks(&self) -> BlockRange {
+        BlockRange {
+            page_id: self.page_id,
+            block_offset: self.block_offset,
+            blocks: blocks_for(self.length as usize) as u32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BlockRange {
+    page_id: u32,
+    block_offset: u32,
+    blocks: u32,
+}
+
+impl BlockRange {
+    fn bytes(&self) -> std::ops::Range<usize> {
+        let start = self.block_offset as usize * BLOCK_SIZE;
+        start..start + self.blocks as usize * BLOCK_SIZE
+    }
+
+    fn block_indices(&self) -> std::ops::Range<usize> {
+        let start = self.block_offset as usize;
+        start..start + self.blocks as usize
+    }
+}
+
+fn blocks_for(length: usize) -> usize {
+    length.div_ceil(BLOCK_SIZE)
+}
+
+/// Find the first range of `blocks` free blocks, starting at `from`
+fn find_free_blocks(occupied: &BitSlice, blocks: usize, from: usize) -> Option<usize> {
+    let mut start = from;
+    while start + blocks <= occupied.len() {
+        match occupied[start..start + blocks].last_one() {
+            None => return Some(start),
+            Some(last_occupied) => start += last_occupied + 1,
+        }
+    }
+    None
+}
+
+/// Payload storage, which keeps serialized payloads in memory mapped pages.
+///
+/// Pages are split into fixed-size blocks, each payload occupies a continuous range of blocks.
+/// Location of the payload of each point is kept in a separate memory mapped tracker.
+/// Updated payloads are never written in place, every value is written into free blocks.
+///
+/// Blocks of outdated values are not reused until the flush, so the persisted tracker never
+/// points to blocks overwritten by other values, and a crash can't leave a partially written value.
+/// Map of occupied blocks is kept in RAM and is restored from the tracker on load.
+pub struct MmapPayloadStorage {
+    path: PathBuf,
+    tracker: ChunkedMmapVectors<ValuePointer>,
+    pages: Vec<MmapChunk<u8>>,
+    /// Occupied blocks of each page
+    occupied: Vec<BitVec>,
+    /// Number of free blocks of each page, used to skip full pages during allocation
+    free_blocks: Vec<usize>,
+    /// Position after the latest allocation, new values are preferably written sequentially
+    cursor: (usize, usize),
+    /// Blocks of outdated values, which are released by the next flush
+    pending_release: Arc<Mutex<Vec<BlockRange>>>,
+    /// Blocks released by a flush, which can be reused
+    released: Arc<Mutex<Vec<BlockRange>>>,
+    /// Read all pages on load, so payloads are served from the page cache
+    populate: bool,
+}
+
+impl MmapPayloadStorage {
+    pub fn open(path: &Path, populate: bool) -> OperationResult<Self> {
+        let pages_path = path.join(PAGES_PATH);
+        create_dir_all(&pages_path)?;
+        let tracker = ChunkedMmapVectors::open(&path.join(TRACKER_PATH), 1)?;
+        let pages: Vec<MmapChunk<u8>> = read_mmaps(&pages_path)?;
+
+        let mut occupied: Vec<BitVec> = pages
+            .iter()
+            .map(|page| BitVec::repeat(false, page.len() / BLOCK_SIZE))
+            .collect();
+
+        for point_id in 0..tracker.len() {
+            let Some(pointer) = Self::pointer(&tracker, point_id as PointOffsetType) else {
+                continue;
+            };
+            let range = pointer.blocks();
+            let page_occupied = occupied
+                .get_mut(range.page_id as usize)
+                .filter(|page_occupied| range.block_indices().end <= page_occupied.len())
+                .ok_or_else(|| {
+                    OperationError::service_error(format!(
+                        "Payload storage {} is corrupted: point {point_id} is out of pages bounds",
+                        path.display(),
+                    ))
+                })?;
+            page_occupied[range.block_indices()].fill(true);
+        }
+
+        let free_blocks = occupied.iter().map(|page| page.count_zeros()).collect();
+
+        if populate {
+            for page in &pages {
+                populate_page(page);
+            }
+        }
+
+        Ok(Self {
+            path: path.to_owned(),
+            tracker,
+            pages,
+            occupied,
+            free_blocks,
+            cursor: (0, 0),
+            pending_release: Default::default(),
+            released: Default::default(),
+            populate,
+        })
+    }
+
+    fn pointer(
+        tracker: &ChunkedMmapVectors<ValuePointer>,
+        point_id: PointOffsetType,
+    ) -> Option<ValuePointer> {
+        if point_id as usize >= tracker.len() {
+            return None;
+        }
+        tracker
+            .get(point_id)
+            .and_then(|pointer| pointer.first().copied())
+            .filter(|pointer| pointer.length > 0)
+    }
+
+    fn get_pointer(&self, point_id: PointOffsetType) -> Option<ValuePointer> {
+        Self::pointer(&self.tracker, point_id)
+    }
+
+    fn set_pointer(
+        &mut self,
+        point_id: PointOffsetType,
+        pointer: ValuePointer,
+    ) -> OperationResult<()> {
+        self.tracker.insert(point_id, &[pointer])
+    }
+
+    pub fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        let Some(pointer) = self.get_pointer(point_id) else {
+            return Ok(None);
+        };
+        let start = pointer.block_offset as usize * BLOCK_SIZE;
+        let bytes = &self.pages[pointer.page_id as usize][start..start + pointer.length as usize];
+        Ok(Some(serde_cbor::from_slice(bytes)?))
+    }
+
+    pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
+    where
+        F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
+    {
+        for point_id in 0..self.tracker.len() as PointOffsetType {
+            let Some(payload) = self.read_payload(point_id)? else {
+                continue;
+            };
+            if !callback(point_id, &payload)? {
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+
+    /// Make blocks released by flushes available for allocation
+    fn reuse_released(&mut self) {
+        let released = mem::take(&mut *self.released.lock());
+        for range in released {
+            self.occupied[range.page_id as usize][range.block_indices()].fill(false);
+            self.free_blocks[range.page_id as usize] += range.blocks as usize;
+        }
+    }
+
+    fn release(&mut self, range: BlockRange) {
+        if range.blocks > 0 {
+            self.pending_release.lock().push(range);
+        }
+    }
+
+    fn add_page(&mut self, min_blocks: usize) -> OperationResult<usize> {
+        // Values larger than a page get a dedicated page of a suitable size
+        let page_size = DEFAULT_PAGE_SIZE.max(min_blocks * BLOCK_SIZE);
+        let page_id = self.pages.len();
+        let page = create_chunk(&self.path.join(PAGES_PATH), page_id, page_size)?;
+        if self.populate {
+            populate_page(&page);
+        }
+        self.pages.push(page);
+        self.occupied
+            .push(BitVec::repeat(false, page_size / BLOCK_SIZE));
+        self.free_blocks.push(page_size / BLOCK_SIZE);
+        Ok(page_id)
+    }
+
+    fn allocate(&mut self, blocks: usize) -> OperationResult<BlockRange> {
+        self.reuse_released();
+
+        let (cursor_page, cursor_block) = self.cursor;
+        let found = (cursor_page < self.pages.len())
+            .then(|| find_free_blocks(&self.occupied[cursor_page], blocks, cursor_block))
+            .flatten()
+            .map(|block_offset| (cursor_page, block_offset))
+            .or_else(|| {
+                (0..self.pages.len())
+                    .filter(|&page_id| self.free_blocks[page_id] >= blocks)
+                    .find_map(|page_id| {
+                        find_free_blocks(&self.occupied[page_id], blocks, 0)
+                            .map(|block_offset| (page_id, block_offset))
+                    })
+            });
+
+        let (page_id, block_offset) = match found {
+            Some(found) => found,
+            None => (self.add_page(blocks)?, 0),
+        };
+
+        self.occupied[page_id][block_offset..block_offset + blocks].fill(true);
+        self.free_blocks[page_id] -= blocks;
+        self.cursor = (page_id, block_offset + blocks);
+
+        Ok(BlockRange {
+            page_id: page_id as u32,
+            block_offset: block_offset as u32,
+            blocks: blocks as u32,
+        })
+    }
+
+    fn write_payload(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+    ) -> OperationResult<()> {
+        let bytes = serde_cbor::to_vec(payload)?;
+        let length = u32::try_from(bytes.len()).map_err(|_| {
+            OperationError::service_error(format!(
+                "Payload of {} bytes is too large to be stored",
+                bytes.len(),
+            ))
+        })?;
+        let blocks = blocks_for(bytes.len());
+
+        let old = self.get_pointer(point_id);
+        let range = self.allocate(blocks)?;
+        if let Some(old) = old {
+            self.release(old.blocks());
+        }
+
+        let start = range.bytes().start;
+        self.pages[range.page_id as usize][start..start + bytes.len()].copy_from_slice(&bytes);
+
+        self.set_pointer(
+            point_id,
+            ValuePointer {
+                page_id: range.page_id,
+                block_offset: range.block_offset,
+                length,
+            },
+        )
+    }
+
+    fn remove_payload(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        let payload = self.read_payload(point_id)?;
+        if let Some(pointer) = self.get_pointer(point_id) {
+            self.set_pointer(point_id, ValuePointer::default())?;
+            self.release(pointer.blocks());
+        }
+        Ok(payload)
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files = self.tracker.files();
+        let pages_path = self.path.join(PAGES_PATH);
+        files.extend((0..self.pages.len()).map(|page_id| chunk_name(&pages_path, page_id)));
+        files
+    }
+}
+
+/// Read the whole page, so it is loaded into the page cache
+fn populate_page(page: &[u8]) {
+    let mut dst = [0; 8096];
+    for chunk in page.chunks(dst.len()) {
+        dst[..chunk.len()].copy_from_slice(chunk);
+    }
+    black_box(dst);
+}
+
+impl PayloadStorage for MmapPayloadStorage {
+    fn assign_all(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        self.write_payload(point_id, payload)
+    }
+
+    fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        match self.read_payload(point_id)? {
+            Some(mut point_payload) => {
+                point_payload.merge(payload);
+                self.write_payload(point_id, &point_payload)
+            }
+            None => self.write_payload(point_id, payload),
+        }
+    }
+
+    fn assign_by_key(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: &JsonPath,
+    ) -> OperationResult<()> {
+        let mut point_payload = self.read_payload(point_id)?.unwrap_or_default();
+        point_payload.merge_by_key(payload, key)?;
+        self.write_payload(point_id, &point_payload)
+    }
+
+    fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
+        Ok(self.read_payload(point_id)?.unwrap_or_default())
+    }
+
+    fn delete(&mut self, point_id: PointOffsetType, key: &JsonPath) -> OperationResult<Vec<Value>> {
+        match self.read_payload(point_id)? {
+            Some(mut payload) => {
+                let res = payload.remove(key);
+                if !res.is_empty() {
+                    self.write_payload(point_id, &payload)?;
+                }
+                Ok(res)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    fn drop(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        self.remove_payload(point_id)
+    }
+
+    fn wipe(&mut self) -> OperationResult<()> {
+        // Release mmaps before removing the files
+        self.pages.clear();
+        if self.path.exists() {
+            remove_dir_all(&self.path)?;
+        }
+        let path = self.path.clone();
+        *self = Self::open(&path, self.populate)?;
+        Ok(())
+    }
+
+    fn flusher(&self) -> Flusher {
+        let tracker_flusher = self.tracker.flusher();
+        let pages_flushers: Vec<_> = self.pages.iter().map(|page| page.flusher()).collect();
+        // Only blocks freed before this moment are guaranteed to be unreferenced after the flush
+        let to_release = mem::take(&mut *self.pending_release.lock());
+        let released = self.released.clone();
+        Box::new(move || {
+            for flusher in pages_flushers {
+                flusher()?;
+            }
+            tracker_flusher()?;
+            released.lock().extend(to_release);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::json_path::path;
+
+    fn payload(value: Value) -> Payload {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_update_and_reload() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        let large_text = "a".repeat(DEFAULT_PAGE_SIZE * 2);
+
+        {
+            let mut storage = MmapPayloadStorage::open(dir.path(), false).unwrap();
+            for point_id in 0..100 {
+                storage
+                    .assign(point_id, &payload(json!({ "id": point_id })))
+                    .unwrap();
+            }
+            // Updated values are moved, whether they grow or shrink
+            storage
+                .assign(5, &payload(json!({ "text": "x".repeat(1000) })))
+                .unwrap();
+            let grown = storage.get_pointer(5).unwrap();
+            storage
+                .assign_all(5, &payload(json!({ "text": "short" })))
+                .unwrap();
+            let shrunk = storage.get_pointer(5).unwrap();
+            assert_ne!(
+                (shrunk.page_id, shrunk.block_offset),
+                (grown.page_id, grown.block_offset),
+            );
+            // Value which doesn't fit into a page
+            storage
+                .assign(7, &payload(json!({ "text": large_text })))
+                .unwrap();
+            storage.drop(10).unwrap();
+            storage.delete(11, &path("id")).unwrap();
+            storage.flusher()().unwrap();
+        }
+
+        let storage = MmapPayloadStorage::open(dir.path(), true).unwrap();
+        assert_eq!(
+            storage.payload(5).unwrap(),
+            payload(json!({ "text": "short" }))
+        );
+        assert_eq!(
+            storage.payload(7).unwrap(),
+            payload(json!({ "id": 7, "text": large_text }))
+        );
+        assert_eq!(storage.payload(10).unwrap(), Payload::default());
+        assert_eq!(storage.payload(11).unwrap(), payload(json!({})));
+        assert_eq!(storage.payload(42).unwrap(), payload(json!({ "id": 42 })));
+
+        let mut count = 0;
+        storage
+            .iter(|_, _| {
+                count += 1;
+                Ok(true)
+            })
+            .unwrap();
+        assert_eq!(count, 99);
+    }
+
+    #[test]
+    fn test_blocks_reused_after_flush() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let mut storage = MmapPayloadStorage::open(dir.path(), false).unwrap();
+
+        let value = payload(json!({ "text": "b".repeat(BLOCK_SIZE * 4) }));
+        storage.assign(0, &value).unwrap();
+        let first = storage.get_pointer(0).unwrap();
+
+        // Blocks of the removed value are not reused before the flush
+        storage.drop(0).unwrap();
+        storage.assign(1, &value).unwrap();
+        assert_ne!(
+            storage.get_pointer(1).unwrap().block_offset,
+            first.block_offset
+        );
+
+        storage.flusher()().unwrap();
+        storage.cursor = (0, 0);
+        storage.assign(2, &value).unwrap();
+        assert_eq!(
+            storage.get_pointer(2).unwrap().block_offset,
+            first.block_offset
+        );
+        assert_eq!(storage.payload(1).unwrap(), value);
+        assert_eq!(storage.payload(2).unwrap(), value);
+    }
+}
@@ -264,6 +264,10 @@ impl ConditionChecker for SimpleConditionChecker {
                                 .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
                                 .map(|x| x.into())
                         }
+                        PayloadStorageEnum::MmapPayloadStorage(s) => s
+                            .read_payload(point_id)
+                            .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
+                            .map(|x| x.into()),
                     };
 
                     payload_ref_cell
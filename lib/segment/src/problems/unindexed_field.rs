@@ -101,6 +101,19 @@ impl UnindexedField {
     }
 }
 
+/// Fields used in the filter, which don't have an index suitable for their conditions
+pub fn unindexed_fields(
+    filter: &Filter,
+    payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+) -> Vec<PayloadKeyType> {
+    let mut fields = Extractor::new(filter, payload_schema, String::new())
+        .unindexed_schema
+        .into_keys()
+        .collect_vec();
+    fields.sort_unstable_by_key(|field| field.to_string());
+    fields
+}
+
 impl Issue for UnindexedField {
     fn instance_id(&self) -> &str {
         &self.instance_id
@@ -160,27 +173,31 @@ fn all_indexes() -> impl Iterator<Item = PayloadFieldSchema> {
     PayloadSchemaType::iter().map(PayloadFieldSchema::FieldType)
 }
 
-fn infer_schema_from_match_value(value: &MatchValue) -> PayloadFieldSchema {
+/// Strings can be indexed either as keywords, or as uuids
+fn string_indexes() -> Vec<PayloadFieldSchema> {
+    vec![
+        PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+        PayloadFieldSchema::FieldType(PayloadSchemaType::Uuid),
+    ]
+}
+
+fn infer_schema_from_match_value(value: &MatchValue) -> Vec<PayloadFieldSchema> {
     match &value.value {
-        crate::types::ValueVariants::Keyword(_string) => {
-            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)
-        }
+        crate::types::ValueVariants::Keyword(_string) => string_indexes(),
         crate::types::ValueVariants::Integer(_integer) => {
-            PayloadFieldSchema::FieldType(PayloadSchemaType::Integer)
+            vec![PayloadFieldSchema::FieldType(PayloadSchemaType::Integer)]
         }
         crate::types::ValueVariants::Bool(_boolean) => {
-            PayloadFieldSchema::FieldType(PayloadSchemaType::Bool)
+            vec![PayloadFieldSchema::FieldType(PayloadSchemaType::Bool)]
         }
     }
 }
 
-fn infer_schema_from_any_variants(value: &AnyVariants) -> PayloadFieldSchema {
+fn infer_schema_from_any_variants(value: &AnyVariants) -> Vec<PayloadFieldSchema> {
     match value {
-        AnyVariants::Keywords(_strings) => {
-            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)
-        }
+        AnyVariants::Keywords(_strings) => string_indexes(),
         AnyVariants::Integers(_integers) => {
-            PayloadFieldSchema::FieldType(PayloadSchemaType::Integer)
+            vec![PayloadFieldSchema::FieldType(PayloadSchemaType::Integer)]
         }
     }
 }
@@ -199,17 +216,18 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
     let mut inferred = Vec::new();
 
     if let Some(r#match) = r#match {
-        inferred.push(match r#match {
+        inferred.extend(match r#match {
             Match::Value(match_value) => infer_schema_from_match_value(match_value),
-            Match::Text(_match_text) => {
-                PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(TextIndexParams {
+            Match::Text(_match_text) => vec![PayloadFieldSchema::FieldParams(
+                PayloadSchemaParams::Text(TextIndexParams {
                     r#type: TextIndexType::Text,
                     tokenizer: TokenizerType::default(),
                     min_token_len: None,
                     max_token_len: None,
                     lowercase: None,
-                }))
-            }
+                    on_disk: None,
+                }),
+            )],
             Match::Any(match_any) => infer_schema_from_any_variants(&match_any.any),
             Match::Except(match_except) => infer_schema_from_any_variants(&match_except.except),
         })
@@ -329,7 +347,10 @@ impl<'a> Extractor<'a> {
 
         let needs_index = match self.payload_schema.get(&full_key) {
             Some(index_info) => {
-                let already_indexed = inferred.iter().any(|inferred| inferred == index_info);
+                // Index params don't matter, as long as the index has a suitable type
+                let already_indexed = inferred
+                    .iter()
+                    .any(|inferred| inferred.data_type() == index_info.data_type());
 
                 !already_indexed
             }
@@ -3,6 +3,7 @@ use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 
@@ -14,6 +15,7 @@ use io::storage_version::{StorageVersion, VERSION_FILE};
 use itertools::Either;
 use memory::mmap_ops;
 use parking_lot::{Mutex, RwLock};
+use rand::seq::{IteratorRandom, SliceRandom};
 use rocksdb::DB;
 use sparse::common::sparse_vector::SparseVector;
 use tar::Builder;
@@ -24,7 +26,11 @@ use crate::common::operation_error::{
     get_service_error, OperationError, OperationResult, SegmentFailedState,
 };
 use crate::common::validate_snapshot_archive::open_snapshot_archive_with_validation;
-use crate::common::{check_named_vectors, check_query_vectors, check_stopped, check_vector_name};
+use crate::common::{
+    check_named_vectors, check_named_vectors_validation, check_query_vectors, check_stopped,
+    check_vector_name,
+};
+use crate::data_types::facets::{FacetParams, FacetValue};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{Direction, OrderBy, OrderValue};
 use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
@@ -33,9 +39,11 @@ use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::numeric_index::StreamRange;
 use crate::index::field_index::CardinalityEstimation;
+use crate::index::hnsw_index::graph_healing::HealedGraph;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::json_path::JsonPath;
+use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
@@ -85,6 +93,8 @@ pub struct Segment {
     pub id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     pub vector_data: HashMap<String, VectorData>,
     pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    /// Storage of point payloads, shared with the payload index
+    pub payload_storage: Arc<AtomicRefCell<PayloadStorageEnum>>,
     /// Shows if it is possible to insert more points into this segment
     pub appendable_flag: bool,
     /// Shows what kind of indexes and storages are used in this segment
@@ -243,6 +253,10 @@ impl Segment {
                             Vector::from(vec![1.0; v.vector_dim()])
                         }
                         VectorStorageEnum::SparseSimple(_) => Vector::from(SparseVector::default()),
+                        VectorStorageEnum::SparseMmap(_) => Vector::from(SparseVector::default()),
+                        VectorStorageEnum::SparseAppendableMmap(_) => {
+                            Vector::from(SparseVector::default())
+                        }
                         VectorStorageEnum::MultiDenseSimple(v) => {
                             Vector::from(MultiDenseVector::placeholder(v.vector_dim()))
                         }
@@ -885,6 +899,52 @@ impl Segment {
         Ok(page)
     }
 
+    /// Sample random points, without any filtering
+    fn read_by_random_id(&self, limit: usize) -> Vec<PointIdType> {
+        let id_tracker = self.id_tracker.borrow();
+
+        let ids_iterator = id_tracker
+            .iter_ids()
+            .filter_map(|internal_id| id_tracker.external_id(internal_id));
+
+        sample_shuffled(ids_iterator, limit)
+    }
+
+    /// Sample random points among all the points matching the condition, as resolved by the payload index
+    pub fn filtered_read_by_index_shuffled(
+        &self,
+        limit: usize,
+        condition: &Filter,
+    ) -> Vec<PointIdType> {
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+
+        let ids_iterator = payload_index
+            .query_points(condition)
+            .into_iter()
+            .filter_map(|internal_id| id_tracker.external_id(internal_id));
+
+        sample_shuffled(ids_iterator, limit)
+    }
+
+    /// Sample random points, checking each of them against the condition
+    pub fn filtered_read_by_random_stream(
+        &self,
+        limit: usize,
+        condition: &Filter,
+    ) -> Vec<PointIdType> {
+        let payload_index = self.payload_index.borrow();
+        let filter_context = payload_index.filter_context(condition);
+        let id_tracker = self.id_tracker.borrow();
+
+        let ids_iterator = id_tracker
+            .iter_ids()
+            .filter(move |internal_id| filter_context.check(*internal_id))
+            .filter_map(|internal_id| id_tracker.external_id(internal_id));
+
+        sample_shuffled(ids_iterator, limit)
+    }
+
     pub fn filtered_read_by_id_stream(
         &self,
         offset: Option<PointIdType>,
@@ -1123,6 +1183,59 @@ impl Segment {
     pub fn cleanup_versions(&mut self) -> OperationResult<()> {
         self.id_tracker.borrow_mut().cleanup_versions()
     }
+
+    /// Largest fraction of deleted vectors among the HNSW graphs of the segment
+    pub fn max_graph_deleted_ratio(&self) -> f64 {
+        self.vector_data
+            .values()
+            .map(|vector_data| match &*vector_data.vector_index.borrow() {
+                VectorIndexEnum::HnswRam(index) => index.deleted_ratio(),
+                VectorIndexEnum::HnswMmap(index) => index.deleted_ratio(),
+                _ => 0.0,
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Heal HNSW graphs, in which more than `deleted_threshold` of indexed vectors are deleted.
+    ///
+    /// Only requires read access, healed graphs are applied with [`Segment::apply_healed_graphs`].
+    pub fn heal_graphs(
+        &self,
+        deleted_threshold: f64,
+        stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<String, HealedGraph>> {
+        let mut healed_graphs = HashMap::new();
+        for (vector_name, vector_data) in &self.vector_data {
+            let healed_graph = match &*vector_data.vector_index.borrow() {
+                VectorIndexEnum::HnswRam(index) if index.deleted_ratio() > deleted_threshold => {
+                    index.heal_graph(stopped)?
+                }
+                VectorIndexEnum::HnswMmap(index) if index.deleted_ratio() > deleted_threshold => {
+                    index.heal_graph(stopped)?
+                }
+                _ => None,
+            };
+            if let Some(healed_graph) = healed_graph {
+                healed_graphs.insert(vector_name.to_owned(), healed_graph);
+            }
+        }
+        Ok(healed_graphs)
+    }
+
+    pub fn apply_healed_graphs(
+        &mut self,
+        healed_graphs: HashMap<String, HealedGraph>,
+    ) -> OperationResult<()> {
+        for (vector_name, healed_graph) in healed_graphs {
+            check_vector_name(&vector_name, &self.segment_config)?;
+            match &mut *self.vector_data[&vector_name].vector_index.borrow_mut() {
+                VectorIndexEnum::HnswRam(index) => index.apply_healed_graph(healed_graph)?,
+                VectorIndexEnum::HnswMmap(index) => index.apply_healed_graph(healed_graph)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
 }
 
 /// This is a basic implementation of `SegmentEntry`,
@@ -1152,6 +1265,7 @@ impl SegmentEntry for Segment {
     ) -> OperationResult<Vec<Vec<ScoredPoint>>> {
         check_query_vectors(vector_name, query_vectors, &self.segment_config)?;
         let vector_data = &self.vector_data[vector_name];
+
         let vector_query_context = query_context.get_vector_context(vector_name);
         let internal_results = vector_data.vector_index.borrow().search(
             query_vectors,
@@ -1181,6 +1295,7 @@ impl SegmentEntry for Segment {
     ) -> OperationResult<bool> {
         debug_assert!(self.is_appendable());
         check_named_vectors(&vectors, &self.segment_config)?;
+        check_named_vectors_validation(point_id, &vectors, &self.segment_config)?;
         vectors.preprocess(|name| self.config().vector_data.get(name).unwrap());
         let stored_internal_point = self.id_tracker.borrow().internal_id(point_id);
         self.handle_point_version_and_failure(op_num, stored_internal_point, |segment| {
@@ -1234,6 +1349,7 @@ impl SegmentEntry for Segment {
         mut vectors: NamedVectors,
     ) -> OperationResult<bool> {
         check_named_vectors(&vectors, &self.segment_config)?;
+        check_named_vectors_validation(point_id, &vectors, &self.segment_config)?;
         vectors.preprocess(|name| self.config().vector_data.get(name).unwrap());
         let internal_id = self.id_tracker.borrow().internal_id(point_id);
         match internal_id {
@@ -1423,6 +1539,78 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> Vec<PointIdType> {
+        match filter {
+            None => self.read_by_random_id(limit),
+            Some(condition) => {
+                if self.should_pre_filter(condition, Some(limit)) {
+                    self.filtered_read_by_index_shuffled(limit, condition)
+                } else {
+                    self.filtered_read_by_random_stream(limit, condition)
+                }
+            }
+        }
+    }
+
+    fn facet(
+        &self,
+        request: &FacetParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, usize>> {
+        let payload_index = self.payload_index.borrow();
+
+        let (field_index, facet_index) = payload_index
+            .field_indexes
+            .get(&request.key)
+            .and_then(|indexes| {
+                indexes
+                    .iter()
+                    .find_map(|index| index.as_facet_index().map(|facet| (index, facet)))
+            })
+            .ok_or_else(|| OperationError::ValidationError { description: "There is no keyword, integer or bool index for the facet `key`, please create one to use facets".to_string() })?;
+
+        let mut counts = HashMap::new();
+
+        match &request.filter {
+            None => {
+                for (value, count) in facet_index.iter_counts_per_value() {
+                    *counts.entry(value.to_owned()).or_insert(0) += count;
+                }
+            }
+            Some(filter) => {
+                let cardinality = payload_index.estimate_cardinality(filter);
+
+                if cardinality.exp < field_index.count_indexed_points() {
+                    // Few points match the filter, so look up the values of each of them
+                    for internal_id in payload_index.query_points(filter) {
+                        check_stopped(is_stopped)?;
+                        for value in facet_index.get_point_values(internal_id) {
+                            *counts.entry(value.to_owned()).or_insert(0) += 1;
+                        }
+                    }
+                } else {
+                    // Most of the points match the filter, so go over the points of each value
+                    let filter_context = payload_index.filter_context(filter);
+                    for (value, points) in facet_index.iter_values_map() {
+                        check_stopped(is_stopped)?;
+                        let count = points
+                            .filter(|internal_id| filter_context.check(*internal_id))
+                            .count();
+                        if count > 0 {
+                            *counts.entry(value.to_owned()).or_insert(0) += count;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(counts)
+    }
+
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let id_tracker = self.id_tracker.borrow();
         let iterator = id_tracker.iter_from(from).map(|x| x.0);
@@ -1481,7 +1669,11 @@ impl SegmentEntry for Segment {
             .into_iter()
             .map(|(key, index_schema)| {
                 let points_count = payload_index.indexed_points(&key);
-                (key, PayloadIndexInfo::new(index_schema, points_count))
+                let disk_usage_bytes = payload_index.disk_usage_bytes(&key);
+                (
+                    key,
+                    PayloadIndexInfo::new(index_schema, points_count, disk_usage_bytes),
+                )
             })
             .collect();
 
@@ -1849,6 +2041,24 @@ impl SegmentEntry for Segment {
             )?;
         }
 
+        for file in self.payload_storage.borrow().files() {
+            utils::tar::append_file_relative_to_base(
+                &mut builder,
+                &self.current_path,
+                &file,
+                &files,
+            )?;
+        }
+
+        for file in self.id_tracker.borrow().files() {
+            utils::tar::append_file_relative_to_base(
+                &mut builder,
+                &self.current_path,
+                &file,
+                &files,
+            )?;
+        }
+
         utils::tar::append_file(
             &mut builder,
             &self.current_path.join(SEGMENT_STATE_FILE),
@@ -1912,6 +2122,24 @@ impl SegmentEntry for Segment {
                     VectorIndexEnum::SparseMmap(sparse_index) => {
                         sparse_index.fill_idf_statistics(idf);
                     }
+                    VectorIndexEnum::SparseCompressedImmutableRamF32(sparse_index) => {
+                        sparse_index.fill_idf_statistics(idf);
+                    }
+                    VectorIndexEnum::SparseCompressedImmutableRamF16(sparse_index) => {
+                        sparse_index.fill_idf_statistics(idf);
+                    }
+                    VectorIndexEnum::SparseCompressedImmutableRamU8(sparse_index) => {
+                        sparse_index.fill_idf_statistics(idf);
+                    }
+                    VectorIndexEnum::SparseCompressedMmapF32(sparse_index) => {
+                        sparse_index.fill_idf_statistics(idf);
+                    }
+                    VectorIndexEnum::SparseCompressedMmapF16(sparse_index) => {
+                        sparse_index.fill_idf_statistics(idf);
+                    }
+                    VectorIndexEnum::SparseCompressedMmapU8(sparse_index) => {
+                        sparse_index.fill_idf_statistics(idf);
+                    }
                     VectorIndexEnum::Plain(_)
                     | VectorIndexEnum::HnswRam(_)
                     | VectorIndexEnum::HnswMmap(_) => {}
@@ -1921,6 +2149,15 @@ impl SegmentEntry for Segment {
     }
 }
 
+/// Select up to `limit` random items, in random order
+fn sample_shuffled<T>(iter: impl Iterator<Item = T>, limit: usize) -> Vec<T> {
+    let mut rng = rand::thread_rng();
+    let mut page = iter.choose_multiple(&mut rng, limit);
+    // `choose_multiple` does not guarantee a random order of the selected items
+    page.shuffle(&mut rng);
+    page
+}
+
 impl Drop for Segment {
     fn drop(&mut self) {
         if let Err(flushing_err) = self.lock_flushing() {
@@ -1991,6 +2228,8 @@ mod tests {
                     quantization_config: None,
                     multivec_config: None,
                     datatype: None,
+                    validation: None,
+                    normalize: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2065,6 +2304,8 @@ mod tests {
                     quantization_config: None,
                     multivec_config: None,
                     datatype: None,
+                    validation: None,
+                    normalize: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2157,6 +2398,8 @@ mod tests {
                     quantization_config: None,
                     multivec_config: None,
                     datatype: None,
+                    validation: None,
+                    normalize: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2253,6 +2496,8 @@ mod tests {
                     quantization_config: None,
                     multivec_config: None,
                     datatype: None,
+                    validation: None,
+                    normalize: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2287,6 +2532,8 @@ mod tests {
                     quantization_config: None,
                     multivec_config: None,
                     datatype: None,
+                    validation: None,
+                    normalize: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2382,6 +2629,8 @@ mod tests {
                     quantization_config: None,
                     multivec_config: None,
                     datatype: None,
+                    validation: None,
+                    normalize: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2437,6 +2686,8 @@ mod tests {
                         quantization_config: None,
                         multivec_config: None,
                         datatype: None,
+                        validation: None,
+                        normalize: None,
                     },
                 ),
                 (
@@ -2449,6 +2700,8 @@ mod tests {
                         quantization_config: None,
                         multivec_config: None,
                         datatype: None,
+                        validation: None,
+                        normalize: None,
                     },
                 ),
             ]),
@@ -2547,6 +2800,8 @@ mod tests {
                         quantization_config: None,
                         multivec_config: None,
                         datatype: None,
+                        validation: None,
+                        normalize: None,
                     },
                 ),
                 (
@@ -2559,6 +2814,8 @@ mod tests {
                         quantization_config: None,
                         multivec_config: None,
                         datatype: None,
+                        validation: None,
+                        normalize: None,
                     },
                 ),
             ]),
@@ -2721,6 +2978,8 @@ mod tests {
                     quantization_config: None,
                     multivec_config: None,
                     datatype: None,
+                    validation: None,
+                    normalize: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -1,31 +1,40 @@
 use std::cmp;
 use std::collections::HashMap;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use common::cpu::CpuPermit;
+use common::types::PointOffsetType;
 use io::storage_version::StorageVersion;
+use parking_lot::RwLock;
+use rocksdb::DB;
+use serde_json::Value;
 
 use super::{
-    create_id_tracker, create_payload_storage, create_sparse_vector_index,
+    build_immutable_id_tracker, create_payload_storage, create_sparse_vector_index,
     create_sparse_vector_storage, create_vector_index, get_payload_index_path,
     get_vector_index_path, get_vector_storage_path, new_segment_path, open_segment_db,
     open_vector_storage,
 };
 use crate::common::error_logging::LogError;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::common::utils::MultiValue;
 use crate::entry::entry_point::SegmentEntry;
+use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
 use crate::id_tracker::{IdTracker, IdTrackerEnum};
+use crate::index::hnsw_index::old_graph::OldGraph;
 use crate::index::struct_payload_index::StructPayloadIndex;
-use crate::index::{PayloadIndex, VectorIndex};
+use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::PayloadStorage;
 use crate::segment::{Segment, SegmentVersion};
 use crate::segment_constructor::load_segment;
 use crate::types::{
-    PayloadFieldSchema, PayloadKeyType, SegmentConfig, SegmentState, SeqNumberType,
+    IntPayloadType, PayloadContainer, PayloadFieldSchema, PayloadKeyType, SegmentConfig,
+    SegmentState, SeqNumberType,
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
@@ -33,6 +42,7 @@ use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 /// Structure for constructing segment out of several other segments
 pub struct SegmentBuilder {
     version: SeqNumberType,
+    database: Arc<RwLock<DB>>,
     id_tracker: IdTrackerEnum,
     payload_storage: PayloadStorageEnum,
     vector_storages: HashMap<String, VectorStorageEnum>,
@@ -43,6 +53,8 @@ pub struct SegmentBuilder {
     // Path to the temporary segment directory
     temp_path: PathBuf,
     indexed_fields: HashMap<PayloadKeyType, PayloadFieldSchema>,
+    // HNSW graphs of the biggest source segment, to start index building from
+    old_graphs: HashMap<String, OldGraph>,
 }
 
 impl SegmentBuilder {
@@ -59,9 +71,11 @@ impl SegmentBuilder {
 
         let database = open_segment_db(&temp_path, segment_config)?;
 
-        let id_tracker = create_id_tracker(database.clone())?;
+        // Points are linked one by one while building,
+        // the tracker of non-appendable segments is made immutable in `build`
+        let id_tracker = IdTrackerEnum::MutableIdTracker(SimpleIdTracker::open(database.clone())?);
 
-        let payload_storage = create_payload_storage(database.clone(), segment_config)?;
+        let payload_storage = create_payload_storage(database.clone(), &temp_path, segment_config)?;
 
         let mut vector_storages = HashMap::new();
 
@@ -78,13 +92,16 @@ impl SegmentBuilder {
             vector_storages.insert(vector_name.to_owned(), vector_storage);
         }
 
-        #[allow(clippy::for_kv_map)]
-        for (vector_name, _sparse_vector_config) in &segment_config.sparse_vector_data {
-            // `_sparse_vector_config` should be used, once we are able to initialize storage with
-            // different datatypes
-
-            let vector_storage =
-                create_sparse_vector_storage(database.clone(), vector_name, &stopped)?;
+        for (vector_name, sparse_vector_config) in &segment_config.sparse_vector_data {
+            let vector_storage_path = get_vector_storage_path(&temp_path, vector_name);
+            let vector_storage = create_sparse_vector_storage(
+                database.clone(),
+                &vector_storage_path,
+                vector_name,
+                sparse_vector_config.storage_type,
+                segment_config.is_appendable(),
+                &stopped,
+            )?;
             vector_storages.insert(vector_name.to_owned(), vector_storage);
         }
 
@@ -92,6 +109,7 @@ impl SegmentBuilder {
 
         Ok(SegmentBuilder {
             version: Default::default(), // default version is 0
+            database,
             id_tracker,
             payload_storage,
             vector_storages,
@@ -100,6 +118,7 @@ impl SegmentBuilder {
             destination_path,
             temp_path,
             indexed_fields: Default::default(),
+            old_graphs: Default::default(),
         })
     }
 
@@ -123,6 +142,146 @@ impl SegmentBuilder {
     /// * `bool` - if `true` - data successfully added, if `false` - process was interrupted
     ///
     pub fn update_from(&mut self, other: &Segment, stopped: &AtomicBool) -> OperationResult<bool> {
+        self.update(&[other], stopped)
+    }
+
+    /// Update current segment builder with all (not deleted) vectors and payload from `segments`
+    ///
+    /// If there is an indexed tenant field, points are ordered by its value,
+    /// so that points of the same tenant are stored next to each other in the new segment.
+    ///
+    /// HNSW graphs of the biggest segment are kept, so that only points of other segments
+    /// have to be inserted into the new graphs.
+    ///
+    /// # Arguments
+    ///
+    /// * `segments` - segments to add into construction
+    ///
+    /// # Result
+    ///
+    /// * `bool` - if `true` - data successfully added, if `false` - process was interrupted
+    ///
+    pub fn update(&mut self, segments: &[&Segment], stopped: &AtomicBool) -> OperationResult<bool> {
+        for segment in segments {
+            for (field, payload_schema) in segment.payload_index.borrow().indexed_fields() {
+                self.indexed_fields.insert(field, payload_schema);
+            }
+        }
+
+        // Points to move, as (segment index, internal id) pairs
+        let mut points: Vec<(usize, PointOffsetType)> = segments
+            .iter()
+            .enumerate()
+            .flat_map(|(segment_idx, segment)| {
+                let id_tracker = segment.id_tracker.borrow();
+                id_tracker
+                    .iter_ids()
+                    .map(|internal_id| (segment_idx, internal_id))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        if let Some(tenant_field) = self.tenant_field() {
+            let payload_indexes: Vec<_> = segments
+                .iter()
+                .map(|segment| segment.payload_index.borrow())
+                .collect();
+
+            let mut keyed_points = Vec::with_capacity(points.len());
+            for (segment_idx, internal_id) in points {
+                check_process_stopped(stopped)?;
+                let payload = payload_indexes[segment_idx].payload(internal_id)?;
+                let key = TenantKey::from_values(payload.get_value(&tenant_field));
+                keyed_points.push((key, segment_idx, internal_id));
+            }
+            keyed_points.sort_unstable();
+
+            points = keyed_points
+                .into_iter()
+                .map(|(_, segment_idx, internal_id)| (segment_idx, internal_id))
+                .collect();
+        }
+
+        let biggest_segment_idx = segments
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, segment)| segment.available_point_count())
+            .map(|(segment_idx, _)| segment_idx);
+        // Old internal id -> new internal id, for points of the biggest segment
+        let mut id_mapping = HashMap::new();
+
+        // Points are moved in chunks of consecutive points from the same segment
+        for chunk in points.chunk_by(|(a, _), (b, _)| a == b) {
+            let segment_idx = chunk[0].0;
+            let internal_ids: Vec<_> = chunk.iter().map(|(_, internal_id)| *internal_id).collect();
+            let new_internal_range =
+                self.update_from_ids(segments[segment_idx], &internal_ids, stopped)?;
+            if Some(segment_idx) == biggest_segment_idx {
+                if let Some(new_internal_range) = new_internal_range {
+                    id_mapping.extend(internal_ids.iter().copied().zip(new_internal_range));
+                }
+            }
+        }
+
+        if let Some(segment_idx) = biggest_segment_idx {
+            // Keep graphs of a previous update, if they cover more points
+            let old_graphs = self.old_graphs(segments[segment_idx], &id_mapping);
+            let graph_points = |graphs: &HashMap<String, OldGraph>| {
+                graphs.values().map(OldGraph::num_points).max().unwrap_or(0)
+            };
+            if graph_points(&old_graphs) > graph_points(&self.old_graphs) {
+                self.old_graphs = old_graphs;
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Indexed field, which is used to group points by tenant
+    fn tenant_field(&self) -> Option<PayloadKeyType> {
+        self.indexed_fields
+            .iter()
+            .filter(|(_, schema)| schema.is_tenant())
+            .map(|(field, _)| field)
+            .min_by_key(|field| field.to_string())
+            .cloned()
+    }
+
+    /// HNSW graphs of `segment`, which can be reused to build the indexes of the new segment
+    fn old_graphs(
+        &self,
+        segment: &Segment,
+        id_mapping: &HashMap<PointOffsetType, PointOffsetType>,
+    ) -> HashMap<String, OldGraph> {
+        segment
+            .vector_data
+            .iter()
+            .filter_map(|(vector_name, vector_data)| {
+                let old_config = segment.config().vector_data.get(vector_name)?;
+                let new_config = self.segment_config.vector_data.get(vector_name)?;
+                // Graph depends on the index parameters and on how vectors are compared
+                if old_config.index != new_config.index
+                    || old_config.multivec_config != new_config.multivec_config
+                {
+                    return None;
+                }
+                let old_graph = match &*vector_data.vector_index.borrow() {
+                    VectorIndexEnum::HnswRam(index) => index.old_graph(id_mapping),
+                    VectorIndexEnum::HnswMmap(index) => index.old_graph(id_mapping),
+                    _ => None,
+                }?;
+                Some((vector_name.to_owned(), old_graph))
+            })
+            .collect()
+    }
+
+    /// Returns range of the new internal ids, assigned to `other_ids` in the same order
+    fn update_from_ids(
+        &mut self,
+        other: &Segment,
+        other_ids: &[PointOffsetType],
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<Range<PointOffsetType>>> {
         self.version = cmp::max(self.version, other.version());
 
         let other_id_tracker = other.id_tracker.borrow();
@@ -153,7 +312,7 @@ impl SegmentBuilder {
             })?;
             let internal_range = vector_storage.update_from(
                 other_vector_storage,
-                &mut other_id_tracker.iter_ids(),
+                &mut other_ids.iter().copied(),
                 stopped,
             )?;
             match new_internal_range.clone() {
@@ -168,8 +327,8 @@ impl SegmentBuilder {
             }
         }
 
-        if let Some(new_internal_range) = new_internal_range {
-            let internal_id_iter = new_internal_range.zip(other_id_tracker.iter_ids());
+        if let Some(new_internal_range) = new_internal_range.clone() {
+            let internal_id_iter = new_internal_range.zip(other_ids.iter().copied());
 
             for (new_internal_id, old_internal_id) in internal_id_iter {
                 check_process_stopped(stopped)?;
@@ -236,20 +395,17 @@ impl SegmentBuilder {
             }
         }
 
-        for (field, payload_schema) in other.payload_index.borrow().indexed_fields() {
-            self.indexed_fields.insert(field, payload_schema);
-        }
-
         id_tracker.mapping_flusher()()?;
         id_tracker.versions_flusher()()?;
 
-        Ok(true)
+        Ok(new_internal_range)
     }
 
     pub fn build(self, permit: CpuPermit, stopped: &AtomicBool) -> Result<Segment, OperationError> {
         let (temp_path, destination_path) = {
             let SegmentBuilder {
                 version,
+                database,
                 id_tracker,
                 payload_storage,
                 mut vector_storages,
@@ -257,6 +413,7 @@ impl SegmentBuilder {
                 destination_path,
                 temp_path,
                 indexed_fields,
+                mut old_graphs,
             } = self;
 
             let appendable_flag = segment_config.is_appendable();
@@ -266,6 +423,16 @@ impl SegmentBuilder {
 
             id_tracker.mapping_flusher()()?;
             id_tracker.versions_flusher()()?;
+            let id_tracker = match id_tracker {
+                IdTrackerEnum::MutableIdTracker(id_tracker) if !appendable_flag => {
+                    IdTrackerEnum::ImmutableIdTracker(build_immutable_id_tracker(
+                        database,
+                        &temp_path,
+                        &id_tracker,
+                    )?)
+                }
+                id_tracker => id_tracker,
+            };
             let id_tracker_arc = Arc::new(AtomicRefCell::new(id_tracker));
 
             let payload_index_path = get_payload_index_path(temp_path.as_path());
@@ -321,6 +488,14 @@ impl SegmentBuilder {
                     quantized_vectors_arc,
                 )?;
 
+                if let Some(old_graph) = old_graphs.remove(vector_name) {
+                    match &mut vector_index {
+                        VectorIndexEnum::HnswRam(index) => index.set_old_graph(old_graph),
+                        VectorIndexEnum::HnswMmap(index) => index.set_old_graph(old_graph),
+                        _ => {}
+                    }
+                }
+
                 vector_index.build_index(permit.clone(), stopped)?;
             }
 
@@ -431,3 +606,21 @@ impl SegmentBuilder {
         Ok(quantized_vectors_map)
     }
 }
+
+/// Value of the tenant field of a point, points are grouped by it in the new segment
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum TenantKey {
+    Int(IntPayloadType),
+    Keyword(String),
+}
+
+impl TenantKey {
+    /// Use the first value, which can be indexed as a tenant. Points without it go first.
+    fn from_values(values: MultiValue<&Value>) -> Option<Self> {
+        values.into_iter().find_map(|value| match value {
+            Value::Number(number) => number.as_i64().map(TenantKey::Int),
+            Value::String(keyword) => Some(TenantKey::Keyword(keyword.clone())),
+            _ => None,
+        })
+    }
+}
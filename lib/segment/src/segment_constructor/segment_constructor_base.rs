@@ -14,8 +14,11 @@ use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
-use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+use crate::common::rocksdb_wrapper::{
+    open_db, DatabaseColumnWrapper, DB_MAPPING_CF, DB_VECTOR_CF, DB_VERSIONS_CF,
+};
 use crate::data_types::vectors::DEFAULT_VECTOR_NAME;
+use crate::id_tracker::immutable_id_tracker::{ImmutableIdTracker, ID_TRACKER_PATH};
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
 use crate::id_tracker::{IdTracker, IdTrackerEnum, IdTrackerSS};
 use crate::index::hnsw_index::graph_links::{GraphLinksMmap, GraphLinksRam};
@@ -25,13 +28,15 @@ use crate::index::sparse_index::sparse_index_config::SparseIndexType;
 use crate::index::sparse_index::sparse_vector_index::SparseVectorIndex;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::VectorIndexEnum;
+use crate::payload_storage::mmap_payload_storage::{MmapPayloadStorage, MMAP_PAYLOAD_STORAGE_PATH};
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
 use crate::segment::{Segment, SegmentVersion, VectorData, SEGMENT_STATE_FILE};
 use crate::types::{
     Distance, Indexes, PayloadStorageType, SegmentConfig, SegmentState, SegmentType, SeqNumberType,
-    SparseVectorDataConfig, VectorDataConfig, VectorStorageDatatype, VectorStorageType,
+    SparseVectorDataConfig, SparseVectorStorageType, VectorDataConfig, VectorStorageDatatype,
+    VectorStorageType,
 };
 use crate::vector_storage::dense::appendable_mmap_dense_vector_storage::{
     open_appendable_memmap_vector_storage, open_appendable_memmap_vector_storage_byte,
@@ -54,6 +59,8 @@ use crate::vector_storage::multi_dense::simple_multi_dense_vector_storage::{
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::simple_sparse_vector_storage::open_simple_sparse_vector_storage;
+use crate::vector_storage::sparse::appendable_mmap_sparse_vector_storage::open_appendable_mmap_sparse_vector_storage;
+use crate::vector_storage::sparse::mmap_sparse_vector_storage::open_mmap_sparse_vector_storage;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
 pub const PAYLOAD_INDEX_PATH: &str = "payload_index";
@@ -269,8 +276,10 @@ pub(crate) fn open_segment_db(
 
 pub(crate) fn create_payload_storage(
     database: Arc<RwLock<DB>>,
+    segment_path: &Path,
     config: &SegmentConfig,
 ) -> OperationResult<PayloadStorageEnum> {
+    let mmap_storage_path = segment_path.join(MMAP_PAYLOAD_STORAGE_PATH);
     let payload_storage = match config.payload_storage_type {
         PayloadStorageType::InMemory => {
             PayloadStorageEnum::from(SimplePayloadStorage::open(database)?)
@@ -278,16 +287,54 @@ pub(crate) fn create_payload_storage(
         PayloadStorageType::OnDisk => {
             PayloadStorageEnum::from(OnDiskPayloadStorage::open(database)?)
         }
+        PayloadStorageType::Mmap => {
+            PayloadStorageEnum::from(MmapPayloadStorage::open(&mmap_storage_path, false)?)
+        }
+        PayloadStorageType::InRamMmap => {
+            PayloadStorageEnum::from(MmapPayloadStorage::open(&mmap_storage_path, true)?)
+        }
     };
     Ok(payload_storage)
 }
 
-pub(crate) fn create_id_tracker(database: Arc<RwLock<DB>>) -> OperationResult<IdTrackerEnum> {
+/// Non-appendable segments use the immutable id tracker, once it is built by the segment builder.
+///
+/// Segments created without it, including ones of older versions, keep the mappings in RocksDB
+/// and are loaded with the simple id tracker.
+pub(crate) fn create_id_tracker(
+    database: Arc<RwLock<DB>>,
+    segment_path: &Path,
+    is_appendable: bool,
+) -> OperationResult<IdTrackerEnum> {
+    let id_tracker_path = segment_path.join(ID_TRACKER_PATH);
+    if !is_appendable && ImmutableIdTracker::files_exist(&id_tracker_path) {
+        return Ok(IdTrackerEnum::ImmutableIdTracker(ImmutableIdTracker::open(
+            &id_tracker_path,
+        )?));
+    }
+
     Ok(IdTrackerEnum::MutableIdTracker(SimpleIdTracker::open(
         database,
     )?))
 }
 
+/// Write mappings of the tracker into the immutable id tracker of the segment.
+///
+/// Mappings are removed from RocksDB only after the files are complete,
+/// so the segment stays loadable if the conversion is interrupted.
+pub(crate) fn build_immutable_id_tracker(
+    database: Arc<RwLock<DB>>,
+    segment_path: &Path,
+    id_tracker: &SimpleIdTracker,
+) -> OperationResult<ImmutableIdTracker> {
+    let immutable_id_tracker =
+        ImmutableIdTracker::build(&segment_path.join(ID_TRACKER_PATH), id_tracker)?;
+    for column_name in [DB_MAPPING_CF, DB_VERSIONS_CF] {
+        DatabaseColumnWrapper::new(database.clone(), column_name).recreate_column_family()?;
+    }
+    Ok(immutable_id_tracker)
+}
+
 pub(crate) fn get_payload_index_path(segment_path: &Path) -> PathBuf {
     segment_path.join(PAYLOAD_INDEX_PATH)
 }
@@ -340,45 +387,122 @@ pub(crate) fn create_sparse_vector_index(
     payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorIndexEnum> {
-    let vector_index = match sparse_vector_config.index.index_type {
-        SparseIndexType::MutableRam => VectorIndexEnum::SparseRam(SparseVectorIndex::open(
+    // Sparse indexes without an explicit datatype are kept in the legacy uncompressed format
+    let vector_index = match (
+        sparse_vector_config.index.index_type,
+        sparse_vector_config.index.datatype,
+    ) {
+        (SparseIndexType::MutableRam, _) => VectorIndexEnum::SparseRam(SparseVectorIndex::open(
             sparse_vector_config.index,
-            id_tracker.clone(),
-            vector_storage.clone(),
-            payload_index.clone(),
+            id_tracker,
+            vector_storage,
+            payload_index,
             vector_index_path,
             stopped,
         )?),
-        SparseIndexType::ImmutableRam => {
+        (SparseIndexType::ImmutableRam, None) => {
             VectorIndexEnum::SparseImmutableRam(SparseVectorIndex::open(
                 sparse_vector_config.index,
-                id_tracker.clone(),
-                vector_storage.clone(),
-                payload_index.clone(),
+                id_tracker,
+                vector_storage,
+                payload_index,
+                vector_index_path,
+                stopped,
+            )?)
+        }
+        (SparseIndexType::ImmutableRam, Some(VectorStorageDatatype::Float32)) => {
+            VectorIndexEnum::SparseCompressedImmutableRamF32(SparseVectorIndex::open(
+                sparse_vector_config.index,
+                id_tracker,
+                vector_storage,
+                payload_index,
+                vector_index_path,
+                stopped,
+            )?)
+        }
+        (SparseIndexType::ImmutableRam, Some(VectorStorageDatatype::Float16)) => {
+            VectorIndexEnum::SparseCompressedImmutableRamF16(SparseVectorIndex::open(
+                sparse_vector_config.index,
+                id_tracker,
+                vector_storage,
+                payload_index,
+                vector_index_path,
+                stopped,
+            )?)
+        }
+        (SparseIndexType::ImmutableRam, Some(VectorStorageDatatype::Uint8)) => {
+            VectorIndexEnum::SparseCompressedImmutableRamU8(SparseVectorIndex::open(
+                sparse_vector_config.index,
+                id_tracker,
+                vector_storage,
+                payload_index,
                 vector_index_path,
                 stopped,
             )?)
         }
-        SparseIndexType::Mmap => VectorIndexEnum::SparseMmap(SparseVectorIndex::open(
+        (SparseIndexType::Mmap, None) => VectorIndexEnum::SparseMmap(SparseVectorIndex::open(
             sparse_vector_config.index,
-            id_tracker.clone(),
-            vector_storage.clone(),
-            payload_index.clone(),
+            id_tracker,
+            vector_storage,
+            payload_index,
             vector_index_path,
             stopped,
         )?),
+        (SparseIndexType::Mmap, Some(VectorStorageDatatype::Float32)) => {
+            VectorIndexEnum::SparseCompressedMmapF32(SparseVectorIndex::open(
+                sparse_vector_config.index,
+                id_tracker,
+                vector_storage,
+                payload_index,
+                vector_index_path,
+                stopped,
+            )?)
+        }
+        (SparseIndexType::Mmap, Some(VectorStorageDatatype::Float16)) => {
+            VectorIndexEnum::SparseCompressedMmapF16(SparseVectorIndex::open(
+                sparse_vector_config.index,
+                id_tracker,
+                vector_storage,
+                payload_index,
+                vector_index_path,
+                stopped,
+            )?)
+        }
+        (SparseIndexType::Mmap, Some(VectorStorageDatatype::Uint8)) => {
+            VectorIndexEnum::SparseCompressedMmapU8(SparseVectorIndex::open(
+                sparse_vector_config.index,
+                id_tracker,
+                vector_storage,
+                payload_index,
+                vector_index_path,
+                stopped,
+            )?)
+        }
     };
 
     Ok(vector_index)
 }
 
+/// Appendable segments keep mmap sparse vectors in chunks, so they can be updated in place.
+/// Non-appendable segments store them compactly.
 pub(crate) fn create_sparse_vector_storage(
     database: Arc<RwLock<DB>>,
+    vector_storage_path: &Path,
     vector_name: &str,
+    storage_type: SparseVectorStorageType,
+    is_appendable: bool,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
-    let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
-    open_simple_sparse_vector_storage(database, &db_column_name, stopped)
+    match storage_type {
+        SparseVectorStorageType::OnDisk => {
+            let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+            open_simple_sparse_vector_storage(database, &db_column_name, stopped)
+        }
+        SparseVectorStorageType::Mmap if is_appendable => {
+            open_appendable_mmap_sparse_vector_storage(vector_storage_path)
+        }
+        SparseVectorStorageType::Mmap => open_mmap_sparse_vector_storage(vector_storage_path),
+    }
 }
 
 fn create_segment(
@@ -388,15 +512,23 @@ fn create_segment(
     stopped: &AtomicBool,
 ) -> OperationResult<Segment> {
     let database = open_segment_db(segment_path, config)?;
-    let payload_storage = sp(create_payload_storage(database.clone(), config)?);
+    let payload_storage = sp(create_payload_storage(
+        database.clone(),
+        segment_path,
+        config,
+    )?);
 
     let appendable_flag = config.is_appendable();
 
-    let id_tracker = sp(create_id_tracker(database.clone())?);
+    let id_tracker = sp(create_id_tracker(
+        database.clone(),
+        segment_path,
+        appendable_flag,
+    )?);
 
     let payload_index_path = get_payload_index_path(segment_path);
     let payload_index: Arc<AtomicRefCell<StructPayloadIndex>> = sp(StructPayloadIndex::open(
-        payload_storage,
+        payload_storage.clone(),
         id_tracker.clone(),
         &payload_index_path,
         appendable_flag,
@@ -466,7 +598,10 @@ fn create_segment(
 
         let vector_storage = sp(create_sparse_vector_storage(
             database.clone(),
+            &vector_storage_path,
             vector_name,
+            sparse_vector_config.storage_type,
+            appendable_flag,
             stopped,
         )?);
 
@@ -516,6 +651,7 @@ fn create_segment(
         segment_type,
         appendable_flag,
         payload_index,
+        payload_storage,
         segment_config: config.clone(),
         error_status: None,
         database,
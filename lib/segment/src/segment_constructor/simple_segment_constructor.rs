@@ -31,6 +31,8 @@ pub fn build_simple_segment(
                     quantization_config: None,
                     multivec_config: None,
                     datatype: None,
+                    validation: None,
+                    normalize: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -57,6 +59,8 @@ pub fn build_multivec_segment(
             quantization_config: None,
             multivec_config: None,
             datatype: None,
+            validation: None,
+            normalize: None,
         },
     );
     vectors_config.insert(
@@ -69,6 +73,8 @@ pub fn build_multivec_segment(
             quantization_config: None,
             multivec_config: None,
             datatype: None,
+            validation: None,
+            normalize: None,
         },
     );
 
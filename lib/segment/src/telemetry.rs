@@ -64,6 +64,24 @@ pub struct VectorIndexSearchesTelemetry {
     #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
     pub filtered_exact: OperationDurationStatistics,
 
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub filtered_range_plain: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub filtered_range_hnsw: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub filtered_geo_plain: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub filtered_geo_hnsw: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub filtered_one_hop: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub filtered_two_hop: OperationDurationStatistics,
+
     #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
     pub filtered_sparse: OperationDurationStatistics,
 
@@ -115,6 +133,7 @@ impl Anonymize for PayloadIndexInfo {
             data_type: self.data_type,
             params: self.params.clone(),
             points: self.points.anonymize(),
+            disk_usage_bytes: self.disk_usage_bytes.anonymize(),
         }
     }
 }
@@ -139,6 +158,8 @@ impl Anonymize for VectorDataConfig {
             quantization_config: self.quantization_config.clone(),
             multivec_config: self.multivec_config,
             datatype: self.datatype,
+            validation: self.validation,
+            normalize: self.normalize,
         }
     }
 }
@@ -147,6 +168,7 @@ impl Anonymize for SparseVectorDataConfig {
     fn anonymize(&self) -> Self {
         SparseVectorDataConfig {
             index: self.index.anonymize(),
+            storage_type: self.storage_type,
         }
     }
 }
@@ -162,6 +184,12 @@ impl Anonymize for VectorIndexSearchesTelemetry {
             filtered_small_cardinality: self.filtered_small_cardinality.anonymize(),
             filtered_large_cardinality: self.filtered_large_cardinality.anonymize(),
             filtered_exact: self.filtered_exact.anonymize(),
+            filtered_range_plain: self.filtered_range_plain.anonymize(),
+            filtered_range_hnsw: self.filtered_range_hnsw.anonymize(),
+            filtered_geo_plain: self.filtered_geo_plain.anonymize(),
+            filtered_geo_hnsw: self.filtered_geo_hnsw.anonymize(),
+            filtered_one_hop: self.filtered_one_hop.anonymize(),
+            filtered_two_hop: self.filtered_two_hop.anonymize(),
             filtered_sparse: self.filtered_sparse.anonymize(),
             unfiltered_exact: self.filtered_exact.anonymize(),
         }
@@ -23,9 +23,15 @@ use validator::{Validate, ValidationError, ValidationErrors};
 
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::utils::{self, MaybeOneOrMany, MultiValue};
+use crate::data_types::bool_index::BoolIndexParams;
+use crate::data_types::datetime_index::DatetimeIndexParams;
+use crate::data_types::float_index::FloatIndexParams;
+use crate::data_types::geo_index::GeoIndexParams;
 use crate::data_types::integer_index::IntegerIndexParams;
+use crate::data_types::keyword_index::KeywordIndexParams;
 use crate::data_types::order_by::OrderValue;
 use crate::data_types::text_index::TextIndexParams;
+use crate::data_types::uuid_index::UuidIndexParams;
 use crate::data_types::vectors::VectorStruct;
 use crate::index::sparse_index::sparse_index_config::SparseIndexConfig;
 use crate::json_path::{JsonPath, JsonPathInterface};
@@ -44,6 +50,8 @@ pub type FloatPayloadType = f64;
 pub type IntPayloadType = i64;
 /// Type of datetime point payload
 pub type DateTimePayloadType = DateTimeWrapper;
+/// Type of uuid point payload, as stored in the index
+pub type UuidIntType = u128;
 
 /// Wraps `DateTime<Utc>` to allow more flexible deserialization
 #[derive(Clone, Copy, Serialize, JsonSchema, Debug, PartialEq, PartialOrd)]
@@ -261,27 +269,29 @@ pub struct PayloadIndexInfo {
     pub params: Option<PayloadSchemaParams>,
     /// Number of points indexed with this index
     pub points: usize,
+    /// Size of the on-disk files of the index in bytes, present only for indexes stored on disk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_usage_bytes: Option<usize>,
 }
 
 impl PayloadIndexInfo {
-    pub fn new(field_type: PayloadFieldSchema, points_count: usize) -> Self {
+    pub fn new(
+        field_type: PayloadFieldSchema,
+        points_count: usize,
+        disk_usage_bytes: Option<usize>,
+    ) -> Self {
         match field_type {
             PayloadFieldSchema::FieldType(data_type) => PayloadIndexInfo {
                 data_type,
                 params: None,
                 points: points_count,
+                disk_usage_bytes,
             },
-            PayloadFieldSchema::FieldParams(schema_params) => match schema_params {
-                PayloadSchemaParams::Text(_) => PayloadIndexInfo {
-                    data_type: PayloadSchemaType::Text,
-                    params: Some(schema_params),
-                    points: points_count,
-                },
-                PayloadSchemaParams::Integer(_) => PayloadIndexInfo {
-                    data_type: PayloadSchemaType::Integer,
-                    params: Some(schema_params),
-                    points: points_count,
-                },
+            PayloadFieldSchema::FieldParams(schema_params) => PayloadIndexInfo {
+                data_type: schema_params.data_type(),
+                params: Some(schema_params),
+                points: points_count,
+                disk_usage_bytes,
             },
         }
     }
@@ -367,6 +377,18 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[serde(default)]
     pub indexed_only: bool,
+
+    /// Maximal number of query token vectors used for multivector search, the query is pooled
+    /// the same way as stored multivectors. Overrides `max_tokens` of the multivector config.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_tokens: Option<usize>,
+
+    /// Filtered graph search explores neighbours of the neighbours, which don't match the filter
+    /// (ACORN-style two-hop traversal). Keeps the search connected with selective filters.
+    /// If not set, it is enabled automatically for filters of medium selectivity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acorn: Option<bool>,
 }
 
 /// Collection default values
@@ -685,11 +707,15 @@ pub enum PayloadStorageType {
     InMemory,
     // Store payload on disk only, read each time it is requested
     OnDisk,
+    // Store payload in memory mapped pages, read each time it is requested
+    Mmap,
+    // Store payload in memory mapped pages, which are loaded into the page cache on start
+    InRamMmap,
 }
 
 impl PayloadStorageType {
     pub fn is_on_disk(&self) -> bool {
-        matches!(self, PayloadStorageType::OnDisk)
+        matches!(self, PayloadStorageType::OnDisk | PayloadStorageType::Mmap)
     }
 }
 
@@ -798,7 +824,7 @@ pub enum VectorStorageType {
 }
 
 /// Storage types for vectors
-#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone)]
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum VectorStorageDatatype {
     // Single-precision floating point
@@ -810,18 +836,112 @@ pub enum VectorStorageDatatype {
     Uint8,
 }
 
-#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
+#[derive(
+    Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Eq, PartialEq, Copy, Clone, Hash,
+)]
 #[serde(rename_all = "snake_case")]
 pub struct MultiVectorConfig {
     /// How to compare multivector points
     pub comparator: MultiVectorComparator,
+    /// Maximal number of token vectors stored per point, extra tokens are pooled on ingest.
+    /// Also limits the number of query tokens, unless overridden in search params.
+    /// Default: no limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_tokens: Option<usize>,
+    /// How to reduce the number of token vectors down to `max_tokens`.
+    /// Default: keep_first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pooling: Option<TokenPooling>,
+    /// If set, the HNSW graph is built over a single representative vector per point,
+    /// search candidates are then re-scored with the full `comparator`.
+    /// Makes indexing of points with many tokens much faster, at the cost of some accuracy.
+    /// Default: graph is built with the full `comparator`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub representative: Option<MultiVectorRepresentative>,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum MultiVectorComparator {
+    /// Sum of maximal similarities of each query token
     #[default]
     MaxSim,
+    /// MaxSim averaged over the query tokens, comparable between queries of different length
+    AvgMaxSim,
+    /// Mean of MaxSim in both directions, comparing `a` to `b` gives the same score as `b` to `a`
+    SymmetricMaxSim,
+}
+
+/// Method to reduce the number of token vectors of a multivector
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenPooling {
+    /// Keep the first `max_tokens` token vectors, drop the rest
+    #[default]
+    KeepFirst,
+    /// Merge the most similar token vectors into their mean, until `max_tokens` are left
+    HierarchicalClustering,
+}
+
+/// Single vector which represents a multivector in the HNSW graph
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiVectorRepresentative {
+    /// Mean of all token vectors
+    #[default]
+    Mean,
+    /// First token vector, e.g. the `[CLS]` token
+    FirstToken,
+}
+
+/// Maximal deviation of the vector length from 1, for vectors to count as unit-norm
+pub const UNIT_NORM_TOLERANCE: f32 = 1e-3;
+
+/// Checks of input vectors, which are applied before the vectors are stored
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct VectorValidationConfig {
+    /// Reject vectors with NaN or infinite values.
+    /// Default: false
+    #[serde(default)]
+    pub reject_non_finite: bool,
+    /// Reject vectors with all values equal to zero.
+    /// Default: false
+    #[serde(default)]
+    pub reject_zero: bool,
+    /// Reject vectors, which are not of unit length.
+    /// Default: false
+    #[serde(default)]
+    pub require_unit_norm: bool,
+}
+
+impl VectorValidationConfig {
+    /// Check a single dense vector, or a single token vector of a multivector
+    ///
+    /// Returns the description of the problem, if the vector is invalid.
+    pub fn check_dense(&self, vector: &[f32]) -> Result<(), &'static str> {
+        if self.reject_non_finite && vector.iter().any(|value| !value.is_finite()) {
+            return Err("contains NaN or infinite values");
+        }
+        if self.reject_zero && vector.iter().all(|&value| value == 0.0) {
+            return Err("has all values equal to zero");
+        }
+        if self.require_unit_norm {
+            let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+            if norm.is_nan() || (norm - 1.0).abs() > UNIT_NORM_TOLERANCE {
+                return Err("is not of unit length");
+            }
+        }
+        Ok(())
+    }
+
+    /// Error for an invalid vector, which identifies the point and the vector
+    pub fn error(point_id: PointIdType, vector_name: &str, problem: &str) -> OperationError {
+        OperationError::ValidationError {
+            description: format!("vector `{vector_name}` of point {point_id} {problem}"),
+        }
+    }
 }
 
 impl VectorStorageType {
@@ -854,6 +974,13 @@ pub struct VectorDataConfig {
     /// Vector specific configuration to set specific storage element type
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub datatype: Option<VectorStorageDatatype>,
+    /// Checks of input vectors, invalid vectors are rejected on insert
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation: Option<VectorValidationConfig>,
+    /// If true, vectors are normalized to unit length on insert, whatever the distance is.
+    /// Vectors of `Cosine` distance are always normalized.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<bool>,
 }
 
 impl VectorDataConfig {
@@ -874,12 +1001,28 @@ impl VectorDataConfig {
     }
 }
 
+/// Storage types for sparse vectors
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Hash, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum SparseVectorStorageType {
+    /// Storage in RocksDB, kept for segments created before mmap storage was introduced
+    #[default]
+    OnDisk,
+    /// Storage in memory mapped files
+    ///
+    /// Appendable segments store vectors in chunks, non-appendable segments store them compactly.
+    Mmap,
+}
+
 /// Config of single sparse vector data storage
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct SparseVectorDataConfig {
     /// Sparse inverted index config
     pub index: SparseIndexConfig,
+    /// Type of storage this sparse vector uses
+    #[serde(default)]
+    pub storage_type: SparseVectorStorageType,
 }
 
 impl SparseVectorDataConfig {
@@ -904,6 +1047,7 @@ pub struct SegmentState {
 /// Geo point payload schema
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
 #[serde(try_from = "GeoPointShadow")]
+#[repr(C)]
 pub struct GeoPoint {
     pub lon: f64,
     pub lat: f64,
@@ -1150,6 +1294,7 @@ pub enum PayloadSchemaType {
     Text,
     Bool,
     Datetime,
+    Uuid,
 }
 
 /// Payload type with parameters
@@ -1158,6 +1303,43 @@ pub enum PayloadSchemaType {
 pub enum PayloadSchemaParams {
     Text(TextIndexParams),
     Integer(IntegerIndexParams),
+    Keyword(KeywordIndexParams),
+    Float(FloatIndexParams),
+    Geo(GeoIndexParams),
+    Bool(BoolIndexParams),
+    Datetime(DatetimeIndexParams),
+    Uuid(UuidIndexParams),
+}
+
+impl PayloadSchemaParams {
+    /// Type of the payload values, indexed with these params
+    pub fn data_type(&self) -> PayloadSchemaType {
+        match self {
+            PayloadSchemaParams::Text(_) => PayloadSchemaType::Text,
+            PayloadSchemaParams::Integer(_) => PayloadSchemaType::Integer,
+            PayloadSchemaParams::Keyword(_) => PayloadSchemaType::Keyword,
+            PayloadSchemaParams::Float(_) => PayloadSchemaType::Float,
+            PayloadSchemaParams::Geo(_) => PayloadSchemaType::Geo,
+            PayloadSchemaParams::Bool(_) => PayloadSchemaType::Bool,
+            PayloadSchemaParams::Datetime(_) => PayloadSchemaType::Datetime,
+            PayloadSchemaParams::Uuid(_) => PayloadSchemaType::Uuid,
+        }
+    }
+
+    /// Whether the index should be stored on disk in non-appendable segments
+    pub fn is_on_disk(&self) -> bool {
+        let on_disk = match self {
+            PayloadSchemaParams::Text(params) => params.on_disk,
+            PayloadSchemaParams::Integer(params) => params.on_disk,
+            PayloadSchemaParams::Keyword(params) => params.on_disk,
+            PayloadSchemaParams::Float(params) => params.on_disk,
+            PayloadSchemaParams::Geo(params) => params.on_disk,
+            PayloadSchemaParams::Bool(params) => params.on_disk,
+            PayloadSchemaParams::Datetime(params) => params.on_disk,
+            PayloadSchemaParams::Uuid(params) => params.on_disk,
+        };
+        on_disk.unwrap_or(false)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
@@ -1168,6 +1350,13 @@ pub enum PayloadFieldSchema {
 }
 
 impl PayloadFieldSchema {
+    pub fn data_type(&self) -> PayloadSchemaType {
+        match self {
+            PayloadFieldSchema::FieldType(data_type) => *data_type,
+            PayloadFieldSchema::FieldParams(params) => params.data_type(),
+        }
+    }
+
     pub fn has_range_index(&self) -> bool {
         match self {
             PayloadFieldSchema::FieldType(PayloadSchemaType::Integer)
@@ -1178,7 +1367,15 @@ impl PayloadFieldSchema {
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Text)
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Geo)
-            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(_)) => false,
+            | PayloadFieldSchema::FieldType(PayloadSchemaType::Uuid)
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Keyword(_))
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(_))
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Geo(_))
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Bool(_))
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Uuid(_)) => false,
+
+            PayloadFieldSchema::FieldParams(PayloadSchemaParams::Float(_))
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Datetime(_)) => true,
 
             PayloadFieldSchema::FieldParams(PayloadSchemaParams::Integer(IntegerIndexParams {
                 range,
@@ -1186,6 +1383,35 @@ impl PayloadFieldSchema {
             })) => *range,
         }
     }
+
+    /// Whether the field is used to partition points by tenant
+    pub fn is_tenant(&self) -> bool {
+        match self {
+            PayloadFieldSchema::FieldType(_) => false,
+            PayloadFieldSchema::FieldParams(PayloadSchemaParams::Keyword(params)) => {
+                params.is_tenant.unwrap_or(false)
+            }
+            PayloadFieldSchema::FieldParams(PayloadSchemaParams::Integer(params)) => {
+                params.is_tenant.unwrap_or(false)
+            }
+            PayloadFieldSchema::FieldParams(
+                PayloadSchemaParams::Text(_)
+                | PayloadSchemaParams::Float(_)
+                | PayloadSchemaParams::Geo(_)
+                | PayloadSchemaParams::Bool(_)
+                | PayloadSchemaParams::Datetime(_)
+                | PayloadSchemaParams::Uuid(_),
+            ) => false,
+        }
+    }
+
+    /// Whether the index should be stored on disk in non-appendable segments
+    pub fn is_on_disk(&self) -> bool {
+        match self {
+            PayloadFieldSchema::FieldType(_) => false,
+            PayloadFieldSchema::FieldParams(params) => params.is_on_disk(),
+        }
+    }
 }
 
 impl From<PayloadSchemaType> for PayloadFieldSchema {
@@ -1202,17 +1428,14 @@ impl TryFrom<PayloadIndexInfo> for PayloadFieldSchema {
             return Ok(PayloadFieldSchema::FieldType(index_info.data_type));
         };
 
-        match (index_info.data_type, params) {
-            (PayloadSchemaType::Text, PayloadSchemaParams::Text(params)) => Ok(
-                PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(params)),
-            ),
-            (PayloadSchemaType::Integer, PayloadSchemaParams::Integer(params)) => Ok(
-                PayloadFieldSchema::FieldParams(PayloadSchemaParams::Integer(params)),
-            ),
-            (data_type, PayloadSchemaParams::Integer(_) | PayloadSchemaParams::Text(_)) => Err(
-                format!("Payload field with type {data_type:?} has unexpected params"),
-            ),
+        if params.data_type() != index_info.data_type {
+            return Err(format!(
+                "Payload field with type {:?} has unexpected params",
+                index_info.data_type
+            ));
         }
+
+        Ok(PayloadFieldSchema::FieldParams(params))
     }
 }
 
@@ -3182,6 +3405,24 @@ mod tests {
         assert!(filter.is_ok());
     }
 
+    #[test]
+    fn test_vector_validation() {
+        let no_checks = VectorValidationConfig::default();
+        assert!(no_checks.check_dense(&[f32::NAN, 0.0]).is_ok());
+        assert!(no_checks.check_dense(&[0.0, 0.0]).is_ok());
+
+        let config = VectorValidationConfig {
+            reject_non_finite: true,
+            reject_zero: true,
+            require_unit_norm: true,
+        };
+        assert!(config.check_dense(&[0.6, 0.8]).is_ok());
+        assert!(config.check_dense(&[f32::NAN, 1.0]).is_err());
+        assert!(config.check_dense(&[f32::INFINITY, 0.0]).is_err());
+        assert!(config.check_dense(&[0.0, 0.0]).is_err());
+        assert!(config.check_dense(&[1.0, 1.0]).is_err());
+    }
+
     #[test]
     fn test_remove_key() {
         test_remove_key_impl::<JsonPathString>();
@@ -138,6 +138,11 @@ impl<T: Sized + Copy + 'static> ChunkedMmapVectors<T> {
         self.config.dim
     }
 
+    /// Maximal number of vectors in a single chunk
+    pub fn chunk_size_vectors(&self) -> usize {
+        self.config.chunk_size_vectors
+    }
+
     fn add_chunk(&mut self) -> OperationResult<()> {
         let chunk = create_chunk(
             &self.directory,
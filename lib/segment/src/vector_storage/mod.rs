@@ -1,7 +1,7 @@
 #[cfg(target_os = "linux")]
 pub mod async_raw_scorer;
-mod chunked_mmap_vectors;
-mod chunked_utils;
+pub(crate) mod chunked_mmap_vectors;
+pub(crate) mod chunked_utils;
 pub mod chunked_vectors;
 pub mod quantized;
 pub mod raw_scorer;
@@ -20,6 +20,7 @@ pub mod multi_dense;
 pub mod query;
 mod query_scorer;
 pub mod simple_sparse_vector_storage;
+pub mod sparse;
 
 pub use raw_scorer::*;
 pub use vector_storage_base::*;
@@ -194,6 +194,63 @@ where
         }
         sum
     }
+
+    /// Custom `score_symmetric_max_similarity` implementation for quantized vectors
+    fn score_point_symmetric_max_similarity(
+        &self,
+        query: &Vec<TEncodedQuery>,
+        vector_index: PointOffsetType,
+    ) -> ScoreType {
+        let offset = self.offsets.get_offset(vector_index);
+        let mut max_sims_point = vec![ScoreType::NEG_INFINITY; offset.count as usize];
+        let mut sum_query = 0.0;
+        for inner_query in query {
+            let mut max_sim_query = ScoreType::NEG_INFINITY;
+            for (i, max_sim_point) in max_sims_point.iter_mut().enumerate() {
+                let sim = self
+                    .quantized_storage
+                    .score_point(inner_query, offset.start + i as PointOffsetType);
+                if sim > max_sim_query {
+                    max_sim_query = sim;
+                }
+                if sim > *max_sim_point {
+                    *max_sim_point = sim;
+                }
+            }
+            sum_query += max_sim_query;
+        }
+        let sum_point: ScoreType = max_sims_point.iter().sum();
+        (sum_query + sum_point) / 2.0
+    }
+
+    /// Custom `score_symmetric_max_similarity` implementation for quantized vectors
+    fn score_internal_symmetric_max_similarity(
+        &self,
+        vector_a_index: PointOffsetType,
+        vector_b_index: PointOffsetType,
+    ) -> ScoreType {
+        let offset_a = self.offsets.get_offset(vector_a_index);
+        let offset_b = self.offsets.get_offset(vector_b_index);
+        let mut max_sims_b = vec![ScoreType::NEG_INFINITY; offset_b.count as usize];
+        let mut sum_a = 0.0;
+        for a in 0..offset_a.count {
+            let mut max_sim_a = ScoreType::NEG_INFINITY;
+            for (b, max_sim_b) in max_sims_b.iter_mut().enumerate() {
+                let sim = self
+                    .quantized_storage
+                    .score_internal(offset_a.start + a, offset_b.start + b as PointOffsetType);
+                if sim > max_sim_a {
+                    max_sim_a = sim;
+                }
+                if sim > *max_sim_b {
+                    *max_sim_b = sim;
+                }
+            }
+            sum_a += max_sim_a;
+        }
+        let sum_b: ScoreType = max_sims_b.iter().sum();
+        (sum_a + sum_b) / 2.0
+    }
 }
 
 impl<TEncodedQuery, QuantizedStorage, TMultivectorOffsetsStorage> EncodedVectors<Vec<TEncodedQuery>>
@@ -233,12 +290,25 @@ where
     fn score_point(&self, query: &Vec<TEncodedQuery>, i: PointOffsetType) -> ScoreType {
         match self.multi_vector_config.comparator {
             MultiVectorComparator::MaxSim => self.score_point_max_similarity(query, i),
+            MultiVectorComparator::AvgMaxSim => {
+                self.score_point_max_similarity(query, i) / query.len() as ScoreType
+            }
+            MultiVectorComparator::SymmetricMaxSim => {
+                self.score_point_symmetric_max_similarity(query, i)
+            }
         }
     }
 
     fn score_internal(&self, i: PointOffsetType, j: PointOffsetType) -> ScoreType {
         match self.multi_vector_config.comparator {
             MultiVectorComparator::MaxSim => self.score_internal_max_similarity(i, j),
+            MultiVectorComparator::AvgMaxSim => {
+                let count = self.offsets.get_offset(i).count;
+                self.score_internal_max_similarity(i, j) / count as ScoreType
+            }
+            MultiVectorComparator::SymmetricMaxSim => {
+                self.score_internal_symmetric_max_similarity(i, j)
+            }
         }
     }
 }
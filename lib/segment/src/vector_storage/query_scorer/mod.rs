@@ -44,6 +44,34 @@ pub fn score_max_similarity<T: PrimitiveVectorElement, TMetric: Metric<T>>(
     sum
 }
 
+/// MaxSim in both directions at once, averaged.
+/// Every similarity is computed once, it is both a candidate for the max of a row and a column.
+/// This metric is also implemented in `QuantizedMultivectorStorage` structure for quantized data.
+pub fn score_symmetric_max_similarity<T: PrimitiveVectorElement, TMetric: Metric<T>>(
+    multi_dense_a: TypedMultiDenseVectorRef<T>,
+    multi_dense_b: TypedMultiDenseVectorRef<T>,
+) -> ScoreType {
+    debug_assert!(!multi_dense_a.is_empty());
+    debug_assert!(!multi_dense_b.is_empty());
+    let mut max_sims_b = vec![ScoreType::NEG_INFINITY; multi_dense_b.vectors_count()];
+    let mut sum_a = 0.0;
+    for dense_a in multi_dense_a.multi_vectors() {
+        let mut max_sim_a = ScoreType::NEG_INFINITY;
+        for (dense_b, max_sim_b) in multi_dense_b.multi_vectors().zip(max_sims_b.iter_mut()) {
+            let sim = TMetric::similarity(dense_a, dense_b);
+            if sim > max_sim_a {
+                max_sim_a = sim;
+            }
+            if sim > *max_sim_b {
+                *max_sim_b = sim;
+            }
+        }
+        sum_a += max_sim_a;
+    }
+    let sum_b: ScoreType = max_sims_b.iter().sum();
+    (sum_a + sum_b) / 2.0
+}
+
 fn score_multi<T: PrimitiveVectorElement, TMetric: Metric<T>>(
     multi_vector_config: &MultiVectorConfig,
     multi_dense_a: TypedMultiDenseVectorRef<T>,
@@ -53,5 +81,42 @@ fn score_multi<T: PrimitiveVectorElement, TMetric: Metric<T>>(
         MultiVectorComparator::MaxSim => {
             score_max_similarity::<T, TMetric>(multi_dense_a, multi_dense_b)
         }
+        MultiVectorComparator::AvgMaxSim => {
+            let count = multi_dense_a.vectors_count();
+            score_max_similarity::<T, TMetric>(multi_dense_a, multi_dense_b) / count as ScoreType
+        }
+        MultiVectorComparator::SymmetricMaxSim => {
+            score_symmetric_max_similarity::<T, TMetric>(multi_dense_a, multi_dense_b)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::vectors::MultiDenseVector;
+    use crate::spaces::simple::DotProductMetric;
+
+    #[test]
+    fn test_multi_vector_comparators() {
+        let a = MultiDenseVector::new_unchecked(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+        let b =
+            MultiDenseVector::new_unchecked(vec![vec![1.0, 0.0], vec![0.5, 0.0], vec![0.0, 0.0]]);
+        let score = |comparator, a: &MultiDenseVector, b: &MultiDenseVector| {
+            let config = MultiVectorConfig {
+                comparator,
+                ..Default::default()
+            };
+            score_multi::<f32, DotProductMetric>(&config, a.into(), b.into())
+        };
+
+        // 1.0 + 0.0
+        assert_eq!(score(MultiVectorComparator::MaxSim, &a, &b), 1.0);
+        // 1.0 + 0.5 + 0.0
+        assert_eq!(score(MultiVectorComparator::MaxSim, &b, &a), 1.5);
+        assert_eq!(score(MultiVectorComparator::AvgMaxSim, &a, &b), 0.5);
+        assert_eq!(score(MultiVectorComparator::AvgMaxSim, &b, &a), 0.5);
+        assert_eq!(score(MultiVectorComparator::SymmetricMaxSim, &a, &b), 1.25);
+        assert_eq!(score(MultiVectorComparator::SymmetricMaxSim, &b, &a), 1.25);
     }
 }
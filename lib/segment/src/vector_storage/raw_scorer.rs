@@ -157,6 +157,12 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::SparseSimple(vs) => {
             raw_sparse_scorer_impl(query, vs, point_deleted, is_stopped)
         }
+        VectorStorageEnum::SparseMmap(vs) => {
+            raw_sparse_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
+        VectorStorageEnum::SparseAppendableMmap(vs) => {
+            raw_sparse_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
         VectorStorageEnum::MultiDenseSimple(vs) => {
             raw_multi_scorer_impl(query, vs, point_deleted, is_stopped)
         }
@@ -234,30 +240,55 @@ pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementType>
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    raw_scorer_with_deleted_vectors_impl(
+        query,
+        vector_storage,
+        point_deleted,
+        vector_storage.deleted_vector_bitslice(),
+        is_stopped,
+    )
+}
+
+/// Same as [`raw_scorer_impl`], but vectors are checked for deletion in `vec_deleted`,
+/// instead of the deleted vectors of the storage
+pub fn raw_scorer_with_deleted_vectors_impl<
+    'a,
+    TVectorStorage: DenseVectorStorage<VectorElementType>,
+>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    vec_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
     match vector_storage.distance() {
         Distance::Cosine => new_scorer_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
             point_deleted,
+            vec_deleted,
             is_stopped,
         ),
         Distance::Euclid => new_scorer_with_metric::<EuclidMetric, _>(
             query,
             vector_storage,
             point_deleted,
+            vec_deleted,
             is_stopped,
         ),
         Distance::Dot => new_scorer_with_metric::<DotProductMetric, _>(
             query,
             vector_storage,
             point_deleted,
+            vec_deleted,
             is_stopped,
         ),
         Distance::Manhattan => new_scorer_with_metric::<ManhattanMetric, _>(
             query,
             vector_storage,
             point_deleted,
+            vec_deleted,
             is_stopped,
         ),
     }
@@ -271,9 +302,9 @@ fn new_scorer_with_metric<
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
+    vec_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    let vec_deleted = vector_storage.deleted_vector_bitslice();
     match query {
         QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
             MetricQueryScorer::<VectorElementType, TMetric, _>::new(
@@ -0,0 +1,282 @@
+use std::fs::create_dir_all;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use bitvec::prelude::BitSlice;
+use common::types::PointOffsetType;
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::{DimId, DimWeight};
+
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::VectorRef;
+use crate::types::{Distance, VectorStorageDatatype};
+use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
+use crate::vector_storage::dense::dynamic_mmap_flags::DynamicMmapFlags;
+use crate::vector_storage::simple_sparse_vector_storage::SPARSE_VECTOR_DISTANCE;
+use crate::vector_storage::{SparseVectorStorage, VectorStorage, VectorStorageEnum};
+
+const INDICES_DIR_PATH: &str = "indices";
+const WEIGHTS_DIR_PATH: &str = "weights";
+const OFFSETS_DIR_PATH: &str = "offsets";
+const DELETED_DIR_PATH: &str = "deleted";
+
+/// Position of a sparse vector in the chunked storages of indices and weights
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+struct SparseVectorMmapOffset {
+    offset: u64,
+    count: u32,
+    capacity: u32,
+}
+
+/// Stores sparse vectors in appendable chunks of memory mapped files
+///
+/// Indices and weights of each vector are stored as consecutive elements, which never cross
+/// a chunk boundary. Updated vectors reuse their place if they fit in it.
+pub struct AppendableMmapSparseVectorStorage {
+    indices: ChunkedMmapVectors<DimId>,
+    weights: ChunkedMmapVectors<DimWeight>,
+    offsets: ChunkedMmapVectors<SparseVectorMmapOffset>,
+    deleted: DynamicMmapFlags,
+    deleted_count: usize,
+}
+
+pub fn open_appendable_mmap_sparse_vector_storage(
+    path: &Path,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = AppendableMmapSparseVectorStorage::open(path)?;
+    Ok(VectorStorageEnum::SparseAppendableMmap(Box::new(storage)))
+}
+
+impl AppendableMmapSparseVectorStorage {
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        create_dir_all(path)?;
+
+        let indices = ChunkedMmapVectors::open(&path.join(INDICES_DIR_PATH), 1)?;
+        let weights = ChunkedMmapVectors::open(&path.join(WEIGHTS_DIR_PATH), 1)?;
+        let offsets = ChunkedMmapVectors::open(&path.join(OFFSETS_DIR_PATH), 1)?;
+
+        let deleted = DynamicMmapFlags::open(&path.join(DELETED_DIR_PATH))?;
+        let deleted_count = deleted.count_flags();
+
+        Ok(Self {
+            indices,
+            weights,
+            offsets,
+            deleted,
+            deleted_count,
+        })
+    }
+
+    /// Set deleted flag for given key. Returns previous deleted state.
+    #[inline]
+    fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> OperationResult<bool> {
+        if self.offsets.len() <= key as usize {
+            return Ok(false);
+        }
+
+        if self.deleted.len() <= key as usize {
+            self.deleted.set_len(key as usize + 1)?;
+        }
+        let previous = self.deleted.set(key, deleted);
+        if !previous && deleted {
+            self.deleted_count += 1;
+        } else if previous && !deleted {
+            self.deleted_count -= 1;
+        }
+        Ok(previous)
+    }
+
+    fn get_offset(&self, key: PointOffsetType) -> Option<SparseVectorMmapOffset> {
+        self.offsets
+            .get(key as usize)
+            .and_then(|offset| offset.first().copied())
+    }
+
+    /// First position after the stored elements, where `count` elements fit in a single chunk
+    fn next_free_offset(&mut self, count: usize) -> OperationResult<usize> {
+        let max_count = self
+            .indices
+            .chunk_size_vectors()
+            .min(self.weights.chunk_size_vectors());
+        if count > max_count {
+            return Err(OperationError::service_error(format!(
+                "Sparse vector with {count} elements exceeds the maximal size {max_count} of the storage chunk",
+            )));
+        }
+
+        let mut offset = self.indices.len();
+        loop {
+            let chunk_left_keys = self
+                .indices
+                .get_remaining_chunk_keys(offset)
+                .min(self.weights.get_remaining_chunk_keys(offset));
+            if count <= chunk_left_keys {
+                return Ok(offset);
+            }
+            offset += chunk_left_keys;
+        }
+    }
+}
+
+impl SparseVectorStorage for AppendableMmapSparseVectorStorage {
+    fn get_sparse(&self, key: PointOffsetType) -> OperationResult<SparseVector> {
+        let offset = self.get_offset(key).ok_or_else(|| {
+            OperationError::service_error(format!("Sparse vector {key} not found in storage"))
+        })?;
+
+        if offset.count == 0 {
+            return Ok(SparseVector::default());
+        }
+
+        let count = offset.count as usize;
+        let indices = self.indices.get_many(offset.offset, count);
+        let weights = self.weights.get_many(offset.offset, count);
+        match (indices, weights) {
+            (Some(indices), Some(weights)) => Ok(SparseVector {
+                indices: indices.to_vec(),
+                values: weights.to_vec(),
+            }),
+            _ => Err(OperationError::service_error(format!(
+                "Sparse vector {key} is out of storage bounds"
+            ))),
+        }
+    }
+}
+
+impl VectorStorage for AppendableMmapSparseVectorStorage {
+    fn distance(&self) -> Distance {
+        SPARSE_VECTOR_DISTANCE
+    }
+
+    fn datatype(&self) -> VectorStorageDatatype {
+        VectorStorageDatatype::Float32
+    }
+
+    fn is_on_disk(&self) -> bool {
+        true
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.offsets.len()
+    }
+
+    fn available_size_in_bytes(&self) -> usize {
+        if self.total_vector_count() > 0 {
+            let total_size = self.indices.len()
+                * (std::mem::size_of::<DimId>() + std::mem::size_of::<DimWeight>());
+            (total_size as u128 * self.available_vector_count() as u128
+                / self.total_vector_count() as u128) as usize
+        } else {
+            0
+        }
+    }
+
+    fn get_vector(&self, key: PointOffsetType) -> CowVector {
+        let vector = self.get_vector_opt(key);
+        debug_assert!(vector.is_some());
+        vector.unwrap_or_else(CowVector::default_sparse)
+    }
+
+    fn get_vector_opt(&self, key: PointOffsetType) -> Option<CowVector> {
+        self.get_sparse(key).ok().map(CowVector::from)
+    }
+
+    fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
+        let vector: &SparseVector = vector.try_into()?;
+        debug_assert!(vector.is_sorted());
+        let count = vector.indices.len();
+
+        let mut offset = self.get_offset(key).unwrap_or_default();
+        if count > offset.capacity as usize {
+            // append vector to the end
+            let new_offset = self.next_free_offset(count)?;
+            offset = SparseVectorMmapOffset {
+                offset: new_offset as u64,
+                count: count as u32,
+                capacity: count as u32,
+            };
+        } else {
+            // use existing place to insert vector
+            offset.count = count as u32;
+        }
+
+        if count > 0 {
+            self.indices
+                .insert_many(offset.offset, &vector.indices, count)?;
+            self.weights
+                .insert_many(offset.offset, &vector.values, count)?;
+        }
+        self.offsets.insert(key as usize, &[offset])?;
+        self.set_deleted(key, false)?;
+
+        Ok(())
+    }
+
+    fn update_from(
+        &mut self,
+        other: &VectorStorageEnum,
+        other_ids: &mut impl Iterator<Item = PointOffsetType>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.offsets.len() as PointOffsetType;
+        for point_id in other_ids {
+            check_process_stopped(stopped)?;
+            // Do not perform preprocessing - vectors should be already processed
+            let other_deleted = other.is_deleted_vector(point_id);
+            let other_vector = other.get_vector(point_id);
+            let new_id = self.offsets.len() as PointOffsetType;
+            self.insert_vector(new_id, other_vector.as_vec_ref())?;
+            self.set_deleted(new_id, other_deleted)?;
+        }
+        let end_index = self.offsets.len() as PointOffsetType;
+        Ok(start_index..end_index)
+    }
+
+    fn flusher(&self) -> Flusher {
+        Box::new({
+            let indices_flusher = self.indices.flusher();
+            let weights_flusher = self.weights.flusher();
+            let offsets_flusher = self.offsets.flusher();
+            let deleted_flusher = self.deleted.flusher();
+            move || {
+                indices_flusher()?;
+                weights_flusher()?;
+                offsets_flusher()?;
+                deleted_flusher()?;
+                Ok(())
+            }
+        })
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = self.indices.files();
+        files.extend(self.weights.files());
+        files.extend(self.offsets.files());
+        files.extend(self.deleted.files());
+        files
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        if key as usize >= self.total_vector_count() {
+            return Ok(false);
+        }
+        let was_deleted = self.set_deleted(key, true)?;
+        Ok(!was_deleted)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.deleted.get(key)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.deleted.get_bitslice()
+    }
+}
@@ -0,0 +1,226 @@
+use std::fs::create_dir_all;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use bitvec::prelude::BitSlice;
+use common::types::PointOffsetType;
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::{DimId, DimWeight};
+
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::VectorRef;
+use crate::index::field_index::mmap_point_to_values::MmapPointToValues;
+use crate::types::{Distance, VectorStorageDatatype};
+use crate::vector_storage::dense::dynamic_mmap_flags::DynamicMmapFlags;
+use crate::vector_storage::simple_sparse_vector_storage::SPARSE_VECTOR_DISTANCE;
+use crate::vector_storage::{SparseVectorStorage, VectorStorage, VectorStorageEnum};
+
+const INDICES_NAME: &str = "indices";
+const WEIGHTS_NAME: &str = "weights";
+const DELETED_DIR_PATH: &str = "deleted";
+
+/// Stores sparse vectors compactly in memory mapped files
+///
+/// Indices and weights of all vectors are stored back to back, without any spare capacity.
+/// It is not possible to insert or update vectors, but possible to mark some vectors as removed.
+///
+/// Mem-mapped storage can only be constructed from another storage
+pub struct MmapSparseVectorStorage {
+    path: PathBuf,
+    indices: MmapPointToValues<DimId>,
+    weights: MmapPointToValues<DimWeight>,
+    deleted: DynamicMmapFlags,
+    deleted_count: usize,
+}
+
+pub fn open_mmap_sparse_vector_storage(path: &Path) -> OperationResult<VectorStorageEnum> {
+    let storage = MmapSparseVectorStorage::open(path)?;
+    Ok(VectorStorageEnum::SparseMmap(Box::new(storage)))
+}
+
+impl MmapSparseVectorStorage {
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        create_dir_all(path)?;
+
+        // New storage starts with empty files, vectors are appended to them in `update_from`
+        if !MmapPointToValues::<DimId>::exists(path, INDICES_NAME) {
+            MmapPointToValues::<DimId>::create(path, INDICES_NAME, Vec::<Vec<DimId>>::new())?;
+        }
+        if !MmapPointToValues::<DimWeight>::exists(path, WEIGHTS_NAME) {
+            MmapPointToValues::<DimWeight>::create(
+                path,
+                WEIGHTS_NAME,
+                Vec::<Vec<DimWeight>>::new(),
+            )?;
+        }
+
+        let indices = MmapPointToValues::open(path, INDICES_NAME)?;
+        let weights = MmapPointToValues::open(path, WEIGHTS_NAME)?;
+        if indices.len() != weights.len() {
+            return Err(OperationError::service_error(format!(
+                "Sparse vector storage {} is corrupted: {} indices and {} weights lists",
+                path.display(),
+                indices.len(),
+                weights.len(),
+            )));
+        }
+
+        let mut deleted = DynamicMmapFlags::open(&path.join(DELETED_DIR_PATH))?;
+        if deleted.len() < indices.len() {
+            deleted.set_len(indices.len())?;
+        }
+        let deleted_count = deleted.count_flags();
+
+        Ok(Self {
+            path: path.to_owned(),
+            indices,
+            weights,
+            deleted,
+            deleted_count,
+        })
+    }
+}
+
+impl SparseVectorStorage for MmapSparseVectorStorage {
+    fn get_sparse(&self, key: PointOffsetType) -> OperationResult<SparseVector> {
+        let indices = self.indices.get_values(key);
+        let weights = self.weights.get_values(key);
+        match (indices, weights) {
+            (Some(indices), Some(weights)) => Ok(SparseVector {
+                indices: indices.to_vec(),
+                values: weights.to_vec(),
+            }),
+            _ => Err(OperationError::service_error(format!(
+                "Sparse vector {key} not found in storage"
+            ))),
+        }
+    }
+}
+
+impl VectorStorage for MmapSparseVectorStorage {
+    fn distance(&self) -> Distance {
+        SPARSE_VECTOR_DISTANCE
+    }
+
+    fn datatype(&self) -> VectorStorageDatatype {
+        VectorStorageDatatype::Float32
+    }
+
+    fn is_on_disk(&self) -> bool {
+        true
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    fn available_size_in_bytes(&self) -> usize {
+        if self.total_vector_count() > 0 {
+            let total_size = self.indices.values_count()
+                * (std::mem::size_of::<DimId>() + std::mem::size_of::<DimWeight>());
+            (total_size as u128 * self.available_vector_count() as u128
+                / self.total_vector_count() as u128) as usize
+        } else {
+            0
+        }
+    }
+
+    fn get_vector(&self, key: PointOffsetType) -> CowVector {
+        let vector = self.get_vector_opt(key);
+        debug_assert!(vector.is_some());
+        vector.unwrap_or_else(CowVector::default_sparse)
+    }
+
+    fn get_vector_opt(&self, key: PointOffsetType) -> Option<CowVector> {
+        self.get_sparse(key).ok().map(CowVector::from)
+    }
+
+    fn insert_vector(&mut self, _key: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
+        panic!("Can't directly update vector in mmap storage")
+    }
+
+    fn update_from(
+        &mut self,
+        other: &VectorStorageEnum,
+        other_ids: &mut impl Iterator<Item = PointOffsetType>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.total_vector_count() as PointOffsetType;
+
+        let mut vectors = Vec::new();
+        let mut deleted_ids = Vec::new();
+        for id in other_ids {
+            check_process_stopped(stopped)?;
+            // Do not perform preprocessing - vectors should be already processed
+            let other_vector = other.get_vector(id);
+            let other_vector: &SparseVector = other_vector.as_vec_ref().try_into()?;
+
+            // Remember deleted IDs so we can propagate deletions later
+            if other.is_deleted_vector(id) {
+                deleted_ids.push(start_index + vectors.len() as PointOffsetType);
+            }
+            vectors.push(other_vector.clone());
+        }
+
+        // Extend files with other vectors and reopen them
+        MmapPointToValues::<DimId>::append(
+            &self.path,
+            INDICES_NAME,
+            vectors.iter().map(|vector| &vector.indices),
+        )?;
+        MmapPointToValues::<DimWeight>::append(
+            &self.path,
+            WEIGHTS_NAME,
+            vectors.iter().map(|vector| &vector.values),
+        )?;
+        self.indices = MmapPointToValues::open(&self.path, INDICES_NAME)?;
+        self.weights = MmapPointToValues::open(&self.path, WEIGHTS_NAME)?;
+        let end_index = self.total_vector_count() as PointOffsetType;
+
+        // Propagate deletions into the resized flags
+        self.deleted.set_len(end_index as usize)?;
+        for id in deleted_ids {
+            check_process_stopped(stopped)?;
+            self.delete_vector(id)?;
+        }
+
+        Ok(start_index..end_index)
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.deleted.flusher()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = self.indices.files();
+        files.extend(self.weights.files());
+        files.extend(self.deleted.files());
+        files
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        if key as usize >= self.total_vector_count() {
+            return Ok(false);
+        }
+        let was_deleted = self.deleted.set(key, true);
+        if !was_deleted {
+            self.deleted_count += 1;
+        }
+        Ok(!was_deleted)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.deleted.get(key)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.deleted.get_bitslice()
+    }
+}
@@ -0,0 +1,2 @@
+pub mod appendable_mmap_sparse_vector_storage;
+pub mod mmap_sparse_vector_storage;
@@ -12,6 +12,8 @@ use crate::fixtures::payload_context_fixture::FixtureIdTracker;
 use crate::id_tracker::IdTrackerSS;
 use crate::vector_storage::query::RecoQuery;
 use crate::vector_storage::simple_sparse_vector_storage::open_simple_sparse_vector_storage;
+use crate::vector_storage::sparse::appendable_mmap_sparse_vector_storage::open_appendable_mmap_sparse_vector_storage;
+use crate::vector_storage::sparse::mmap_sparse_vector_storage::open_mmap_sparse_vector_storage;
 use crate::vector_storage::{new_raw_scorer, VectorStorage, VectorStorageEnum};
 
 fn do_test_delete_points(storage: &mut VectorStorageEnum) {
@@ -215,3 +217,78 @@ fn test_update_from_delete_points_simple_sparse_vector_storage() {
     let _storage =
         open_simple_sparse_vector_storage(db, DB_VECTOR_CF, &AtomicBool::new(false)).unwrap();
 }
+
+#[test]
+fn test_delete_points_in_appendable_mmap_sparse_vector_storage() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let mut storage = open_appendable_mmap_sparse_vector_storage(dir.path()).unwrap();
+        do_test_delete_points(&mut storage);
+        storage.flusher()().unwrap();
+    }
+    let storage = open_appendable_mmap_sparse_vector_storage(dir.path()).unwrap();
+    assert_eq!(storage.total_vector_count(), 5);
+    assert_eq!(storage.deleted_vector_count(), 5);
+}
+
+#[test]
+fn test_update_from_delete_points_appendable_mmap_sparse_vector_storage() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let mut storage = open_appendable_mmap_sparse_vector_storage(dir.path()).unwrap();
+        do_test_update_from_delete_points(&mut storage);
+        storage.flusher()().unwrap();
+    }
+    let _storage = open_appendable_mmap_sparse_vector_storage(dir.path()).unwrap();
+}
+
+#[test]
+fn test_update_from_delete_points_mmap_sparse_vector_storage() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let mut storage = open_mmap_sparse_vector_storage(dir.path()).unwrap();
+        do_test_update_from_delete_points(&mut storage);
+        storage.flusher()().unwrap();
+    }
+    let storage = open_mmap_sparse_vector_storage(dir.path()).unwrap();
+    assert_eq!(storage.total_vector_count(), 5);
+    assert_eq!(storage.deleted_vector_count(), 5);
+}
+
+#[test]
+fn test_update_vectors_in_appendable_mmap_sparse_vector_storage() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let vectors: Vec<SparseVector> = vec![
+        vec![(0, 1.0), (2, 2.0), (3, 3.0)],
+        vec![(1, 1.0)],
+        vec![],
+        vec![(0, 4.0), (1, 5.0), (2, 6.0), (3, 7.0), (4, 8.0)],
+    ]
+    .into_iter()
+    .map(|v| v.try_into().unwrap())
+    .collect();
+
+    {
+        let mut storage = open_appendable_mmap_sparse_vector_storage(dir.path()).unwrap();
+        // Shorter vector reuses the place of the first one, longer one is moved to the end
+        for (i, vec) in vectors.iter().enumerate() {
+            storage.insert_vector(0, vec.into()).unwrap();
+            storage
+                .insert_vector(i as PointOffsetType, vec.into())
+                .unwrap();
+        }
+        let stored_vec = storage.get_vector(0);
+        let sparse: &SparseVector = stored_vec.as_vec_ref().try_into().unwrap();
+        assert_eq!(sparse, &vectors[3]);
+        storage.insert_vector(0, (&vectors[0]).into()).unwrap();
+        storage.flusher()().unwrap();
+    }
+
+    let storage = open_appendable_mmap_sparse_vector_storage(dir.path()).unwrap();
+    assert_eq!(storage.total_vector_count(), vectors.len());
+    for (i, vec) in vectors.iter().enumerate() {
+        let stored_vec = storage.get_vector(i as PointOffsetType);
+        let sparse: &SparseVector = stored_vec.as_vec_ref().try_into().unwrap();
+        assert_eq!(sparse, vec);
+    }
+}
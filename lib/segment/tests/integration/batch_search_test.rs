@@ -44,6 +44,8 @@ fn test_batch_and_single_request_equivalency() {
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
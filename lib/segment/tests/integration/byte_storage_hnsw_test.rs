@@ -130,6 +130,8 @@ fn test_byte_storage_hnsw(
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -146,6 +148,8 @@ fn test_byte_storage_hnsw(
                 quantization_config: None,
                 multivec_config: None,
                 datatype: Some(storage_data_type),
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -253,6 +253,8 @@ fn test_byte_storage_binary_quantization_hnsw(
                 quantization_config: None,
                 multivec_config: None,
                 datatype: Some(storage_data_type),
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -52,6 +52,8 @@ fn exact_search_test() {
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -131,7 +133,9 @@ fn exact_search_test() {
             coverage.insert(point, coverage.get(&point).unwrap_or(&0) + 1);
         }
     }
-    let expected_blocks = num_vectors as usize / indexing_threshold * 2;
+    // Blocks of the smallest level and of a four times coarser level
+    let expected_blocks = num_vectors as usize / indexing_threshold * 2
+        + num_vectors as usize / (indexing_threshold * 4) * 2;
 
     eprintln!("blocks.len() = {:#?}", blocks.len());
     assert!(
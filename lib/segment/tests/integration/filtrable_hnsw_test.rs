@@ -114,6 +114,8 @@ fn _test_filterable_hnsw(
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -190,7 +192,9 @@ fn _test_filterable_hnsw(
             coverage.insert(point, coverage.get(&point).unwrap_or(&0) + 1);
         }
     }
-    let expected_blocks = num_vectors as usize / indexing_threshold * 2;
+    // Blocks of the smallest level and of a four times coarser level
+    let expected_blocks = num_vectors as usize / indexing_threshold * 2
+        + num_vectors as usize / (indexing_threshold * 4) * 2;
 
     eprintln!("blocks.len() = {:#?}", blocks.len());
     assert!(
@@ -242,13 +246,13 @@ fn _test_filterable_hnsw(
             .unwrap();
 
         // check that search was performed using HNSW index
-        assert_eq!(
-            hnsw_index
-                .get_telemetry_data(TelemetryDetail::default())
-                .filtered_large_cardinality
-                .count,
-            i + 1
-        );
+        let telemetry = hnsw_index.get_telemetry_data(TelemetryDetail::default());
+        assert_eq!(telemetry.filtered_large_cardinality.count, i + 1);
+        assert_eq!(telemetry.filtered_range_hnsw.count, i + 1);
+        // Range blocks of the indexed field are linked in the graph, so two-hop traversal is
+        // not needed, even though the filter is selective enough for it
+        assert!(telemetry.filtered_one_hop.count > i);
+        assert_eq!(telemetry.filtered_two_hop.count, 0);
 
         let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
             .vector_index
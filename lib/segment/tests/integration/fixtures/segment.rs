@@ -136,6 +136,8 @@ pub fn build_segment_3(path: &Path) -> Segment {
                         quantization_config: None,
                         multivec_config: None,
                         datatype: None,
+                        validation: None,
+                        normalize: None,
                     },
                 ),
                 (
@@ -148,6 +150,8 @@ pub fn build_segment_3(path: &Path) -> Segment {
                         quantization_config: None,
                         multivec_config: None,
                         datatype: None,
+                        validation: None,
+                        normalize: None,
                     },
                 ),
                 (
@@ -160,6 +164,8 @@ pub fn build_segment_3(path: &Path) -> Segment {
                         quantization_config: None,
                         multivec_config: None,
                         datatype: None,
+                        validation: None,
+                        normalize: None,
                     },
                 ),
             ]),
@@ -254,7 +260,8 @@ pub fn build_segment_sparse_1(path: &Path) -> Segment {
             sparse_vector_data: HashMap::from([(
                 "sparse".to_owned(),
                 SparseVectorDataConfig {
-                    index: SparseIndexConfig::new(None, SparseIndexType::MutableRam),
+                    index: SparseIndexConfig::new(None, SparseIndexType::MutableRam, None),
+                    storage_type: Default::default(),
                 },
             )]),
             payload_storage_type: Default::default(),
@@ -339,7 +346,8 @@ pub fn build_segment_sparse_2(path: &Path) -> Segment {
             sparse_vector_data: HashMap::from([(
                 "sparse".to_owned(),
                 SparseVectorDataConfig {
-                    index: SparseIndexConfig::new(None, SparseIndexType::MutableRam),
+                    index: SparseIndexConfig::new(None, SparseIndexType::MutableRam, None),
+                    storage_type: Default::default(),
                 },
             )]),
             payload_storage_type: Default::default(),
@@ -77,6 +77,8 @@ fn hnsw_discover_precision() {
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -189,6 +191,8 @@ fn filtered_hnsw_discover_precision() {
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         payload_storage_type: Default::default(),
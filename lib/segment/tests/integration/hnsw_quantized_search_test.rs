@@ -73,6 +73,8 @@ fn hnsw_quantized_search_test(
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
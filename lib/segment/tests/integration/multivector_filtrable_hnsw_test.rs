@@ -129,6 +129,8 @@ fn test_multi_filterable_hnsw(
                 quantization_config: None,
                 multivec_config: Some(MultiVectorConfig::default()), // uses multivec config
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
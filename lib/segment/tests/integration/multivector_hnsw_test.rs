@@ -1,19 +1,19 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use common::cpu::CpuPermit;
 use rand::prelude::StdRng;
-use rand::SeedableRng;
+use rand::{Rng, SeedableRng};
 use segment::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
 use segment::data_types::vectors::{
-    only_default_vector, MultiDenseVector, QueryVector, TypedMultiDenseVectorRef,
-    VectorElementType, VectorRef, DEFAULT_VECTOR_NAME,
+    only_default_multi_vector, only_default_vector, MultiDenseVector, QueryVector,
+    TypedMultiDenseVectorRef, VectorElementType, VectorRef, DEFAULT_VECTOR_NAME,
 };
 use segment::entry::entry_point::SegmentEntry;
 use segment::fixtures::index_fixtures::random_vector;
-use segment::fixtures::payload_fixtures::random_int_payload;
+use segment::fixtures::payload_fixtures::{random_int_payload, random_multi_vector};
 use segment::index::hnsw_index::graph_links::GraphLinksRam;
 use segment::index::hnsw_index::hnsw::HNSWIndex;
 use segment::index::VectorIndex;
@@ -22,8 +22,9 @@ use segment::segment_constructor::build_segment;
 use segment::spaces::metric::Metric;
 use segment::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use segment::types::{
-    Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, MultiVectorConfig, Payload,
-    PayloadSchemaType, SegmentConfig, SeqNumberType, VectorDataConfig, VectorStorageType,
+    Condition, Distance, FieldCondition, Filter, HasIdCondition, HnswConfig, Indexes,
+    MultiVectorConfig, MultiVectorRepresentative, Payload, PayloadSchemaType, SearchParams,
+    SegmentConfig, SeqNumberType, VectorDataConfig, VectorStorageType,
 };
 use segment::vector_storage::multi_dense::simple_multi_dense_vector_storage::open_simple_multi_dense_vector_storage;
 use segment::vector_storage::VectorStorage;
@@ -52,6 +53,8 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -199,3 +202,180 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         assert_eq!(search_res_dense, search_res_multi);
     }
 }
+
+#[test]
+fn test_multivector_hnsw_with_representatives() {
+    let num_vectors: u64 = 2_000;
+    let dim = 8;
+    let top = 10;
+    let ef = 64;
+    let attempts = 50;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivec_config: Some(MultiVectorConfig {
+                    representative: Some(MultiVectorRepresentative::Mean),
+                    ..Default::default()
+                }),
+                datatype: None,
+                validation: None,
+                normalize: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+    for n in 0..num_vectors {
+        let num_tokens = rnd.gen_range(1..=4);
+        let vector = random_multi_vector(&mut rnd, dim, num_tokens);
+        segment
+            .upsert_point(
+                n as SeqNumberType,
+                n.into(),
+                only_default_multi_vector(&vector),
+            )
+            .unwrap();
+    }
+
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+
+    let hnsw_config = HnswConfig {
+        m: 16,
+        ef_construct: 100,
+        full_scan_threshold: 1, // KB, to always search the graph
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+    };
+
+    let permit = Arc::new(CpuPermit::dummy(2));
+
+    let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
+    let quantized_vectors = &segment.vector_data[DEFAULT_VECTOR_NAME].quantized_vectors;
+    let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
+        hnsw_dir.path(),
+        segment.id_tracker.clone(),
+        vector_storage.clone(),
+        quantized_vectors.clone(),
+        segment.payload_index.clone(),
+        hnsw_config,
+    )
+    .unwrap();
+    hnsw_index.build_index(permit, &stopped).unwrap();
+
+    let plain_index = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow();
+
+    let search_params = SearchParams {
+        hnsw_ef: Some(ef),
+        ..Default::default()
+    };
+
+    let mut hits = 0;
+    for _ in 0..attempts {
+        let num_tokens = rnd.gen_range(1..=4);
+        let query: QueryVector = random_multi_vector(&mut rnd, dim, num_tokens).into();
+
+        let index_result = hnsw_index
+            .search(
+                &[&query],
+                None,
+                top,
+                Some(&search_params),
+                &Default::default(),
+            )
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(index_result.len(), top);
+
+        let plain_result = plain_index
+            .search(&[&query], None, top, None, &Default::default())
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        // Found points must be scored with the full multivectors, not with their representatives
+        let found_ids: HashSet<_> = index_result
+            .iter()
+            .map(|scored| segment.id_tracker.borrow().external_id(scored.idx).unwrap())
+            .collect();
+        let found_filter = Filter::new_must(Condition::HasId(HasIdCondition::from(found_ids)));
+        let exact_result = plain_index
+            .search(
+                &[&query],
+                Some(&found_filter),
+                top,
+                None,
+                &Default::default(),
+            )
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(index_result, exact_result);
+
+        hits += index_result
+            .iter()
+            .filter(|scored| plain_result.iter().any(|plain| plain.idx == scored.idx))
+            .count();
+    }
+
+    let recall = hits as f32 / (attempts * top) as f32;
+    assert!(recall >= 0.8, "recall is too low: {recall}");
+
+    drop(plain_index);
+
+    // Vectors deleted after the index was built are only deleted in the vector storage,
+    // they must not be found through the representatives
+    let query: QueryVector = random_multi_vector(&mut rnd, dim, 2).into();
+    let search = |hnsw_index: &HNSWIndex<GraphLinksRam>| {
+        hnsw_index
+            .search(
+                &[&query],
+                None,
+                top,
+                Some(&search_params),
+                &Default::default(),
+            )
+            .unwrap()
+            .pop()
+            .unwrap()
+    };
+
+    let deleted_ids: HashSet<_> = search(&hnsw_index)
+        .iter()
+        .map(|scored| segment.id_tracker.borrow().external_id(scored.idx).unwrap())
+        .collect();
+    for &point_id in &deleted_ids {
+        segment
+            .delete_vector(num_vectors, point_id, DEFAULT_VECTOR_NAME)
+            .unwrap();
+    }
+
+    let index_result = search(&hnsw_index);
+    assert_eq!(index_result.len(), top);
+    for scored in &index_result {
+        let point_id = segment.id_tracker.borrow().external_id(scored.idx).unwrap();
+        assert!(
+            !deleted_ids.contains(&point_id),
+            "deleted vector {point_id} is found"
+        );
+    }
+}
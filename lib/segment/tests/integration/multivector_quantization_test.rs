@@ -229,6 +229,8 @@ fn test_multivector_quantization_hnsw(
                 quantization_config: None,
                 multivec_config: Some(MultiVectorConfig::default()), // uses multivec config
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -55,6 +55,8 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -110,6 +112,8 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
                     r#type: IntegerIndexType::Integer,
                     lookup: true,
                     range: false,
+                    is_tenant: None,
+                    on_disk: None,
                 },
             ))),
         )
@@ -123,6 +127,8 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
                     r#type: IntegerIndexType::Integer,
                     lookup: false,
                     range: true,
+                    is_tenant: None,
+                    on_disk: None,
                 },
             ))),
         )
@@ -192,6 +198,8 @@ fn build_test_segments_nested_payload(path_struct: &Path, path_plain: &Path) ->
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -919,3 +927,61 @@ fn test_any_matcher_cardinality_estimation() {
     assert!(exact <= estimation.max);
     assert!(exact >= estimation.min);
 }
+
+#[test]
+fn test_uuid_index_except_with_non_uuid_values() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let mut payload_storage = InMemoryPayloadStorage::default();
+
+    let uuid_a = "f9a5a3e4-3c3f-4e61-9b4a-8a2f0c7e7d01";
+    let uuid_b = "0b8d7c4e-2d1a-4f0e-8c3b-5e6f7a8b9c02";
+
+    let payloads: Vec<Payload> = vec![
+        json!({ "field": uuid_a }).into(),
+        json!({ "field": uuid_b }).into(),
+        json!({ "field": "foo" }).into(),
+        json!({ "field": [uuid_a, "foo"] }).into(),
+        json!({ "other": uuid_b }).into(),
+    ];
+    let point_num = payloads.len();
+
+    for (idx, payload) in payloads.iter().enumerate() {
+        payload_storage
+            .assign(idx as PointOffsetType, payload)
+            .unwrap();
+    }
+
+    let wrapped_payload_storage = Arc::new(AtomicRefCell::new(payload_storage.into()));
+    let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(point_num)));
+
+    let mut index =
+        StructPayloadIndex::open(wrapped_payload_storage, id_tracker, dir.path(), true).unwrap();
+
+    let field = path("field");
+    index
+        .set_indexed(&field, PayloadSchemaType::Uuid.into())
+        .unwrap();
+
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        field,
+        Match::new_except(AnyVariants::Keywords(
+            [uuid_a.to_string()].into_iter().collect(),
+        )),
+    )));
+
+    // Points with values which are not UUIDs still match, they are not excluded
+    let expected = vec![1, 2, 3];
+
+    let queried = index
+        .query_points(&filter)
+        .into_iter()
+        .sorted()
+        .collect_vec();
+    assert_eq!(queried, expected);
+
+    let filter_context = index.filter_context(&filter);
+    let checked = (0..point_num as PointOffsetType)
+        .filter(|point_id| filter_context.check(*point_id))
+        .collect_vec();
+    assert_eq!(checked, expected);
+}
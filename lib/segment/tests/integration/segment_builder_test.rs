@@ -1,18 +1,36 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use common::cpu::CpuPermit;
+use common::types::PointOffsetType;
 use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use segment::common::operation_error::OperationError;
+use segment::data_types::keyword_index::{KeywordIndexParams, KeywordIndexType};
 use segment::data_types::named_vectors::NamedVectors;
-use segment::data_types::vectors::{only_default_vector, VectorRef, DEFAULT_VECTOR_NAME};
+use segment::data_types::vectors::{
+    only_default_vector, QueryVector, VectorRef, DEFAULT_VECTOR_NAME,
+};
 use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::id_tracker::IdTrackerSS;
+use segment::index::field_index::map_index::MapIndex;
+use segment::index::field_index::FieldIndex;
+use segment::index::hnsw_index::graph_layers::{GraphLayers, GraphLayersBase};
+use segment::index::hnsw_index::graph_links::GraphLinks;
 use segment::index::hnsw_index::num_rayon_threads;
+use segment::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
+use segment::json_path::JsonPath;
 use segment::segment::Segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
-use segment::types::{Indexes, SegmentConfig, VectorDataConfig, VectorStorageType};
+use segment::types::{
+    Condition, FieldCondition, Filter, HnswConfig, Indexes, PayloadContainer, PayloadFieldSchema,
+    PayloadSchemaParams, PointIdType, SearchParams, SegmentConfig, VectorDataConfig,
+    VectorStorageType,
+};
 use sparse::common::sparse_vector::SparseVector;
 use tempfile::Builder;
 
@@ -79,6 +97,97 @@ fn test_building_new_segment() {
     assert_eq!(merged_segment.point_version(3.into()), Some(100));
 }
 
+#[test]
+fn test_building_new_segment_with_tenant_field() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+
+    let mut segment1 = build_segment_1(dir.path());
+    let segment2 = build_segment_2(dir.path());
+
+    let tenant_field: JsonPath = "color".parse().unwrap();
+    let tenant_schema =
+        PayloadFieldSchema::FieldParams(PayloadSchemaParams::Keyword(KeywordIndexParams {
+            r#type: KeywordIndexType::Keyword,
+            is_tenant: Some(true),
+            on_disk: None,
+        }));
+    segment1
+        .create_field_index(7, &tenant_field, Some(&tenant_schema))
+        .unwrap();
+
+    // Build a non-appendable segment, so that the tenant index is stored on disk
+    let mut config = segment1.segment_config.clone();
+    for vector_config in config.vector_data.values_mut() {
+        vector_config.storage_type = VectorStorageType::Mmap;
+    }
+
+    let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &config).unwrap();
+    builder.update(&[&segment1, &segment2], &stopped).unwrap();
+
+    let permit = CpuPermit::dummy(num_rayon_threads(0) as u32);
+    let merged_segment: Segment = builder.build(permit, &stopped).unwrap();
+
+    assert_eq!(
+        merged_segment.available_point_count(),
+        segment1
+            .iter_points()
+            .chain(segment2.iter_points())
+            .unique()
+            .count(),
+    );
+
+    // Id mappings of non-appendable segments are stored in mmap files
+    assert!(!merged_segment.id_tracker.borrow().files().is_empty());
+
+    // Points of the same tenant are stored next to each other
+    let tenants: Vec<_> = {
+        let id_tracker = merged_segment.id_tracker.borrow();
+        let payload_index = merged_segment.payload_index.borrow();
+        id_tracker
+            .iter_ids()
+            .map(|internal_id| {
+                let payload = payload_index.payload(internal_id).unwrap();
+                payload
+                    .get_value(&tenant_field)
+                    .first()
+                    .and_then(|value| value.as_str())
+                    .map(ToOwned::to_owned)
+            })
+            .collect()
+    };
+    assert!(tenants.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    let payload_index = merged_segment.payload_index.borrow();
+    assert!(matches!(
+        payload_index.field_indexes[&tenant_field].as_slice(),
+        [FieldIndex::KeywordIndex(MapIndex::Mmap(_))]
+    ));
+    drop(payload_index);
+
+    // Tenant index gives the same results as the payload
+    for color in ["red", "blue"] {
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            tenant_field.clone(),
+            color.to_string().into(),
+        )));
+        let expected = segment1
+            .read_filtered(None, None, Some(&filter))
+            .into_iter()
+            .chain(segment2.read_filtered(None, None, Some(&filter)))
+            .unique()
+            .count();
+        assert_eq!(
+            merged_segment
+                .read_filtered(None, None, Some(&filter))
+                .len(),
+            expected,
+        );
+    }
+}
+
 #[test]
 fn test_building_new_sparse_segment() {
     let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -160,6 +269,8 @@ fn estimate_build_time(segment: &Segment, stop_delay_millis: u64) -> (u64, bool)
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -264,3 +375,138 @@ fn test_building_cancellation() {
         was_cancelled_later,
     );
 }
+
+/// Level and links on level 0 of each point in the HNSW graph of the segment, by external ids
+fn hnsw_graph_links(segment: &Segment) -> HashMap<PointIdType, (usize, HashSet<PointIdType>)> {
+    let vector_index = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow();
+    let id_tracker = segment.id_tracker.borrow();
+    match &*vector_index {
+        VectorIndexEnum::HnswRam(index) => graph_links(index.graph().unwrap(), &*id_tracker),
+        VectorIndexEnum::HnswMmap(index) => graph_links(index.graph().unwrap(), &*id_tracker),
+        _ => panic!("segment is not indexed with HNSW"),
+    }
+}
+
+fn graph_links<TGraphLinks: GraphLinks>(
+    graph: &GraphLayers<TGraphLinks>,
+    id_tracker: &IdTrackerSS,
+) -> HashMap<PointIdType, (usize, HashSet<PointIdType>)> {
+    (0..graph.num_points() as PointOffsetType)
+        .filter_map(|internal_id| {
+            let external_id = id_tracker.external_id(internal_id)?;
+            let mut links = HashSet::new();
+            graph.links_map(internal_id, 0, |link| {
+                // Links to deleted points are skipped
+                links.extend(id_tracker.external_id(link));
+            });
+            Some((external_id, (graph.point_level(internal_id), links)))
+        })
+        .collect()
+}
+
+#[test]
+fn test_building_hnsw_segment_from_indexed_segment() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+    let mut rnd = StdRng::seed_from_u64(42);
+    let dim = 4;
+
+    let mut segment1 = empty_segment(dir.path());
+    let mut segment2 = empty_segment(dir.path());
+    for idx in 0..1000u64 {
+        let vector = random_vector(&mut rnd, dim);
+        segment1
+            .upsert_point(1, idx.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+    for idx in 1000..1100u64 {
+        let vector = random_vector(&mut rnd, dim);
+        segment2
+            .upsert_point(1, idx.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let mut config = segment1.segment_config.clone();
+    for vector_config in config.vector_data.values_mut() {
+        vector_config.index = Indexes::Hnsw(HnswConfig {
+            m: 16,
+            ef_construct: 100,
+            full_scan_threshold: 1, // KB
+            max_indexing_threads: 0,
+            on_disk: None,
+            payload_m: None,
+        });
+    }
+
+    let build = |segments: &[&Segment]| {
+        let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &config).unwrap();
+        builder.update(segments, &stopped).unwrap();
+        let permit = CpuPermit::dummy(num_rayon_threads(0) as u32);
+        builder.build(permit, &stopped).unwrap()
+    };
+
+    let mut indexed_segment = build(&[&segment1]);
+
+    // Deleted points are dropped from the graph, which is reused for the new segment
+    for idx in 0..100u64 {
+        indexed_segment.delete_point(2, idx.into()).unwrap();
+    }
+
+    let merged_segment = build(&[&indexed_segment, &segment2]);
+    assert_eq!(merged_segment.available_point_count(), 1000);
+
+    let vector_index = merged_segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow();
+    assert_eq!(vector_index.indexed_vector_count(), 1000);
+
+    // Points of the indexed segment keep their levels and most of their links,
+    // only links to the inserted points may replace some of them
+    let old_links = hnsw_graph_links(&indexed_segment);
+    let new_links = hnsw_graph_links(&merged_segment);
+    assert_eq!(old_links.len(), 900);
+    let mut kept_links = 0;
+    let mut total_links = 0;
+    for (point_id, (old_level, old_point_links)) in &old_links {
+        let (new_level, new_point_links) = &new_links[point_id];
+        assert_eq!(old_level, new_level, "level of point {point_id} changed");
+        kept_links += old_point_links.intersection(new_point_links).count();
+        total_links += old_point_links.len();
+    }
+    assert!(
+        kept_links >= total_links * 9 / 10,
+        "only {kept_links} of {total_links} links are reused",
+    );
+
+    let top = 10;
+    let attempts = 50;
+    let mut sames = 0;
+    for _ in 0..attempts {
+        let query: QueryVector = random_vector(&mut rnd, dim).into();
+        let search = |exact| {
+            let params = SearchParams {
+                hnsw_ef: Some(64),
+                exact,
+                ..Default::default()
+            };
+            vector_index
+                .search(&[&query], None, top, Some(&params), &Default::default())
+                .unwrap()
+                .remove(0)
+                .into_iter()
+                .map(|scored_point| scored_point.idx)
+                .collect_vec()
+        };
+        if search(false) == search(true) {
+            sames += 1;
+        }
+    }
+    assert!(
+        sames >= attempts * 8 / 10,
+        "only {sames} of {attempts} searches match the exact search",
+    );
+}
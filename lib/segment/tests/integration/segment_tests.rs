@@ -10,12 +10,17 @@ use segment::data_types::vectors::{
 };
 use segment::entry::entry_point::SegmentEntry;
 use segment::fixtures::index_fixtures::random_vector;
+use segment::json_path::path;
+use segment::payload_storage::mmap_payload_storage::MMAP_PAYLOAD_STORAGE_PATH;
 use segment::segment_constructor::load_segment;
 use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
-use segment::types::{Condition, Distance, Filter, SearchParams, WithPayload};
+use segment::types::{
+    Condition, Distance, FieldCondition, Filter, PayloadStorageType, SearchParams,
+    SparseVectorStorageType, WithPayload,
+};
 use tempfile::Builder;
 
-use crate::fixtures::segment::{build_segment_1, build_segment_3};
+use crate::fixtures::segment::{build_segment_1, build_segment_3, build_segment_sparse_1};
 
 #[test]
 fn test_point_exclusion() {
@@ -213,6 +218,79 @@ fn ordered_deletion_test() {
     assert_eq!(best_match.id, 3.into());
 }
 
+/// Segments with RocksDB payload storage are loaded as is, they are migrated by the optimizer
+#[test]
+fn rocksdb_payload_storage_load_test() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let (segment_path, payloads) = {
+        let mut segment = build_segment_1(dir.path());
+        assert_eq!(
+            segment.segment_config.payload_storage_type,
+            PayloadStorageType::InMemory,
+        );
+        segment.delete_point(6, 5.into()).unwrap();
+        segment.flush(true).unwrap();
+        let payloads: Vec<_> = (1..=4)
+            .map(|id| segment.payload(id.into()).unwrap())
+            .collect();
+        (segment.current_path.clone(), payloads)
+    };
+
+    let segment = load_segment(&segment_path, &AtomicBool::new(false))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        segment.segment_config.payload_storage_type,
+        PayloadStorageType::InMemory,
+    );
+    assert!(!segment_path.join(MMAP_PAYLOAD_STORAGE_PATH).exists());
+    for (id, payload) in (1..=4).zip(payloads) {
+        assert_eq!(segment.payload(id.into()).unwrap(), payload);
+    }
+    assert!(!segment.has_point(5.into()));
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        path("color"),
+        "blue".to_owned().into(),
+    )));
+    assert_eq!(
+        segment.read_filtered(None, None, Some(&filter)),
+        vec![3.into(), 4.into()],
+    );
+}
+
+/// Segments with sparse vectors in RocksDB are loaded as is, they are migrated by the optimizer
+#[test]
+fn rocksdb_sparse_vector_storage_load_test() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let (segment_path, vectors) = {
+        let mut segment = build_segment_sparse_1(dir.path());
+        assert_eq!(
+            segment.segment_config.sparse_vector_data["sparse"].storage_type,
+            SparseVectorStorageType::OnDisk,
+        );
+        segment.delete_point(7, 5.into()).unwrap();
+        segment.flush(true).unwrap();
+        let vectors: Vec<_> = (1..=4)
+            .map(|id| segment.vector("sparse", id.into()).unwrap())
+            .collect();
+        (segment.current_path.clone(), vectors)
+    };
+
+    let segment = load_segment(&segment_path, &AtomicBool::new(false))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        segment.segment_config.sparse_vector_data["sparse"].storage_type,
+        SparseVectorStorageType::OnDisk,
+    );
+    for (id, vector) in (1..=4).zip(vectors) {
+        assert_eq!(segment.vector("sparse", id.into()).unwrap(), vector);
+    }
+    assert!(!segment.has_point(5.into()));
+}
+
 #[test]
 fn skip_deleted_segment() {
     let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -261,6 +339,8 @@ fn test_update_named_vector() {
         exact: true,
         quantization: None,
         indexed_only: false,
+        max_tokens: None,
+        acorn: None,
     };
     let nearest_upsert = segment
         .search(
@@ -123,7 +123,9 @@ fn sparse_index_discover_test() {
                 index: SparseIndexConfig {
                     full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
                     index_type: SparseIndexType::MutableRam,
+                    datatype: None,
                 },
+                storage_type: Default::default(),
             },
         )]),
         payload_storage_type: Default::default(),
@@ -139,6 +141,8 @@ fn sparse_index_discover_test() {
                 quantization_config: None,
                 multivec_config: None,
                 datatype: None,
+                validation: None,
+                normalize: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -170,6 +174,7 @@ fn sparse_index_discover_test() {
         SparseIndexConfig {
             full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
             index_type: SparseIndexType::ImmutableRam,
+            datatype: None,
         },
         sparse_segment.id_tracker.clone(),
         vector_storage.clone(),
@@ -34,6 +34,8 @@ use serde_json::json;
 use sparse::common::sparse_vector::SparseVector;
 use sparse::common::sparse_vector_fixture::{random_full_sparse_vector, random_sparse_vector};
 use sparse::common::types::DimId;
+use sparse::index::inverted_index::inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam;
+use sparse::index::inverted_index::inverted_index_compressed_mmap::InvertedIndexCompressedMmap;
 use sparse::index::inverted_index::inverted_index_immutable_ram::InvertedIndexImmutableRam;
 use sparse::index::inverted_index::inverted_index_mmap::InvertedIndexMmap;
 use sparse::index::inverted_index::InvertedIndex;
@@ -621,7 +623,9 @@ fn sparse_vector_index_persistence_test() {
                 index: SparseIndexConfig {
                     full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
                     index_type: SparseIndexType::MutableRam,
+                    datatype: None,
                 },
+                storage_type: Default::default(),
             },
         )]),
         payload_storage_type: Default::default(),
@@ -688,6 +692,20 @@ fn sparse_vector_index_persistence_test() {
         top,
     );
     check_persistence::<InvertedIndexMmap>(&segment, &permit, &search_result, &query_vector, top);
+    check_persistence::<InvertedIndexCompressedImmutableRam<f32>>(
+        &segment,
+        &permit,
+        &search_result,
+        &query_vector,
+        top,
+    );
+    check_persistence::<InvertedIndexCompressedMmap<f32>>(
+        &segment,
+        &permit,
+        &search_result,
+        &query_vector,
+        top,
+    );
 }
 
 fn check_persistence<TInvertedIndex: InvertedIndex>(
@@ -709,6 +727,7 @@ fn check_persistence<TInvertedIndex: InvertedIndex>(
             SparseIndexConfig {
                 full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
                 index_type: SparseIndexType::Mmap,
+                datatype: None,
             },
             segment.id_tracker.clone(),
             segment.vector_data[SPARSE_VECTOR_NAME]
@@ -823,7 +842,9 @@ fn sparse_vector_test_large_index() {
                 index: SparseIndexConfig {
                     full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
                     index_type: SparseIndexType::MutableRam,
+                    datatype: None,
                 },
+                storage_type: Default::default(),
             },
         )]),
         payload_storage_type: Default::default(),
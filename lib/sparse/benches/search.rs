@@ -14,8 +14,9 @@ use rand::SeedableRng as _;
 use sparse::common::scores_memory_pool::ScoresMemoryPool;
 use sparse::common::sparse_vector::{RemappedSparseVector, SparseVector};
 use sparse::common::sparse_vector_fixture::{random_positive_sparse_vector, random_sparse_vector};
-use sparse::index::inverted_index::inverted_index_compressed_immutable_ram::InvertedIndexImmutableRam;
-use sparse::index::inverted_index::inverted_index_compressed_mmap::InvertedIndexMmap as InvertedIndexCompressedMmap;
+use sparse::common::types::QuantizedU8;
+use sparse::index::inverted_index::inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam;
+use sparse::index::inverted_index::inverted_index_compressed_mmap::InvertedIndexCompressedMmap;
 use sparse::index::inverted_index::inverted_index_mmap::InvertedIndexMmap;
 use sparse::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 use sparse::index::inverted_index::inverted_index_ram_builder::InvertedIndexBuilder;
@@ -137,7 +138,7 @@ pub fn run_bench(
 
     run_bench2(
         c.benchmark_group(format!("search/ram_c32/{name}")),
-        &InvertedIndexImmutableRam::<f32>::from_ram_index(
+        &InvertedIndexCompressedImmutableRam::<f32>::from_ram_index(
             Cow::Borrowed(&index),
             "nonexistent/path",
         )
@@ -148,7 +149,18 @@ pub fn run_bench(
 
     run_bench2(
         c.benchmark_group(format!("search/ram_c16/{name}")),
-        &InvertedIndexImmutableRam::<half::f16>::from_ram_index(
+        &InvertedIndexCompressedImmutableRam::<half::f16>::from_ram_index(
+            Cow::Borrowed(&index),
+            "nonexistent/path",
+        )
+        .unwrap(),
+        query_vectors,
+        &hottest_query_vectors,
+    );
+
+    run_bench2(
+        c.benchmark_group(format!("search/ram_u8/{name}")),
+        &InvertedIndexCompressedImmutableRam::<QuantizedU8>::from_ram_index(
             Cow::Borrowed(&index),
             "nonexistent/path",
         )
@@ -201,6 +213,21 @@ pub fn run_bench(
         query_vectors,
         &hottest_query_vectors,
     );
+
+    run_bench2(
+        c.benchmark_group(format!("search/mmap_u8/{name}")),
+        &InvertedIndexCompressedMmap::<QuantizedU8>::from_ram_index(
+            Cow::Borrowed(&index),
+            tempfile::Builder::new()
+                .prefix("test_index_dir")
+                .tempdir()
+                .unwrap()
+                .path(),
+        )
+        .unwrap(),
+        query_vectors,
+        &hottest_query_vectors,
+    );
 }
 
 fn run_bench2(
@@ -6,34 +6,148 @@ pub type DimOffset = u32;
 pub type DimId = u32;
 pub type DimWeight = f32;
 
-pub trait Weight: PartialEq + Copy + Debug + Into<DimWeight> + 'static {
-    fn from_f32(value: f32) -> Self;
+pub trait Weight: PartialEq + Copy + Debug + 'static {
+    /// Parameters used to convert weights of a single posting list from and into `f32`
+    type QuantizationParams: Copy + PartialEq + Debug + Default;
 
-    fn into_f32_slice<'a>(weights: &'a [Self], buffer: &'a mut [f32]) -> &'a [f32];
+    /// Compute quantization parameters for the given weights of a posting list
+    fn quantization_params_for(
+        values: impl Iterator<Item = DimWeight>,
+    ) -> Self::QuantizationParams;
+
+    fn from_f32(params: Self::QuantizationParams, value: f32) -> Self;
+
+    fn to_f32(self, params: Self::QuantizationParams) -> f32;
+
+    fn into_f32_slice<'a>(
+        params: Self::QuantizationParams,
+        weights: &'a [Self],
+        buffer: &'a mut [f32],
+    ) -> &'a [f32];
 }
 
 impl Weight for f32 {
+    type QuantizationParams = ();
+
+    #[inline]
+    fn quantization_params_for(_values: impl Iterator<Item = DimWeight>) {}
+
     #[inline]
-    fn from_f32(value: f32) -> Self {
+    fn from_f32(_params: (), value: f32) -> Self {
         value
     }
 
     #[inline]
-    fn into_f32_slice<'a>(weights: &'a [Self], _buffer: &'a mut [f32]) -> &'a [f32] {
+    fn to_f32(self, _params: ()) -> f32 {
+        self
+    }
+
+    #[inline]
+    fn into_f32_slice<'a>(_params: (), weights: &'a [Self], _buffer: &'a mut [f32]) -> &'a [f32] {
         // Zero-copy conversion, ignore buffer
         weights
     }
 }
 
 impl Weight for half::f16 {
+    type QuantizationParams = ();
+
     #[inline]
-    fn from_f32(value: f32) -> Self {
+    fn quantization_params_for(_values: impl Iterator<Item = DimWeight>) {}
+
+    #[inline]
+    fn from_f32(_params: (), value: f32) -> Self {
         half::f16::from_f32(value)
     }
 
     #[inline]
-    fn into_f32_slice<'a>(weights: &'a [Self], buffer: &'a mut [f32]) -> &'a [f32] {
+    fn to_f32(self, _params: ()) -> f32 {
+        half::f16::to_f32(self)
+    }
+
+    #[inline]
+    fn into_f32_slice<'a>(_params: (), weights: &'a [Self], buffer: &'a mut [f32]) -> &'a [f32] {
         weights.convert_to_f32_slice(buffer);
         buffer
     }
 }
+
+/// Weight quantized into 256 levels between the min and max weight of its posting list
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(transparent)]
+pub struct QuantizedU8(u8);
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[repr(C)]
+pub struct QuantizedU8Params {
+    /// Weight of the level 0
+    min: f32,
+    /// Difference between weights of two adjacent levels
+    step: f32,
+}
+
+impl Weight for QuantizedU8 {
+    type QuantizationParams = QuantizedU8Params;
+
+    fn quantization_params_for(values: impl Iterator<Item = DimWeight>) -> QuantizedU8Params {
+        let (min, max) = values.fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+        if min > max {
+            // No values
+            return QuantizedU8Params::default();
+        }
+        QuantizedU8Params {
+            min,
+            step: (max - min) / u8::MAX as f32,
+        }
+    }
+
+    #[inline]
+    fn from_f32(params: QuantizedU8Params, value: f32) -> Self {
+        if params.step == 0.0 {
+            // All weights of the posting list are equal
+            return QuantizedU8(0);
+        }
+        let level = ((value - params.min) / params.step).round();
+        QuantizedU8(level.clamp(0.0, u8::MAX as f32) as u8)
+    }
+
+    #[inline]
+    fn to_f32(self, params: QuantizedU8Params) -> f32 {
+        params.min + self.0 as f32 * params.step
+    }
+
+    #[inline]
+    fn into_f32_slice<'a>(
+        params: QuantizedU8Params,
+        weights: &'a [Self],
+        buffer: &'a mut [f32],
+    ) -> &'a [f32] {
+        for (weight, value) in weights.iter().zip(buffer.iter_mut()) {
+            *value = weight.to_f32(params);
+        }
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantized_u8_round_trip() {
+        let values = [-1.5, 0.0, 0.3, 2.0, 7.25];
+        let params = QuantizedU8::quantization_params_for(values.iter().copied());
+        let max_error = params.step / 2.0 + f32::EPSILON;
+        for value in values {
+            let restored = QuantizedU8::from_f32(params, value).to_f32(params);
+            assert!((restored - value).abs() <= max_error, "{value} != {restored}");
+        }
+        assert_eq!(QuantizedU8::from_f32(params, -1.5).to_f32(params), -1.5);
+
+        // Posting list with equal weights
+        let params = QuantizedU8::quantization_params_for([3.0, 3.0].into_iter());
+        assert_eq!(QuantizedU8::from_f32(params, 3.0).to_f32(params), 3.0);
+    }
+}
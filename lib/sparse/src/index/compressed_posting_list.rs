@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 use std::fmt::Debug;
+use std::marker::PhantomData;
 use std::mem::size_of;
 
 use bitpacking::BitPacker as _;
@@ -14,7 +15,7 @@ use crate::common::types::{DimWeight, Weight};
 type BitPackerImpl = bitpacking::BitPacker4x;
 
 #[derive(Default, Debug, Clone, PartialEq)]
-pub struct CompressedPostingList<W> {
+pub struct CompressedPostingList<W: Weight> {
     /// Compressed ids data. Chunks refer to subslies of this data.
     id_data: Vec<u8>,
 
@@ -26,15 +27,19 @@ pub struct CompressedPostingList<W> {
 
     /// Id of the last element in the list. Used to avoid unpacking the last chunk.
     last_id: Option<PointOffsetType>,
+
+    /// Parameters to convert weights of this list into `f32`.
+    quantization_params: W::QuantizationParams,
 }
 
 /// A non-owning view of [`GenericCompressedPostingList`].
 #[derive(Default, Debug, Clone, PartialEq)]
-pub struct CompressedPostingListView<'a, W> {
+pub struct CompressedPostingListView<'a, W: Weight> {
     id_data: &'a [u8],
     chunks: &'a [CompressedPostingChunk<W>],
     remainders: &'a [GenericPostingElement<W>],
     last_id: Option<PointOffsetType>,
+    quantization_params: W::QuantizationParams,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -57,6 +62,7 @@ impl<W: Weight> CompressedPostingList<W> {
             chunks: &self.chunks,
             remainders: &self.remainders,
             last_id: self.last_id,
+            quantization_params: self.quantization_params,
         }
     }
 
@@ -65,7 +71,7 @@ impl<W: Weight> CompressedPostingList<W> {
     }
 
     #[cfg(test)]
-    pub fn from(records: Vec<(PointOffsetType, W)>) -> CompressedPostingList<W> {
+    pub fn from(records: Vec<(PointOffsetType, DimWeight)>) -> CompressedPostingList<W> {
         let mut posting_list = CompressedPostingBuilder::new();
         for (id, weight) in records {
             posting_list.add(id, weight);
@@ -98,12 +104,14 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
         chunks: &'a [CompressedPostingChunk<W>],
         remainders: &'a [GenericPostingElement<W>],
         last_id: Option<PointOffsetType>,
+        quantization_params: W::QuantizationParams,
     ) -> Self {
         CompressedPostingListView {
             id_data,
             chunks,
             remainders,
             last_id,
+            quantization_params,
         }
     }
 
@@ -121,6 +129,10 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
         self.last_id
     }
 
+    pub fn quantization_params(&self) -> W::QuantizationParams {
+        self.quantization_params
+    }
+
     pub(super) fn store_size(&self) -> CompressedPostingListStoreSize {
         CompressedPostingListStoreSize::new::<W>(
             self.id_data.len(),
@@ -135,6 +147,7 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
             chunks: self.chunks.to_vec(),
             remainders: self.remainders.to_vec(),
             last_id: self.last_id,
+            quantization_params: self.quantization_params,
         }
     }
 
@@ -180,7 +193,8 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
 }
 
 pub struct CompressedPostingBuilder<W> {
-    elements: Vec<GenericPostingElement<W>>,
+    elements: Vec<PostingElement>,
+    _phantom: PhantomData<W>,
 }
 
 impl<W: Weight> CompressedPostingBuilder<W> {
@@ -188,13 +202,13 @@ impl<W: Weight> CompressedPostingBuilder<W> {
     pub fn new() -> Self {
         CompressedPostingBuilder {
             elements: Vec::new(),
+            _phantom: PhantomData,
         }
     }
 
     /// Add a new record to the posting list.
-    pub fn add(&mut self, record_id: PointOffsetType, weight: W) {
-        self.elements
-            .push(GenericPostingElement { record_id, weight });
+    pub fn add(&mut self, record_id: PointOffsetType, weight: DimWeight) {
+        self.elements.push(PostingElement { record_id, weight });
     }
 
     pub fn build(mut self) -> CompressedPostingList<W> {
@@ -206,6 +220,9 @@ impl<W: Weight> CompressedPostingBuilder<W> {
             panic!("Duplicate id {} in posting list", e.record_id);
         }
 
+        let quantization_params =
+            W::quantization_params_for(self.elements.iter().map(|e| e.weight));
+
         let mut this_chunk = Vec::with_capacity(BitPackerImpl::BLOCK_LEN);
 
         let bitpacker = BitPackerImpl::new();
@@ -226,14 +243,17 @@ impl<W: Weight> CompressedPostingBuilder<W> {
                     offset: data_size as u32,
                     weights: chunk
                         .iter()
-                        .map(|e| e.weight)
+                        .map(|e| W::from_f32(quantization_params, e.weight))
                         .collect::<Vec<_>>()
                         .try_into()
                         .expect("Invalid chunk size"),
                 });
                 data_size += chunk_size;
             } else {
-                remainders.extend_from_slice(chunk);
+                remainders.extend(chunk.iter().map(|e| GenericPostingElement {
+                    record_id: e.record_id,
+                    weight: W::from_f32(quantization_params, e.weight),
+                }));
             }
         }
 
@@ -263,12 +283,13 @@ impl<W: Weight> CompressedPostingBuilder<W> {
             chunks,
             remainders,
             last_id: self.elements.last().map(|e| e.record_id),
+            quantization_params,
         }
     }
 }
 
 #[derive(Clone)]
-pub struct CompressedPostingListIterator<'a, W> {
+pub struct CompressedPostingListIterator<'a, W: Weight> {
     list: CompressedPostingListView<'a, W>,
 
     /// If true, then `decompressed_chunk` contains the unpacked chunk for the current position.
@@ -321,7 +342,8 @@ impl<'a, W: Weight> PostingListIter for CompressedPostingListIterator<'a, W> {
             let chunk = &self.list.chunks[pos / BitPackerImpl::BLOCK_LEN];
             return Some(PostingElementEx {
                 record_id: self.decompressed_chunk[pos % BitPackerImpl::BLOCK_LEN],
-                weight: chunk.weights[pos % BitPackerImpl::BLOCK_LEN].into(),
+                weight: chunk.weights[pos % BitPackerImpl::BLOCK_LEN]
+                    .to_f32(self.list.quantization_params),
                 max_next_weight: Default::default(),
             });
         }
@@ -331,7 +353,7 @@ impl<'a, W: Weight> PostingListIter for CompressedPostingListIterator<'a, W> {
             .get(pos - self.list.chunks.len() * BitPackerImpl::BLOCK_LEN)
             .map(|e| PostingElementEx {
                 record_id: e.record_id,
-                weight: e.weight.into(),
+                weight: e.weight.to_f32(self.list.quantization_params),
                 max_next_weight: Default::default(),
             })
     }
@@ -396,6 +418,7 @@ impl<'a, W: Weight> PostingListIter for CompressedPostingListIterator<'a, W> {
             let start = pos % BitPackerImpl::BLOCK_LEN;
             let count = count_le_sorted(id, &self.decompressed_chunk[start..]);
             let weights = W::into_f32_slice(
+                self.list.quantization_params,
                 &chunk.weights[start..start + count],
                 &mut weights_buf[..count],
             );
@@ -419,7 +442,11 @@ impl<'a, W: Weight> PostingListIter for CompressedPostingListIterator<'a, W> {
                 self.pos = pos;
                 return;
             }
-            f(ctx, e.record_id, e.weight.into());
+            f(
+                ctx,
+                e.record_id,
+                e.weight.to_f32(self.list.quantization_params),
+            );
             pos += 1;
         }
         self.pos = pos;
@@ -435,7 +462,7 @@ impl<'a, W: Weight> PostingListIter for CompressedPostingListIterator<'a, W> {
 }
 
 #[derive(Clone)]
-pub struct CompressedPostingListStdIterator<'a, W>(CompressedPostingListIterator<'a, W>);
+pub struct CompressedPostingListStdIterator<'a, W: Weight>(CompressedPostingListIterator<'a, W>);
 
 impl<W: Weight> Iterator for CompressedPostingListStdIterator<'_, W> {
     type Item = PostingElement;
@@ -460,6 +487,7 @@ fn count_le_sorted<T: Copy + Eq + Ord>(val: T, data: &[T]) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::types::QuantizedU8;
 
     const CASES: [usize; 6] = [0, 64, 128, 192, 256, 320];
 
@@ -476,7 +504,7 @@ mod tests {
     #[test]
     fn test_iter() {
         for case in cases() {
-            let list = CompressedPostingList::from(case.clone());
+            let list = CompressedPostingList::<f32>::from(case.clone());
 
             let mut iter = list.iter();
 
@@ -493,6 +521,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_iter_quantized_u8() {
+        for case in cases() {
+            let list = CompressedPostingList::<QuantizedU8>::from(case.clone());
+            let max_error = case.len() as DimWeight / u8::MAX as DimWeight;
+
+            let mut iter = list.iter();
+            let mut count = 0;
+            while let Some(e) = iter.next() {
+                assert_eq!(e.record_id, case[count].0);
+                assert!((e.weight - case[count].1).abs() <= max_error);
+                count += 1;
+            }
+            assert_eq!(count, case.len());
+
+            let mut data = Vec::new();
+            list.iter()
+                .for_each_till_id(PointOffsetType::MAX, &mut data, |data, id, weight| {
+                    data.push((id, weight));
+                });
+            assert_eq!(data.len(), case.len());
+            for ((id, weight), (expected_id, expected_weight)) in data.into_iter().zip(case) {
+                assert_eq!(id, expected_id);
+                assert!((weight - expected_weight).abs() <= max_error);
+            }
+        }
+    }
+
     #[test]
     #[allow(clippy::needless_range_loop)] // for consistency
     fn test_try_till_id() {
@@ -501,7 +557,7 @@ mod tests {
                 for k in j..CASES.len() {
                     eprintln!("\n\n\n{} {} {}", CASES[i], CASES[j], CASES[k]);
                     let case = mk_case(CASES[k]);
-                    let pl = CompressedPostingList::from(case.clone());
+                    let pl = CompressedPostingList::<f32>::from(case.clone());
 
                     let mut iter = pl.iter();
 
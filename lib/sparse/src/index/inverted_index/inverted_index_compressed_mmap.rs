@@ -15,7 +15,7 @@ use memory::mmap_ops::{
 };
 use serde::{Deserialize, Serialize};
 
-use super::inverted_index_compressed_immutable_ram::InvertedIndexImmutableRam;
+use super::inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam;
 use super::INDEX_FILE_NAME;
 use crate::common::sparse_vector::RemappedSparseVector;
 use crate::common::types::{DimId, DimOffset, Weight};
@@ -26,7 +26,6 @@ use crate::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 use crate::index::inverted_index::InvertedIndex;
 use crate::index::posting_list_common::GenericPostingElement;
 
-const POSTING_HEADER_SIZE: usize = size_of::<PostingListFileHeader>();
 const INDEX_CONFIG_FILE_NAME: &str = "inverted_index_config.json";
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -36,16 +35,16 @@ pub struct InvertedIndexFileHeader {
 }
 
 /// Inverted flatten index from dimension id to posting list
-pub struct InvertedIndexMmap<W> {
+pub struct InvertedIndexCompressedMmap<W> {
     path: PathBuf,
     mmap: Arc<Mmap>,
     pub file_header: InvertedIndexFileHeader,
     _phantom: PhantomData<W>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 #[repr(C)]
-struct PostingListFileHeader {
+struct PostingListFileHeader<W: Weight> {
     pub ids_start: u64,
     pub last_id: u32,
     /// Possible values: 0, 4, 8, ..., 512.
@@ -53,9 +52,12 @@ struct PostingListFileHeader {
     /// Max = 512 = `BLOCK_LEN * size_of::<u32>()` = `128 * 4`.
     pub ids_len: u32,
     pub chunks_count: u32,
+    /// Parameters to convert weights of the posting list into `f32`.
+    /// Zero-sized for weights that are not quantized.
+    pub quantization_params: W::QuantizationParams,
 }
 
-impl<W: Weight> InvertedIndex for InvertedIndexMmap<W> {
+impl<W: Weight> InvertedIndex for InvertedIndexCompressedMmap<W> {
     type Iter<'a> = CompressedPostingListIterator<'a, W>;
 
     fn open(path: &Path) -> std::io::Result<Self> {
@@ -94,7 +96,7 @@ impl<W: Weight> InvertedIndex for InvertedIndexMmap<W> {
         ram_index: Cow<InvertedIndexRam>,
         path: P,
     ) -> std::io::Result<Self> {
-        let index = InvertedIndexImmutableRam::from_ram_index(ram_index, &path)?;
+        let index = InvertedIndexCompressedImmutableRam::from_ram_index(ram_index, &path)?;
         Self::convert_and_save(&index, path)
     }
 
@@ -110,7 +112,9 @@ impl<W: Weight> InvertedIndex for InvertedIndexMmap<W> {
     }
 }
 
-impl<W: Weight> InvertedIndexMmap<W> {
+impl<W: Weight> InvertedIndexCompressedMmap<W> {
+    const POSTING_HEADER_SIZE: usize = size_of::<PostingListFileHeader<W>>();
+
     pub fn index_file_path(path: &Path) -> PathBuf {
         path.join(INDEX_FILE_NAME)
     }
@@ -125,17 +129,19 @@ impl<W: Weight> InvertedIndexMmap<W> {
             return None;
         }
 
-        let header: PostingListFileHeader = self
-            .slice_part::<PostingListFileHeader>(*id as u64 * POSTING_HEADER_SIZE as u64, 1u32)[0]
-            .clone();
+        let header: PostingListFileHeader<W> = self.slice_part::<PostingListFileHeader<W>>(
+            *id as u64 * Self::POSTING_HEADER_SIZE as u64,
+            1u32,
+        )[0]
+        .clone();
 
         let remainders_start = header.ids_start
             + header.ids_len as u64
             + header.chunks_count as u64 * size_of::<CompressedPostingChunk<W>>() as u64;
 
         let remainders_end = if *id + 1 < self.file_header.posting_count as DimId {
-            self.slice_part::<PostingListFileHeader>(
-                (*id + 1) as u64 * POSTING_HEADER_SIZE as u64,
+            self.slice_part::<PostingListFileHeader<W>>(
+                (*id + 1) as u64 * Self::POSTING_HEADER_SIZE as u64,
                 1u32,
             )[0]
             .ids_start
@@ -162,6 +168,7 @@ impl<W: Weight> InvertedIndexMmap<W> {
                 &self.mmap[remainders_start as usize..remainders_end as usize],
             ),
             header.last_id.checked_sub(1),
+            header.quantization_params,
         ))
     }
 
@@ -172,10 +179,11 @@ impl<W: Weight> InvertedIndexMmap<W> {
     }
 
     pub fn convert_and_save<P: AsRef<Path>>(
-        index: &InvertedIndexImmutableRam<W>,
+        index: &InvertedIndexCompressedImmutableRam<W>,
         path: P,
     ) -> std::io::Result<Self> {
-        let total_posting_headers_size = index.postings.as_slice().len() * POSTING_HEADER_SIZE;
+        let total_posting_headers_size =
+            index.postings.as_slice().len() * Self::POSTING_HEADER_SIZE;
 
         let file_length = total_posting_headers_size
             + index
@@ -198,6 +206,7 @@ impl<W: Weight> InvertedIndexMmap<W> {
                 ids_len: store_size.id_data_bytes as u32,
                 chunks_count: store_size.chunks_count as u32,
                 last_id: posting.view().last_id().map_or(0, |id| id + 1),
+                quantization_params: posting.view().quantization_params(),
             };
             buf.write_all(transmute_to_u8(&posting_header))?;
             offset += store_size.total;
@@ -253,11 +262,12 @@ mod tests {
     use tempfile::Builder;
 
     use super::*;
+    use crate::common::types::QuantizedU8;
     use crate::index::inverted_index::inverted_index_ram_builder::InvertedIndexBuilder;
 
     fn compare_indexes<W: Weight>(
-        inverted_index_ram: &InvertedIndexImmutableRam<W>,
-        inverted_index_mmap: &InvertedIndexMmap<W>,
+        inverted_index_ram: &InvertedIndexCompressedImmutableRam<W>,
+        inverted_index_mmap: &InvertedIndexCompressedMmap<W>,
     ) {
         for id in 0..inverted_index_ram.postings.len() as DimId {
             let posting_list_ram = inverted_index_ram.postings.get(id as usize).unwrap().view();
@@ -270,6 +280,7 @@ mod tests {
     fn test_inverted_index_mmap() {
         check_inverted_index_mmap::<f32>();
         check_inverted_index_mmap::<half::f16>();
+        check_inverted_index_mmap::<QuantizedU8>();
     }
 
     fn check_inverted_index_mmap<W: Weight>() {
@@ -286,7 +297,7 @@ mod tests {
         builder.add(9, [(1, 6.0)].into());
         let inverted_index_ram = builder.build();
         let tmp_dir_path = Builder::new().prefix("test_index_dir1").tempdir().unwrap();
-        let inverted_index_ram = InvertedIndexImmutableRam::from_ram_index(
+        let inverted_index_ram = InvertedIndexCompressedImmutableRam::from_ram_index(
             Cow::Borrowed(&inverted_index_ram),
             &tmp_dir_path,
         )
@@ -295,13 +306,15 @@ mod tests {
         let tmp_dir_path = Builder::new().prefix("test_index_dir2").tempdir().unwrap();
 
         {
-            let inverted_index_mmap =
-                InvertedIndexMmap::<W>::convert_and_save(&inverted_index_ram, &tmp_dir_path)
-                    .unwrap();
+            let inverted_index_mmap = InvertedIndexCompressedMmap::<W>::convert_and_save(
+                &inverted_index_ram,
+                &tmp_dir_path,
+            )
+            .unwrap();
 
             compare_indexes(&inverted_index_ram, &inverted_index_mmap);
         }
-        let inverted_index_mmap = InvertedIndexMmap::<W>::load(&tmp_dir_path).unwrap();
+        let inverted_index_mmap = InvertedIndexCompressedMmap::<W>::load(&tmp_dir_path).unwrap();
         // posting_count: 0th entry is always empty + 1st + 2nd + 3rd + 4th empty + 5th
         assert_eq!(inverted_index_mmap.file_header.posting_count, 6);
         assert_eq!(inverted_index_mmap.file_header.vector_count, 9);
@@ -409,16 +409,16 @@ mod tests {
     #[instantiate_tests(<inverted_index_immutable_ram::InvertedIndexImmutableRam>)]
     mod iram {}
 
-    #[instantiate_tests(<inverted_index_compressed_immutable_ram::InvertedIndexImmutableRam<f32>>)]
+    #[instantiate_tests(<inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam<f32>>)]
     mod iram_f32 {}
 
-    #[instantiate_tests(<inverted_index_compressed_immutable_ram::InvertedIndexImmutableRam<half::f16>>)]
+    #[instantiate_tests(<inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam<half::f16>>)]
     mod iram_f16 {}
 
-    #[instantiate_tests(<inverted_index_compressed_mmap::InvertedIndexMmap<f32>>)]
+    #[instantiate_tests(<inverted_index_compressed_mmap::InvertedIndexCompressedMmap<f32>>)]
     mod mmap_f32 {}
 
-    #[instantiate_tests(<inverted_index_compressed_mmap::InvertedIndexMmap<half::f16>>)]
+    #[instantiate_tests(<inverted_index_compressed_mmap::InvertedIndexCompressedMmap<half::f16>>)]
     mod mmap_f16 {}
 
     // --- End of test instantiations ---
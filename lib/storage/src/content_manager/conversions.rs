@@ -88,6 +88,7 @@ impl TryFrom<api::grpc::qdrant::UpdateCollection> for CollectionMetaOperations {
                         config.map.into_iter().map(|(k, v)| (k, v.into())).collect(),
                     )
                 }),
+                strict_mode_config: value.strict_mode_config.map(Into::into),
             },
         )))
     }
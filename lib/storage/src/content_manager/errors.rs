@@ -131,6 +131,9 @@ impl StorageError {
                 description: overriding_description,
                 backtrace: None,
             },
+            CollectionError::StrictMode { .. } => StorageError::Forbidden {
+                description: overriding_description,
+            },
         }
     }
 }
@@ -179,6 +182,9 @@ impl From<CollectionError> for StorageError {
                 description: format!("{err}"),
                 backtrace: None,
             },
+            CollectionError::StrictMode { .. } => StorageError::Forbidden {
+                description: format!("{err}"),
+            },
         }
     }
 }
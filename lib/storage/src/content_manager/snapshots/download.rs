@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use collection::common::snapshot_manifest::SnapshotManifest;
 use futures::StreamExt;
 use reqwest;
 use tempfile::TempPath;
@@ -95,3 +96,57 @@ pub async fn download_snapshot(
         ))),
     }
 }
+
+/// Download base snapshots of an incremental snapshot, which was downloaded from `url`.
+///
+/// Base snapshots are resolved relative to the snapshot URL, and downloaded next to the snapshot,
+/// where restore expects them. Local snapshots need nothing, their bases are next to them already.
+///
+/// Returns `TempPath`s which delete the downloaded files once dropped.
+#[must_use = "returns TempPaths, if dropped the downloaded files are deleted"]
+pub async fn download_base_snapshots(
+    client: &reqwest::Client,
+    url: &Url,
+    snapshot_path: &Path,
+) -> Result<Vec<TempPath>, StorageError> {
+    let mut temp_paths = Vec::new();
+
+    if url.scheme() == "file" {
+        return Ok(temp_paths);
+    }
+
+    let mut url = url.clone();
+    let mut snapshot_path = snapshot_path.to_path_buf();
+
+    loop {
+        let manifest_path = snapshot_path.clone();
+        let manifest = tokio::task::spawn_blocking(move || {
+            SnapshotManifest::load_from_archive(&manifest_path)
+        })
+        .await??;
+
+        let Some(base) = manifest.and_then(|manifest| manifest.base) else {
+            break;
+        };
+
+        let base_path = snapshot_path.with_file_name(&base);
+        if base_path.exists() {
+            // Already downloaded, the chain is cyclic
+            break;
+        }
+
+        url = url.join(&base).map_err(|err| {
+            StorageError::bad_input(format!("Invalid base snapshot {base} of {url}: {err}"))
+        })?;
+
+        log::debug!(
+            "Downloading base snapshot from {url} to {}",
+            base_path.display()
+        );
+        temp_paths.push(download_file(client, &url, &base_path).await?);
+
+        snapshot_path = base_path;
+    }
+
+    Ok(temp_paths)
+}
@@ -1,5 +1,6 @@
 pub mod download;
 pub mod recover;
+pub mod schedule;
 
 use std::collections::HashMap;
 use std::path::Path;
@@ -11,6 +12,7 @@ use tempfile::TempPath;
 use tokio::io::AsyncWriteExt;
 use tokio::task::JoinHandle;
 
+use crate::content_manager::snapshots::schedule::SnapshotTarget;
 use crate::content_manager::toc::FULL_SNAPSHOT_FILE_NAME;
 use crate::dispatcher::Dispatcher;
 use crate::rbac::{Access, AccessRequirements};
@@ -42,11 +44,16 @@ pub async fn do_delete_full_snapshot(
     }))
 }
 
+/// Delete a snapshot of a collection
+///
+/// Snapshots, which incremental snapshots are based on, are only deleted with `force`,
+/// as the incremental snapshots can't be restored without them.
 pub async fn do_delete_collection_snapshot(
     dispatcher: &Dispatcher,
     access: Access,
     collection_name: &str,
     snapshot_name: &str,
+    force: bool,
 ) -> Result<JoinHandle<Result<bool, StorageError>>, StorageError> {
     let collection_pass = access
         .check_collection_access(collection_name, AccessRequirements::new().write().whole())?;
@@ -58,6 +65,23 @@ pub async fn do_delete_collection_snapshot(
         .get_snapshot_path(collection.snapshots_path(), &snapshot_name)
         .await?;
 
+    if !force {
+        let dependent = collection
+            .list_snapshots()
+            .await?
+            .into_iter()
+            .map(|snapshot| snapshot.name)
+            .filter(|name| *name != snapshot_name);
+        let bases = SnapshotTarget::Collection(&collection)
+            .with_bases(toc, &snapshot_manager, dependent)
+            .await?;
+        if bases.contains(&snapshot_name) {
+            return Err(StorageError::bad_request(format!(
+                "Snapshot {snapshot_name} is a base of incremental snapshots, which can't be restored without it. Use `force` to delete it anyway"
+            )));
+        }
+    }
+
     log::info!("Deleting collection snapshot {:?}", file_name);
     Ok(tokio::spawn(async move {
         Ok(snapshot_manager.delete_snapshot(&file_name).await?)
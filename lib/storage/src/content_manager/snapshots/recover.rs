@@ -11,7 +11,7 @@ use tokio::task::JoinHandle;
 use crate::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreateCollectionOperation,
 };
-use crate::content_manager::snapshots::download::download_snapshot;
+use crate::content_manager::snapshots::download::{download_base_snapshots, download_snapshot};
 use crate::dispatcher::Dispatcher;
 use crate::rbac::{Access, AccessRequirements, CollectionPass};
 use crate::{StorageError, TableOfContent};
@@ -91,7 +91,7 @@ async fn _do_recover_from_snapshot(
     );
 
     let (snapshot_path, snapshot_temp_path) =
-        download_snapshot(client, location, download_dir.path()).await?;
+        download_snapshot(client, location.clone(), download_dir.path()).await?;
 
     if let Some(checksum) = checksum {
         let snapshot_checksum = hash_file(&snapshot_path).await?;
@@ -104,6 +104,9 @@ async fn _do_recover_from_snapshot(
 
     log::debug!("Snapshot downloaded to {}", snapshot_path.display());
 
+    // Incremental snapshot is restored together with the chain of its base snapshots
+    let base_temp_paths = download_base_snapshots(client, &location, &snapshot_path).await?;
+
     let temp_storage_path = toc.optional_temp_or_storage_temp_path()?;
 
     let tmp_collection_dir = tempfile::Builder::new()
@@ -317,6 +320,11 @@ async fn _do_recover_from_snapshot(
     tokio::fs::remove_dir_all(&tmp_collection_dir).await?;
 
     // Remove snapshot after recovery if downloaded
+    for path in base_temp_paths {
+        if let Err(err) = path.close() {
+            log::error!("Failed to remove downloaded base snapshot after recovery: {err}");
+        }
+    }
     if let Some(path) = snapshot_temp_path {
         if let Err(err) = path.close() {
             log::error!("Failed to remove downloaded collection snapshot after recovery: {err}");
@@ -0,0 +1,758 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Weak;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use collection::collection::Collection;
+use collection::common::snapshots_manager::SnapshotStorageManager;
+use collection::operations::snapshot_ops::{SnapshotDescription, SnapshotScheduleStatus};
+use collection::save_on_disk::SaveOnDisk;
+use collection::shards::replica_set::ReplicaState;
+use collection::shards::shard::{PeerId, ShardId};
+use collection::shards::CollectionId;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use super::_do_create_full_snapshot;
+use crate::content_manager::toc::FULL_SNAPSHOT_FILE_NAME;
+use crate::rbac::{Access, CollectionPass};
+use crate::{StorageError, TableOfContent};
+
+/// How often the scheduler checks for due snapshots
+const SCHEDULER_TICK: Duration = Duration::from_secs(1);
+
+const SCHEDULER_ACCESS: Access = Access::full("Snapshot scheduler");
+
+/// Names of the snapshots taken by the scheduler, stored in the snapshots directory
+const SCHEDULED_SNAPSHOTS_FILE: &str = "scheduled_snapshots.json";
+
+/// Schedules of automatic snapshots
+#[derive(Debug, Deserialize, Serialize, Clone, Default, Validate)]
+pub struct SnapshotScheduleConfig {
+    /// Schedule of full storage snapshots
+    #[validate]
+    #[serde(default)]
+    pub full: Option<SnapshotSchedule>,
+    /// Schedule of snapshots of every collection, unless overridden in `collections`
+    #[validate]
+    #[serde(default)]
+    pub collections_default: Option<SnapshotSchedule>,
+    /// Schedules of snapshots of specific collections, `null` disables scheduled snapshots of a collection
+    #[validate(custom = "validate_collection_schedules")]
+    #[serde(default)]
+    pub collections: HashMap<CollectionId, Option<SnapshotSchedule>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Validate)]
+pub struct SnapshotSchedule {
+    /// Interval between snapshots in seconds.
+    /// Snapshots are taken at multiples of the interval since the Unix epoch, shifted by `offset_sec`,
+    /// e.g. interval of 86400 takes a snapshot every day at midnight UTC.
+    #[validate(range(min = 1))]
+    pub interval_sec: u64,
+    /// Shift of the snapshot time within the interval in seconds
+    #[serde(default)]
+    pub offset_sec: u64,
+    /// Which of the existing snapshots to keep after a scheduled snapshot is taken
+    #[validate]
+    #[serde(default)]
+    pub retention: SnapshotRetention,
+}
+
+/// Retention policy of snapshots.
+///
+/// Only applies to snapshots taken by the scheduler, other snapshots are never deleted.
+/// A snapshot is kept if any of `keep_last` and `keep_daily` retains it, or if neither is set.
+/// Snapshots older than `max_age_sec` are deleted in any case.
+/// Base snapshots of kept incremental snapshots are never deleted.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, Validate)]
+pub struct SnapshotRetention {
+    /// Keep this number of the latest snapshots
+    #[validate(range(min = 1))]
+    #[serde(default)]
+    pub keep_last: Option<usize>,
+    /// Keep the latest snapshot of each of this number of the latest days with snapshots
+    #[validate(range(min = 1))]
+    #[serde(default)]
+    pub keep_daily: Option<usize>,
+    /// Delete snapshots older than this number of seconds
+    #[serde(default)]
+    pub max_age_sec: Option<u64>,
+}
+
+fn validate_collection_schedules(
+    schedules: &HashMap<CollectionId, Option<SnapshotSchedule>>,
+) -> Result<(), ValidationError> {
+    for schedule in schedules.values().flatten() {
+        if schedule.validate().is_err() {
+            return Err(ValidationError::new("invalid_collection_snapshot_schedule"));
+        }
+    }
+    Ok(())
+}
+
+impl SnapshotScheduleConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.full.is_some()
+            || self.collections_default.is_some()
+            || self.collections.values().any(Option::is_some)
+    }
+
+    fn collection_schedule(&self, collection_name: &str) -> Option<&SnapshotSchedule> {
+        match self.collections.get(collection_name) {
+            Some(schedule) => schedule.as_ref(),
+            None => self.collections_default.as_ref(),
+        }
+    }
+}
+
+impl SnapshotSchedule {
+    /// First scheduled time strictly after `time`
+    fn next_run(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let interval = self.interval_sec as i64;
+        let offset = (self.offset_sec % self.interval_sec) as i64;
+        let slot = (time.timestamp() - offset).div_euclid(interval);
+        DateTime::from_timestamp((slot + 1) * interval + offset, 0)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+
+    fn initial_status(&self, now: DateTime<Utc>) -> SnapshotScheduleStatus {
+        SnapshotScheduleStatus {
+            interval_sec: self.interval_sec,
+            next_run: self.next_run(now),
+            last_run: None,
+            last_snapshots: Vec::new(),
+            last_error: None,
+            created: 0,
+            deleted: 0,
+            failed: 0,
+            shards: None,
+        }
+    }
+}
+
+impl SnapshotRetention {
+    fn keeps_all(&self) -> bool {
+        self.keep_last.is_none() && self.keep_daily.is_none() && self.max_age_sec.is_none()
+    }
+
+    /// Names of the snapshots, which are not retained by the policy.
+    ///
+    /// Snapshots without creation time are always kept.
+    fn expired_snapshots(
+        &self,
+        snapshots: &[SnapshotDescription],
+        now: NaiveDateTime,
+    ) -> HashSet<String> {
+        let mut dated: Vec<_> = snapshots
+            .iter()
+            .filter_map(|snapshot| Some((snapshot.creation_time?, snapshot.name.as_str())))
+            .collect();
+        // Latest first
+        dated.sort_unstable_by(|a, b| b.cmp(a));
+
+        let keep_by_count = self.keep_last.is_some() || self.keep_daily.is_some();
+        let max_age = self
+            .max_age_sec
+            .map(|max_age| i64::try_from(max_age).unwrap_or(i64::MAX));
+        let mut kept_days = HashSet::new();
+
+        let mut expired = HashSet::new();
+        for (index, (creation_time, name)) in dated.into_iter().enumerate() {
+            let is_last = self.keep_last.is_some_and(|keep_last| index < keep_last);
+            let is_daily = match self.keep_daily {
+                Some(keep_daily) if kept_days.len() < keep_daily => {
+                    kept_days.insert(creation_time.date())
+                }
+                _ => false,
+            };
+            let is_too_old = max_age.is_some_and(|max_age| {
+                now.signed_duration_since(creation_time).num_seconds() > max_age
+            });
+
+            if is_too_old || (keep_by_count && !is_last && !is_daily) {
+                expired.insert(name.to_string());
+            }
+        }
+        expired
+    }
+}
+
+/// Peer, which takes scheduled snapshots of the shard.
+///
+/// Active replicas take turns by shard ID, so that snapshots are spread over the cluster.
+/// Consensus state is the same on all peers, so every shard is taken by a single peer.
+fn responsible_peer(shard_id: ShardId, replicas: &HashMap<PeerId, ReplicaState>) -> Option<PeerId> {
+    let mut active_peers: Vec<_> = replicas
+        .iter()
+        .filter(|(_, state)| **state == ReplicaState::Active)
+        .map(|(peer_id, _)| *peer_id)
+        .collect();
+    if active_peers.is_empty() {
+        return None;
+    }
+    active_peers.sort_unstable();
+    Some(active_peers[shard_id as usize % active_peers.len()])
+}
+
+/// Takes scheduled snapshots and applies retention policies, keeps status of the schedules
+pub struct SnapshotScheduler {
+    config: SnapshotScheduleConfig,
+    full_status: Mutex<Option<SnapshotScheduleStatus>>,
+    collection_statuses: Mutex<HashMap<CollectionId, SnapshotScheduleStatus>>,
+    /// Retention policies only apply to these snapshots
+    scheduled_snapshots: SaveOnDisk<HashSet<String>>,
+}
+
+/// Outcome of a single scheduled run
+#[derive(Default)]
+struct ScheduledRun {
+    snapshots: Vec<String>,
+    deleted: usize,
+    shards: Option<Vec<ShardId>>,
+    /// Errors of shards, which failed while others succeeded
+    error: Option<String>,
+}
+
+/// Snapshots a retention policy is applied to
+pub(super) enum SnapshotTarget<'a> {
+    Full,
+    Collection(&'a Collection),
+    Shard(&'a Collection, ShardId),
+}
+
+impl SnapshotScheduler {
+    pub fn new(
+        config: SnapshotScheduleConfig,
+        snapshots_path: &Path,
+    ) -> Result<Self, StorageError> {
+        let scheduled_snapshots =
+            SaveOnDisk::load_or_init_default(snapshots_path.join(SCHEDULED_SNAPSHOTS_FILE))
+                .map_err(|err| {
+                    StorageError::service_error(format!("Can't load scheduled snapshots: {err}"))
+                })?;
+        Ok(Self {
+            config,
+            full_status: Default::default(),
+            collection_statuses: Default::default(),
+            scheduled_snapshots,
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.is_enabled()
+    }
+
+    pub fn full_status(&self) -> Option<SnapshotScheduleStatus> {
+        self.full_status.lock().clone()
+    }
+
+    pub fn collection_status(&self, collection_name: &str) -> Option<SnapshotScheduleStatus> {
+        self.collection_statuses
+            .lock()
+            .get(collection_name)
+            .cloned()
+    }
+
+    /// Run scheduled snapshots until the table of content is dropped
+    pub async fn run(toc: Weak<TableOfContent>) {
+        let mut ticks = tokio::time::interval(SCHEDULER_TICK);
+        ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            let Some(toc) = toc.upgrade() else {
+                return;
+            };
+            toc.snapshot_scheduler().run_due(&toc).await;
+        }
+    }
+
+    async fn run_due(&self, toc: &TableOfContent) {
+        if let Some(schedule) = &self.config.full {
+            let now = Utc::now();
+            let is_due = now
+                >= self
+                    .full_status
+                    .lock()
+                    .get_or_insert_with(|| schedule.initial_status(now))
+                    .next_run;
+
+            if is_due {
+                log::info!("Creating scheduled full snapshot");
+                let result = self.snapshot_full(toc, &schedule.retention).await;
+                if let Some(status) = self.full_status.lock().as_mut() {
+                    Self::update_status(status, schedule, now, result);
+                }
+            }
+        }
+
+        let collections = toc.all_collections(&SCHEDULER_ACCESS).await;
+
+        // Forget statuses of removed collections
+        self.collection_statuses
+            .lock()
+            .retain(|name, _| collections.iter().any(|pass| pass.name() == name.as_str()));
+
+        for collection_pass in &collections {
+            let collection_name = collection_pass.name();
+            let Some(schedule) = self.config.collection_schedule(collection_name) else {
+                continue;
+            };
+
+            let now = Utc::now();
+            let is_due = now
+                >= self
+                    .collection_statuses
+                    .lock()
+                    .entry(collection_name.to_string())
+                    .or_insert_with(|| schedule.initial_status(now))
+                    .next_run;
+            if !is_due {
+                continue;
+            }
+
+            log::info!("Creating scheduled snapshot of collection {collection_name}");
+            let result = self
+                .snapshot_collection(toc, collection_pass, &schedule.retention)
+                .await;
+            if let Some(status) = self.collection_statuses.lock().get_mut(collection_name) {
+                Self::update_status(status, schedule, now, result);
+            }
+        }
+    }
+
+    fn update_status(
+        status: &mut SnapshotScheduleStatus,
+        schedule: &SnapshotSchedule,
+        started: DateTime<Utc>,
+        result: Result<ScheduledRun, StorageError>,
+    ) {
+        // Runs missed while this one was in progress are skipped
+        status.next_run = schedule.next_run(Utc::now());
+        status.last_run = Some(started);
+        match result {
+            Ok(run) => {
+                if run.error.is_some() {
+                    status.failed += 1;
+                }
+                status.created += run.snapshots.len();
+                status.deleted += run.deleted;
+                status.last_snapshots = run.snapshots;
+                status.last_error = run.error;
+                status.shards = run.shards;
+            }
+            Err(err) => {
+                log::error!("Scheduled snapshot failed: {err}");
+                status.failed += 1;
+                status.last_snapshots = Vec::new();
+                status.last_error = Some(err.to_string());
+            }
+        }
+    }
+
+    /// Remember that the snapshot is taken by the scheduler, so that retention applies to it
+    fn track_snapshot(&self, snapshot_name: &str) -> Result<(), StorageError> {
+        self.scheduled_snapshots
+            .write(|scheduled| scheduled.insert(snapshot_name.to_string()))
+            .map_err(|err| {
+                StorageError::service_error(format!(
+                    "Can't save scheduled snapshot {snapshot_name}: {err}"
+                ))
+            })?;
+        Ok(())
+    }
+
+    async fn snapshot_full(
+        &self,
+        toc: &TableOfContent,
+        retention: &SnapshotRetention,
+    ) -> Result<ScheduledRun, StorageError> {
+        let snapshot = _do_create_full_snapshot(toc, SCHEDULER_ACCESS).await?;
+        self.track_snapshot(&snapshot.name)?;
+        let deleted = self
+            .apply_retention(toc, SnapshotTarget::Full, retention)
+            .await?;
+        Ok(ScheduledRun {
+            snapshots: vec![snapshot.name],
+            deleted,
+            ..Default::default()
+        })
+    }
+
+    /// Take a collection snapshot, or in cluster mode - snapshots of shards this peer is responsible for
+    ///
+    /// A failure of one shard doesn't stop snapshots of the other shards, it is reported in the run.
+    async fn snapshot_collection(
+        &self,
+        toc: &TableOfContent,
+        collection_pass: &CollectionPass<'_>,
+        retention: &SnapshotRetention,
+    ) -> Result<ScheduledRun, StorageError> {
+        if !toc.is_distributed() {
+            let snapshot = toc.create_snapshot(collection_pass).await?;
+            self.track_snapshot(&snapshot.name)?;
+            let collection = toc.get_collection(collection_pass).await?;
+            let deleted = self
+                .apply_retention(toc, SnapshotTarget::Collection(&collection), retention)
+                .await?;
+            return Ok(ScheduledRun {
+                snapshots: vec![snapshot.name],
+                deleted,
+                ..Default::default()
+            });
+        }
+
+        let collection = toc.get_collection(collection_pass).await?;
+        let mut shards: Vec<_> = collection.state().await.shards.into_iter().collect();
+        shards.sort_unstable_by_key(|(shard_id, _)| *shard_id);
+
+        let mut run = ScheduledRun {
+            shards: Some(Vec::new()),
+            ..Default::default()
+        };
+        let mut errors = Vec::new();
+        for (shard_id, shard_info) in shards {
+            if !shard_info.replicas.contains_key(&toc.this_peer_id) {
+                continue;
+            }
+
+            let is_responsible =
+                responsible_peer(shard_id, &shard_info.replicas) == Some(toc.this_peer_id);
+            match self
+                .snapshot_shard(toc, &collection, shard_id, is_responsible, retention)
+                .await
+            {
+                Ok((snapshot, deleted)) => {
+                    if let Some(snapshot) = snapshot {
+                        run.snapshots.push(snapshot);
+                        run.shards.get_or_insert_with(Vec::new).push(shard_id);
+                    }
+                    run.deleted += deleted;
+                }
+                Err(err) => {
+                    log::error!(
+                        "Scheduled snapshot of shard {shard_id} of collection {} failed: {err}",
+                        collection_pass.name(),
+                    );
+                    errors.push(format!("shard {shard_id}: {err}"));
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            run.error = Some(errors.join("; "));
+        }
+        Ok(run)
+    }
+
+    /// Take a snapshot of the shard if this peer is responsible for it, and apply retention.
+    ///
+    /// Returns the name of the taken snapshot and the number of deleted snapshots.
+    async fn snapshot_shard(
+        &self,
+        toc: &TableOfContent,
+        collection: &Collection,
+        shard_id: ShardId,
+        is_responsible: bool,
+        retention: &SnapshotRetention,
+    ) -> Result<(Option<String>, usize), StorageError> {
+        let snapshot = if is_responsible {
+            let temp_dir = toc.optional_temp_or_snapshot_temp_path()?;
+            let snapshot = collection
+                .create_shard_snapshot(shard_id, &temp_dir)
+                .await?;
+            self.track_snapshot(&snapshot.name)?;
+            Some(snapshot.name)
+        } else {
+            None
+        };
+
+        // Also applied to replicas this peer is not responsible for anymore,
+        // so that snapshots it took before are cleaned up eventually
+        let deleted = self
+            .apply_retention(toc, SnapshotTarget::Shard(collection, shard_id), retention)
+            .await?;
+        Ok((snapshot, deleted))
+    }
+
+    /// Delete snapshots, which are not retained by the policy, returns number of deleted snapshots
+    async fn apply_retention(
+        &self,
+        toc: &TableOfContent,
+        target: SnapshotTarget<'_>,
+        retention: &SnapshotRetention,
+    ) -> Result<usize, StorageError> {
+        if retention.keeps_all() {
+            return Ok(0);
+        }
+
+        let snapshot_manager = toc.get_snapshots_storage_manager()?;
+        let snapshots = target.list_snapshots(toc, &snapshot_manager).await?;
+
+        // Snapshots created by users are never deleted
+        let scheduled: Vec<_> = {
+            let scheduled_snapshots = self.scheduled_snapshots.read();
+            snapshots
+                .iter()
+                .filter(|snapshot| scheduled_snapshots.contains(&snapshot.name))
+                .cloned()
+                .collect()
+        };
+        let mut expired = retention.expired_snapshots(&scheduled, Utc::now().naive_utc());
+        if expired.is_empty() {
+            return Ok(0);
+        }
+
+        // Incremental snapshots can't be restored without their bases
+        let kept = snapshots
+            .iter()
+            .filter(|snapshot| !expired.contains(&snapshot.name))
+            .map(|snapshot| snapshot.name.clone());
+        let protected = target.with_bases(toc, &snapshot_manager, kept).await?;
+        expired.retain(|name| !protected.contains(name));
+
+        let mut deleted = 0;
+        for name in expired {
+            let path = target.snapshot_path(toc, &snapshot_manager, &name).await?;
+            log::info!("Deleting snapshot {path:?} by retention policy");
+            if snapshot_manager.delete_snapshot(&path).await? {
+                deleted += 1;
+            }
+            self.scheduled_snapshots
+                .write(|scheduled| scheduled.remove(&name))
+                .map_err(|err| {
+                    StorageError::service_error(format!(
+                        "Can't save scheduled snapshots after deleting {name}: {err}"
+                    ))
+                })?;
+        }
+        Ok(deleted)
+    }
+}
+
+impl SnapshotTarget<'_> {
+    async fn list_snapshots(
+        &self,
+        toc: &TableOfContent,
+        snapshot_manager: &SnapshotStorageManager,
+    ) -> Result<Vec<SnapshotDescription>, StorageError> {
+        let snapshots = match self {
+            // Listing of object storage is recursive, it would include collection snapshots
+            SnapshotTarget::Full => snapshot_manager
+                .list_snapshots(Path::new(toc.snapshots_path()))
+                .await?
+                .into_iter()
+                .filter(|snapshot| snapshot.name.starts_with(FULL_SNAPSHOT_FILE_NAME))
+                .collect(),
+            SnapshotTarget::Collection(collection) => collection.list_snapshots().await?,
+            SnapshotTarget::Shard(collection, shard_id) => {
+                collection.list_shard_snapshots(*shard_id).await?
+            }
+        };
+        Ok(snapshots)
+    }
+
+    async fn snapshot_path(
+        &self,
+        toc: &TableOfContent,
+        snapshot_manager: &SnapshotStorageManager,
+        snapshot_name: &str,
+    ) -> Result<PathBuf, StorageError> {
+        let path = match self {
+            SnapshotTarget::Full => {
+                snapshot_manager
+                    .get_full_snapshot_path(toc.snapshots_path(), snapshot_name)
+                    .await?
+            }
+            SnapshotTarget::Collection(collection) => {
+                snapshot_manager
+                    .get_snapshot_path(collection.snapshots_path(), snapshot_name)
+                    .await?
+            }
+            SnapshotTarget::Shard(collection, shard_id) => {
+                snapshot_manager
+                    .get_shard_snapshot_path(
+                        collection.shards_holder(),
+                        *shard_id,
+                        collection.snapshots_path(),
+                        snapshot_name,
+                    )
+                    .await?
+            }
+        };
+        Ok(path)
+    }
+
+    /// Names of `snapshots` and of all snapshots they are based on, directly or through other
+    /// incremental snapshots
+    pub(super) async fn with_bases(
+        &self,
+        toc: &TableOfContent,
+        snapshot_manager: &SnapshotStorageManager,
+        snapshots: impl IntoIterator<Item = String>,
+    ) -> Result<HashSet<String>, StorageError> {
+        let mut visited = HashSet::new();
+        for snapshot in snapshots {
+            let mut next_name = Some(snapshot);
+            while let Some(name) = next_name.take() {
+                if !visited.insert(name.clone()) {
+                    break;
+                }
+                let Ok(path) = self.snapshot_path(toc, snapshot_manager, &name).await else {
+                    break;
+                };
+                next_name = snapshot_manager
+                    .read_snapshot_manifest(&path)
+                    .await?
+                    .and_then(|manifest| manifest.base);
+            }
+        }
+        Ok(visited)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn snapshot(name: &str, creation_time: NaiveDateTime) -> SnapshotDescription {
+        SnapshotDescription {
+            name: name.to_string(),
+            creation_time: Some(creation_time),
+            size: 0,
+            checksum: None,
+        }
+    }
+
+    fn time(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 5, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn sorted(names: HashSet<String>) -> Vec<String> {
+        let mut names: Vec<_> = names.into_iter().collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_next_run() {
+        let schedule = SnapshotSchedule {
+            interval_sec: 3600,
+            offset_sec: 600,
+            retention: Default::default(),
+        };
+
+        let time = DateTime::from_timestamp(7 * 3600 + 300, 0).unwrap();
+        assert_eq!(
+            schedule.next_run(time).timestamp(),
+            7 * 3600 + 600,
+            "next run is in the current interval, after the offset",
+        );
+
+        let time = DateTime::from_timestamp(7 * 3600 + 600, 0).unwrap();
+        assert_eq!(schedule.next_run(time).timestamp(), 8 * 3600 + 600);
+    }
+
+    #[test]
+    fn test_retention() {
+        let snapshots = vec![
+            snapshot("a", time(1, 10)),
+            snapshot("b", time(1, 20)),
+            snapshot("c", time(2, 10)),
+            snapshot("d", time(3, 10)),
+            snapshot("e", time(3, 20)),
+            SnapshotDescription {
+                name: "undated".to_string(),
+                creation_time: None,
+                size: 0,
+                checksum: None,
+            },
+        ];
+        let now = time(4, 0);
+
+        let retention = SnapshotRetention::default();
+        assert!(retention.keeps_all());
+        assert!(retention.expired_snapshots(&snapshots, now).is_empty());
+
+        let retention = SnapshotRetention {
+            keep_last: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            sorted(retention.expired_snapshots(&snapshots, now)),
+            ["a", "b", "c"],
+        );
+
+        let retention = SnapshotRetention {
+            keep_daily: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            sorted(retention.expired_snapshots(&snapshots, now)),
+            ["a", "b", "d"],
+        );
+
+        let retention = SnapshotRetention {
+            keep_last: Some(1),
+            keep_daily: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            sorted(retention.expired_snapshots(&snapshots, now)),
+            ["a", "d"],
+        );
+
+        // Max age applies even to snapshots retained by count
+        let retention = SnapshotRetention {
+            keep_daily: Some(3),
+            max_age_sec: Some(2 * 24 * 3600),
+            ..Default::default()
+        };
+        assert_eq!(
+            sorted(retention.expired_snapshots(&snapshots, now)),
+            ["a", "b", "d"],
+        );
+    }
+
+    #[test]
+    fn test_scheduled_snapshots_are_persisted() {
+        let dir = tempfile::Builder::new()
+            .prefix("snapshots")
+            .tempdir()
+            .unwrap();
+
+        let scheduler = SnapshotScheduler::new(Default::default(), dir.path()).unwrap();
+        scheduler.track_snapshot("scheduled.snapshot").unwrap();
+        drop(scheduler);
+
+        let scheduler = SnapshotScheduler::new(Default::default(), dir.path()).unwrap();
+        let scheduled_snapshots = scheduler.scheduled_snapshots.read();
+        assert!(scheduled_snapshots.contains("scheduled.snapshot"));
+        assert!(!scheduled_snapshots.contains("user.snapshot"));
+    }
+
+    #[test]
+    fn test_responsible_peer() {
+        let replicas = HashMap::from([
+            (3, ReplicaState::Active),
+            (1, ReplicaState::Active),
+            (2, ReplicaState::Dead),
+            (4, ReplicaState::Active),
+        ]);
+
+        assert_eq!(responsible_peer(0, &replicas), Some(1));
+        assert_eq!(responsible_peer(1, &replicas), Some(3));
+        assert_eq!(responsible_peer(2, &replicas), Some(4));
+        assert_eq!(responsible_peer(3, &replicas), Some(1));
+
+        let replicas = HashMap::from([(1, ReplicaState::Partial)]);
+        assert_eq!(responsible_peer(0, &replicas), None);
+    }
+}
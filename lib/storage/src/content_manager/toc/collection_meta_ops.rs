@@ -3,7 +3,7 @@ use std::path::Path;
 
 use collection::collection_state;
 use collection::config::ShardingMethod;
-use collection::events::{CollectionDeletedEvent, IndexCreatedEvent};
+use collection::events::{CollectionDeletedEvent, CollectionUpdatedEvent, IndexCreatedEvent};
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::transfer::ShardTransfer;
@@ -126,6 +126,7 @@ impl TableOfContent {
             optimizers_config,
             quantization_config,
             sparse_vectors,
+            strict_mode_config,
         } = operation.update_collection;
         let collection = self
             .get_collection_unchecked(&operation.collection_name)
@@ -158,6 +159,9 @@ impl TableOfContent {
             collection.update_sparse_vectors_from_other(&diff).await?;
             recreate_optimizers = true;
         }
+        if let Some(diff) = strict_mode_config {
+            collection.update_strict_mode_config(diff).await?;
+        }
         if let Some(changes) = replica_changes {
             collection.handle_replica_changes(changes).await?;
         }
@@ -166,6 +170,11 @@ impl TableOfContent {
         if recreate_optimizers {
             collection.recreate_optimizers_blocking().await?;
         }
+
+        issues::publish(CollectionUpdatedEvent {
+            collection_id: operation.collection_name,
+        });
+
         Ok(true)
     }
 
@@ -3,6 +3,7 @@ use std::num::NonZeroU32;
 
 use collection::collection::Collection;
 use collection::config::{self, CollectionConfig, CollectionParams, ShardingMethod};
+use collection::events::CollectionCreatedEvent;
 use collection::operations::config_diff::DiffConfig as _;
 use collection::operations::types::{
     check_sparse_compatible, CollectionResult, SparseVectorParams, VectorsConfig,
@@ -11,6 +12,7 @@ use collection::shards::collection_shard_distribution::CollectionShardDistributi
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::CollectionId;
+use segment::types::SparseVectorStorageType;
 
 use super::TableOfContent;
 use crate::content_manager::collection_meta_ops::*;
@@ -43,7 +45,7 @@ impl TableOfContent {
             write_consistency_factor,
             init_from,
             quantization_config,
-            sparse_vectors,
+            mut sparse_vectors,
         } = operation;
 
         self.collections
@@ -131,6 +133,14 @@ impl TableOfContent {
             };
         }
 
+        // New collections store sparse vectors in mmap, unless configured otherwise.
+        // Collections created before keep their RocksDB storage, as their config has no storage type.
+        for sparse_vector_params in sparse_vectors.iter_mut().flat_map(BTreeMap::values_mut) {
+            sparse_vector_params
+                .storage_type
+                .get_or_insert(SparseVectorStorageType::Mmap);
+        }
+
         let collection_params = CollectionParams {
             vectors,
             sparse_vectors,
@@ -186,6 +196,7 @@ impl TableOfContent {
             optimizer_config: optimizers_config,
             hnsw_config,
             quantization_config,
+            strict_mode_config: None,
         };
         let collection = Collection::new(
             collection_name.to_string(),
@@ -235,6 +246,10 @@ impl TableOfContent {
                 .await?;
         }
 
+        issues::publish(CollectionCreatedEvent {
+            collection_id: collection_name.to_string(),
+        });
+
         if let Some(init_from) = init_from {
             self.run_data_initialization(init_from.collection, collection_name.to_string())
                 .await;
@@ -22,6 +22,7 @@ use api::grpc::qdrant::qdrant_internal_client::QdrantInternalClient;
 use api::grpc::qdrant::WaitOnConsensusCommitRequest;
 use api::grpc::transport_channel_pool::AddTimeout;
 use collection::collection::{Collection, RequestShardTransfer};
+use collection::common::snapshots_manager::{SnapshotStorageManager, SnapshotsStorageConfig};
 use collection::config::{default_replication_factor, CollectionConfig};
 use collection::operations::types::*;
 use collection::shards::channel_service::ChannelService;
@@ -45,6 +46,7 @@ use crate::content_manager::collections_ops::{Checker, Collections};
 use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::errors::StorageError;
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
+use crate::content_manager::snapshots::schedule::SnapshotScheduler;
 use crate::rbac::{Access, AccessRequirements, CollectionPass};
 use crate::types::{PeerAddressById, StorageConfig};
 use crate::ConsensusOperations;
@@ -83,6 +85,8 @@ pub struct TableOfContent {
     collection_create_lock: Mutex<()>,
     /// Dispatcher for shard transfer to access consensus.
     shard_transfer_dispatcher: parking_lot::Mutex<Option<ShardTransferDispatcher>>,
+    /// Schedules of automatic snapshots and their status.
+    snapshot_scheduler: SnapshotScheduler,
 }
 
 impl TableOfContent {
@@ -166,6 +170,16 @@ impl TableOfContent {
 
             collections.insert(collection_name, collection);
         }
+
+        if storage_config.snapshots_config.snapshots_storage == SnapshotsStorageConfig::Mirror {
+            let _runtime = general_runtime.enter();
+            let resumed = SnapshotStorageManager::new(storage_config.snapshots_config.clone())
+                .and_then(|manager| manager.resume_mirror_replication(&snapshots_path));
+            if let Err(err) = resumed {
+                log::error!("Failed to resume replication of snapshots to mirror: {err}");
+            }
+        }
+
         let alias_path = Path::new(&storage_config.storage_path).join(ALIASES_PATH);
         let alias_persistence =
             AliasPersistence::open(alias_path).expect("Can't open database by the provided config");
@@ -203,6 +217,11 @@ impl TableOfContent {
             update_rate_limiter: rate_limiter,
             collection_create_lock: Default::default(),
             shard_transfer_dispatcher: Default::default(),
+            snapshot_scheduler: SnapshotScheduler::new(
+                storage_config.snapshot_schedule.clone(),
+                &snapshots_path,
+            )
+            .expect("Can't load snapshot scheduler"),
         }
     }
 
@@ -1,5 +1,8 @@
 use std::time::Duration;
 
+use collection::collection::distance_matrix::{
+    CollectionSearchMatrixRequest, CollectionSearchMatrixResponse,
+};
 use collection::collection::Collection;
 use collection::grouping::group_by::GroupRequest;
 use collection::grouping::GroupBy;
@@ -12,6 +15,7 @@ use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
 use collection::{discovery, recommendations};
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt as _;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::types::{ScoredPoint, ShardKey};
 
 use super::TableOfContent;
@@ -41,6 +45,7 @@ impl TableOfContent {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
         recommendations::recommend_by(
             request,
             &collection,
@@ -80,6 +85,9 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
+        for (request, _shard_selector) in &requests {
+            collection.check_strict_mode(request).await?;
+        }
         recommendations::recommend_batch_by(
             requests,
             &collection,
@@ -123,6 +131,9 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
+        for search in &request.searches {
+            collection.check_strict_mode(search).await?;
+        }
         collection
             .core_search_batch(request, read_consistency, shard_selection, timeout)
             .await
@@ -158,6 +169,69 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Count the points per value of a payload field
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we count
+    /// * `request` - [`FacetParams`]
+    /// * `shard_selection` - which local shard to use
+    /// * `timeout` - timeout for the operation
+    ///
+    /// # Result
+    ///
+    /// The most frequent values of the field, along with their counts.
+    ///
+    pub async fn facet(
+        &self,
+        collection_name: &str,
+        mut request: FacetParams,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> Result<FacetResponse, StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .facet(request, shard_selection, read_consistency, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Compute the distances between randomly sampled points
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we sample points
+    /// * `request` - [`CollectionSearchMatrixRequest`]
+    /// * `shard_selection` - which local shard to use
+    /// * `timeout` - timeout for the operation
+    ///
+    /// # Result
+    ///
+    /// Sampled points, along with their nearest neighbours among the sample.
+    ///
+    pub async fn search_points_matrix(
+        &self,
+        collection_name: &str,
+        mut request: CollectionSearchMatrixRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> Result<CollectionSearchMatrixResponse, StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
+        collection
+            .search_points_matrix(request, shard_selection, read_consistency, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Return specific points by IDs
     ///
     /// # Arguments
@@ -198,6 +272,7 @@ impl TableOfContent {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
 
         let collection_by_name = |name| self.get_collection_opt(name);
 
@@ -225,6 +300,7 @@ impl TableOfContent {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
         discovery::discover(
             request,
             &collection,
@@ -254,6 +330,9 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
+        for (request, _shard_selector) in &requests {
+            collection.check_strict_mode(request).await?;
+        }
 
         discovery::discover_batch(
             requests,
@@ -301,13 +380,42 @@ impl TableOfContent {
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         access: Access,
+        timeout: Option<Duration>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
+
+        collection
+            .query(request, read_consistency, &shard_selection, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    pub async fn query_batch(
+        &self,
+        collection_name: &str,
+        mut requests: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        let mut collection_pass = None;
+        for (request, _shard_selector) in &mut requests {
+            collection_pass = Some(access.check_point_op(collection_name, request)?);
+        }
+        let Some(collection_pass) = collection_pass else {
+            return Ok(vec![]);
+        };
+
+        let collection = self.get_collection(&collection_pass).await?;
+        for (request, _shard_selector) in &requests {
+            collection.check_strict_mode(request).await?;
+        }
 
         collection
-            .query(request, read_consistency, &shard_selection)
+            .query_batch(requests, read_consistency, timeout)
             .await
             .map_err(|err| err.into())
     }
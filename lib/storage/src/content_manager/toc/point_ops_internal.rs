@@ -1,5 +1,7 @@
 //! Methods here are for distributed internal use only.
 
+use std::time::Duration;
+
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::ShardQueryRequest;
 use segment::types::ScoredPoint;
@@ -11,17 +13,15 @@ impl TableOfContent {
     pub async fn query_internal(
         &self,
         collection_name: &str,
-        _request: ShardQueryRequest,
-        _shard_selection: ShardSelectorInternal, // TODO(universal-query): pass this to collection
+        request: ShardQueryRequest,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
-        let _collection = self.get_collection_unchecked(collection_name).await?;
-
-        // TODO(universal-query): implement query_internal in collection
-        // collection
-        //     .query(request, read_consistency, &shard_selection)
-        //     .await
-        //     .map_err(|err| err.into())
+        let collection = self.get_collection_unchecked(collection_name).await?;
 
-        todo!()
+        collection
+            .query_internal(request, None, &shard_selection, timeout)
+            .await
+            .map_err(|err| err.into())
     }
 }
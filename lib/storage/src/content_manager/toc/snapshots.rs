@@ -10,6 +10,7 @@ use super::TableOfContent;
 use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::consensus_ops::ConsensusOperations;
 use crate::content_manager::errors::StorageError;
+use crate::content_manager::snapshots::schedule::SnapshotScheduler;
 use crate::rbac::CollectionPass;
 
 impl TableOfContent {
@@ -21,6 +22,10 @@ impl TableOfContent {
         })
     }
 
+    pub fn snapshot_scheduler(&self) -> &SnapshotScheduler {
+        &self.snapshot_scheduler
+    }
+
     pub fn snapshots_path(&self) -> &str {
         &self.storage_config.snapshots_path
     }
@@ -65,6 +70,30 @@ impl TableOfContent {
             .await?)
     }
 
+    pub async fn create_incremental_snapshot<'a>(
+        &self,
+        collection: &CollectionPass<'a>,
+        base: &str,
+    ) -> Result<SnapshotDescription, StorageError> {
+        let collection = self.get_collection(collection).await?;
+        let temp_dir = self.optional_temp_or_storage_temp_path()?;
+        Ok(collection
+            .create_incremental_snapshot(&temp_dir, self.this_peer_id, base)
+            .await?)
+    }
+
+    pub async fn compact_snapshot<'a>(
+        &self,
+        collection: &CollectionPass<'a>,
+        snapshot_name: &str,
+    ) -> Result<SnapshotDescription, StorageError> {
+        let collection = self.get_collection(collection).await?;
+        let temp_dir = self.optional_temp_or_storage_temp_path()?;
+        Ok(collection
+            .compact_snapshot(snapshot_name, &temp_dir)
+            .await?)
+    }
+
     pub fn send_set_replica_state_proposal(
         &self,
         collection_name: String,
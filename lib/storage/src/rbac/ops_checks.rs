@@ -2,6 +2,7 @@ use std::borrow::Cow;
 use std::collections::HashSet;
 use std::mem::take;
 
+use collection::collection::distance_matrix::CollectionSearchMatrixRequest;
 use collection::grouping::group_by::{GroupRequest, SourceRequest};
 use collection::lookup::WithLookup;
 use collection::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
@@ -16,6 +17,7 @@ use collection::operations::universal_query::collection_query::{
 };
 use collection::operations::vector_ops::VectorOperations;
 use collection::operations::CollectionUpdateOperations;
+use segment::data_types::facets::FacetParams;
 use segment::types::{Condition, ExtendedPointId, FieldCondition, Filter, Match, Payload};
 
 use super::{
@@ -153,7 +155,7 @@ impl<'a> CollectionAccessView<'a> {
 
     fn check_vector_input(&self, vector_input: &VectorInput) -> Result<(), StorageError> {
         match vector_input {
-            VectorInput::Vector(_) => Ok(()),
+            VectorInput::Vector(_) | VectorInput::Document(_) => Ok(()),
             VectorInput::Id(_) => self.check_whole_access(),
         }
     }
@@ -241,6 +243,44 @@ impl CheckableCollectionOperation for CountRequestInternal {
     }
 }
 
+impl CheckableCollectionOperation for FacetParams {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
+impl CheckableCollectionOperation for CollectionSearchMatrixRequest {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
 impl CheckableCollectionOperation for GroupRequest {
     fn access_requirements(&self) -> AccessRequirements {
         AccessRequirements {
@@ -260,6 +300,7 @@ impl CheckableCollectionOperation for GroupRequest {
                 view.apply_filter(&mut s.filter);
             }
             SourceRequest::Recommend(r) => r.check_access(view, access)?,
+            SourceRequest::Query(q) => q.check_access(view, access)?,
         }
         access.check_with_lookup(&self.with_lookup)?;
         Ok(())
@@ -860,6 +901,66 @@ mod tests_ops {
         );
     }
 
+    #[test]
+    fn test_facet_params() {
+        let op = FacetParams {
+            key: "path".parse().unwrap(),
+            limit: 10,
+            filter: None,
+            exact: false,
+        };
+
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Read));
+
+        assert_allowed(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, true)
+                .into(),
+        );
+
+        assert_allowed_rewrite(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .into(),
+            |op| {
+                op.filter = Some(PayloadConstraint::new_test("col").to_filter());
+            },
+        );
+    }
+
+    #[test]
+    fn test_search_matrix_request() {
+        let op = CollectionSearchMatrixRequest {
+            sample_size: 100,
+            limit_per_sample: 10,
+            filter: None,
+            using: "vector".to_string(),
+        };
+
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Read));
+
+        assert_allowed(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, true)
+                .into(),
+        );
+
+        assert_allowed_rewrite(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .into(),
+            |op| {
+                op.filter = Some(PayloadConstraint::new_test("col").to_filter());
+            },
+        );
+    }
+
     #[test]
     fn test_group_request_source() {
         let op = GroupRequest {
@@ -930,7 +1031,7 @@ mod tests_ops {
                 SourceRequest::Search(s) => {
                     s.filter = Some(PayloadConstraint::new_test("col").to_filter());
                 }
-                SourceRequest::Recommend(_) => unreachable!(),
+                SourceRequest::Recommend(_) | SourceRequest::Query(_) => unreachable!(),
             },
         );
     }
@@ -21,6 +21,8 @@ use serde::{Deserialize, Serialize};
 use tonic::transport::Uri;
 use validator::Validate;
 
+use crate::content_manager::snapshots::schedule::SnapshotScheduleConfig;
+
 pub type PeerAddressById = HashMap<PeerId, Uri>;
 pub type PeerMetadataById = HashMap<PeerId, PeerMetadata>;
 
@@ -59,6 +61,10 @@ pub struct StorageConfig {
     pub snapshots_path: String,
     #[serde(default)]
     pub snapshots_config: SnapShotsConfig,
+    /// Schedules of automatic snapshots
+    #[validate]
+    #[serde(default)]
+    pub snapshot_schedule: SnapshotScheduleConfig,
     #[validate(length(min = 1))]
     #[serde(default)]
     pub temp_path: Option<String>,
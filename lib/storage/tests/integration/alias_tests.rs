@@ -34,6 +34,7 @@ fn test_alias_operation() {
             .unwrap()
             .to_string(),
         snapshots_config: Default::default(),
+        snapshot_schedule: Default::default(),
         temp_path: None,
         on_disk_payload: false,
         optimizers: OptimizersConfig {
@@ -45,6 +46,7 @@ fn test_alias_operation() {
             indexing_threshold: Some(100),
             flush_interval_sec: 2,
             max_optimization_threads: Some(2),
+            graph_healing_threshold: None,
         },
         optimizers_overwrite: None,
         wal: Default::default(),
@@ -0,0 +1,52 @@
+use actix_web::{delete, post, web, Responder};
+use actix_web_validator as valid;
+use collection::operations::change_ops::ChangesRequest;
+use collection::shards::shard::ShardId;
+use storage::dispatcher::Dispatcher;
+
+use crate::actix::auth::ActixAccess;
+use crate::actix::helpers;
+use crate::common;
+
+#[post("/collections/{collection}/shards/{shard}/changes")]
+async fn read_shard_changes(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<(String, ShardId)>,
+    request: valid::Json<ChangesRequest>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let (collection, shard) = path.into_inner();
+    let future = common::changes::read_shard_changes(
+        dispatcher.toc(&access).clone(),
+        access,
+        collection,
+        shard,
+        request.into_inner(),
+    );
+
+    helpers::time(future).await
+}
+
+#[delete("/collections/{collection}/shards/{shard}/changes/consumers/{consumer}")]
+async fn delete_change_consumer(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<(String, ShardId, String)>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let (collection, shard, consumer) = path.into_inner();
+    let future = common::changes::delete_change_consumer(
+        dispatcher.toc(&access).clone(),
+        access,
+        collection,
+        shard,
+        consumer,
+    );
+
+    helpers::time(future).await
+}
+
+// Configure services
+pub fn config_changes_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(read_shard_changes)
+        .service(delete_change_consumer);
+}
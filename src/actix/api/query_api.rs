@@ -1,7 +1,8 @@
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
-use api::rest::QueryRequest;
+use api::rest::{QueryRequest, QueryRequestBatch};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::QueryGroupsRequest;
 use itertools::Itertools;
 use storage::dispatcher::Dispatcher;
 
@@ -37,7 +38,7 @@ async fn query_points(
                 params.consistency,
                 shard_selection,
                 access,
-                // TODO(universal-query): add params.timeout()
+                params.timeout(),
             )
             .await
             .map(|scored_points| {
@@ -50,6 +51,95 @@ async fn query_points(
     .await
 }
 
+#[post("/collections/{name}/points/query/batch")]
+async fn query_points_batch(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<QueryRequestBatch>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(async move {
+        let QueryRequestBatch { searches } = request.into_inner();
+
+        let batch = searches
+            .into_iter()
+            .map(|request| {
+                let QueryRequest {
+                    internal,
+                    shard_key,
+                } = request;
+
+                let shard_selection = match shard_key {
+                    None => ShardSelectorInternal::All,
+                    Some(shard_keys) => shard_keys.into(),
+                };
+
+                (internal.into(), shard_selection)
+            })
+            .collect();
+
+        dispatcher
+            .toc(&access)
+            .query_batch(
+                &collection.name,
+                batch,
+                params.consistency,
+                access,
+                params.timeout(),
+            )
+            .await
+            .map(|batch_scored_points| {
+                batch_scored_points
+                    .into_iter()
+                    .map(|scored_points| {
+                        scored_points
+                            .into_iter()
+                            .map(api::rest::ScoredPoint::from)
+                            .collect_vec()
+                    })
+                    .collect_vec()
+            })
+    })
+    .await
+}
+
+#[post("/collections/{name}/points/query/groups")]
+async fn query_points_groups(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<QueryGroupsRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(async move {
+        let QueryGroupsRequest {
+            query_group_request,
+            shard_key,
+        } = request.into_inner();
+
+        let shard_selection = match shard_key {
+            None => ShardSelectorInternal::All,
+            Some(shard_keys) => shard_keys.into(),
+        };
+
+        dispatcher
+            .toc(&access)
+            .group(
+                &collection.name,
+                query_group_request.into(),
+                params.consistency,
+                shard_selection,
+                access,
+                params.timeout(),
+            )
+            .await
+    })
+    .await
+}
+
 pub fn config_query_api(cfg: &mut web::ServiceConfig) {
-    cfg.service(query_points);
+    cfg.service(query_points)
+        .service(query_points_batch)
+        .service(query_points_groups);
 }
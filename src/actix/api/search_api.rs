@@ -1,6 +1,7 @@
 use actix_web::rt::time::Instant;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use api::rest::{SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixRequest};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CoreSearchRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
@@ -143,9 +144,83 @@ async fn search_point_groups(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/points/search/matrix/pairs")]
+async fn search_points_matrix_pairs(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchMatrixRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let SearchMatrixRequest {
+        search_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let response = dispatcher
+        .toc(&access)
+        .search_points_matrix(
+            &collection.name,
+            search_request.into(),
+            params.consistency,
+            shard_selection,
+            access,
+            params.timeout(),
+        )
+        .await
+        .map(SearchMatrixPairsResponse::from);
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/search/matrix/offsets")]
+async fn search_points_matrix_offsets(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchMatrixRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let SearchMatrixRequest {
+        search_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let response = dispatcher
+        .toc(&access)
+        .search_points_matrix(
+            &collection.name,
+            search_request.into(),
+            params.consistency,
+            shard_selection,
+            access,
+            params.timeout(),
+        )
+        .await
+        .map(SearchMatrixOffsetsResponse::from);
+
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_search_api(cfg: &mut web::ServiceConfig) {
     cfg.service(search_points)
         .service(batch_search_points)
-        .service(search_point_groups);
+        .service(search_point_groups)
+        .service(search_points_matrix_pairs)
+        .service(search_points_matrix_offsets);
 }
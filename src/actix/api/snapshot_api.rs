@@ -58,6 +58,21 @@ pub struct SnapshottingParam {
     pub wait: Option<bool>,
 }
 
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct DeleteSnapshotParam {
+    pub wait: Option<bool>,
+    /// Delete the snapshot even if incremental snapshots are based on it
+    pub force: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct CreateSnapshotParam {
+    pub wait: Option<bool>,
+    /// Snapshot to create an incremental snapshot from
+    #[validate(length(min = 1))]
+    pub base: Option<String>,
+}
+
 #[derive(MultipartForm)]
 pub struct SnapshottingForm {
     snapshot: TempFile,
@@ -162,12 +177,18 @@ async fn list_snapshots(
 async fn create_snapshot(
     dispatcher: web::Data<Dispatcher>,
     path: web::Path<String>,
-    params: valid::Query<SnapshottingParam>,
+    params: valid::Query<CreateSnapshotParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let collection_name = path.into_inner();
-    helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
-        do_create_snapshot(dispatcher.toc(&access).clone(), access, &collection_name)
+    let CreateSnapshotParam { wait, base } = params.into_inner();
+    helpers::time_or_accept_with_handle(wait.unwrap_or(true), async move {
+        do_create_snapshot(
+            dispatcher.toc(&access).clone(),
+            access,
+            &collection_name,
+            base,
+        )
     })
     .await
 }
@@ -258,6 +279,25 @@ async fn get_snapshot(
     .await
 }
 
+#[post("/collections/{name}/snapshots/{snapshot_name}/compact")]
+async fn compact_snapshot(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<(String, String)>,
+    params: valid::Query<SnapshottingParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let (collection_name, snapshot_name) = path.into_inner();
+    helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
+        do_compact_snapshot(
+            dispatcher.toc(&access).clone(),
+            access,
+            &collection_name,
+            snapshot_name,
+        )
+    })
+    .await
+}
+
 #[get("/snapshots")]
 async fn list_full_snapshots(
     dispatcher: web::Data<Dispatcher>,
@@ -309,9 +349,10 @@ async fn delete_full_snapshot(
 async fn delete_collection_snapshot(
     dispatcher: web::Data<Dispatcher>,
     path: web::Path<(String, String)>,
-    params: valid::Query<SnapshottingParam>,
+    params: valid::Query<DeleteSnapshotParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
+    let force = params.force.unwrap_or(false);
     helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
         let (collection_name, snapshot_name) = path.into_inner();
         do_delete_collection_snapshot(
@@ -319,6 +360,7 @@ async fn delete_collection_snapshot(
             access,
             &collection_name,
             &snapshot_name,
+            force,
         )
         .await
     })
@@ -512,6 +554,7 @@ pub fn config_snapshots_api(cfg: &mut web::ServiceConfig) {
         .service(upload_snapshot)
         .service(recover_from_snapshot)
         .service(get_snapshot)
+        .service(compact_snapshot)
         .service(list_full_snapshots)
         .service(create_full_snapshot)
         .service(get_full_snapshot)
@@ -21,11 +21,13 @@ use collection::operations::validation;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
 
+use crate::actix::api::changes_api::config_changes_api;
 use crate::actix::api::cluster_api::config_cluster_api;
 use crate::actix::api::collections_api::config_collections_api;
 use crate::actix::api::count_api::count_points;
 use crate::actix::api::debug_api::config_debugger_api;
 use crate::actix::api::discovery_api::config_discovery_api;
+use crate::actix::api::facet_api::config_facet_api;
 use crate::actix::api::issues_api::config_issues_api;
 use crate::actix::api::query_api::config_query_api;
 use crate::actix::api::recommend_api::config_recommend_api;
@@ -171,7 +173,9 @@ pub fn init(
                 .configure(config_recommend_api)
                 .configure(config_discovery_api)
                 .configure(config_query_api)
+                .configure(config_facet_api)
                 .configure(config_shards_api)
+                .configure(config_changes_api)
                 .configure(config_issues_api)
                 .configure(config_debugger_api)
                 // Ordering of services is important for correct path pattern matching
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::operations::change_ops::{ChangeRecord, ChangesRequest, ChangesResult};
+use collection::shards::shard::ShardId;
+use futures::{stream, Stream, TryStreamExt as _};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::{Access, AccessRequirements};
+use tokio::time::{timeout_at, Instant};
+
+/// Delivered changes are acknowledged for the consumer of a stream at most once per this interval
+const STREAM_ACKNOWLEDGE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Read changes of a local shard, waiting up to `request.timeout` seconds if there are none yet.
+///
+/// # Cancel safety
+///
+/// This function is cancel safe.
+pub async fn read_shard_changes(
+    toc: Arc<TableOfContent>,
+    access: Access,
+    collection_name: String,
+    shard_id: ShardId,
+    request: ChangesRequest,
+) -> Result<ChangesResult, StorageError> {
+    let deadline = Instant::now() + Duration::from_secs(request.timeout.unwrap_or(0));
+    read_changes_until(
+        &toc,
+        &access,
+        &collection_name,
+        shard_id,
+        &request,
+        Some(deadline),
+    )
+    .await
+}
+
+/// Stream changes of a local shard, until there are no new changes for `idle_timeout`.
+///
+/// A consumer in the request acknowledges the cursor the stream starts from, and then the last
+/// delivered change, at most once per [`STREAM_ACKNOWLEDGE_INTERVAL`]. Changes of the last batch,
+/// which were sent but might not be processed yet, are not acknowledged, so that they are not
+/// truncated from the WAL.
+pub fn stream_shard_changes(
+    toc: Arc<TableOfContent>,
+    access: Access,
+    collection_name: String,
+    shard_id: ShardId,
+    request: ChangesRequest,
+    idle_timeout: Option<Duration>,
+) -> Result<impl Stream<Item = Result<ChangeRecord, StorageError>>, StorageError> {
+    // Fail early, instead of on the first element of the stream
+    access.check_collection_access(&collection_name, changes_access_requirements(&request))?;
+
+    let consumer = request.consumer.clone();
+    let mut acknowledged_at: Option<Instant> = None;
+
+    let changes = stream::try_unfold(request, move |mut request| {
+        let toc = toc.clone();
+        let access = access.clone();
+        let collection_name = collection_name.clone();
+
+        // Cursor of the request is the last change delivered from the previous batch, which is
+        // only requested once the whole previous batch is consumed
+        let acknowledge =
+            !acknowledged_at.is_some_and(|instant| instant.elapsed() < STREAM_ACKNOWLEDGE_INTERVAL);
+        if acknowledge {
+            request.consumer.clone_from(&consumer);
+            acknowledged_at = consumer.as_ref().map(|_| Instant::now());
+        } else {
+            request.consumer = None;
+        }
+
+        async move {
+            let deadline = idle_timeout.map(|timeout| Instant::now() + timeout);
+            let changes = read_changes_until(
+                &toc,
+                &access,
+                &collection_name,
+                shard_id,
+                &request,
+                deadline,
+            )
+            .await?;
+            if changes.changes.is_empty() {
+                return Ok(None);
+            }
+            request.cursor = changes.next_cursor;
+            Ok(Some((changes.changes, request)))
+        }
+    });
+
+    Ok(changes
+        .map_ok(|changes| stream::iter(changes.into_iter().map(Ok)))
+        .try_flatten())
+}
+
+/// Unregister a change consumer of a local shard.
+///
+/// # Cancel safety
+///
+/// This function is cancel safe.
+pub async fn delete_change_consumer(
+    toc: Arc<TableOfContent>,
+    access: Access,
+    collection_name: String,
+    shard_id: ShardId,
+    consumer: String,
+) -> Result<bool, StorageError> {
+    let collection_pass = access
+        .check_collection_access(&collection_name, AccessRequirements::new().write().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    let removed = collection
+        .remove_shard_change_consumer(shard_id, &consumer)
+        .await?;
+    Ok(removed)
+}
+
+/// Changes expose whole points, and registering a consumer makes the shard keep its WAL
+fn changes_access_requirements(request: &ChangesRequest) -> AccessRequirements {
+    if request.consumer.is_some() {
+        AccessRequirements::new().write().whole()
+    } else {
+        AccessRequirements::new().whole()
+    }
+}
+
+/// Read changes, waiting for new ones until `deadline` if there are none yet
+async fn read_changes_until(
+    toc: &TableOfContent,
+    access: &Access,
+    collection_name: &str,
+    shard_id: ShardId,
+    request: &ChangesRequest,
+    deadline: Option<Instant>,
+) -> Result<ChangesResult, StorageError> {
+    let collection_pass =
+        access.check_collection_access(collection_name, changes_access_requirements(request))?;
+
+    loop {
+        // Collection is not held while waiting for new changes
+        let (changes, mut writes) = toc
+            .get_collection(&collection_pass)
+            .await?
+            .read_shard_changes(shard_id, request)
+            .await?;
+
+        if !changes.changes.is_empty() || request.limit == Some(0) {
+            return Ok(changes);
+        }
+
+        let changed = match deadline {
+            Some(deadline) => timeout_at(deadline, writes.changed()).await.ok(),
+            None => Some(writes.changed().await),
+        };
+
+        match changed {
+            Some(Ok(())) => {}
+            // Timed out, or the shard is gone and nothing will be written to it anymore
+            Some(Err(_)) | None => return Ok(changes),
+        }
+    }
+}
@@ -64,7 +64,11 @@ pub async fn do_get_collection(
         Some(shard_id) => ShardSelectorInternal::ShardId(shard_id),
     };
 
-    Ok(collection.info(&shard_selection).await?)
+    let mut info = collection.info(&shard_selection).await?;
+    info.snapshot_schedule = toc
+        .snapshot_scheduler()
+        .collection_status(&collection.name());
+    Ok(info)
 }
 
 pub async fn do_list_collections(
@@ -163,16 +167,38 @@ pub async fn do_list_snapshots(
         .await?)
 }
 
+/// Create a full collection snapshot, or an incremental one if `base` snapshot is given
 pub fn do_create_snapshot(
     toc: Arc<TableOfContent>,
     access: Access,
     collection_name: &str,
+    base: Option<String>,
 ) -> Result<JoinHandle<Result<SnapshotDescription, StorageError>>, StorageError> {
     let collection_pass = access
         .check_collection_access(collection_name, AccessRequirements::new().write().whole())?
         .into_static();
     Ok(tokio::spawn(async move {
-        toc.create_snapshot(&collection_pass).await
+        match base {
+            Some(base) => {
+                toc.create_incremental_snapshot(&collection_pass, &base)
+                    .await
+            }
+            None => toc.create_snapshot(&collection_pass).await,
+        }
+    }))
+}
+
+pub fn do_compact_snapshot(
+    toc: Arc<TableOfContent>,
+    access: Access,
+    collection_name: &str,
+    snapshot_name: String,
+) -> Result<JoinHandle<Result<SnapshotDescription, StorageError>>, StorageError> {
+    let collection_pass = access
+        .check_collection_access(collection_name, AccessRequirements::new().write().whole())?
+        .into_static();
+    Ok(tokio::spawn(async move {
+        toc.compact_snapshot(&collection_pass, &snapshot_name).await
     }))
 }
 
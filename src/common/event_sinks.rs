@@ -0,0 +1,453 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use collection::events::{
+    CollectionCreatedEvent, CollectionDeletedEvent, CollectionUpdatedEvent, IndexCreatedEvent,
+    OptimizationFinishedEvent, OptimizationStartedEvent, ReplicaStateChangedEvent,
+    ShardTransferAbortedEvent, ShardTransferFinishedEvent, ShardTransferStartedEvent,
+    SlowQueryEvent,
+};
+use collection::shards::shard::PeerId;
+use issues::broker::Subscriber;
+use parking_lot::Mutex;
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt as _;
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+use validator::Validate;
+
+use crate::common::telemetry_ops::events_telemetry::EventSinkTelemetry;
+
+const DEFAULT_QUEUE_SIZE: usize = 1024;
+const DEFAULT_WEBHOOK_TIMEOUT_MS: u64 = 10_000;
+const DEFAULT_WEBHOOK_MAX_RETRIES: usize = 3;
+const DEFAULT_WEBHOOK_RETRY_DELAY_MS: u64 = 1_000;
+
+#[derive(Debug, Deserialize, Clone, Validate)]
+pub struct EventsConfig {
+    /// Max number of events waiting for delivery in each sink.
+    /// New events are dropped while the queue is full.
+    #[serde(default = "default_queue_size")]
+    #[validate(range(min = 1))]
+    pub queue_size: usize,
+    /// Sinks to deliver events to, no events are delivered if empty.
+    #[serde(default)]
+    pub sinks: Vec<EventSinkConfig>,
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            queue_size: default_queue_size(),
+            sinks: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct EventSinkConfig {
+    /// Name of the sink in telemetry, defaults to the type of the sink and its position
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Events to deliver to this sink, all events are delivered if not set
+    #[serde(default)]
+    pub events: Option<HashSet<EventKind>>,
+    #[serde(flatten)]
+    pub sink: EventSinkType,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventSinkType {
+    /// POST every event as JSON to the URL
+    Webhook(WebhookSinkConfig),
+    /// Append every event as a JSON line to the file
+    File(FileSinkConfig),
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WebhookSinkConfig {
+    pub url: String,
+    /// Additional headers to send with every request, e.g. for authorization
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default = "default_webhook_timeout_ms")]
+    pub timeout_ms: u64,
+    /// How many times to retry a failed delivery, before giving up on the event
+    #[serde(default = "default_webhook_max_retries")]
+    pub max_retries: usize,
+    /// Delay before the first retry, it is doubled for every next retry
+    #[serde(default = "default_webhook_retry_delay_ms")]
+    pub retry_delay_ms: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FileSinkConfig {
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    CollectionCreated,
+    CollectionUpdated,
+    CollectionDeleted,
+    IndexCreated,
+    SlowQuery,
+    ShardTransferStarted,
+    ShardTransferFinished,
+    ShardTransferAborted,
+    ReplicaStateChanged,
+    OptimizationStarted,
+    OptimizationFinished,
+}
+
+/// Event, as it is delivered to the sinks
+#[derive(Serialize)]
+struct EventRecord<'a, E> {
+    event: EventKind,
+    peer_id: PeerId,
+    timestamp: DateTime<Utc>,
+    data: &'a E,
+}
+
+/// Sinks, which deliver published events in background
+pub struct EventSinks {
+    sinks: Vec<EventSink>,
+}
+
+struct EventSink {
+    name: String,
+    events: Option<HashSet<EventKind>>,
+    sender: mpsc::Sender<Arc<str>>,
+    stats: Arc<EventSinkStats>,
+}
+
+#[derive(Default)]
+struct EventSinkStats {
+    delivered: AtomicUsize,
+    failed: AtomicUsize,
+    dropped: AtomicUsize,
+    retries: AtomicUsize,
+    last_error: Mutex<Option<String>>,
+}
+
+impl EventSinkStats {
+    fn record_failure(&self, error: String) {
+        self.failed.fetch_add(1, Ordering::Relaxed);
+        *self.last_error.lock() = Some(error);
+    }
+}
+
+impl EventSinks {
+    /// Spawn delivery workers of all configured sinks on the given runtime
+    pub fn spawn(config: &EventsConfig, runtime: &Handle) -> Self {
+        let sinks = config
+            .sinks
+            .iter()
+            .enumerate()
+            .map(|(index, sink_config)| {
+                let (sender, receiver) = mpsc::channel(config.queue_size);
+                let stats = Arc::new(EventSinkStats::default());
+
+                let name = match &sink_config.sink {
+                    EventSinkType::Webhook(webhook) => {
+                        runtime.spawn(run_webhook_sink(webhook.clone(), receiver, stats.clone()));
+                        "webhook"
+                    }
+                    EventSinkType::File(file) => {
+                        runtime.spawn(run_file_sink(file.path.clone(), receiver, stats.clone()));
+                        "file"
+                    }
+                };
+
+                EventSink {
+                    name: sink_config
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| format!("{name}-{index}")),
+                    events: sink_config.events.clone(),
+                    sender,
+                    stats,
+                }
+            })
+            .collect();
+
+        Self { sinks }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    pub fn telemetry(&self) -> Vec<EventSinkTelemetry> {
+        self.sinks
+            .iter()
+            .map(|sink| EventSinkTelemetry {
+                name: sink.name.clone(),
+                delivered: sink.stats.delivered.load(Ordering::Relaxed),
+                failed: sink.stats.failed.load(Ordering::Relaxed),
+                dropped: sink.stats.dropped.load(Ordering::Relaxed),
+                retries: sink.stats.retries.load(Ordering::Relaxed),
+                last_error: sink.stats.last_error.lock().clone(),
+            })
+            .collect()
+    }
+
+    fn accepts(&self, kind: EventKind) -> bool {
+        self.sinks.iter().any(|sink| sink.accepts(kind))
+    }
+
+    /// Queue serialized event for delivery, never blocks
+    fn send(&self, kind: EventKind, record: Arc<str>) {
+        for sink in self.sinks.iter().filter(|sink| sink.accepts(kind)) {
+            if sink.sender.try_send(record.clone()).is_err() {
+                sink.stats.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl EventSink {
+    fn accepts(&self, kind: EventKind) -> bool {
+        self.events
+            .as_ref()
+            .map_or(true, |events| events.contains(&kind))
+    }
+}
+
+/// Subscribes the event sinks to all events they accept
+pub fn setup_event_sinks(
+    config: &EventsConfig,
+    peer_id: PeerId,
+    runtime: &Handle,
+) -> Arc<EventSinks> {
+    let sinks = Arc::new(EventSinks::spawn(config, runtime));
+
+    subscribe::<CollectionCreatedEvent>(EventKind::CollectionCreated, peer_id, &sinks);
+    subscribe::<CollectionUpdatedEvent>(EventKind::CollectionUpdated, peer_id, &sinks);
+    subscribe::<CollectionDeletedEvent>(EventKind::CollectionDeleted, peer_id, &sinks);
+    subscribe::<IndexCreatedEvent>(EventKind::IndexCreated, peer_id, &sinks);
+    subscribe::<SlowQueryEvent>(EventKind::SlowQuery, peer_id, &sinks);
+    subscribe::<ShardTransferStartedEvent>(EventKind::ShardTransferStarted, peer_id, &sinks);
+    subscribe::<ShardTransferFinishedEvent>(EventKind::ShardTransferFinished, peer_id, &sinks);
+    subscribe::<ShardTransferAbortedEvent>(EventKind::ShardTransferAborted, peer_id, &sinks);
+    subscribe::<ReplicaStateChangedEvent>(EventKind::ReplicaStateChanged, peer_id, &sinks);
+    subscribe::<OptimizationStartedEvent>(EventKind::OptimizationStarted, peer_id, &sinks);
+    subscribe::<OptimizationFinishedEvent>(EventKind::OptimizationFinished, peer_id, &sinks);
+
+    sinks
+}
+
+fn subscribe<E: Serialize + 'static>(kind: EventKind, peer_id: PeerId, sinks: &Arc<EventSinks>) {
+    // Don't even serialize events, which no sink is interested in
+    if !sinks.accepts(kind) {
+        return;
+    }
+
+    issues::broker::add_subscriber::<E>(Box::new(EventSinkSubscriber {
+        kind,
+        peer_id,
+        sinks: sinks.clone(),
+    }));
+}
+
+struct EventSinkSubscriber {
+    kind: EventKind,
+    peer_id: PeerId,
+    sinks: Arc<EventSinks>,
+}
+
+impl<E: Serialize> Subscriber<E> for EventSinkSubscriber {
+    fn notify(&self, event: Arc<E>) {
+        let record = EventRecord {
+            event: self.kind,
+            peer_id: self.peer_id,
+            timestamp: Utc::now(),
+            data: event.as_ref(),
+        };
+
+        match serde_json::to_string(&record) {
+            Ok(record) => self.sinks.send(self.kind, record.into()),
+            Err(err) => log::error!("Failed to serialize {:?} event: {err}", self.kind),
+        }
+    }
+}
+
+async fn run_webhook_sink(
+    config: WebhookSinkConfig,
+    mut receiver: mpsc::Receiver<Arc<str>>,
+    stats: Arc<EventSinkStats>,
+) {
+    let client = reqwest::Client::new();
+
+    while let Some(record) = receiver.recv().await {
+        let mut retry_delay = Duration::from_millis(config.retry_delay_ms);
+        let mut retries = 0;
+
+        loop {
+            match send_webhook(&client, &config, &record).await {
+                Ok(()) => {
+                    stats.delivered.fetch_add(1, Ordering::Relaxed);
+                    break;
+                }
+                Err(err) if retries < config.max_retries => {
+                    log::debug!("Failed to deliver event to {}, retrying: {err}", config.url);
+                    stats.retries.fetch_add(1, Ordering::Relaxed);
+                    retries += 1;
+                    tokio::time::sleep(retry_delay).await;
+                    retry_delay *= 2;
+                }
+                Err(err) => {
+                    log::warn!("Failed to deliver event to {}: {err}", config.url);
+                    stats.record_failure(err.to_string());
+                    break;
+                }
+            }
+        }
+    }
+}
+
+async fn send_webhook(
+    client: &reqwest::Client,
+    config: &WebhookSinkConfig,
+    record: &str,
+) -> anyhow::Result<()> {
+    let mut request = client
+        .post(&config.url)
+        .timeout(Duration::from_millis(config.timeout_ms))
+        .header(CONTENT_TYPE, "application/json")
+        .body(record.to_string());
+    for (name, value) in &config.headers {
+        request = request.header(name, value);
+    }
+
+    let status = request.send().await?.status();
+    if !status.is_success() {
+        anyhow::bail!("webhook responded with status {status}");
+    }
+    Ok(())
+}
+
+async fn run_file_sink(
+    path: PathBuf,
+    mut receiver: mpsc::Receiver<Arc<str>>,
+    stats: Arc<EventSinkStats>,
+) {
+    // Opened lazily, and reopened after a failed write
+    let mut file = None;
+
+    while let Some(record) = receiver.recv().await {
+        match append_line(&mut file, &path, &record).await {
+            Ok(()) => {
+                stats.delivered.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(err) => {
+                log::warn!("Failed to write event to {}: {err}", path.display());
+                file = None;
+                stats.record_failure(err.to_string());
+            }
+        }
+    }
+}
+
+async fn append_line(file: &mut Option<File>, path: &Path, line: &str) -> std::io::Result<()> {
+    let file = match file {
+        Some(file) => file,
+        None => file.insert(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .await?,
+        ),
+    };
+
+    let mut buffer = Vec::with_capacity(line.len() + 1);
+    buffer.extend_from_slice(line.as_bytes());
+    buffer.push(b'\n');
+    file.write_all(&buffer).await?;
+    file.flush().await
+}
+
+const fn default_queue_size() -> usize {
+    DEFAULT_QUEUE_SIZE
+}
+
+const fn default_webhook_timeout_ms() -> u64 {
+    DEFAULT_WEBHOOK_TIMEOUT_MS
+}
+
+const fn default_webhook_max_retries() -> usize {
+    DEFAULT_WEBHOOK_MAX_RETRIES
+}
+
+const fn default_webhook_retry_delay_ms() -> u64 {
+    DEFAULT_WEBHOOK_RETRY_DELAY_MS
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_sink() {
+        let dir = Builder::new().prefix("event_sinks").tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+
+        let config = EventsConfig {
+            queue_size: 16,
+            sinks: vec![EventSinkConfig {
+                name: None,
+                events: Some(HashSet::from([EventKind::CollectionCreated])),
+                sink: EventSinkType::File(FileSinkConfig { path: path.clone() }),
+            }],
+        };
+        let sinks = EventSinks::spawn(&config, &Handle::current());
+        let subscriber = EventSinkSubscriber {
+            kind: EventKind::CollectionCreated,
+            peer_id: 1,
+            sinks: Arc::new(sinks),
+        };
+
+        for collection_id in ["first", "second"] {
+            subscriber.notify(Arc::new(CollectionCreatedEvent {
+                collection_id: collection_id.to_string(),
+            }));
+        }
+        // Not accepted by the sink
+        subscriber
+            .sinks
+            .send(EventKind::CollectionDeleted, "{}".into());
+
+        let telemetry = loop {
+            let telemetry = subscriber.sinks.telemetry();
+            if telemetry[0].delivered == 2 {
+                break telemetry;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        assert_eq!(telemetry[0].name, "file-0");
+        assert_eq!(telemetry[0].failed, 0);
+        assert_eq!(telemetry[0].dropped, 0);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let records: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["event"], "collection_created");
+        assert_eq!(records[0]["peer_id"], 1);
+        assert_eq!(records[0]["data"]["collection_id"], "first");
+        assert_eq!(records[1]["data"]["collection_id"], "second");
+    }
+}
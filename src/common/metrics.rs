@@ -24,6 +24,8 @@ const REST_ENDPOINT_WHITELIST: &[&str] = &[
     "/collections/{name}/points/discover/batch",
     "/collections/{name}/points/payload",
     "/collections/{name}/points/query",
+    "/collections/{name}/points/query/batch",
+    "/collections/{name}/points/query/groups",
     "/collections/{name}/points/recommend",
     "/collections/{name}/points/recommend/batch",
     "/collections/{name}/points/search",
@@ -40,6 +42,8 @@ const GRPC_ENDPOINT_WHITELIST: &[&str] = &[
     "/qdrant.Points/DiscoverBatch",
     "/qdrant.Points/OverwritePayload",
     "/qdrant.Points/Query",
+    "/qdrant.Points/QueryBatch",
+    "/qdrant.Points/QueryGroups",
     "/qdrant.Points/Recommend",
     "/qdrant.Points/RecommendBatch",
     "/qdrant.Points/Search",
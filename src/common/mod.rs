@@ -1,7 +1,9 @@
+pub mod changes;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod collections;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod error_reporting;
+pub mod event_sinks;
 #[allow(dead_code)]
 pub mod health;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
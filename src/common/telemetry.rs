@@ -9,18 +9,22 @@ use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
 use uuid::Uuid;
 
+use crate::common::event_sinks::EventSinks;
 use crate::common::telemetry_ops::app_telemetry::{AppBuildTelemetry, AppBuildTelemetryCollector};
 use crate::common::telemetry_ops::cluster_telemetry::ClusterTelemetry;
 use crate::common::telemetry_ops::collections_telemetry::CollectionsTelemetry;
+use crate::common::telemetry_ops::events_telemetry::EventsTelemetry;
 use crate::common::telemetry_ops::requests_telemetry::{
     ActixTelemetryCollector, RequestsTelemetry, TonicTelemetryCollector,
 };
+use crate::common::telemetry_ops::snapshots_telemetry::SnapshotScheduleTelemetry;
 use crate::settings::Settings;
 
 pub struct TelemetryCollector {
     process_id: Uuid,
     settings: Settings,
     dispatcher: Arc<Dispatcher>,
+    event_sinks: Arc<EventSinks>,
     pub app_telemetry_collector: AppBuildTelemetryCollector,
     pub actix_telemetry_collector: Arc<Mutex<ActixTelemetryCollector>>,
     pub tonic_telemetry_collector: Arc<Mutex<TonicTelemetryCollector>>,
@@ -34,6 +38,10 @@ pub struct TelemetryData {
     pub(crate) collections: CollectionsTelemetry,
     pub(crate) cluster: ClusterTelemetry,
     pub(crate) requests: RequestsTelemetry,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) events: Option<EventsTelemetry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) snapshot_schedule: Option<SnapshotScheduleTelemetry>,
 }
 
 impl Anonymize for TelemetryData {
@@ -44,6 +52,8 @@ impl Anonymize for TelemetryData {
             collections: self.collections.anonymize(),
             cluster: self.cluster.anonymize(),
             requests: self.requests.anonymize(),
+            events: self.events.anonymize(),
+            snapshot_schedule: self.snapshot_schedule.anonymize(),
         }
     }
 }
@@ -57,11 +67,17 @@ impl TelemetryCollector {
         Uuid::new_v4()
     }
 
-    pub fn new(settings: Settings, dispatcher: Arc<Dispatcher>, id: Uuid) -> Self {
+    pub fn new(
+        settings: Settings,
+        dispatcher: Arc<Dispatcher>,
+        event_sinks: Arc<EventSinks>,
+        id: Uuid,
+    ) -> Self {
         Self {
             process_id: id,
             settings,
             dispatcher,
+            event_sinks,
             app_telemetry_collector: AppBuildTelemetryCollector::new(),
             actix_telemetry_collector: Arc::new(Mutex::new(ActixTelemetryCollector {
                 workers: Vec::new(),
@@ -84,6 +100,12 @@ impl TelemetryCollector {
                 &self.tonic_telemetry_collector.lock(),
                 detail,
             ),
+            events: EventsTelemetry::collect(&self.event_sinks),
+            snapshot_schedule: SnapshotScheduleTelemetry::collect(
+                access,
+                self.dispatcher.toc(access),
+            )
+            .await,
         }
     }
 }
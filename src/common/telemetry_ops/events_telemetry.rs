@@ -0,0 +1,54 @@
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use serde::Serialize;
+
+use crate::common::event_sinks::EventSinks;
+
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct EventSinkTelemetry {
+    pub name: String,
+    /// Number of events successfully delivered
+    pub delivered: usize,
+    /// Number of events, which could not be delivered even after retries
+    pub failed: usize,
+    /// Number of events dropped because the queue of the sink was full
+    pub dropped: usize,
+    /// Number of retried delivery attempts
+    pub retries: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct EventsTelemetry {
+    pub sinks: Vec<EventSinkTelemetry>,
+}
+
+impl EventsTelemetry {
+    pub fn collect(event_sinks: &EventSinks) -> Option<EventsTelemetry> {
+        (!event_sinks.is_empty()).then(|| EventsTelemetry {
+            sinks: event_sinks.telemetry(),
+        })
+    }
+}
+
+impl Anonymize for EventSinkTelemetry {
+    fn anonymize(&self) -> Self {
+        EventSinkTelemetry {
+            name: self.name.anonymize(),
+            delivered: self.delivered,
+            failed: self.failed,
+            dropped: self.dropped,
+            retries: self.retries,
+            last_error: None,
+        }
+    }
+}
+
+impl Anonymize for EventsTelemetry {
+    fn anonymize(&self) -> Self {
+        EventsTelemetry {
+            sinks: self.sinks.anonymize(),
+        }
+    }
+}
@@ -1,4 +1,6 @@
 pub mod app_telemetry;
 pub mod cluster_telemetry;
 pub mod collections_telemetry;
+pub mod events_telemetry;
 pub mod requests_telemetry;
+pub mod snapshots_telemetry;
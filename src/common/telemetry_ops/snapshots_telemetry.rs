@@ -0,0 +1,68 @@
+use collection::operations::snapshot_ops::SnapshotScheduleStatus;
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use serde::Serialize;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::{Access, AccessRequirements};
+
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct CollectionSnapshotScheduleTelemetry {
+    pub collection: String,
+    pub status: SnapshotScheduleStatus,
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct SnapshotScheduleTelemetry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full: Option<SnapshotScheduleStatus>,
+    pub collections: Vec<CollectionSnapshotScheduleTelemetry>,
+}
+
+impl SnapshotScheduleTelemetry {
+    pub async fn collect(
+        access: &Access,
+        toc: &TableOfContent,
+    ) -> Option<SnapshotScheduleTelemetry> {
+        let scheduler = toc.snapshot_scheduler();
+        if !scheduler.is_enabled() {
+            return None;
+        }
+
+        let full = access
+            .check_global_access(AccessRequirements::new())
+            .ok()
+            .and_then(|_| scheduler.full_status());
+
+        let collections = toc
+            .all_collections(access)
+            .await
+            .into_iter()
+            .filter_map(|pass| {
+                Some(CollectionSnapshotScheduleTelemetry {
+                    status: scheduler.collection_status(pass.name())?,
+                    collection: pass.name().to_string(),
+                })
+            })
+            .collect();
+
+        Some(SnapshotScheduleTelemetry { full, collections })
+    }
+}
+
+impl Anonymize for CollectionSnapshotScheduleTelemetry {
+    fn anonymize(&self) -> Self {
+        CollectionSnapshotScheduleTelemetry {
+            collection: self.collection.anonymize(),
+            status: self.status.anonymize(),
+        }
+    }
+}
+
+impl Anonymize for SnapshotScheduleTelemetry {
+    fn anonymize(&self) -> Self {
+        SnapshotScheduleTelemetry {
+            full: self.full.anonymize(),
+            collections: self.collections.anonymize(),
+        }
+    }
+}
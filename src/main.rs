@@ -30,6 +30,7 @@ use startup::setup_panic_hook;
 use storage::content_manager::consensus::operation_sender::OperationSender;
 use storage::content_manager::consensus::persistent::Persistent;
 use storage::content_manager::consensus_manager::{ConsensusManager, ConsensusStateRef};
+use storage::content_manager::snapshots::schedule::SnapshotScheduler;
 use storage::content_manager::toc::transfer::ShardTransferDispatcher;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
@@ -40,6 +41,7 @@ use storage::rbac::Access;
 ))]
 use tikv_jemallocator::Jemalloc;
 
+use crate::common::event_sinks::setup_event_sinks;
 use crate::common::helpers::{
     create_general_purpose_runtime, create_search_runtime, create_update_runtime,
     load_tls_client_config,
@@ -280,6 +282,18 @@ fn main() -> anyhow::Result<()> {
     // It decides if query should go directly to the ToC or through the consensus.
     let mut dispatcher = Dispatcher::new(toc_arc.clone());
 
+    // Deliver collection and cluster events to the configured sinks
+    let event_sinks = setup_event_sinks(
+        &settings.events,
+        persistent_consensus_state.this_peer_id(),
+        &runtime_handle,
+    );
+
+    // Take scheduled snapshots and apply their retention policies
+    if toc_arc.snapshot_scheduler().is_enabled() {
+        runtime_handle.spawn(SnapshotScheduler::run(Arc::downgrade(&toc_arc)));
+    }
+
     let (telemetry_collector, dispatcher_arc, health_checker) = if is_distributed_deployment {
         let consensus_state: ConsensusStateRef = ConsensusManager::new(
             persistent_consensus_state,
@@ -299,8 +313,12 @@ fn main() -> anyhow::Result<()> {
         let dispatcher_arc = Arc::new(dispatcher);
 
         // Monitoring and telemetry.
-        let telemetry_collector =
-            TelemetryCollector::new(settings.clone(), dispatcher_arc.clone(), reporting_id);
+        let telemetry_collector = TelemetryCollector::new(
+            settings.clone(),
+            dispatcher_arc.clone(),
+            event_sinks.clone(),
+            reporting_id,
+        );
         let tonic_telemetry_collector = telemetry_collector.tonic_telemetry_collector.clone();
 
         // `raft` crate uses `slog` crate so it is needed to use `slog_stdlog::StdLog` to forward
@@ -377,8 +395,12 @@ fn main() -> anyhow::Result<()> {
         let dispatcher_arc = Arc::new(dispatcher);
 
         // Monitoring and telemetry.
-        let telemetry_collector =
-            TelemetryCollector::new(settings.clone(), dispatcher_arc.clone(), reporting_id);
+        let telemetry_collector = TelemetryCollector::new(
+            settings.clone(),
+            dispatcher_arc.clone(),
+            event_sinks,
+            reporting_id,
+        );
         (telemetry_collector, dispatcher_arc, None)
     };
 
@@ -1,5 +1,9 @@
 use api::grpc::models::{CollectionsResponse, VersionInfo};
-use api::rest::{QueryRequest, Record, ScoredPoint};
+use api::rest::{
+    FacetRequest, QueryRequest, QueryRequestBatch, Record, ScoredPoint,
+    SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixRequest,
+};
+use collection::operations::change_ops::{ChangesRequest, ChangesResult};
 use collection::operations::cluster_ops::ClusterOperations;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
@@ -10,13 +14,14 @@ use collection::operations::snapshot_ops::{
 use collection::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionExistence, CollectionInfo,
     CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
-    GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
-    RecommendRequestBatch, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
-    SearchRequestBatch, UpdateResult,
+    GroupsResult, PointGroup, PointRequest, QueryGroupsRequest, RecommendGroupsRequest,
+    RecommendRequest, RecommendRequestBatch, ScrollRequest, ScrollResult, SearchGroupsRequest,
+    SearchRequest, SearchRequestBatch, UpdateResult,
 };
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::gen::SchemaSettings;
 use schemars::JsonSchema;
+use segment::data_types::facets::FacetResponse;
 use serde::Serialize;
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CreateCollection, UpdateCollection,
@@ -82,6 +87,15 @@ struct AllDefinitions {
     bc: VersionInfo,
     bd: CollectionExistence,
     be: QueryRequest,
+    bf: QueryRequestBatch,
+    bg: QueryGroupsRequest,
+    bh: FacetRequest,
+    bi: FacetResponse,
+    bj: SearchMatrixRequest,
+    bk: SearchMatrixOffsetsResponse,
+    bl: SearchMatrixPairsResponse,
+    bm: ChangesRequest,
+    bn: ChangesResult,
 }
 
 fn save_schema<T: JsonSchema>() {
@@ -10,6 +10,7 @@ use storage::types::StorageConfig;
 use validator::Validate;
 
 use crate::common::debugger::DebuggerConfig;
+use crate::common::event_sinks::EventsConfig;
 use crate::tracing;
 
 const DEFAULT_CONFIG: &str = include_str!("../config/config.yaml");
@@ -140,6 +141,9 @@ pub struct Settings {
     pub tls: Option<TlsConfig>,
     #[serde(default)]
     pub debugger: DebuggerConfig,
+    #[serde(default)]
+    #[validate]
+    pub events: EventsConfig,
     /// A list of messages for errors that happened during loading the configuration. We collect
     /// them and store them here while loading because then our logger is not configured yet.
     /// We therefore need to log these messages later, after the logger is ready.
@@ -1,26 +1,34 @@
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 
 use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
-    ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
+    ChangeRecord, ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
     DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
-    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, GetPoints,
-    GetResponse, PointsOperationResponse, RecommendBatchPoints, RecommendBatchResponse,
-    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
-    ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse,
-    SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints, UpdateBatchPoints,
-    UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, FacetCounts,
+    FacetResponse, GetPoints, GetResponse, PointsOperationResponse, QueryBatchPoints,
+    QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse,
+    ReadChanges, RecommendBatchPoints, RecommendBatchResponse, RecommendGroupsResponse,
+    RecommendPointGroups, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
+    SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse, SearchMatrixOffsetsResponse,
+    SearchMatrixPairsResponse, SearchMatrixPoints, SearchPointGroups, SearchPoints, SearchResponse,
+    SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
+use collection::operations::change_ops::ChangesRequest;
 use collection::operations::types::CoreSearchRequest;
+use futures::{future, Stream, TryStreamExt as _};
+use storage::content_manager::conversions::error_to_status;
 use storage::dispatcher::Dispatcher;
 use tonic::{Request, Response, Status};
 
 use super::points_common::{
-    delete_vectors, discover, discover_batch, recommend_groups, search_groups, update_batch,
-    update_vectors,
+    delete_vectors, discover, discover_batch, facet, query, query_batch, query_groups,
+    recommend_groups, search_groups, search_points_matrix_offsets, search_points_matrix_pairs,
+    update_batch, update_vectors,
 };
 use super::validate;
+use crate::common::changes::stream_shard_changes;
 use crate::tonic::api::points_common::{
     clear_payload, convert_shard_selector_for_read, core_search_batch, count, create_field_index,
     delete, delete_field_index, delete_payload, get, overwrite_payload, recommend, recommend_batch,
@@ -444,4 +452,126 @@ impl Points for PointsService {
         )
         .await
     }
+
+    async fn query(
+        &self,
+        mut request: Request<QueryPoints>,
+    ) -> Result<Response<QueryResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        query(self.dispatcher.toc(&access), request.into_inner(), access).await
+    }
+
+    async fn query_batch(
+        &self,
+        mut request: Request<QueryBatchPoints>,
+    ) -> Result<Response<QueryBatchResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        let QueryBatchPoints {
+            collection_name,
+            query_points,
+            read_consistency,
+            timeout,
+        } = request.into_inner();
+
+        query_batch(
+            self.dispatcher.toc(&access),
+            collection_name,
+            query_points,
+            read_consistency,
+            access,
+            timeout.map(Duration::from_secs),
+        )
+        .await
+    }
+
+    async fn query_groups(
+        &self,
+        mut request: Request<QueryPointGroups>,
+    ) -> Result<Response<QueryGroupsResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        query_groups(self.dispatcher.toc(&access), request.into_inner(), access).await
+    }
+
+    async fn facet(
+        &self,
+        mut request: Request<FacetCounts>,
+    ) -> Result<Response<FacetResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        facet(self.dispatcher.toc(&access), request.into_inner(), access).await
+    }
+
+    async fn search_matrix_pairs(
+        &self,
+        mut request: Request<SearchMatrixPoints>,
+    ) -> Result<Response<SearchMatrixPairsResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        search_points_matrix_pairs(self.dispatcher.toc(&access), request.into_inner(), access).await
+    }
+
+    async fn search_matrix_offsets(
+        &self,
+        mut request: Request<SearchMatrixPoints>,
+    ) -> Result<Response<SearchMatrixOffsetsResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        search_points_matrix_offsets(self.dispatcher.toc(&access), request.into_inner(), access)
+            .await
+    }
+
+    type StreamChangesStream = Pin<Box<dyn Stream<Item = Result<ChangeRecord, Status>> + Send>>;
+
+    async fn stream_changes(
+        &self,
+        mut request: Request<ReadChanges>,
+    ) -> Result<Response<Self::StreamChangesStream>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        let ReadChanges {
+            collection_name,
+            shard_id,
+            consumer,
+            cursor,
+            timeout,
+        } = request.into_inner();
+
+        let changes_request = ChangesRequest {
+            consumer,
+            cursor: cursor.map(Into::into),
+            limit: None,
+            timeout: None,
+        };
+
+        let changes = stream_shard_changes(
+            self.dispatcher.toc(&access).clone(),
+            access,
+            collection_name,
+            shard_id,
+            changes_request,
+            timeout.map(Duration::from_secs),
+        )
+        .map_err(error_to_status)?
+        .and_then(|change| future::ready(ChangeRecord::try_from(change).map_err(Into::into)))
+        .map_err(error_to_status);
+
+        Ok(Response::new(Box::pin(changes)))
+    }
 }
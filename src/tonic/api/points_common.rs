@@ -8,14 +8,19 @@ use api::grpc::qdrant::{
     points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchPoints, CountPoints,
     CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
     DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
-    FieldType, GetPoints, GetResponse, PayloadIndexParams, PointsOperationResponseInternal,
-    PointsSelector, ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse,
-    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
-    ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse, SearchPointGroups,
-    SearchPoints, SearchResponse, SetPayloadPoints, SyncPoints, UpdateBatchPoints,
-    UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    FacetCounts, FacetResponse, FieldType, GetPoints, GetResponse, PayloadIndexParams,
+    PointsOperationResponseInternal, PointsSelector, QueryBatchResponse, QueryGroupsResponse,
+    QueryPointGroups, QueryPoints, QueryResponse, ReadConsistency as ReadConsistencyGrpc,
+    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
+    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse,
+    SearchMatrixOffsets, SearchMatrixOffsetsResponse, SearchMatrixPairs, SearchMatrixPairsResponse,
+    SearchMatrixPoints, SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints,
+    SyncPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
 use api::rest::{OrderByInterface, ShardKeySelector};
+use collection::collection::distance_matrix::{
+    CollectionSearchMatrixRequest, CollectionSearchMatrixResponse,
+};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::{
     try_discover_request_from_grpc, try_points_selector_from_grpc, write_ordering_from_proto,
@@ -30,12 +35,16 @@ use collection::operations::types::{
     default_exact_count, CoreSearchRequest, CoreSearchRequestBatch, PointRequestInternal,
     RecommendExample, Record, ScrollRequestInternal,
 };
+use collection::operations::universal_query::collection_query::{
+    IntoCollectionQueryRequest, IntoQueryGroupRequest,
+};
 use collection::operations::vector_ops::{DeleteVectors, PointVectors, UpdateVectors};
 use collection::operations::{ClockTag, CollectionUpdateOperations, OperationWithClockTag};
 use collection::shards::shard::ShardId;
 use itertools::Itertools;
+use segment::data_types::facets::FacetParams;
 use segment::data_types::order_by::OrderBy;
-use segment::data_types::vectors::VectorStruct;
+use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
 use segment::types::{
     ExtendedPointId, Filter, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType,
 };
@@ -249,14 +258,11 @@ pub async fn update_vectors(
             Some(id) => id.try_into()?,
             None => return Err(Status::invalid_argument("id is expected")),
         };
-        let vector: VectorStruct = match point.vectors {
+        let vector: api::rest::VectorStruct = match point.vectors {
             Some(vectors) => vectors.try_into()?,
             None => return Err(Status::invalid_argument("vectors is expected")),
         };
-        op_points.push(PointVectors {
-            id,
-            vector: api::rest::VectorStruct::from(vector),
-        });
+        op_points.push(PointVectors { id, vector });
     }
 
     let operation = UpdateVectors {
@@ -769,6 +775,60 @@ fn convert_field_type(
         ) => Some(PayloadFieldSchema::FieldParams(
             PayloadSchemaParams::Integer(integer_params.try_into()?),
         )),
+        // Parameterized keyword type
+        (
+            Some(FieldType::Keyword),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::KeywordIndexParams(keyword_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(
+            PayloadSchemaParams::Keyword(keyword_params.try_into()?),
+        )),
+        // Parameterized float type
+        (
+            Some(FieldType::Float),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::FloatIndexParams(float_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(PayloadSchemaParams::Float(
+            float_params.try_into()?,
+        ))),
+        // Parameterized geo type
+        (
+            Some(FieldType::Geo),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::GeoIndexParams(geo_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(PayloadSchemaParams::Geo(
+            geo_params.try_into()?,
+        ))),
+        // Parameterized bool type
+        (
+            Some(FieldType::Bool),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::BoolIndexParams(bool_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(PayloadSchemaParams::Bool(
+            bool_params.try_into()?,
+        ))),
+        // Parameterized datetime type
+        (
+            Some(FieldType::Datetime),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::DatetimeIndexParams(datetime_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(
+            PayloadSchemaParams::Datetime(datetime_params.try_into()?),
+        )),
+        // Parameterized uuid type
+        (
+            Some(FieldType::Uuid),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::UuidIndexParams(uuid_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(PayloadSchemaParams::Uuid(
+            uuid_params.try_into()?,
+        ))),
         // Regular field types
         (Some(v), None | Some(PayloadIndexParams { index_params: None })) => match v {
             FieldType::Keyword => Some(PayloadSchemaType::Keyword.into()),
@@ -778,6 +838,7 @@ fn convert_field_type(
             FieldType::Text => Some(PayloadSchemaType::Text.into()),
             FieldType::Bool => Some(PayloadSchemaType::Bool.into()),
             FieldType::Datetime => Some(PayloadSchemaType::Datetime.into()),
+            FieldType::Uuid => Some(PayloadSchemaType::Uuid.into()),
         },
         // Parameterized index with mismatching types
         (
@@ -1506,6 +1567,201 @@ pub async fn count(
     Ok(Response::new(response))
 }
 
+pub async fn query(
+    toc: &TableOfContent,
+    query_points: QueryPoints,
+    access: Access,
+) -> Result<Response<QueryResponse>, Status> {
+    let IntoCollectionQueryRequest {
+        request,
+        collection_name,
+        shard_key,
+        read_consistency,
+        timeout,
+    } = query_points.try_into()?;
+
+    let timing = Instant::now();
+    let scored_points = toc
+        .query(
+            &collection_name,
+            request,
+            read_consistency,
+            shard_key,
+            access,
+            timeout,
+        )
+        .await
+        .map_err(error_to_status)?;
+
+    let response = QueryResponse {
+        result: scored_points
+            .into_iter()
+            .map(|point| point.into())
+            .collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn query_batch(
+    toc: &TableOfContent,
+    collection_name: String,
+    query_points: Vec<QueryPoints>,
+    read_consistency: Option<ReadConsistencyGrpc>,
+    access: Access,
+    timeout: Option<Duration>,
+) -> Result<Response<QueryBatchResponse>, Status> {
+    let mut read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let mut requests = Vec::with_capacity(query_points.len());
+
+    for query_request in query_points {
+        let IntoCollectionQueryRequest {
+            request,
+            collection_name: request_collection_name,
+            shard_key,
+            read_consistency: request_read_consistency,
+            // The timeout of the batch applies to all of its queries
+            timeout: _,
+        } = query_request.try_into()?;
+
+        if !request_collection_name.is_empty() && request_collection_name != collection_name {
+            return Err(Status::invalid_argument(format!(
+                "Query targets collection `{request_collection_name}`, \
+                 but the batch targets collection `{collection_name}`",
+            )));
+        }
+
+        // All queries of the batch are executed with the same read consistency
+        if let Some(request_read_consistency) = request_read_consistency {
+            match read_consistency {
+                None => read_consistency = Some(request_read_consistency),
+                Some(batch_read_consistency)
+                    if batch_read_consistency != request_read_consistency =>
+                {
+                    return Err(Status::invalid_argument(
+                        "Read consistency of a query conflicts with the read consistency of the batch",
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+
+        requests.push((request, shard_key));
+    }
+
+    let timing = Instant::now();
+    let scored_points = toc
+        .query_batch(
+            &collection_name,
+            requests,
+            read_consistency,
+            access,
+            timeout,
+        )
+        .await
+        .map_err(error_to_status)?;
+
+    let response = QueryBatchResponse {
+        result: scored_points
+            .into_iter()
+            .map(|points| BatchResult {
+                result: points.into_iter().map(|p| p.into()).collect(),
+            })
+            .collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn query_groups(
+    toc: &TableOfContent,
+    query_point_groups: QueryPointGroups,
+    access: Access,
+) -> Result<Response<QueryGroupsResponse>, Status> {
+    let IntoQueryGroupRequest {
+        request,
+        collection_name,
+        shard_key,
+        read_consistency,
+        timeout,
+    } = query_point_groups.try_into()?;
+
+    let timing = Instant::now();
+    let groups_result = toc
+        .group(
+            &collection_name,
+            request,
+            read_consistency,
+            shard_key,
+            access,
+            timeout,
+        )
+        .await
+        .map_err(error_to_status)?;
+
+    let response = QueryGroupsResponse {
+        result: Some(groups_result.into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn facet(
+    toc: &TableOfContent,
+    facet_counts: FacetCounts,
+    access: Access,
+) -> Result<Response<FacetResponse>, Status> {
+    let FacetCounts {
+        collection_name,
+        key,
+        filter,
+        limit,
+        exact,
+        timeout,
+        read_consistency,
+        shard_key_selector,
+    } = facet_counts;
+
+    let facet_request = FacetParams {
+        key: json_path_from_proto(&key)?,
+        limit: limit
+            .map(|limit| limit as usize)
+            .unwrap_or(FacetParams::DEFAULT_LIMIT),
+        filter: filter.map(TryInto::try_into).transpose()?,
+        exact: exact.unwrap_or(false),
+    };
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
+
+    let timeout = timeout.map(Duration::from_secs);
+
+    let timing = Instant::now();
+    let facet_response = toc
+        .facet(
+            &collection_name,
+            facet_request,
+            read_consistency,
+            shard_selector,
+            access,
+            timeout,
+        )
+        .await
+        .map_err(error_to_status)?;
+
+    let response = FacetResponse {
+        hits: facet_response.hits.into_iter().map(From::from).collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
 pub async fn get(
     toc: &TableOfContent,
     get_points: GetPoints,
@@ -1556,3 +1812,82 @@ pub async fn get(
 
     Ok(Response::new(response))
 }
+
+async fn search_points_matrix(
+    toc: &TableOfContent,
+    request: SearchMatrixPoints,
+    access: Access,
+) -> Result<CollectionSearchMatrixResponse, Status> {
+    let SearchMatrixPoints {
+        collection_name,
+        filter,
+        sample,
+        limit,
+        using,
+        timeout,
+        read_consistency,
+        shard_key_selector,
+    } = request;
+
+    let search_matrix_request = CollectionSearchMatrixRequest {
+        sample_size: sample
+            .map(|sample| sample as usize)
+            .unwrap_or(CollectionSearchMatrixRequest::DEFAULT_SAMPLE),
+        limit_per_sample: limit
+            .map(|limit| limit as usize)
+            .unwrap_or(CollectionSearchMatrixRequest::DEFAULT_LIMIT_PER_SAMPLE),
+        filter: filter.map(TryInto::try_into).transpose()?,
+        using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string()),
+    };
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
+
+    let timeout = timeout.map(Duration::from_secs);
+
+    toc.search_points_matrix(
+        &collection_name,
+        search_matrix_request,
+        read_consistency,
+        shard_selector,
+        access,
+        timeout,
+    )
+    .await
+    .map_err(error_to_status)
+}
+
+pub async fn search_points_matrix_pairs(
+    toc: &TableOfContent,
+    request: SearchMatrixPoints,
+    access: Access,
+) -> Result<Response<SearchMatrixPairsResponse>, Status> {
+    let timing = Instant::now();
+
+    let search_matrix_response = search_points_matrix(toc, request, access).await?;
+
+    let response = SearchMatrixPairsResponse {
+        result: Some(SearchMatrixPairs::from(search_matrix_response)),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn search_points_matrix_offsets(
+    toc: &TableOfContent,
+    request: SearchMatrixPoints,
+    access: Access,
+) -> Result<Response<SearchMatrixOffsetsResponse>, Status> {
+    let timing = Instant::now();
+
+    let search_matrix_response = search_points_matrix(toc, request, access).await?;
+
+    let response = SearchMatrixOffsetsResponse {
+        result: Some(SearchMatrixOffsets::from(search_matrix_response)),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
@@ -1,19 +1,22 @@
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use api::grpc::conversions::json_path_from_proto;
 use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
     ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
-    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, GetPointsInternal,
-    GetResponse, IntermediateResult, PointsOperationResponseInternal, QueryPointsInternal,
-    QueryResponse, QueryShardPoints, RecommendPointsInternal, RecommendResponse,
-    ScrollPointsInternal, ScrollResponse, SearchBatchResponse, SetPayloadPointsInternal,
-    SyncPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
+    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, FacetCountsInternal,
+    FacetResponseInternal, GetPointsInternal, GetResponse, IntermediateResult,
+    PointsOperationResponseInternal, QueryPointsInternal, QueryResponseInternal, QueryShardPoints,
+    RecommendPointsInternal, RecommendResponse, ScrollPointsInternal, ScrollResponse,
+    SearchBatchResponse, SetPayloadPointsInternal, SyncPointsInternal, UpdateVectorsInternal,
+    UpsertPointsInternal,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::ShardQueryRequest;
 use collection::shards::shard::ShardId;
+use segment::data_types::facets::FacetParams;
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
 use storage::rbac::Access;
@@ -45,7 +48,8 @@ pub async fn query(
     collection_name: String,
     query_points: QueryShardPoints,
     shard_selection: Option<ShardId>,
-) -> Result<Response<QueryResponse>, Status> {
+    timeout: Option<Duration>,
+) -> Result<Response<QueryResponseInternal>, Status> {
     let request = ShardQueryRequest::try_from(query_points)?;
 
     let timing = Instant::now();
@@ -61,11 +65,11 @@ pub async fn query(
     };
 
     let scored_points = toc
-        .query_internal(&collection_name, request, shard_selection)
+        .query_internal(&collection_name, request, shard_selection, timeout)
         .await
         .map_err(error_to_status)?;
 
-    let response = QueryResponse {
+    let response = QueryResponseInternal {
         result: scored_points
             .into_iter()
             .map(|points| IntermediateResult {
@@ -474,7 +478,7 @@ impl PointsInternal for PointsInternalService {
     async fn query(
         &self,
         request: Request<QueryPointsInternal>,
-    ) -> Result<Response<QueryResponse>, Status> {
+    ) -> Result<Response<QueryResponseInternal>, Status> {
         // TODO(universal-query): validate
         // validate_and_log(request.get_ref());
 
@@ -482,16 +486,69 @@ impl PointsInternal for PointsInternalService {
             collection_name,
             shard_id,
             query_points,
+            timeout,
         } = request.into_inner();
 
         let query_points =
             query_points.ok_or_else(|| Status::invalid_argument("QueryPoints is missing"))?;
 
-        // TODO(universal-query): add timeout
-        // let timeout = timeout.map(Duration::from_secs);
+        let timeout = timeout.map(Duration::from_secs);
 
         // Individual `read_consistency` values are ignored
 
-        query(self.toc.as_ref(), collection_name, query_points, shard_id).await
+        query(
+            self.toc.as_ref(),
+            collection_name,
+            query_points,
+            shard_id,
+            timeout,
+        )
+        .await
+    }
+
+    async fn facet(
+        &self,
+        request: Request<FacetCountsInternal>,
+    ) -> Result<Response<FacetResponseInternal>, Status> {
+        let FacetCountsInternal {
+            collection_name,
+            key,
+            filter,
+            limit,
+            exact,
+            shard_id,
+            timeout,
+        } = request.into_inner();
+
+        let timeout = timeout.map(Duration::from_secs);
+
+        let request = FacetParams {
+            key: json_path_from_proto(&key)?,
+            limit: limit as usize,
+            filter: filter.map(TryInto::try_into).transpose()?,
+            exact,
+        };
+
+        let timing = Instant::now();
+
+        let response = self
+            .toc
+            .facet(
+                &collection_name,
+                request,
+                None,
+                ShardSelectorInternal::ShardId(shard_id),
+                FULL_ACCESS.clone(),
+                timeout,
+            )
+            .await
+            .map_err(error_to_status)?;
+
+        let response = FacetResponseInternal {
+            hits: response.hits.into_iter().map(From::from).collect(),
+            time: timing.elapsed().as_secs_f64(),
+        };
+
+        Ok(Response::new(response))
     }
 }
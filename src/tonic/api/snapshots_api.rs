@@ -4,11 +4,11 @@ use std::time::Instant;
 use api::grpc::qdrant::shard_snapshots_server::ShardSnapshots;
 use api::grpc::qdrant::snapshots_server::Snapshots;
 use api::grpc::qdrant::{
-    CreateFullSnapshotRequest, CreateShardSnapshotRequest, CreateSnapshotRequest,
-    CreateSnapshotResponse, DeleteFullSnapshotRequest, DeleteShardSnapshotRequest,
-    DeleteSnapshotRequest, DeleteSnapshotResponse, ListFullSnapshotsRequest,
-    ListShardSnapshotsRequest, ListSnapshotsRequest, ListSnapshotsResponse,
-    RecoverShardSnapshotRequest, RecoverSnapshotResponse,
+    CompactSnapshotRequest, CreateFullSnapshotRequest, CreateShardSnapshotRequest,
+    CreateSnapshotRequest, CreateSnapshotResponse, DeleteFullSnapshotRequest,
+    DeleteShardSnapshotRequest, DeleteSnapshotRequest, DeleteSnapshotResponse,
+    ListFullSnapshotsRequest, ListShardSnapshotsRequest, ListSnapshotsRequest,
+    ListSnapshotsResponse, RecoverShardSnapshotRequest, RecoverSnapshotResponse,
 };
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::snapshots::{
@@ -21,7 +21,7 @@ use tonic::{async_trait, Request, Response, Status};
 
 use super::{validate, validate_and_log};
 use crate::common;
-use crate::common::collections::{do_create_snapshot, do_list_snapshots};
+use crate::common::collections::{do_compact_snapshot, do_create_snapshot, do_list_snapshots};
 use crate::common::http_client::HttpClient;
 use crate::tonic::auth::extract_access;
 
@@ -43,7 +43,10 @@ impl Snapshots for SnapshotsService {
     ) -> Result<Response<CreateSnapshotResponse>, Status> {
         validate(request.get_ref())?;
         let access = extract_access(&mut request);
-        let collection_name = request.into_inner().collection_name;
+        let CreateSnapshotRequest {
+            collection_name,
+            base,
+        } = request.into_inner();
         let timing = Instant::now();
         let dispatcher = self.dispatcher.clone();
         let response = async move {
@@ -51,6 +54,7 @@ impl Snapshots for SnapshotsService {
                 Arc::clone(dispatcher.toc(&access)),
                 access,
                 &collection_name,
+                base,
             )?
             .await?
         }
@@ -89,6 +93,7 @@ impl Snapshots for SnapshotsService {
         let DeleteSnapshotRequest {
             collection_name,
             snapshot_name,
+            force,
         } = request.into_inner();
         let timing = Instant::now();
         let _response = async move {
@@ -97,6 +102,7 @@ impl Snapshots for SnapshotsService {
                 access,
                 &collection_name,
                 &snapshot_name,
+                force.unwrap_or(false),
             )
             .await?
             .await?
@@ -108,6 +114,35 @@ impl Snapshots for SnapshotsService {
         }))
     }
 
+    async fn compact(
+        &self,
+        mut request: Request<CompactSnapshotRequest>,
+    ) -> Result<Response<CreateSnapshotResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        let CompactSnapshotRequest {
+            collection_name,
+            snapshot_name,
+        } = request.into_inner();
+        let timing = Instant::now();
+        let dispatcher = self.dispatcher.clone();
+        let response = async move {
+            do_compact_snapshot(
+                Arc::clone(dispatcher.toc(&access)),
+                access,
+                &collection_name,
+                snapshot_name,
+            )?
+            .await?
+        }
+        .await
+        .map_err(error_to_status)?;
+        Ok(Response::new(CreateSnapshotResponse {
+            snapshot_description: Some(response.into()),
+            time: timing.elapsed().as_secs_f64(),
+        }))
+    }
+
     async fn create_full(
         &self,
         mut request: Request<CreateFullSnapshotRequest>,
@@ -1,6 +1,6 @@
 import json
 import tempfile
-from inspect import isfunction
+from inspect import isfunction, isgenerator
 from typing import Callable, List, Optional, Tuple, Union
 
 import grpc
@@ -288,6 +288,14 @@ ACTION_ACCESS = {
         "qdrant.Snapshots/Delete",
         coll_rw_payload=False,
     ),
+    "compact_collection_snapshot": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/snapshots/{snapshot_name}/compact",
+        "qdrant.Snapshots/Compact",
+        coll_rw_payload=False,
+    ),
     "download_collection_snapshot": EndpointAccess(
         True,
         True,
@@ -345,6 +353,22 @@ ACTION_ACCESS = {
         "GET /collections/{collection_name}/shards/{shard_id}/snapshots/{snapshot_name}",
         coll_rw_payload=False,
     ),
+    ### Shard Changes ###
+    "read_shard_changes": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/shards/{shard_id}/changes",
+        "qdrant.Points/StreamChanges",
+        coll_rw_payload=False,
+    ),
+    "delete_change_consumer": EndpointAccess(
+        False,
+        True,
+        True,
+        "DELETE /collections/{collection_name}/shards/{shard_id}/changes/consumers/{consumer}",
+        coll_rw_payload=False,
+    ),
     ### Full Snapshots ###
     "list_full_snapshots": EndpointAccess(
         True,
@@ -525,7 +549,38 @@ ACTION_ACCESS = {
         True, True, True, "POST /collections/{collection_name}/points/count", "qdrant.Points/Count"
     ),
     "query_points": EndpointAccess(
-        True, True, True, "POST /collections/{collection_name}/points/query", # "qdrant.Points/Query"
+        True, True, True, "POST /collections/{collection_name}/points/query", "qdrant.Points/Query"
+    ),
+    "query_points_batch": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/query/batch",
+        "qdrant.Points/QueryBatch",
+    ),
+    "query_points_groups": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/query/groups",
+        "qdrant.Points/QueryGroups",
+    ),
+    "facet": EndpointAccess(
+        True, True, True, "POST /collections/{collection_name}/points/facet", "qdrant.Points/Facet"
+    ),
+    "search_points_matrix_pairs": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/search/matrix/pairs",
+        "qdrant.Points/SearchMatrixPairs",
+    ),
+    "search_points_matrix_offsets": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/search/matrix/offsets",
+        "qdrant.Points/SearchMatrixOffsets",
     ),
     ### Service ###
     "root": EndpointAccess(True, True, True, "GET /", "qdrant.Qdrant/HealthCheck"),
@@ -657,6 +712,9 @@ def check_grpc_access(
 
     try:
         _res = client.request(service=service, method=method, request=request)
+        # Streaming responses only fail once consumed
+        if isgenerator(_res):
+            list(_res)
     except grpc.RpcError as e:
         if should_succeed:
             if e.code() not in [grpc.StatusCode.INVALID_ARGUMENT, grpc.StatusCode.NOT_FOUND]:
@@ -1210,6 +1268,18 @@ def test_delete_collection_snapshot():
     )
 
 
+def test_compact_collection_snapshot():
+    fake_name = random_str()
+    check_access(
+        "compact_collection_snapshot",
+        path_params={
+            "collection_name": COLL_NAME,
+            "snapshot_name": fake_name,
+        },
+        grpc_request={"collection_name": COLL_NAME, "snapshot_name": fake_name},
+    )
+
+
 def test_download_collection_snapshot():
     res = requests.post(
         f"{REST_URI}/collections/{COLL_NAME}/snapshots?wait=true",
@@ -1341,6 +1411,26 @@ def test_download_shard_snapshot(shard_snapshot_name: str):
     )
 
 
+def test_read_shard_changes():
+    check_access(
+        "read_shard_changes",
+        rest_request={"limit": 1},
+        path_params={"collection_name": COLL_NAME, "shard_id": SHARD_ID},
+        grpc_request={"collection_name": COLL_NAME, "shard_id": SHARD_ID, "timeout": 1},
+    )
+
+
+def test_delete_change_consumer():
+    check_access(
+        "delete_change_consumer",
+        path_params={
+            "collection_name": COLL_NAME,
+            "shard_id": SHARD_ID,
+            "consumer": random_str(),
+        },
+    )
+
+
 def test_list_full_snapshots():
     check_access("list_full_snapshots")
 
@@ -1714,9 +1804,76 @@ def test_query_points():
         "query_points",
         rest_request={"query": [0.1, 0.2, 0.3, 0.4]},
         path_params={"collection_name": COLL_NAME},
-        grpc_request={"collection_name": COLL_NAME, "query": {"vector": {"data": [0.1, 0.2, 0.3, 0.4]}}},
+        grpc_request={
+            "collection_name": COLL_NAME,
+            "query": {"nearest": {"dense": {"data": [0.1, 0.2, 0.3, 0.4]}}},
+        },
+    )
+
+
+def test_query_points_batch():
+    check_access(
+        "query_points_batch",
+        rest_request={"searches": [{"query": [0.1, 0.2, 0.3, 0.4]}]},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={
+            "collection_name": COLL_NAME,
+            "query_points": [
+                {
+                    "collection_name": COLL_NAME,
+                    "query": {"nearest": {"dense": {"data": [0.1, 0.2, 0.3, 0.4]}}},
+                }
+            ],
+        },
     )
-    
+
+
+def test_query_points_groups():
+    check_access(
+        "query_points_groups",
+        rest_request={
+            "query": [0.1, 0.2, 0.3, 0.4],
+            "limit": 10,
+            "group_by": FIELD_NAME,
+            "group_size": 3,
+        },
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={
+            "collection_name": COLL_NAME,
+            "query": {"nearest": {"dense": {"data": [0.1, 0.2, 0.3, 0.4]}}},
+            "limit": 10,
+            "group_by": FIELD_NAME,
+            "group_size": 3,
+        },
+    )
+
+
+def test_facet():
+    check_access(
+        "facet",
+        rest_request={"key": FIELD_NAME},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={"collection_name": COLL_NAME, "key": FIELD_NAME},
+    )
+
+
+def test_search_points_matrix_pairs():
+    check_access(
+        "search_points_matrix_pairs",
+        rest_request={"sample": 5, "limit": 2},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={"collection_name": COLL_NAME, "sample": 5, "limit": 2},
+    )
+
+
+def test_search_points_matrix_offsets():
+    check_access(
+        "search_points_matrix_offsets",
+        rest_request={"sample": 5, "limit": 2},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={"collection_name": COLL_NAME, "sample": 5, "limit": 2},
+    )
+
 
 def test_root():
     check_access("root")
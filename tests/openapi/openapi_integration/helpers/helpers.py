@@ -111,3 +111,40 @@ def reciprocal_rank_fusion(
         point["score"] = score
         sorted_points.append(point)
     return sorted_points
+
+
+def distribution_based_score_fusion(
+    responses: List[List[Any]], limit: int = 10, weights: List[float] = None
+) -> List[Any]:
+    def normalize(response: List[Any]) -> List[float]:
+        if len(response) == 0:
+            return []
+        # responses are sorted from best to worst, flip the scores if smaller is better
+        sign = -1 if response[0]["score"] < response[-1]["score"] else 1
+        scores = [sign * point["score"] for point in response]
+        mean = sum(scores) / len(scores)
+        std_dev = (sum((score - mean) ** 2 for score in scores) / len(scores)) ** 0.5
+        low = mean - 3 * std_dev
+        high = mean + 3 * std_dev
+        if high - low <= 0:
+            return [0.5 for _ in scores]
+        return [(score - low) / (high - low) for score in scores]
+
+    scores: Dict[Any, float] = {} # id -> score
+    point_pile = {}
+    for idx, response in enumerate(responses):
+        weight = weights[idx] if weights is not None and idx < len(weights) else 1.0
+        for scored_point, normalized in zip(response, normalize(response)):
+            if scored_point["id"] in scores:
+                scores[scored_point["id"]] += weight * normalized
+            else:
+                point_pile[scored_point["id"]] = scored_point
+                scores[scored_point["id"]] = weight * normalized
+
+    sorted_scores = sorted(scores.items(), key=lambda item: item[1], reverse=True)
+    sorted_points = []
+    for point_id, score in sorted_scores[:limit]:
+        point = point_pile[point_id]
+        point["score"] = score
+        sorted_points.append(point)
+    return sorted_points
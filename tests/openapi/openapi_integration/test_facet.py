@@ -0,0 +1,112 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_facet'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "city",
+            "field_schema": "keyword"
+        }
+    )
+    assert response.ok
+
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_facet():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/facet',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "key": "city",
+            "exact": True,
+        }
+    )
+    assert response.ok, response.json()
+
+    assert response.json()['result']['hits'] == [
+        {"value": "Berlin", "count": 3},
+        {"value": "London", "count": 2},
+        {"value": "Moscow", "count": 2},
+    ]
+
+
+def test_facet_with_filter_and_limit():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/facet',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "key": "city",
+            "limit": 1,
+            "filter": {
+                "must_not": [
+                    {
+                        "key": "city",
+                        "match": {
+                            "value": "Berlin"
+                        }
+                    }
+                ]
+            }
+        }
+    )
+    assert response.ok, response.json()
+
+    assert response.json()['result']['hits'] == [
+        {"value": "London", "count": 1},
+    ]
+
+
+def test_approximate_facet_with_filter():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/facet',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "key": "city",
+            "exact": False,
+            "filter": {
+                "must": [
+                    {
+                        "key": "city",
+                        "match": {
+                            "value": "Berlin"
+                        }
+                    }
+                ]
+            }
+        }
+    )
+    assert response.ok, response.json()
+
+    # Only values of the points matching the filter are counted
+    assert response.json()['result']['hits'] == [
+        {"value": "Berlin", "count": 3},
+    ]
+
+
+def test_facet_without_index():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/facet',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "key": "price",
+        }
+    )
+    assert response.status_code == 400
@@ -230,6 +230,69 @@ def test_datetime_indexing():
         assert not response.ok
 
 
+
+def test_uuid_indexing():
+    uuid_key = "uuid_payload"
+    # create payload
+    set_payload({uuid_key: "c5f8d2a6-7b3e-4d19-9a0f-2e6c8b1d4f37"}, [1])
+    set_payload({uuid_key: ["C5F8D2A6-7B3E-4D19-9A0F-2E6C8B1D4F37", "3b1e9f4c-8d2a-4e7b-a6c5-0f9d1e2b3c48"]}, [2])
+    # Create index
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": uuid_key,
+            "field_schema": "uuid"
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['payload_schema'][uuid_key]['data_type'] == "uuid"
+
+    data = [
+        ({"value": "c5f8d2a6-7b3e-4d19-9a0f-2e6c8b1d4f37"}, [1, 2]),
+        ({"any": ["3b1e9f4c-8d2a-4e7b-a6c5-0f9d1e2b3c48"]}, [2]),
+        ({"except": ["c5f8d2a6-7b3e-4d19-9a0f-2e6c8b1d4f37"]}, [2]),
+    ]
+    for match, expected_ids in data:
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/scroll",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body={
+                "with_vector": False,
+                "filter": {"must": [{"key": uuid_key, "match": match}]},
+            },
+        )
+        assert response.ok, response.json()
+
+        point_ids = sorted(p["id"] for p in response.json()["result"]["points"])
+        assert point_ids == expected_ids, match
+
+    # Strings which are not UUIDs are not indexed, but can still be matched
+    set_payload({uuid_key: "not-a-uuid"}, [3])
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/scroll",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "with_vector": False,
+            "filter": {"must": [{"key": uuid_key, "match": {"value": "not-a-uuid"}}]},
+        },
+    )
+    assert response.ok, response.json()
+    assert [p["id"] for p in response.json()["result"]["points"]] == [3]
+
+
 def test_update_payload_on_indexed_field():
     keyword_field = "city"
 
@@ -292,3 +355,46 @@ def test_update_payload_on_indexed_field():
     )
     assert response.ok
     assert [p['id'] for p in response.json()['result']['points']] == [1, 2, 3]
+
+
+def test_tenant_keyword_indexing():
+    tenant_key = "tenant_payload"
+    set_payload({tenant_key: "tenant_a"}, [1, 2])
+    set_payload({tenant_key: "tenant_b"}, [3])
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": tenant_key,
+            "field_schema": {
+                "type": "keyword",
+                "is_tenant": True,
+            }
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    schema = response.json()['result']['payload_schema'][tenant_key]
+    assert schema['data_type'] == "keyword"
+    assert schema['params']['is_tenant']
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/scroll",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "with_vector": False,
+            "filter": {"must": [{"key": tenant_key, "match": {"value": "tenant_a"}}]},
+        },
+    )
+    assert response.ok, response.json()
+    assert sorted(p["id"] for p in response.json()["result"]["points"]) == [1, 2]
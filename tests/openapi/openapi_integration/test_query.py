@@ -2,7 +2,11 @@ from math import isclose
 import pytest
 
 from .helpers.collection_setup import basic_collection_setup, drop_collection
-from .helpers.helpers import reciprocal_rank_fusion, request_with_validation
+from .helpers.helpers import (
+    distribution_based_score_fusion,
+    reciprocal_rank_fusion,
+    request_with_validation,
+)
 
 collection_name = "test_query"
 
@@ -285,3 +289,104 @@ def test_basic_rrf():
         assert expected["id"] == result["id"]
         assert expected["payload"] == result["payload"]
         assert isclose(expected["score"], result["score"], rel_tol=1e-5)
+
+
+
+@pytest.mark.parametrize("weights", [None, [2.0, 0.5]])
+def test_basic_dbsf(weights):
+    search_results = []
+    for vector in ([0.1, 0.2, 0.3, 0.4], [0.5, 0.6, 0.7, 0.8]):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/search",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body={
+                "vector": vector,
+                "limit": 10,
+            },
+        )
+        assert response.ok
+        search_results.append(response.json()["result"])
+
+    dbsf_expected = distribution_based_score_fusion(search_results, limit=10, weights=weights)
+
+    prefetch = [
+        {"query": [0.1, 0.2, 0.3, 0.4]},
+        {"query": [0.5, 0.6, 0.7, 0.8]},
+    ]
+    if weights is not None:
+        for prefetch_item, weight in zip(prefetch, weights):
+            prefetch_item["weight"] = weight
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "prefetch": prefetch,
+            "query": {"fusion": "dbsf"},
+        },
+    )
+    assert response.ok, response.json()
+    dbsf_result = response.json()["result"]
+
+    def get_id(x):
+        return x["id"]
+
+    # fusion order is not deterministic with same scores, so we need to sort by id
+    assert len(dbsf_expected) == len(dbsf_result)
+    for expected, result in zip(sorted(dbsf_expected, key=get_id), sorted(dbsf_result, key=get_id)):
+        assert expected["id"] == result["id"]
+        assert isclose(expected["score"], result["score"], rel_tol=1e-4)
+
+def test_basic_query_batch():
+    queries = [[0.1, 0.2, 0.3, 0.4], {"order_by": "price"}]
+
+    expected_results = [root_and_rescored_query(query) for query in queries]
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query/batch",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "searches": [{"query": query} for query in queries],
+        },
+    )
+    assert response.ok, response.json()
+    batch_results = response.json()["result"]
+
+    assert batch_results == expected_results
+
+
+def test_basic_query_groups():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/search/groups",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "vector": [0.1, 0.2, 0.3, 0.4],
+            "group_by": "city",
+            "limit": 3,
+            "group_size": 2,
+            "with_payload": True,
+        },
+    )
+    assert response.ok
+    search_groups_result = response.json()["result"]
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query/groups",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": [0.1, 0.2, 0.3, 0.4],
+            "group_by": "city",
+            "limit": 3,
+            "group_size": 2,
+            "with_payload": True,
+        },
+    )
+    assert response.ok, response.json()
+    query_groups_result = response.json()["result"]
+
+    assert search_groups_result == query_groups_result
@@ -0,0 +1,94 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_search_matrix'
+
+# Points with a dense vector in the basic collection
+DENSE_POINT_IDS = list(range(1, 9))
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_search_matrix_pairs():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/matrix/pairs',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "sample": 10,
+            "limit": 2,
+        }
+    )
+    assert response.ok, response.json()
+
+    pairs = response.json()['result']['pairs']
+
+    # Every sampled point with a vector gets its 2 nearest neighbours
+    assert len(pairs) == len(DENSE_POINT_IDS) * 2
+    assert {pair['a'] for pair in pairs} == set(DENSE_POINT_IDS)
+
+    for pair in pairs:
+        assert pair['a'] != pair['b']
+        assert pair['b'] in DENSE_POINT_IDS
+
+
+def test_search_matrix_offsets():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/matrix/offsets',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "sample": 10,
+            "limit": 2,
+        }
+    )
+    assert response.ok, response.json()
+
+    result = response.json()['result']
+
+    assert result['ids'] == DENSE_POINT_IDS
+    assert len(result['offsets_row']) == len(DENSE_POINT_IDS) * 2
+    assert len(result['offsets_col']) == len(DENSE_POINT_IDS) * 2
+    assert len(result['scores']) == len(DENSE_POINT_IDS) * 2
+
+    for row, col in zip(result['offsets_row'], result['offsets_col']):
+        assert row != col
+        assert 0 <= col < len(DENSE_POINT_IDS)
+
+
+def test_search_matrix_with_filter():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/matrix/pairs',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {
+                "must": [
+                    {
+                        "key": "city",
+                        "match": {
+                            "value": "Berlin"
+                        }
+                    }
+                ]
+            },
+            "sample": 10,
+            "limit": 5,
+        }
+    )
+    assert response.ok, response.json()
+
+    pairs = response.json()['result']['pairs']
+
+    # Only points 1, 2 and 3 are in Berlin, each of them has 2 neighbours among them
+    assert len(pairs) == 6
+    for pair in pairs:
+        assert pair['a'] in [1, 2, 3]
+        assert pair['b'] in [1, 2, 3]
@@ -0,0 +1,146 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_sparse_bm25'
+
+TEXTS = [
+    "I must not fear. Fear is the mind-killer.",
+    "All animals are equal, but some animals are more equal than others.",
+    "It was a pleasure to burn.",
+    "The sky above the port was the color of television, tuned to a dead channel.",
+    "War is peace. Freedom is slavery. Ignorance is strength.",
+    "We're not in Infinity; we're in the suburbs.",
+]
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    bm25_collection_setup(collection_name=collection_name)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def bm25_collection_setup(collection_name='test_collection'):
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "sparse_vectors": {
+                "text": {
+                    "modifier": "idf",
+                    "bm25": {"k": 1.2, "b": 0.75}
+                }
+            }
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": idx,
+                    "vector": {
+                        "text": {"text": text, "model": "bm25"}
+                    },
+                    "payload": {
+                        "group": idx % 2
+                    }
+                }
+                for idx, text in enumerate(TEXTS)
+            ]
+        }
+    )
+    assert response.ok
+
+
+def test_query_groups_with_document():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/query/groups',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "query": {"text": "television channel", "model": "bm25"},
+            "using": "text",
+            "group_by": "group",
+            "group_size": 1,
+            "limit": 2,
+        }
+    )
+    assert response.ok, response.text
+
+    groups = response.json()['result']['groups']
+    assert groups[0]['hits'][0]['id'] == 3
+
+
+def test_bm25_config_change_is_rejected():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "sparse_vectors": {
+                "text": {
+                    "bm25": {"k": 2.0, "b": 0.75}
+                }
+            }
+        }
+    )
+    assert response.status_code == 400
+
+    # Same config is accepted
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "sparse_vectors": {
+                "text": {
+                    "bm25": {"k": 1.2, "b": 0.75}
+                }
+            }
+        }
+    )
+    assert response.ok
+
+
+def test_bm25_config_cannot_be_added():
+    plain_collection_name = f'{collection_name}_plain'
+    drop_collection(collection_name=plain_collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': plain_collection_name},
+        body={
+            "sparse_vectors": {
+                "text": {}
+            }
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': plain_collection_name},
+        body={
+            "sparse_vectors": {
+                "text": {
+                    "bm25": {"k": 1.2, "b": 0.75}
+                }
+            }
+        }
+    )
+    assert response.status_code == 400
+
+    drop_collection(collection_name=plain_collection_name)